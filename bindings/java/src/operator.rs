@@ -104,7 +104,8 @@ fn intern_write(
 ) -> Result<()> {
     let path = jstring_to_string(env, &path)?;
     let content = env.convert_byte_array(content)?;
-    Ok(op.write(&path, content)?)
+    op.write(&path, content)?;
+    Ok(())
 }
 
 /// # Safety
@@ -0,0 +1,70 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use criterion::Criterion;
+use opendal::raw::tests::init_test_service;
+use opendal::raw::tests::TEST_RUNTIME;
+use opendal::Operator;
+use rand::prelude::*;
+use size::Size;
+
+use super::utils::*;
+
+pub fn bench(c: &mut Criterion) {
+    if let Some(op) = init_test_service().unwrap() {
+        if op.info().full_capability().blocking {
+            bench_blocking_vs_async(c, op.info().scheme().into_static(), op.clone());
+        }
+    }
+}
+
+/// Compare `BlockingOperator` against blocking on the async operator.
+///
+/// For embedded kv services (like sled, redb and persy), the blocking path
+/// hits the database directly while the async path goes through
+/// `spawn_blocking`, so small operations should be noticeably faster.
+fn bench_blocking_vs_async(c: &mut Criterion, name: &str, op: Operator) {
+    let mut group = c.benchmark_group(format!("service_{name}_blocking_vs_async"));
+
+    let mut rng = thread_rng();
+    let size = Size::from_bytes(256);
+    let content = gen_bytes(&mut rng, size.bytes() as usize);
+    let path = uuid::Uuid::new_v4().to_string();
+    let _temp_data = TempData::generate(op.clone(), &path, content.clone());
+
+    group.throughput(criterion::Throughput::Bytes(size.bytes() as u64));
+    group.bench_function("read_blocking", |b| {
+        let op = op.blocking();
+        b.iter(|| op.read(&path).unwrap())
+    });
+    group.bench_function("read_async", |b| {
+        b.iter(|| TEST_RUNTIME.block_on(op.read(&path)).unwrap())
+    });
+    group.bench_function("write_blocking", |b| {
+        let op = op.blocking();
+        b.iter(|| op.write(&path, content.clone()).unwrap())
+    });
+    group.bench_function("write_async", |b| {
+        b.iter(|| {
+            TEST_RUNTIME
+                .block_on(op.write(&path, content.clone()))
+                .unwrap()
+        })
+    });
+
+    group.finish()
+}
@@ -0,0 +1,72 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use criterion::Criterion;
+
+pub fn bench(c: &mut Criterion) {
+    #[cfg(feature = "services-fs")]
+    if let Some(op) = opendal::raw::tests::init_test_service().unwrap() {
+        if op.info().scheme() == opendal::Scheme::Fs {
+            bench_sync_on_close(c, op.info().root());
+        }
+    }
+    #[cfg(not(feature = "services-fs"))]
+    let _ = c;
+}
+
+/// Compare fs writes with and without fsync before reporting complete.
+#[cfg(feature = "services-fs")]
+fn bench_sync_on_close(c: &mut Criterion, root: &str) {
+    use opendal::raw::tests::TEST_RUNTIME;
+    use opendal::services::Fs;
+    use opendal::Operator;
+    use rand::prelude::*;
+    use size::Size;
+
+    use super::utils::*;
+
+    let mut group = c.benchmark_group("service_fs_sync_on_close");
+
+    let mut rng = thread_rng();
+    let size = Size::from_kibibytes(4);
+    let content = gen_bytes(&mut rng, size.bytes() as usize);
+
+    let cases = [
+        ("disabled", Fs::default().root(root).sync_on_close(false)),
+        ("enabled", Fs::default().root(root).sync_on_close(true)),
+        (
+            "enabled_with_sync_dir",
+            Fs::default().root(root).sync_on_close(true).sync_dir(),
+        ),
+    ];
+    for (name, builder) in cases {
+        let op = Operator::new(builder).unwrap().finish();
+        let path = uuid::Uuid::new_v4().to_string();
+        let temp_data = TempData::existing(op.clone(), &path);
+
+        group.throughput(criterion::Throughput::Bytes(size.bytes() as u64));
+        group.bench_function(name, |b| {
+            b.to_async(&*TEST_RUNTIME).iter(|| async {
+                op.write(&path, content.clone()).await.unwrap();
+            })
+        });
+
+        std::mem::drop(temp_data);
+    }
+
+    group.finish()
+}
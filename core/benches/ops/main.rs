@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+mod blocking;
+mod fs;
 mod read;
 mod utils;
 mod write;
@@ -22,5 +24,11 @@ mod write;
 use criterion::criterion_group;
 use criterion::criterion_main;
 
-criterion_group!(benches, read::bench, write::bench);
+criterion_group!(
+    benches,
+    read::bench,
+    write::bench,
+    blocking::bench,
+    fs::bench
+);
 criterion_main!(benches);
@@ -102,6 +102,11 @@ impl<A: Access> LayeredAccess for AsyncBacktraceAccessor<A> {
         self.inner.stat(path, args).await
     }
 
+    #[async_backtrace::framed]
+    async fn exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        self.inner.exists(path, args).await
+    }
+
     #[async_backtrace::framed]
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.inner.delete(path, args).await
@@ -179,7 +184,7 @@ impl<R: oio::Write> oio::Write for AsyncBacktraceWrapper<R> {
     }
 
     #[async_backtrace::framed]
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         self.inner.close().await
     }
 }
@@ -189,7 +194,7 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for AsyncBacktraceWrapper<R> {
         self.inner.write(bs)
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         self.inner.close()
     }
 }
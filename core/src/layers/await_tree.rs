@@ -120,6 +120,13 @@ impl<A: Access> LayeredAccess for AwaitTreeAccessor<A> {
             .await
     }
 
+    async fn exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        self.inner
+            .exists(path, args)
+            .instrument_await(format!("opendal::{}", Operation::Stat))
+            .await
+    }
+
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.inner
             .delete(path, args)
@@ -206,7 +213,7 @@ impl<R: oio::Write> oio::Write for AwaitTreeWrapper<R> {
             .instrument_await(format!("opendal::{}", WriteOperation::Abort.into_static()))
     }
 
-    fn close(&mut self) -> impl Future<Output = Result<()>> + MaybeSend {
+    fn close(&mut self) -> impl Future<Output = Result<Metadata>> + MaybeSend {
         self.inner
             .close()
             .instrument_await(format!("opendal::{}", WriteOperation::Close.into_static()))
@@ -218,7 +225,7 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for AwaitTreeWrapper<R> {
         self.inner.write(bs)
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         self.inner.close()
     }
 }
@@ -204,6 +204,10 @@ impl<A: Access> LayeredAccess for BlockingAccessor<A> {
         self.inner.stat(path, args).await
     }
 
+    async fn exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        self.inner.exists(path, args).await
+    }
+
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.inner.delete(path, args).await
     }
@@ -253,6 +257,10 @@ impl<A: Access> LayeredAccess for BlockingAccessor<A> {
         self.handle.block_on(self.inner.stat(path, args))
     }
 
+    fn blocking_exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        self.handle.block_on(self.inner.exists(path, args))
+    }
+
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.handle.block_on(self.inner.delete(path, args))
     }
@@ -288,7 +296,7 @@ impl<I: oio::Write + 'static> oio::BlockingWrite for BlockingWrapper<I> {
         self.handle.block_on(self.inner.write(bs))
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         self.handle.block_on(self.inner.close())
     }
 }
@@ -20,8 +20,13 @@ use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
 
+use base64::engine::general_purpose;
+use base64::Engine;
+use md5::Digest;
+
 use crate::raw::oio::FlatLister;
 use crate::raw::oio::PrefixLister;
+use crate::raw::oio::SortedLister;
 use crate::raw::*;
 use crate::*;
 
@@ -98,6 +103,19 @@ use crate::*;
 /// - If support `list_with_recursive`, return directly.
 /// - if not, wrap with [`FlatLister`].
 ///
+/// If `ordered` is requested but the service is not `list_ordered`, entries
+/// of every dir will be sorted in memory when `sort_unordered` is enabled,
+/// otherwise an `Unsupported` error will be returned.
+///
+/// ## Write Checksum
+///
+/// If `content_md5` or `content_sha256` is given, content written will be
+/// hashed and verified before the writer is closed. The writer will be
+/// aborted with [`ErrorKind::DataCorrupted`] if not matched.
+///
+/// Verifying `content_sha256` requires the `content-sha256` feature, an
+/// `Unsupported` error will be returned otherwise.
+///
 /// ## Capability Check
 ///
 /// Before performing any operations, `CompleteLayer` will first check
@@ -139,6 +157,113 @@ impl<A: Access> CompleteAccessor<A> {
         .with_operation(op)
     }
 
+    /// Check if entries need to be sorted for ordered list.
+    fn check_list_ordered(&self, op: impl Into<&'static str>, args: &OpList) -> Result<bool> {
+        if !args.ordered() || self.meta.full_capability().list_ordered {
+            return Ok(false);
+        }
+        if args.sort_unordered() {
+            return Ok(true);
+        }
+
+        let op = op.into();
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            format!(
+                "service {} can't list in order, enable sort_unordered to sort entries in memory",
+                self.meta.scheme()
+            ),
+        )
+        .with_operation(op))
+    }
+
+    /// Copy options that can't be honored must not be ignored silently.
+    fn check_copy_args(&self, args: &OpCopy) -> Result<()> {
+        let capability = self.meta.full_capability();
+        let unsupported = [
+            (
+                args.metadata_directive() == Some(MetadataDirective::Replace)
+                    && !capability.copy_with_metadata_directive,
+                "metadata_directive",
+            ),
+            (
+                args.content_type().is_some() && !capability.copy_with_content_type,
+                "content_type",
+            ),
+            (
+                args.cache_control().is_some() && !capability.copy_with_cache_control,
+                "cache_control",
+            ),
+            (
+                args.if_not_exists() && !capability.copy_with_if_not_exists,
+                "if_not_exists",
+            ),
+        ];
+
+        match unsupported.iter().find(|(v, _)| *v) {
+            Some((_, name)) => Err(Error::new(
+                ErrorKind::Unsupported,
+                format!(
+                    "service {} doesn't support operation copy with {name}",
+                    self.meta.scheme()
+                ),
+            )
+            .with_operation(Operation::Copy)),
+            None => Ok(()),
+        }
+    }
+
+    fn check_write_checksum(&self, args: &OpWrite) -> Result<()> {
+        // Appends only carry part of the content, which can't be verified
+        // against the checksum of the whole content.
+        if args.append() && (args.content_md5().is_some() || args.content_sha256().is_some()) {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "content checksum can't be used with append",
+            )
+            .with_operation(Operation::Write)
+            .with_context("service", self.meta.scheme()));
+        }
+
+        #[cfg(not(feature = "content-sha256"))]
+        if args.content_sha256().is_some() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "content sha256 verification requires the content-sha256 feature",
+            )
+            .with_operation(Operation::Write)
+            .with_context("service", self.meta.scheme()));
+        }
+
+        Ok(())
+    }
+
+    fn check_stat_args(&self, args: &OpStat) -> Result<()> {
+        let capability = self.meta.full_capability();
+        let unsupported = [
+            (
+                args.if_modified_since().is_some() && !capability.stat_with_if_modified_since,
+                "if_modified_since",
+            ),
+            (
+                args.if_unmodified_since().is_some() && !capability.stat_with_if_unmodified_since,
+                "if_unmodified_since",
+            ),
+        ];
+
+        match unsupported.iter().find(|(v, _)| *v) {
+            Some((_, name)) => Err(Error::new(
+                ErrorKind::Unsupported,
+                format!(
+                    "service {} doesn't support operation stat with {name}",
+                    self.meta.scheme()
+                ),
+            )
+            .with_operation(Operation::Stat)),
+            None => Ok(()),
+        }
+    }
+
     async fn complete_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         let capability = self.meta.full_capability();
         if capability.create_dir {
@@ -172,6 +297,7 @@ impl<A: Access> CompleteAccessor<A> {
         if !capability.stat {
             return Err(self.new_unsupported_error(Operation::Stat));
         }
+        self.check_stat_args(&args)?;
 
         if path == "/" {
             return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
@@ -225,6 +351,7 @@ impl<A: Access> CompleteAccessor<A> {
         if !capability.stat {
             return Err(self.new_unsupported_error(Operation::Stat));
         }
+        self.check_stat_args(&args)?;
 
         if path == "/" {
             return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
@@ -281,22 +408,31 @@ impl<A: Access> CompleteAccessor<A> {
         }
 
         let recursive = args.recursive();
+        let sort = self.check_list_ordered(Operation::List, &args)?;
+        let ordered = args.ordered();
 
-        match (recursive, cap.list_with_recursive) {
+        // Native recursive list can't be used if entries need to be sorted.
+        match (recursive, cap.list_with_recursive && !sort) {
             // - If service can list_with_recursive, we can forward list to it directly.
             (_, true) => {
                 let (rp, p) = self.inner.list(path, args).await?;
-                Ok((rp, CompleteLister::One(p)))
+                Ok((rp, CompleteLister::One(SortedLister::new(p, false))))
             }
             // If recursive is true but service can't list_with_recursive
             (true, false) => {
                 // Forward path that ends with /
                 if path.ends_with('/') {
-                    let p = FlatLister::new(self.inner.clone(), path);
+                    let mut p = FlatLister::new(self.inner.clone(), path);
+                    if ordered {
+                        p = p.with_ordered(sort);
+                    }
                     Ok((RpList::default(), CompleteLister::Two(p)))
                 } else {
                     let parent = get_parent(path);
-                    let p = FlatLister::new(self.inner.clone(), parent);
+                    let mut p = FlatLister::new(self.inner.clone(), parent);
+                    if ordered {
+                        p = p.with_ordered(sort);
+                    }
                     let p = PrefixLister::new(p, path);
                     Ok((RpList::default(), CompleteLister::Four(p)))
                 }
@@ -307,11 +443,11 @@ impl<A: Access> CompleteAccessor<A> {
                 // Forward path that ends with /
                 if path.ends_with('/') {
                     let (rp, p) = self.inner.list(path, args).await?;
-                    Ok((rp, CompleteLister::One(p)))
+                    Ok((rp, CompleteLister::One(SortedLister::new(p, sort))))
                 } else {
                     let parent = get_parent(path);
                     let (rp, p) = self.inner.list(parent, args).await?;
-                    let p = PrefixLister::new(p, path);
+                    let p = PrefixLister::new(SortedLister::new(p, sort), path);
                     Ok((rp, CompleteLister::Three(p)))
                 }
             }
@@ -329,22 +465,31 @@ impl<A: Access> CompleteAccessor<A> {
         }
 
         let recursive = args.recursive();
+        let sort = self.check_list_ordered(Operation::BlockingList, &args)?;
+        let ordered = args.ordered();
 
-        match (recursive, cap.list_with_recursive) {
+        // Native recursive list can't be used if entries need to be sorted.
+        match (recursive, cap.list_with_recursive && !sort) {
             // - If service can list_with_recursive, we can forward list to it directly.
             (_, true) => {
                 let (rp, p) = self.inner.blocking_list(path, args)?;
-                Ok((rp, CompleteLister::One(p)))
+                Ok((rp, CompleteLister::One(SortedLister::new(p, false))))
             }
             // If recursive is true but service can't list_with_recursive
             (true, false) => {
                 // Forward path that ends with /
                 if path.ends_with('/') {
-                    let p = FlatLister::new(self.inner.clone(), path);
+                    let mut p = FlatLister::new(self.inner.clone(), path);
+                    if ordered {
+                        p = p.with_ordered(sort);
+                    }
                     Ok((RpList::default(), CompleteLister::Two(p)))
                 } else {
                     let parent = get_parent(path);
-                    let p = FlatLister::new(self.inner.clone(), parent);
+                    let mut p = FlatLister::new(self.inner.clone(), parent);
+                    if ordered {
+                        p = p.with_ordered(sort);
+                    }
                     let p = PrefixLister::new(p, path);
                     Ok((RpList::default(), CompleteLister::Four(p)))
                 }
@@ -355,11 +500,11 @@ impl<A: Access> CompleteAccessor<A> {
                 // Forward path that ends with /
                 if path.ends_with('/') {
                     let (rp, p) = self.inner.blocking_list(path, args)?;
-                    Ok((rp, CompleteLister::One(p)))
+                    Ok((rp, CompleteLister::One(SortedLister::new(p, sort))))
                 } else {
                     let parent = get_parent(path);
                     let (rp, p) = self.inner.blocking_list(parent, args)?;
-                    let p = PrefixLister::new(p, path);
+                    let p = PrefixLister::new(SortedLister::new(p, sort), path);
                     Ok((rp, CompleteLister::Three(p)))
                 }
             }
@@ -421,9 +566,19 @@ impl<A: Access> LayeredAccess for CompleteAccessor<A> {
                 ),
             ));
         }
+        if args.if_not_exists() && !capability.write_with_if_not_exists {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                format!(
+                    "service {} doesn't support operation write with if_not_exists",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        self.check_write_checksum(&args)?;
 
         let (rp, w) = self.inner.write(path, args.clone()).await?;
-        let w = CompleteWriter::new(w);
+        let w = CompleteWriter::new(w, &args);
         Ok((rp, w))
     }
 
@@ -432,6 +587,7 @@ impl<A: Access> LayeredAccess for CompleteAccessor<A> {
         if !capability.copy {
             return Err(self.new_unsupported_error(Operation::Copy));
         }
+        self.check_copy_args(&args)?;
 
         self.inner().copy(from, to, args).await
     }
@@ -449,6 +605,25 @@ impl<A: Access> LayeredAccess for CompleteAccessor<A> {
         self.complete_stat(path, args).await
     }
 
+    async fn exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        let capability = self.meta.full_capability();
+        if !capability.stat {
+            return Err(self.new_unsupported_error(Operation::Stat));
+        }
+        self.check_stat_args(&args)?;
+
+        // Dir needs to be simulated by stat, so we can't forward it directly.
+        if path.ends_with('/') {
+            return match self.complete_stat(path, args).await {
+                Ok(_) => Ok(true),
+                Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+                Err(err) => Err(err),
+            };
+        }
+
+        self.inner().exists(path, args).await
+    }
+
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         let capability = self.meta.full_capability();
         if !capability.delete {
@@ -472,6 +647,20 @@ impl<A: Access> LayeredAccess for CompleteAccessor<A> {
         if !capability.batch {
             return Err(self.new_unsupported_error(Operation::Batch));
         }
+        // Services that only set `batch` can only delete in batch, other
+        // operations must be allowed by their own capability.
+        for (_, op) in args.operation() {
+            let supported = match op {
+                BatchOperation::Delete(_) => true,
+                BatchOperation::Write(_, _) => capability.batch_write,
+                BatchOperation::Stat(_) => capability.batch_stat,
+            };
+            if !supported {
+                return Err(self
+                    .new_unsupported_error(Operation::Batch)
+                    .with_context("batch_operation", op.operation().into_static()));
+            }
+        }
 
         self.inner().batch(args).await
     }
@@ -485,6 +674,49 @@ impl<A: Access> LayeredAccess for CompleteAccessor<A> {
         self.inner.presign(path, args).await
     }
 
+    async fn initiate_multipart(&self, path: &str, args: OpWrite) -> Result<RpInitiateMultipart> {
+        let capability = self.meta.full_capability();
+        if !capability.multipart {
+            return Err(self.new_unsupported_error(Operation::InitiateMultipart));
+        }
+
+        self.inner.initiate_multipart(path, args).await
+    }
+
+    async fn complete_multipart(
+        &self,
+        path: &str,
+        args: OpCompleteMultipart,
+    ) -> Result<RpCompleteMultipart> {
+        let capability = self.meta.full_capability();
+        if !capability.multipart {
+            return Err(self.new_unsupported_error(Operation::CompleteMultipart));
+        }
+        if args.parts().is_empty() {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "multipart upload must have at least one part",
+            )
+            .with_operation(Operation::CompleteMultipart)
+            .with_context("upload_id", args.upload_id()));
+        }
+
+        self.inner.complete_multipart(path, args).await
+    }
+
+    async fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> Result<RpAbortMultipart> {
+        let capability = self.meta.full_capability();
+        if !capability.multipart {
+            return Err(self.new_unsupported_error(Operation::AbortMultipart));
+        }
+
+        self.inner.abort_multipart(path, args).await
+    }
+
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         self.complete_blocking_create_dir(path, args)
     }
@@ -516,10 +748,20 @@ impl<A: Access> LayeredAccess for CompleteAccessor<A> {
                 ),
             ));
         }
+        if args.if_not_exists() && !capability.write_with_if_not_exists {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                format!(
+                    "service {} doesn't support operation write with if_not_exists",
+                    self.info().scheme()
+                ),
+            ));
+        }
+        self.check_write_checksum(&args)?;
 
         self.inner
-            .blocking_write(path, args)
-            .map(|(rp, w)| (rp, CompleteWriter::new(w)))
+            .blocking_write(path, args.clone())
+            .map(|(rp, w)| (rp, CompleteWriter::new(w, &args)))
     }
 
     fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
@@ -544,6 +786,25 @@ impl<A: Access> LayeredAccess for CompleteAccessor<A> {
         self.complete_blocking_stat(path, args)
     }
 
+    fn blocking_exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        let capability = self.meta.full_capability();
+        if !capability.stat || !capability.blocking {
+            return Err(self.new_unsupported_error(Operation::BlockingStat));
+        }
+        self.check_stat_args(&args)?;
+
+        // Dir needs to be simulated by stat, so we can't forward it directly.
+        if path.ends_with('/') {
+            return match self.complete_blocking_stat(path, args) {
+                Ok(_) => Ok(true),
+                Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+                Err(err) => Err(err),
+            };
+        }
+
+        self.inner().blocking_exists(path, args)
+    }
+
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         let capability = self.meta.full_capability();
         if !capability.delete || !capability.blocking {
@@ -563,8 +824,12 @@ impl<A: Access> LayeredAccess for CompleteAccessor<A> {
     }
 }
 
-pub type CompleteLister<A, P> =
-    FourWays<P, FlatLister<Arc<A>, P>, PrefixLister<P>, PrefixLister<FlatLister<Arc<A>, P>>>;
+pub type CompleteLister<A, P> = FourWays<
+    SortedLister<P>,
+    FlatLister<Arc<A>, P>,
+    PrefixLister<SortedLister<P>>,
+    PrefixLister<FlatLister<Arc<A>, P>>,
+>;
 
 pub struct CompleteReader<R> {
     inner: R,
@@ -632,11 +897,76 @@ impl<R: oio::BlockingRead> oio::BlockingRead for CompleteReader<R> {
 
 pub struct CompleteWriter<W> {
     inner: Option<W>,
+    checksum: Option<ChecksumVerifier>,
 }
 
 impl<W> CompleteWriter<W> {
-    pub fn new(inner: W) -> CompleteWriter<W> {
-        CompleteWriter { inner: Some(inner) }
+    pub fn new(inner: W, args: &OpWrite) -> CompleteWriter<W> {
+        CompleteWriter {
+            inner: Some(inner),
+            checksum: ChecksumVerifier::new(args),
+        }
+    }
+}
+
+/// Verify the content written against the checksums given by users.
+struct ChecksumVerifier {
+    md5: Option<(md5::Md5, String)>,
+    #[cfg(feature = "content-sha256")]
+    sha256: Option<(sha2::Sha256, String)>,
+}
+
+impl ChecksumVerifier {
+    fn new(args: &OpWrite) -> Option<Self> {
+        if args.content_md5().is_none() && args.content_sha256().is_none() {
+            return None;
+        }
+
+        Some(Self {
+            md5: args.content_md5().map(|v| (md5::Md5::new(), v.to_string())),
+            #[cfg(feature = "content-sha256")]
+            sha256: args
+                .content_sha256()
+                .map(|v| (sha2::Sha256::new(), v.to_string())),
+        })
+    }
+
+    fn update(&mut self, bs: &Buffer) {
+        for chunk in bs.clone() {
+            if let Some((hasher, _)) = &mut self.md5 {
+                hasher.update(&chunk);
+            }
+            #[cfg(feature = "content-sha256")]
+            if let Some((hasher, _)) = &mut self.sha256 {
+                hasher.update(&chunk);
+            }
+        }
+    }
+
+    fn verify(self) -> Result<()> {
+        let mut digests = Vec::with_capacity(2);
+        if let Some((hasher, expected)) = self.md5 {
+            digests.push(("md5", expected, hasher.finalize().to_vec()));
+        }
+        #[cfg(feature = "content-sha256")]
+        if let Some((hasher, expected)) = self.sha256 {
+            digests.push(("sha256", expected, hasher.finalize().to_vec()));
+        }
+
+        for (algorithm, expected, digest) in digests {
+            let actual = general_purpose::STANDARD.encode(digest);
+            if expected != actual {
+                return Err(Error::new(
+                    ErrorKind::DataCorrupted,
+                    "content doesn't match the given checksum",
+                )
+                .with_operation("write")
+                .with_context("algorithm", algorithm)
+                .with_context("expected", expected)
+                .with_context("actual", actual));
+            }
+        }
+        Ok(())
     }
 }
 
@@ -660,18 +990,30 @@ where
             Error::new(ErrorKind::Unexpected, "writer has been closed or aborted")
         })?;
 
-        w.write(bs).await
+        w.write(bs.clone()).await?;
+        if let Some(checksum) = &mut self.checksum {
+            checksum.update(&bs);
+        }
+        Ok(())
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         let w = self.inner.as_mut().ok_or_else(|| {
             Error::new(ErrorKind::Unexpected, "writer has been closed or aborted")
         })?;
 
-        w.close().await?;
+        // Verify before committing, so that corrupted content is never visible.
+        if let Some(Err(err)) = self.checksum.take().map(|v| v.verify()) {
+            // Keep the original error, the abort error is not actionable.
+            let _ = w.abort().await;
+            self.inner = None;
+            return Err(err);
+        }
+
+        let meta = w.close().await?;
         self.inner = None;
 
-        Ok(())
+        Ok(meta)
     }
 
     async fn abort(&mut self) -> Result<()> {
@@ -695,17 +1037,27 @@ where
             Error::new(ErrorKind::Unexpected, "writer has been closed or aborted")
         })?;
 
-        w.write(bs)
+        w.write(bs.clone())?;
+        if let Some(checksum) = &mut self.checksum {
+            checksum.update(&bs);
+        }
+        Ok(())
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         let w = self.inner.as_mut().ok_or_else(|| {
             Error::new(ErrorKind::Unexpected, "writer has been closed or aborted")
         })?;
 
-        w.close()?;
+        // Blocking writers can't be aborted, drop it without closing.
+        if let Some(Err(err)) = self.checksum.take().map(|v| v.verify()) {
+            self.inner = None;
+            return Err(err);
+        }
+
+        let meta = w.close()?;
         self.inner = None;
-        Ok(())
+        Ok(meta)
     }
 }
 
@@ -17,7 +17,10 @@
 
 use std::fmt::Debug;
 use std::sync::Arc;
+use std::time::Duration;
+use std::time::Instant;
 
+use log::debug;
 use tokio::sync::OwnedSemaphorePermit;
 use tokio::sync::Semaphore;
 
@@ -31,6 +34,16 @@ use crate::*;
 /// Users can control how many concurrent connections could be established
 /// between OpenDAL and underlying storage services.
 ///
+/// Operations are grouped into classes (read, write, list and metadata),
+/// each class can have its own permits besides the overall permits, so that
+/// heavy list traffic won't starve reads. Readers, writers and listers hold
+/// their permits until they are dropped.
+///
+/// Time spent waiting for permits is logged at debug level under
+/// `opendal::layers::concurrent_limit`, and recorded as histogram
+/// `opendal_concurrent_limit_wait_duration_seconds` with the `class` label
+/// if feature `layers-metrics` is enabled.
+///
 /// # Examples
 ///
 /// ```no_run
@@ -45,15 +58,69 @@ use crate::*;
 ///     .layer(ConcurrentLimitLayer::new(1024))
 ///     .finish();
 /// ```
+///
+/// Limit list and read separately under the overall permits:
+///
+/// ```no_run
+/// use opendal::layers::ConcurrentLimitLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(
+///         ConcurrentLimitLayer::new(1024)
+///             .with_list_permits(16)
+///             .with_read_permits(512),
+///     )
+///     .finish();
+/// ```
 #[derive(Clone)]
 pub struct ConcurrentLimitLayer {
     permits: usize,
+    read_permits: Option<usize>,
+    write_permits: Option<usize>,
+    list_permits: Option<usize>,
+    metadata_permits: Option<usize>,
 }
 
 impl ConcurrentLimitLayer {
     /// Create a new ConcurrentLimitLayer will specify permits
+    ///
+    /// The permits are shared by all operations.
     pub fn new(permits: usize) -> Self {
-        Self { permits }
+        Self {
+            permits,
+            read_permits: None,
+            write_permits: None,
+            list_permits: None,
+            metadata_permits: None,
+        }
+    }
+
+    /// Set the permits of `read`, readers hold the permit until dropped.
+    pub fn with_read_permits(mut self, permits: usize) -> Self {
+        self.read_permits = Some(permits);
+        self
+    }
+
+    /// Set the permits of `write`, `create_dir`, `delete` and `batch`,
+    /// writers hold the permit until dropped.
+    pub fn with_write_permits(mut self, permits: usize) -> Self {
+        self.write_permits = Some(permits);
+        self
+    }
+
+    /// Set the permits of `list`, listers hold the permit until dropped.
+    pub fn with_list_permits(mut self, permits: usize) -> Self {
+        self.list_permits = Some(permits);
+        self
+    }
+
+    /// Set the permits of `stat` and `exists`.
+    pub fn with_metadata_permits(mut self, permits: usize) -> Self {
+        self.metadata_permits = Some(permits);
+        self
     }
 }
 
@@ -61,9 +128,35 @@ impl<A: Access> Layer<A> for ConcurrentLimitLayer {
     type LayeredAccess = ConcurrentLimitAccessor<A>;
 
     fn layer(&self, inner: A) -> Self::LayeredAccess {
+        let new_semaphore = |permits: Option<usize>| permits.map(|v| Arc::new(Semaphore::new(v)));
+
         ConcurrentLimitAccessor {
             inner,
             semaphore: Arc::new(Semaphore::new(self.permits)),
+            read_semaphore: new_semaphore(self.read_permits),
+            write_semaphore: new_semaphore(self.write_permits),
+            list_semaphore: new_semaphore(self.list_permits),
+            metadata_semaphore: new_semaphore(self.metadata_permits),
+        }
+    }
+}
+
+/// The class of operations that share the same permits.
+#[derive(Debug, Clone, Copy)]
+enum LimitClass {
+    Read,
+    Write,
+    List,
+    Metadata,
+}
+
+impl LimitClass {
+    fn as_str(&self) -> &'static str {
+        match self {
+            LimitClass::Read => "read",
+            LimitClass::Write => "write",
+            LimitClass::List => "list",
+            LimitClass::Metadata => "metadata",
         }
     }
 }
@@ -72,6 +165,93 @@ impl<A: Access> Layer<A> for ConcurrentLimitLayer {
 pub struct ConcurrentLimitAccessor<A: Access> {
     inner: A,
     semaphore: Arc<Semaphore>,
+    read_semaphore: Option<Arc<Semaphore>>,
+    write_semaphore: Option<Arc<Semaphore>>,
+    list_semaphore: Option<Arc<Semaphore>>,
+    metadata_semaphore: Option<Arc<Semaphore>>,
+}
+
+impl<A: Access> ConcurrentLimitAccessor<A> {
+    fn class_semaphore(&self, class: LimitClass) -> Option<&Arc<Semaphore>> {
+        match class {
+            LimitClass::Read => self.read_semaphore.as_ref(),
+            LimitClass::Write => self.write_semaphore.as_ref(),
+            LimitClass::List => self.list_semaphore.as_ref(),
+            LimitClass::Metadata => self.metadata_semaphore.as_ref(),
+        }
+    }
+
+    /// Acquire the permits of given class and the overall permits.
+    ///
+    /// The class permit is acquired first so that operations blocked by
+    /// their own class won't hold the overall permits.
+    async fn acquire(&self, class: LimitClass) -> ConcurrentLimitPermit {
+        let start = Instant::now();
+
+        let class_permit = match self.class_semaphore(class) {
+            Some(semaphore) => Some(acquire_owned(semaphore).await),
+            None => None,
+        };
+        let permit = acquire_owned(&self.semaphore).await;
+
+        record_wait(class, start.elapsed());
+        ConcurrentLimitPermit {
+            _class: class_permit,
+            _overall: permit,
+        }
+    }
+
+    fn blocking_acquire(&self, class: LimitClass) -> ConcurrentLimitPermit {
+        let class_permit = self.class_semaphore(class).map(|semaphore| {
+            semaphore
+                .clone()
+                .try_acquire_owned()
+                .expect("semaphore must be valid")
+        });
+        let permit = self
+            .semaphore
+            .clone()
+            .try_acquire_owned()
+            .expect("semaphore must be valid");
+
+        ConcurrentLimitPermit {
+            _class: class_permit,
+            _overall: permit,
+        }
+    }
+}
+
+async fn acquire_owned(semaphore: &Arc<Semaphore>) -> OwnedSemaphorePermit {
+    semaphore
+        .clone()
+        .acquire_owned()
+        .await
+        .expect("semaphore must be valid")
+}
+
+fn record_wait(class: LimitClass, waited: Duration) {
+    // Permits acquired without contention are not worth logging.
+    if waited >= Duration::from_millis(1) {
+        debug!(
+            target: "opendal::layers::concurrent_limit",
+            "{} operation waited {}s for permits",
+            class.as_str(),
+            waited.as_secs_f64()
+        );
+    }
+
+    #[cfg(feature = "layers-metrics")]
+    metrics::histogram!(
+        "opendal_concurrent_limit_wait_duration_seconds",
+        "class" => class.as_str()
+    )
+    .record(waited.as_secs_f64());
+}
+
+/// The permits held by an operation, released on drop.
+struct ConcurrentLimitPermit {
+    _class: Option<OwnedSemaphorePermit>,
+    _overall: OwnedSemaphorePermit,
 }
 
 impl<A: Access> LayeredAccess for ConcurrentLimitAccessor<A> {
@@ -88,22 +268,13 @@ impl<A: Access> LayeredAccess for ConcurrentLimitAccessor<A> {
     }
 
     async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
-        let _permit = self
-            .semaphore
-            .acquire()
-            .await
-            .expect("semaphore must be valid");
+        let _permit = self.acquire(LimitClass::Write).await;
 
         self.inner.create_dir(path, args).await
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let permit = self
-            .semaphore
-            .clone()
-            .acquire_owned()
-            .await
-            .expect("semaphore must be valid");
+        let permit = self.acquire(LimitClass::Read).await;
 
         self.inner
             .read(path, args)
@@ -112,12 +283,7 @@ impl<A: Access> LayeredAccess for ConcurrentLimitAccessor<A> {
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        let permit = self
-            .semaphore
-            .clone()
-            .acquire_owned()
-            .await
-            .expect("semaphore must be valid");
+        let permit = self.acquire(LimitClass::Write).await;
 
         self.inner
             .write(path, args)
@@ -126,32 +292,25 @@ impl<A: Access> LayeredAccess for ConcurrentLimitAccessor<A> {
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        let _permit = self
-            .semaphore
-            .acquire()
-            .await
-            .expect("semaphore must be valid");
+        let _permit = self.acquire(LimitClass::Metadata).await;
 
         self.inner.stat(path, args).await
     }
 
+    async fn exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        let _permit = self.acquire(LimitClass::Metadata).await;
+
+        self.inner.exists(path, args).await
+    }
+
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        let _permit = self
-            .semaphore
-            .acquire()
-            .await
-            .expect("semaphore must be valid");
+        let _permit = self.acquire(LimitClass::Write).await;
 
         self.inner.delete(path, args).await
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
-        let permit = self
-            .semaphore
-            .clone()
-            .acquire_owned()
-            .await
-            .expect("semaphore must be valid");
+        let permit = self.acquire(LimitClass::List).await;
 
         self.inner
             .list(path, args)
@@ -160,30 +319,19 @@ impl<A: Access> LayeredAccess for ConcurrentLimitAccessor<A> {
     }
 
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
-        let _permit = self
-            .semaphore
-            .acquire()
-            .await
-            .expect("semaphore must be valid");
+        let _permit = self.acquire(LimitClass::Write).await;
 
         self.inner.batch(args).await
     }
 
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
-        let _permit = self
-            .semaphore
-            .try_acquire()
-            .expect("semaphore must be valid");
+        let _permit = self.blocking_acquire(LimitClass::Write);
 
         self.inner.blocking_create_dir(path, args)
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
-        let permit = self
-            .semaphore
-            .clone()
-            .try_acquire_owned()
-            .expect("semaphore must be valid");
+        let permit = self.blocking_acquire(LimitClass::Read);
 
         self.inner
             .blocking_read(path, args)
@@ -191,11 +339,7 @@ impl<A: Access> LayeredAccess for ConcurrentLimitAccessor<A> {
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
-        let permit = self
-            .semaphore
-            .clone()
-            .try_acquire_owned()
-            .expect("semaphore must be valid");
+        let permit = self.blocking_acquire(LimitClass::Write);
 
         self.inner
             .blocking_write(path, args)
@@ -203,29 +347,25 @@ impl<A: Access> LayeredAccess for ConcurrentLimitAccessor<A> {
     }
 
     fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        let _permit = self
-            .semaphore
-            .try_acquire()
-            .expect("semaphore must be valid");
+        let _permit = self.blocking_acquire(LimitClass::Metadata);
 
         self.inner.blocking_stat(path, args)
     }
 
+    fn blocking_exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        let _permit = self.blocking_acquire(LimitClass::Metadata);
+
+        self.inner.blocking_exists(path, args)
+    }
+
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        let _permit = self
-            .semaphore
-            .try_acquire()
-            .expect("semaphore must be valid");
+        let _permit = self.blocking_acquire(LimitClass::Write);
 
         self.inner.blocking_delete(path, args)
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
-        let permit = self
-            .semaphore
-            .clone()
-            .try_acquire_owned()
-            .expect("semaphore must be valid");
+        let permit = self.blocking_acquire(LimitClass::List);
 
         self.inner
             .blocking_list(path, args)
@@ -237,11 +377,11 @@ pub struct ConcurrentLimitWrapper<R> {
     inner: R,
 
     // Hold on this permit until this reader has been dropped.
-    _permit: OwnedSemaphorePermit,
+    _permit: ConcurrentLimitPermit,
 }
 
 impl<R> ConcurrentLimitWrapper<R> {
-    fn new(inner: R, permit: OwnedSemaphorePermit) -> Self {
+    fn new(inner: R, permit: ConcurrentLimitPermit) -> Self {
         Self {
             inner,
             _permit: permit,
@@ -266,7 +406,7 @@ impl<R: oio::Write> oio::Write for ConcurrentLimitWrapper<R> {
         self.inner.write(bs).await
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         self.inner.close().await
     }
 
@@ -280,7 +420,7 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for ConcurrentLimitWrapper<R> {
         self.inner.write(bs)
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         self.inner.close()
     }
 }
@@ -296,3 +436,32 @@ impl<R: oio::BlockingList> oio::BlockingList for ConcurrentLimitWrapper<R> {
         self.inner.next()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::services;
+
+    #[tokio::test]
+    async fn test_list_permits_not_starve_read() {
+        let op = Operator::new(services::Memory::default())
+            .unwrap()
+            .layer(ConcurrentLimitLayer::new(2).with_list_permits(1))
+            .finish();
+        op.write("test", "hello").await.unwrap();
+
+        // The lister holds the only list permit until dropped.
+        let _lister = op.lister("/").await.unwrap();
+
+        let list = tokio::time::timeout(Duration::from_millis(100), op.lister("/")).await;
+        assert!(list.is_err(), "list must wait for the list permit");
+
+        let bs = tokio::time::timeout(Duration::from_millis(100), op.read("test"))
+            .await
+            .expect("read must not be blocked by list")
+            .unwrap();
+        assert_eq!(bs.to_vec(), b"hello");
+    }
+}
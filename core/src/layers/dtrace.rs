@@ -39,29 +39,33 @@ use crate::*;
 ///     2. read
 ///     3. write
 ///     4. stat
-///     5. delete
-///     6. list
-///     7. presign
-///     8. blocking_create_dir
-///     9. blocking_read
-///     10. blocking_write
-///     11. blocking_stat
-///     12. blocking_delete
-///     13. blocking_list
+///     5. exists
+///     6. delete
+///     7. list
+///     8. presign
+///     9. blocking_create_dir
+///     10. blocking_read
+///     11. blocking_write
+///     12. blocking_stat
+///     13. blocking_exists
+///     14. blocking_delete
+///     15. blocking_list
 /// 2. ${operation}_end, arguments: path
 ///     1. create_dir
 ///     2. read
 ///     3. write
 ///     4. stat
-///     5. delete
-///     6. list
-///     7. presign
-///     8. blocking_create_dir
-///     9. blocking_read
-///     10. blocking_write
-///     11. blocking_stat
-///     12. blocking_delete
-///     13. blocking_list
+///     5. exists
+///     6. delete
+///     7. list
+///     8. presign
+///     9. blocking_create_dir
+///     10. blocking_read
+///     11. blocking_write
+///     12. blocking_stat
+///     13. blocking_exists
+///     14. blocking_delete
+///     15. blocking_list
 ///
 /// ### For Reader
 ///
@@ -242,6 +246,14 @@ impl<A: Access> LayeredAccess for DTraceAccessor<A> {
         result
     }
 
+    async fn exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        let c_path = CString::new(path).unwrap();
+        probe_lazy!(opendal, exists_start, c_path.as_ptr());
+        let result = self.inner.exists(path, args).await;
+        probe_lazy!(opendal, exists_end, c_path.as_ptr());
+        result
+    }
+
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         let c_path = CString::new(path).unwrap();
         probe_lazy!(opendal, delete_start, c_path.as_ptr());
@@ -308,6 +320,14 @@ impl<A: Access> LayeredAccess for DTraceAccessor<A> {
         result
     }
 
+    fn blocking_exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        let c_path = CString::new(path).unwrap();
+        probe_lazy!(opendal, blocking_exists_start, c_path.as_ptr());
+        let result = self.inner.blocking_exists(path, args);
+        probe_lazy!(opendal, blocking_exists_end, c_path.as_ptr());
+        result
+    }
+
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         let c_path = CString::new(path).unwrap();
         probe_lazy!(opendal, blocking_delete_start, c_path.as_ptr());
@@ -409,14 +429,15 @@ impl<R: oio::Write> oio::Write for DtraceLayerWrapper<R> {
             })
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         let c_path = CString::new(self.path.clone()).unwrap();
         probe_lazy!(opendal, writer_close_start, c_path.as_ptr());
         self.inner
             .close()
             .await
-            .map(|_| {
+            .map(|meta| {
                 probe_lazy!(opendal, writer_close_ok, c_path.as_ptr());
+                meta
             })
             .map_err(|err| {
                 probe_lazy!(opendal, writer_close_error, c_path.as_ptr());
@@ -440,13 +461,14 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for DtraceLayerWrapper<R> {
             })
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         let c_path = CString::new(self.path.clone()).unwrap();
         probe_lazy!(opendal, blocking_writer_close_start, c_path.as_ptr());
         self.inner
             .close()
-            .map(|_| {
+            .map(|meta| {
                 probe_lazy!(opendal, blocking_writer_close_ok, c_path.as_ptr());
+                meta
             })
             .map_err(|err| {
                 probe_lazy!(opendal, blocking_writer_close_error, c_path.as_ptr());
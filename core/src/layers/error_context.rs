@@ -167,6 +167,17 @@ impl<A: Access> LayeredAccess for ErrorContextAccessor<A> {
             .await
     }
 
+    async fn exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        self.inner
+            .exists(path, args)
+            .map_err(|err| {
+                err.with_operation(Operation::Stat)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("path", path)
+            })
+            .await
+    }
+
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.inner
             .delete(path, args)
@@ -229,6 +240,48 @@ impl<A: Access> LayeredAccess for ErrorContextAccessor<A> {
         })
     }
 
+    async fn initiate_multipart(&self, path: &str, args: OpWrite) -> Result<RpInitiateMultipart> {
+        self.inner
+            .initiate_multipart(path, args)
+            .await
+            .map_err(|err| {
+                err.with_operation(Operation::InitiateMultipart)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("path", path)
+            })
+    }
+
+    async fn complete_multipart(
+        &self,
+        path: &str,
+        args: OpCompleteMultipart,
+    ) -> Result<RpCompleteMultipart> {
+        let upload_id = args.upload_id().to_string();
+        self.inner
+            .complete_multipart(path, args)
+            .await
+            .map_err(|err| {
+                err.with_operation(Operation::CompleteMultipart)
+                    .with_context("service", self.meta.scheme())
+                    .with_context("path", path)
+                    .with_context("upload_id", upload_id)
+            })
+    }
+
+    async fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> Result<RpAbortMultipart> {
+        let upload_id = args.upload_id().to_string();
+        self.inner.abort_multipart(path, args).await.map_err(|err| {
+            err.with_operation(Operation::AbortMultipart)
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+                .with_context("upload_id", upload_id)
+        })
+    }
+
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
         self.inner.blocking_create_dir(path, args).map_err(|err| {
             err.with_operation(Operation::BlockingCreateDir)
@@ -298,6 +351,14 @@ impl<A: Access> LayeredAccess for ErrorContextAccessor<A> {
         })
     }
 
+    fn blocking_exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        self.inner.blocking_exists(path, args).map_err(|err| {
+            err.with_operation(Operation::BlockingStat)
+                .with_context("service", self.meta.scheme())
+                .with_context("path", path)
+        })
+    }
+
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.inner.blocking_delete(path, args).map_err(|err| {
             err.with_operation(Operation::BlockingDelete)
@@ -403,7 +464,7 @@ impl<T: oio::Write> oio::Write for ErrorContextWrapper<T> {
             })
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         self.inner.close().await.map_err(|err| {
             err.with_operation(WriteOperation::Close)
                 .with_context("service", self.scheme)
@@ -439,7 +500,7 @@ impl<T: oio::BlockingWrite> oio::BlockingWrite for ErrorContextWrapper<T> {
             })
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         self.inner.close().map_err(|err| {
             err.with_operation(WriteOperation::BlockingClose)
                 .with_context("service", self.scheme)
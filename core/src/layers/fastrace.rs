@@ -194,6 +194,11 @@ impl<A: Access> LayeredAccess for FastraceAccessor<A> {
         self.inner.stat(path, args).await
     }
 
+    #[trace(enter_on_poll = true)]
+    async fn exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        self.inner.exists(path, args).await
+    }
+
     #[trace(enter_on_poll = true)]
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.inner.delete(path, args).await
@@ -264,6 +269,11 @@ impl<A: Access> LayeredAccess for FastraceAccessor<A> {
         self.inner.blocking_stat(path, args)
     }
 
+    #[trace]
+    fn blocking_exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        self.inner.blocking_exists(path, args)
+    }
+
     #[trace]
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.inner.blocking_delete(path, args)
@@ -319,7 +329,7 @@ impl<R: oio::Write> oio::Write for FastraceWrapper<R> {
         self.inner.abort()
     }
 
-    fn close(&mut self) -> impl Future<Output = Result<()>> + MaybeSend {
+    fn close(&mut self) -> impl Future<Output = Result<Metadata>> + MaybeSend {
         let _g = self.span.set_local_parent();
         let _span = LocalSpan::enter_with_local_parent(WriteOperation::Close.into_static());
         self.inner.close()
@@ -333,7 +343,7 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for FastraceWrapper<R> {
         self.inner.write(bs)
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         let _g = self.span.set_local_parent();
         let _span = LocalSpan::enter_with_local_parent(WriteOperation::BlockingClose.into_static());
         self.inner.close()
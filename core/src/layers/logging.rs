@@ -15,19 +15,23 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::fmt;
 use std::fmt::Debug;
-use std::sync::atomic::AtomicU64;
-use std::sync::atomic::Ordering;
+use std::fmt::Display;
+use std::future::Future;
 use std::sync::Arc;
-
-use bytes::Buf;
-use futures::FutureExt;
-use futures::TryFutureExt;
-use log::debug;
-use log::log;
-use log::trace;
+use std::time::Duration;
+use std::time::Instant;
+
+use log::kv;
+use log::kv::Key;
+use log::kv::Source;
+use log::kv::Value;
+use log::kv::VisitSource;
 use log::Level;
+use log::Record;
 
+use crate::raw::oio::ListOperation;
 use crate::raw::oio::ReadOperation;
 use crate::raw::oio::WriteOperation;
 use crate::raw::*;
@@ -43,14 +47,23 @@ use crate::*;
 ///   - `finished`: the operation is successful.
 ///   - `errored`: the operation returns an expected error like `NotFound`.
 ///   - `failed`: the operation returns an unexpected error.
+/// - The default log level while operation started or finished is `Debug`.
 /// - The default log level while expected error happened is `Warn`.
 /// - The default log level while unexpected failure happened is `Error`.
 ///
-/// # Todo
+/// Every log entry carries the following key-values via log's kv api, they
+/// are also rendered in the message for loggers that don't support kv:
 ///
-/// We should migrate to log's kv api after it's ready.
+/// - `service`: the scheme of the service.
+/// - `operation`: the operation name.
+/// - `path`, or `from` and `to` for `copy` and `rename`.
+/// - `count`: the number of entries listed or operations in a batch.
+/// - `bytes`: the number of bytes read or written.
+/// - `duration_ms`: the time taken by the operation, set once it's finished.
+/// - `error_kind`: the [`ErrorKind`] of the returned error.
 ///
-/// Tracking issue: <https://github.com/rust-lang/log/issues/328>
+/// Set [`LoggingLayer::with_slow_threshold`] to only log operations that
+/// are slower than the threshold, errors are always logged.
 ///
 /// # Examples
 ///
@@ -67,6 +80,28 @@ use crate::*;
 ///     .finish();
 /// ```
 ///
+/// Only log operations taking longer than 1s at `info` level:
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use anyhow::Result;
+/// use opendal::layers::LoggingLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// # fn main() -> Result<()> {
+/// let _ = Operator::new(services::Memory::default())?
+///     .layer(
+///         LoggingLayer::default()
+///             .with_finished_level(Some("info"))?
+///             .with_slow_threshold(Duration::from_secs(1)),
+///     )
+///     .finish();
+/// # Ok(())
+/// # }
+/// ```
+///
 /// # Output
 ///
 /// OpenDAL is using [`log`](https://docs.rs/log/latest/log/) for logging internally.
@@ -84,37 +119,61 @@ use crate::*;
 /// ```
 #[derive(Debug, Copy, Clone)]
 pub struct LoggingLayer {
+    started_level: Option<Level>,
+    finished_level: Option<Level>,
     error_level: Option<Level>,
     failure_level: Option<Level>,
+    slow_threshold: Option<Duration>,
     backtrace_output: bool,
 }
 
 impl Default for LoggingLayer {
     fn default() -> Self {
         Self {
+            started_level: Some(Level::Debug),
+            finished_level: Some(Level::Debug),
             error_level: Some(Level::Warn),
             failure_level: Some(Level::Error),
+            slow_threshold: None,
             backtrace_output: false,
         }
     }
 }
 
+fn parse_level(level: Option<&str>) -> Result<Option<Level>> {
+    level
+        .map(|v| {
+            v.parse().map_err(|_| {
+                Error::new(ErrorKind::ConfigInvalid, "invalid log level").with_context("level", v)
+            })
+        })
+        .transpose()
+}
+
 impl LoggingLayer {
+    /// Setting the log level while operation started.
+    ///
+    /// `None` means disable the log for started operations.
+    pub fn with_started_level(mut self, level: Option<&str>) -> Result<Self> {
+        self.started_level = parse_level(level)?;
+        Ok(self)
+    }
+
+    /// Setting the log level while operation finished successfully.
+    ///
+    /// `None` means disable the log for finished operations.
+    pub fn with_finished_level(mut self, level: Option<&str>) -> Result<Self> {
+        self.finished_level = parse_level(level)?;
+        Ok(self)
+    }
+
     /// Setting the log level while expected error happened.
     ///
     /// For example: accessor returns NotFound.
     ///
     /// `None` means disable the log for error.
     pub fn with_error_level(mut self, level: Option<&str>) -> Result<Self> {
-        if let Some(level_str) = level {
-            let level = level_str.parse().map_err(|_| {
-                Error::new(ErrorKind::ConfigInvalid, "invalid log level")
-                    .with_context("level", level_str)
-            })?;
-            self.error_level = Some(level);
-        } else {
-            self.error_level = None;
-        }
+        self.error_level = parse_level(level)?;
         Ok(self)
     }
 
@@ -124,18 +183,21 @@ impl LoggingLayer {
     ///
     /// `None` means disable the log for failure.
     pub fn with_failure_level(mut self, level: Option<&str>) -> Result<Self> {
-        if let Some(level_str) = level {
-            let level = level_str.parse().map_err(|_| {
-                Error::new(ErrorKind::ConfigInvalid, "invalid log level")
-                    .with_context("level", level_str)
-            })?;
-            self.failure_level = Some(level);
-        } else {
-            self.failure_level = None;
-        }
+        self.failure_level = parse_level(level)?;
         Ok(self)
     }
 
+    /// Only log operations that take longer than `threshold`.
+    ///
+    /// # Notes
+    ///
+    /// - `started` logs are skipped since the duration is unknown yet.
+    /// - Errors are always logged regardless of the duration.
+    pub fn with_slow_threshold(mut self, threshold: Duration) -> Self {
+        self.slow_threshold = Some(threshold);
+        self
+    }
+
     /// Setting whether to output backtrace while unexpected failure happened.
     ///
     /// # Notes
@@ -158,23 +220,140 @@ impl<A: Access> Layer<A> for LoggingLayer {
 
             ctx: LoggingContext {
                 scheme: meta.scheme(),
+                started_level: self.started_level,
+                finished_level: self.finished_level,
                 error_level: self.error_level,
                 failure_level: self.failure_level,
+                slow_threshold: self.slow_threshold,
                 backtrace_output: self.backtrace_output,
             },
         }
     }
 }
 
+/// Structured fields of a log entry.
+///
+/// Fields are emitted as key-values and rendered as the message prefix.
+#[derive(Debug, Clone, Copy)]
+struct LoggingFields<'a> {
+    scheme: Scheme,
+    operation: &'static str,
+    path: Option<&'a str>,
+    from: Option<&'a str>,
+    to: Option<&'a str>,
+    count: Option<u64>,
+    bytes: Option<u64>,
+    duration: Option<Duration>,
+    error_kind: Option<ErrorKind>,
+}
+
+impl<'a> LoggingFields<'a> {
+    fn new(scheme: Scheme, operation: impl Into<&'static str>) -> Self {
+        Self {
+            scheme,
+            operation: operation.into(),
+            path: None,
+            from: None,
+            to: None,
+            count: None,
+            bytes: None,
+            duration: None,
+            error_kind: None,
+        }
+    }
+
+    fn with_path(mut self, path: &'a str) -> Self {
+        self.path = Some(path);
+        self
+    }
+
+    fn with_from_to(mut self, from: &'a str, to: &'a str) -> Self {
+        self.from = Some(from);
+        self.to = Some(to);
+        self
+    }
+
+    fn with_count(mut self, count: u64) -> Self {
+        self.count = Some(count);
+        self
+    }
+
+    fn with_bytes(mut self, bytes: u64) -> Self {
+        self.bytes = Some(bytes);
+        self
+    }
+}
+
+impl Source for LoggingFields<'_> {
+    fn visit<'kvs>(&'kvs self, visitor: &mut dyn VisitSource<'kvs>) -> Result<(), kv::Error> {
+        visitor.visit_pair(Key::from("service"), Value::from(self.scheme.into_static()))?;
+        visitor.visit_pair(Key::from("operation"), Value::from(self.operation))?;
+        if let Some(v) = self.path {
+            visitor.visit_pair(Key::from("path"), Value::from(v))?;
+        }
+        if let Some(v) = self.from {
+            visitor.visit_pair(Key::from("from"), Value::from(v))?;
+        }
+        if let Some(v) = self.to {
+            visitor.visit_pair(Key::from("to"), Value::from(v))?;
+        }
+        if let Some(v) = self.count {
+            visitor.visit_pair(Key::from("count"), Value::from(v))?;
+        }
+        if let Some(v) = self.bytes {
+            visitor.visit_pair(Key::from("bytes"), Value::from(v))?;
+        }
+        if let Some(v) = self.duration {
+            visitor.visit_pair(Key::from("duration_ms"), Value::from(v.as_millis() as u64))?;
+        }
+        if let Some(v) = self.error_kind {
+            visitor.visit_pair(Key::from("error_kind"), Value::from(v.into_static()))?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for LoggingFields<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "service={} operation={}", self.scheme, self.operation)?;
+        if let Some(v) = self.path {
+            write!(f, " path={v}")?;
+        }
+        if let Some(v) = self.from {
+            write!(f, " from={v}")?;
+        }
+        if let Some(v) = self.to {
+            write!(f, " to={v}")?;
+        }
+        if let Some(v) = self.count {
+            write!(f, " count={v}")?;
+        }
+        if let Some(v) = self.bytes {
+            write!(f, " bytes={v}")?;
+        }
+        if let Some(v) = self.duration {
+            write!(f, " duration_ms={}", v.as_millis())?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct LoggingContext {
     scheme: Scheme,
+    started_level: Option<Level>,
+    finished_level: Option<Level>,
     error_level: Option<Level>,
     failure_level: Option<Level>,
+    slow_threshold: Option<Duration>,
     backtrace_output: bool,
 }
 
 impl LoggingContext {
+    fn fields(&self, operation: impl Into<&'static str>) -> LoggingFields<'static> {
+        LoggingFields::new(self.scheme, operation)
+    }
+
     #[inline]
     fn error_level(&self, err: &Error) -> Option<Level> {
         if err.kind() == ErrorKind::Unexpected {
@@ -198,6 +377,104 @@ impl LoggingContext {
             format!("{err}")
         }
     }
+
+    /// Returns the level to log a finished operation that took `duration`.
+    #[inline]
+    fn finished_level(&self, duration: Duration) -> Option<Level> {
+        match self.slow_threshold {
+            Some(threshold) if duration < threshold => None,
+            _ => self.finished_level,
+        }
+    }
+
+    fn emit(&self, lvl: Level, fields: &LoggingFields, msg: fmt::Arguments) {
+        if !log::log_enabled!(target: LOGGING_TARGET, lvl) {
+            return;
+        }
+
+        log::logger().log(
+            &Record::builder()
+                .level(lvl)
+                .target(LOGGING_TARGET)
+                .module_path_static(Some(module_path!()))
+                .file_static(Some(file!()))
+                .line(Some(line!()))
+                .key_values(fields)
+                .args(format_args!("{fields} -> {msg}"))
+                .build(),
+        );
+    }
+
+    fn started(&self, fields: &LoggingFields) {
+        // The duration is unknown yet, leave it to finished.
+        if self.slow_threshold.is_some() {
+            return;
+        }
+        if let Some(lvl) = self.started_level {
+            self.emit(lvl, fields, format_args!("started"))
+        }
+    }
+
+    fn finished(&self, fields: LoggingFields, start: Instant, msg: fmt::Arguments) {
+        let duration = start.elapsed();
+        if let Some(lvl) = self.finished_level(duration) {
+            let fields = LoggingFields {
+                duration: Some(duration),
+                ..fields
+            };
+            self.emit(lvl, &fields, msg)
+        }
+    }
+
+    fn failed(&self, fields: LoggingFields, start: Instant, err: &Error) {
+        if let Some(lvl) = self.error_level(err) {
+            let fields = LoggingFields {
+                duration: Some(start.elapsed()),
+                error_kind: Some(err.kind()),
+                ..fields
+            };
+            let msg = self.error_print(err);
+            self.emit(lvl, &fields, format_args!("{msg}"))
+        }
+    }
+
+    /// Log the progress of readers, writers and listers.
+    fn progress(&self, fields: &LoggingFields, msg: fmt::Arguments) {
+        // Progress is too noisy to be logged while only slow operations are wanted.
+        if self.slow_threshold.is_none() {
+            self.emit(Level::Trace, fields, msg)
+        }
+    }
+
+    async fn call<T>(
+        &self,
+        fields: LoggingFields<'_>,
+        fut: impl Future<Output = Result<T>>,
+    ) -> Result<T> {
+        self.started(&fields);
+        let start = Instant::now();
+        let res = fut.await;
+        match &res {
+            Ok(_) => self.finished(fields, start, format_args!("finished")),
+            Err(err) => self.failed(fields, start, err),
+        }
+        res
+    }
+
+    fn blocking_call<T>(
+        &self,
+        fields: LoggingFields<'_>,
+        f: impl FnOnce() -> Result<T>,
+    ) -> Result<T> {
+        self.started(&fields);
+        let start = Instant::now();
+        let res = f();
+        match &res {
+            Ok(_) => self.finished(fields, start, format_args!("finished")),
+            Err(err) => self.failed(fields, start, err),
+        }
+        res
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -223,721 +500,198 @@ impl<A: Access> LayeredAccess for LoggingAccessor<A> {
     }
 
     fn metadata(&self) -> Arc<AccessorInfo> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} -> started",
-            self.ctx.scheme,
-            Operation::Info
-        );
+        let fields = self.ctx.fields(Operation::Info);
+        self.ctx.started(&fields);
+        let start = Instant::now();
         let result = self.inner.info();
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} -> finished: {:?}",
-            self.ctx.scheme,
-            Operation::Info,
-            result
-        );
+        self.ctx
+            .finished(fields, start, format_args!("finished: {result:?}"));
 
         result
     }
 
     async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.ctx.scheme,
-            Operation::CreateDir,
-            path
-        );
-
-        self.inner
-            .create_dir(path, args)
+        let fields = self.ctx.fields(Operation::CreateDir).with_path(path);
+        self.ctx
+            .call(fields, self.inner.create_dir(path, args))
             .await
-            .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished",
-                    self.ctx.scheme,
-                    Operation::CreateDir,
-                    path
-                );
-                v
-            })
-            .map_err(|err| {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}",
-                        self.ctx.scheme,
-                        Operation::CreateDir,
-                        path,
-                        self.ctx.error_print(&err)
-                    )
-                };
-                err
-            })
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.ctx.scheme,
-            Operation::Read,
-            path,
-        );
-
-        self.inner
-            .read(path, args)
+        let fields = self.ctx.fields(Operation::Read).with_path(path);
+        self.ctx
+            .call(fields, self.inner.read(path, args))
             .await
             .map(|(rp, r)| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> got reader",
-                    self.ctx.scheme,
-                    Operation::Read,
-                    path,
-                );
                 (
                     rp,
                     LoggingReader::new(self.ctx.clone(), Operation::Read, path, r),
                 )
             })
-            .map_err(|err| {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}",
-                        self.ctx.scheme,
-                        Operation::Read,
-                        path,
-                        self.ctx.error_print(&err)
-                    )
-                }
-                err
-            })
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.ctx.scheme,
-            Operation::Write,
-            path
-        );
-
-        self.inner
-            .write(path, args)
+        let fields = self.ctx.fields(Operation::Write).with_path(path);
+        self.ctx
+            .call(fields, self.inner.write(path, args))
             .await
             .map(|(rp, w)| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> start writing",
-                    self.ctx.scheme,
-                    Operation::Write,
-                    path,
-                );
-                let w = LoggingWriter::new(self.ctx.clone(), Operation::Write, path, w);
-                (rp, w)
-            })
-            .map_err(|err| {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}",
-                        self.ctx.scheme,
-                        Operation::Write,
-                        path,
-                        self.ctx.error_print(&err)
-                    )
-                };
-                err
+                (
+                    rp,
+                    LoggingWriter::new(self.ctx.clone(), Operation::Write, path, w),
+                )
             })
     }
 
     async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} from={} to={} -> started",
-            self.ctx.scheme,
-            Operation::Copy,
-            from,
-            to
-        );
-
-        self.inner
-            .copy(from, to, args)
-            .await
-            .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} from={} to={} -> finished",
-                    self.ctx.scheme,
-                    Operation::Copy,
-                    from,
-                    to
-                );
-                v
-            })
-            .map_err(|err| {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} from={} to={} -> {}",
-                        self.ctx.scheme,
-                        Operation::Copy,
-                        from,
-                        to,
-                        self.ctx.error_print(&err),
-                    )
-                };
-                err
-            })
+        let fields = self.ctx.fields(Operation::Copy).with_from_to(from, to);
+        self.ctx.call(fields, self.inner.copy(from, to, args)).await
     }
 
     async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} from={} to={} -> started",
-            self.ctx.scheme,
-            Operation::Rename,
-            from,
-            to
-        );
-
-        self.inner
-            .rename(from, to, args)
+        let fields = self.ctx.fields(Operation::Rename).with_from_to(from, to);
+        self.ctx
+            .call(fields, self.inner.rename(from, to, args))
             .await
-            .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} from={} to={} -> finished",
-                    self.ctx.scheme,
-                    Operation::Rename,
-                    from,
-                    to
-                );
-                v
-            })
-            .map_err(|err| {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} from={} to={} -> {}",
-                        self.ctx.scheme,
-                        Operation::Rename,
-                        from,
-                        to,
-                        self.ctx.error_print(&err)
-                    )
-                };
-                err
-            })
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.ctx.scheme,
-            Operation::Stat,
-            path
-        );
+        let fields = self.ctx.fields(Operation::Stat).with_path(path);
+        self.ctx.call(fields, self.inner.stat(path, args)).await
+    }
 
-        self.inner
-            .stat(path, args)
-            .await
-            .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished: {v:?}",
-                    self.ctx.scheme,
-                    Operation::Stat,
-                    path
-                );
-                v
-            })
-            .map_err(|err| {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}",
-                        self.ctx.scheme,
-                        Operation::Stat,
-                        path,
-                        self.ctx.error_print(&err)
-                    );
-                };
-                err
-            })
+    async fn exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        let fields = self.ctx.fields(Operation::Stat).with_path(path);
+        self.ctx.call(fields, self.inner.exists(path, args)).await
     }
 
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.ctx.scheme,
-            Operation::Delete,
-            path
-        );
-
-        self.inner
-            .delete(path, args.clone())
-            .inspect(|v| match v {
-                Ok(_) => {
-                    debug!(
-                        target: LOGGING_TARGET,
-                        "service={} operation={} path={} -> finished",
-                        self.ctx.scheme,
-                        Operation::Delete,
-                        path
-                    );
-                }
-                Err(err) => {
-                    if let Some(lvl) = self.ctx.error_level(err) {
-                        log!(
-                            target: LOGGING_TARGET,
-                            lvl,
-                            "service={} operation={} path={} -> {}",
-                            self.ctx.scheme,
-                            Operation::Delete,
-                            path,
-                            self.ctx.error_print(err)
-                        );
-                    }
-                }
-            })
-            .await
+        let fields = self.ctx.fields(Operation::Delete).with_path(path);
+        self.ctx.call(fields, self.inner.delete(path, args)).await
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.ctx.scheme,
-            Operation::List,
-            path
-        );
-
-        self.inner
-            .list(path, args)
-            .map(|v| match v {
-                Ok((rp, v)) => {
-                    debug!(
-                        target: LOGGING_TARGET,
-                        "service={} operation={} path={} -> start listing dir",
-                        self.ctx.scheme,
-                        Operation::List,
-                        path
-                    );
-                    let streamer = LoggingLister::new(self.ctx.clone(), path, Operation::List, v);
-                    Ok((rp, streamer))
-                }
-                Err(err) => {
-                    if let Some(lvl) = self.ctx.error_level(&err) {
-                        log!(
-                            target: LOGGING_TARGET,
-                            lvl,
-                            "service={} operation={} path={} -> {}",
-                            self.ctx.scheme,
-                            Operation::List,
-                            path,
-                            self.ctx.error_print(&err)
-                        );
-                    }
-                    Err(err)
-                }
-            })
+        let fields = self.ctx.fields(Operation::List).with_path(path);
+        self.ctx
+            .call(fields, self.inner.list(path, args))
             .await
+            .map(|(rp, v)| {
+                (
+                    rp,
+                    LoggingLister::new(self.ctx.clone(), path, Operation::List, v),
+                )
+            })
     }
 
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.ctx.scheme,
-            Operation::Presign,
-            path
-        );
-
-        self.inner
-            .presign(path, args)
-            .await
-            .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished: {v:?}",
-                    self.ctx.scheme,
-                    Operation::Presign,
-                    path
-                );
-                v
-            })
-            .map_err(|err| {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}",
-                        self.ctx.scheme,
-                        Operation::Presign,
-                        path,
-                        self.ctx.error_print(&err)
-                    );
-                }
-                err
-            })
+        let fields = self.ctx.fields(Operation::Presign).with_path(path);
+        self.ctx.call(fields, self.inner.presign(path, args)).await
     }
 
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
-        let (op, count) = (args.operation()[0].1.operation(), args.operation().len());
-
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={}-{op} count={count} -> started",
-            self.ctx.scheme,
-            Operation::Batch,
-        );
-
-        self.inner
-            .batch(args)
-            .map_ok(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={}-{op} count={count} -> finished: {}, succeed: {}, failed: {}",
-                    self.ctx.scheme,
-                    Operation::Batch,
-                    v.results().len(),
-                    v.results().iter().filter(|(_, v)|v.is_ok()).count(),
-                    v.results().iter().filter(|(_, v)|v.is_err()).count(),
-                );
-                v
-            })
-            .map_err(|err| {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={}-{op} count={count} -> {}",
-                        self.ctx.scheme,
-                        Operation::Batch,
-                        self.ctx.error_print(&err)
-                    );
-                }
-                err
-            })
-            .await
+        let fields = self
+            .ctx
+            .fields(Operation::Batch)
+            .with_count(args.operation().len() as u64);
+
+        self.ctx.started(&fields);
+        let start = Instant::now();
+        let res = self.inner.batch(args).await;
+        match &res {
+            Ok(v) => {
+                let succeed = v.results().iter().filter(|(_, v)| v.is_ok()).count();
+                self.ctx.finished(
+                    fields,
+                    start,
+                    format_args!(
+                        "finished: succeed: {succeed}, failed: {}",
+                        v.results().len() - succeed
+                    ),
+                )
+            }
+            Err(err) => self.ctx.failed(fields, start, err),
+        }
+        res
     }
 
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.ctx.scheme,
-            Operation::BlockingCreateDir,
-            path
-        );
-
-        self.inner
-            .blocking_create_dir(path, args)
-            .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished",
-                    self.ctx.scheme,
-                    Operation::BlockingCreateDir,
-                    path
-                );
-                v
-            })
-            .map_err(|err| {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}",
-                        self.ctx.scheme,
-                        Operation::BlockingCreateDir,
-                        path,
-                        self.ctx.error_print(&err)
-                    );
-                }
-                err
-            })
+        let fields = self
+            .ctx
+            .fields(Operation::BlockingCreateDir)
+            .with_path(path);
+        self.ctx
+            .blocking_call(fields, || self.inner.blocking_create_dir(path, args))
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.ctx.scheme,
-            Operation::BlockingRead,
-            path,
-        );
-
-        self.inner
-            .blocking_read(path, args.clone())
+        let fields = self.ctx.fields(Operation::BlockingRead).with_path(path);
+        self.ctx
+            .blocking_call(fields, || self.inner.blocking_read(path, args))
             .map(|(rp, r)| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> got reader",
-                    self.ctx.scheme,
-                    Operation::BlockingRead,
-                    path,
-                );
-                let r = LoggingReader::new(self.ctx.clone(), Operation::BlockingRead, path, r);
-                (rp, r)
-            })
-            .map_err(|err| {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}",
-                        self.ctx.scheme,
-                        Operation::BlockingRead,
-                        path,
-                        self.ctx.error_print(&err)
-                    );
-                }
-                err
+                (
+                    rp,
+                    LoggingReader::new(self.ctx.clone(), Operation::BlockingRead, path, r),
+                )
             })
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.ctx.scheme,
-            Operation::BlockingWrite,
-            path,
-        );
-
-        self.inner
-            .blocking_write(path, args)
+        let fields = self.ctx.fields(Operation::BlockingWrite).with_path(path);
+        self.ctx
+            .blocking_call(fields, || self.inner.blocking_write(path, args))
             .map(|(rp, w)| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> start writing",
-                    self.ctx.scheme,
-                    Operation::BlockingWrite,
-                    path,
-                );
-                let w = LoggingWriter::new(self.ctx.clone(), Operation::BlockingWrite, path, w);
-                (rp, w)
-            })
-            .map_err(|err| {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}",
-                        self.ctx.scheme,
-                        Operation::BlockingWrite,
-                        path,
-                        self.ctx.error_print(&err)
-                    );
-                }
-                err
+                (
+                    rp,
+                    LoggingWriter::new(self.ctx.clone(), Operation::BlockingWrite, path, w),
+                )
             })
     }
 
     fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} from={} to={} -> started",
-            self.ctx.scheme,
-            Operation::BlockingCopy,
-            from,
-            to,
-        );
-
-        self.inner
-            .blocking_copy(from, to, args)
-            .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} from={} to={} -> finished",
-                    self.ctx.scheme,
-                    Operation::BlockingCopy,
-                    from,
-                    to,
-                );
-                v
-            })
-            .map_err(|err| {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} from={} to={} -> {}",
-                        self.ctx.scheme,
-                        Operation::BlockingCopy,
-                        from,
-                        to,
-                        self.ctx.error_print(&err)
-                    );
-                }
-                err
-            })
+        let fields = self
+            .ctx
+            .fields(Operation::BlockingCopy)
+            .with_from_to(from, to);
+        self.ctx
+            .blocking_call(fields, || self.inner.blocking_copy(from, to, args))
     }
 
     fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} from={} to={} -> started",
-            self.ctx.scheme,
-            Operation::BlockingRename,
-            from,
-            to,
-        );
-
-        self.inner
-            .blocking_rename(from, to, args)
-            .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} from={} to={} -> finished",
-                    self.ctx.scheme,
-                    Operation::BlockingRename,
-                    from,
-                    to,
-                );
-                v
-            })
-            .map_err(|err| {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} from={} to={} -> {}",
-                        self.ctx.scheme,
-                        Operation::BlockingRename,
-                        from,
-                        to,
-                        self.ctx.error_print(&err)
-                    );
-                }
-                err
-            })
+        let fields = self
+            .ctx
+            .fields(Operation::BlockingRename)
+            .with_from_to(from, to);
+        self.ctx
+            .blocking_call(fields, || self.inner.blocking_rename(from, to, args))
     }
 
     fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.ctx.scheme,
-            Operation::BlockingStat,
-            path
-        );
+        let fields = self.ctx.fields(Operation::BlockingStat).with_path(path);
+        self.ctx
+            .blocking_call(fields, || self.inner.blocking_stat(path, args))
+    }
 
-        self.inner
-            .blocking_stat(path, args)
-            .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished: {v:?}",
-                    self.ctx.scheme,
-                    Operation::BlockingStat,
-                    path
-                );
-                v
-            })
-            .map_err(|err| {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}",
-                        self.ctx.scheme,
-                        Operation::BlockingStat,
-                        path,
-                        self.ctx.error_print(&err)
-                    );
-                }
-                err
-            })
+    fn blocking_exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        let fields = self.ctx.fields(Operation::BlockingStat).with_path(path);
+        self.ctx
+            .blocking_call(fields, || self.inner.blocking_exists(path, args))
     }
 
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.ctx.scheme,
-            Operation::BlockingDelete,
-            path
-        );
-
-        self.inner
-            .blocking_delete(path, args)
-            .map(|v| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished",
-                    self.ctx.scheme,
-                    Operation::BlockingDelete,
-                    path
-                );
-                v
-            })
-            .map_err(|err| {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}",
-                        self.ctx.scheme,
-                        Operation::BlockingDelete,
-                        path,
-                        self.ctx.error_print(&err)
-                    );
-                }
-                err
-            })
+        let fields = self.ctx.fields(Operation::BlockingDelete).with_path(path);
+        self.ctx
+            .blocking_call(fields, || self.inner.blocking_delete(path, args))
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} -> started",
-            self.ctx.scheme,
-            Operation::BlockingList,
-            path
-        );
-
-        self.inner
-            .blocking_list(path, args)
+        let fields = self.ctx.fields(Operation::BlockingList).with_path(path);
+        self.ctx
+            .blocking_call(fields, || self.inner.blocking_list(path, args))
             .map(|(rp, v)| {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> got dir",
-                    self.ctx.scheme,
-                    Operation::BlockingList,
-                    path
-                );
-                let li = LoggingLister::new(self.ctx.clone(), path, Operation::BlockingList, v);
-                (rp, li)
-            })
-            .map_err(|err| {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}",
-                        self.ctx.scheme,
-                        Operation::BlockingList,
-                        path,
-                        self.ctx.error_print(&err)
-                    );
-                }
-                err
+                (
+                    rp,
+                    LoggingLister::new(self.ctx.clone(), path, Operation::BlockingList, v),
+                )
             })
     }
 }
@@ -947,8 +701,9 @@ pub struct LoggingReader<R> {
     ctx: LoggingContext,
     path: String,
     op: Operation,
+    start: Instant,
 
-    read: AtomicU64,
+    read: u64,
     inner: R,
 }
 
@@ -958,95 +713,55 @@ impl<R> LoggingReader<R> {
             ctx,
             op,
             path: path.to_string(),
+            start: Instant::now(),
 
-            read: AtomicU64::new(0),
+            read: 0,
             inner: reader,
         }
     }
+
+    fn fields(&self, op: impl Into<&'static str>) -> LoggingFields<'_> {
+        self.ctx
+            .fields(op)
+            .with_path(&self.path)
+            .with_bytes(self.read)
+    }
+
+    fn on_read(&mut self, op: ReadOperation, res: &Result<Buffer>) {
+        match res {
+            Ok(bs) => {
+                self.read += bs.len() as u64;
+                self.ctx
+                    .progress(&self.fields(op), format_args!("read returns {}B", bs.len()));
+            }
+            Err(err) => self.ctx.failed(self.fields(op), self.start, err),
+        }
+    }
 }
 
 impl<R> Drop for LoggingReader<R> {
     fn drop(&mut self) {
-        debug!(
-            target: LOGGING_TARGET,
-            "service={} operation={} path={} read={} -> data read finished",
-            self.ctx.scheme,
-            self.op,
-            self.path,
-            self.read.load(Ordering::Relaxed)
+        self.ctx.finished(
+            self.fields(self.op),
+            self.start,
+            format_args!("data read finished"),
         );
     }
 }
 
 impl<R: oio::Read> oio::Read for LoggingReader<R> {
     async fn read(&mut self) -> Result<Buffer> {
-        match self.inner.read().await {
-            Ok(bs) => {
-                self.read
-                    .fetch_add(bs.remaining() as u64, Ordering::Relaxed);
-                trace!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} read={} -> read returns {}B",
-                    self.ctx.scheme,
-                    ReadOperation::Read,
-                    self.path,
-                    self.read.load(Ordering::Relaxed),
-                    bs.remaining()
-                );
-                Ok(bs)
-            }
-            Err(err) => {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} read={} -> read failed: {}",
-                        self.ctx.scheme,
-                        ReadOperation::Read,
-                        self.path,
-                        self.read.load(Ordering::Relaxed),
-                        self.ctx.error_print(&err),
-                    )
-                }
-                Err(err)
-            }
-        }
+        let res = self.inner.read().await;
+        self.on_read(ReadOperation::Read, &res);
+        res
     }
 }
 
 impl<R: oio::BlockingRead> oio::BlockingRead for LoggingReader<R> {
     fn read(&mut self) -> Result<Buffer> {
-        match self.inner.read() {
-            Ok(bs) => {
-                self.read
-                    .fetch_add(bs.remaining() as u64, Ordering::Relaxed);
-                trace!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} read={} -> read returns {}B",
-                    self.ctx.scheme,
-                    ReadOperation::BlockingRead,
-                    self.path,
-                    self.read.load(Ordering::Relaxed),
-                    bs.remaining()
-                );
-                Ok(bs)
-            }
-            Err(err) => {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} read={} -> read failed: {}",
-                        self.ctx.scheme,
-                        ReadOperation::BlockingRead,
-                        self.path,
-                        self.read.load(Ordering::Relaxed),
-                        self.ctx.error_print(&err),
-                    );
-                }
-                Err(err)
-            }
-        }
+        let res = self.inner.read();
+        self.on_read(ReadOperation::BlockingRead, &res);
+        res
     }
 }
 
@@ -1054,6 +769,7 @@ pub struct LoggingWriter<W> {
     ctx: LoggingContext,
     op: Operation,
     path: String,
+    start: Instant,
 
     written: u64,
     inner: W,
@@ -1065,172 +781,85 @@ impl<W> LoggingWriter<W> {
             ctx,
             op,
             path: path.to_string(),
+            start: Instant::now(),
 
             written: 0,
             inner: writer,
         }
     }
+
+    fn fields(&self, op: impl Into<&'static str>) -> LoggingFields<'_> {
+        self.ctx
+            .fields(op)
+            .with_path(&self.path)
+            .with_bytes(self.written)
+    }
+
+    fn on_write(&mut self, op: WriteOperation, size: usize, res: &Result<()>) {
+        match res {
+            Ok(_) => {
+                self.written += size as u64;
+                self.ctx
+                    .progress(&self.fields(op), format_args!("data write {size}B"));
+            }
+            Err(err) => self.ctx.failed(self.fields(op), self.start, err),
+        }
+    }
+
+    fn on_close(&self, op: WriteOperation, res: &Result<Metadata>) {
+        match res {
+            Ok(_) => self.ctx.finished(
+                self.fields(self.op),
+                self.start,
+                format_args!("data written finished"),
+            ),
+            Err(err) => self.ctx.failed(self.fields(op), self.start, err),
+        }
+    }
 }
 
 impl<W: oio::Write> oio::Write for LoggingWriter<W> {
     async fn write(&mut self, bs: Buffer) -> Result<()> {
         let size = bs.len();
-        match self.inner.write(bs).await {
-            Ok(_) => {
-                trace!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} written={}B -> data write {}B",
-                    self.ctx.scheme,
-                    WriteOperation::Write,
-                    self.path,
-                    self.written,
-                    size,
-                );
-                Ok(())
-            }
-            Err(err) => {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} written={}B -> data write failed: {}",
-                        self.ctx.scheme,
-                        WriteOperation::Write,
-                        self.path,
-                        self.written,
-                        self.ctx.error_print(&err),
-                    )
-                }
-                Err(err)
-            }
-        }
+        let res = self.inner.write(bs).await;
+        self.on_write(WriteOperation::Write, size, &res);
+        res
     }
 
     async fn abort(&mut self) -> Result<()> {
-        match self.inner.abort().await {
-            Ok(_) => {
-                trace!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} written={}B -> abort writer",
-                    self.ctx.scheme,
-                    WriteOperation::Abort,
-                    self.path,
-                    self.written,
-                );
-                Ok(())
-            }
-            Err(err) => {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} written={}B -> abort writer failed: {}",
-                        self.ctx.scheme,
-                        WriteOperation::Abort,
-                        self.path,
-                        self.written,
-                        self.ctx.error_print(&err),
-                    )
-                }
-                Err(err)
-            }
+        let res = self.inner.abort().await;
+        match &res {
+            Ok(_) => self.ctx.finished(
+                self.fields(WriteOperation::Abort),
+                self.start,
+                format_args!("abort writer"),
+            ),
+            Err(err) => self
+                .ctx
+                .failed(self.fields(WriteOperation::Abort), self.start, err),
         }
+        res
     }
 
-    async fn close(&mut self) -> Result<()> {
-        match self.inner.close().await {
-            Ok(_) => {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} written={}B -> data written finished",
-                    self.ctx.scheme,
-                    self.op,
-                    self.path,
-                    self.written
-                );
-                Ok(())
-            }
-            Err(err) => {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} written={}B -> data close failed: {}",
-                        self.ctx.scheme,
-                        WriteOperation::Close,
-                        self.path,
-                        self.written,
-                        self.ctx.error_print(&err),
-                    )
-                }
-                Err(err)
-            }
-        }
+    async fn close(&mut self) -> Result<Metadata> {
+        let res = self.inner.close().await;
+        self.on_close(WriteOperation::Close, &res);
+        res
     }
 }
 
 impl<W: oio::BlockingWrite> oio::BlockingWrite for LoggingWriter<W> {
     fn write(&mut self, bs: Buffer) -> Result<()> {
-        match self.inner.write(bs.clone()) {
-            Ok(_) => {
-                trace!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} written={}B -> data write {}B",
-                    self.ctx.scheme,
-                    WriteOperation::BlockingWrite,
-                    self.path,
-                    self.written,
-                    bs.len(),
-                );
-                Ok(())
-            }
-            Err(err) => {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} written={}B -> data write failed: {}",
-                        self.ctx.scheme,
-                        WriteOperation::BlockingWrite,
-                        self.path,
-                        self.written,
-                        self.ctx.error_print(&err),
-                    )
-                }
-                Err(err)
-            }
-        }
+        let size = bs.len();
+        let res = self.inner.write(bs);
+        self.on_write(WriteOperation::BlockingWrite, size, &res);
+        res
     }
 
-    fn close(&mut self) -> Result<()> {
-        match self.inner.close() {
-            Ok(_) => {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} written={}B -> data written finished",
-                    self.ctx.scheme,
-                    self.op,
-                    self.path,
-                    self.written
-                );
-                Ok(())
-            }
-            Err(err) => {
-                if let Some(lvl) = self.ctx.error_level(&err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} written={}B -> data close failed: {}",
-                        self.ctx.scheme,
-                        WriteOperation::BlockingClose,
-                        self.path,
-                        self.written,
-                        self.ctx.error_print(&err),
-                    )
-                }
-                Err(err)
-            }
-        }
+    fn close(&mut self) -> Result<Metadata> {
+        let res = self.inner.close();
+        self.on_close(WriteOperation::BlockingClose, &res);
+        res
     }
 }
 
@@ -1238,7 +867,9 @@ pub struct LoggingLister<P> {
     ctx: LoggingContext,
     path: String,
     op: Operation,
+    start: Instant,
 
+    listed: u64,
     finished: bool,
     inner: P,
 }
@@ -1249,29 +880,50 @@ impl<P> LoggingLister<P> {
             ctx,
             path: path.to_string(),
             op,
+            start: Instant::now(),
+
+            listed: 0,
             finished: false,
             inner,
         }
     }
+
+    fn fields(&self, op: impl Into<&'static str>) -> LoggingFields<'_> {
+        self.ctx
+            .fields(op)
+            .with_path(&self.path)
+            .with_count(self.listed)
+    }
+
+    fn on_next(&mut self, op: ListOperation, res: &Result<Option<oio::Entry>>) {
+        match res {
+            Ok(Some(de)) => {
+                self.listed += 1;
+                self.ctx.progress(
+                    &self.fields(op),
+                    format_args!("listed entry: {}", de.path()),
+                );
+            }
+            Ok(None) => {
+                self.finished = true;
+                self.ctx.finished(
+                    self.fields(self.op),
+                    self.start,
+                    format_args!("all entries read finished"),
+                );
+            }
+            Err(err) => self.ctx.failed(self.fields(op), self.start, err),
+        }
+    }
 }
 
 impl<P> Drop for LoggingLister<P> {
     fn drop(&mut self) {
-        if self.finished {
-            debug!(
-                target: LOGGING_TARGET,
-                "service={} operation={} path={} -> all entries read finished",
-                self.ctx.scheme,
-                self.op,
-                self.path
-            );
-        } else {
-            debug!(
-                target: LOGGING_TARGET,
-                "service={} operation={} path={} -> partial entries read finished",
-                self.ctx.scheme,
-                self.op,
-                self.path
+        if !self.finished {
+            self.ctx.finished(
+                self.fields(self.op),
+                self.start,
+                format_args!("partial entries read finished"),
             );
         }
     }
@@ -1280,43 +932,7 @@ impl<P> Drop for LoggingLister<P> {
 impl<P: oio::List> oio::List for LoggingLister<P> {
     async fn next(&mut self) -> Result<Option<oio::Entry>> {
         let res = self.inner.next().await;
-
-        match &res {
-            Ok(Some(de)) => {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> listed entry: {}",
-                    self.ctx.scheme,
-                    self.op,
-                    self.path,
-                    de.path(),
-                );
-            }
-            Ok(None) => {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished",
-                    self.ctx.scheme,
-                    self.op,
-                    self.path
-                );
-                self.finished = true;
-            }
-            Err(err) => {
-                if let Some(lvl) = self.ctx.error_level(err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}",
-                        self.ctx.scheme,
-                        self.op,
-                        self.path,
-                        self.ctx.error_print(err)
-                    )
-                }
-            }
-        };
-
+        self.on_next(ListOperation::Next, &res);
         res
     }
 }
@@ -1324,43 +940,77 @@ impl<P: oio::List> oio::List for LoggingLister<P> {
 impl<P: oio::BlockingList> oio::BlockingList for LoggingLister<P> {
     fn next(&mut self) -> Result<Option<oio::Entry>> {
         let res = self.inner.next();
+        self.on_next(ListOperation::BlockingNext, &res);
+        res
+    }
+}
 
-        match &res {
-            Ok(Some(des)) => {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> listed entry: {}",
-                    self.ctx.scheme,
-                    self.op,
-                    self.path,
-                    des.path(),
-                );
-            }
-            Ok(None) => {
-                debug!(
-                    target: LOGGING_TARGET,
-                    "service={} operation={} path={} -> finished",
-                    self.ctx.scheme,
-                    self.op,
-                    self.path
-                );
-                self.finished = true;
-            }
-            Err(err) => {
-                if let Some(lvl) = self.ctx.error_level(err) {
-                    log!(
-                        target: LOGGING_TARGET,
-                        lvl,
-                        "service={} operation={} path={} -> {}",
-                        self.ctx.scheme,
-                        self.op,
-                        self.path,
-                        self.ctx.error_print(err)
-                    )
-                }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fields_display() {
+        let fields = LoggingFields::new(Scheme::Memory, Operation::Copy)
+            .with_from_to("a", "b")
+            .with_bytes(3);
+        assert_eq!(
+            fields.to_string(),
+            "service=memory operation=copy from=a to=b bytes=3"
+        );
+    }
+
+    #[test]
+    fn test_fields_key_values() {
+        struct Collect(Vec<(String, String)>);
+
+        impl<'kvs> VisitSource<'kvs> for Collect {
+            fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+                self.0.push((key.to_string(), value.to_string()));
+                Ok(())
             }
+        }
+
+        let fields = LoggingFields {
+            duration: Some(Duration::from_millis(1500)),
+            error_kind: Some(ErrorKind::NotFound),
+            ..LoggingFields::new(Scheme::Memory, Operation::Stat).with_path("a")
         };
+        let mut visitor = Collect(vec![]);
+        fields.visit(&mut visitor).unwrap();
+
+        let kvs: Vec<_> = visitor
+            .0
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_str()))
+            .collect();
+        assert_eq!(
+            kvs,
+            vec![
+                ("service", "memory"),
+                ("operation", "stat"),
+                ("path", "a"),
+                ("duration_ms", "1500"),
+                ("error_kind", "NotFound"),
+            ]
+        );
+    }
 
-        res
+    #[test]
+    fn test_slow_threshold() {
+        let layer = LoggingLayer::default()
+            .with_finished_level(Some("info"))
+            .unwrap()
+            .with_slow_threshold(Duration::from_secs(1));
+        let ctx = layer.layer(()).ctx;
+
+        assert_eq!(ctx.finished_level(Duration::from_millis(10)), None);
+        assert_eq!(
+            ctx.finished_level(Duration::from_secs(2)),
+            Some(Level::Info)
+        );
+        assert!(LoggingLayer::default()
+            .with_started_level(Some("loud"))
+            .is_err());
     }
 }
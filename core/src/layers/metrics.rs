@@ -521,6 +521,25 @@ impl<A: Access> LayeredAccess for MetricsAccessor<A> {
             .await
     }
 
+    async fn exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        self.handle.requests_total_stat.increment(1);
+
+        let start = Instant::now();
+
+        self.inner
+            .exists(path, args)
+            .inspect_ok(|_| {
+                let dur = start.elapsed().as_secs_f64();
+
+                self.handle.requests_duration_seconds_stat.record(dur);
+            })
+            .inspect_err(|e| {
+                self.handle
+                    .increment_errors_total(Operation::Stat, e.kind());
+            })
+            .await
+    }
+
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.handle.requests_total_delete.increment(1);
 
@@ -682,6 +701,24 @@ impl<A: Access> LayeredAccess for MetricsAccessor<A> {
         })
     }
 
+    fn blocking_exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        self.handle.requests_total_blocking_stat.increment(1);
+
+        let start = Instant::now();
+        let result = self.inner.blocking_exists(path, args);
+        let dur = start.elapsed().as_secs_f64();
+
+        self.handle
+            .requests_duration_seconds_blocking_stat
+            .record(dur);
+
+        result.map_err(|e| {
+            self.handle
+                .increment_errors_total(Operation::BlockingStat, e.kind());
+            e
+        })
+    }
+
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.handle.requests_total_blocking_delete.increment(1);
 
@@ -810,7 +847,7 @@ impl<R: oio::Write> oio::Write for MetricWrapper<R> {
         })
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         self.inner.close().await.map_err(|err| {
             self.handle.increment_errors_total(self.op, err.kind());
             err
@@ -833,7 +870,7 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for MetricWrapper<R> {
             })
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         self.inner.close().map_err(|err| {
             self.handle.increment_errors_total(self.op, err.kind());
             err
@@ -138,6 +138,15 @@ impl<A: Access> LayeredAccess for MimeGuessAccessor<A> {
             .map(|rp| rpstat_with_mime(path, rp))
     }
 
+    async fn exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        // Existence doesn't depend on the content type, skip the guessing.
+        self.inner().exists(path, args).await
+    }
+
+    fn blocking_exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        self.inner().blocking_exists(path, args)
+    }
+
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
         self.inner().read(path, args).await
     }
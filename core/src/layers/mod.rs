@@ -71,6 +71,7 @@ pub use self::prometheus_client::PrometheusClientLayer;
 mod retry;
 pub use self::retry::RetryInterceptor;
 pub use self::retry::RetryLayer;
+pub use self::retry::RetryPolicy;
 
 #[cfg(feature = "layers-tracing")]
 mod tracing;
@@ -90,6 +91,8 @@ pub use self::oteltrace::OtelTraceLayer;
 #[cfg(feature = "layers-throttle")]
 mod throttle;
 #[cfg(feature = "layers-throttle")]
+pub use self::throttle::ThrottleHandle;
+#[cfg(feature = "layers-throttle")]
 pub use self::throttle::ThrottleLayer;
 
 #[cfg(feature = "layers-await-tree")]
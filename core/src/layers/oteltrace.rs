@@ -141,6 +141,15 @@ impl<A: Access> LayeredAccess for OtelTraceAccessor<A> {
         self.inner().stat(path, args).with_context(cx).await
     }
 
+    async fn exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        let tracer = global::tracer("opendal");
+        let mut span = tracer.start("exists");
+        span.set_attribute(KeyValue::new("path", path.to_string()));
+        span.set_attribute(KeyValue::new("args", format!("{:?}", args)));
+        let cx = TraceContext::current_with_span(span);
+        self.inner().exists(path, args).with_context(cx).await
+    }
+
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         let tracer = global::tracer("opendal");
         let mut span = tracer.start("delete");
@@ -240,6 +249,16 @@ impl<A: Access> LayeredAccess for OtelTraceAccessor<A> {
         })
     }
 
+    fn blocking_exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        let tracer = global::tracer("opendal");
+        tracer.in_span("blocking_exists", |cx| {
+            let span = cx.span();
+            span.set_attribute(KeyValue::new("path", path.to_string()));
+            span.set_attribute(KeyValue::new("args", format!("{:?}", args)));
+            self.inner().blocking_exists(path, args)
+        })
+    }
+
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         let tracer = global::tracer("opendal");
         tracer.in_span("blocking_delete", |cx| {
@@ -293,7 +312,7 @@ impl<R: oio::Write> oio::Write for OtelTraceWrapper<R> {
         self.inner.abort()
     }
 
-    fn close(&mut self) -> impl Future<Output = Result<()>> + MaybeSend {
+    fn close(&mut self) -> impl Future<Output = Result<Metadata>> + MaybeSend {
         self.inner.close()
     }
 }
@@ -303,7 +322,7 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for OtelTraceWrapper<R> {
         self.inner.write(bs)
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         self.inner.close()
     }
 }
@@ -399,6 +399,27 @@ impl<A: Access> LayeredAccess for PrometheusAccessor<A> {
         })
     }
 
+    async fn exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        let labels = self.stats.generate_metric_label(
+            self.scheme.into_static(),
+            Operation::Stat.into_static(),
+            path,
+        );
+        self.stats.requests_total.with_label_values(&labels).inc();
+        let timer = self
+            .stats
+            .requests_duration_seconds
+            .with_label_values(&labels)
+            .start_timer();
+
+        let result = self.inner.exists(path, args).await;
+        timer.observe_duration();
+        result.map_err(|e| {
+            self.stats.increment_errors_total(Operation::Stat, e.kind());
+            e
+        })
+    }
+
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         let labels = self.stats.generate_metric_label(
             self.scheme.into_static(),
@@ -603,6 +624,28 @@ impl<A: Access> LayeredAccess for PrometheusAccessor<A> {
         })
     }
 
+    fn blocking_exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        let labels = self.stats.generate_metric_label(
+            self.scheme.into_static(),
+            Operation::BlockingStat.into_static(),
+            path,
+        );
+        self.stats.requests_total.with_label_values(&labels).inc();
+
+        let timer = self
+            .stats
+            .requests_duration_seconds
+            .with_label_values(&labels)
+            .start_timer();
+        let result = self.inner.blocking_exists(path, args);
+        timer.observe_duration();
+        result.map_err(|e| {
+            self.stats
+                .increment_errors_total(Operation::BlockingStat, e.kind());
+            e
+        })
+    }
+
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         let labels = self.stats.generate_metric_label(
             self.scheme.into_static(),
@@ -798,7 +841,7 @@ impl<R: oio::Write> oio::Write for PrometheusMetricWrapper<R> {
         }
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         let labels = self.stats.generate_metric_label(
             self.scheme.into_static(),
             WriteOperation::Close.into_static(),
@@ -814,7 +857,7 @@ impl<R: oio::Write> oio::Write for PrometheusMetricWrapper<R> {
         timer.observe_duration();
 
         match res {
-            Ok(()) => Ok(()),
+            Ok(meta) => Ok(meta),
             Err(err) => {
                 self.stats.increment_errors_total(self.op, err.kind());
                 Err(err)
@@ -856,7 +899,7 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for PrometheusMetricWrapper<R> {
         }
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         let labels = self.stats.generate_metric_label(
             self.scheme.into_static(),
             WriteOperation::BlockingClose.into_static(),
@@ -872,7 +915,7 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for PrometheusMetricWrapper<R> {
         timer.observe_duration();
 
         match res {
-            Ok(()) => Ok(()),
+            Ok(meta) => Ok(meta),
             Err(err) => {
                 self.stats.increment_errors_total(self.op, err.kind());
                 Err(err)
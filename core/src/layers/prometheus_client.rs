@@ -408,6 +408,25 @@ impl<A: Access> LayeredAccess for PrometheusAccessor<A> {
         })
     }
 
+    async fn exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        self.metrics
+            .increment_request_total(self.scheme, Operation::Stat.into_static());
+        let start_time = Instant::now();
+
+        let result = self.inner.exists(path, args).await;
+        self.metrics.observe_request_duration(
+            self.scheme,
+            Operation::Stat.into_static(),
+            start_time.elapsed(),
+        );
+
+        result.map_err(|e| {
+            self.metrics
+                .increment_errors_total(Operation::Stat.into_static(), e.kind());
+            e
+        })
+    }
+
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.metrics
             .increment_request_total(self.scheme, Operation::Delete.into_static());
@@ -558,6 +577,25 @@ impl<A: Access> LayeredAccess for PrometheusAccessor<A> {
         })
     }
 
+    fn blocking_exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        self.metrics
+            .increment_request_total(self.scheme, Operation::BlockingStat.into_static());
+        let start_time = Instant::now();
+
+        let result = self.inner.blocking_exists(path, args);
+        self.metrics.observe_request_duration(
+            self.scheme,
+            Operation::BlockingStat.into_static(),
+            start_time.elapsed(),
+        );
+
+        result.map_err(|e| {
+            self.metrics
+                .increment_errors_total(Operation::BlockingStat.into_static(), e.kind());
+            e
+        })
+    }
+
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.metrics
             .increment_request_total(self.scheme, Operation::BlockingDelete.into_static());
@@ -714,18 +752,19 @@ impl<R: oio::Write> oio::Write for PrometheusMetricWrapper<R> {
             })
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         let start = Instant::now();
 
         self.inner
             .close()
             .await
-            .map(|_| {
+            .map(|meta| {
                 self.metrics.observe_request_duration(
                     self.scheme,
                     WriteOperation::Close.into_static(),
                     start.elapsed(),
                 );
+                meta
             })
             .map_err(|err| {
                 self.metrics
@@ -763,17 +802,18 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for PrometheusMetricWrapper<R> {
             })
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         let start = Instant::now();
 
         self.inner
             .close()
-            .map(|_| {
+            .map(|meta| {
                 self.metrics.observe_request_duration(
                     self.scheme,
                     WriteOperation::BlockingClose.into_static(),
                     start.elapsed(),
                 );
+                meta
             })
             .map_err(|err| {
                 self.metrics.increment_errors_total(
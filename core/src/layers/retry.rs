@@ -15,12 +15,16 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::time::Duration;
 
+use backon::BackoffBuilder;
 use backon::BlockingRetryable;
+use backon::ExponentialBackoff;
 use backon::ExponentialBuilder;
 use backon::Retryable;
 use futures::FutureExt;
@@ -37,6 +41,12 @@ use crate::*;
 /// returns true. If operation still failed, this layer will set error to
 /// `Persistent` which means error has been retried.
 ///
+/// Interrupted reads are resumed from the offset that has been delivered
+/// instead of the beginning. If the service returns the etag and supports
+/// `read_with_if_match`, resumed reads are pinned to that etag, so a file
+/// modified while reading fails with `ConditionNotMatch` instead of mixing
+/// two versions.
+///
 /// # Panics
 ///
 /// While retrying `Reader` or `Writer` operations, please make sure either:
@@ -46,6 +56,13 @@ use crate::*;
 ///
 /// Otherwise, `RetryLayer` could panic while hitting in bad states.
 ///
+/// # Retry-After
+///
+/// If the error carries a delay suggested by service (see [`Error::retry_after`]),
+/// for example from the `Retry-After` header of a `429 Too Many Requests`
+/// response, this layer will sleep for that delay instead of the computed
+/// backoff. The suggested delay is still limited by `max_delay`.
+///
 /// For example, while composing `RetryLayer` with `TimeoutLayer`. The order of layer is sensitive.
 ///
 /// ```no_build
@@ -103,15 +120,44 @@ use crate::*;
 ///     .layer(RetryLayer::new().with_notify(MyRetryInterceptor))
 ///     .finish();
 /// ```
+///
+/// ## Customize retry policy for error kinds
+///
+/// Errors of different kinds can be retried with their own [`RetryPolicy`].
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use opendal::layers::RetryLayer;
+/// use opendal::layers::RetryPolicy;
+/// use opendal::services;
+/// use opendal::ErrorKind;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(
+///         RetryLayer::new()
+///             .with_policy(ErrorKind::ConditionNotMatch, RetryPolicy::no_retry())
+///             .with_policy(
+///                 ErrorKind::RateLimited,
+///                 RetryPolicy::new()
+///                     .with_min_delay(Duration::from_secs(1))
+///                     .with_max_delay(Duration::from_secs(300))
+///                     .with_max_times(10),
+///             ),
+///     )
+///     .finish();
+/// ```
 pub struct RetryLayer<I = DefaultRetryInterceptor> {
-    builder: ExponentialBuilder,
+    policies: RetryPolicies,
     notify: Arc<I>,
 }
 
 impl<I> Clone for RetryLayer<I> {
     fn clone(&self) -> Self {
         Self {
-            builder: self.builder.clone(),
+            policies: self.policies.clone(),
             notify: self.notify.clone(),
         }
     }
@@ -120,7 +166,7 @@ impl<I> Clone for RetryLayer<I> {
 impl Default for RetryLayer {
     fn default() -> Self {
         Self {
-            builder: ExponentialBuilder::default(),
+            policies: RetryPolicies::default(),
             notify: Arc::new(DefaultRetryInterceptor),
         }
     }
@@ -173,11 +219,97 @@ impl RetryLayer {
     /// ```
     pub fn with_notify<I: RetryInterceptor>(self, notify: I) -> RetryLayer<I> {
         RetryLayer {
-            builder: self.builder,
+            policies: self.policies,
             notify: Arc::new(notify),
         }
     }
 
+    /// Set jitter of current backoff.
+    ///
+    /// If jitter is enabled, ExponentialBackoff will add a random jitter in `[0, min_delay)
+    /// to current delay.
+    pub fn with_jitter(mut self) -> Self {
+        self.policies.default = self.policies.default.with_jitter();
+        self
+    }
+
+    /// Set factor of current backoff.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if input factor smaller than `1.0`.
+    pub fn with_factor(mut self, factor: f32) -> Self {
+        self.policies.default = self.policies.default.with_factor(factor);
+        self
+    }
+
+    /// Set min_delay of current backoff.
+    pub fn with_min_delay(mut self, min_delay: Duration) -> Self {
+        self.policies.default = self.policies.default.with_min_delay(min_delay);
+        self
+    }
+
+    /// Set max_delay of current backoff.
+    ///
+    /// Delay will not increasing if current delay is larger than max_delay.
+    pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
+        self.policies.default = self.policies.default.with_max_delay(max_delay);
+        self
+    }
+
+    /// Set max_times of current backoff.
+    ///
+    /// Backoff will return `None` if max times is reaching.
+    pub fn with_max_times(mut self, max_times: usize) -> Self {
+        self.policies.default = self.policies.default.with_max_times(max_times);
+        self
+    }
+
+    /// Set the retry policy for errors of given kind.
+    ///
+    /// Errors without a specific policy will be retried by the backoff
+    /// configured on this layer.
+    pub fn with_policy(mut self, kind: ErrorKind, policy: RetryPolicy) -> Self {
+        self.policies.kinds.insert(kind, policy);
+        self
+    }
+}
+
+/// RetryPolicy controls how errors of a specific [`ErrorKind`] will be retried.
+///
+/// Only temporary errors will be retried, no matter what policy is used.
+#[derive(Clone, Debug)]
+pub struct RetryPolicy {
+    builder: ExponentialBuilder,
+    max_delay: Duration,
+    retry: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            builder: ExponentialBuilder::default(),
+            // Keep the same with the default max_delay of `ExponentialBuilder`.
+            max_delay: Duration::from_secs(60),
+            retry: true,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Create a new retry policy with default exponential backoff.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a retry policy that never retries.
+    pub fn no_retry() -> Self {
+        Self {
+            retry: false,
+            ..Self::default()
+        }
+    }
+
     /// Set jitter of current backoff.
     ///
     /// If jitter is enabled, ExponentialBackoff will add a random jitter in `[0, min_delay)
@@ -206,8 +338,10 @@ impl RetryLayer {
     /// Set max_delay of current backoff.
     ///
     /// Delay will not increasing if current delay is larger than max_delay.
+    /// Delay suggested by service will also be limited by max_delay.
     pub fn with_max_delay(mut self, max_delay: Duration) -> Self {
         self.builder = self.builder.with_max_delay(max_delay);
+        self.max_delay = max_delay;
         self
     }
 
@@ -220,13 +354,93 @@ impl RetryLayer {
     }
 }
 
+#[derive(Clone, Debug, Default)]
+struct RetryPolicies {
+    default: RetryPolicy,
+    kinds: HashMap<ErrorKind, RetryPolicy>,
+}
+
+impl RetryPolicies {
+    fn policy(&self, kind: ErrorKind) -> &RetryPolicy {
+        self.kinds.get(&kind).unwrap_or(&self.default)
+    }
+
+    /// Create a backoff for one retry call.
+    fn backoff(self: &Arc<Self>) -> RetryBackoff {
+        RetryBackoff {
+            policies: self.clone(),
+            state: Arc::default(),
+        }
+    }
+}
+
+/// RetryBackoff is the backoff used by a single retry call.
+///
+/// `backon` decides whether to retry an error before asking backoff for the
+/// next delay, so we record the last retryable error in `should_retry` and
+/// pick the delay based on it in `next`.
+#[derive(Clone, Debug)]
+struct RetryBackoff {
+    policies: Arc<RetryPolicies>,
+    state: Arc<Mutex<RetryBackoffState>>,
+}
+
+#[derive(Debug, Default)]
+struct RetryBackoffState {
+    last: Option<(ErrorKind, Option<Duration>)>,
+    /// Backoffs of error kinds, `None` is used for the default policy.
+    backoffs: HashMap<Option<ErrorKind>, ExponentialBackoff>,
+}
+
+impl RetryBackoff {
+    fn should_retry(&self, err: &Error) -> bool {
+        if !err.is_temporary() || !self.policies.policy(err.kind()).retry {
+            return false;
+        }
+
+        let mut state = self.state.lock().expect("lock must succeed");
+        state.last = Some((err.kind(), err.retry_after()));
+        true
+    }
+}
+
+impl BackoffBuilder for RetryBackoff {
+    type Backoff = Self;
+
+    fn build(&self) -> Self::Backoff {
+        self.clone()
+    }
+}
+
+impl Iterator for RetryBackoff {
+    type Item = Duration;
+
+    fn next(&mut self) -> Option<Duration> {
+        let mut state = self.state.lock().expect("lock must succeed");
+        let (kind, retry_after) = state.last.take()?;
+
+        let key = self.policies.kinds.contains_key(&kind).then_some(kind);
+        let policy = self.policies.policy(kind);
+        let delay = state
+            .backoffs
+            .entry(key)
+            .or_insert_with(|| policy.builder.build())
+            .next()?;
+
+        match retry_after {
+            Some(retry_after) => Some(retry_after.min(policy.max_delay)),
+            None => Some(delay),
+        }
+    }
+}
+
 impl<A: Access, I: RetryInterceptor> Layer<A> for RetryLayer<I> {
     type LayeredAccess = RetryAccessor<A, I>;
 
     fn layer(&self, inner: A) -> Self::LayeredAccess {
         RetryAccessor {
             inner: Arc::new(inner),
-            builder: self.builder.clone(),
+            policies: Arc::new(self.policies.clone()),
             notify: self.notify.clone(),
         }
     }
@@ -266,7 +480,7 @@ impl RetryInterceptor for DefaultRetryInterceptor {
 
 pub struct RetryAccessor<A: Access, I: RetryInterceptor> {
     inner: Arc<A>,
-    builder: ExponentialBuilder,
+    policies: Arc<RetryPolicies>,
     notify: Arc<I>,
 }
 
@@ -278,6 +492,25 @@ impl<A: Access, I: RetryInterceptor> Debug for RetryAccessor<A, I> {
     }
 }
 
+impl<A: Access, I: RetryInterceptor> RetryAccessor<A, I> {
+    /// Build the args used to resume interrupted reads.
+    ///
+    /// The etag of the file is pinned via `if_match` if possible, so that a
+    /// file modified while reading fails with `ConditionNotMatch` instead of
+    /// splicing two versions together.
+    fn resume_args(&self, rp: &RpRead, args: OpRead) -> OpRead {
+        match rp.etag() {
+            Some(etag)
+                if args.if_match().is_none()
+                    && self.inner.info().full_capability().read_with_if_match =>
+            {
+                args.with_if_match(etag)
+            }
+            _ => args,
+        }
+    }
+}
+
 impl<A: Access, I: RetryInterceptor> LayeredAccess for RetryAccessor<A, I> {
     type Inner = A;
     type Reader = RetryWrapper<RetryReader<A, A::Reader>, I>;
@@ -292,39 +525,43 @@ impl<A: Access, I: RetryInterceptor> LayeredAccess for RetryAccessor<A, I> {
     }
 
     async fn create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let backoff = self.policies.backoff();
         { || self.inner.create_dir(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e))
             .notify(|err, dur: Duration| self.notify.intercept(err, dur))
             .map(|v| v.map_err(|e| e.set_persistent()))
             .await
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+        let backoff = self.policies.backoff();
         let (rp, reader) = { || self.inner.read(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .await
             .map_err(|e| e.set_persistent())?;
 
+        let args = self.resume_args(&rp, args);
         let retry_reader = RetryReader::new(self.inner.clone(), path.to_string(), args, reader);
         let retry_wrapper =
-            RetryWrapper::new(retry_reader, self.notify.clone(), self.builder.clone());
+            RetryWrapper::new(retry_reader, self.notify.clone(), self.policies.clone());
 
         Ok((rp, retry_wrapper))
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        let backoff = self.policies.backoff();
         { || self.inner.write(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .map(|v| {
                 v.map(|(rp, r)| {
                     (
                         rp,
-                        RetryWrapper::new(r, self.notify.clone(), self.builder.clone()),
+                        RetryWrapper::new(r, self.notify.clone(), self.policies.clone()),
                     )
                 })
                 .map_err(|e| e.set_persistent())
@@ -333,49 +570,64 @@ impl<A: Access, I: RetryInterceptor> LayeredAccess for RetryAccessor<A, I> {
     }
 
     async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let backoff = self.policies.backoff();
         { || self.inner.stat(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e))
+            .notify(|err, dur| self.notify.intercept(err, dur))
+            .map(|v| v.map_err(|e| e.set_persistent()))
+            .await
+    }
+
+    async fn exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        let backoff = self.policies.backoff();
+        { || self.inner.exists(path, args.clone()) }
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .map(|v| v.map_err(|e| e.set_persistent()))
             .await
     }
 
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let backoff = self.policies.backoff();
         { || self.inner.delete(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .map(|v| v.map_err(|e| e.set_persistent()))
             .await
     }
 
     async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let backoff = self.policies.backoff();
         { || self.inner.copy(from, to, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .map(|v| v.map_err(|e| e.set_persistent()))
             .await
     }
 
     async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let backoff = self.policies.backoff();
         { || self.inner.rename(from, to, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .map(|v| v.map_err(|e| e.set_persistent()))
             .await
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let backoff = self.policies.backoff();
         { || self.inner.list(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .map(|v| {
                 v.map(|(l, p)| {
-                    let lister = RetryWrapper::new(p, self.notify.clone(), self.builder.clone());
+                    let lister = RetryWrapper::new(p, self.notify.clone(), self.policies.clone());
                     (l, lister)
                 })
                 .map_err(|e| e.set_persistent())
@@ -384,6 +636,7 @@ impl<A: Access, I: RetryInterceptor> LayeredAccess for RetryAccessor<A, I> {
     }
 
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let backoff = self.policies.backoff();
         {
             || async {
                 let rp = self.inner.batch(args.clone()).await?;
@@ -395,96 +648,115 @@ impl<A: Access, I: RetryInterceptor> LayeredAccess for RetryAccessor<A, I> {
                 Ok(RpBatch::new(nrp))
             }
         }
-        .retry(&self.builder)
-        .when(|e: &Error| e.is_temporary())
+        .retry(&backoff)
+        .when(|e: &Error| backoff.should_retry(e))
         .notify(|err, dur| self.notify.intercept(err, dur))
         .await
         .map_err(|e| e.set_persistent())
     }
 
     fn blocking_create_dir(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir> {
+        let backoff = self.policies.backoff();
         { || self.inner.blocking_create_dir(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .call()
             .map_err(|e| e.set_persistent())
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
+        let backoff = self.policies.backoff();
         let (rp, reader) = { || self.inner.blocking_read(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .call()
             .map_err(|e| e.set_persistent())?;
 
+        let args = self.resume_args(&rp, args);
         let retry_reader = RetryReader::new(self.inner.clone(), path.to_string(), args, reader);
         let retry_wrapper =
-            RetryWrapper::new(retry_reader, self.notify.clone(), self.builder.clone());
+            RetryWrapper::new(retry_reader, self.notify.clone(), self.policies.clone());
 
         Ok((rp, retry_wrapper))
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
+        let backoff = self.policies.backoff();
         { || self.inner.blocking_write(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .call()
             .map(|(rp, r)| {
                 (
                     rp,
-                    RetryWrapper::new(r, self.notify.clone(), self.builder.clone()),
+                    RetryWrapper::new(r, self.notify.clone(), self.policies.clone()),
                 )
             })
             .map_err(|e| e.set_persistent())
     }
 
     fn blocking_stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+        let backoff = self.policies.backoff();
         { || self.inner.blocking_stat(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e))
+            .notify(|err, dur| self.notify.intercept(err, dur))
+            .call()
+            .map_err(|e| e.set_persistent())
+    }
+
+    fn blocking_exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        let backoff = self.policies.backoff();
+        { || self.inner.blocking_exists(path, args.clone()) }
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .call()
             .map_err(|e| e.set_persistent())
     }
 
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        let backoff = self.policies.backoff();
         { || self.inner.blocking_delete(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .call()
             .map_err(|e| e.set_persistent())
     }
 
     fn blocking_copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let backoff = self.policies.backoff();
         { || self.inner.blocking_copy(from, to, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .call()
             .map_err(|e| e.set_persistent())
     }
 
     fn blocking_rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let backoff = self.policies.backoff();
         { || self.inner.blocking_rename(from, to, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .call()
             .map_err(|e| e.set_persistent())
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        let backoff = self.policies.backoff();
         { || self.inner.blocking_list(path, args.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e))
             .notify(|err, dur| self.notify.intercept(err, dur))
             .call()
             .map(|(rp, p)| {
-                let p = RetryWrapper::new(p, self.notify.clone(), self.builder.clone());
+                let p = RetryWrapper::new(p, self.notify.clone(), self.policies.clone());
                 (rp, p)
             })
             .map_err(|e| e.set_persistent())
@@ -509,6 +781,16 @@ impl<A, R> RetryReader<A, R> {
             args,
         }
     }
+
+    fn resume_error(&self, err: Error) -> Error {
+        let err = err.with_context("resume_offset", self.args.range().offset().to_string());
+        match self.args.if_match() {
+            Some(etag) if err.kind() == ErrorKind::ConditionNotMatch => err
+                .with_context("if_match", etag)
+                .with_context("reason", "file has been modified while reading"),
+            _ => err,
+        }
+    }
 }
 
 impl<A: Access> oio::Read for RetryReader<A, A::Reader> {
@@ -516,7 +798,11 @@ impl<A: Access> oio::Read for RetryReader<A, A::Reader> {
         loop {
             match self.reader.take() {
                 None => {
-                    let (_, r) = self.inner.read(&self.path, self.args.clone()).await?;
+                    let (_, r) = self
+                        .inner
+                        .read(&self.path, self.args.clone())
+                        .await
+                        .map_err(|err| self.resume_error(err))?;
                     self.reader = Some(r);
                     continue;
                 }
@@ -536,7 +822,10 @@ impl<A: Access> oio::BlockingRead for RetryReader<A, A::BlockingReader> {
         loop {
             match self.reader.take() {
                 None => {
-                    let (_, r) = self.inner.blocking_read(&self.path, self.args.clone())?;
+                    let (_, r) = self
+                        .inner
+                        .blocking_read(&self.path, self.args.clone())
+                        .map_err(|err| self.resume_error(err))?;
                     self.reader = Some(r);
                     continue;
                 }
@@ -555,15 +844,15 @@ pub struct RetryWrapper<R, I> {
     inner: Option<R>,
     notify: Arc<I>,
 
-    builder: ExponentialBuilder,
+    policies: Arc<RetryPolicies>,
 }
 
 impl<R, I> RetryWrapper<R, I> {
-    fn new(inner: R, notify: Arc<I>, backoff: ExponentialBuilder) -> Self {
+    fn new(inner: R, notify: Arc<I>, policies: Arc<RetryPolicies>) -> Self {
         Self {
             inner: Some(inner),
             notify,
-            builder: backoff,
+            policies,
         }
     }
 
@@ -582,6 +871,7 @@ impl<R: oio::Read, I: RetryInterceptor> oio::Read for RetryWrapper<R, I> {
         use backon::RetryableWithContext;
 
         let inner = self.take_inner()?;
+        let backoff = self.policies.backoff();
 
         let (inner, res) = {
             |mut r: R| async move {
@@ -590,8 +880,8 @@ impl<R: oio::Read, I: RetryInterceptor> oio::Read for RetryWrapper<R, I> {
                 (r, res)
             }
         }
-        .retry(&self.builder)
-        .when(|e| e.is_temporary())
+        .retry(&backoff)
+        .when(|e| backoff.should_retry(e))
         .context(inner)
         .notify(|err, dur| self.notify.intercept(err, dur))
         .await;
@@ -606,6 +896,7 @@ impl<R: oio::BlockingRead, I: RetryInterceptor> oio::BlockingRead for RetryWrapp
         use backon::BlockingRetryableWithContext;
 
         let inner = self.take_inner()?;
+        let backoff = self.policies.backoff();
 
         let (inner, res) = {
             |mut r: R| {
@@ -614,8 +905,8 @@ impl<R: oio::BlockingRead, I: RetryInterceptor> oio::BlockingRead for RetryWrapp
                 (r, res)
             }
         }
-        .retry(&self.builder)
-        .when(|e| e.is_temporary())
+        .retry(&backoff)
+        .when(|e| backoff.should_retry(e))
         .context(inner)
         .notify(|err, dur| self.notify.intercept(err, dur))
         .call();
@@ -630,6 +921,7 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
         use backon::RetryableWithContext;
 
         let inner = self.take_inner()?;
+        let backoff = self.policies.backoff();
 
         let ((inner, _), res) = {
             |(mut r, bs): (R, Buffer)| async move {
@@ -638,8 +930,8 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
                 ((r, bs), res)
             }
         }
-        .retry(&self.builder)
-        .when(|e| e.is_temporary())
+        .retry(&backoff)
+        .when(|e| backoff.should_retry(e))
         .context((inner, bs))
         .notify(|err, dur| self.notify.intercept(err, dur))
         .await;
@@ -652,6 +944,7 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
         use backon::RetryableWithContext;
 
         let inner = self.take_inner()?;
+        let backoff = self.policies.backoff();
 
         let (inner, res) = {
             |mut r: R| async move {
@@ -660,8 +953,8 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
                 (r, res)
             }
         }
-        .retry(&self.builder)
-        .when(|e| e.is_temporary())
+        .retry(&backoff)
+        .when(|e| backoff.should_retry(e))
         .context(inner)
         .notify(|err, dur| self.notify.intercept(err, dur))
         .await;
@@ -670,10 +963,11 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
         res.map_err(|err| err.set_persistent())
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         use backon::RetryableWithContext;
 
         let inner = self.take_inner()?;
+        let backoff = self.policies.backoff();
 
         let (inner, res) = {
             |mut r: R| async move {
@@ -682,8 +976,8 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
                 (r, res)
             }
         }
-        .retry(&self.builder)
-        .when(|e| e.is_temporary())
+        .retry(&backoff)
+        .when(|e| backoff.should_retry(e))
         .context(inner)
         .notify(|err, dur| self.notify.intercept(err, dur))
         .await;
@@ -695,9 +989,10 @@ impl<R: oio::Write, I: RetryInterceptor> oio::Write for RetryWrapper<R, I> {
 
 impl<R: oio::BlockingWrite, I: RetryInterceptor> oio::BlockingWrite for RetryWrapper<R, I> {
     fn write(&mut self, bs: Buffer) -> Result<()> {
+        let backoff = self.policies.backoff();
         { || self.inner.as_mut().unwrap().write(bs.clone()) }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e))
             .notify(|err, dur| {
                 self.notify.intercept(err, dur);
             })
@@ -705,10 +1000,11 @@ impl<R: oio::BlockingWrite, I: RetryInterceptor> oio::BlockingWrite for RetryWra
             .map_err(|e| e.set_persistent())
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
+        let backoff = self.policies.backoff();
         { || self.inner.as_mut().unwrap().close() }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e))
             .notify(|err, dur| {
                 self.notify.intercept(err, dur);
             })
@@ -722,6 +1018,7 @@ impl<P: oio::List, I: RetryInterceptor> oio::List for RetryWrapper<P, I> {
         use backon::RetryableWithContext;
 
         let inner = self.take_inner()?;
+        let backoff = self.policies.backoff();
 
         let (inner, res) = {
             |mut p: P| async move {
@@ -730,8 +1027,8 @@ impl<P: oio::List, I: RetryInterceptor> oio::List for RetryWrapper<P, I> {
                 (p, res)
             }
         }
-        .retry(&self.builder)
-        .when(|e| e.is_temporary())
+        .retry(&backoff)
+        .when(|e| backoff.should_retry(e))
         .context(inner)
         .notify(|err, dur| self.notify.intercept(err, dur))
         .await;
@@ -743,9 +1040,10 @@ impl<P: oio::List, I: RetryInterceptor> oio::List for RetryWrapper<P, I> {
 
 impl<P: oio::BlockingList, I: RetryInterceptor> oio::BlockingList for RetryWrapper<P, I> {
     fn next(&mut self) -> Result<Option<oio::Entry>> {
+        let backoff = self.policies.backoff();
         { || self.inner.as_mut().unwrap().next() }
-            .retry(&self.builder)
-            .when(|e| e.is_temporary())
+            .retry(&backoff)
+            .when(|e| backoff.should_retry(e))
             .notify(|err, dur| {
                 self.notify.intercept(err, dur);
             })
@@ -769,6 +1067,7 @@ mod tests {
     #[derive(Default, Clone)]
     struct MockBuilder {
         attempt: Arc<Mutex<usize>>,
+        reads: Arc<Mutex<Vec<OpRead>>>,
     }
 
     impl Builder for MockBuilder {
@@ -778,6 +1077,7 @@ mod tests {
         fn build(self) -> Result<impl Access> {
             Ok(MockService {
                 attempt: self.attempt.clone(),
+                reads: self.reads.clone(),
             })
         }
     }
@@ -785,6 +1085,7 @@ mod tests {
     #[derive(Debug, Clone, Default)]
     struct MockService {
         attempt: Arc<Mutex<usize>>,
+        reads: Arc<Mutex<Vec<OpRead>>>,
     }
 
     impl Access for MockService {
@@ -799,11 +1100,13 @@ mod tests {
             let mut am = AccessorInfo::default();
             am.set_native_capability(Capability {
                 read: true,
+                read_with_if_match: true,
                 write: true,
                 write_can_multi: true,
                 stat: true,
                 list: true,
                 list_with_recursive: true,
+                delete: true,
                 batch: true,
                 ..Default::default()
             });
@@ -818,8 +1121,9 @@ mod tests {
         }
 
         async fn read(&self, _: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            self.reads.lock().unwrap().push(args.clone());
             Ok((
-                RpRead::new(),
+                RpRead::new().with_etag(Some("\"etag\"".to_string())),
                 MockReader {
                     buf: Bytes::from("Hello, World!").into(),
                     range: args.range(),
@@ -837,6 +1141,32 @@ mod tests {
             Ok((RpList::default(), lister))
         }
 
+        async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
+            let mut attempt = self.attempt.lock().unwrap();
+            *attempt += 1;
+
+            match (path, *attempt) {
+                ("condition_not_match", _) => Err(Error::new(
+                    ErrorKind::ConditionNotMatch,
+                    "retryable condition not match error",
+                )
+                .set_temporary()),
+                ("rate_limited", 1) => Err(Error::new(
+                    ErrorKind::RateLimited,
+                    "retryable rate limited error",
+                )
+                .with_retry_after(Duration::from_millis(20))
+                .set_temporary()),
+                ("rate_limited", 2) => Err(Error::new(
+                    ErrorKind::RateLimited,
+                    "retryable rate limited error",
+                )
+                .with_retry_after(Duration::from_secs(3600))
+                .set_temporary()),
+                _ => Ok(RpDelete::default()),
+            }
+        }
+
         async fn batch(&self, op: OpBatch) -> Result<RpBatch> {
             let mut attempt = self.attempt.lock().unwrap();
             *attempt += 1;
@@ -937,7 +1267,7 @@ mod tests {
             Ok(())
         }
 
-        async fn close(&mut self) -> Result<()> {
+        async fn close(&mut self) -> Result<Metadata> {
             Err(Error::new(ErrorKind::Unexpected, "always close failed").set_temporary())
         }
 
@@ -1012,6 +1342,23 @@ mod tests {
         assert_eq!(content, "Hello, World!".as_bytes());
         // The error is retryable, we should request it 3 times.
         assert_eq!(*builder.attempt.lock().unwrap(), 5);
+
+        // Interrupted reads are resumed from the delivered offset with the
+        // etag pinned.
+        let reads = builder.reads.lock().unwrap();
+        let resumed: Vec<_> = reads
+            .iter()
+            .map(|v| (v.range().offset(), v.if_match()))
+            .collect();
+        assert_eq!(
+            resumed,
+            vec![
+                (0, None),
+                (0, Some("\"etag\"")),
+                (0, Some("\"etag\"")),
+                (13, Some("\"etag\"")),
+            ]
+        );
     }
 
     /// This test is used to reproduce the panic issue while composing retry layer with timeout layer.
@@ -1095,4 +1442,66 @@ mod tests {
         op.remove(paths).await.expect("batch must succeed");
         assert_eq!(*builder.attempt.lock().unwrap(), 5);
     }
+
+    #[derive(Default, Clone)]
+    struct MockRetryInterceptor {
+        durations: Arc<Mutex<Vec<Duration>>>,
+    }
+
+    impl RetryInterceptor for MockRetryInterceptor {
+        fn intercept(&self, _: &Error, dur: Duration) {
+            self.durations.lock().unwrap().push(dur);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_retry_policy_no_retry() {
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+        let builder = MockBuilder::default();
+        let op = Operator::new(builder.clone())
+            .unwrap()
+            .layer(
+                RetryLayer::new()
+                    .with_min_delay(Duration::from_millis(1))
+                    .with_policy(ErrorKind::ConditionNotMatch, RetryPolicy::no_retry()),
+            )
+            .finish();
+
+        let err = op
+            .delete("condition_not_match")
+            .await
+            .expect_err("delete must fail");
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+        assert_eq!(*builder.attempt.lock().unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_after() {
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+        let builder = MockBuilder::default();
+        let interceptor = MockRetryInterceptor::default();
+        let op = Operator::new(builder.clone())
+            .unwrap()
+            .layer(
+                RetryLayer::new()
+                    .with_policy(
+                        ErrorKind::RateLimited,
+                        RetryPolicy::new().with_max_delay(Duration::from_millis(50)),
+                    )
+                    .with_notify(interceptor.clone()),
+            )
+            .finish();
+
+        op.delete("rate_limited")
+            .await
+            .expect("delete must succeed");
+        assert_eq!(*builder.attempt.lock().unwrap(), 3);
+        // Retry-After is preferred but limited by max_delay of the policy.
+        assert_eq!(
+            *interceptor.durations.lock().unwrap(),
+            vec![Duration::from_millis(20), Duration::from_millis(50)]
+        );
+    }
 }
@@ -17,7 +17,9 @@
 
 use std::num::NonZeroU32;
 use std::sync::Arc;
+use std::sync::RwLock;
 use std::thread;
+use std::time::Duration;
 
 use governor::clock::Clock;
 use governor::clock::DefaultClock;
@@ -39,10 +41,15 @@ use crate::*;
 /// [Governor](https://docs.rs/governor/latest/governor/index.html).
 /// By setting the `bandwidth` and `burst`, we can control the byte flow rate of underlying services.
 ///
+/// Reads and writes are throttled by separate budgets. Bytes are counted while streaming through
+/// `Reader` and `Writer`, a buffer larger than `burst` will be let through in several `burst`
+/// sized steps, so a single huge object can't exceed the bandwidth.
+///
 /// # Note
 ///
-/// When setting the ThrottleLayer, always consider the largest possible operation size as the burst size,
-/// as **the burst size should be larger than any possible byte length to allow it to pass through**.
+/// All operators built with the same `ThrottleLayer` (and its clones) share the same budgets.
+/// Limits can be changed at runtime via [`ThrottleLayer::handle`], the budget restarts full
+/// after a change.
 ///
 /// Read more about [Quota](https://docs.rs/governor/latest/governor/struct.Quota.html#examples)
 ///
@@ -62,24 +69,69 @@ use crate::*;
 ///     .layer(ThrottleLayer::new(10 * 1024, 10000 * 1024))
 ///     .finish();
 /// ```
-#[derive(Clone)]
+///
+/// This example limits uploads to 1 MiB/s and downloads to 8 MiB/s, and lifts the upload limit
+/// to 64 MiB/s later.
+///
+/// ```no_build
+/// use opendal::layers::ThrottleLayer;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let layer = ThrottleLayer::default()
+///     .with_write_bandwidth(1024 * 1024, 1024 * 1024)
+///     .with_read_bandwidth(8 * 1024 * 1024, 8 * 1024 * 1024);
+/// let handle = layer.handle();
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(layer)
+///     .finish();
+///
+/// // For example, in the nightly backup window.
+/// handle.set_write_bandwidth(64 * 1024 * 1024, 64 * 1024 * 1024);
+/// ```
+#[derive(Clone, Default)]
 pub struct ThrottleLayer {
-    bandwidth: NonZeroU32,
-    burst: NonZeroU32,
+    handle: ThrottleHandle,
 }
 
 impl ThrottleLayer {
-    /// Create a new `ThrottleLayer` with given bandwidth and burst.
+    /// Create a new `ThrottleLayer` with given bandwidth and burst for both reads and writes.
     ///
     /// - bandwidth: the maximum number of bytes allowed to pass through per second.
     /// - burst: the maximum number of bytes allowed to pass through at once.
+    ///
+    /// Use `ThrottleLayer::default()` to start without any limits.
     pub fn new(bandwidth: u32, burst: u32) -> Self {
-        assert!(bandwidth > 0);
-        assert!(burst > 0);
-        Self {
-            bandwidth: NonZeroU32::new(bandwidth).unwrap(),
-            burst: NonZeroU32::new(burst).unwrap(),
-        }
+        Self::default()
+            .with_read_bandwidth(bandwidth, burst)
+            .with_write_bandwidth(bandwidth, burst)
+    }
+
+    /// Set the bandwidth and burst of reads.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if bandwidth or burst is 0.
+    pub fn with_read_bandwidth(self, bandwidth: u32, burst: u32) -> Self {
+        self.handle.set_read_bandwidth(bandwidth, burst);
+        self
+    }
+
+    /// Set the bandwidth and burst of writes.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if bandwidth or burst is 0.
+    pub fn with_write_bandwidth(self, bandwidth: u32, burst: u32) -> Self {
+        self.handle.set_write_bandwidth(bandwidth, burst);
+        self
+    }
+
+    /// Get the handle to change the limits of this layer at runtime.
+    pub fn handle(&self) -> ThrottleHandle {
+        self.handle.clone()
     }
 }
 
@@ -87,25 +139,127 @@ impl<A: Access> Layer<A> for ThrottleLayer {
     type LayeredAccess = ThrottleAccessor<A>;
 
     fn layer(&self, accessor: A) -> Self::LayeredAccess {
-        let rate_limiter = Arc::new(RateLimiter::direct(
-            Quota::per_second(self.bandwidth).allow_burst(self.burst),
-        ));
         ThrottleAccessor {
             inner: accessor,
-            rate_limiter,
+            handle: self.handle.clone(),
         }
     }
 }
 
-/// Share an atomic RateLimiter instance across all threads in one operator.
+/// Handle to change the limits of a [`ThrottleLayer`] at runtime.
+///
+/// Changes take effect on all readers and writers, including the ones
+/// already created.
+#[derive(Debug, Clone, Default)]
+pub struct ThrottleHandle {
+    read: Arc<ThrottleBucket>,
+    write: Arc<ThrottleBucket>,
+}
+
+impl ThrottleHandle {
+    /// Set the bandwidth and burst of reads.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if bandwidth or burst is 0.
+    pub fn set_read_bandwidth(&self, bandwidth: u32, burst: u32) {
+        self.read.set(bandwidth, burst)
+    }
+
+    /// Set the bandwidth and burst of writes.
+    ///
+    /// # Panics
+    ///
+    /// This function will panic if bandwidth or burst is 0.
+    pub fn set_write_bandwidth(&self, bandwidth: u32, burst: u32) {
+        self.write.set(bandwidth, burst)
+    }
+
+    /// Remove the limit of reads.
+    pub fn unset_read_bandwidth(&self) {
+        self.read.unset()
+    }
+
+    /// Remove the limit of writes.
+    pub fn unset_write_bandwidth(&self) {
+        self.write.unset()
+    }
+}
+
 /// If want to add more observability in the future, replace the default NoOpMiddleware with other middleware types.
 /// Read more about [Middleware](https://docs.rs/governor/latest/governor/middleware/index.html)
 type SharedRateLimiter = Arc<RateLimiter<NotKeyed, InMemoryState, DefaultClock, NoOpMiddleware>>;
 
+/// A replaceable rate limiter, no limit will be applied if it's not set.
+#[derive(Debug, Default)]
+struct ThrottleBucket {
+    limiter: RwLock<Option<(SharedRateLimiter, NonZeroU32)>>,
+}
+
+impl ThrottleBucket {
+    fn set(&self, bandwidth: u32, burst: u32) {
+        let bandwidth = NonZeroU32::new(bandwidth).expect("bandwidth must be greater than 0");
+        let burst = NonZeroU32::new(burst).expect("burst must be greater than 0");
+        let limiter = Arc::new(RateLimiter::direct(
+            Quota::per_second(bandwidth).allow_burst(burst),
+        ));
+        *self.limiter.write().expect("lock must be valid") = Some((limiter, burst));
+    }
+
+    fn unset(&self) {
+        *self.limiter.write().expect("lock must be valid") = None;
+    }
+
+    /// Take at most `burst` bytes from `remaining`.
+    ///
+    /// Returns the time to wait before trying again if the bucket can't
+    /// accommodate them now.
+    fn take(&self, remaining: &mut usize) -> Option<Duration> {
+        let guard = self.limiter.read().expect("lock must be valid");
+        let Some((limiter, burst)) = guard.as_ref() else {
+            *remaining = 0;
+            return None;
+        };
+
+        let n = (*remaining).min(burst.get() as usize);
+        let cells = NonZeroU32::new(n as u32)?;
+        match limiter.check_n(cells) {
+            Ok(Ok(())) => {
+                *remaining -= n;
+                None
+            }
+            Ok(Err(not_until)) => Some(not_until.wait_time_from(DefaultClock::default().now())),
+            // Never happens since we never ask for more than burst.
+            Err(_) => {
+                *remaining = 0;
+                None
+            }
+        }
+    }
+
+    async fn acquire(&self, size: usize) {
+        let mut remaining = size;
+        while remaining > 0 {
+            if let Some(wait) = self.take(&mut remaining) {
+                tokio::time::sleep(wait).await;
+            }
+        }
+    }
+
+    fn blocking_acquire(&self, size: usize) {
+        let mut remaining = size;
+        while remaining > 0 {
+            if let Some(wait) = self.take(&mut remaining) {
+                thread::sleep(wait);
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ThrottleAccessor<A: Access> {
     inner: A,
-    rate_limiter: SharedRateLimiter,
+    handle: ThrottleHandle,
 }
 
 impl<A: Access> LayeredAccess for ThrottleAccessor<A> {
@@ -122,21 +276,21 @@ impl<A: Access> LayeredAccess for ThrottleAccessor<A> {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let limiter = self.rate_limiter.clone();
+        let bucket = self.handle.read.clone();
 
         self.inner
             .read(path, args)
             .await
-            .map(|(rp, r)| (rp, ThrottleWrapper::new(r, limiter)))
+            .map(|(rp, r)| (rp, ThrottleWrapper::new(r, bucket)))
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        let limiter = self.rate_limiter.clone();
+        let bucket = self.handle.write.clone();
 
         self.inner
             .write(path, args)
             .await
-            .map(|(rp, w)| (rp, ThrottleWrapper::new(w, limiter)))
+            .map(|(rp, w)| (rp, ThrottleWrapper::new(w, bucket)))
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
@@ -144,19 +298,19 @@ impl<A: Access> LayeredAccess for ThrottleAccessor<A> {
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
-        let limiter = self.rate_limiter.clone();
+        let bucket = self.handle.read.clone();
 
         self.inner
             .blocking_read(path, args)
-            .map(|(rp, r)| (rp, ThrottleWrapper::new(r, limiter)))
+            .map(|(rp, r)| (rp, ThrottleWrapper::new(r, bucket)))
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
-        let limiter = self.rate_limiter.clone();
+        let bucket = self.handle.write.clone();
 
         self.inner
             .blocking_write(path, args)
-            .map(|(rp, w)| (rp, ThrottleWrapper::new(w, limiter)))
+            .map(|(rp, w)| (rp, ThrottleWrapper::new(w, bucket)))
     }
 
     fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
@@ -166,89 +320,94 @@ impl<A: Access> LayeredAccess for ThrottleAccessor<A> {
 
 pub struct ThrottleWrapper<R> {
     inner: R,
-    limiter: SharedRateLimiter,
+    bucket: Arc<ThrottleBucket>,
 }
 
 impl<R> ThrottleWrapper<R> {
-    pub fn new(inner: R, rate_limiter: SharedRateLimiter) -> Self {
-        Self {
-            inner,
-            limiter: rate_limiter,
-        }
+    fn new(inner: R, bucket: Arc<ThrottleBucket>) -> Self {
+        Self { inner, bucket }
     }
 }
 
 impl<R: oio::Read> oio::Read for ThrottleWrapper<R> {
     async fn read(&mut self) -> Result<Buffer> {
-        self.inner.read().await
+        // The size is only known after reading, so the bytes are paid
+        // before handing them to users.
+        let bs = self.inner.read().await?;
+        self.bucket.acquire(bs.len()).await;
+        Ok(bs)
     }
 }
 
 impl<R: oio::BlockingRead> oio::BlockingRead for ThrottleWrapper<R> {
     fn read(&mut self) -> Result<Buffer> {
-        self.inner.read()
+        let bs = self.inner.read()?;
+        self.bucket.blocking_acquire(bs.len());
+        Ok(bs)
     }
 }
 
 impl<R: oio::Write> oio::Write for ThrottleWrapper<R> {
     async fn write(&mut self, bs: Buffer) -> Result<()> {
-        let buf_length = NonZeroU32::new(bs.len() as u32).unwrap();
-
-        loop {
-            match self.limiter.check_n(buf_length) {
-                Ok(res) => match res {
-                    Ok(_) => return self.inner.write(bs).await,
-                    // the query is valid but the Decider can not accommodate them.
-                    Err(not_until) => {
-                        let _ = not_until.wait_time_from(DefaultClock::default().now());
-                        // TODO: Should lock the limiter and wait for the wait_time, or should let other small requests go first?
-
-                        // FIXME: we should sleep here.
-                        // tokio::time::sleep(wait_time).await;
-                    }
-                },
-                // the query was invalid as the rate limit parameters can "never" accommodate the number of cells queried for.
-                Err(_) => return Err(Error::new(
-                    ErrorKind::RateLimited,
-                    "InsufficientCapacity due to burst size being smaller than the request size",
-                )),
-            }
-        }
+        self.bucket.acquire(bs.len()).await;
+        self.inner.write(bs).await
     }
 
     async fn abort(&mut self) -> Result<()> {
         self.inner.abort().await
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         self.inner.close().await
     }
 }
 
 impl<R: oio::BlockingWrite> oio::BlockingWrite for ThrottleWrapper<R> {
     fn write(&mut self, bs: Buffer) -> Result<()> {
-        let buf_length = NonZeroU32::new(bs.len() as u32).unwrap();
-
-        loop {
-            match self.limiter.check_n(buf_length) {
-                Ok(res) => match res {
-                    Ok(_) => return self.inner.write(bs),
-                    // the query is valid but the Decider can not accommodate them.
-                    Err(not_until) => {
-                        let wait_time = not_until.wait_time_from(DefaultClock::default().now());
-                        thread::sleep(wait_time);
-                    }
-                },
-                // the query was invalid as the rate limit parameters can "never" accommodate the number of cells queried for.
-                Err(_) => return Err(Error::new(
-                    ErrorKind::RateLimited,
-                    "InsufficientCapacity due to burst size being smaller than the request size",
-                )),
-            }
-        }
+        self.bucket.blocking_acquire(bs.len());
+        self.inner.write(bs)
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         self.inner.close()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_acquire_larger_than_burst() {
+        let bucket = ThrottleBucket::default();
+        bucket.set(1024, 1024);
+
+        let start = Instant::now();
+        // The first 1024 bytes are allowed by burst, the rest must wait.
+        bucket.acquire(2048).await;
+        assert!(start.elapsed() >= Duration::from_millis(900));
+    }
+
+    #[tokio::test]
+    async fn test_handle_changes_limit() {
+        let layer = ThrottleLayer::new(1, 1);
+        let handle = layer.handle();
+
+        handle.set_read_bandwidth(1024 * 1024, 1024 * 1024);
+        let start = Instant::now();
+        handle.read.acquire(1024 * 1024).await;
+        assert!(start.elapsed() < Duration::from_millis(500));
+
+        // Write budget is not affected by read.
+        let mut remaining = 2;
+        assert!(handle.write.take(&mut remaining).is_none());
+        assert!(handle.write.take(&mut remaining).is_some());
+
+        handle.unset_write_bandwidth();
+        let start = Instant::now();
+        handle.write.acquire(usize::MAX).await;
+        assert!(start.elapsed() < Duration::from_millis(500));
+    }
+}
@@ -15,10 +15,13 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 
+use tokio::time::Instant;
+
 use crate::raw::oio::ListOperation;
 use crate::raw::oio::ReadOperation;
 use crate::raw::oio::WriteOperation;
@@ -38,12 +41,22 @@ use crate::*;
 /// - Non IO Operation like `stat`, `delete` they operate on a single file. We control
 ///   them by setting `timeout`.
 /// - IO Operation like `read`, `Reader::read` and `Writer::write`, they operate on data directly, we
-///   control them by setting `io_timeout`.
+///   control them by setting `io_timeout`. `io_timeout` applies to every single call, so it limits
+///   both the time to the first byte and the time between bytes.
+///
+/// The total time a `Reader`, `Writer` or `Lister` could live is not limited by default, users
+/// can set `stream_timeout` to bound it. Timeouts of specific operations can be overridden by
+/// `with_operation_timeout`.
+///
+/// Timeouts are returned as [`ErrorKind::Timeout`]. They are only marked as temporary for
+/// idempotent operations so that `RetryLayer` won't retry `write` or `rename` after a timeout,
+/// which could have taken effect on the service side already.
 ///
 /// # Default
 ///
 /// - timeout: 60 seconds
 /// - io_timeout: 10 seconds
+/// - stream_timeout: none
 ///
 /// # Panics
 ///
@@ -89,6 +102,29 @@ use crate::*;
 ///     .finish();
 /// ```
 ///
+/// The following examples will limit `stat` to 5 seconds, wait at most 30 seconds between bytes
+/// while reading, 60 seconds while writing and abort any stream that lives longer than 1 hour.
+///
+/// ```no_run
+/// use std::time::Duration;
+///
+/// use opendal::layers::TimeoutLayer;
+/// use opendal::raw::Operation;
+/// use opendal::services;
+/// use opendal::Operator;
+///
+/// let _ = Operator::new(services::Memory::default())
+///     .expect("must init")
+///     .layer(
+///         TimeoutLayer::default()
+///             .with_operation_timeout(Operation::Stat, Duration::from_secs(5))
+///             .with_operation_timeout(Operation::Read, Duration::from_secs(30))
+///             .with_operation_timeout(Operation::Write, Duration::from_secs(60))
+///             .with_stream_timeout(Duration::from_secs(3600)),
+///     )
+///     .finish();
+/// ```
+///
 /// # Implementation Notes
 ///
 /// TimeoutLayer is using [`tokio::time::timeout`] to implement timeout for operations. And IO
@@ -107,6 +143,8 @@ use crate::*;
 pub struct TimeoutLayer {
     timeout: Duration,
     io_timeout: Duration,
+    stream_timeout: Option<Duration>,
+    overrides: HashMap<Operation, Duration>,
 }
 
 impl Default for TimeoutLayer {
@@ -114,6 +152,8 @@ impl Default for TimeoutLayer {
         Self {
             timeout: Duration::from_secs(60),
             io_timeout: Duration::from_secs(10),
+            stream_timeout: None,
+            overrides: HashMap::new(),
         }
     }
 }
@@ -140,6 +180,25 @@ impl TimeoutLayer {
         self
     }
 
+    /// Set the total timeout of a stream like `Reader`, `Writer` and `Lister`.
+    ///
+    /// The stream timeout starts when the stream is created. Every io operation
+    /// on the stream will fail after it's reached even if data keeps flowing.
+    pub fn with_stream_timeout(mut self, timeout: Duration) -> Self {
+        self.stream_timeout = Some(timeout);
+        self
+    }
+
+    /// Override the timeout of given operation.
+    ///
+    /// For io operations like [`Operation::Read`], [`Operation::Write`] and
+    /// [`Operation::List`], this overrides the io timeout of the operation and
+    /// its stream. For other operations, this overrides the operation timeout.
+    pub fn with_operation_timeout(mut self, op: Operation, timeout: Duration) -> Self {
+        self.overrides.insert(op, timeout);
+        self
+    }
+
     /// Set speed for TimeoutLayer with given value.
     ///
     /// # Notes
@@ -165,6 +224,8 @@ impl<A: Access> Layer<A> for TimeoutLayer {
 
             timeout: self.timeout,
             io_timeout: self.io_timeout,
+            stream_timeout: self.stream_timeout,
+            overrides: Arc::new(self.overrides.clone()),
         }
     }
 }
@@ -175,15 +236,50 @@ pub struct TimeoutAccessor<A: Access> {
 
     timeout: Duration,
     io_timeout: Duration,
+    stream_timeout: Option<Duration>,
+    overrides: Arc<HashMap<Operation, Duration>>,
+}
+
+/// Returns whether the operation is safe to retry after a timeout.
+///
+/// A timed out `write` or `rename` could have taken effect on the service side
+/// already, retrying them might lead to unexpected results.
+fn is_idempotent(op: Operation) -> bool {
+    !matches!(
+        op,
+        Operation::Write | Operation::Rename | Operation::BlockingWrite | Operation::BlockingRename
+    )
+}
+
+fn new_timeout_error(
+    message: &'static str,
+    op: impl Into<&'static str>,
+    timeout: Duration,
+    idempotent: bool,
+) -> Error {
+    let err = Error::new(ErrorKind::Timeout, message)
+        .with_operation(op)
+        .with_context("timeout", timeout.as_secs_f64().to_string());
+    if idempotent {
+        err.set_temporary()
+    } else {
+        err
+    }
 }
 
 impl<A: Access> TimeoutAccessor<A> {
+    fn timeout_of(&self, op: Operation) -> Duration {
+        self.overrides.get(&op).copied().unwrap_or(self.timeout)
+    }
+
+    fn io_timeout_of(&self, op: Operation) -> Duration {
+        self.overrides.get(&op).copied().unwrap_or(self.io_timeout)
+    }
+
     async fn timeout<F: Future<Output = Result<T>>, T>(&self, op: Operation, fut: F) -> Result<T> {
-        tokio::time::timeout(self.timeout, fut).await.map_err(|_| {
-            Error::new(ErrorKind::Unexpected, "operation timeout reached")
-                .with_operation(op)
-                .with_context("timeout", self.timeout.as_secs_f64().to_string())
-                .set_temporary()
+        let timeout = self.timeout_of(op);
+        tokio::time::timeout(timeout, fut).await.map_err(|_| {
+            new_timeout_error("operation timeout reached", op, timeout, is_idempotent(op))
         })?
     }
 
@@ -192,14 +288,19 @@ impl<A: Access> TimeoutAccessor<A> {
         op: Operation,
         fut: F,
     ) -> Result<T> {
-        tokio::time::timeout(self.io_timeout, fut)
+        let timeout = self.io_timeout_of(op);
+        tokio::time::timeout(timeout, fut)
             .await
-            .map_err(|_| {
-                Error::new(ErrorKind::Unexpected, "io timeout reached")
-                    .with_operation(op)
-                    .with_context("timeout", self.io_timeout.as_secs_f64().to_string())
-                    .set_temporary()
-            })?
+            .map_err(|_| new_timeout_error("io timeout reached", op, timeout, is_idempotent(op)))?
+    }
+
+    fn wrap<R>(&self, op: Operation, inner: R) -> TimeoutWrapper<R> {
+        TimeoutWrapper::new(
+            inner,
+            self.io_timeout_of(op),
+            self.stream_timeout,
+            is_idempotent(op),
+        )
     }
 }
 
@@ -225,26 +326,26 @@ impl<A: Access> LayeredAccess for TimeoutAccessor<A> {
         if let Some(exec) = args.executor().cloned() {
             args = args.with_executor(Executor::with(TimeoutExecutor::new(
                 exec.into_inner(),
-                self.io_timeout,
+                self.io_timeout_of(Operation::Read),
             )));
         }
 
         self.io_timeout(Operation::Read, self.inner.read(path, args))
             .await
-            .map(|(rp, r)| (rp, TimeoutWrapper::new(r, self.io_timeout)))
+            .map(|(rp, r)| (rp, self.wrap(Operation::Read, r)))
     }
 
     async fn write(&self, path: &str, mut args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
         if let Some(exec) = args.executor().cloned() {
             args = args.with_executor(Executor::with(TimeoutExecutor::new(
                 exec.into_inner(),
-                self.io_timeout,
+                self.io_timeout_of(Operation::Write),
             )));
         }
 
         self.io_timeout(Operation::Write, self.inner.write(path, args))
             .await
-            .map(|(rp, r)| (rp, TimeoutWrapper::new(r, self.io_timeout)))
+            .map(|(rp, r)| (rp, self.wrap(Operation::Write, r)))
     }
 
     async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
@@ -262,6 +363,11 @@ impl<A: Access> LayeredAccess for TimeoutAccessor<A> {
             .await
     }
 
+    async fn exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        self.timeout(Operation::Stat, self.inner.exists(path, args))
+            .await
+    }
+
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.timeout(Operation::Delete, self.inner.delete(path, args))
             .await
@@ -270,7 +376,7 @@ impl<A: Access> LayeredAccess for TimeoutAccessor<A> {
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
         self.io_timeout(Operation::List, self.inner.list(path, args))
             .await
-            .map(|(rp, r)| (rp, TimeoutWrapper::new(r, self.io_timeout)))
+            .map(|(rp, r)| (rp, self.wrap(Operation::List, r)))
     }
 
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
@@ -320,56 +426,120 @@ pub struct TimeoutWrapper<R> {
     inner: R,
 
     timeout: Duration,
+    /// The instant that the whole stream must finish before.
+    deadline: Option<Instant>,
+    idempotent: bool,
 }
 
 impl<R> TimeoutWrapper<R> {
-    fn new(inner: R, timeout: Duration) -> Self {
-        Self { inner, timeout }
+    fn new(
+        inner: R,
+        timeout: Duration,
+        stream_timeout: Option<Duration>,
+        idempotent: bool,
+    ) -> Self {
+        Self {
+            inner,
+            timeout,
+            deadline: stream_timeout.map(|v| Instant::now() + v),
+            idempotent,
+        }
+    }
+
+    /// Returns the timeout of next io operation and the message to use
+    /// if it's reached.
+    ///
+    /// The io timeout will be shortened to the remaining time of the stream.
+    fn next_timeout(&self) -> (Duration, &'static str) {
+        match self.deadline {
+            Some(deadline) => {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                if remaining < self.timeout {
+                    (remaining, "stream timeout reached")
+                } else {
+                    (self.timeout, "io operation timeout reached")
+                }
+            }
+            None => (self.timeout, "io operation timeout reached"),
+        }
     }
 
     #[inline]
     async fn io_timeout<F: Future<Output = Result<T>>, T>(
-        timeout: Duration,
+        (timeout, message): (Duration, &'static str),
+        idempotent: bool,
         op: &'static str,
         fut: F,
     ) -> Result<T> {
-        tokio::time::timeout(timeout, fut).await.map_err(|_| {
-            Error::new(ErrorKind::Unexpected, "io operation timeout reached")
-                .with_operation(op)
-                .with_context("timeout", timeout.as_secs_f64().to_string())
-                .set_temporary()
-        })?
+        tokio::time::timeout(timeout, fut)
+            .await
+            .map_err(|_| new_timeout_error(message, op, timeout, idempotent))?
     }
 }
 
 impl<R: oio::Read> oio::Read for TimeoutWrapper<R> {
     async fn read(&mut self) -> Result<Buffer> {
+        let timeout = self.next_timeout();
         let fut = self.inner.read();
-        Self::io_timeout(self.timeout, ReadOperation::Read.into_static(), fut).await
+        Self::io_timeout(
+            timeout,
+            self.idempotent,
+            ReadOperation::Read.into_static(),
+            fut,
+        )
+        .await
     }
 }
 
 impl<R: oio::Write> oio::Write for TimeoutWrapper<R> {
     async fn write(&mut self, bs: Buffer) -> Result<()> {
+        let timeout = self.next_timeout();
         let fut = self.inner.write(bs);
-        Self::io_timeout(self.timeout, WriteOperation::Write.into_static(), fut).await
+        Self::io_timeout(
+            timeout,
+            self.idempotent,
+            WriteOperation::Write.into_static(),
+            fut,
+        )
+        .await
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
+        let timeout = self.next_timeout();
         let fut = self.inner.close();
-        Self::io_timeout(self.timeout, WriteOperation::Close.into_static(), fut).await
+        Self::io_timeout(
+            timeout,
+            self.idempotent,
+            WriteOperation::Close.into_static(),
+            fut,
+        )
+        .await
     }
 
     async fn abort(&mut self) -> Result<()> {
+        let timeout = self.next_timeout();
         let fut = self.inner.abort();
-        Self::io_timeout(self.timeout, WriteOperation::Abort.into_static(), fut).await
+        Self::io_timeout(
+            timeout,
+            self.idempotent,
+            WriteOperation::Abort.into_static(),
+            fut,
+        )
+        .await
     }
 }
 
 impl<R: oio::List> oio::List for TimeoutWrapper<R> {
     async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        let timeout = self.next_timeout();
         let fut = self.inner.next();
-        Self::io_timeout(self.timeout, ListOperation::Next.into_static(), fut).await
+        Self::io_timeout(
+            timeout,
+            self.idempotent,
+            ListOperation::Next.into_static(),
+            fut,
+        )
+        .await
     }
 }
 
@@ -394,7 +564,7 @@ mod tests {
 
     impl Access for MockService {
         type Reader = MockReader;
-        type Writer = ();
+        type Writer = MockWriter;
         type Lister = MockLister;
         type BlockingReader = ();
         type BlockingWriter = ();
@@ -404,7 +574,9 @@ mod tests {
             let mut am = AccessorInfo::default();
             am.set_native_capability(Capability {
                 read: true,
+                write: true,
                 delete: true,
+                list: true,
                 ..Default::default()
             });
 
@@ -416,6 +588,11 @@ mod tests {
             Ok((RpRead::new(), MockReader))
         }
 
+        /// This function will build a writer that always return pending.
+        async fn write(&self, _: &str, _: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+            Ok((RpWrite::new(), MockWriter))
+        }
+
         /// This function will never return.
         async fn delete(&self, _: &str, _: OpDelete) -> Result<RpDelete> {
             sleep(Duration::from_secs(u64::MAX)).await;
@@ -437,6 +614,23 @@ mod tests {
         }
     }
 
+    #[derive(Debug, Clone, Default)]
+    struct MockWriter;
+
+    impl oio::Write for MockWriter {
+        fn write(&mut self, _: Buffer) -> impl Future<Output = Result<()>> {
+            pending()
+        }
+
+        fn close(&mut self) -> impl Future<Output = Result<Metadata>> {
+            pending()
+        }
+
+        fn abort(&mut self) -> impl Future<Output = Result<()>> {
+            pending()
+        }
+    }
+
     #[derive(Debug, Clone, Default)]
     struct MockLister;
 
@@ -456,7 +650,7 @@ mod tests {
             let res = op.delete("test").await;
             assert!(res.is_err());
             let err = res.unwrap_err();
-            assert_eq!(err.kind(), ErrorKind::Unexpected);
+            assert_eq!(err.kind(), ErrorKind::Timeout);
             assert!(err.to_string().contains("timeout"))
         };
 
@@ -476,7 +670,7 @@ mod tests {
         let res = reader.read(0..4).await;
         assert!(res.is_err());
         let err = res.unwrap_err();
-        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert_eq!(err.kind(), ErrorKind::Timeout);
         assert!(err.to_string().contains("timeout"))
     }
 
@@ -494,7 +688,7 @@ mod tests {
         let res = lister.next().await.unwrap();
         assert!(res.is_err());
         let err = res.unwrap_err();
-        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert_eq!(err.kind(), ErrorKind::Timeout);
         assert!(err.to_string().contains("timeout"))
     }
 
@@ -515,7 +709,68 @@ mod tests {
         let res = lister.next().await;
         assert!(res.is_err());
         let err = res.unwrap_err();
-        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert_eq!(err.kind(), ErrorKind::Timeout);
         assert!(err.to_string().contains("timeout"));
     }
+
+    #[tokio::test]
+    async fn test_operation_timeout_override() {
+        let acc = Arc::new(TypeEraseLayer.layer(MockService)) as Accessor;
+        let op = Operator::from_inner(acc).layer(
+            TimeoutLayer::new()
+                .with_timeout(Duration::from_secs(60))
+                .with_operation_timeout(Operation::Delete, Duration::from_secs(1)),
+        );
+
+        let fut = async {
+            let err = op.delete("test").await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::Timeout);
+            assert!(err.is_temporary());
+        };
+
+        timeout(Duration::from_secs(2), fut)
+            .await
+            .expect("this test should not exceed 2 seconds")
+    }
+
+    #[tokio::test]
+    async fn test_stream_timeout() {
+        let acc = Arc::new(TypeEraseLayer.layer(MockService)) as Accessor;
+        let op = Operator::from_inner(acc).layer(
+            TimeoutLayer::new()
+                .with_io_timeout(Duration::from_secs(60))
+                .with_stream_timeout(Duration::from_secs(1)),
+        );
+
+        let fut = async {
+            let reader = op.reader("test").await.unwrap();
+            let err = reader.read(0..4).await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::Timeout);
+            assert!(err.to_string().contains("stream timeout"));
+        };
+
+        timeout(Duration::from_secs(2), fut)
+            .await
+            .expect("this test should not exceed 2 seconds")
+    }
+
+    #[tokio::test]
+    async fn test_write_timeout_not_temporary() {
+        let acc = Arc::new(TypeEraseLayer.layer(MockService)) as Accessor;
+        let op = Operator::from_inner(acc).layer(
+            TimeoutLayer::new()
+                .with_io_timeout(Duration::from_secs(60))
+                .with_operation_timeout(Operation::Write, Duration::from_secs(1)),
+        );
+
+        let fut = async {
+            let err = op.write("test", "hello").await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::Timeout);
+            assert!(!err.is_temporary());
+        };
+
+        timeout(Duration::from_secs(3), fut)
+            .await
+            .expect("this test should not exceed 3 seconds")
+    }
 }
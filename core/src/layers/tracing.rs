@@ -181,6 +181,11 @@ impl<A: Access> LayeredAccess for TracingAccessor<A> {
         self.inner.stat(path, args).await
     }
 
+    #[tracing::instrument(level = "debug", skip(self))]
+    async fn exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        self.inner.exists(path, args).await
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.inner.delete(path, args).await
@@ -238,6 +243,11 @@ impl<A: Access> LayeredAccess for TracingAccessor<A> {
         self.inner.blocking_stat(path, args)
     }
 
+    #[tracing::instrument(level = "debug", skip(self))]
+    fn blocking_exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        self.inner.blocking_exists(path, args)
+    }
+
     #[tracing::instrument(level = "debug", skip(self))]
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.inner.blocking_delete(path, args)
@@ -303,7 +313,7 @@ impl<R: oio::Write> oio::Write for TracingWrapper<R> {
         parent = &self.span,
         level = "trace",
         skip_all)]
-    fn close(&mut self) -> impl Future<Output = Result<()>> + MaybeSend {
+    fn close(&mut self) -> impl Future<Output = Result<Metadata>> + MaybeSend {
         self.inner.close()
     }
 }
@@ -321,7 +331,7 @@ impl<R: oio::BlockingWrite> oio::BlockingWrite for TracingWrapper<R> {
         parent = &self.span,
         level = "trace",
         skip_all)]
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         self.inner.close()
     }
 }
@@ -121,6 +121,29 @@ pub trait Access: Send + Sync + Debug + Unpin + 'static {
         )))
     }
 
+    /// Invoke the `exists` operation on the specified path.
+    ///
+    /// Require [`Capability::stat`]
+    ///
+    /// # Behavior
+    ///
+    /// - Return `Ok(false)` only if the path is known to be absent, errors
+    ///   that can't prove the absence like `PermissionDenied` MUST be returned.
+    /// - The default implementation falls back to `stat`, services SHOULD
+    ///   override it if they can check the existence without fetching the
+    ///   metadata.
+    fn exists(&self, path: &str, args: OpStat) -> impl Future<Output = Result<bool>> + MaybeSend {
+        let fut = self.stat(path, args);
+
+        async move {
+            match fut.await {
+                Ok(_) => Ok(true),
+                Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+                Err(err) => Err(err),
+            }
+        }
+    }
+
     /// Invoke the `read` operation on the specified path, returns a
     /// [`Reader`][crate::Reader] if operate successful.
     ///
@@ -266,6 +289,69 @@ pub trait Access: Send + Sync + Debug + Unpin + 'static {
         )))
     }
 
+    /// Invoke the `initiate_multipart` operation on the specified path.
+    ///
+    /// Require [`Capability::multipart`]
+    ///
+    /// # Behavior
+    ///
+    /// - This API is optional, return [`std::io::ErrorKind::Unsupported`] if not supported.
+    /// - The returned upload id can be used to presign upload parts via [`PresignOperation::UploadPart`].
+    fn initiate_multipart(
+        &self,
+        path: &str,
+        args: OpWrite,
+    ) -> impl Future<Output = Result<RpInitiateMultipart>> + MaybeSend {
+        let (_, _) = (path, args);
+
+        ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        )))
+    }
+
+    /// Invoke the `complete_multipart` operation on the specified path.
+    ///
+    /// Require [`Capability::multipart`]
+    ///
+    /// # Behavior
+    ///
+    /// - This API is optional, return [`std::io::ErrorKind::Unsupported`] if not supported.
+    fn complete_multipart(
+        &self,
+        path: &str,
+        args: OpCompleteMultipart,
+    ) -> impl Future<Output = Result<RpCompleteMultipart>> + MaybeSend {
+        let (_, _) = (path, args);
+
+        ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        )))
+    }
+
+    /// Invoke the `abort_multipart` operation on the specified path.
+    ///
+    /// Require [`Capability::multipart`]
+    ///
+    /// # Behavior
+    ///
+    /// - This API is optional, return [`std::io::ErrorKind::Unsupported`] if not supported.
+    /// - Uploaded parts will be discarded, services without upload sessions
+    ///   could leave them to be cleaned up by the server.
+    fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> impl Future<Output = Result<RpAbortMultipart>> + MaybeSend {
+        let (_, _) = (path, args);
+
+        ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "operation is not supported",
+        )))
+    }
+
     /// Invoke the `batch` operations.
     ///
     /// Require [`Capability::batch`]
@@ -306,6 +392,19 @@ pub trait Access: Send + Sync + Debug + Unpin + 'static {
         ))
     }
 
+    /// Invoke the `blocking_exists` operation on the specified path.
+    ///
+    /// This operation is the blocking version of [`Accessor::exists`]
+    ///
+    /// Require [`Capability::stat`] and [`Capability::blocking`]
+    fn blocking_exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        match self.blocking_stat(path, args) {
+            Ok(_) => Ok(true),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
     /// Invoke the `blocking_read` operation on the specified path.
     ///
     /// This operation is the blocking version of [`Accessor::read`]
@@ -408,6 +507,8 @@ pub trait AccessDyn: Send + Sync + Debug + Unpin {
     ) -> BoxedFuture<'a, Result<RpCreateDir>>;
     /// Dyn version of [`Accessor::stat`]
     fn stat_dyn<'a>(&'a self, path: &'a str, args: OpStat) -> BoxedFuture<'a, Result<RpStat>>;
+    /// Dyn version of [`Accessor::exists`]
+    fn exists_dyn<'a>(&'a self, path: &'a str, args: OpStat) -> BoxedFuture<'a, Result<bool>>;
     /// Dyn version of [`Accessor::read`]
     fn read_dyn<'a>(
         &'a self,
@@ -449,12 +550,32 @@ pub trait AccessDyn: Send + Sync + Debug + Unpin {
         path: &'a str,
         args: OpPresign,
     ) -> BoxedFuture<'a, Result<RpPresign>>;
+    /// Dyn version of [`Accessor::initiate_multipart`]
+    fn initiate_multipart_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpWrite,
+    ) -> BoxedFuture<'a, Result<RpInitiateMultipart>>;
+    /// Dyn version of [`Accessor::complete_multipart`]
+    fn complete_multipart_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpCompleteMultipart,
+    ) -> BoxedFuture<'a, Result<RpCompleteMultipart>>;
+    /// Dyn version of [`Accessor::abort_multipart`]
+    fn abort_multipart_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpAbortMultipart,
+    ) -> BoxedFuture<'a, Result<RpAbortMultipart>>;
     /// Dyn version of [`Accessor::batch`]
     fn batch_dyn(&self, args: OpBatch) -> BoxedFuture<'_, Result<RpBatch>>;
     /// Dyn version of [`Accessor::blocking_create_dir`]
     fn blocking_create_dir_dyn(&self, path: &str, args: OpCreateDir) -> Result<RpCreateDir>;
     /// Dyn version of [`Accessor::blocking_stat`]
     fn blocking_stat_dyn(&self, path: &str, args: OpStat) -> Result<RpStat>;
+    /// Dyn version of [`Accessor::blocking_exists`]
+    fn blocking_exists_dyn(&self, path: &str, args: OpStat) -> Result<bool>;
     /// Dyn version of [`Accessor::blocking_read`]
     fn blocking_read_dyn(&self, path: &str, args: OpRead) -> Result<(RpRead, oio::BlockingReader)>;
     /// Dyn version of [`Accessor::blocking_write`]
@@ -500,6 +621,10 @@ where
         Box::pin(self.stat(path, args))
     }
 
+    fn exists_dyn<'a>(&'a self, path: &'a str, args: OpStat) -> BoxedFuture<'a, Result<bool>> {
+        Box::pin(self.exists(path, args))
+    }
+
     fn read_dyn<'a>(
         &'a self,
         path: &'a str,
@@ -558,6 +683,30 @@ where
         Box::pin(self.presign(path, args))
     }
 
+    fn initiate_multipart_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpWrite,
+    ) -> BoxedFuture<'a, Result<RpInitiateMultipart>> {
+        Box::pin(self.initiate_multipart(path, args))
+    }
+
+    fn complete_multipart_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpCompleteMultipart,
+    ) -> BoxedFuture<'a, Result<RpCompleteMultipart>> {
+        Box::pin(self.complete_multipart(path, args))
+    }
+
+    fn abort_multipart_dyn<'a>(
+        &'a self,
+        path: &'a str,
+        args: OpAbortMultipart,
+    ) -> BoxedFuture<'a, Result<RpAbortMultipart>> {
+        Box::pin(self.abort_multipart(path, args))
+    }
+
     fn batch_dyn(&self, args: OpBatch) -> BoxedFuture<'_, Result<RpBatch>> {
         Box::pin(self.batch(args))
     }
@@ -570,6 +719,10 @@ where
         self.blocking_stat(path, args)
     }
 
+    fn blocking_exists_dyn(&self, path: &str, args: OpStat) -> Result<bool> {
+        self.blocking_exists(path, args)
+    }
+
     fn blocking_read_dyn(&self, path: &str, args: OpRead) -> Result<(RpRead, oio::BlockingReader)> {
         self.blocking_read(path, args)
     }
@@ -619,6 +772,10 @@ impl Access for dyn AccessDyn {
         self.stat_dyn(path, args).await
     }
 
+    async fn exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        self.exists_dyn(path, args).await
+    }
+
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
         self.read_dyn(path, args).await
     }
@@ -647,6 +804,26 @@ impl Access for dyn AccessDyn {
         self.presign_dyn(path, args).await
     }
 
+    async fn initiate_multipart(&self, path: &str, args: OpWrite) -> Result<RpInitiateMultipart> {
+        self.initiate_multipart_dyn(path, args).await
+    }
+
+    async fn complete_multipart(
+        &self,
+        path: &str,
+        args: OpCompleteMultipart,
+    ) -> Result<RpCompleteMultipart> {
+        self.complete_multipart_dyn(path, args).await
+    }
+
+    async fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> Result<RpAbortMultipart> {
+        self.abort_multipart_dyn(path, args).await
+    }
+
     fn batch(&self, args: OpBatch) -> impl Future<Output = Result<RpBatch>> + MaybeSend {
         self.batch_dyn(args)
     }
@@ -663,6 +840,10 @@ impl Access for dyn AccessDyn {
         self.blocking_stat_dyn(path, args)
     }
 
+    fn blocking_exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        self.blocking_exists_dyn(path, args)
+    }
+
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
         self.blocking_write_dyn(path, args)
     }
@@ -734,6 +915,10 @@ impl<T: Access + ?Sized> Access for Arc<T> {
         async move { self.as_ref().stat(path, args).await }
     }
 
+    fn exists(&self, path: &str, args: OpStat) -> impl Future<Output = Result<bool>> + MaybeSend {
+        async move { self.as_ref().exists(path, args).await }
+    }
+
     fn read(
         &self,
         path: &str,
@@ -792,6 +977,30 @@ impl<T: Access + ?Sized> Access for Arc<T> {
         async move { self.as_ref().presign(path, args).await }
     }
 
+    fn initiate_multipart(
+        &self,
+        path: &str,
+        args: OpWrite,
+    ) -> impl Future<Output = Result<RpInitiateMultipart>> + MaybeSend {
+        async move { self.as_ref().initiate_multipart(path, args).await }
+    }
+
+    fn complete_multipart(
+        &self,
+        path: &str,
+        args: OpCompleteMultipart,
+    ) -> impl Future<Output = Result<RpCompleteMultipart>> + MaybeSend {
+        async move { self.as_ref().complete_multipart(path, args).await }
+    }
+
+    fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> impl Future<Output = Result<RpAbortMultipart>> + MaybeSend {
+        async move { self.as_ref().abort_multipart(path, args).await }
+    }
+
     fn batch(&self, args: OpBatch) -> impl Future<Output = Result<RpBatch>> + MaybeSend {
         async move { self.as_ref().batch(args).await }
     }
@@ -804,6 +1013,10 @@ impl<T: Access + ?Sized> Access for Arc<T> {
         self.as_ref().blocking_stat(path, args)
     }
 
+    fn blocking_exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        self.as_ref().blocking_exists(path, args)
+    }
+
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
         self.as_ref().blocking_read(path, args)
     }
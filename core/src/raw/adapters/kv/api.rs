@@ -48,6 +48,21 @@ pub trait Adapter: Send + Sync + Debug + Unpin + 'static {
         .with_operation("kv::Adapter::blocking_get"))
     }
 
+    /// Check if a key exists in service.
+    ///
+    /// The default implementation will fetch the value, services should
+    /// override it if they can check the key without reading the value.
+    fn exists(&self, path: &str) -> impl Future<Output = Result<bool>> + MaybeSend {
+        let fut = self.get(path);
+
+        async move { Ok(fut.await?.is_some()) }
+    }
+
+    /// The blocking version of exists.
+    fn blocking_exists(&self, path: &str) -> Result<bool> {
+        Ok(self.blocking_get(path)?.is_some())
+    }
+
     /// Set a key into service.
     fn set(&self, path: &str, value: Buffer) -> impl Future<Output = Result<()>> + MaybeSend;
 
@@ -103,6 +118,39 @@ pub trait Adapter: Send + Sync + Debug + Unpin + 'static {
         .with_operation("kv::Adapter::blocking_scan"))
     }
 
+    /// Delete keys from service in one batch.
+    ///
+    /// - return `Ok(())` even if some keys are not exist.
+    /// - the batch should be applied atomically, either all keys are
+    ///   deleted or none of them.
+    fn batch_delete(&self, paths: &[String]) -> impl Future<Output = Result<()>> + MaybeSend {
+        let _ = paths;
+
+        ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "kv adapter doesn't support this operation",
+        )
+        .with_operation("kv::Adapter::batch_delete")))
+    }
+
+    /// Write keys into service in one transaction.
+    ///
+    /// A `None` value means deleting the key. All changes in the batch must
+    /// be applied atomically, conflicts with other transactions should be
+    /// returned as temporary errors so that they can be retried.
+    fn write_batch(
+        &self,
+        batch: Vec<(String, Option<Buffer>)>,
+    ) -> impl Future<Output = Result<()>> + MaybeSend {
+        let _ = batch;
+
+        ready(Err(Error::new(
+            ErrorKind::Unsupported,
+            "kv adapter doesn't support this operation",
+        )
+        .with_operation("kv::Adapter::write_batch")))
+    }
+
     /// Append a key into service
     fn append(&self, path: &str, value: &[u8]) -> impl Future<Output = Result<()>> + MaybeSend {
         let _ = path;
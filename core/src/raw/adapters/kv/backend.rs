@@ -108,13 +108,17 @@ impl<S: Adapter> Access for Backend<S> {
     async fn write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::Writer)> {
         let p = build_abs_path(&self.root, path);
 
-        Ok((RpWrite::new(), KvWriter::new(self.kv.clone(), p)))
+        // All data is buffered and set at once while closing.
+        let rp = RpWrite::new().with_mode(WriteMode::OneShot);
+        Ok((rp, KvWriter::new(self.kv.clone(), p)))
     }
 
     fn blocking_write(&self, path: &str, _: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
         let p = build_abs_path(&self.root, path);
 
-        Ok((RpWrite::new(), KvWriter::new(self.kv.clone(), p)))
+        // All data is buffered and set at once while closing.
+        let rp = RpWrite::new().with_mode(WriteMode::OneShot);
+        Ok((rp, KvWriter::new(self.kv.clone(), p)))
     }
 
     async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
@@ -149,6 +153,16 @@ impl<S: Adapter> Access for Backend<S> {
         }
     }
 
+    async fn exists(&self, path: &str, _: OpStat) -> Result<bool> {
+        let p = build_abs_path(&self.root, path);
+
+        if p == build_abs_path(&self.root, "") {
+            Ok(true)
+        } else {
+            self.kv.exists(&p).await
+        }
+    }
+
     async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
         let p = build_abs_path(&self.root, path);
 
@@ -156,6 +170,16 @@ impl<S: Adapter> Access for Backend<S> {
         Ok(RpDelete::default())
     }
 
+    fn blocking_exists(&self, path: &str, _: OpStat) -> Result<bool> {
+        let p = build_abs_path(&self.root, path);
+
+        if p == build_abs_path(&self.root, "") {
+            Ok(true)
+        } else {
+            self.kv.blocking_exists(&p)
+        }
+    }
+
     fn blocking_delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
         let p = build_abs_path(&self.root, path);
 
@@ -163,6 +187,44 @@ impl<S: Adapter> Access for Backend<S> {
         Ok(RpDelete::default())
     }
 
+    async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
+        let ops = args.into_operation();
+
+        let mut batch = Vec::with_capacity(ops.len());
+        let mut results = Vec::with_capacity(ops.len());
+        for (path, op) in ops {
+            let p = build_abs_path(&self.root, &path);
+            match op {
+                BatchOperation::Delete(_) => {
+                    batch.push((p, None));
+                    results.push((path, Ok(RpDelete::default().into())));
+                }
+                BatchOperation::Write(_, bs) => {
+                    batch.push((p, Some(bs)));
+                    results.push((path, Ok(RpWrite::default().into())));
+                }
+                op => {
+                    results.push((
+                        path,
+                        Err(Error::new(
+                            ErrorKind::Unsupported,
+                            "kv doesn't support this operation in batch",
+                        )
+                        .with_operation(op.operation())),
+                    ));
+                }
+            }
+        }
+
+        if batch.iter().all(|(_, v)| v.is_none()) {
+            let paths: Vec<_> = batch.into_iter().map(|(p, _)| p).collect();
+            self.kv.batch_delete(&paths).await?;
+        } else {
+            self.kv.write_batch(batch).await?;
+        }
+        Ok(RpBatch::new(results))
+    }
+
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
         let p = build_abs_path(&self.root, path);
         let res = self.kv.scan(&p).await?;
@@ -247,9 +309,10 @@ impl<S: Adapter> oio::Write for KvWriter<S> {
         Ok(())
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         let buf = self.buffer.clone().collect();
-        self.kv.set(&self.path, buf).await
+        self.kv.set(&self.path, buf).await?;
+        Ok(Metadata::new(EntryMode::FILE))
     }
 
     async fn abort(&mut self) -> Result<()> {
@@ -264,9 +327,9 @@ impl<S: Adapter> oio::BlockingWrite for KvWriter<S> {
         Ok(())
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         let buf = self.buffer.clone().collect();
         self.kv.blocking_set(&self.path, buf)?;
-        Ok(())
+        Ok(Metadata::new(EntryMode::FILE))
     }
 }
@@ -54,6 +54,21 @@ pub trait Adapter: Send + Sync + Debug + Unpin + 'static {
     /// Get a value from adapter.
     fn blocking_get(&self, path: &str) -> Result<Option<Value>>;
 
+    /// Check if a value exists in adapter.
+    ///
+    /// The default implementation will clone the value, adapters should
+    /// override it if they can check the key directly.
+    fn exists(&self, path: &str) -> impl Future<Output = Result<bool>> + MaybeSend {
+        let fut = self.get(path);
+
+        async move { Ok(fut.await?.is_some()) }
+    }
+
+    /// Check if a value exists in adapter.
+    fn blocking_exists(&self, path: &str) -> Result<bool> {
+        Ok(self.blocking_get(path)?.is_some())
+    }
+
     /// Set a value into adapter.
     fn set(&self, path: &str, value: Value) -> impl Future<Output = Result<()>> + MaybeSend;
 
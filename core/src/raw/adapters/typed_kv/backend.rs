@@ -18,6 +18,9 @@
 use std::sync::Arc;
 use std::vec::IntoIter;
 
+use bytes::Bytes;
+
+use super::encode_snapshot;
 use super::Adapter;
 use super::Value;
 use crate::raw::oio::HierarchyLister;
@@ -49,6 +52,28 @@ where
         self.root = normalize_root(root);
         self
     }
+
+    /// Get the underlying adapter.
+    pub(crate) fn adapter(&self) -> &S {
+        &self.kv
+    }
+
+    /// Dump all entries of the underlying adapter into a snapshot.
+    ///
+    /// The snapshot covers the whole adapter regardless of root, and can be
+    /// decoded by [`decode_snapshot`](super::decode_snapshot).
+    pub fn snapshot(&self) -> Result<Bytes> {
+        let mut entries = Vec::new();
+        for key in self.kv.blocking_scan("")? {
+            if let Some(value) = self.kv.blocking_get(&key)? {
+                entries.push((key, value));
+            }
+        }
+
+        Ok(encode_snapshot(
+            entries.iter().map(|(k, v)| (k.as_str(), v)),
+        ))
+    }
 }
 
 impl<S: Adapter> Access for Backend<S> {
@@ -121,13 +146,17 @@ impl<S: Adapter> Access for Backend<S> {
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
         let p = build_abs_path(&self.root, path);
 
-        Ok((RpWrite::new(), KvWriter::new(self.kv.clone(), p, args)))
+        // All data is buffered and set at once while closing.
+        let rp = RpWrite::new().with_mode(WriteMode::OneShot);
+        Ok((rp, KvWriter::new(self.kv.clone(), p, args)))
     }
 
     fn blocking_write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::BlockingWriter)> {
         let p = build_abs_path(&self.root, path);
 
-        Ok((RpWrite::new(), KvWriter::new(self.kv.clone(), p, args)))
+        // All data is buffered and set at once while closing.
+        let rp = RpWrite::new().with_mode(WriteMode::OneShot);
+        Ok((rp, KvWriter::new(self.kv.clone(), p, args)))
     }
 
     async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
@@ -158,6 +187,16 @@ impl<S: Adapter> Access for Backend<S> {
         }
     }
 
+    async fn exists(&self, path: &str, _: OpStat) -> Result<bool> {
+        let p = build_abs_path(&self.root, path);
+
+        if p == build_abs_path(&self.root, "") {
+            Ok(true)
+        } else {
+            self.kv.exists(&p).await
+        }
+    }
+
     async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
         let p = build_abs_path(&self.root, path);
 
@@ -165,6 +204,16 @@ impl<S: Adapter> Access for Backend<S> {
         Ok(RpDelete::default())
     }
 
+    fn blocking_exists(&self, path: &str, _: OpStat) -> Result<bool> {
+        let p = build_abs_path(&self.root, path);
+
+        if p == build_abs_path(&self.root, "") {
+            Ok(true)
+        } else {
+            self.kv.blocking_exists(&p)
+        }
+    }
+
     fn blocking_delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
         let p = build_abs_path(&self.root, path);
 
@@ -269,6 +318,10 @@ impl<S> KvWriter<S> {
         if let Some(v) = self.op.content_type() {
             metadata.set_content_type(v);
         }
+        // Content has been verified against it by `CompleteLayer`.
+        if let Some(v) = self.op.content_md5() {
+            metadata.set_content_md5(v);
+        }
 
         Value { metadata, value }
     }
@@ -282,7 +335,7 @@ impl<S: Adapter> oio::Write for KvWriter<S> {
         Ok(())
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         let value = match &self.value {
             Some(value) => value.clone(),
             None => {
@@ -291,8 +344,9 @@ impl<S: Adapter> oio::Write for KvWriter<S> {
                 value
             }
         };
+        let meta = value.metadata.clone();
         self.kv.set(&self.path, value).await?;
-        Ok(())
+        Ok(meta)
     }
 
     async fn abort(&mut self) -> Result<()> {
@@ -309,7 +363,7 @@ impl<S: Adapter> oio::BlockingWrite for KvWriter<S> {
         Ok(())
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         let kv = self.kv.clone();
         let value = match &self.value {
             Some(value) => value.clone(),
@@ -320,7 +374,8 @@ impl<S: Adapter> oio::BlockingWrite for KvWriter<S> {
             }
         };
 
+        let meta = value.metadata.clone();
         kv.blocking_set(&self.path, value)?;
-        Ok(())
+        Ok(meta)
     }
 }
@@ -27,3 +27,8 @@ pub use api::Value;
 
 mod backend;
 pub use backend::Backend;
+
+mod snapshot;
+pub use snapshot::decode_snapshot;
+pub use snapshot::encode_snapshot;
+pub use snapshot::SNAPSHOT_VERSION;
@@ -0,0 +1,238 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Snapshot encodes all entries of a typed kv adapter into a portable binary
+//! blob.
+//!
+//! The layout (all integers are little endian):
+//!
+//! ```text
+//! magic: b"ODKV" | version: u8 | count: u64 | entry * count
+//!
+//! entry:
+//!   key: u32 len + bytes
+//!   mode: u8 (0 for file, 1 for dir)
+//!   content_type, content_disposition, cache_control: u8 flag + u32 len + bytes
+//!   last_modified: u8 flag + i64 secs + u32 nanos
+//!   value: u64 len + bytes
+//! ```
+
+use bytes::Buf;
+use bytes::BufMut;
+use bytes::Bytes;
+use bytes::BytesMut;
+use chrono::TimeZone;
+use chrono::Utc;
+
+use super::Value;
+use crate::*;
+
+const MAGIC: &[u8; 4] = b"ODKV";
+
+/// The version of snapshot format written by this build.
+pub const SNAPSHOT_VERSION: u8 = 1;
+
+/// Encode entries into a snapshot.
+pub fn encode_snapshot<'a>(entries: impl IntoIterator<Item = (&'a str, &'a Value)>) -> Bytes {
+    let entries: Vec<_> = entries.into_iter().collect();
+
+    let mut buf = BytesMut::new();
+    buf.put_slice(MAGIC);
+    buf.put_u8(SNAPSHOT_VERSION);
+    buf.put_u64_le(entries.len() as u64);
+
+    for (key, value) in entries {
+        // Values stored in typed kv always carry all their metadata, mark it
+        // as complete so the getters below can read every field.
+        let metadata = value.metadata.clone().with_metakey(Metakey::Complete);
+
+        put_str(&mut buf, key);
+        buf.put_u8(if metadata.is_dir() { 1 } else { 0 });
+        put_opt_str(&mut buf, metadata.content_type());
+        put_opt_str(&mut buf, metadata.content_disposition());
+        put_opt_str(&mut buf, metadata.cache_control());
+        match metadata.last_modified() {
+            Some(t) => {
+                buf.put_u8(1);
+                buf.put_i64_le(t.timestamp());
+                buf.put_u32_le(t.timestamp_subsec_nanos());
+            }
+            None => buf.put_u8(0),
+        }
+        buf.put_u64_le(value.value.len() as u64);
+        for bs in value.value.clone() {
+            buf.put_slice(&bs);
+        }
+    }
+
+    buf.freeze()
+}
+
+/// Decode entries from a snapshot.
+///
+/// Returns [`ErrorKind::Unsupported`] if the snapshot is written by a newer
+/// format version, and [`ErrorKind::Unexpected`] if it's malformed.
+pub fn decode_snapshot(mut bs: Bytes) -> Result<Vec<(String, Value)>> {
+    let r = &mut bs;
+
+    if take(r, MAGIC.len())? != MAGIC[..] {
+        return Err(new_snapshot_error("snapshot magic mismatch"));
+    }
+    let version = take(r, 1)?[0];
+    if version > SNAPSHOT_VERSION {
+        return Err(Error::new(
+            ErrorKind::Unsupported,
+            "snapshot is written by a newer format version",
+        )
+        .with_context("version", version.to_string())
+        .with_context("supported_version", SNAPSHOT_VERSION.to_string()));
+    }
+
+    let count = take(r, 8)?.get_u64_le();
+    let mut entries = Vec::new();
+    for _ in 0..count {
+        let key = get_str(r)?;
+        let mode = match take(r, 1)?[0] {
+            0 => EntryMode::FILE,
+            1 => EntryMode::DIR,
+            v => {
+                return Err(new_snapshot_error("snapshot entry mode is invalid")
+                    .with_context("mode", v.to_string()))
+            }
+        };
+
+        let mut metadata = Metadata::new(mode).with_metakey(Metakey::Complete);
+        if let Some(v) = get_opt_str(r)? {
+            metadata.set_content_type(&v);
+        }
+        if let Some(v) = get_opt_str(r)? {
+            metadata.set_content_disposition(&v);
+        }
+        if let Some(v) = get_opt_str(r)? {
+            metadata.set_cache_control(&v);
+        }
+        if take(r, 1)?[0] == 1 {
+            let secs = take(r, 8)?.get_i64_le();
+            let nanos = take(r, 4)?.get_u32_le();
+            let t = Utc.timestamp_opt(secs, nanos).single().ok_or_else(|| {
+                new_snapshot_error("snapshot last modified is out of range")
+                    .with_context("secs", secs.to_string())
+            })?;
+            metadata.set_last_modified(t);
+        }
+
+        let len = take(r, 8)?.get_u64_le() as usize;
+        let value = Buffer::from(take(r, len)?);
+        metadata.set_content_length(value.len() as u64);
+
+        entries.push((key, Value { metadata, value }));
+    }
+
+    if r.has_remaining() {
+        return Err(new_snapshot_error("snapshot has trailing bytes"));
+    }
+    Ok(entries)
+}
+
+fn new_snapshot_error(msg: &'static str) -> Error {
+    Error::new(ErrorKind::Unexpected, msg)
+}
+
+fn take(r: &mut Bytes, n: usize) -> Result<Bytes> {
+    if r.remaining() < n {
+        return Err(new_snapshot_error("snapshot is truncated"));
+    }
+    Ok(r.split_to(n))
+}
+
+fn put_str(buf: &mut BytesMut, s: &str) {
+    buf.put_u32_le(s.len() as u32);
+    buf.put_slice(s.as_bytes());
+}
+
+fn put_opt_str(buf: &mut BytesMut, s: Option<&str>) {
+    match s {
+        Some(s) => {
+            buf.put_u8(1);
+            put_str(buf, s);
+        }
+        None => buf.put_u8(0),
+    }
+}
+
+fn get_str(r: &mut Bytes) -> Result<String> {
+    let len = take(r, 4)?.get_u32_le() as usize;
+    String::from_utf8(take(r, len)?.to_vec())
+        .map_err(|err| new_snapshot_error("snapshot contains invalid utf-8").set_source(err))
+}
+
+fn get_opt_str(r: &mut Bytes) -> Result<Option<String>> {
+    match take(r, 1)?[0] {
+        0 => Ok(None),
+        _ => get_str(r).map(Some),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let last_modified = Utc.timestamp_opt(1_700_000_000, 42).unwrap();
+        let file = Value {
+            metadata: Metadata::new(EntryMode::FILE)
+                .with_content_type("text/plain".to_string())
+                .with_last_modified(last_modified),
+            value: Buffer::from("hello"),
+        };
+        let empty = Value {
+            metadata: Metadata::new(EntryMode::FILE),
+            value: Buffer::new(),
+        };
+        let dir = Value::new_dir();
+
+        let bs = encode_snapshot([("a/b/c", &file), ("a/empty", &empty), ("a/b/", &dir)]);
+        let entries = decode_snapshot(bs.clone()).unwrap();
+        assert_eq!(entries.len(), 3);
+
+        assert_eq!(entries[0].0, "a/b/c");
+        assert_eq!(entries[0].1.metadata.content_type(), Some("text/plain"));
+        assert_eq!(entries[0].1.metadata.last_modified(), Some(last_modified));
+        assert_eq!(entries[0].1.value.to_bytes(), "hello");
+
+        assert_eq!(entries[1].0, "a/empty");
+        assert!(entries[1].1.value.is_empty());
+        assert_eq!(entries[1].1.metadata.last_modified(), None);
+
+        assert_eq!(entries[2].0, "a/b/");
+        assert!(entries[2].1.metadata.is_dir());
+
+        // Truncated snapshot.
+        let err = decode_snapshot(bs.slice(..bs.len() - 1)).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+
+    #[test]
+    fn test_snapshot_newer_version() {
+        let mut bs = encode_snapshot([]).to_vec();
+        bs[MAGIC.len()] = SNAPSHOT_VERSION + 1;
+
+        let err = decode_snapshot(bs.into()).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+}
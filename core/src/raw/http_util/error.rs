@@ -18,6 +18,7 @@
 use http::response::Parts;
 use http::Uri;
 
+use super::parse_retry_after;
 use crate::Error;
 use crate::ErrorKind;
 
@@ -62,6 +63,10 @@ pub fn with_error_response_context(mut err: Error, mut parts: Parts) -> Error {
     parts.headers.remove("WWW-Authenticate");
     parts.headers.remove("Proxy-Authenticate");
 
+    if let Some(v) = parse_retry_after(&parts.headers) {
+        err = err.with_retry_after(v);
+    }
+
     err = err.with_context("response", format!("{parts:?}"));
 
     err
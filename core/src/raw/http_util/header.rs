@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::time::Duration;
+
 use base64::engine::general_purpose;
 use base64::Engine;
 use chrono::DateTime;
@@ -28,6 +30,7 @@ use http::header::CONTENT_TYPE;
 use http::header::ETAG;
 use http::header::LAST_MODIFIED;
 use http::header::LOCATION;
+use http::header::RETRY_AFTER;
 use http::HeaderMap;
 use http::HeaderName;
 use http::HeaderValue;
@@ -98,6 +101,20 @@ pub fn parse_last_modified(headers: &HeaderMap) -> Result<Option<DateTime<Utc>>>
         .transpose()
 }
 
+/// Parse the delay to wait before retrying from the `Retry-After` header.
+///
+/// Both delay seconds and http date are supported, a date in the past
+/// means retrying immediately. Invalid values are ignored.
+pub fn parse_retry_after(headers: &HeaderMap) -> Option<Duration> {
+    let v = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(secs) = v.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let at = parse_datetime_from_rfc2822(v).ok()?;
+    Some((at - Utc::now()).to_std().unwrap_or_default())
+}
+
 /// Parse etag from header map.
 pub fn parse_etag(headers: &HeaderMap) -> Result<Option<&str>> {
     parse_header_to_str(headers, ETAG)
@@ -189,6 +206,28 @@ pub fn parse_into_metadata(path: &str, headers: &HeaderMap) -> Result<Metadata>
     Ok(m)
 }
 
+/// parse_into_write_metadata will parse the headers of a write response into
+/// Metadata.
+///
+/// # Notes
+///
+/// Only etag and last modified are parsed here. Headers like content length
+/// and content md5 of a write response describe the response body or the
+/// uploaded chunk instead of the written file.
+pub fn parse_into_write_metadata(headers: &HeaderMap) -> Result<Metadata> {
+    let mut m = Metadata::new(EntryMode::FILE);
+
+    if let Some(v) = parse_etag(headers)? {
+        m.set_etag(v);
+    }
+
+    if let Some(v) = parse_last_modified(headers)? {
+        m.set_last_modified(v);
+    }
+
+    Ok(m)
+}
+
 /// format content md5 header by given input.
 pub fn format_content_md5(bs: &[u8]) -> String {
     let mut hasher = md5::Md5::new();
@@ -246,6 +285,7 @@ pub fn build_header_value(v: &str) -> Result<HeaderValue> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::Metakey;
 
     /// Test cases is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_DeleteObjects.html
     #[test]
@@ -307,4 +347,41 @@ mod tests {
             assert_eq!(actual, expected)
         }
     }
+
+    #[test]
+    fn test_parse_into_write_metadata() {
+        let mut headers = HeaderMap::new();
+        headers.insert(ETAG, HeaderValue::from_static("\"abc\""));
+        headers.insert(CONTENT_LENGTH, HeaderValue::from_static("0"));
+        headers.insert(
+            LAST_MODIFIED,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+
+        let meta = parse_into_write_metadata(&headers).expect("parse must success");
+        assert!(meta.is_file());
+        assert_eq!(meta.etag(), Some("\"abc\""));
+        assert!(meta.last_modified().is_some());
+        // Content length of the response body must be ignored.
+        assert!(!meta.metakey().contains(Metakey::ContentLength));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(parse_retry_after(&headers), None);
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+        assert_eq!(parse_retry_after(&headers), Some(Duration::from_secs(120)));
+
+        // Dates in the past mean retrying immediately.
+        headers.insert(
+            RETRY_AFTER,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(parse_retry_after(&headers), Some(Duration::ZERO));
+
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("soon"));
+        assert_eq!(parse_retry_after(&headers), None);
+    }
 }
@@ -42,8 +42,10 @@ pub use header::parse_content_type;
 pub use header::parse_etag;
 pub use header::parse_header_to_str;
 pub use header::parse_into_metadata;
+pub use header::parse_into_write_metadata;
 pub use header::parse_last_modified;
 pub use header::parse_location;
+pub use header::parse_retry_after;
 
 mod uri;
 pub use uri::percent_decode_path;
@@ -66,3 +68,10 @@ pub use multipart::FormDataPart;
 pub use multipart::MixedPart;
 pub use multipart::Multipart;
 pub use multipart::Part;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+pub(crate) use mock::MockResponse;
+#[cfg(test)]
+pub(crate) use mock::MockServer;
@@ -186,6 +186,10 @@ pub trait LayeredAccess: Send + Sync + Debug + Unpin + 'static {
         self.inner().stat(path, args)
     }
 
+    fn exists(&self, path: &str, args: OpStat) -> impl Future<Output = Result<bool>> + MaybeSend {
+        self.inner().exists(path, args)
+    }
+
     fn delete(
         &self,
         path: &str,
@@ -200,6 +204,30 @@ pub trait LayeredAccess: Send + Sync + Debug + Unpin + 'static {
         args: OpList,
     ) -> impl Future<Output = Result<(RpList, Self::Lister)>> + MaybeSend;
 
+    fn initiate_multipart(
+        &self,
+        path: &str,
+        args: OpWrite,
+    ) -> impl Future<Output = Result<RpInitiateMultipart>> + MaybeSend {
+        self.inner().initiate_multipart(path, args)
+    }
+
+    fn complete_multipart(
+        &self,
+        path: &str,
+        args: OpCompleteMultipart,
+    ) -> impl Future<Output = Result<RpCompleteMultipart>> + MaybeSend {
+        self.inner().complete_multipart(path, args)
+    }
+
+    fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> impl Future<Output = Result<RpAbortMultipart>> + MaybeSend {
+        self.inner().abort_multipart(path, args)
+    }
+
     fn batch(&self, args: OpBatch) -> impl Future<Output = Result<RpBatch>> + MaybeSend {
         self.inner().batch(args)
     }
@@ -232,6 +260,10 @@ pub trait LayeredAccess: Send + Sync + Debug + Unpin + 'static {
         self.inner().blocking_stat(path, args)
     }
 
+    fn blocking_exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        self.inner().blocking_exists(path, args)
+    }
+
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         self.inner().blocking_delete(path, args)
     }
@@ -275,6 +307,10 @@ impl<L: LayeredAccess> Access for L {
         (self as &L).stat(path, args).await
     }
 
+    async fn exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        (self as &L).exists(path, args).await
+    }
+
     async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         (self as &L).delete(path, args).await
     }
@@ -283,6 +319,26 @@ impl<L: LayeredAccess> Access for L {
         (self as &L).list(path, args).await
     }
 
+    async fn initiate_multipart(&self, path: &str, args: OpWrite) -> Result<RpInitiateMultipart> {
+        (self as &L).initiate_multipart(path, args).await
+    }
+
+    async fn complete_multipart(
+        &self,
+        path: &str,
+        args: OpCompleteMultipart,
+    ) -> Result<RpCompleteMultipart> {
+        (self as &L).complete_multipart(path, args).await
+    }
+
+    async fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> Result<RpAbortMultipart> {
+        (self as &L).abort_multipart(path, args).await
+    }
+
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
         (self as &L).batch(args).await
     }
@@ -315,6 +371,10 @@ impl<L: LayeredAccess> Access for L {
         (self as &L).blocking_stat(path, args)
     }
 
+    fn blocking_exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        (self as &L).blocking_exists(path, args)
+    }
+
     fn blocking_delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
         (self as &L).blocking_delete(path, args)
     }
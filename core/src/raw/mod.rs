@@ -61,6 +61,9 @@ pub use serde_util::*;
 mod chrono_util;
 pub use chrono_util::*;
 
+mod oauth2_util;
+pub use oauth2_util::*;
+
 #[cfg(feature = "internal-tokio-rt")]
 mod tokio_util;
 #[cfg(feature = "internal-tokio-rt")]
@@ -0,0 +1,102 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::DateTime;
+use chrono::Utc;
+
+/// Callback to persist the refresh token rotated by the OAuth server.
+pub type RefreshTokenCallback = Arc<dyn Fn(&str) + Send + Sync>;
+
+/// The default duration to refresh the access token before it expires.
+pub const DEFAULT_TOKEN_REFRESH_SKEW: Duration = Duration::from_secs(120);
+
+/// Calculate the time to refresh the access token that expires in
+/// `expires_in` seconds.
+///
+/// The skew is capped to the half of the token lifetime, so that a large
+/// skew won't make us refresh on every request.
+pub fn token_expires_at(expires_in: u64, skew: Duration) -> DateTime<Utc> {
+    let skew = skew.as_secs().min(expires_in / 2);
+
+    Utc::now()
+        + chrono::TimeDelta::try_seconds((expires_in - skew) as i64)
+            .expect("expires_in must be valid seconds")
+}
+
+/// Replace `current` with the refresh token returned by the OAuth server.
+///
+/// Some servers rotate the refresh token on every refresh, the old one will
+/// be invalid since then. The callback is only called when the token
+/// actually changed.
+pub fn rotate_refresh_token(
+    current: &mut String,
+    rotated: Option<String>,
+    callback: Option<&RefreshTokenCallback>,
+) {
+    let Some(rotated) = rotated else {
+        return;
+    };
+    if rotated == *current {
+        return;
+    }
+
+    *current = rotated;
+    if let Some(f) = callback {
+        f(current);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    #[test]
+    fn test_token_expires_at_caps_skew() {
+        let now = Utc::now();
+
+        let at = token_expires_at(3600, Duration::from_secs(120));
+        assert!(at - now >= chrono::TimeDelta::try_seconds(3480).unwrap());
+
+        // A skew larger than the lifetime is capped to the half of it.
+        let at = token_expires_at(60, Duration::from_secs(3600));
+        assert!(at - now >= chrono::TimeDelta::try_seconds(30).unwrap());
+    }
+
+    #[test]
+    fn test_rotate_refresh_token() {
+        let seen = Arc::new(Mutex::new(vec![]));
+        let cb: RefreshTokenCallback = {
+            let seen = seen.clone();
+            Arc::new(move |v: &str| seen.lock().unwrap().push(v.to_string()))
+        };
+
+        let mut token = "old".to_string();
+        rotate_refresh_token(&mut token, None, Some(&cb));
+        rotate_refresh_token(&mut token, Some("old".to_string()), Some(&cb));
+        assert_eq!(token, "old");
+        assert!(seen.lock().unwrap().is_empty());
+
+        rotate_refresh_token(&mut token, Some("new".to_string()), Some(&cb));
+        assert_eq!(token, "new");
+        assert_eq!(*seen.lock().unwrap(), vec!["new".to_string()]);
+    }
+}
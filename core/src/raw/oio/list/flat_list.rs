@@ -54,12 +54,29 @@ use crate::*;
 /// Especially, for storage services that can't return dirs first, ToFlatLister
 /// may output parent dirs' files before nested dirs, this is expected because files
 /// always output directly while listing.
+///
+/// # Ordered
+///
+/// With [`FlatLister::with_ordered`], dirs will be walked in top down way instead:
+/// a dir is output before its nested entries. As long as every dir is listed in
+/// lexicographical order, the output will be in lexicographical order too:
+///
+/// ```txt
+/// dir_x/
+/// dir_x/dir_y/
+/// dir_x/dir_y/dir_z/
+/// dir_x/dir_y/dir_z/file_c
+/// dir_x/dir_y/file_b
+/// dir_x/file_a
+/// ```
 pub struct FlatLister<A: Access, L> {
     acc: A,
     root: String,
+    ordered: bool,
+    sort: bool,
 
     next_dir: Option<oio::Entry>,
-    active_lister: Vec<(Option<oio::Entry>, L)>,
+    active_lister: Vec<(Option<oio::Entry>, oio::SortedLister<L>)>,
 }
 
 /// # Safety
@@ -80,10 +97,23 @@ where
         FlatLister {
             acc,
             root: path.to_string(),
+            ordered: false,
+            sort: false,
             next_dir: Some(oio::Entry::new(path, Metadata::new(EntryMode::DIR))),
             active_lister: vec![],
         }
     }
+
+    /// Walk dirs in top down way to output entries in lexicographical order.
+    ///
+    /// Set `sort` to `true` if the service can't list a dir in order, entries
+    /// of every dir will be buffered and sorted. Only the remaining entries
+    /// of dirs on the current path are kept, see [`oio::SortedLister`].
+    pub fn with_ordered(mut self, sort: bool) -> Self {
+        self.ordered = true;
+        self.sort = sort;
+        self
+    }
 }
 
 impl<A, L> oio::List for FlatLister<A, L>
@@ -95,7 +125,10 @@ where
         loop {
             if let Some(de) = self.next_dir.take() {
                 let (_, l) = self.acc.list(de.path(), OpList::new()).await?;
-                self.active_lister.push((Some(de), l));
+                let l = oio::SortedLister::new(l, self.sort);
+                // The dir has been output before listing in ordered mode.
+                let de = (!self.ordered).then_some(de);
+                self.active_lister.push((de, l));
             }
 
             let (de, lister) = match self.active_lister.last_mut() {
@@ -104,6 +137,10 @@ where
             };
 
             match lister.next().await? {
+                Some(v) if v.mode().is_dir() && self.ordered => {
+                    self.next_dir = Some(v.clone());
+                    return Ok(Some(v));
+                }
                 Some(v) if v.mode().is_dir() => {
                     self.next_dir = Some(v);
                     continue;
@@ -138,8 +175,10 @@ where
         loop {
             if let Some(de) = self.next_dir.take() {
                 let (_, l) = self.acc.blocking_list(de.path(), OpList::new())?;
-
-                self.active_lister.push((Some(de), l))
+                let l = oio::SortedLister::new(l, self.sort);
+                // The dir has been output before listing in ordered mode.
+                let de = (!self.ordered).then_some(de);
+                self.active_lister.push((de, l))
             }
 
             let (de, lister) = match self.active_lister.last_mut() {
@@ -148,6 +187,10 @@ where
             };
 
             match lister.next()? {
+                Some(v) if v.mode().is_dir() && self.ordered => {
+                    self.next_dir = Some(v.clone());
+                    return Ok(Some(v));
+                }
                 Some(v) if v.mode().is_dir() => {
                     self.next_dir = Some(v);
                     continue;
@@ -264,6 +307,30 @@ mod tests {
             oio::Entry::new("x/x/x/x", Metadata::new(EntryMode::FILE))
         );
 
+        Ok(())
+    }
+    #[test]
+    fn test_blocking_list_ordered() -> Result<()> {
+        let _ = tracing_subscriber::fmt().with_test_writer().try_init();
+
+        let mut map = HashMap::default();
+        map.insert("x/", vec!["x/z", "x/b/", "x/a-b", "x/a/"]);
+        map.insert("x/a/", vec!["x/a/y", "x/a/b/"]);
+        map.insert("x/a/b/", vec![]);
+        map.insert("x/b/", vec!["x/b/x"]);
+        let acc = MockService { map };
+        let mut lister = FlatLister::new(acc, "x/").with_ordered(true);
+
+        let mut entries = Vec::default();
+        while let Some(e) = lister.next()? {
+            entries.push(e.path().to_string())
+        }
+
+        assert_eq!(
+            entries,
+            vec!["x/a-b", "x/a/", "x/a/b/", "x/a/y", "x/b/", "x/b/x", "x/z"]
+        );
+
         Ok(())
     }
 }
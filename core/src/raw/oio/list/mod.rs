@@ -35,3 +35,6 @@ pub use hierarchy_list::HierarchyLister;
 
 mod prefix_list;
 pub use prefix_list::PrefixLister;
+
+mod sorted_list;
+pub use sorted_list::SortedLister;
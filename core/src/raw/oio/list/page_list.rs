@@ -62,6 +62,20 @@ pub struct PageContext {
     pub entries: VecDeque<oio::Entry>,
 }
 
+impl PageContext {
+    /// Sort entries of current page by path.
+    ///
+    /// Services like s3 return dirs and files in two sorted lists for the same
+    /// page, call this after pushing all of them to return entries in
+    /// lexicographical order. Stable sort detects the sorted runs, so this is
+    /// a linear merge in most cases.
+    pub fn sort_entries(&mut self) {
+        self.entries
+            .make_contiguous()
+            .sort_by(|a, b| a.path().cmp(b.path()));
+    }
+}
+
 /// PageLister implements [`oio::List`] based on [`PageList`].
 pub struct PageLister<L: PageList> {
     inner: L,
@@ -0,0 +1,99 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::collections::VecDeque;
+
+use crate::raw::*;
+use crate::*;
+
+/// SortedLister is used to list entries of a dir in lexicographical order
+/// for services that can't guarantee it.
+///
+/// If `sort` is disabled, entries will be forwarded as is.
+///
+/// # Memory
+///
+/// Entries of the inner lister come in no order, the smallest one is only
+/// known after all of them have been seen. So all entries of the dir will be
+/// buffered and sorted by path before returning the first one, and memory
+/// usage grows with the size of the dir.
+///
+/// `CompleteLayer` only enables sorting if users opt in via `sort_unordered`,
+/// otherwise ordered list on such services returns `Unsupported`.
+pub struct SortedLister<L> {
+    inner: L,
+    sort: bool,
+    entries: Option<VecDeque<oio::Entry>>,
+}
+
+impl<L> SortedLister<L> {
+    /// Create a new sorted lister.
+    pub fn new(inner: L, sort: bool) -> Self {
+        Self {
+            inner,
+            sort,
+            entries: None,
+        }
+    }
+
+    fn sort_entries(mut entries: Vec<oio::Entry>) -> VecDeque<oio::Entry> {
+        entries.sort_by(|a, b| a.path().cmp(b.path()));
+        entries.into()
+    }
+}
+
+impl<L> oio::List for SortedLister<L>
+where
+    L: oio::List,
+{
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        if !self.sort {
+            return self.inner.next().await;
+        }
+
+        if self.entries.is_none() {
+            let mut entries = Vec::new();
+            while let Some(e) = self.inner.next().await? {
+                entries.push(e);
+            }
+            self.entries = Some(Self::sort_entries(entries));
+        }
+
+        Ok(self.entries.as_mut().and_then(|v| v.pop_front()))
+    }
+}
+
+impl<L> oio::BlockingList for SortedLister<L>
+where
+    L: oio::BlockingList,
+{
+    fn next(&mut self) -> Result<Option<oio::Entry>> {
+        if !self.sort {
+            return self.inner.next();
+        }
+
+        if self.entries.is_none() {
+            let mut entries = Vec::new();
+            while let Some(e) = self.inner.next()? {
+                entries.push(e);
+            }
+            self.entries = Some(Self::sort_entries(entries));
+        }
+
+        Ok(self.entries.as_mut().and_then(|v| v.pop_front()))
+    }
+}
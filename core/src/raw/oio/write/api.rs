@@ -68,6 +68,15 @@ impl From<WriteOperation> for &'static str {
     }
 }
 
+/// Fill the content length with the size that has been written if the
+/// service didn't return it.
+pub(crate) fn with_written_size(mut meta: Metadata, size: u64) -> Metadata {
+    if !meta.metakey().contains(Metakey::ContentLength) {
+        meta.set_content_length(size);
+    }
+    meta
+}
+
 /// Writer is a type erased [`Write`]
 pub type Writer = Box<dyn WriteDyn>;
 
@@ -82,7 +91,10 @@ pub trait Write: Unpin + Send + Sync {
     fn write(&mut self, bs: Buffer) -> impl Future<Output = Result<()>> + MaybeSend;
 
     /// Close the writer and make sure all data has been flushed.
-    fn close(&mut self) -> impl Future<Output = Result<()>> + MaybeSend;
+    ///
+    /// Returns the metadata of the written file. Only the fields returned
+    /// by the service are set, see `write_returns_*` in [`Capability`].
+    fn close(&mut self) -> impl Future<Output = Result<Metadata>> + MaybeSend;
 
     /// Abort the pending writer.
     fn abort(&mut self) -> impl Future<Output = Result<()>> + MaybeSend;
@@ -93,7 +105,7 @@ impl Write for () {
         unimplemented!("write is required to be implemented for oio::Write")
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         Err(Error::new(
             ErrorKind::Unsupported,
             "output writer doesn't support close",
@@ -111,7 +123,7 @@ impl Write for () {
 pub trait WriteDyn: Unpin + Send + Sync {
     fn write_dyn(&mut self, bs: Buffer) -> BoxedFuture<Result<()>>;
 
-    fn close_dyn(&mut self) -> BoxedFuture<Result<()>>;
+    fn close_dyn(&mut self) -> BoxedFuture<Result<Metadata>>;
 
     fn abort_dyn(&mut self) -> BoxedFuture<Result<()>>;
 }
@@ -121,7 +133,7 @@ impl<T: Write + ?Sized> WriteDyn for T {
         Box::pin(self.write(bs))
     }
 
-    fn close_dyn(&mut self) -> BoxedFuture<Result<()>> {
+    fn close_dyn(&mut self) -> BoxedFuture<Result<Metadata>> {
         Box::pin(self.close())
     }
 
@@ -135,7 +147,7 @@ impl<T: WriteDyn + ?Sized> Write for Box<T> {
         self.deref_mut().write_dyn(bs).await
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         self.deref_mut().close_dyn().await
     }
 
@@ -161,7 +173,9 @@ pub trait BlockingWrite: Send + Sync + 'static {
     fn write(&mut self, bs: Buffer) -> Result<()>;
 
     /// Close the writer and make sure all data has been flushed.
-    fn close(&mut self) -> Result<()>;
+    ///
+    /// Returns the metadata of the written file like [`Write::close`].
+    fn close(&mut self) -> Result<Metadata>;
 }
 
 impl BlockingWrite for () {
@@ -171,7 +185,7 @@ impl BlockingWrite for () {
         unimplemented!("write is required to be implemented for oio::BlockingWrite")
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         Err(Error::new(
             ErrorKind::Unsupported,
             "output writer doesn't support close",
@@ -187,7 +201,7 @@ impl<T: BlockingWrite + ?Sized> BlockingWrite for Box<T> {
         (**self).write(bs)
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         (**self).close()
     }
 }
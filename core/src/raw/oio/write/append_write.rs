@@ -16,6 +16,9 @@
 // under the License.
 
 use std::future::Future;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
 
 use crate::raw::*;
 use crate::*;
@@ -43,12 +46,14 @@ pub trait AppendWrite: Send + Sync + Unpin + 'static {
     fn offset(&self) -> impl Future<Output = Result<u64>> + MaybeSend;
 
     /// Append the data to the end of this object.
+    ///
+    /// Returns the metadata of the object after this append.
     fn append(
         &self,
         offset: u64,
         size: u64,
         body: Buffer,
-    ) -> impl Future<Output = Result<()>> + MaybeSend;
+    ) -> impl Future<Output = Result<Metadata>> + MaybeSend;
 }
 
 /// AppendWriter will implements [`oio::Write`] based on append object.
@@ -59,7 +64,11 @@ pub trait AppendWrite: Send + Sync + Unpin + 'static {
 pub struct AppendWriter<W: AppendWrite> {
     inner: W,
 
+    /// The offset of the object before the first append.
+    start: Option<u64>,
     offset: Option<u64>,
+    requests: Option<Arc<AtomicUsize>>,
+    meta: Option<Metadata>,
 }
 
 /// # Safety
@@ -71,9 +80,20 @@ impl<W: AppendWrite> AppendWriter<W> {
     pub fn new(inner: W) -> Self {
         Self {
             inner,
+            start: None,
             offset: None,
+            requests: None,
+            meta: None,
         }
     }
+
+    /// Count every append request into `counter`.
+    ///
+    /// Usually the counter comes from [`RpWrite::append_request_counter`].
+    pub fn with_request_counter(mut self, counter: Arc<AtomicUsize>) -> Self {
+        self.requests = Some(counter);
+        self
+    }
 }
 
 impl<W> oio::Write for AppendWriter<W>
@@ -85,23 +105,166 @@ where
             Some(offset) => offset,
             None => {
                 let offset = self.inner.offset().await?;
+                self.start = Some(offset);
                 self.offset = Some(offset);
                 offset
             }
         };
 
         let size = bs.len();
-        self.inner.append(offset, size as u64, bs).await?;
+        if let Some(requests) = &self.requests {
+            requests.fetch_add(1, Ordering::Relaxed);
+        }
+        let meta = self.inner.append(offset, size as u64, bs).await?;
+        // Prefer the offset reported by the service for the first append.
+        if self.meta.is_none() && meta.contains_metakey(Metakey::AppendOffset) {
+            if let Some(v) = meta.append_offset() {
+                self.start = Some(v);
+            }
+        }
         // Update offset after succeed.
         self.offset = Some(offset + size as u64);
+        self.meta = Some(meta);
         Ok(())
     }
 
-    async fn close(&mut self) -> Result<()> {
-        Ok(())
+    async fn close(&mut self) -> Result<Metadata> {
+        let meta = self
+            .meta
+            .take()
+            .unwrap_or_else(|| Metadata::new(EntryMode::FILE));
+        // Nothing has been appended, the data lands at the current end of object.
+        let (start, offset) = match (self.start, self.offset) {
+            (Some(start), Some(offset)) => (start, offset),
+            _ => {
+                let offset = self.inner.offset().await?;
+                (offset, offset)
+            }
+        };
+        // The offset after the last append is the size of the object.
+        Ok(oio::with_written_size(meta, offset).with_append_offset(start))
     }
 
     async fn abort(&mut self) -> Result<()> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raw::oio::Write;
+
+    struct TestWrite;
+
+    impl AppendWrite for TestWrite {
+        async fn offset(&self) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn append(&self, _: u64, _: u64, _: Buffer) -> Result<Metadata> {
+            Ok(Metadata::new(EntryMode::FILE).with_etag("etag".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_append_request_counter() {
+        let rp = RpWrite::new().with_mode(WriteMode::Append);
+        let mut w = AppendWriter::new(TestWrite).with_request_counter(rp.append_request_counter());
+
+        for _ in 0..3 {
+            w.write(Buffer::from("hello")).await.unwrap();
+        }
+        let meta = w.close().await.unwrap();
+        assert_eq!(meta.etag(), Some("etag"));
+        assert_eq!(meta.content_length(), 15);
+        assert_eq!(meta.append_offset(), Some(0));
+
+        assert_eq!(rp.mode(), Some(WriteMode::Append));
+        assert_eq!(rp.append_requests(), 3);
+    }
+
+    struct OffsetWrite(u64);
+
+    impl AppendWrite for OffsetWrite {
+        async fn offset(&self) -> Result<u64> {
+            Ok(self.0)
+        }
+
+        async fn append(&self, _: u64, _: u64, _: Buffer) -> Result<Metadata> {
+            Ok(Metadata::new(EntryMode::FILE))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_append_offset() {
+        let mut w = AppendWriter::new(OffsetWrite(100));
+        w.write(Buffer::from("hello")).await.unwrap();
+        w.write(Buffer::from("world")).await.unwrap();
+        let meta = w.close().await.unwrap();
+        assert_eq!(meta.append_offset(), Some(100));
+        assert_eq!(meta.content_length(), 110);
+
+        let mut w = AppendWriter::new(OffsetWrite(100));
+        let meta = w.close().await.unwrap();
+        assert_eq!(meta.append_offset(), Some(100));
+        assert_eq!(meta.content_length(), 100);
+    }
+
+    /// Report the offset at which every append is committed.
+    struct ReportedOffsetWrite;
+
+    impl AppendWrite for ReportedOffsetWrite {
+        async fn offset(&self) -> Result<u64> {
+            Ok(100)
+        }
+
+        async fn append(&self, offset: u64, _: u64, _: Buffer) -> Result<Metadata> {
+            Ok(Metadata::new(EntryMode::FILE).with_append_offset(offset + 20))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_append_offset_reported_by_service() {
+        let mut w = AppendWriter::new(ReportedOffsetWrite);
+        w.write(Buffer::from("hello")).await.unwrap();
+        w.write(Buffer::from("world")).await.unwrap();
+        let meta = w.close().await.unwrap();
+        // The offset of the first append reported by service wins.
+        assert_eq!(meta.append_offset(), Some(120));
+    }
+
+    /// Fail the first append and record the offsets of all appends.
+    #[derive(Default)]
+    struct FlakyWrite {
+        offsets: std::sync::Mutex<Vec<u64>>,
+    }
+
+    impl AppendWrite for FlakyWrite {
+        async fn offset(&self) -> Result<u64> {
+            Ok(0)
+        }
+
+        async fn append(&self, offset: u64, _: u64, _: Buffer) -> Result<Metadata> {
+            let mut offsets = self.offsets.lock().unwrap();
+            offsets.push(offset);
+            if offsets.len() == 1 {
+                return Err(Error::new(ErrorKind::Unexpected, "append failed").set_temporary());
+            }
+            Ok(Metadata::new(EntryMode::FILE))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_append_retry_offset() {
+        let mut w = AppendWriter::new(FlakyWrite::default());
+        assert!(w.write(Buffer::from("hello")).await.is_err());
+        // The retried append must be sent at the same offset.
+        w.write(Buffer::from("hello")).await.unwrap();
+        w.write(Buffer::from("world")).await.unwrap();
+
+        let meta = w.close().await.unwrap();
+        assert_eq!(meta.content_length(), 10);
+        assert_eq!(*w.inner.offsets.lock().unwrap(), vec![0, 0, 5]);
+    }
+}
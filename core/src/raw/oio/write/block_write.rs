@@ -63,7 +63,11 @@ pub trait BlockWrite: Send + Sync + Unpin + 'static {
     /// BlockWriter will call this API when:
     ///
     /// - All the data has been written to the buffer and we can perform the upload at once.
-    fn write_once(&self, size: u64, body: Buffer) -> impl Future<Output = Result<()>> + MaybeSend;
+    fn write_once(
+        &self,
+        size: u64,
+        body: Buffer,
+    ) -> impl Future<Output = Result<Metadata>> + MaybeSend;
 
     /// write_block will write a block of the data.
     ///
@@ -79,8 +83,11 @@ pub trait BlockWrite: Send + Sync + Unpin + 'static {
     ) -> impl Future<Output = Result<()>> + MaybeSend;
 
     /// complete_block will complete the block upload to build the final
-    /// file.
-    fn complete_block(&self, block_ids: Vec<Uuid>) -> impl Future<Output = Result<()>> + MaybeSend;
+    /// file, and returns the metadata of it.
+    fn complete_block(
+        &self,
+        block_ids: Vec<Uuid>,
+    ) -> impl Future<Output = Result<Metadata>> + MaybeSend;
 
     /// abort_block will cancel the block upload and purge all data.
     fn abort_block(&self, block_ids: Vec<Uuid>) -> impl Future<Output = Result<()>> + MaybeSend;
@@ -187,16 +194,16 @@ where
         Ok(())
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         if !self.started {
             let (size, body) = match self.cache.clone() {
                 Some(cache) => (cache.len(), cache),
                 None => (0, Buffer::new()),
             };
 
-            self.w.write_once(size as u64, body).await?;
+            let meta = self.w.write_once(size as u64, body).await?;
             self.cache = None;
-            return Ok(());
+            return Ok(meta);
         }
 
         if let Some(cache) = self.cache.clone() {
@@ -268,8 +275,8 @@ mod tests {
     }
 
     impl BlockWrite for Arc<Mutex<TestWrite>> {
-        async fn write_once(&self, _: u64, _: Buffer) -> Result<()> {
-            Ok(())
+        async fn write_once(&self, _: u64, _: Buffer) -> Result<Metadata> {
+            Ok(Metadata::new(EntryMode::FILE))
         }
 
         async fn write_block(&self, block_id: Uuid, size: u64, body: Buffer) -> Result<()> {
@@ -290,7 +297,7 @@ mod tests {
             Ok(())
         }
 
-        async fn complete_block(&self, block_ids: Vec<Uuid>) -> Result<()> {
+        async fn complete_block(&self, block_ids: Vec<Uuid>) -> Result<Metadata> {
             let mut this = self.lock().unwrap();
             let mut bs = Vec::new();
             for id in block_ids {
@@ -298,7 +305,7 @@ mod tests {
             }
             this.content = Some(bs.into_iter().flatten().collect());
 
-            Ok(())
+            Ok(Metadata::new(EntryMode::FILE))
         }
 
         async fn abort_block(&self, _: Vec<Uuid>) -> Result<()> {
@@ -16,6 +16,7 @@
 // under the License.
 
 mod api;
+pub(crate) use api::with_written_size;
 pub use api::BlockingWrite;
 pub use api::BlockingWriter;
 pub use api::Write;
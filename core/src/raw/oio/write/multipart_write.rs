@@ -61,7 +61,11 @@ pub trait MultipartWrite: Send + Sync + Unpin + 'static {
     /// MultipartWriter will call this API when:
     ///
     /// - All the data has been written to the buffer and we can perform the upload at once.
-    fn write_once(&self, size: u64, body: Buffer) -> impl Future<Output = Result<()>> + MaybeSend;
+    fn write_once(
+        &self,
+        size: u64,
+        body: Buffer,
+    ) -> impl Future<Output = Result<Metadata>> + MaybeSend;
 
     /// initiate_part will call start a multipart upload and return the upload id.
     ///
@@ -88,12 +92,12 @@ pub trait MultipartWrite: Send + Sync + Unpin + 'static {
     ) -> impl Future<Output = Result<MultipartPart>> + MaybeSend;
 
     /// complete_part will complete the multipart upload to build the final
-    /// file.
+    /// file, and returns the metadata of it.
     fn complete_part(
         &self,
         upload_id: &str,
         parts: &[MultipartPart],
-    ) -> impl Future<Output = Result<()>> + MaybeSend;
+    ) -> impl Future<Output = Result<Metadata>> + MaybeSend;
 
     /// abort_part will cancel the multipart upload and purge all data.
     fn abort_part(&self, upload_id: &str) -> impl Future<Output = Result<()>> + MaybeSend;
@@ -238,7 +242,7 @@ where
         Ok(())
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         let upload_id = match self.upload_id.clone() {
             Some(v) => v,
             None => {
@@ -247,9 +251,9 @@ where
                     None => (0, Buffer::new()),
                 };
                 // Call write_once if there is no upload_id.
-                self.w.write_once(size as u64, body).await?;
+                let meta = self.w.write_once(size as u64, body).await?;
                 self.cache = None;
-                return Ok(());
+                return Ok(meta);
             }
         };
 
@@ -334,9 +338,9 @@ mod tests {
     }
 
     impl MultipartWrite for Arc<Mutex<TestWrite>> {
-        async fn write_once(&self, size: u64, _: Buffer) -> Result<()> {
+        async fn write_once(&self, size: u64, _: Buffer) -> Result<Metadata> {
             self.lock().await.length += size;
-            Ok(())
+            Ok(Metadata::new(EntryMode::FILE))
         }
 
         async fn initiate_part(&self) -> Result<String> {
@@ -379,12 +383,16 @@ mod tests {
             })
         }
 
-        async fn complete_part(&self, upload_id: &str, parts: &[MultipartPart]) -> Result<()> {
+        async fn complete_part(
+            &self,
+            upload_id: &str,
+            parts: &[MultipartPart],
+        ) -> Result<Metadata> {
             let test = self.lock().await;
             assert_eq!(upload_id, test.upload_id);
             assert_eq!(parts.len(), test.part_numbers.len());
 
-            Ok(())
+            Ok(Metadata::new(EntryMode::FILE))
         }
 
         async fn abort_part(&self, upload_id: &str) -> Result<()> {
@@ -30,7 +30,9 @@ pub trait OneShotWrite: Send + Sync + Unpin + 'static {
     /// write_once write all data at once.
     ///
     /// Implementations should make sure that the data is written correctly at once.
-    fn write_once(&self, bs: Buffer) -> impl Future<Output = Result<()>> + MaybeSend;
+    ///
+    /// Returns the metadata of the written file from the response.
+    fn write_once(&self, bs: Buffer) -> impl Future<Output = Result<Metadata>> + MaybeSend;
 }
 
 /// OneShotWrite is used to implement [`oio::Write`] based on one shot.
@@ -63,7 +65,7 @@ impl<W: OneShotWrite> oio::Write for OneShotWriter<W> {
         }
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         match self.buffer.clone() {
             Some(bs) => self.inner.write_once(bs).await,
             None => self.inner.write_once(Buffer::new()).await,
@@ -59,7 +59,7 @@ pub trait RangeWrite: Send + Sync + Unpin + 'static {
     /// RangeWriter will call this API when:
     ///
     /// - All the data has been written to the buffer and we can perform the upload at once.
-    fn write_once(&self, body: Buffer) -> impl Future<Output = Result<()>> + MaybeSend;
+    fn write_once(&self, body: Buffer) -> impl Future<Output = Result<Metadata>> + MaybeSend;
 
     /// Initiate range the range write, the returning value is the location.
     fn initiate_range(&self) -> impl Future<Output = Result<String>> + MaybeSend;
@@ -72,13 +72,14 @@ pub trait RangeWrite: Send + Sync + Unpin + 'static {
         body: Buffer,
     ) -> impl Future<Output = Result<()>> + MaybeSend;
 
-    /// complete_range will complete the range write by uploading the last chunk.
+    /// complete_range will complete the range write by uploading the last chunk,
+    /// and returns the metadata of the written file.
     fn complete_range(
         &self,
         location: &str,
         offset: u64,
         body: Buffer,
-    ) -> impl Future<Output = Result<()>> + MaybeSend;
+    ) -> impl Future<Output = Result<Metadata>> + MaybeSend;
 
     /// abort_range will abort the range write by abort all already uploaded data.
     fn abort_range(&self, location: &str) -> impl Future<Output = Result<()>> + MaybeSend;
@@ -192,25 +193,26 @@ impl<W: RangeWrite> oio::Write for RangeWriter<W> {
         Ok(())
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         let Some(location) = self.location.clone() else {
             let body = self.cache.clone().unwrap_or_default();
             // Call write_once if there is no data in buffer and no location.
-            self.w.write_once(body).await?;
+            let meta = self.w.write_once(body).await?;
             self.cache = None;
-            return Ok(());
+            return Ok(meta);
         };
 
         // Make sure all tasks are finished.
         while self.tasks.next().await.transpose()?.is_some() {}
 
+        let mut meta = Metadata::new(EntryMode::FILE);
         if let Some(buffer) = self.cache.clone() {
             let offset = self.next_offset;
-            self.w.complete_range(&location, offset, buffer).await?;
+            meta = self.w.complete_range(&location, offset, buffer).await?;
             self.cache = None;
         }
 
-        Ok(())
+        Ok(meta)
     }
 
     async fn abort(&mut self) -> Result<()> {
@@ -257,13 +259,13 @@ mod tests {
     }
 
     impl RangeWrite for Arc<Mutex<TestWrite>> {
-        async fn write_once(&self, body: Buffer) -> Result<()> {
+        async fn write_once(&self, body: Buffer) -> Result<Metadata> {
             let mut test = self.lock().unwrap();
             let size = body.len() as u64;
             test.length += size;
             test.bytes.extend(0..size);
 
-            Ok(())
+            Ok(Metadata::new(EntryMode::FILE))
         }
 
         async fn initiate_range(&self) -> Result<String> {
@@ -296,7 +298,7 @@ mod tests {
             Ok(())
         }
 
-        async fn complete_range(&self, _: &str, offset: u64, body: Buffer) -> Result<()> {
+        async fn complete_range(&self, _: &str, offset: u64, body: Buffer) -> Result<Metadata> {
             // Add an async sleep here to enforce some pending.
             sleep(Duration::from_millis(50)).await;
 
@@ -318,7 +320,7 @@ mod tests {
             );
             test.bytes.extend(input);
 
-            Ok(())
+            Ok(Metadata::new(EntryMode::FILE))
         }
 
         async fn abort_range(&self, _: &str) -> Result<()> {
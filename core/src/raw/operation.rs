@@ -45,6 +45,12 @@ pub enum Operation {
     Batch,
     /// Operation for [`crate::raw::Access::presign`]
     Presign,
+    /// Operation for [`crate::raw::Access::initiate_multipart`]
+    InitiateMultipart,
+    /// Operation for [`crate::raw::Access::complete_multipart`]
+    CompleteMultipart,
+    /// Operation for [`crate::raw::Access::abort_multipart`]
+    AbortMultipart,
     /// Operation for [`crate::raw::Access::blocking_create_dir`]
     BlockingCreateDir,
     /// Operation for [`crate::raw::Access::blocking_read`]
@@ -90,6 +96,9 @@ impl From<Operation> for &'static str {
             Operation::List => "list",
             Operation::Presign => "presign",
             Operation::Batch => "batch",
+            Operation::InitiateMultipart => "initiate_multipart",
+            Operation::CompleteMultipart => "complete_multipart",
+            Operation::AbortMultipart => "abort_multipart",
             Operation::BlockingCreateDir => "blocking_create_dir",
             Operation::BlockingRead => "blocking_read",
             Operation::BlockingWrite => "blocking_write",
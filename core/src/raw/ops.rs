@@ -20,9 +20,15 @@
 //! By using ops, users can add more context for operation.
 
 use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::sync::Arc;
 use std::time::Duration;
 
+use chrono::DateTime;
+use chrono::Utc;
 use flagset::FlagSet;
+use http::HeaderMap;
 
 use crate::raw::*;
 use crate::*;
@@ -46,6 +52,7 @@ impl OpCreateDir {
 #[derive(Debug, Clone, Default)]
 pub struct OpDelete {
     version: Option<String>,
+    lock_token: Option<String>,
 }
 
 impl OpDelete {
@@ -66,6 +73,92 @@ impl OpDelete {
     pub fn version(&self) -> Option<&str> {
         self.version.as_deref()
     }
+
+    /// Set the lock token of this delete operation.
+    ///
+    /// Services like azdls will carry the lease id as lock token so that
+    /// the delete can be performed on a path leased by the caller.
+    pub fn with_lock_token(mut self, lock_token: &str) -> Self {
+        self.lock_token = Some(lock_token.to_string());
+        self
+    }
+
+    /// Get the lock token of this delete operation.
+    pub fn lock_token(&self) -> Option<&str> {
+        self.lock_token.as_deref()
+    }
+}
+
+/// Args for `remove_all` operation.
+///
+/// The path must be normalized.
+#[derive(Clone)]
+pub struct OpRemoveAll {
+    concurrent: usize,
+    continue_on_error: bool,
+    progress: Option<Arc<dyn Fn(u64, u64) + Send + Sync>>,
+}
+
+impl Debug for OpRemoveAll {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OpRemoveAll")
+            .field("concurrent", &self.concurrent)
+            .field("continue_on_error", &self.continue_on_error)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for OpRemoveAll {
+    fn default() -> Self {
+        Self {
+            concurrent: 1,
+            continue_on_error: false,
+            progress: None,
+        }
+    }
+}
+
+impl OpRemoveAll {
+    /// Create a new `OpRemoveAll`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Change the concurrent of this remove_all operation.
+    ///
+    /// The default concurrent is 1.
+    pub fn with_concurrent(mut self, concurrent: usize) -> Self {
+        self.concurrent = concurrent;
+        self
+    }
+
+    /// Get the concurrent of remove_all operation.
+    pub fn concurrent(&self) -> usize {
+        self.concurrent
+    }
+
+    /// Keep removing other entries if some of them failed.
+    pub fn with_continue_on_error(mut self, continue_on_error: bool) -> Self {
+        self.continue_on_error = continue_on_error;
+        self
+    }
+
+    /// Get the continue_on_error of remove_all operation.
+    pub fn continue_on_error(&self) -> bool {
+        self.continue_on_error
+    }
+
+    /// Set the progress callback, which will be called with the total
+    /// entries removed and the total bytes freed.
+    pub fn with_progress(mut self, f: impl Fn(u64, u64) + Send + Sync + 'static) -> Self {
+        self.progress = Some(Arc::new(f));
+        self
+    }
+
+    /// Get the progress callback of remove_all operation.
+    pub fn progress(&self) -> Option<&(dyn Fn(u64, u64) + Send + Sync)> {
+        self.progress.as_deref()
+    }
 }
 
 /// Args for `list` operation.
@@ -100,6 +193,21 @@ pub struct OpList {
     /// - If this is set to > 1, the list operation will be concurrent,
     ///   and the maximum number of concurrent operations will be determined by this value.
     concurrent: usize,
+    /// Only return entries whose path relative to the listed path matches
+    /// the glob pattern.
+    matching_glob: Option<String>,
+    /// Only return entries whose path relative to the listed path matches
+    /// the regex.
+    matching_regex: Option<String>,
+    /// Return entries in lexicographical order of their paths.
+    ///
+    /// Default to `false`.
+    ordered: bool,
+    /// Sort entries in memory if `ordered` is set but the service can't
+    /// list in order. Otherwise, `Unsupported` error will be returned.
+    ///
+    /// Default to `false`.
+    sort_unordered: bool,
 }
 
 impl Default for OpList {
@@ -111,6 +219,10 @@ impl Default for OpList {
             // By default, we want to know what's the mode of this entry.
             metakey: Metakey::Mode.into(),
             concurrent: 1,
+            matching_glob: None,
+            matching_regex: None,
+            ordered: false,
+            sort_unordered: false,
         }
     }
 }
@@ -184,6 +296,62 @@ impl OpList {
     pub fn concurrent(&self) -> usize {
         self.concurrent
     }
+
+    /// Only return entries whose path relative to the listed path matches
+    /// the glob pattern.
+    ///
+    /// `*` and `?` match any characters except `/`, `**` matches across
+    /// dirs, `[abc]`, `[!abc]` and `{a,b}` are supported as well.
+    pub fn with_matching_glob(mut self, glob: &str) -> Self {
+        self.matching_glob = Some(glob.to_string());
+        self
+    }
+
+    /// Get the glob pattern of list operation.
+    pub fn matching_glob(&self) -> Option<&str> {
+        self.matching_glob.as_deref()
+    }
+
+    /// Only return entries whose path relative to the listed path matches
+    /// the regex.
+    ///
+    /// The regex is not anchored, use `^` and `$` to match the whole path.
+    pub fn with_matching_regex(mut self, regex: &str) -> Self {
+        self.matching_regex = Some(regex.to_string());
+        self
+    }
+
+    /// Get the regex of list operation.
+    pub fn matching_regex(&self) -> Option<&str> {
+        self.matching_regex.as_deref()
+    }
+
+    /// Return entries in lexicographical order of their paths.
+    ///
+    /// Default to `false`.
+    pub fn with_ordered(mut self, ordered: bool) -> Self {
+        self.ordered = ordered;
+        self
+    }
+
+    /// Get the ordered of list operation.
+    pub fn ordered(&self) -> bool {
+        self.ordered
+    }
+
+    /// Sort entries in memory if `ordered` is set but the service can't
+    /// list in order.
+    ///
+    /// Default to `false`, which returns `Unsupported` error instead.
+    pub fn with_sort_unordered(mut self, sort_unordered: bool) -> Self {
+        self.sort_unordered = sort_unordered;
+        self
+    }
+
+    /// Get the sort_unordered of list operation.
+    pub fn sort_unordered(&self) -> bool {
+        self.sort_unordered
+    }
 }
 
 /// Args for `presign` operation.
@@ -192,6 +360,7 @@ impl OpList {
 #[derive(Debug, Clone)]
 pub struct OpPresign {
     expire: Duration,
+    signed_headers: HeaderMap,
 
     op: PresignOperation,
 }
@@ -202,9 +371,23 @@ impl OpPresign {
         Self {
             op: op.into(),
             expire,
+            signed_headers: HeaderMap::new(),
         }
     }
 
+    /// Set the extra headers to include in the signature.
+    ///
+    /// Clients must send them verbatim while using the presigned request.
+    pub fn with_signed_headers(mut self, headers: HeaderMap) -> Self {
+        self.signed_headers = headers;
+        self
+    }
+
+    /// Get the extra headers to include in the signature.
+    pub fn signed_headers(&self) -> &HeaderMap {
+        &self.signed_headers
+    }
+
     /// Get operation from op.
     pub fn operation(&self) -> &PresignOperation {
         &self.op
@@ -231,6 +414,10 @@ pub enum PresignOperation {
     Read(OpRead),
     /// Presign a write operation.
     Write(OpWrite),
+    /// Presign a delete operation.
+    Delete(OpDelete),
+    /// Presign an upload part operation of a multipart upload.
+    UploadPart(OpUploadPart),
 }
 
 impl From<OpStat> for PresignOperation {
@@ -251,6 +438,98 @@ impl From<OpWrite> for PresignOperation {
     }
 }
 
+impl From<OpDelete> for PresignOperation {
+    fn from(v: OpDelete) -> Self {
+        Self::Delete(v)
+    }
+}
+
+impl From<OpUploadPart> for PresignOperation {
+    fn from(v: OpUploadPart) -> Self {
+        Self::UploadPart(v)
+    }
+}
+
+/// Args for presigning an upload part of a multipart upload.
+#[derive(Debug, Clone)]
+pub struct OpUploadPart {
+    upload_id: String,
+    part_number: usize,
+}
+
+impl OpUploadPart {
+    /// Create a new `OpUploadPart`.
+    ///
+    /// `part_number` starts from 1.
+    pub fn new(upload_id: &str, part_number: usize) -> Self {
+        Self {
+            upload_id: upload_id.to_string(),
+            part_number,
+        }
+    }
+
+    /// Get the upload id of this part.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+
+    /// Get the part number of this part.
+    pub fn part_number(&self) -> usize {
+        self.part_number
+    }
+}
+
+/// Args for `complete_multipart` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone)]
+pub struct OpCompleteMultipart {
+    upload_id: String,
+    parts: Vec<UploadedPart>,
+}
+
+impl OpCompleteMultipart {
+    /// Create a new `OpCompleteMultipart`.
+    pub fn new(upload_id: &str, parts: Vec<UploadedPart>) -> Self {
+        Self {
+            upload_id: upload_id.to_string(),
+            parts,
+        }
+    }
+
+    /// Get the upload id to complete.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+
+    /// Get the uploaded parts, they will be concatenated in order.
+    pub fn parts(&self) -> &[UploadedPart] {
+        &self.parts
+    }
+}
+
+/// Args for `abort_multipart` operation.
+///
+/// The path must be normalized.
+#[derive(Debug, Clone)]
+pub struct OpAbortMultipart {
+    upload_id: String,
+}
+
+impl OpAbortMultipart {
+    /// Create a new `OpAbortMultipart`.
+    pub fn new(upload_id: &str) -> Self {
+        Self {
+            upload_id: upload_id.to_string(),
+        }
+    }
+
+    /// Get the upload id to abort.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+}
+
 /// Args for `batch` operation.
 #[derive(Debug, Clone)]
 pub struct OpBatch {
@@ -272,14 +551,40 @@ impl OpBatch {
     pub fn into_operation(self) -> Vec<(String, BatchOperation)> {
         self.ops
     }
+
+    /// Consume OpBatch into the paths to delete.
+    ///
+    /// Returns `Unsupported` error if the batch contains any operation other
+    /// than delete, services that only support batch delete should use this
+    /// instead of dropping the operation kind.
+    pub fn into_delete_paths(self) -> Result<Vec<String>> {
+        self.ops
+            .into_iter()
+            .map(|(path, op)| match op {
+                BatchOperation::Delete(_) => Ok(path),
+                op => Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "service only supports delete in batch",
+                )
+                .with_operation(Operation::Batch)
+                .with_context("batch_operation", op.operation().into_static())
+                .with_context("path", path)),
+            })
+            .collect()
+    }
 }
 
 /// Batch operation used for batch.
 #[derive(Debug, Clone)]
 #[non_exhaustive]
+#[allow(clippy::large_enum_variant)]
 pub enum BatchOperation {
     /// Batch delete operation.
     Delete(OpDelete),
+    /// Batch write operation with the content to write.
+    Write(OpWrite, Buffer),
+    /// Batch stat operation.
+    Stat(OpStat),
 }
 
 impl From<OpDelete> for BatchOperation {
@@ -288,12 +593,26 @@ impl From<OpDelete> for BatchOperation {
     }
 }
 
+impl From<OpStat> for BatchOperation {
+    fn from(op: OpStat) -> Self {
+        Self::Stat(op)
+    }
+}
+
+impl From<(OpWrite, Buffer)> for BatchOperation {
+    fn from((op, bs): (OpWrite, Buffer)) -> Self {
+        Self::Write(op, bs)
+    }
+}
+
 impl BatchOperation {
     /// Return the operation of this batch.
     pub fn operation(&self) -> Operation {
         use BatchOperation::*;
         match self {
             Delete(_) => Operation::Delete,
+            Write(_, _) => Operation::Write,
+            Stat(_) => Operation::Stat,
         }
     }
 }
@@ -308,6 +627,7 @@ pub struct OpRead {
     override_cache_control: Option<String>,
     override_content_disposition: Option<String>,
     version: Option<String>,
+    snapshot: Option<String>,
     executor: Option<Executor>,
 }
 
@@ -400,6 +720,20 @@ impl OpRead {
         self.version.as_deref()
     }
 
+    /// Set the snapshot of the option
+    ///
+    /// Snapshot is a read-only point-in-time copy of the file that services
+    /// like azblob and azdls could take.
+    pub fn with_snapshot(mut self, snapshot: &str) -> Self {
+        self.snapshot = Some(snapshot.to_string());
+        self
+    }
+
+    /// Get snapshot from option
+    pub fn snapshot(&self) -> Option<&str> {
+        self.snapshot.as_deref()
+    }
+
     /// Set the executor of the option
     pub fn with_executor(mut self, executor: Executor) -> Self {
         self.executor = Some(executor);
@@ -435,6 +769,8 @@ pub struct OpReader {
     chunk: Option<usize>,
     /// The gap size of each request.
     gap: Option<usize>,
+    /// Verify the content read against the checksum stored in metadata.
+    verify_checksum: bool,
 }
 
 impl Default for OpReader {
@@ -443,6 +779,7 @@ impl Default for OpReader {
             concurrent: 1,
             chunk: None,
             gap: None,
+            verify_checksum: false,
         }
     }
 }
@@ -485,6 +822,17 @@ impl OpReader {
     pub fn gap(&self) -> Option<usize> {
         self.gap
     }
+
+    /// Set the verify_checksum of the option
+    pub fn with_verify_checksum(mut self, v: bool) -> Self {
+        self.verify_checksum = v;
+        self
+    }
+
+    /// Get verify_checksum from option
+    pub fn verify_checksum(&self) -> bool {
+        self.verify_checksum
+    }
 }
 
 /// Args for `stat` operation.
@@ -492,10 +840,13 @@ impl OpReader {
 pub struct OpStat {
     if_match: Option<String>,
     if_none_match: Option<String>,
+    if_modified_since: Option<DateTime<Utc>>,
+    if_unmodified_since: Option<DateTime<Utc>>,
     override_content_type: Option<String>,
     override_cache_control: Option<String>,
     override_content_disposition: Option<String>,
     version: Option<String>,
+    snapshot: Option<String>,
 }
 
 impl OpStat {
@@ -526,6 +877,64 @@ impl OpStat {
         self.if_none_match.as_deref()
     }
 
+    /// Set the If-Modified-Since of the option
+    pub fn with_if_modified_since(mut self, v: DateTime<Utc>) -> Self {
+        self.if_modified_since = Some(v);
+        self
+    }
+
+    /// Get If-Modified-Since from option
+    pub fn if_modified_since(&self) -> Option<DateTime<Utc>> {
+        self.if_modified_since
+    }
+
+    /// Set the If-Unmodified-Since of the option
+    pub fn with_if_unmodified_since(mut self, v: DateTime<Utc>) -> Self {
+        self.if_unmodified_since = Some(v);
+        self
+    }
+
+    /// Get If-Unmodified-Since from option
+    pub fn if_unmodified_since(&self) -> Option<DateTime<Utc>> {
+        self.if_unmodified_since
+    }
+
+    /// Evaluate If-Modified-Since and If-Unmodified-Since against the
+    /// given last modified time, for services that can't check them on the
+    /// server side.
+    ///
+    /// Like HTTP, the times are compared in seconds and conditions are
+    /// ignored if last modified is unknown.
+    pub fn check_modified_since(&self, last_modified: Option<DateTime<Utc>>) -> Result<()> {
+        let Some(last_modified) = last_modified else {
+            return Ok(());
+        };
+        let last_modified = last_modified.timestamp();
+
+        if let Some(v) = self.if_modified_since {
+            if last_modified <= v.timestamp() {
+                return Err(Error::new(
+                    ErrorKind::ConditionNotMatch,
+                    "the object is not modified since the given time",
+                )
+                .with_operation(Operation::Stat)
+                .with_context("if_modified_since", v.to_rfc3339()));
+            }
+        }
+        if let Some(v) = self.if_unmodified_since {
+            if last_modified > v.timestamp() {
+                return Err(Error::new(
+                    ErrorKind::ConditionNotMatch,
+                    "the object is modified since the given time",
+                )
+                .with_operation(Operation::Stat)
+                .with_context("if_unmodified_since", v.to_rfc3339()));
+            }
+        }
+
+        Ok(())
+    }
+
     /// Sets the content-disposition header that should be send back by the remote read operation.
     pub fn with_override_content_disposition(mut self, content_disposition: &str) -> Self {
         self.override_content_disposition = Some(content_disposition.into());
@@ -570,6 +979,20 @@ impl OpStat {
     pub fn version(&self) -> Option<&str> {
         self.version.as_deref()
     }
+
+    /// Set the snapshot of the option
+    ///
+    /// Snapshot is a read-only point-in-time copy of the file that services
+    /// like azblob and azdls could take.
+    pub fn with_snapshot(mut self, snapshot: &str) -> Self {
+        self.snapshot = Some(snapshot.to_string());
+        self
+    }
+
+    /// Get snapshot from option
+    pub fn snapshot(&self) -> Option<&str> {
+        self.snapshot.as_deref()
+    }
 }
 
 /// Args for `write` operation.
@@ -582,6 +1005,15 @@ pub struct OpWrite {
     cache_control: Option<String>,
     executor: Option<Executor>,
     user_metadata: Option<HashMap<String, String>>,
+    tags: Option<HashMap<String, String>>,
+    lock_token: Option<String>,
+    position: Option<u64>,
+    retain_uncommitted_data: bool,
+    expires_in: Option<Duration>,
+    expires_at: Option<DateTime<Utc>>,
+    if_not_exists: bool,
+    content_md5: Option<String>,
+    content_sha256: Option<String>,
 }
 
 impl OpWrite {
@@ -690,6 +1122,134 @@ impl OpWrite {
     pub fn user_metadata(&self) -> Option<&HashMap<String, String>> {
         self.user_metadata.as_ref()
     }
+
+    /// Set the tags of the op
+    pub fn with_tags(mut self, tags: HashMap<String, String>) -> Self {
+        self.tags = Some(tags);
+        self
+    }
+
+    /// Get the tags from the op
+    pub fn tags(&self) -> Option<&HashMap<String, String>> {
+        self.tags.as_ref()
+    }
+
+    /// Get the lock token from op.
+    pub fn lock_token(&self) -> Option<&str> {
+        self.lock_token.as_deref()
+    }
+
+    /// Set the lock token of op.
+    ///
+    /// Services like WebDAV will carry the lock token so that the write
+    /// can be performed on a locked resource, and azdls will carry it as
+    /// the lease id of a leased path.
+    pub fn with_lock_token(mut self, lock_token: &str) -> Self {
+        self.lock_token = Some(lock_token.to_string());
+        self
+    }
+
+    /// Get the position from op.
+    ///
+    /// The position is the offset that an append write should continue from.
+    pub fn position(&self) -> Option<u64> {
+        self.position
+    }
+
+    /// Set the position of op.
+    ///
+    /// If the position is set, services that support position based append will
+    /// continue writing from this position instead of fetching current offset from
+    /// storage. This allows resuming an interrupted append write.
+    pub fn with_position(mut self, position: u64) -> Self {
+        self.position = Some(position);
+        self
+    }
+
+    /// Get the retain uncommitted data from op.
+    pub fn retain_uncommitted_data(&self) -> bool {
+        self.retain_uncommitted_data
+    }
+
+    /// Set the retain uncommitted data of op.
+    ///
+    /// Services like azdls will flush the committed data of an append write
+    /// without finalizing the file, so that it can be checkpointed and
+    /// appended later.
+    pub fn with_retain_uncommitted_data(mut self, v: bool) -> Self {
+        self.retain_uncommitted_data = v;
+        self
+    }
+
+    /// Get the relative expiry from op.
+    pub fn expires_in(&self) -> Option<Duration> {
+        self.expires_in
+    }
+
+    /// Set the file to expire after the given duration since now.
+    ///
+    /// This overrides the absolute expiry set by [`OpWrite::with_expires_at`].
+    pub fn with_expires_in(mut self, v: Duration) -> Self {
+        self.expires_in = Some(v);
+        self.expires_at = None;
+        self
+    }
+
+    /// Get the absolute expiry from op.
+    pub fn expires_at(&self) -> Option<DateTime<Utc>> {
+        self.expires_at
+    }
+
+    /// Set the file to expire at the given time.
+    ///
+    /// This overrides the relative expiry set by [`OpWrite::with_expires_in`].
+    pub fn with_expires_at(mut self, v: DateTime<Utc>) -> Self {
+        self.expires_at = Some(v);
+        self.expires_in = None;
+        self
+    }
+
+    /// Get the if not exists from op.
+    pub fn if_not_exists(&self) -> bool {
+        self.if_not_exists
+    }
+
+    /// Set the if not exists of op.
+    ///
+    /// If set, the write will only succeed if the target doesn't exist,
+    /// otherwise [`ErrorKind::ConditionNotMatch`] will be returned.
+    pub fn with_if_not_exists(mut self, v: bool) -> Self {
+        self.if_not_exists = v;
+        self
+    }
+
+    /// Get the content md5 from op.
+    pub fn content_md5(&self) -> Option<&str> {
+        self.content_md5.as_deref()
+    }
+
+    /// Set the base64 encoded MD5 of the whole content.
+    ///
+    /// The write will fail with [`ErrorKind::DataCorrupted`] if the content
+    /// written doesn't match it.
+    pub fn with_content_md5(mut self, v: &str) -> Self {
+        self.content_md5 = Some(v.to_string());
+        self
+    }
+
+    /// Get the content sha256 from op.
+    pub fn content_sha256(&self) -> Option<&str> {
+        self.content_sha256.as_deref()
+    }
+
+    /// Set the base64 encoded SHA256 of the whole content.
+    ///
+    /// The write will fail with [`ErrorKind::DataCorrupted`] if the content
+    /// written doesn't match it.
+    pub fn with_content_sha256(mut self, v: &str) -> Self {
+        self.content_sha256 = Some(v.to_string());
+        self
+    }
 }
 
 /// Args for `writer` operation.
@@ -728,22 +1288,162 @@ impl OpWriter {
 
 /// Args for `copy` operation.
 #[derive(Debug, Clone, Default)]
-pub struct OpCopy {}
+pub struct OpCopy {
+    metadata_directive: Option<MetadataDirective>,
+    content_type: Option<String>,
+    cache_control: Option<String>,
+    if_not_exists: bool,
+}
 
 impl OpCopy {
     /// Create a new `OpCopy`.
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Get the metadata directive from op.
+    pub fn metadata_directive(&self) -> Option<MetadataDirective> {
+        self.metadata_directive
+    }
+
+    /// Set the metadata directive of op.
+    pub fn with_metadata_directive(mut self, v: MetadataDirective) -> Self {
+        self.metadata_directive = Some(v);
+        self
+    }
+
+    /// Check whether the metadata of target should be replaced.
+    ///
+    /// Overriding content type or cache control implies replacing.
+    pub fn replace_metadata(&self) -> bool {
+        self.metadata_directive == Some(MetadataDirective::Replace)
+            || self.content_type.is_some()
+            || self.cache_control.is_some()
+    }
+
+    /// Get the content type from op.
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Set the content type of the target.
+    pub fn with_content_type(mut self, v: &str) -> Self {
+        self.content_type = Some(v.to_string());
+        self
+    }
+
+    /// Get the cache control from op.
+    pub fn cache_control(&self) -> Option<&str> {
+        self.cache_control.as_deref()
+    }
+
+    /// Set the cache control of the target.
+    pub fn with_cache_control(mut self, v: &str) -> Self {
+        self.cache_control = Some(v.to_string());
+        self
+    }
+
+    /// Get the if not exists from op.
+    pub fn if_not_exists(&self) -> bool {
+        self.if_not_exists
+    }
+
+    /// Set the if not exists of op.
+    ///
+    /// If set, the copy will only succeed if the target doesn't exist,
+    /// otherwise [`ErrorKind::ConditionNotMatch`] will be returned.
+    pub fn with_if_not_exists(mut self, v: bool) -> Self {
+        self.if_not_exists = v;
+        self
+    }
+}
+
+/// Args for `copy_to` operation.
+#[derive(Debug, Clone)]
+pub struct OpCopyTo {
+    chunk: Option<usize>,
+    concurrent: usize,
+    native_copy: bool,
+}
+
+impl Default for OpCopyTo {
+    fn default() -> Self {
+        Self {
+            chunk: None,
+            concurrent: 1,
+            native_copy: false,
+        }
+    }
+}
+
+impl OpCopyTo {
+    /// Create a new `OpCopyTo`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the chunk size from op.
+    pub fn chunk(&self) -> Option<usize> {
+        self.chunk
+    }
+
+    /// Set the chunk size used to read from source and write to target.
+    pub fn with_chunk(mut self, chunk: usize) -> Self {
+        self.chunk = Some(chunk);
+        self
+    }
+
+    /// Get the concurrent from op.
+    pub fn concurrent(&self) -> usize {
+        self.concurrent
+    }
+
+    /// Set the concurrent of reading from source and writing to target.
+    ///
+    /// The default concurrent is 1.
+    pub fn with_concurrent(mut self, concurrent: usize) -> Self {
+        self.concurrent = concurrent;
+        self
+    }
+
+    /// Get the native copy from op.
+    pub fn native_copy(&self) -> bool {
+        self.native_copy
+    }
+
+    /// Set whether to use native copy if source and target are the
+    /// same storage.
+    ///
+    /// The default is false.
+    pub fn with_native_copy(mut self, v: bool) -> Self {
+        self.native_copy = v;
+        self
+    }
 }
 
 /// Args for `rename` operation.
 #[derive(Debug, Clone, Default)]
-pub struct OpRename {}
+pub struct OpRename {
+    lock_token: Option<String>,
+}
 
 impl OpRename {
     /// Create a new `OpMove`.
     pub fn new() -> Self {
         Self::default()
     }
+
+    /// Get the lock token from op.
+    pub fn lock_token(&self) -> Option<&str> {
+        self.lock_token.as_deref()
+    }
+
+    /// Set the lock token of op.
+    ///
+    /// Services like WebDAV will carry the lock token so that the rename
+    /// can be performed on a locked resource.
+    pub fn with_lock_token(mut self, lock_token: &str) -> Self {
+        self.lock_token = Some(lock_token.to_string());
+        self
+    }
 }
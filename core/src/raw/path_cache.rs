@@ -75,6 +75,14 @@ impl<Q: PathQuery> PathCacher<Q> {
         }
     }
 
+    /// Set the max number of entries to cache, default to `64 * 1024`.
+    ///
+    /// Entries will be evicted once the capacity is reached.
+    pub fn with_capacity(mut self, capacity: u64) -> Self {
+        self.cache = Cache::new(capacity);
+        self
+    }
+
     /// Enable the lock for the path cacher.
     pub fn with_lock(mut self) -> Self {
         self.lock = Some(Mutex::default());
@@ -112,6 +120,26 @@ impl<Q: PathQuery> PathCacher<Q> {
         self.cache.invalidate(path)
     }
 
+    /// Export all cached entries as `(path, id)` pairs.
+    ///
+    /// The exported entries can be loaded by [`PathCacher::extend`] later
+    /// so that a new cacher doesn't need to query the hierarchy again.
+    pub fn entries(&self) -> Vec<(String, String)> {
+        self.cache
+            .iter()
+            .map(|(path, id)| (path.as_ref().clone(), id))
+            .collect()
+    }
+
+    /// Load `(path, id)` pairs into the cache.
+    ///
+    /// Existing entries will be overwritten.
+    pub fn extend(&self, entries: impl IntoIterator<Item = (String, String)>) {
+        for (path, id) in entries {
+            self.cache.insert(path, id);
+        }
+    }
+
     /// Get the id for the given path.
     pub async fn get(&self, path: &str) -> Result<Option<String>> {
         let _guard = self.lock().await;
@@ -241,4 +269,28 @@ mod tests {
             assert_eq!(actual.as_deref(), expect, "{}", name)
         }
     }
+
+    #[tokio::test]
+    async fn test_path_cacher_entries() {
+        let cache = PathCacher::new(TestQuery {});
+        cache.get("/a/b").await.unwrap();
+
+        let mut entries = cache.entries();
+        entries.sort();
+        assert_eq!(
+            entries,
+            vec![
+                ("/".to_string(), "root/".to_string()),
+                ("/a/".to_string(), "root/a/".to_string()),
+                ("/a/b".to_string(), "root/a/b".to_string()),
+            ]
+        );
+
+        let other = PathCacher::new(TestQuery {});
+        other.extend([("/a/b".to_string(), "preloaded".to_string())]);
+        assert_eq!(
+            other.get("/a/b").await.unwrap().as_deref(),
+            Some("preloaded")
+        );
+    }
 }
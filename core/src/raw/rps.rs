@@ -15,6 +15,10 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
 use http::Request;
 
 use crate::raw::*;
@@ -79,6 +83,9 @@ impl PresignedRequest {
     }
 
     /// Return request's header.
+    ///
+    /// These headers could be covered by the signature, clients must send
+    /// all of them verbatim.
     pub fn header(&self) -> &http::HeaderMap {
         &self.headers
     }
@@ -116,6 +123,11 @@ pub struct RpRead {
     /// It's ok to leave range as empty, but it's recommended to set range if possible. We will use
     /// this range as hint to do some optimization like avoid an extra stat or read.
     range: Option<BytesContentRange>,
+    /// Etag of the file that is being read.
+    ///
+    /// It's used to make sure the same version is read while resuming
+    /// interrupted reads, leave it empty if the service doesn't return it.
+    etag: Option<String>,
 }
 
 impl RpRead {
@@ -151,6 +163,17 @@ impl RpRead {
         self.range = range;
         self
     }
+
+    /// Got the etag of the file that is being read.
+    pub fn etag(&self) -> Option<&str> {
+        self.etag.as_deref()
+    }
+
+    /// Set the etag of the file that is being read.
+    pub fn with_etag(mut self, etag: Option<String>) -> Self {
+        self.etag = etag;
+        self
+    }
 }
 
 /// Reply for `batch` operation.
@@ -179,6 +202,12 @@ impl RpBatch {
 pub enum BatchedReply {
     /// results of `delete batch` operation
     Delete(RpDelete),
+    /// results of `write batch` operation
+    Write(RpWrite),
+    /// results of `stat batch` operation
+    ///
+    /// Boxed since [`RpStat`] carries a whole [`crate::Metadata`].
+    Stat(Box<RpStat>),
 }
 
 impl From<RpDelete> for BatchedReply {
@@ -187,6 +216,18 @@ impl From<RpDelete> for BatchedReply {
     }
 }
 
+impl From<RpWrite> for BatchedReply {
+    fn from(rp: RpWrite) -> Self {
+        Self::Write(rp)
+    }
+}
+
+impl From<RpStat> for BatchedReply {
+    fn from(rp: RpStat) -> Self {
+        Self::Stat(Box::new(rp))
+    }
+}
+
 /// Reply for `stat` operation.
 #[derive(Debug, Clone)]
 pub struct RpStat {
@@ -212,16 +253,57 @@ impl RpStat {
 }
 
 /// Reply for `write` operation.
+///
+/// The reply is returned before any data is written, so the numbers of
+/// requests are updated by the writer while writing. Read them after the
+/// writer is closed to get the final values.
 #[derive(Debug, Clone, Default)]
-pub struct RpWrite {}
+pub struct RpWrite {
+    mode: Option<WriteMode>,
+    append_requests: Arc<AtomicUsize>,
+}
 
 impl RpWrite {
     /// Create a new reply for `write`.
     pub fn new() -> Self {
-        Self {}
+        Self::default()
+    }
+
+    /// Set the write mode used by the writer.
+    pub fn with_mode(mut self, mode: WriteMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// Get the write mode used by the writer.
+    ///
+    /// Returns `None` if the service doesn't report it.
+    pub fn mode(&self) -> Option<WriteMode> {
+        self.mode
+    }
+
+    /// Get the counter of append requests, writers should increase it
+    /// for every append request they sent.
+    pub fn append_request_counter(&self) -> Arc<AtomicUsize> {
+        self.append_requests.clone()
+    }
+
+    /// Get the number of append requests issued by the writer so far.
+    pub fn append_requests(&self) -> usize {
+        self.append_requests.load(Ordering::Relaxed)
     }
 }
 
+/// The strategy that a writer uses to upload data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum WriteMode {
+    /// All data is buffered and uploaded in one request.
+    OneShot,
+    /// Data is uploaded by appending to the object in several requests.
+    Append,
+}
+
 /// Reply for `copy` operation.
 #[derive(Debug, Clone, Default)]
 pub struct RpCopy {}
@@ -244,6 +326,60 @@ impl RpRename {
     }
 }
 
+/// Reply for `initiate_multipart` operation.
+#[derive(Debug, Clone)]
+pub struct RpInitiateMultipart {
+    upload_id: String,
+}
+
+impl RpInitiateMultipart {
+    /// Create a new reply for `initiate_multipart`.
+    pub fn new(upload_id: &str) -> Self {
+        Self {
+            upload_id: upload_id.to_string(),
+        }
+    }
+
+    /// Get the upload id assigned by services.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+
+    /// Consume reply to get the upload id.
+    pub fn into_upload_id(self) -> String {
+        self.upload_id
+    }
+}
+
+/// Reply for `complete_multipart` operation.
+#[derive(Debug, Clone)]
+pub struct RpCompleteMultipart {
+    meta: Metadata,
+}
+
+impl RpCompleteMultipart {
+    /// Create a new reply for `complete_multipart`.
+    pub fn new(meta: Metadata) -> Self {
+        Self { meta }
+    }
+
+    /// Consume reply to get the metadata of the completed file.
+    pub fn into_metadata(self) -> Metadata {
+        self.meta
+    }
+}
+
+/// Reply for `abort_multipart` operation.
+#[derive(Debug, Clone, Default)]
+pub struct RpAbortMultipart {}
+
+impl RpAbortMultipart {
+    /// Create a new reply for `abort_multipart`.
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use anyhow::Result;
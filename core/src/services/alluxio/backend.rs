@@ -20,6 +20,7 @@ use std::fmt::Formatter;
 use std::sync::Arc;
 
 use http::Response;
+use http::StatusCode;
 use log::debug;
 use serde::Deserialize;
 use serde::Serialize;
@@ -27,6 +28,7 @@ use serde::Serialize;
 use super::core::AlluxioCore;
 use super::error::parse_error;
 use super::lister::AlluxioLister;
+use super::reader::AlluxioReader;
 use super::writer::AlluxioWriter;
 use super::writer::AlluxioWriters;
 use crate::raw::*;
@@ -169,7 +171,7 @@ pub struct AlluxioBackend {
 }
 
 impl Access for AlluxioBackend {
-    type Reader = HttpBody;
+    type Reader = AlluxioReader;
     type Writer = AlluxioWriters;
     type Lister = oio::PageLister<AlluxioLister>;
     type BlockingReader = ();
@@ -183,11 +185,7 @@ impl Access for AlluxioBackend {
             .set_native_capability(Capability {
                 stat: true,
 
-                // FIXME:
-                //
-                // alluxio's read support is not implemented correctly
-                // We need to refactor by use [page_read](https://github.com/Alluxio/alluxio-py/blob/main/alluxio/const.py#L18)
-                read: false,
+                read: true,
 
                 write: true,
                 write_can_multi: true,
@@ -218,12 +216,26 @@ impl Access for AlluxioBackend {
         let stream_id = self.core.open_file(path).await?;
 
         let resp = self.core.read(stream_id, args.range()).await?;
-        if !resp.status().is_success() {
-            let (part, mut body) = resp.into_parts();
-            let buf = body.to_buffer().await?;
-            return Err(parse_error(Response::from_parts(part, buf)).await?);
-        }
-        Ok((RpRead::new(), resp.into_body()))
+        let range = match resp.status() {
+            // The proxy has seeked to the offset already.
+            StatusCode::PARTIAL_CONTENT => BytesRange::new(0, args.range().size()),
+            StatusCode::OK => args.range(),
+            StatusCode::RANGE_NOT_SATISFIABLE => {
+                return Err(Error::new(
+                    ErrorKind::RangeNotSatisfied,
+                    "read offset is beyond the end of file",
+                )
+                .with_context("offset", args.range().offset().to_string()));
+            }
+            _ => {
+                let (part, mut body) = resp.into_parts();
+                let buf = body.to_buffer().await?;
+                return Err(parse_error(Response::from_parts(part, buf)).await?);
+            }
+        };
+
+        let r = AlluxioReader::new(self.core.clone(), stream_id, resp.into_body(), range);
+        Ok((RpRead::new(), r))
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
@@ -277,4 +289,59 @@ mod test {
 
         assert!(builder.is_ok());
     }
+
+    async fn read_range(partial: bool, range: BytesRange) -> (String, Option<String>) {
+        let server = MockServer::start(move |req| {
+            if req.uri.ends_with("/open-file") {
+                return MockResponse::new(StatusCode::OK).with_body("1");
+            }
+            if !req.uri.ends_with("/read") {
+                return MockResponse::new(StatusCode::OK);
+            }
+            match req.header("range") {
+                Some(_) if partial => {
+                    MockResponse::new(StatusCode::PARTIAL_CONTENT).with_body("lo wo")
+                }
+                _ => MockResponse::new(StatusCode::OK).with_body("hello world"),
+            }
+        });
+        let backend = AlluxioBuilder::default()
+            .endpoint(server.endpoint())
+            .build()
+            .unwrap();
+
+        let (_, mut r) = backend
+            .read("file", OpRead::new().with_range(range))
+            .await
+            .unwrap();
+        let mut bs = vec![];
+        loop {
+            let buf = oio::Read::read(&mut r).await.unwrap();
+            if buf.is_empty() {
+                break;
+            }
+            bs.extend_from_slice(&buf.to_bytes());
+        }
+
+        let range_header = server
+            .requests()
+            .iter()
+            .find(|req| req.uri.ends_with("/read"))
+            .and_then(|req| req.header("range").map(|v| v.to_string()));
+        (String::from_utf8(bs).unwrap(), range_header)
+    }
+
+    #[tokio::test]
+    async fn test_read_with_range() {
+        let range = BytesRange::new(3, Some(5));
+
+        // The offset is passed to the proxy.
+        let (content, range_header) = read_range(true, range).await;
+        assert_eq!(content, "lo wo");
+        assert_eq!(range_header.as_deref(), Some("bytes=3-7"));
+
+        // Proxies ignoring the range return the whole stream.
+        let (content, _) = read_range(false, range).await;
+        assert_eq!(content, "lo wo");
+    }
 }
@@ -19,6 +19,7 @@ use std::fmt::Debug;
 use std::fmt::Formatter;
 
 use bytes::Buf;
+use http::header::RANGE;
 use http::Request;
 use http::Response;
 use http::StatusCode;
@@ -288,15 +289,22 @@ impl AlluxioCore {
         }
     }
 
-    /// TODO: we should implement range support correctly.
+    /// Read the opened stream.
     ///
-    /// Please refer to [alluxio-py](https://github.com/Alluxio/alluxio-py/blob/main/alluxio/const.py#L18)
-    pub async fn read(&self, stream_id: u64, _: BytesRange) -> Result<Response<HttpBody>> {
-        let req = Request::post(format!(
+    /// The range is sent as `Range` header so that the proxy could seek to
+    /// the offset. Proxies ignoring it return the whole stream with
+    /// `200 OK`, then the range will be applied by `AlluxioReader` while
+    /// streaming.
+    pub async fn read(&self, stream_id: u64, range: BytesRange) -> Result<Response<HttpBody>> {
+        let mut req = Request::post(format!(
             "{}/api/v1/streams/{}/read",
             self.endpoint, stream_id,
         ));
 
+        if !range.is_full() {
+            req = req.header(RANGE, range.to_header());
+        }
+
         let req = req.body(Buffer::new()).map_err(new_request_build_error)?;
 
         self.client.fetch(req).await
@@ -22,4 +22,5 @@ pub use backend::AlluxioConfig;
 mod core;
 mod error;
 mod lister;
+mod reader;
 mod writer;
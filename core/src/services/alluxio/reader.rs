@@ -0,0 +1,175 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use bytes::Buf;
+
+use super::core::AlluxioCore;
+use crate::raw::*;
+use crate::*;
+
+/// AlluxioReader reads the given range from an opened alluxio stream.
+///
+/// The range is passed to the proxy, but proxies ignoring it return the
+/// stream from the beginning of the file. In that case the range is applied
+/// while streaming: bytes before the offset will be discarded and the stream
+/// will be closed as soon as the range is consumed.
+pub struct AlluxioReader {
+    core: Arc<AlluxioCore>,
+    stream_id: u64,
+    body: RangedBody<HttpBody>,
+    closed: bool,
+}
+
+impl AlluxioReader {
+    pub fn new(core: Arc<AlluxioCore>, stream_id: u64, body: HttpBody, range: BytesRange) -> Self {
+        AlluxioReader {
+            core,
+            stream_id,
+            body: RangedBody::new(body, range),
+            closed: false,
+        }
+    }
+}
+
+impl oio::Read for AlluxioReader {
+    async fn read(&mut self) -> Result<Buffer> {
+        if self.closed {
+            return Ok(Buffer::new());
+        }
+
+        let buf = self.body.read().await?;
+        if buf.is_empty() {
+            self.closed = true;
+            self.core.close(self.stream_id).await?;
+        }
+        Ok(buf)
+    }
+}
+
+/// RangedBody yields only the bytes in `range` of the inner reader.
+struct RangedBody<R> {
+    inner: R,
+    offset: u64,
+    /// Bytes to discard before the range starts.
+    skip: u64,
+    /// Bytes left in the range, `None` means reading till the end.
+    remaining: Option<u64>,
+    /// Whether any byte at or after the offset has been seen.
+    reached: bool,
+}
+
+impl<R: oio::Read> RangedBody<R> {
+    fn new(inner: R, range: BytesRange) -> Self {
+        RangedBody {
+            inner,
+            offset: range.offset(),
+            skip: range.offset(),
+            remaining: range.size(),
+            reached: false,
+        }
+    }
+
+    async fn read(&mut self) -> Result<Buffer> {
+        loop {
+            if self.remaining == Some(0) {
+                return Ok(Buffer::new());
+            }
+
+            let mut buf = self.inner.read().await?;
+            if buf.is_empty() {
+                // Same as `416 Range Not Satisfiable`: the offset is at or
+                // beyond the end of file.
+                if self.offset > 0 && !self.reached {
+                    return Err(Error::new(
+                        ErrorKind::RangeNotSatisfied,
+                        "read offset is beyond the end of file",
+                    )
+                    .with_context("offset", self.offset.to_string()));
+                }
+                return Ok(Buffer::new());
+            }
+
+            if (buf.len() as u64) <= self.skip {
+                self.skip -= buf.len() as u64;
+                continue;
+            }
+            buf.advance(self.skip as usize);
+            self.skip = 0;
+            self.reached = true;
+
+            if let Some(remaining) = self.remaining.as_mut() {
+                buf.truncate((*remaining).min(buf.len() as u64) as usize);
+                *remaining -= buf.len() as u64;
+            }
+            return Ok(buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use super::*;
+
+    struct MockBody(VecDeque<Buffer>);
+
+    impl oio::Read for MockBody {
+        async fn read(&mut self) -> Result<Buffer> {
+            Ok(self.0.pop_front().unwrap_or_default())
+        }
+    }
+
+    async fn read_range(range: BytesRange) -> Result<Vec<u8>> {
+        let chunks = ["hello", " ", "world"].map(Buffer::from).into();
+        let mut body = RangedBody::new(MockBody(chunks), range);
+
+        let mut bs = vec![];
+        loop {
+            let buf = body.read().await?;
+            if buf.is_empty() {
+                return Ok(bs);
+            }
+            bs.extend_from_slice(&buf.to_bytes());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ranged_body() {
+        let cases = vec![
+            (BytesRange::new(0, None), "hello world"),
+            (BytesRange::new(0, Some(5)), "hello"),
+            (BytesRange::new(3, Some(5)), "lo wo"),
+            (BytesRange::new(5, Some(1)), " "),
+            (BytesRange::new(6, None), "world"),
+            (BytesRange::new(8, Some(100)), "rld"),
+            (BytesRange::new(3, Some(0)), ""),
+        ];
+
+        for (range, expected) in cases {
+            let bs = read_range(range).await.unwrap();
+            assert_eq!(String::from_utf8(bs).unwrap(), expected, "{range}");
+        }
+
+        for offset in [11, 20] {
+            let err = read_range(BytesRange::new(offset, None)).await.unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::RangeNotSatisfied);
+        }
+    }
+}
@@ -34,7 +34,9 @@ use serde::Deserialize;
 use serde::Serialize;
 use sha2::Digest;
 use sha2::Sha256;
+use uuid::Uuid;
 
+use super::core::multipart_block_id;
 use super::error::parse_error;
 use super::lister::AzblobLister;
 use super::writer::AzblobWriter;
@@ -565,6 +567,8 @@ impl Access for AzblobBackend {
                 stat: true,
                 stat_with_if_match: true,
                 stat_with_if_none_match: true,
+                stat_with_if_modified_since: true,
+                stat_with_if_unmodified_since: true,
 
                 read: true,
 
@@ -574,21 +578,32 @@ impl Access for AzblobBackend {
 
                 write: true,
                 write_can_append: true,
+                write_returns_append_offset: true,
                 write_can_empty: true,
                 write_can_multi: true,
                 write_with_cache_control: true,
                 write_with_content_type: true,
+                write_with_content_md5: true,
+                write_returns_etag: true,
+                write_returns_version: true,
+                write_returns_last_modified: true,
 
                 delete: true,
                 copy: true,
+                copy_with_if_not_exists: true,
 
                 list: true,
                 list_with_recursive: true,
+                list_ordered: true,
 
                 presign: self.has_sas_token,
                 presign_stat: self.has_sas_token,
                 presign_read: self.has_sas_token,
                 presign_write: self.has_sas_token,
+                presign_delete: self.has_sas_token,
+                presign_upload_part: self.has_sas_token,
+
+                multipart: true,
 
                 batch: true,
                 batch_delete: true,
@@ -616,7 +631,10 @@ impl Access for AzblobBackend {
 
         let status = resp.status();
         match status {
-            StatusCode::OK | StatusCode::PARTIAL_CONTENT => Ok((RpRead::new(), resp.into_body())),
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
+                let etag = parse_etag(resp.headers())?.map(|v| v.to_string());
+                Ok((RpRead::new().with_etag(etag), resp.into_body()))
+            }
             _ => {
                 let (part, mut body) = resp.into_parts();
                 let buf = body.to_buffer().await?;
@@ -662,13 +680,20 @@ impl Access for AzblobBackend {
         Ok((RpList::default(), oio::PageLister::new(l)))
     }
 
-    async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
-        let resp = self.core.azblob_copy_blob(from, to).await?;
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let resp = self.core.azblob_copy_blob(from, to, &args).await?;
 
         let status = resp.status();
 
         match status {
             StatusCode::ACCEPTED => Ok(RpCopy::default()),
+            // Azure reports an existing target as `BlobAlreadyExists`.
+            StatusCode::CONFLICT if args.if_not_exists() => Err(Error::new(
+                ErrorKind::ConditionNotMatch,
+                "target blob already exists",
+            )
+            .with_context("to", to)
+            .set_source(parse_error(resp).await?)),
             _ => Err(parse_error(resp).await?),
         }
     }
@@ -680,11 +705,22 @@ impl Access for AzblobBackend {
                 self.core
                     .azblob_get_blob_request(path, BytesRange::default(), v)?
             }
-            PresignOperation::Write(_) => {
+            PresignOperation::Write(v) => {
                 self.core
-                    .azblob_put_blob_request(path, None, &OpWrite::default(), Buffer::new())?
+                    .azblob_put_blob_request(path, None, v, Buffer::new())?
             }
+            PresignOperation::Delete(_) => self.core.azblob_delete_blob_request(path)?,
+            PresignOperation::UploadPart(v) => self.core.azblob_put_block_request(
+                path,
+                multipart_block_id(v.upload_id(), v.part_number())?,
+                None,
+                &OpWrite::default(),
+                Buffer::new(),
+            )?,
         };
+        // SAS tokens can't cover headers, they are returned for clients to
+        // send as is.
+        req.headers_mut().extend(args.signed_headers().clone());
 
         self.core.sign_query(&mut req).await?;
 
@@ -697,9 +733,41 @@ impl Access for AzblobBackend {
         )))
     }
 
+    async fn initiate_multipart(&self, _: &str, _: OpWrite) -> Result<RpInitiateMultipart> {
+        // Blocks are staged on the blob directly, there is no upload session
+        // to create on server side.
+        let mut bs = *Uuid::new_v4().as_bytes();
+        bs[12..].fill(0);
+        Ok(RpInitiateMultipart::new(
+            &Uuid::from_bytes(bs).hyphenated().to_string(),
+        ))
+    }
+
+    async fn complete_multipart(
+        &self,
+        path: &str,
+        args: OpCompleteMultipart,
+    ) -> Result<RpCompleteMultipart> {
+        let block_ids = args
+            .parts()
+            .iter()
+            .map(|p| multipart_block_id(args.upload_id(), p.part_number()))
+            .collect::<Result<Vec<_>>>()?;
+
+        let w = AzblobWriter::new(self.core.clone(), OpWrite::default(), path.to_string());
+        let meta = oio::BlockWrite::complete_block(&w, block_ids).await?;
+        Ok(RpCompleteMultipart::new(meta))
+    }
+
+    async fn abort_multipart(&self, _: &str, args: OpAbortMultipart) -> Result<RpAbortMultipart> {
+        // Uncommitted blocks can't be deleted explicitly, azblob will
+        // garbage collect them after a week. Only check the upload id here.
+        multipart_block_id(args.upload_id(), 0)?;
+        Ok(RpAbortMultipart::new())
+    }
+
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
-        let ops = args.into_operation();
-        let paths = ops.into_iter().map(|(p, _)| p).collect::<Vec<_>>();
+        let paths = args.into_delete_paths()?;
         if paths.len() > AZBLOB_BATCH_LIMIT {
             return Err(Error::new(
                 ErrorKind::Unsupported,
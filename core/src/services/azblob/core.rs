@@ -28,7 +28,9 @@ use http::header::HeaderName;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
 use http::header::IF_MATCH;
+use http::header::IF_MODIFIED_SINCE;
 use http::header::IF_NONE_MATCH;
+use http::header::IF_UNMODIFIED_SINCE;
 use http::HeaderValue;
 use http::Request;
 use http::Response;
@@ -48,6 +50,7 @@ mod constants {
     pub const X_MS_BLOB_TYPE: &str = "x-ms-blob-type";
     pub const X_MS_COPY_SOURCE: &str = "x-ms-copy-source";
     pub const X_MS_BLOB_CACHE_CONTROL: &str = "x-ms-blob-cache-control";
+    pub const X_MS_BLOB_CONTENT_MD5: &str = "x-ms-blob-content-md5";
     pub const X_MS_BLOB_CONDITION_APPENDPOS: &str = "x-ms-blob-condition-appendpos";
 
     // Server-side encryption with customer-provided headers
@@ -257,6 +260,11 @@ impl AzblobCore {
             req = req.header(CONTENT_TYPE, ty)
         }
 
+        // Azblob verifies the content against Content-MD5 and stores it.
+        if let Some(md5) = args.content_md5() {
+            req = req.header("Content-MD5", md5)
+        }
+
         req = req.header(
             HeaderName::from_static(constants::X_MS_BLOB_TYPE),
             "BlockBlob",
@@ -442,6 +450,11 @@ impl AzblobCore {
         if let Some(cache_control) = args.cache_control() {
             req = req.header(constants::X_MS_BLOB_CACHE_CONTROL, cache_control);
         }
+        // Blocks can't be verified as a whole, store the md5 that has been
+        // verified client-side instead.
+        if let Some(md5) = args.content_md5() {
+            req = req.header(constants::X_MS_BLOB_CONTENT_MD5, md5);
+        }
 
         let content = quick_xml::se::to_string(&PutBlockListRequest {
             latest: block_ids
@@ -501,6 +514,14 @@ impl AzblobCore {
             req = req.header(IF_MATCH, if_match);
         }
 
+        if let Some(v) = args.if_modified_since() {
+            req = req.header(IF_MODIFIED_SINCE, format_datetime_into_http_date(v));
+        }
+
+        if let Some(v) = args.if_unmodified_since() {
+            req = req.header(IF_UNMODIFIED_SINCE, format_datetime_into_http_date(v));
+        }
+
         let req = req.body(Buffer::new()).map_err(new_request_build_error)?;
 
         Ok(req)
@@ -541,7 +562,12 @@ impl AzblobCore {
         self.send(req).await
     }
 
-    pub async fn azblob_copy_blob(&self, from: &str, to: &str) -> Result<Response<Buffer>> {
+    pub async fn azblob_copy_blob(
+        &self,
+        from: &str,
+        to: &str,
+        args: &OpCopy,
+    ) -> Result<Response<Buffer>> {
         let source = build_abs_path(&self.root, from);
         let target = build_abs_path(&self.root, to);
 
@@ -560,9 +586,13 @@ impl AzblobCore {
 
         let mut req = Request::put(&target)
             .header(constants::X_MS_COPY_SOURCE, source)
-            .header(CONTENT_LENGTH, 0)
-            .body(Buffer::new())
-            .map_err(new_request_build_error)?;
+            .header(CONTENT_LENGTH, 0);
+
+        if args.if_not_exists() {
+            req = req.header(IF_NONE_MATCH, "*");
+        }
+
+        let mut req = req.body(Buffer::new()).map_err(new_request_build_error)?;
 
         self.sign(&mut req).await?;
         self.send(req).await
@@ -628,6 +658,28 @@ impl AzblobCore {
     }
 }
 
+/// Build the block id of a part uploaded via presigned multipart upload.
+///
+/// Azblob doesn't have multipart upload sessions, the upload id is a random
+/// uuid generated by us, the part number is stored in its last 4 bytes so
+/// that the block list can be rebuilt from part numbers while completing.
+pub fn multipart_block_id(upload_id: &str, part_number: usize) -> Result<Uuid> {
+    let upload_id = Uuid::parse_str(upload_id).map_err(|err| {
+        Error::new(ErrorKind::ConfigInvalid, "upload id is not a valid uuid")
+            .with_context("upload_id", upload_id)
+            .set_source(err)
+    })?;
+    let part_number = u32::try_from(part_number).map_err(|err| {
+        Error::new(ErrorKind::ConfigInvalid, "part number is too large")
+            .with_context("part_number", part_number.to_string())
+            .set_source(err)
+    })?;
+
+    let mut bs = *upload_id.as_bytes();
+    bs[12..].copy_from_slice(&part_number.to_be_bytes());
+    Ok(Uuid::from_bytes(bs))
+}
+
 /// Request of PutBlockListRequest
 #[derive(Default, Debug, Serialize, Deserialize)]
 #[serde(default, rename = "BlockList", rename_all = "PascalCase")]
@@ -685,6 +737,19 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_multipart_block_id() {
+        let upload_id = "3c3e6a7a-2b4d-4f7e-9b1a-5d6c7e8f9a0b";
+
+        let first = multipart_block_id(upload_id, 1).unwrap();
+        let second = multipart_block_id(upload_id, 2).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(first, multipart_block_id(upload_id, 1).unwrap());
+        assert_eq!(&first.as_bytes()[12..], &[0, 0, 0, 1]);
+
+        assert!(multipart_block_id("not-a-uuid", 1).is_err());
+    }
+
     #[test]
     fn test_parse_xml() {
         let bs = bytes::Bytes::from(
@@ -26,6 +26,7 @@ use crate::raw::*;
 use crate::*;
 
 const X_MS_BLOB_TYPE: &str = "x-ms-blob-type";
+const X_MS_BLOB_APPEND_OFFSET: &str = "x-ms-blob-append-offset";
 
 pub type AzblobWriters = TwoWays<oio::BlockWriter<AzblobWriter>, oio::AppendWriter<AzblobWriter>>;
 
@@ -88,7 +89,7 @@ impl oio::AppendWrite for AzblobWriter {
         }
     }
 
-    async fn append(&self, offset: u64, size: u64, body: Buffer) -> Result<()> {
+    async fn append(&self, offset: u64, size: u64, body: Buffer) -> Result<Metadata> {
         let mut req = self
             .core
             .azblob_append_blob_request(&self.path, offset, size, body)?;
@@ -99,14 +100,25 @@ impl oio::AppendWrite for AzblobWriter {
 
         let status = resp.status();
         match status {
-            StatusCode::CREATED => Ok(()),
+            StatusCode::CREATED => {
+                let mut meta = parse_write_metadata(resp.headers())?;
+                // The offset at which the block was committed.
+                if let Some(v) = parse_header_to_str(resp.headers(), X_MS_BLOB_APPEND_OFFSET)? {
+                    let v = v.parse::<u64>().map_err(|e| {
+                        Error::new(ErrorKind::Unexpected, "header value is not valid integer")
+                            .set_source(e)
+                    })?;
+                    meta.set_append_offset(v);
+                }
+                Ok(meta)
+            }
             _ => Err(parse_error(resp).await?),
         }
     }
 }
 
 impl oio::BlockWrite for AzblobWriter {
-    async fn write_once(&self, size: u64, body: Buffer) -> Result<()> {
+    async fn write_once(&self, size: u64, body: Buffer) -> Result<Metadata> {
         let mut req: http::Request<Buffer> =
             self.core
                 .azblob_put_blob_request(&self.path, Some(size), &self.op, body)?;
@@ -117,7 +129,7 @@ impl oio::BlockWrite for AzblobWriter {
         let status = resp.status();
 
         match status {
-            StatusCode::CREATED | StatusCode::OK => Ok(()),
+            StatusCode::CREATED | StatusCode::OK => parse_write_metadata(resp.headers()),
             _ => Err(parse_error(resp).await?),
         }
     }
@@ -135,7 +147,7 @@ impl oio::BlockWrite for AzblobWriter {
         }
     }
 
-    async fn complete_block(&self, block_ids: Vec<Uuid>) -> Result<()> {
+    async fn complete_block(&self, block_ids: Vec<Uuid>) -> Result<Metadata> {
         let resp = self
             .core
             .azblob_complete_put_block_list(&self.path, block_ids, &self.op)
@@ -143,7 +155,7 @@ impl oio::BlockWrite for AzblobWriter {
 
         let status = resp.status();
         match status {
-            StatusCode::CREATED | StatusCode::OK => Ok(()),
+            StatusCode::CREATED | StatusCode::OK => parse_write_metadata(resp.headers()),
             _ => Err(parse_error(resp).await?),
         }
     }
@@ -155,3 +167,12 @@ impl oio::BlockWrite for AzblobWriter {
         Ok(())
     }
 }
+
+/// Parse the metadata of written blob from response headers.
+fn parse_write_metadata(headers: &http::HeaderMap) -> Result<Metadata> {
+    let mut meta = parse_into_write_metadata(headers)?;
+    if let Some(v) = parse_header_to_str(headers, "x-ms-version-id")? {
+        meta.set_version(v);
+    }
+    Ok(meta)
+}
@@ -15,10 +15,22 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::future::Future;
 use std::sync::Arc;
-
+use std::time::Duration;
+use std::time::Instant;
+
+use backon::BackoffBuilder;
+use backon::ExponentialBuilder;
+use futures::stream;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use http::header::IF_NONE_MATCH;
+use http::HeaderMap;
+use http::HeaderValue;
 use http::Response;
 use http::StatusCode;
 use log::debug;
@@ -27,10 +39,24 @@ use reqsign::AzureStorageLoader;
 use reqsign::AzureStorageSigner;
 use serde::Deserialize;
 use serde::Serialize;
+use tokio::sync::OnceCell;
 
+use super::core::parse_properties;
 use super::core::AzdlsCore;
+use super::core::ClientRequestIdFn;
+use super::core::X_MS_ACCESS_TIER;
+use super::core::X_MS_COPY_STATUS;
+use super::core::X_MS_CREATION_TIME;
+use super::core::X_MS_PROPERTIES;
+use super::core::X_MS_VERSION_ID;
 use super::error::parse_error;
+use super::lister::AzdlsDirSize;
 use super::lister::AzdlsLister;
+use super::lister::AzdlsPageLister;
+use super::lister::AzdlsVersionLister;
+use super::reader::AzdlsDecompressReader;
+use super::reader::AzdlsPrefetchReader;
+use super::reader::ContentEncoding;
 use super::writer::AzdlsWriter;
 use super::writer::AzdlsWriters;
 use crate::raw::*;
@@ -46,6 +72,15 @@ const KNOWN_AZDLS_ENDPOINT_SUFFIX: &[&str] = &[
     "dfs.core.chinacloudapi.cn",
 ];
 
+/// Size of appends used by [`AzdlsBackend::write_from_reader`].
+const STREAM_WRITE_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+
+/// Backoff used to poll the status of pending copies in rename fallback,
+/// gives up after about 5 minutes.
+const COPY_STATUS_MIN_DELAY: Duration = Duration::from_millis(100);
+const COPY_STATUS_MAX_DELAY: Duration = Duration::from_secs(10);
+const COPY_STATUS_MAX_POLLS: usize = 36;
+
 /// Azure Data Lake Storage Gen2 Support.
 #[derive(Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct AzdlsConfig {
@@ -59,6 +94,72 @@ pub struct AzdlsConfig {
     pub account_name: Option<String>,
     /// Account key of this backend.
     pub account_key: Option<String>,
+    /// Blob endpoint of this backend.
+    ///
+    /// Used by operations that must go through the blob service (like copy).
+    /// If not set, it will be derived from `endpoint` by replacing `dfs` with
+    /// `blob` in the host name.
+    pub blob_endpoint: Option<String>,
+    /// Allow anonymous will allow opendal to send read requests without
+    /// signing when credential is not loaded.
+    pub allow_anonymous: bool,
+    /// Decompress the content transparently while reading if it's stored
+    /// with `Content-Encoding: gzip` or `deflate`.
+    pub enable_decompression: bool,
+    /// Override the `Accept-Encoding` header of read requests, set to
+    /// `identity` to make sure responses are never transfer-compressed.
+    pub accept_encoding: Option<String>,
+    /// Use `endpoint` and `blob_endpoint` exactly as given without trimming
+    /// the trailing `/`.
+    pub preserve_endpoint: bool,
+    /// Stat the directory after `create_dir` to make sure it's visible
+    /// before returning.
+    pub confirm_create_dir: bool,
+    /// Read ahead at least this many bytes from the response body before
+    /// returning data to the caller.
+    pub read_prefetch_size: Option<usize>,
+    /// Additional dfs endpoint suffix used to infer the account name from
+    /// `endpoint`, like `dfs.local.azurestack.external`.
+    pub endpoint_suffix: Option<String>,
+    /// Fetch blob index tags in `stat`, which costs an extra request.
+    pub enable_stat_tags: bool,
+    /// Max `307`/`308` redirects to follow for read requests, disabled if
+    /// not set.
+    pub max_redirects: Option<usize>,
+    /// Retry budget of requests within the backend, disabled if not set.
+    ///
+    /// Only idempotent requests are retried, appends are retried only if
+    /// the previous attempt is known not to be committed.
+    pub max_retries: Option<usize>,
+    /// Max time to establish the connection, unlimited if not set.
+    pub connect_timeout: Option<Duration>,
+    /// Max time to wait for the response after sending a request without
+    /// body, unlimited if not set.
+    pub first_byte_timeout: Option<Duration>,
+    /// Max time of the whole request including reading the response body,
+    /// unlimited if not set.
+    pub timeout: Option<Duration>,
+    /// Create the filesystem on first write if it doesn't exist.
+    pub create_filesystem_if_not_exists: bool,
+    /// Stat the file after each flush and check that its length matches
+    /// the written bytes.
+    pub verify_on_write: bool,
+    /// Cache results of `AzdlsBackend::dir_size` for this long, disabled if
+    /// not set.
+    pub dir_size_cache_ttl: Option<Duration>,
+    /// Skip directory entries in list results, only files will be returned.
+    pub skip_list_dirs: bool,
+    /// Fall back to copy and delete if azdls rejects `rename` as
+    /// unsupported.
+    pub rename_fallback_to_copy: bool,
+    /// Prefix of the `x-ms-client-request-id` generated for every request.
+    pub client_request_id_prefix: Option<String>,
+    /// Additional query parameters appended to every request, in the form of
+    /// a percent-encoded query string like `k1=v1&k2=v2`.
+    ///
+    /// It's an escape hatch to opt into features that are not modeled yet,
+    /// parameters already set by opendal will not be overwritten.
+    pub extra_query: Option<String>,
 }
 
 impl Debug for AzdlsConfig {
@@ -68,6 +169,30 @@ impl Debug for AzdlsConfig {
         ds.field("root", &self.root);
         ds.field("filesystem", &self.filesystem);
         ds.field("endpoint", &self.endpoint);
+        ds.field("blob_endpoint", &self.blob_endpoint);
+        ds.field("allow_anonymous", &self.allow_anonymous);
+        ds.field("enable_decompression", &self.enable_decompression);
+        ds.field("accept_encoding", &self.accept_encoding);
+        ds.field("preserve_endpoint", &self.preserve_endpoint);
+        ds.field("confirm_create_dir", &self.confirm_create_dir);
+        ds.field("skip_list_dirs", &self.skip_list_dirs);
+        ds.field("rename_fallback_to_copy", &self.rename_fallback_to_copy);
+        ds.field("client_request_id_prefix", &self.client_request_id_prefix);
+        ds.field("extra_query", &self.extra_query);
+        ds.field("read_prefetch_size", &self.read_prefetch_size);
+        ds.field("endpoint_suffix", &self.endpoint_suffix);
+        ds.field("enable_stat_tags", &self.enable_stat_tags);
+        ds.field("max_redirects", &self.max_redirects);
+        ds.field("max_retries", &self.max_retries);
+        ds.field("connect_timeout", &self.connect_timeout);
+        ds.field("first_byte_timeout", &self.first_byte_timeout);
+        ds.field("timeout", &self.timeout);
+        ds.field(
+            "create_filesystem_if_not_exists",
+            &self.create_filesystem_if_not_exists,
+        );
+        ds.field("verify_on_write", &self.verify_on_write);
+        ds.field("dir_size_cache_ttl", &self.dir_size_cache_ttl);
 
         if self.account_name.is_some() {
             ds.field("account_name", &"<redacted>");
@@ -85,6 +210,8 @@ impl Configurator for AzdlsConfig {
         AzdlsBuilder {
             config: self,
             http_client: None,
+            credential_loader: None,
+            client_request_id_fn: None,
         }
     }
 }
@@ -95,6 +222,8 @@ impl Configurator for AzdlsConfig {
 pub struct AzdlsBuilder {
     config: AzdlsConfig,
     http_client: Option<HttpClient>,
+    credential_loader: Option<Arc<AzureStorageLoader>>,
+    client_request_id_fn: Option<ClientRequestIdFn>,
 }
 
 impl Debug for AzdlsBuilder {
@@ -134,8 +263,24 @@ impl AzdlsBuilder {
     /// - Azurite: `http://127.0.0.1:10000/devstoreaccount1`
     pub fn endpoint(mut self, endpoint: &str) -> Self {
         if !endpoint.is_empty() {
-            // Trim trailing `/` so that we can accept `http://127.0.0.1:9000/`
-            self.config.endpoint = Some(endpoint.trim_end_matches('/').to_string());
+            self.config.endpoint = Some(endpoint.to_string());
+        }
+
+        self
+    }
+
+    /// Set blob endpoint of this backend.
+    ///
+    /// Blob endpoint is used by operations that must go through the blob
+    /// service like `copy`. By default, it's derived from endpoint by
+    /// replacing `dfs` with `blob`, e.g. `https://accountname.dfs.core.windows.net`
+    /// will be mapped to `https://accountname.blob.core.windows.net`.
+    ///
+    /// Set this value if the derived endpoint is wrong, for example, while
+    /// using private endpoints or custom DNS.
+    pub fn blob_endpoint(mut self, endpoint: &str) -> Self {
+        if !endpoint.is_empty() {
+            self.config.blob_endpoint = Some(endpoint.to_string());
         }
 
         self
@@ -165,6 +310,312 @@ impl AzdlsBuilder {
         self
     }
 
+    /// Allow anonymous will allow opendal to send read requests (like `stat`,
+    /// `read` and `list`) without signing when credential is not loaded.
+    ///
+    /// This is useful for reading from public filesystems. Write requests
+    /// still require valid credential.
+    pub fn allow_anonymous(mut self) -> Self {
+        self.config.allow_anonymous = true;
+        self
+    }
+
+    /// Enable decompression will make opendal decompress the content
+    /// transparently while reading if the file is stored with
+    /// `Content-Encoding: gzip` or `deflate`.
+    ///
+    /// # Notes
+    ///
+    /// Compressed content can't be decoded from the middle, so reads on
+    /// compressed files always fetch and decode from the start of the file,
+    /// the bytes before the requested range are discarded. Stat on them
+    /// decodes the whole content to report the decompressed length. Avoid
+    /// chunked or concurrent reads and repeated stats on large compressed
+    /// files, since each of them fetches the content again.
+    pub fn enable_decompression(mut self) -> Self {
+        self.config.enable_decompression = true;
+        self
+    }
+
+    /// Set the `Accept-Encoding` header sent with read requests.
+    ///
+    /// Intermediaries like proxies may compress responses on the fly, which
+    /// breaks range math and length checks. Set it to `identity` to make sure
+    /// the returned bytes and `Content-Length` always match the stored
+    /// content.
+    ///
+    /// # Notes
+    ///
+    /// This doesn't affect content stored with `Content-Encoding`, use
+    /// [`AzdlsBuilder::enable_decompression`] to decode it.
+    pub fn accept_encoding(mut self, v: &str) -> Self {
+        self.config.accept_encoding = if v.is_empty() {
+            None
+        } else {
+            Some(v.to_string())
+        };
+        self
+    }
+
+    /// Set the read prefetch size.
+    ///
+    /// The response body arrives in small chunks, consumers that read a few
+    /// bytes at a time will wait for the network on every chunk. With this
+    /// set, blocks of at least `size` bytes are read ahead in background by
+    /// the executor of the read, while the caller is consuming the previous
+    /// block.
+    ///
+    /// Seeking outside of the buffered data drops the prefetched bytes and
+    /// starts a new read from the target position.
+    pub fn read_prefetch_size(mut self, size: usize) -> Self {
+        self.config.read_prefetch_size = Some(size);
+        self
+    }
+
+    /// Set an additional dfs endpoint suffix for account name inference.
+    ///
+    /// The account name is inferred from `endpoint` only if the endpoint ends
+    /// with a well-known public cloud suffix. Azure Stack Hub and private
+    /// clouds use their own suffixes like `dfs.local.azurestack.external`,
+    /// set it here so the account name can still be inferred.
+    pub fn endpoint_suffix(mut self, suffix: &str) -> Self {
+        if !suffix.is_empty() {
+            self.config.endpoint_suffix = Some(suffix.to_string());
+        }
+        self
+    }
+
+    /// Confirm the directory exists after `create_dir`.
+    ///
+    /// With this enabled, `create_dir` will send a follow-up stat and only
+    /// return after azdls reports the path as a `directory`. This closes the
+    /// window where a just-created directory isn't yet listable, at the cost
+    /// of an extra request.
+    ///
+    /// A missing directory will be returned as a temporary error so that
+    /// it can be retried by `RetryLayer`.
+    pub fn confirm_create_dir(mut self) -> Self {
+        self.config.confirm_create_dir = true;
+        self
+    }
+
+    /// Skip directory entries in list results.
+    ///
+    /// Azdls has real directories which are returned by `list` like files.
+    /// With this enabled, `list` only returns files, which matches the flat
+    /// view of object stores like s3. Combine with recursive list to walk all
+    /// files under a prefix.
+    ///
+    /// Since nested dirs can't be listed anymore, deleting a dir will delete
+    /// everything under it, so that `remove_all` won't leave empty dirs
+    /// behind.
+    pub fn skip_list_dirs(mut self) -> Self {
+        self.config.skip_list_dirs = true;
+        self
+    }
+
+    /// Fall back to copy and delete if `rename` is rejected as unsupported.
+    ///
+    /// Some accounts can't serve rename on the dfs endpoint, for example
+    /// when account features like blob soft delete are enabled. Such
+    /// renames fail with [`ErrorKind::Unsupported`] by default. With this
+    /// enabled, the file will be copied via the blob endpoint of the same
+    /// account and the source will be deleted after the copy succeeds.
+    ///
+    /// The fallback is not atomic and only works for files.
+    pub fn rename_fallback_to_copy(mut self) -> Self {
+        self.config.rename_fallback_to_copy = true;
+        self
+    }
+
+    /// Set the prefix of client request ids.
+    ///
+    /// Every request will carry a `x-ms-client-request-id` of `{prefix}{uuid}`,
+    /// which is recorded in Azure's server-side diagnostic logs.
+    pub fn client_request_id_prefix(mut self, prefix: &str) -> Self {
+        if !prefix.is_empty() {
+            self.config.client_request_id_prefix = Some(prefix.to_string());
+        }
+        self
+    }
+
+    /// Append an additional query parameter to every request.
+    ///
+    /// Key and value will be percent-encoded. The parameters are added before
+    /// signing, so they are covered by the shared key signature. Parameters
+    /// already set by opendal for an operation are kept as is.
+    ///
+    /// # Notes
+    ///
+    /// This is an escape hatch for features that opendal doesn't model yet.
+    /// Parameters that change the semantics of requests (like `resource`,
+    /// `action` or SAS fields) can break signing or the operations built on
+    /// top of them, use with care.
+    pub fn extra_query_param(mut self, key: &str, value: &str) -> Self {
+        if key.is_empty() {
+            return self;
+        }
+        let kv = format!(
+            "{}={}",
+            percent_encode_path(key),
+            percent_encode_path(value)
+        );
+        self.config.extra_query = Some(match self.config.extra_query.take() {
+            Some(v) if !v.is_empty() => format!("{v}&{kv}"),
+            _ => kv,
+        });
+        self
+    }
+
+    /// Register a hook to provide the client request id of requests.
+    ///
+    /// The hook is called for every request before it's signed, the returned
+    /// id will be sent as `x-ms-client-request-id` and attached to errors as
+    /// `client_request_id`. Read the id from the current trace context in the
+    /// hook to correlate operations with Azure's server-side diagnostic logs.
+    ///
+    /// Returning `None` falls back to [`AzdlsBuilder::client_request_id_prefix`].
+    pub fn client_request_id(
+        mut self,
+        f: impl Fn() -> Option<String> + Send + Sync + 'static,
+    ) -> Self {
+        self.client_request_id_fn = Some(Arc::new(f));
+        self
+    }
+
+    /// Fetch blob index tags while `stat` files.
+    ///
+    /// Tags are stored in the blob service and are not returned by the dfs
+    /// get properties API, so enabling this costs an extra request for every
+    /// `stat` on a file. The tags can be read via [`Metadata::tags`].
+    pub fn enable_stat_tags(mut self) -> Self {
+        self.config.enable_stat_tags = true;
+        self
+    }
+
+    /// Create the filesystem if it doesn't exist before the first write.
+    ///
+    /// The creation is attempted at most once per backend (including the
+    /// children derived by [`AzdlsBackend::with_root_prefix`]), concurrent
+    /// operations on cold start will wait for the same attempt instead of
+    /// racing to create it. A failed attempt will be retried by the next
+    /// write.
+    pub fn create_filesystem_if_not_exists(mut self) -> Self {
+        self.config.create_filesystem_if_not_exists = true;
+        self
+    }
+
+    /// Verify the written length after each flush.
+    ///
+    /// Once the content is flushed, the file will be stated again and an
+    /// error will be returned if its `Content-Length` doesn't match the bytes
+    /// written, so that partial writes won't be regarded as succeeded. This
+    /// costs an extra request per flush.
+    pub fn verify_on_write(mut self) -> Self {
+        self.config.verify_on_write = true;
+        self
+    }
+
+    /// Cache results of [`AzdlsBackend::dir_size`] for `ttl`.
+    ///
+    /// Dashboards tend to ask for the size of the same directories again and
+    /// again, cached results could be stale for at most `ttl`.
+    pub fn dir_size_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.config.dir_size_cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Follow at most `max` `307`/`308` redirects for read requests.
+    ///
+    /// Geo-redundant (RA-GRS) accounts could redirect reads to the secondary
+    /// endpoint during failover. Redirects are only followed to the same host
+    /// or the secondary host of the account (like
+    /// `account-secondary.dfs.core.windows.net`), and requests are signed
+    /// again for the new location.
+    ///
+    /// # Notes
+    ///
+    /// If [`AzdlsBuilder::http_client`] is set, it should be built with
+    /// redirects disabled, otherwise the client may follow redirects by
+    /// itself with a stale signature.
+    pub fn max_redirects(mut self, max: usize) -> Self {
+        self.config.max_redirects = Some(max);
+        self
+    }
+
+    /// Retry failed requests at most `max` times within the backend.
+    ///
+    /// Only idempotent requests (`GET`, `HEAD`, `DELETE` and `PUT` with
+    /// precondition) will be retried on temporary errors. Appends are
+    /// position based and could duplicate data if retried blindly, they
+    /// are retried only after a stat shows the previous attempt didn't
+    /// commit.
+    ///
+    /// # Notes
+    ///
+    /// `RetryLayer` retries the whole operation without this knowledge,
+    /// prefer this over it for write heavy workloads.
+    pub fn max_retries(mut self, max: usize) -> Self {
+        self.config.max_retries = Some(max);
+        self
+    }
+
+    /// Set the timeout of establishing the connection.
+    ///
+    /// Set this strictly to fail fast on dead endpoints.
+    ///
+    /// # Notes
+    ///
+    /// This can't be applied to the client set via [`AzdlsBuilder::http_client`],
+    /// building will fail if both are set.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.config.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the timeout of waiting for the response after the request has
+    /// been sent, which is the time to first byte.
+    ///
+    /// Only requests without body like reads, stats and deletes are limited,
+    /// writes need to upload the body before the response arrives. Timed out
+    /// requests are temporary errors and could be retried.
+    pub fn first_byte_timeout(mut self, timeout: Duration) -> Self {
+        self.config.first_byte_timeout = Some(timeout);
+        self
+    }
+
+    /// Set the timeout of the whole request, from connecting till the
+    /// response body has been read.
+    ///
+    /// Set this generously for large downloads, and rely on
+    /// [`AzdlsBuilder::connect_timeout`] and [`AzdlsBuilder::first_byte_timeout`]
+    /// to detect dead endpoints.
+    ///
+    /// # Notes
+    ///
+    /// This can't be applied to the client set via [`AzdlsBuilder::http_client`],
+    /// building will fail if both are set.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.config.timeout = Some(timeout);
+        self
+    }
+
+    /// Preserve `endpoint` and `blob_endpoint` exactly as given.
+    ///
+    /// By default, trailing `/` of endpoints will be trimmed so that we can
+    /// accept `http://127.0.0.1:9000/`. Enable this if ADLS is fronted by a
+    /// gateway that routes on a path which must keep its trailing `/`.
+    ///
+    /// # Notes
+    ///
+    /// Request urls are built as `{endpoint}/{filesystem}/{path}` without
+    /// duplicated `/`, so `https://gateway/adls/` will lead to
+    /// `https://gateway/adls/{filesystem}/{path}`.
+    pub fn preserve_endpoint(mut self) -> Self {
+        self.config.preserve_endpoint = true;
+        self
+    }
+
     /// Specify the http client that used by this service.
     ///
     /// # Notes
@@ -175,13 +626,27 @@ impl AzdlsBuilder {
         self.http_client = Some(client);
         self
     }
-}
 
-impl Builder for AzdlsBuilder {
-    const SCHEME: Scheme = Scheme::Azdls;
-    type Config = AzdlsConfig;
+    /// Specify the credential loader that used by this service.
+    ///
+    /// The loader caches the loaded credential (like OAuth tokens) inside,
+    /// so sharing the same loader across operators that point to the same
+    /// account will avoid fetching tokens repeatedly.
+    ///
+    /// # Notes
+    ///
+    /// If credential_loader has been set, we will ignore `account_name` and
+    /// `account_key` while loading credential.
+    pub fn credential_loader(mut self, loader: Arc<AzureStorageLoader>) -> Self {
+        self.credential_loader = Some(loader);
+        self
+    }
 
-    fn build(self) -> Result<impl Access> {
+    /// Build an [`AzdlsBackend`] directly.
+    ///
+    /// Most users should use `Operator::new(builder)` instead. This is
+    /// useful while deriving child backends via [`AzdlsBackend::with_root_prefix`].
+    pub fn build_backend(self) -> Result<AzdlsBackend> {
         debug!("backend build started: {:?}", &self);
 
         let root = normalize_root(&self.config.root.unwrap_or_default());
@@ -194,59 +659,567 @@ impl Builder for AzdlsBuilder {
                 .with_operation("Builder::build")
                 .with_context("service", Scheme::Azdls)),
         }?;
+        check_filesystem_name(filesystem)?;
         debug!("backend use filesystem {}", &filesystem);
 
+        // Trim trailing `/` so that we can accept `http://127.0.0.1:9000/`
+        let trim_endpoint = |v: &str| {
+            if self.config.preserve_endpoint {
+                v.to_string()
+            } else {
+                v.trim_end_matches('/').to_string()
+            }
+        };
+
         let endpoint = match &self.config.endpoint {
-            Some(endpoint) => Ok(endpoint.clone()),
+            Some(endpoint) => Ok(trim_endpoint(endpoint)),
             None => Err(Error::new(ErrorKind::ConfigInvalid, "endpoint is empty")
                 .with_operation("Builder::build")
                 .with_context("service", Scheme::Azdls)),
         }?;
         debug!("backend use endpoint {}", &endpoint);
 
+        let blob_endpoint = match &self.config.blob_endpoint {
+            Some(v) => trim_endpoint(v),
+            None => infer_blob_endpoint_from_dfs_endpoint(&endpoint),
+        };
+        debug!("backend use blob endpoint {}", &blob_endpoint);
+
+        let max_redirects = self.config.max_redirects.unwrap_or_default();
         let client = if let Some(client) = self.http_client {
+            if self.config.connect_timeout.is_some() || self.config.timeout.is_some() {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "connect_timeout and timeout can't be applied to the given http_client",
+                )
+                .with_operation("Builder::build")
+                .with_context("service", Scheme::Azdls));
+            }
             client
         } else {
-            HttpClient::new().map_err(|err| {
+            let mut builder = reqwest::ClientBuilder::new();
+            if max_redirects > 0 {
+                // Redirects will be followed by us so that requests can be signed again.
+                builder = builder.redirect(reqwest::redirect::Policy::none());
+            }
+            if let Some(v) = self.config.connect_timeout {
+                builder = builder.connect_timeout(v);
+            }
+            if let Some(v) = self.config.timeout {
+                builder = builder.timeout(v);
+            }
+            HttpClient::build(builder).map_err(|err| {
                 err.with_operation("Builder::build")
                     .with_context("service", Scheme::Azdls)
             })?
         };
 
+        let extra_query = match self
+            .config
+            .extra_query
+            .as_deref()
+            .map(|v| v.trim_start_matches('?'))
+        {
+            None | Some("") => None,
+            Some(v) => {
+                if v.split('&')
+                    .any(|kv| kv.split('=').next().unwrap_or_default().is_empty())
+                {
+                    return Err(Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "extra_query must be a query string like k1=v1&k2=v2",
+                    )
+                    .with_operation("Builder::build")
+                    .with_context("service", Scheme::Azdls)
+                    .with_context("extra_query", v));
+                }
+                Some(v.to_string())
+            }
+        };
+
         let config_loader = AzureStorageConfig {
-            account_name: self
-                .config
-                .account_name
-                .clone()
-                .or_else(|| infer_storage_name_from_endpoint(endpoint.as_str())),
+            account_name: self.config.account_name.clone().or_else(|| {
+                infer_storage_name_from_endpoint(
+                    endpoint.as_str(),
+                    self.config.endpoint_suffix.as_deref(),
+                )
+            }),
             account_key: self.config.account_key.clone(),
             sas_token: None,
             ..Default::default()
         };
 
-        let cred_loader = AzureStorageLoader::new(config_loader);
+        let secondary_hosts = match &config_loader.account_name {
+            Some(account) => [&endpoint, &blob_endpoint]
+                .into_iter()
+                .filter_map(|v| infer_secondary_host(v, account))
+                .collect(),
+            None => vec![],
+        };
+
+        let auth_mode = if self.credential_loader.is_some() {
+            "custom_loader"
+        } else if config_loader.account_name.is_some() && config_loader.account_key.is_some() {
+            "shared_key"
+        } else {
+            "managed_identity"
+        };
+
+        // If credential_loader is set, we will reuse it to share the token cache.
+        let cred_loader = match self.credential_loader {
+            Some(v) => v,
+            None => Arc::new(AzureStorageLoader::new(config_loader)),
+        };
         let signer = AzureStorageSigner::new();
         Ok(AzdlsBackend {
             core: Arc::new(AzdlsCore {
                 filesystem: self.config.filesystem.clone(),
                 root,
                 endpoint,
+                blob_endpoint,
                 client,
                 loader: cred_loader,
                 signer,
+                auth_mode,
+                allow_anonymous: self.config.allow_anonymous,
+                enable_decompression: self.config.enable_decompression,
+                accept_encoding: self.config.accept_encoding.clone(),
+                confirm_create_dir: self.config.confirm_create_dir,
+                skip_list_dirs: self.config.skip_list_dirs,
+                rename_fallback_to_copy: self.config.rename_fallback_to_copy,
+                client_request_id_prefix: self.config.client_request_id_prefix.clone(),
+                client_request_id_fn: self.client_request_id_fn,
+                extra_query,
+                read_prefetch_size: self.config.read_prefetch_size.unwrap_or_default(),
+                enable_stat_tags: self.config.enable_stat_tags,
+                max_redirects,
+                max_retries: self.config.max_retries.unwrap_or_default(),
+                first_byte_timeout: self.config.first_byte_timeout,
+                secondary_hosts,
+                create_filesystem_if_not_exists: self.config.create_filesystem_if_not_exists,
+                verify_on_write: self.config.verify_on_write,
+                dir_size_cache_ttl: self.config.dir_size_cache_ttl,
+                dir_size_cache: Arc::default(),
+                filesystem_created: Arc::new(OnceCell::new()),
             }),
         })
     }
 }
 
+impl Builder for AzdlsBuilder {
+    const SCHEME: Scheme = Scheme::Azdls;
+    type Config = AzdlsConfig;
+
+    fn build(self) -> Result<impl Access> {
+        self.build_backend()
+    }
+}
+
 /// Backend for azblob services.
 #[derive(Debug, Clone)]
 pub struct AzdlsBackend {
     core: Arc<AzdlsCore>,
 }
 
+impl AzdlsBackend {
+    /// Derive a child backend whose root is `prefix` under current root.
+    ///
+    /// The child shares the same http client and credential loader (and
+    /// so the token cache) with current backend, which makes it cheap to
+    /// create one for every tenant.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::Result;
+    /// use opendal::services::Azdls;
+    /// use opendal::OperatorBuilder;
+    ///
+    /// # fn main() -> Result<()> {
+    /// let base = Azdls::default()
+    ///     .filesystem("test")
+    ///     .endpoint("https://accountname.dfs.core.windows.net")
+    ///     .root("/tenants")
+    ///     .build_backend()?;
+    ///
+    /// // All operations of `op` will happen under `/tenants/tenant-a/`.
+    /// let op = OperatorBuilder::new(base.with_root_prefix("tenant-a")).finish();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_root_prefix(&self, prefix: &str) -> Self {
+        let core = &self.core;
+        let root = normalize_root(&format!("{}{}", core.root, prefix));
+
+        AzdlsBackend {
+            core: Arc::new(AzdlsCore {
+                filesystem: core.filesystem.clone(),
+                root,
+                endpoint: core.endpoint.clone(),
+                blob_endpoint: core.blob_endpoint.clone(),
+                client: core.client.clone(),
+                loader: core.loader.clone(),
+                signer: AzureStorageSigner::new(),
+                auth_mode: core.auth_mode,
+                allow_anonymous: core.allow_anonymous,
+                enable_decompression: core.enable_decompression,
+                accept_encoding: core.accept_encoding.clone(),
+                confirm_create_dir: core.confirm_create_dir,
+                skip_list_dirs: core.skip_list_dirs,
+                rename_fallback_to_copy: core.rename_fallback_to_copy,
+                client_request_id_prefix: core.client_request_id_prefix.clone(),
+                client_request_id_fn: core.client_request_id_fn.clone(),
+                extra_query: core.extra_query.clone(),
+                read_prefetch_size: core.read_prefetch_size,
+                enable_stat_tags: core.enable_stat_tags,
+                max_redirects: core.max_redirects,
+                max_retries: core.max_retries,
+                first_byte_timeout: core.first_byte_timeout,
+                secondary_hosts: core.secondary_hosts.clone(),
+                create_filesystem_if_not_exists: core.create_filesystem_if_not_exists,
+                verify_on_write: core.verify_on_write,
+                dir_size_cache_ttl: core.dir_size_cache_ttl,
+                dir_size_cache: core.dir_size_cache.clone(),
+                filesystem_created: core.filesystem_created.clone(),
+            }),
+        }
+    }
+
+    /// Stat many paths concurrently.
+    ///
+    /// At most `concurrent` requests will be in flight at the same time.
+    /// Failures are reported per path, so a missing file won't fail the
+    /// others. Paths are relative to root, the same as [`Operator::stat`].
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::Result;
+    /// use opendal::services::Azdls;
+    ///
+    /// # async fn test() -> Result<()> {
+    /// let backend = Azdls::default()
+    ///     .filesystem("test")
+    ///     .endpoint("https://accountname.dfs.core.windows.net")
+    ///     .build_backend()?;
+    ///
+    /// let metas = backend
+    ///     .stat_many(vec!["a.parquet".to_string(), "b.parquet".to_string()], 16)
+    ///     .await;
+    /// for (path, meta) in metas {
+    ///     match meta {
+    ///         Ok(meta) => println!("{path}: {}", meta.content_length()),
+    ///         Err(err) => println!("{path}: {err}"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn stat_many(
+        &self,
+        paths: impl IntoIterator<Item = String>,
+        concurrent: usize,
+    ) -> HashMap<String, Result<Metadata>> {
+        stream::iter(paths)
+            .map(|path| async move {
+                let meta = self
+                    .stat(&normalize_path(&path), OpStat::new())
+                    .await
+                    .map(|rp| rp.into_metadata());
+                (path, meta)
+            })
+            .buffer_unordered(concurrent.max(1))
+            .collect()
+            .await
+    }
+
+    /// List entries under `path` page by page.
+    ///
+    /// Pages are fetched lazily, only when [`AzdlsPageLister::next_page`] is
+    /// called. Pass the [`AzdlsPageLister::continuation_token`] saved from a
+    /// previous listing to resume it, even across process restarts.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::Result;
+    /// use opendal::services::Azdls;
+    ///
+    /// # async fn test(checkpoint: Option<String>) -> Result<()> {
+    /// let backend = Azdls::default()
+    ///     .filesystem("test")
+    ///     .endpoint("https://accountname.dfs.core.windows.net")
+    ///     .build_backend()?;
+    ///
+    /// let mut pages = backend.list_pages("dir/", true, checkpoint.as_deref());
+    /// while let Some(entries) = pages.next_page().await? {
+    ///     for entry in entries {
+    ///         println!("{}", entry.path());
+    ///     }
+    ///     // Persist the token to resume from here.
+    ///     let _checkpoint = pages.continuation_token();
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_pages(
+        &self,
+        path: &str,
+        recursive: bool,
+        continuation_token: Option<&str>,
+    ) -> AzdlsPageLister {
+        let l = AzdlsLister::new(self.core.clone(), normalize_path(path), recursive, None);
+        AzdlsPageLister::new(l, continuation_token)
+    }
+
+    /// List versions and snapshots of the file at `path` page by page.
+    ///
+    /// Versions are only kept by accounts with blob versioning enabled. Pass
+    /// [`AzdlsVersion::version_id`](super::AzdlsVersion::version_id) or
+    /// [`AzdlsVersion::snapshot`](super::AzdlsVersion::snapshot) to
+    /// `read_with` to read a prior copy of the file.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::Result;
+    /// use opendal::services::Azdls;
+    ///
+    /// # async fn test() -> Result<()> {
+    /// let backend = Azdls::default()
+    ///     .filesystem("test")
+    ///     .endpoint("https://accountname.dfs.core.windows.net")
+    ///     .build_backend()?;
+    ///
+    /// let mut pages = backend.list_versions("report.pdf", None);
+    /// while let Some(versions) = pages.next_page().await? {
+    ///     for v in versions {
+    ///         println!("{:?} {:?} {}", v.version_id(), v.snapshot(), v.is_current());
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_versions(
+        &self,
+        path: &str,
+        continuation_token: Option<&str>,
+    ) -> AzdlsVersionLister {
+        AzdlsVersionLister::new(self.core.clone(), normalize_path(path), continuation_token)
+    }
+
+    /// Compute the total bytes and count of files under the directory at
+    /// `path`.
+    ///
+    /// Children directories are walked with recursive listing, at most
+    /// `concurrent` of them at the same time. Results are cached if
+    /// [`AzdlsBuilder::dir_size_cache_ttl`] is set.
+    ///
+    /// # Notes
+    ///
+    /// If `skip_list_dirs` is enabled, children directories can't be found,
+    /// the whole directory will be walked by one recursive listing instead.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::Result;
+    /// use opendal::services::Azdls;
+    ///
+    /// # async fn test() -> Result<()> {
+    /// let backend = Azdls::default()
+    ///     .filesystem("test")
+    ///     .endpoint("https://accountname.dfs.core.windows.net")
+    ///     .build_backend()?;
+    ///
+    /// let size = backend.dir_size("logs/", 8).await?;
+    /// println!("{} files, {} bytes", size.objects(), size.bytes());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn dir_size(&self, path: &str, concurrent: usize) -> Result<AzdlsDirSize> {
+        let path = normalize_path(path);
+        if !validate_path(&path, EntryMode::DIR) {
+            return Err(Error::new(
+                ErrorKind::NotADirectory,
+                "the path to compute size should end with `/`",
+            )
+            .with_operation("Backend::azdls_dir_size")
+            .with_context("path", &path));
+        }
+
+        let key = build_abs_path(&self.core.root, &path);
+        if let Some(ttl) = self.core.dir_size_cache_ttl {
+            let cache = self.core.dir_size_cache.lock().unwrap();
+            if let Some((at, size)) = cache.get(&key) {
+                if at.elapsed() < ttl {
+                    return Ok(*size);
+                }
+            }
+        }
+
+        let size = if self.core.skip_list_dirs {
+            self.list_pages(&path, true, None).sum().await?.0
+        } else {
+            let (size, dirs) = self.list_pages(&path, false, None).sum().await?;
+            stream::iter(dirs)
+                .map(|dir| async move { self.list_pages(&dir, true, None).sum().await })
+                .buffer_unordered(concurrent.max(1))
+                .try_fold(size, |acc, (size, _)| async move { Ok(acc + size) })
+                .await?
+        };
+
+        if let Some(ttl) = self.core.dir_size_cache_ttl {
+            let mut cache = self.core.dir_size_cache.lock().unwrap();
+            cache.retain(|_, (at, _)| at.elapsed() < ttl);
+            cache.insert(key, (Instant::now(), size));
+        }
+        Ok(size)
+    }
+
+    /// Acquire an exclusive lease on the file at `path`, returns the lease id.
+    ///
+    /// `duration` must be between 15 and 60 seconds, the lease never expires
+    /// if it's `None`. Acquiring a lease held by others fails with
+    /// [`ErrorKind::ConditionNotMatch`], which makes it usable as a
+    /// distributed lock.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use std::time::Duration;
+    /// # use opendal::Result;
+    /// use opendal::services::Azdls;
+    ///
+    /// # async fn test() -> Result<()> {
+    /// let backend = Azdls::default()
+    ///     .filesystem("test")
+    ///     .endpoint("https://accountname.dfs.core.windows.net")
+    ///     .build_backend()?;
+    ///
+    /// let lease_id = backend
+    ///     .acquire_lease("leader.lock", Some(Duration::from_secs(30)))
+    ///     .await?;
+    /// // Keep the lease alive while doing the work.
+    /// backend.renew_lease("leader.lock", &lease_id).await?;
+    /// backend.release_lease("leader.lock", &lease_id).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn acquire_lease(&self, path: &str, duration: Option<Duration>) -> Result<String> {
+        self.core
+            .azdls_acquire_lease(&normalize_path(path), duration)
+            .await
+    }
+
+    /// Renew the lease on the file at `path` before it expires.
+    pub async fn renew_lease(&self, path: &str, lease_id: &str) -> Result<()> {
+        self.core
+            .azdls_renew_lease(&normalize_path(path), lease_id)
+            .await
+    }
+
+    /// Release the lease on the file at `path` so that others can acquire it.
+    pub async fn release_lease(&self, path: &str, lease_id: &str) -> Result<()> {
+        self.core
+            .azdls_release_lease(&normalize_path(path), lease_id)
+            .await
+    }
+
+    /// Set the access tier of the file at `path`, like `Hot`, `Cool`, `Cold`
+    /// or `Archive`.
+    ///
+    /// Files in `Archive` tier can't be read until they are rehydrated by
+    /// moving them to an online tier, `rehydrate_priority` can be `High` or
+    /// `Standard` for that. Rehydration is asynchronous and may take hours,
+    /// check [`Metadata::storage_class`] returned by `stat` to see if it's
+    /// finished.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::Result;
+    /// use opendal::services::Azdls;
+    /// use opendal::Operator;
+    ///
+    /// # async fn test() -> Result<()> {
+    /// let backend = Azdls::default()
+    ///     .filesystem("test")
+    ///     .endpoint("https://accountname.dfs.core.windows.net")
+    ///     .build_backend()?;
+    /// let op = Operator::new(Azdls::default().filesystem("test"))?.finish();
+    ///
+    /// let meta = op.stat("data.parquet").await?;
+    /// if meta.storage_class() == Some("Archive") {
+    ///     backend
+    ///         .set_access_tier("data.parquet", "Hot", Some("High"))
+    ///         .await?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn set_access_tier(
+        &self,
+        path: &str,
+        tier: &str,
+        rehydrate_priority: Option<&str>,
+    ) -> Result<()> {
+        self.core
+            .azdls_set_access_tier(&normalize_path(path), tier, rehydrate_priority)
+            .await
+    }
+
+    /// Write all content of `reader` to the file at `path` without knowing
+    /// its size in advance.
+    ///
+    /// Content is appended in chunks as it arrives and flushed once `reader`
+    /// returns EOF, so the whole payload is never buffered in memory.
+    /// Appended data is not visible before the final flush, the file will be
+    /// left empty if the write failed.
+    ///
+    /// Use [`futures::TryStreamExt::into_async_read`] to write from a stream
+    /// of bytes.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::Result;
+    /// use opendal::raw::OpWrite;
+    /// use opendal::services::Azdls;
+    ///
+    /// # async fn test() -> Result<()> {
+    /// let backend = Azdls::default()
+    ///     .filesystem("test")
+    ///     .endpoint("https://accountname.dfs.core.windows.net")
+    ///     .build_backend()?;
+    ///
+    /// let socket = futures::io::Cursor::new(vec![0; 1024]);
+    /// let meta = backend
+    ///     .write_from_reader("upload.bin", OpWrite::new(), socket)
+    ///     .await?;
+    /// assert_eq!(meta.content_length(), 1024);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_from_reader<R>(
+        &self,
+        path: &str,
+        args: OpWrite,
+        mut reader: R,
+    ) -> Result<Metadata>
+    where
+        R: futures::AsyncRead + Unpin,
+    {
+        self.core.azdls_ensure_filesystem().await?;
+
+        AzdlsWriter::new(self.core.clone(), args, normalize_path(path))
+            .write_from_reader(&mut reader, STREAM_WRITE_CHUNK_SIZE)
+            .await
+    }
+}
+
 impl Access for AzdlsBackend {
-    type Reader = HttpBody;
+    type Reader = AzdlsPrefetchReader<TwoWays<HttpBody, AzdlsDecompressReader>>;
     type Writer = AzdlsWriters;
     type Lister = oio::PageLister<AzdlsLister>;
     type BlockingReader = ();
@@ -260,16 +1233,32 @@ impl Access for AzdlsBackend {
             .set_name(&self.core.filesystem)
             .set_native_capability(Capability {
                 stat: true,
+                stat_with_if_modified_since: true,
+                stat_with_if_unmodified_since: true,
 
                 read: true,
+                read_with_if_match: true,
+                read_with_if_none_match: true,
 
                 write: true,
                 write_can_append: true,
+                write_returns_append_offset: true,
+                write_with_tags: true,
+                write_with_expiry: true,
+                write_with_lock_token: true,
+                write_with_user_metadata: true,
+                update_metadata: true,
+                write_returns_etag: true,
+                write_returns_last_modified: true,
                 create_dir: true,
+                create_dir_rejects_file: true,
                 delete: true,
+                delete_with_lock_token: true,
+                copy: true,
                 rename: true,
 
                 list: true,
+                list_with_recursive: true,
 
                 ..Default::default()
             });
@@ -278,12 +1267,18 @@ impl Access for AzdlsBackend {
     }
 
     async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
+        self.core.azdls_ensure_filesystem().await?;
+
         let mut req = self.core.azdls_create_request(
             path,
             "directory",
             &OpWrite::default(),
             Buffer::new(),
         )?;
+        // Only create the directory if nothing exists at the path, otherwise
+        // an existing file will be replaced by the directory.
+        req.headers_mut()
+            .insert(IF_NONE_MATCH, HeaderValue::from_static("*"));
 
         self.core.sign(&mut req).await?;
 
@@ -292,24 +1287,53 @@ impl Access for AzdlsBackend {
         let status = resp.status();
 
         match status {
-            StatusCode::CREATED | StatusCode::OK => Ok(RpCreateDir::default()),
-            _ => Err(parse_error(resp).await?),
+            StatusCode::CREATED | StatusCode::OK => {
+                if self.core.confirm_create_dir {
+                    self.core.azdls_confirm_directory(path).await?;
+                }
+            }
+            // The path exists already, it's fine only if it's a directory.
+            StatusCode::CONFLICT if is_path_already_exists(resp.headers()) => {
+                self.core.azdls_confirm_directory(path).await?;
+            }
+            _ => return Err(parse_error(resp).await?),
         }
+
+        Ok(RpCreateDir::default())
     }
 
-    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         // Stat root always returns a DIR.
         if path == "/" {
             return Ok(RpStat::new(Metadata::new(EntryMode::DIR)));
         }
 
-        let resp = self.core.azdls_get_properties(path).await?;
+        let resp = self.core.azdls_get_properties(path, &args).await?;
 
         if resp.status() != StatusCode::OK {
             return Err(parse_error(resp).await?);
         }
 
         let mut meta = parse_into_metadata(path, resp.headers())?;
+        let encoding = if self.core.enable_decompression {
+            ContentEncoding::from_headers(resp.headers())?
+        } else {
+            None
+        };
+        // `x-ms-creation-time` is in RFC 1123 format with `GMT` zone.
+        if let Some(v) = parse_header_to_str(resp.headers(), X_MS_CREATION_TIME)? {
+            meta.set_created(parse_datetime_from_rfc2822(v)?);
+        }
+        if let Some(v) = parse_header_to_str(resp.headers(), X_MS_ACCESS_TIER)? {
+            meta.set_storage_class(v);
+        }
+        if let Some(v) = parse_header_to_str(resp.headers(), X_MS_PROPERTIES)? {
+            meta.with_user_metadata(parse_properties(v)?);
+        }
+        // Only returned by accounts with versioning enabled.
+        if let Some(v) = parse_header_to_str(resp.headers(), X_MS_VERSION_ID)? {
+            meta.set_version(v);
+        }
         let resource = resp
             .headers()
             .get("x-ms-resource-type")
@@ -340,15 +1364,103 @@ impl Access for AzdlsBackend {
             }
         };
 
+        // Reads return decompressed content, so does the length.
+        if let Some(encoding) = encoding.filter(|_| meta.is_file()) {
+            meta.set_content_length(self.decompressed_length(path, encoding, &args).await?);
+        }
+
+        // Tags are only supported on files, and live in the blob service.
+        if self.core.enable_stat_tags && meta.is_file() {
+            meta.set_tags(self.core.azdls_get_tags(path).await?);
+        }
+
         Ok(RpStat::new(meta))
     }
 
+    async fn exists(&self, path: &str, args: OpStat) -> Result<bool> {
+        if path == "/" {
+            return Ok(true);
+        }
+
+        // Only the status code matters, skip parsing metadata and fetching tags.
+        let resp = self.core.azdls_get_properties(path, &args).await?;
+
+        match resp.status() {
+            StatusCode::OK => Ok(true),
+            _ => {
+                let err = parse_error(resp).await?;
+                // A missing filesystem is reported as 404 too, it's not
+                // mapped to `NotFound` so we won't mistake it for a missing path.
+                if err.kind() == ErrorKind::NotFound {
+                    Ok(false)
+                } else {
+                    Err(err)
+                }
+            }
+        }
+    }
+
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let resp = self.core.azdls_read(path, args.range()).await?;
+        let resp = self.core.azdls_read(path, args.range(), &args).await?;
 
         let status = resp.status();
         match status {
-            StatusCode::OK | StatusCode::PARTIAL_CONTENT => Ok((RpRead::new(), resp.into_body())),
+            StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
+                let rp =
+                    RpRead::new().with_etag(parse_etag(resp.headers())?.map(|v| v.to_string()));
+
+                let encoding = if self.core.enable_decompression {
+                    ContentEncoding::from_headers(resp.headers())?
+                } else {
+                    None
+                };
+
+                let Some(encoding) = encoding else {
+                    if status == StatusCode::PARTIAL_CONTENT {
+                        check_content_range(args.range(), parse_content_range(resp.headers())?)
+                            .map_err(|err| err.with_context("path", path))?;
+                    }
+
+                    return Ok((
+                        rp,
+                        AzdlsPrefetchReader::new(
+                            TwoWays::One(resp.into_body()),
+                            self.core.read_prefetch_size,
+                            args.executor().cloned().unwrap_or_default(),
+                        ),
+                    ));
+                };
+
+                // The range is in decompressed bytes, but compressed content
+                // can't be decoded from the middle. Decode from the start of
+                // the whole content instead.
+                let body = if status == StatusCode::OK && args.range().is_full() {
+                    resp.into_body()
+                } else {
+                    drop(resp);
+                    self.read_whole(path, &args).await?.into_body()
+                };
+
+                Ok((rp, self.new_decompress_reader(body, encoding, &args)))
+            }
+            // The range in decompressed bytes could exceed the compressed
+            // content, check whether it's compressed before giving up.
+            StatusCode::RANGE_NOT_SATISFIABLE if self.core.enable_decompression => {
+                let (part, mut body) = resp.into_parts();
+                let buf = body.to_buffer().await?;
+                let err = parse_error(Response::from_parts(part, buf)).await?;
+
+                let resp = self.read_whole(path, &args).await?;
+                let Some(encoding) = ContentEncoding::from_headers(resp.headers())? else {
+                    return Err(err);
+                };
+                let rp =
+                    RpRead::new().with_etag(parse_etag(resp.headers())?.map(|v| v.to_string()));
+                Ok((
+                    rp,
+                    self.new_decompress_reader(resp.into_body(), encoding, &args),
+                ))
+            }
             _ => {
                 let (part, mut body) = resp.into_parts();
                 let buf = body.to_buffer().await?;
@@ -358,17 +1470,28 @@ impl Access for AzdlsBackend {
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        self.core.azdls_ensure_filesystem().await?;
+
         let w = AzdlsWriter::new(self.core.clone(), args.clone(), path.to_string());
-        let w = if args.append() {
-            AzdlsWriters::Two(oio::AppendWriter::new(w))
+        if args.append() {
+            let rp = RpWrite::new().with_mode(WriteMode::Append);
+            let w = oio::AppendWriter::new(w).with_request_counter(rp.append_request_counter());
+            Ok((rp, AzdlsWriters::Two(w)))
         } else {
-            AzdlsWriters::One(oio::OneShotWriter::new(w))
-        };
-        Ok((RpWrite::default(), w))
+            let rp = RpWrite::new().with_mode(WriteMode::OneShot);
+            Ok((rp, AzdlsWriters::One(oio::OneShotWriter::new(w))))
+        }
     }
 
-    async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
-        let resp = self.core.azdls_delete(path).await?;
+    async fn delete(&self, path: &str, args: OpDelete) -> Result<RpDelete> {
+        // Nested dirs are invisible to `list` with `skip_list_dirs`, so they
+        // can't be removed one by one. Delete dirs recursively instead to
+        // avoid `remove_all` leaving empty dirs behind.
+        let recursive = self.core.skip_list_dirs && path.ends_with('/');
+        let resp = self
+            .core
+            .azdls_delete(path, recursive, args.lock_token())
+            .await?;
 
         let status = resp.status();
 
@@ -379,12 +1502,32 @@ impl Access for AzdlsBackend {
     }
 
     async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
-        let l = AzdlsLister::new(self.core.clone(), path.to_string(), args.limit());
+        let l = AzdlsLister::new(
+            self.core.clone(),
+            path.to_string(),
+            args.recursive(),
+            args.limit(),
+        );
 
         Ok((RpList::default(), oio::PageLister::new(l)))
     }
 
+    async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
+        self.core.azdls_ensure_filesystem().await?;
+
+        let resp = self.core.azdls_copy(from, to).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::ACCEPTED | StatusCode::CREATED => Ok(RpCopy::default()),
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
     async fn rename(&self, from: &str, to: &str, _args: OpRename) -> Result<RpRename> {
+        self.core.azdls_ensure_filesystem().await?;
+
         if let Some(resp) = self.core.azdls_ensure_parent_path(to).await? {
             let status = resp.status();
             match status {
@@ -397,19 +1540,172 @@ impl Access for AzdlsBackend {
 
         let status = resp.status();
 
+        let err = match status {
+            StatusCode::CREATED => return Ok(RpRename::default()),
+            _ => parse_error(resp).await?,
+        };
+        if err.kind() != ErrorKind::Unsupported {
+            return Err(err);
+        }
+        if !self.core.rename_fallback_to_copy {
+            return Err(err.with_context(
+                "hint",
+                "enable rename_fallback_to_copy to rename via copy and delete",
+            ));
+        }
+
+        debug!("azdls rename from {from} to {to} is unsupported, fallback to copy and delete");
+        self.rename_via_copy(from, to).await
+    }
+}
+
+impl AzdlsBackend {
+    /// Read the whole content of the file at `path`, ignoring the range in
+    /// `args`.
+    async fn read_whole(&self, path: &str, args: &OpRead) -> Result<Response<HttpBody>> {
+        let resp = self
+            .core
+            .azdls_read(path, BytesRange::default(), args)
+            .await?;
+        if resp.status() != StatusCode::OK {
+            let (part, mut body) = resp.into_parts();
+            let buf = body.to_buffer().await?;
+            return Err(parse_error(Response::from_parts(part, buf)).await?);
+        }
+        Ok(resp)
+    }
+
+    fn new_decompress_reader(
+        &self,
+        body: HttpBody,
+        encoding: ContentEncoding,
+        args: &OpRead,
+    ) -> AzdlsPrefetchReader<TwoWays<HttpBody, AzdlsDecompressReader>> {
+        AzdlsPrefetchReader::new(
+            TwoWays::Two(AzdlsDecompressReader::new(body, encoding, args.range())),
+            self.core.read_prefetch_size,
+            args.executor().cloned().unwrap_or_default(),
+        )
+    }
+
+    /// Compute the decompressed length of the file at `path` by decoding
+    /// its whole content.
+    async fn decompressed_length(
+        &self,
+        path: &str,
+        encoding: ContentEncoding,
+        args: &OpStat,
+    ) -> Result<u64> {
+        let mut op = OpRead::new();
+        if let Some(v) = args.version() {
+            op = op.with_version(v);
+        }
+        if let Some(v) = args.snapshot() {
+            op = op.with_snapshot(v);
+        }
+
+        let resp = self.read_whole(path, &op).await?;
+        let mut r = AzdlsDecompressReader::new(resp.into_body(), encoding, BytesRange::default());
+        let mut size = 0;
+        loop {
+            let buf = oio::Read::read(&mut r).await?;
+            if buf.is_empty() {
+                return Ok(size);
+            }
+            size += buf.len() as u64;
+        }
+    }
+
+    /// Rename by copying the file within the same account and deleting the
+    /// source.
+    ///
+    /// Pending copies are polled until completed. The source is only deleted
+    /// if the copy succeeded, failed or still pending copies will be returned
+    /// as error and both paths are kept.
+    async fn rename_via_copy(&self, from: &str, to: &str) -> Result<RpRename> {
+        let resp = self.core.azdls_copy(from, to).await?;
+
+        let status = resp.status();
         match status {
-            StatusCode::CREATED => Ok(RpRename::default()),
+            StatusCode::ACCEPTED | StatusCode::CREATED => {}
+            _ => return Err(parse_error(resp).await?),
+        }
+
+        let mut copy_status = parse_copy_status(&resp);
+        let mut backoff = ExponentialBuilder::default()
+            .with_min_delay(COPY_STATUS_MIN_DELAY)
+            .with_max_delay(COPY_STATUS_MAX_DELAY)
+            .with_max_times(COPY_STATUS_MAX_POLLS)
+            .build();
+        while is_copy_pending(copy_status.as_deref()) {
+            let Some(delay) = backoff.next() else {
+                break;
+            };
+            tokio::time::sleep(delay).await;
+
+            let resp = self.core.azdls_get_copy_status(to).await?;
+            if resp.status() != StatusCode::OK {
+                return Err(parse_error(resp).await?);
+            }
+            copy_status = parse_copy_status(&resp);
+        }
+
+        if !is_copy_completed(copy_status.as_deref()) {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "copy for rename fallback is not completed, source is kept",
+            )
+            .with_operation(Operation::Rename)
+            .with_context("from", from)
+            .with_context("to", to)
+            .with_context("copy_status", copy_status.unwrap_or_default()));
+        }
+
+        let resp = self.core.azdls_delete(from, false, None).await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::OK | StatusCode::NOT_FOUND => Ok(RpRename::default()),
             _ => Err(parse_error(resp).await?),
         }
     }
 }
 
-fn infer_storage_name_from_endpoint(endpoint: &str) -> Option<String> {
+/// Copy blob within the same account is synchronous in most cases, but it
+/// could still be `pending` for large files.
+///
+/// Missing status is treated as completed since emulators may not return it.
+fn is_copy_completed(copy_status: Option<&str>) -> bool {
+    copy_status.map_or(true, |v| v.eq_ignore_ascii_case("success"))
+}
+
+fn is_path_already_exists(headers: &HeaderMap) -> bool {
+    headers
+        .get("x-ms-error-code")
+        .is_some_and(|v| v == "PathAlreadyExists")
+}
+
+fn is_copy_pending(copy_status: Option<&str>) -> bool {
+    copy_status.is_some_and(|v| v.eq_ignore_ascii_case("pending"))
+}
+
+fn parse_copy_status<T>(resp: &Response<T>) -> Option<String> {
+    resp.headers()
+        .get(X_MS_COPY_STATUS)
+        .and_then(|v| v.to_str().ok())
+        .map(|v| v.to_string())
+}
+
+fn infer_storage_name_from_endpoint(endpoint: &str, extra_suffix: Option<&str>) -> Option<String> {
     let endpoint: &str = endpoint
         .strip_prefix("http://")
         .or_else(|| endpoint.strip_prefix("https://"))
         .unwrap_or(endpoint);
 
+    if let Some(account) = infer_storage_name_from_emulator_endpoint(endpoint) {
+        return Some(account);
+    }
+
     let mut parts = endpoint.splitn(2, '.');
     let storage_name = parts.next();
     let endpoint_suffix = parts
@@ -418,9 +1714,13 @@ fn infer_storage_name_from_endpoint(endpoint: &str) -> Option<String> {
         .trim_end_matches('/')
         .to_lowercase();
 
+    let extra_suffix =
+        extra_suffix.map(|s| s.trim_matches(|c| c == '.' || c == '/').to_lowercase());
     if KNOWN_AZDLS_ENDPOINT_SUFFIX
         .iter()
-        .any(|s| *s == endpoint_suffix.as_str())
+        .copied()
+        .chain(extra_suffix.as_deref())
+        .any(|s| s == endpoint_suffix.as_str())
     {
         storage_name.map(|s| s.to_string())
     } else {
@@ -428,21 +1728,1235 @@ fn infer_storage_name_from_endpoint(endpoint: &str) -> Option<String> {
     }
 }
 
+/// Infer the account name from emulator endpoints like Azurite.
+///
+/// Emulators are served from `localhost` or an IP address and put the account
+/// name in the first path segment, like `http://127.0.0.1:10000/devstoreaccount1`.
+fn infer_storage_name_from_emulator_endpoint(endpoint: &str) -> Option<String> {
+    let (host, path) = endpoint.split_once('/')?;
+    let host = match host.rsplit_once(':') {
+        Some((host, port)) if port.chars().all(|c| c.is_ascii_digit()) => host,
+        _ => host,
+    };
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    if !host.eq_ignore_ascii_case("localhost") && host.parse::<std::net::IpAddr>().is_err() {
+        return None;
+    }
+
+    path.split('/')
+        .next()
+        .filter(|v| !v.is_empty())
+        .map(|v| v.to_string())
+}
+
+/// Infer the read-access secondary host of a geo-redundant account, like
+/// `account-secondary.dfs.core.windows.net` for `account.dfs.core.windows.net`.
+fn infer_secondary_host(endpoint: &str, account: &str) -> Option<String> {
+    let host = endpoint
+        .strip_prefix("http://")
+        .or_else(|| endpoint.strip_prefix("https://"))
+        .unwrap_or(endpoint)
+        .split('/')
+        .next()?;
+
+    let suffix = host.strip_prefix(account)?.strip_prefix('.')?;
+    Some(format!("{account}-secondary.{suffix}"))
+}
+
+/// Check whether the filesystem name conforms to Azure naming rules:
+///
+/// - Must be between 3 and 63 characters long.
+/// - Must contain only lowercase letters, numbers, and hyphens.
+/// - Must begin and end with a letter or number.
+/// - Must not contain consecutive hyphens.
+///
+/// ref: <https://learn.microsoft.com/en-us/rest/api/storageservices/naming-and-referencing-containers--blobs--and-metadata#container-names>
+fn check_filesystem_name(filesystem: &str) -> Result<()> {
+    let err = |msg: &str| {
+        Err(Error::new(ErrorKind::ConfigInvalid, msg)
+            .with_operation("Builder::build")
+            .with_context("service", Scheme::Azdls)
+            .with_context("filesystem", filesystem))
+    };
+
+    if !(3..=63).contains(&filesystem.len()) {
+        return err("filesystem name must be between 3 and 63 characters long");
+    }
+    if !filesystem
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+    {
+        return err("filesystem name must contain only lowercase letters, numbers and hyphens");
+    }
+    if filesystem.starts_with('-') || filesystem.ends_with('-') {
+        return err("filesystem name must begin and end with a letter or number");
+    }
+    if filesystem.contains("--") {
+        return err("filesystem name must not contain consecutive hyphens");
+    }
+
+    Ok(())
+}
+
+/// Make sure the returned `Content-Range` matches the requested range.
+///
+/// Proxies or misbehaving endpoints could return a different slice, which
+/// will corrupt the content silently if we trust it.
+fn check_content_range(range: BytesRange, cr: Option<BytesContentRange>) -> Result<()> {
+    let mismatch = |actual: &str| {
+        Error::new(
+            ErrorKind::Unexpected,
+            "azdls returns content range that doesn't match the requested range",
+        )
+        .with_context("range", range.to_string())
+        .with_context("content_range", actual)
+    };
+
+    let Some(cr) = cr else {
+        return Err(mismatch("<missing>"));
+    };
+    let Some(actual) = cr.range() else {
+        return Err(mismatch(&cr.to_string()));
+    };
+
+    // The end of range could be clamped by the size of file.
+    let expected_end = match (range.size(), cr.size()) {
+        (Some(size), Some(total)) => Some((range.offset() + size).min(total)),
+        (Some(size), None) => Some(range.offset() + size),
+        (None, total) => total,
+    };
+    if actual.start != range.offset() || expected_end.is_some_and(|end| actual.end != end) {
+        return Err(mismatch(&cr.to_string()));
+    }
+    Ok(())
+}
+
+/// Derive the blob endpoint from dfs endpoint by replacing the `dfs` label
+/// in host with `blob`.
+///
+/// Endpoints without a `dfs` label (like Azurite) will be returned as is.
+fn infer_blob_endpoint_from_dfs_endpoint(endpoint: &str) -> String {
+    let (scheme, rest) = match endpoint.split_once("://") {
+        Some((scheme, rest)) => (Some(scheme), rest),
+        None => (None, endpoint),
+    };
+    let (host, path) = match rest.split_once('/') {
+        Some((host, path)) => (host, Some(path)),
+        None => (rest, None),
+    };
+
+    let host = host
+        .split('.')
+        .map(|label| if label == "dfs" { "blob" } else { label })
+        .collect::<Vec<_>>()
+        .join(".");
+
+    let mut blob_endpoint = String::with_capacity(endpoint.len());
+    if let Some(scheme) = scheme {
+        blob_endpoint.push_str(scheme);
+        blob_endpoint.push_str("://");
+    }
+    blob_endpoint.push_str(&host);
+    if let Some(path) = path {
+        blob_endpoint.push('/');
+        blob_endpoint.push_str(path);
+    }
+    blob_endpoint
+}
+
+/// Properties to update by [`AzdlsPropertiesExt::set_properties`].
+///
+/// Properties that are not set keep their current values.
+#[derive(Debug, Clone, Default)]
+pub struct AzdlsProperties {
+    content_type: Option<String>,
+    content_disposition: Option<String>,
+    cache_control: Option<String>,
+    user_metadata: Option<HashMap<String, String>>,
+    lock_token: Option<String>,
+}
+
+impl AzdlsProperties {
+    /// Create a new `AzdlsProperties`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the content type.
+    pub fn with_content_type(mut self, v: &str) -> Self {
+        self.content_type = Some(v.to_string());
+        self
+    }
+
+    /// Get the content type.
+    pub fn content_type(&self) -> Option<&str> {
+        self.content_type.as_deref()
+    }
+
+    /// Set the content disposition.
+    pub fn with_content_disposition(mut self, v: &str) -> Self {
+        self.content_disposition = Some(v.to_string());
+        self
+    }
+
+    /// Get the content disposition.
+    pub fn content_disposition(&self) -> Option<&str> {
+        self.content_disposition.as_deref()
+    }
+
+    /// Set the cache control.
+    pub fn with_cache_control(mut self, v: &str) -> Self {
+        self.cache_control = Some(v.to_string());
+        self
+    }
+
+    /// Get the cache control.
+    pub fn cache_control(&self) -> Option<&str> {
+        self.cache_control.as_deref()
+    }
+
+    /// Set the user metadata, which replaces the current user metadata as
+    /// a whole.
+    pub fn with_user_metadata(mut self, v: HashMap<String, String>) -> Self {
+        self.user_metadata = Some(v);
+        self
+    }
+
+    /// Get the user metadata.
+    pub fn user_metadata(&self) -> Option<&HashMap<String, String>> {
+        self.user_metadata.as_ref()
+    }
+
+    /// Set the lease id, which is required if the path is leased.
+    pub fn with_lock_token(mut self, v: &str) -> Self {
+        self.lock_token = Some(v.to_string());
+        self
+    }
+
+    /// Get the lease id.
+    pub fn lock_token(&self) -> Option<&str> {
+        self.lock_token.as_deref()
+    }
+}
+
+/// Extension of [`AzdlsBackend`] to update properties of paths in place.
+///
+/// Services implementing it set [`Capability::update_metadata`].
+pub trait AzdlsPropertiesExt {
+    /// Update properties of the file or directory at `path` in place,
+    /// without rewriting its content.
+    ///
+    /// Properties not set in `props` keep their current values, including
+    /// the stored content md5. Returns the metadata with the new etag, or
+    /// [`ErrorKind::ConditionNotMatch`] if the path was changed during the
+    /// update.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::Result;
+    /// use opendal::services::Azdls;
+    /// use opendal::services::AzdlsProperties;
+    /// use opendal::services::AzdlsPropertiesExt;
+    ///
+    /// # async fn test() -> Result<()> {
+    /// let backend = Azdls::default()
+    ///     .filesystem("test")
+    ///     .endpoint("https://accountname.dfs.core.windows.net")
+    ///     .build_backend()?;
+    ///
+    /// let meta = backend
+    ///     .set_properties(
+    ///         "data.json",
+    ///         AzdlsProperties::new().with_content_type("application/json"),
+    ///     )
+    ///     .await?;
+    /// println!("new etag: {:?}", meta.etag());
+    /// # Ok(())
+    /// # }
+    /// ```
+    fn set_properties(
+        &self,
+        path: &str,
+        props: AzdlsProperties,
+    ) -> impl Future<Output = Result<Metadata>> + MaybeSend;
+}
+
+impl AzdlsPropertiesExt for AzdlsBackend {
+    async fn set_properties(&self, path: &str, props: AzdlsProperties) -> Result<Metadata> {
+        let path = normalize_path(path);
+
+        // setProperties clears properties that are not sent, fetch the
+        // current ones to carry them forward.
+        let resp = self
+            .core
+            .azdls_get_properties(&path, &OpStat::new())
+            .await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp)
+                .await?
+                .with_operation("AzdlsPropertiesExt::set_properties")
+                .with_context("path", path));
+        }
+
+        let mut req = self
+            .core
+            .azdls_set_properties_request(&path, &props, resp.headers())?;
+        self.core.sign(&mut req).await?;
+
+        let resp = self.core.send(req).await?;
+        match resp.status() {
+            StatusCode::OK => parse_into_write_metadata(resp.headers()),
+            _ => Err(parse_error(resp)
+                .await?
+                .with_operation("AzdlsPropertiesExt::set_properties")
+                .with_context("path", path)),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::io::Write;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use http::StatusCode;
+
+    use super::check_content_range;
+    use super::check_filesystem_name;
+    use super::infer_blob_endpoint_from_dfs_endpoint;
+    use super::infer_secondary_host;
     use super::infer_storage_name_from_endpoint;
+    use super::is_copy_completed;
+    use super::is_copy_pending;
+    use super::AzdlsBackend;
+    use super::AzdlsBuilder;
+    use super::AzdlsProperties;
+    use super::AzdlsPropertiesExt;
+    use super::AzdlsWriter;
+    use super::X_MS_COPY_STATUS;
+    use crate::raw::oio::List;
+    use crate::raw::Access;
+    use crate::raw::BytesContentRange;
+    use crate::raw::BytesRange;
+    use crate::raw::HttpClient;
+    use crate::raw::MockResponse;
+    use crate::raw::MockServer;
+    use crate::raw::OpCreateDir;
+    use crate::raw::OpDelete;
+    use crate::raw::OpList;
+    use crate::raw::OpStat;
+    use crate::raw::OpWrite;
+    use crate::Buffer;
+    use crate::ErrorKind;
+    use crate::Metakey;
+    use crate::OperatorBuilder;
+
+    fn test_builder(server: &MockServer) -> AzdlsBuilder {
+        AzdlsBuilder::default()
+            .filesystem("test")
+            .endpoint(server.endpoint())
+            .account_name("account")
+            .account_key("a2V5")
+    }
+
+    fn test_backend(server: &MockServer) -> AzdlsBackend {
+        test_builder(server).build_backend().unwrap()
+    }
 
     #[test]
     fn test_infer_storage_name_from_endpoint() {
         let endpoint = "https://account.dfs.core.windows.net";
-        let storage_name = infer_storage_name_from_endpoint(endpoint);
+        let storage_name = infer_storage_name_from_endpoint(endpoint, None);
         assert_eq!(storage_name, Some("account".to_string()));
     }
 
     #[test]
     fn test_infer_storage_name_from_endpoint_with_trailing_slash() {
         let endpoint = "https://account.dfs.core.windows.net/";
-        let storage_name = infer_storage_name_from_endpoint(endpoint);
+        let storage_name = infer_storage_name_from_endpoint(endpoint, None);
         assert_eq!(storage_name, Some("account".to_string()));
     }
+
+    #[test]
+    fn test_infer_storage_name_from_emulator_endpoint() {
+        let cases = vec![
+            (
+                "http://127.0.0.1:10000/devstoreaccount1",
+                Some("devstoreaccount1"),
+            ),
+            (
+                "http://localhost:10000/devstoreaccount1/",
+                Some("devstoreaccount1"),
+            ),
+            (
+                "http://[::1]:10000/devstoreaccount1",
+                Some("devstoreaccount1"),
+            ),
+            ("http://127.0.0.1:10000", None),
+            ("http://127.0.0.1:10000/", None),
+            ("https://example.com/account", None),
+        ];
+
+        for (endpoint, expected) in cases {
+            assert_eq!(
+                infer_storage_name_from_endpoint(endpoint, None).as_deref(),
+                expected,
+                "{endpoint}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_copy_completed() {
+        assert!(is_copy_completed(Some("success")));
+        assert!(is_copy_completed(None));
+        assert!(!is_copy_completed(Some("pending")));
+        assert!(!is_copy_completed(Some("failed")));
+        assert!(is_copy_pending(Some("pending")));
+        assert!(!is_copy_pending(Some("success")));
+        assert!(!is_copy_pending(None));
+    }
+
+    #[test]
+    fn test_infer_secondary_host() {
+        assert_eq!(
+            infer_secondary_host("https://account.dfs.core.windows.net/", "account"),
+            Some("account-secondary.dfs.core.windows.net".to_string())
+        );
+        assert_eq!(
+            infer_secondary_host("https://account.blob.core.windows.net", "account"),
+            Some("account-secondary.blob.core.windows.net".to_string())
+        );
+        assert_eq!(
+            infer_secondary_host("http://127.0.0.1:10000", "account"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_infer_storage_name_from_endpoint_with_custom_suffix() {
+        let endpoint = "https://account.dfs.local.azurestack.external";
+        assert_eq!(infer_storage_name_from_endpoint(endpoint, None), None);
+        assert_eq!(
+            infer_storage_name_from_endpoint(endpoint, Some("dfs.local.azurestack.external")),
+            Some("account".to_string())
+        );
+        assert_eq!(
+            infer_storage_name_from_endpoint(endpoint, Some(".DFS.local.azurestack.external")),
+            Some("account".to_string())
+        );
+        // Well-known suffixes still work.
+        assert_eq!(
+            infer_storage_name_from_endpoint(
+                "https://account.dfs.core.windows.net",
+                Some("dfs.local.azurestack.external")
+            ),
+            Some("account".to_string())
+        );
+    }
+
+    #[test]
+    fn test_infer_blob_endpoint_from_dfs_endpoint() {
+        let cases = vec![
+            (
+                "https://account.dfs.core.windows.net",
+                "https://account.blob.core.windows.net",
+            ),
+            (
+                "https://account.dfs.core.chinacloudapi.cn",
+                "https://account.blob.core.chinacloudapi.cn",
+            ),
+            (
+                "http://127.0.0.1:10000/devstoreaccount1",
+                "http://127.0.0.1:10000/devstoreaccount1",
+            ),
+            (
+                "https://dfs.example.com/dfs",
+                "https://blob.example.com/dfs",
+            ),
+        ];
+
+        for (endpoint, expected) in cases {
+            assert_eq!(infer_blob_endpoint_from_dfs_endpoint(endpoint), expected);
+        }
+    }
+
+    #[test]
+    fn test_check_filesystem_name() {
+        let long_valid = "a".repeat(63);
+        let valid = vec!["abc", "test-filesystem", "0123", &long_valid];
+        for name in valid {
+            assert!(
+                check_filesystem_name(name).is_ok(),
+                "{name} should be valid"
+            );
+        }
+
+        let long_invalid = "a".repeat(64);
+        let invalid = vec![
+            "ab",
+            "Test",
+            "test_fs",
+            "-test",
+            "test-",
+            "te--st",
+            &long_invalid,
+        ];
+        for name in invalid {
+            assert!(
+                check_filesystem_name(name).is_err(),
+                "{name} should be invalid"
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_with_timeouts() {
+        let builder = AzdlsBuilder::default()
+            .filesystem("test")
+            .endpoint("https://account.dfs.core.windows.net")
+            .connect_timeout(Duration::from_secs(3))
+            .first_byte_timeout(Duration::from_secs(10))
+            .timeout(Duration::from_secs(3600));
+        let backend = builder.clone().build_backend().unwrap();
+        assert_eq!(
+            backend.core.first_byte_timeout,
+            Some(Duration::from_secs(10))
+        );
+
+        // The timeouts of a given client can't be changed.
+        let err = builder
+            .http_client(HttpClient::new().unwrap())
+            .build_backend()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+
+    #[test]
+    fn test_with_root_prefix() {
+        let base = AzdlsBuilder::default()
+            .filesystem("test")
+            .endpoint("https://account.dfs.core.windows.net")
+            .account_name("account")
+            .account_key("a2V5")
+            .root("/tenants")
+            .build_backend()
+            .unwrap();
+
+        let child = base.with_root_prefix("tenant-a");
+        assert_eq!(child.info().root(), "/tenants/tenant-a/");
+        assert!(Arc::ptr_eq(&base.core.loader, &child.core.loader));
+
+        let child = child.with_root_prefix("/nested/");
+        assert_eq!(child.info().root(), "/tenants/tenant-a/nested/");
+    }
+
+    #[test]
+    fn test_update_request_content_md5() {
+        let backend = AzdlsBuilder::default()
+            .filesystem("test")
+            .endpoint("https://account.dfs.core.windows.net")
+            .build_backend()
+            .unwrap();
+
+        let md5 = "XUFAKrxLKna5cZ2REBfFkg==";
+        let req = backend
+            .core
+            .azdls_update_request("a", Some(5), 0, Some(md5), Buffer::from("hello"))
+            .unwrap();
+        assert_eq!(req.headers()["x-ms-content-md5"], md5);
+
+        let req = backend
+            .core
+            .azdls_update_request("a", Some(5), 5, None, Buffer::from("hello"))
+            .unwrap();
+        assert!(!req.headers().contains_key("x-ms-content-md5"));
+    }
+
+    #[test]
+    fn test_flush_resets_content_encoding() {
+        let backend = AzdlsBuilder::default()
+            .filesystem("test")
+            .endpoint("https://account.dfs.core.windows.net")
+            .build_backend()
+            .unwrap();
+
+        let req = backend
+            .core
+            .azdls_update_request("a", Some(5), 0, None, Buffer::from("hello"))
+            .unwrap();
+        assert_eq!(req.headers()["x-ms-content-encoding"], "identity");
+
+        let req = backend
+            .core
+            .azdls_flush_request("a", 5, false, true)
+            .unwrap();
+        assert_eq!(req.headers()["x-ms-content-encoding"], "identity");
+    }
+
+    #[test]
+    fn test_request_path_encoding() {
+        let backend = AzdlsBuilder::default()
+            .filesystem("test")
+            .endpoint("https://account.dfs.core.windows.net")
+            .account_name("account")
+            .account_key("a2V5")
+            .root("/root dir/")
+            .build_backend()
+            .unwrap();
+
+        let cases = [
+            ("a b", "/test/root%20dir/a%20b"),
+            ("c#d", "/test/root%20dir/c%23d"),
+            ("e+f", "/test/root%20dir/e%2Bf"),
+            ("dir/g?h", "/test/root%20dir/dir/g%3Fh"),
+            (
+                "日本語.txt",
+                "/test/root%20dir/%E6%97%A5%E6%9C%AC%E8%AA%9E.txt",
+            ),
+        ];
+        for (path, expected) in cases {
+            let req = backend
+                .core
+                .azdls_create_request(path, "file", &OpWrite::default(), Buffer::new())
+                .unwrap();
+            assert_eq!(req.uri().path(), expected, "path: {path}");
+            assert_eq!(req.uri().query(), Some("resource=file"), "path: {path}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stat_storage_class() {
+        let server = MockServer::start(|_| {
+            MockResponse::new(StatusCode::OK)
+                .with_header("x-ms-resource-type", "file")
+                .with_header("x-ms-access-tier", "Archive")
+        });
+        let backend = test_backend(&server);
+
+        let meta = backend
+            .stat("a", OpStat::new())
+            .await
+            .unwrap()
+            .into_metadata();
+        assert!(meta.contains_metakey(Metakey::StorageClass));
+        assert_eq!(meta.storage_class(), Some("Archive"));
+    }
+
+    #[tokio::test]
+    async fn test_stat_many_partial_failure() {
+        let server = MockServer::start(|req| match req.uri.as_str() {
+            "/test/a?action=getStatus" => MockResponse::new(StatusCode::OK)
+                .with_header("x-ms-resource-type", "file")
+                .with_header("etag", "\"0x1\""),
+            _ => MockResponse::new(StatusCode::NOT_FOUND),
+        });
+        let backend = test_backend(&server);
+
+        let paths = vec!["/".to_string(), "a".to_string(), "dir/b".to_string()];
+        let metas = backend.stat_many(paths, 2).await;
+        assert_eq!(metas.len(), 3);
+        assert!(metas["/"].as_ref().unwrap().is_dir());
+        assert!(metas["a"].as_ref().unwrap().is_file());
+        assert_eq!(metas["a"].as_ref().unwrap().etag(), Some("\"0x1\""));
+        assert_eq!(
+            metas["dir/b"].as_ref().unwrap_err().kind(),
+            ErrorKind::NotFound
+        );
+        // The root is answered without a request.
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_list_recursive_with_prefix() {
+        let server = MockServer::start(|_| {
+            MockResponse::new(StatusCode::OK).with_body(
+                r#"{"paths":[
+                    {"contentLength":"0","etag":"0x1","isDirectory":"true","lastModified":"Sat, 26 Nov 2022 10:43:05 GMT","name":"dir/ab"},
+                    {"contentLength":"1","etag":"0x2","lastModified":"Sat, 26 Nov 2022 10:43:05 GMT","name":"dir/ab/c"},
+                    {"contentLength":"1","etag":"0x3","lastModified":"Sat, 26 Nov 2022 10:43:05 GMT","name":"dir/b"}
+                ]}"#,
+            )
+        });
+        let backend = test_backend(&server);
+
+        let (_, mut lister) = backend
+            .list("dir/a", OpList::new().with_recursive(true))
+            .await
+            .unwrap();
+        let mut paths = vec![];
+        while let Some(entry) = lister.next().await.unwrap() {
+            paths.push(entry.path().to_string());
+        }
+        assert_eq!(paths, vec!["dir/ab/", "dir/ab/c"]);
+
+        // Prefix that is not a dir must be listed from its parent.
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(
+            requests[0].uri,
+            "/test?resource=filesystem&recursive=true&directory=dir"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_properties_keeps_current() {
+        let server = MockServer::start(|req| match req.method {
+            http::Method::HEAD => MockResponse::new(StatusCode::OK)
+                .with_header("etag", "\"0x1\"")
+                .with_header("content-type", "text/plain")
+                .with_header("cache-control", "no-cache")
+                .with_header("content-disposition", "inline")
+                .with_header("content-md5", "XUFAKrxLKna5cZ2REBfFkg==")
+                .with_header("x-ms-properties", "owner=b3BlbmRhbA=="),
+            _ => MockResponse::new(StatusCode::OK).with_header("etag", "\"0x2\""),
+        });
+        let backend = test_backend(&server);
+
+        let meta = backend
+            .set_properties(
+                "a.txt",
+                AzdlsProperties::new().with_content_type("text/csv"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(meta.etag(), Some("\"0x2\""));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 2);
+        let req = &requests[1];
+        assert_eq!(req.method, http::Method::PATCH);
+        assert_eq!(req.uri, "/test/a.txt?action=setProperties");
+        assert_eq!(req.header("if-match"), Some("\"0x1\""));
+        assert_eq!(req.header("x-ms-content-type"), Some("text/csv"));
+        assert_eq!(req.header("x-ms-cache-control"), Some("no-cache"));
+        assert_eq!(req.header("x-ms-content-disposition"), Some("inline"));
+        assert_eq!(
+            req.header("x-ms-content-md5"),
+            Some("XUFAKrxLKna5cZ2REBfFkg==")
+        );
+        assert_eq!(req.header("x-ms-properties"), Some("owner=b3BlbmRhbA=="));
+    }
+
+    #[tokio::test]
+    async fn test_delete_dir_with_skip_list_dirs() {
+        let server = MockServer::start(|_| MockResponse::new(StatusCode::OK));
+        let builder = test_builder(&server);
+
+        let backend = builder.clone().build_backend().unwrap();
+        backend.delete("dir/", OpDelete::new()).await.unwrap();
+        let backend = builder.skip_list_dirs().build_backend().unwrap();
+        backend.delete("dir/", OpDelete::new()).await.unwrap();
+        backend.delete("file", OpDelete::new()).await.unwrap();
+
+        let uris: Vec<_> = server.requests().into_iter().map(|v| v.uri).collect();
+        assert_eq!(
+            uris,
+            vec!["/test/dir", "/test/dir?recursive=true", "/test/file"]
+        );
+    }
+
+    #[test]
+    fn test_check_content_range() {
+        let cr = |s: &str| Some(s.parse::<BytesContentRange>().unwrap());
+
+        let cases = vec![
+            (
+                "exact",
+                BytesRange::new(0, Some(10)),
+                cr("bytes 0-9/100"),
+                true,
+            ),
+            (
+                "offset",
+                BytesRange::new(10, Some(10)),
+                cr("bytes 10-19/100"),
+                true,
+            ),
+            (
+                "clamped",
+                BytesRange::new(90, Some(20)),
+                cr("bytes 90-99/100"),
+                true,
+            ),
+            (
+                "to end",
+                BytesRange::new(90, None),
+                cr("bytes 90-99/100"),
+                true,
+            ),
+            (
+                "unknown size",
+                BytesRange::new(0, Some(10)),
+                cr("bytes 0-9/*"),
+                true,
+            ),
+            (
+                "wrong start",
+                BytesRange::new(10, Some(10)),
+                cr("bytes 0-9/100"),
+                false,
+            ),
+            (
+                "wrong end",
+                BytesRange::new(0, Some(10)),
+                cr("bytes 0-19/100"),
+                false,
+            ),
+            (
+                "short end",
+                BytesRange::new(0, None),
+                cr("bytes 0-9/100"),
+                false,
+            ),
+            ("missing", BytesRange::new(0, Some(10)), None, false),
+        ];
+
+        for (name, range, cr, ok) in cases {
+            assert_eq!(check_content_range(range, cr).is_ok(), ok, "{name}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_from_reader_retry_lost_append() {
+        let attempts = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = attempts.clone();
+        let server = MockServer::start(move |req| {
+            if req.method == http::Method::PUT {
+                return MockResponse::new(StatusCode::CREATED);
+            }
+            // The append at 4 is applied by server, but its response is lost.
+            if req.uri.contains("action=append&position=4")
+                && counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 0
+            {
+                return MockResponse::new(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+            MockResponse::new(StatusCode::ACCEPTED)
+        });
+        let backend = test_builder(&server)
+            .max_retries(1)
+            .build_backend()
+            .unwrap();
+
+        let meta = AzdlsWriter::new(backend.core.clone(), OpWrite::new(), "file".to_string())
+            .write_from_reader(&mut &b"helloworld"[..], 4)
+            .await
+            .unwrap();
+        assert_eq!(meta.content_length(), 10);
+
+        // The lost append is sent again at the same position, and the flush
+        // happens at the length of all appended data.
+        assert_eq!(
+            append_positions(&server),
+            vec!["append@0", "append@4", "append@4", "append@8", "flush@10"]
+        );
+    }
+
+    /// Collect `{action}@{position}` of append and flush requests.
+    fn append_positions(server: &MockServer) -> Vec<String> {
+        server
+            .requests()
+            .iter()
+            .filter_map(|req| {
+                let (_, query) = req.uri.split_once('?')?;
+                let action = query.split('&').find_map(|v| v.strip_prefix("action="))?;
+                let position = query.split('&').find_map(|v| v.strip_prefix("position="))?;
+                Some(format!("{action}@{position}"))
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "chunk of write_from_reader must be greater than 0")]
+    async fn test_write_from_reader_zero_chunk() {
+        let server = MockServer::start(|_| MockResponse::new(StatusCode::OK));
+        let backend = test_backend(&server);
+
+        let _ = AzdlsWriter::new(backend.core.clone(), OpWrite::new(), "file".to_string())
+            .write_from_reader(&mut &b"hello"[..], 0)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_write_from_reader() {
+        let hello: &[u8] = b"hel";
+        let world: &[u8] = b"loworld";
+        let cases: Vec<(Box<dyn futures::AsyncRead + Unpin>, u64, Vec<&str>)> = vec![
+            (
+                Box::new(&b"helloworld!"[..]),
+                11,
+                vec!["append@0", "append@4", "append@8", "flush@11"],
+            ),
+            // Short reads are merged into full chunks.
+            (
+                Box::new(futures::AsyncReadExt::chain(hello, world)),
+                10,
+                vec!["append@0", "append@4", "append@8", "flush@10"],
+            ),
+            (Box::new(&b"hell"[..]), 4, vec!["append@0", "flush@4"]),
+            // Nothing to append, only flush the created file.
+            (Box::new(&b""[..]), 0, vec!["flush@0"]),
+        ];
+
+        for (mut reader, size, expected) in cases {
+            let server = MockServer::start(|req| match req.method {
+                http::Method::PUT => MockResponse::new(StatusCode::CREATED),
+                _ => MockResponse::new(StatusCode::ACCEPTED),
+            });
+            let backend = test_backend(&server);
+
+            let meta = AzdlsWriter::new(backend.core.clone(), OpWrite::new(), "file".to_string())
+                .write_from_reader(&mut reader, 4)
+                .await
+                .unwrap();
+            assert_eq!(meta.content_length(), size);
+
+            let requests = server.requests();
+            assert_eq!(requests[0].method, http::Method::PUT);
+            assert_eq!(append_positions(&server), expected);
+            let appended: usize = requests
+                .iter()
+                .filter(|req| req.uri.contains("action=append"))
+                .map(|req| req.body.len())
+                .sum();
+            assert_eq!(appended as u64, size);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verify_on_write() {
+        // The mock server reports `Content-Length: 0` on HEAD, so only empty
+        // content matches the stored length.
+        for (verify, content, expected) in [
+            (true, "hello", Some(ErrorKind::Unexpected)),
+            (true, "", None),
+            (false, "hello", None),
+        ] {
+            let server = MockServer::start(|req| match req.method {
+                http::Method::PUT => MockResponse::new(StatusCode::CREATED),
+                _ => MockResponse::new(StatusCode::OK),
+            });
+            let mut builder = test_builder(&server);
+            if verify {
+                builder = builder.verify_on_write();
+            }
+            let op = OperatorBuilder::new(builder.build_backend().unwrap()).finish();
+
+            let res = op.write("file", content).await;
+            assert_eq!(
+                res.err().map(|err| err.kind()),
+                expected,
+                "verify: {verify}, content: {content:?}"
+            );
+
+            let requests = server.requests();
+            let heads = requests
+                .iter()
+                .filter(|req| req.method == http::Method::HEAD)
+                .count();
+            // Verify the stored length after flush, no extra request if disabled.
+            assert_eq!(heads, usize::from(verify));
+            if verify {
+                assert_eq!(requests.last().unwrap().method, http::Method::HEAD);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rename_via_copy_polls_pending_copy() {
+        let polls = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = polls.clone();
+        let server = MockServer::start(move |req| match req.method {
+            http::Method::PUT => {
+                MockResponse::new(StatusCode::ACCEPTED).with_header(X_MS_COPY_STATUS, "pending")
+            }
+            http::Method::HEAD => {
+                let status = if counter.fetch_add(1, std::sync::atomic::Ordering::SeqCst) == 0 {
+                    "pending"
+                } else {
+                    "success"
+                };
+                MockResponse::new(StatusCode::OK).with_header(X_MS_COPY_STATUS, status)
+            }
+            _ => MockResponse::new(StatusCode::OK),
+        });
+        let backend = test_builder(&server)
+            .blob_endpoint(server.endpoint())
+            .build_backend()
+            .unwrap();
+
+        backend.rename_via_copy("from", "to").await.unwrap();
+
+        let methods: Vec<_> = server
+            .requests()
+            .iter()
+            .map(|req| req.method.to_string())
+            .collect();
+        // The source is only deleted after the copy has been completed.
+        assert_eq!(methods, vec!["PUT", "HEAD", "HEAD", "DELETE"]);
+    }
+
+    #[tokio::test]
+    async fn test_rename_via_copy_keeps_source_on_failed_copy() {
+        let server = MockServer::start(|req| match req.method {
+            http::Method::PUT => {
+                MockResponse::new(StatusCode::ACCEPTED).with_header(X_MS_COPY_STATUS, "pending")
+            }
+            _ => MockResponse::new(StatusCode::OK).with_header(X_MS_COPY_STATUS, "failed"),
+        });
+        let backend = test_builder(&server)
+            .blob_endpoint(server.endpoint())
+            .build_backend()
+            .unwrap();
+
+        let err = backend.rename_via_copy("from", "to").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(server
+            .requests()
+            .iter()
+            .all(|req| req.method != http::Method::DELETE));
+    }
+
+    #[tokio::test]
+    async fn test_preserve_endpoint() {
+        // The gateway routes on `/adls//`, trimming would lose the empty segment.
+        for (preserve, expected) in [
+            (true, ["/adls//test/file", "/adls//test/dir/file"]),
+            (false, ["/adls/test/file", "/adls/test/dir/file"]),
+        ] {
+            let server = MockServer::start(|_| MockResponse::new(StatusCode::OK));
+            let mut builder = AzdlsBuilder::default()
+                .filesystem("test")
+                .root("/")
+                .endpoint(&format!("{}/adls//", server.endpoint()))
+                .account_name("account")
+                .account_key("a2V5");
+            if preserve {
+                builder = builder.preserve_endpoint();
+            }
+            let backend = builder.build_backend().unwrap();
+
+            backend.delete("file", OpDelete::new()).await.unwrap();
+            backend.delete("dir/file", OpDelete::new()).await.unwrap();
+
+            let paths: Vec<_> = server
+                .requests()
+                .iter()
+                .map(|req| req.uri.split('?').next().unwrap_or_default().to_string())
+                .collect();
+            assert_eq!(paths, expected, "preserve_endpoint: {preserve}");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_dir_on_existing_path() {
+        for (resource_type, expected) in [
+            ("directory", None),
+            ("file", Some(ErrorKind::NotADirectory)),
+        ] {
+            let server = MockServer::start(move |req| match req.method {
+                http::Method::PUT => MockResponse::new(StatusCode::CONFLICT)
+                    .with_header("x-ms-error-code", "PathAlreadyExists"),
+                _ => MockResponse::new(StatusCode::OK)
+                    .with_header("x-ms-resource-type", resource_type),
+            });
+            let backend = test_backend(&server);
+
+            let res = backend.create_dir("dir/", OpCreateDir::new()).await;
+            assert_eq!(res.err().map(|err| err.kind()), expected, "{resource_type}");
+
+            let requests = server.requests();
+            assert_eq!(requests[0].header("if-none-match"), Some("*"));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_confirm_create_dir() {
+        for (status, resource_type, expected) in [
+            (StatusCode::OK, "directory", None),
+            (
+                StatusCode::OK,
+                "file",
+                Some((ErrorKind::NotADirectory, false)),
+            ),
+            // Not visible yet after create, retrying is ok.
+            (
+                StatusCode::NOT_FOUND,
+                "directory",
+                Some((ErrorKind::Unexpected, true)),
+            ),
+        ] {
+            let server = MockServer::start(move |req| match req.method {
+                http::Method::PUT => MockResponse::new(StatusCode::CREATED),
+                _ => MockResponse::new(status).with_header("x-ms-resource-type", resource_type),
+            });
+            let backend = test_builder(&server)
+                .confirm_create_dir()
+                .build_backend()
+                .unwrap();
+
+            let res = backend.create_dir("dir/", OpCreateDir::new()).await;
+            let actual = res.err().map(|err| (err.kind(), err.is_temporary()));
+            assert_eq!(actual, expected, "{status} {resource_type}");
+
+            let requests = server.requests();
+            assert_eq!(requests.len(), 2);
+            assert_eq!(requests[1].method, http::Method::HEAD);
+            assert_eq!(requests[1].uri, "/test/dir?action=getStatus");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_page_lister_sum() {
+        let server = MockServer::start(|req| {
+            if req.uri.contains("continuation=page2") {
+                MockResponse::new(StatusCode::OK).with_body(
+                    r#"{"paths":[
+                        {"contentLength":"7","etag":"0x3","lastModified":"Sat, 26 Nov 2022 10:43:05 GMT","name":"dir/c"}
+                    ]}"#,
+                )
+            } else {
+                MockResponse::new(StatusCode::OK)
+                    .with_header("x-ms-continuation", "page2")
+                    .with_body(
+                        r#"{"paths":[
+                            {"contentLength":"0","etag":"0x1","isDirectory":"true","lastModified":"Sat, 26 Nov 2022 10:43:05 GMT","name":"dir/sub"},
+                            {"contentLength":"3","etag":"0x2","lastModified":"Sat, 26 Nov 2022 10:43:05 GMT","name":"dir/a"},
+                            {"contentLength":"5","etag":"0x4","lastModified":"Sat, 26 Nov 2022 10:43:05 GMT","name":"dir/b"}
+                        ]}"#,
+                    )
+            }
+        });
+        let backend = test_backend(&server);
+
+        let (size, dirs) = backend.list_pages("dir/", false, None).sum().await.unwrap();
+        assert_eq!(size.bytes(), 15);
+        assert_eq!(size.objects(), 3);
+        assert_eq!(dirs, vec!["dir/sub/".to_string()]);
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_dir_size_cache() {
+        let server = MockServer::start(|_| {
+            MockResponse::new(StatusCode::OK).with_body(
+                r#"{"paths":[
+                    {"contentLength":"3","etag":"0x1","lastModified":"Sat, 26 Nov 2022 10:43:05 GMT","name":"dir/a"}
+                ]}"#,
+            )
+        });
+        let backend = test_builder(&server)
+            .dir_size_cache_ttl(Duration::from_millis(200))
+            .build_backend()
+            .unwrap();
+
+        let size = backend.dir_size("dir/", 1).await.unwrap();
+        assert_eq!((size.bytes(), size.objects()), (3, 1));
+
+        // Served from cache within ttl.
+        assert_eq!(backend.dir_size("dir/", 1).await.unwrap(), size);
+        assert_eq!(server.requests().len(), 1);
+
+        // Listed again once expired.
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(backend.dir_size("dir/", 1).await.unwrap(), size);
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_operator_read_decompressed() {
+        let content = "hello, world! ".repeat(64);
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(content.as_bytes()).unwrap();
+        let compressed = encoder.finish().unwrap();
+        assert!(compressed.len() < content.len());
+
+        let server = MockServer::start(move |req| match req.method {
+            http::Method::HEAD => MockResponse::new(StatusCode::OK)
+                .with_header("x-ms-resource-type", "file")
+                .with_header("content-encoding", "gzip"),
+            _ => MockResponse::new(StatusCode::OK)
+                .with_header("content-encoding", "gzip")
+                .with_body(compressed.clone()),
+        });
+        let backend = test_builder(&server)
+            .enable_decompression()
+            .build_backend()
+            .unwrap();
+        let op = OperatorBuilder::new(backend).finish();
+
+        let meta = op.stat("file").await.unwrap();
+        assert_eq!(meta.content_length(), content.len() as u64);
+
+        let bs = op.read("file").await.unwrap();
+        assert_eq!(bs.to_vec(), content.as_bytes());
+
+        let bs = op.read_with("file").range(7..20).await.unwrap();
+        assert_eq!(bs.to_vec(), &content.as_bytes()[7..20]);
+
+        let r = op
+            .reader_with("file")
+            .chunk(100)
+            .concurrent(2)
+            .await
+            .unwrap();
+        let bs = r.read(..).await.unwrap();
+        assert_eq!(bs.to_vec(), content.as_bytes());
+    }
+
+    #[tokio::test]
+    async fn test_append_retain_uncommitted_data() {
+        let server = MockServer::start(|req| match req.method {
+            http::Method::HEAD => MockResponse::new(StatusCode::NOT_FOUND),
+            _ => MockResponse::new(StatusCode::OK),
+        });
+        let backend = test_backend(&server);
+        let op = OperatorBuilder::new(backend).finish();
+
+        let mut w = op
+            .writer_with("wal.log")
+            .append(true)
+            .retain_uncommitted_data(true)
+            .await
+            .unwrap();
+        w.write("hello").await.unwrap();
+        w.write("world").await.unwrap();
+        w.close().await.unwrap();
+
+        let requests: Vec<_> = server
+            .requests()
+            .into_iter()
+            .filter(|req| req.method != http::Method::HEAD)
+            .map(|req| (req.method, req.uri))
+            .collect();
+        assert_eq!(
+            requests,
+            vec![
+                (http::Method::PUT, "/test/wal.log?resource=file".to_string()),
+                (
+                    http::Method::PATCH,
+                    "/test/wal.log?action=append&position=0".to_string()
+                ),
+                (
+                    http::Method::PATCH,
+                    "/test/wal.log?action=flush&position=5&retainUncommittedData=true&close=false"
+                        .to_string()
+                ),
+                // The file is still open, keep appending after the flushed prefix.
+                (
+                    http::Method::PATCH,
+                    "/test/wal.log?action=append&position=5".to_string()
+                ),
+                (
+                    http::Method::PATCH,
+                    "/test/wal.log?action=flush&position=10&retainUncommittedData=true&close=false"
+                        .to_string()
+                ),
+            ]
+        );
+    }
 }
@@ -15,36 +15,125 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::fmt::Write;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
 
+use backon::BackoffBuilder;
+use backon::ExponentialBuilder;
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use bytes::Buf;
+use http::header::ACCEPT_ENCODING;
+use http::header::AUTHORIZATION;
+use http::header::CACHE_CONTROL;
 use http::header::CONTENT_DISPOSITION;
 use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
+use http::header::IF_MATCH;
+use http::header::IF_MODIFIED_SINCE;
+use http::header::IF_NONE_MATCH;
+use http::header::IF_UNMODIFIED_SINCE;
+use http::HeaderMap;
 use http::HeaderName;
 use http::HeaderValue;
+use http::Method;
 use http::Request;
 use http::Response;
+use http::StatusCode;
+use http::Uri;
+use log::debug;
+use log::warn;
 use reqsign::AzureStorageCredential;
 use reqsign::AzureStorageLoader;
 use reqsign::AzureStorageSigner;
+use serde::Deserialize;
+use serde::Serialize;
+use tokio::sync::OnceCell;
+use uuid::Uuid;
 
+use super::backend::AzdlsProperties;
+use super::error::parse_error;
+use super::lister::AzdlsDirSize;
 use crate::raw::*;
 use crate::*;
 
 const X_MS_RENAME_SOURCE: &str = "x-ms-rename-source";
+const X_MS_COPY_SOURCE: &str = "x-ms-copy-source";
+pub const X_MS_COPY_STATUS: &str = "x-ms-copy-status";
 const X_MS_VERSION: &str = "x-ms-version";
+const X_MS_EXPIRY_OPTION: &str = "x-ms-expiry-option";
+const X_MS_EXPIRY_TIME: &str = "x-ms-expiry-time";
+const X_MS_CONTENT_MD5: &str = "x-ms-content-md5";
+pub const X_MS_CLIENT_REQUEST_ID: &str = "x-ms-client-request-id";
+pub const X_MS_CREATION_TIME: &str = "x-ms-creation-time";
+const X_MS_LEASE_ACTION: &str = "x-ms-lease-action";
+const X_MS_LEASE_DURATION: &str = "x-ms-lease-duration";
+const X_MS_LEASE_ID: &str = "x-ms-lease-id";
+pub const X_MS_ACCESS_TIER: &str = "x-ms-access-tier";
+const X_MS_REHYDRATE_PRIORITY: &str = "x-ms-rehydrate-priority";
+pub const X_MS_PROPERTIES: &str = "x-ms-properties";
+const X_MS_CONTENT_TYPE: &str = "x-ms-content-type";
+const X_MS_CONTENT_DISPOSITION: &str = "x-ms-content-disposition";
+const X_MS_CACHE_CONTROL: &str = "x-ms-cache-control";
+const X_MS_CONTENT_ENCODING: &str = "x-ms-content-encoding";
+pub const X_MS_VERSION_ID: &str = "x-ms-version-id";
+
+/// Content written by opendal is never encoded. The encoding is reset
+/// explicitly while flushing, otherwise the encoding of an overwritten file
+/// survives and breaks reads with decompression enabled.
+const IDENTITY_CONTENT_ENCODING: &str = "identity";
+
+/// Hook to provide the client request id of requests.
+pub type ClientRequestIdFn = Arc<dyn Fn() -> Option<String> + Send + Sync>;
 
 pub struct AzdlsCore {
     pub filesystem: String,
     pub root: String,
     pub endpoint: String,
+    pub blob_endpoint: String,
 
     pub client: HttpClient,
-    pub loader: AzureStorageLoader,
+    pub loader: Arc<AzureStorageLoader>,
     pub signer: AzureStorageSigner,
+    /// The auth mode in use, only used to make signing failures debuggable.
+    pub auth_mode: &'static str,
+    pub allow_anonymous: bool,
+    pub enable_decompression: bool,
+    pub accept_encoding: Option<String>,
+    pub confirm_create_dir: bool,
+    pub skip_list_dirs: bool,
+    pub rename_fallback_to_copy: bool,
+    pub client_request_id_prefix: Option<String>,
+    pub client_request_id_fn: Option<ClientRequestIdFn>,
+    /// Percent-encoded query string appended to every request.
+    pub extra_query: Option<String>,
+    /// Bytes to read ahead from the response body, `0` means disabled.
+    pub read_prefetch_size: usize,
+    pub enable_stat_tags: bool,
+    /// Max redirects to follow for read requests, `0` means disabled.
+    pub max_redirects: usize,
+    /// Max retries of idempotent requests, `0` means disabled.
+    pub max_retries: usize,
+    /// Max time to wait for the response of requests without body.
+    pub first_byte_timeout: Option<Duration>,
+    /// Secondary hosts that redirects are allowed to point to.
+    pub secondary_hosts: Vec<String>,
+    pub create_filesystem_if_not_exists: bool,
+    pub verify_on_write: bool,
+    pub dir_size_cache_ttl: Option<Duration>,
+    /// Cached results of `dir_size` by absolute path, shared with the
+    /// backends derived via `with_root_prefix`.
+    pub dir_size_cache: Arc<Mutex<HashMap<String, (Instant, AzdlsDirSize)>>>,
+    /// Initialized once the filesystem is known to exist, shared with the
+    /// backends derived via `with_root_prefix`.
+    pub filesystem_created: Arc<OnceCell<()>>,
 }
 
 impl Debug for AzdlsCore {
@@ -53,28 +142,75 @@ impl Debug for AzdlsCore {
             .field("filesystem", &self.filesystem)
             .field("root", &self.root)
             .field("endpoint", &self.endpoint)
+            .field("blob_endpoint", &self.blob_endpoint)
+            .field("auth_mode", &self.auth_mode)
+            .field("allow_anonymous", &self.allow_anonymous)
+            .field("enable_decompression", &self.enable_decompression)
+            .field("accept_encoding", &self.accept_encoding)
+            .field("confirm_create_dir", &self.confirm_create_dir)
+            .field("skip_list_dirs", &self.skip_list_dirs)
+            .field("rename_fallback_to_copy", &self.rename_fallback_to_copy)
+            .field("client_request_id_prefix", &self.client_request_id_prefix)
+            .field("extra_query", &self.extra_query)
+            .field("read_prefetch_size", &self.read_prefetch_size)
+            .field("enable_stat_tags", &self.enable_stat_tags)
+            .field("max_redirects", &self.max_redirects)
+            .field("max_retries", &self.max_retries)
+            .field("first_byte_timeout", &self.first_byte_timeout)
+            .field("secondary_hosts", &self.secondary_hosts)
+            .field(
+                "create_filesystem_if_not_exists",
+                &self.create_filesystem_if_not_exists,
+            )
+            .field("verify_on_write", &self.verify_on_write)
+            .field("dir_size_cache_ttl", &self.dir_size_cache_ttl)
             .finish_non_exhaustive()
     }
 }
 
 impl AzdlsCore {
-    async fn load_credential(&self) -> Result<AzureStorageCredential> {
-        let cred = self
-            .loader
-            .load()
-            .await
-            .map_err(new_request_credential_error)?;
+    async fn load_credential(&self) -> Result<Option<AzureStorageCredential>> {
+        let cred = self.loader.load().await.map_err(|err| {
+            // The source error may carry the raw AAD response, only pick
+            // the error code out of it so that we don't leak anything else.
+            let aad_error = parse_aad_error_code(&err.to_string());
+            warn!(
+                "azdls load credential failed: auth_mode={}, aad_error={}",
+                self.auth_mode,
+                aad_error.as_deref().unwrap_or("-"),
+            );
+
+            let mut err =
+                new_request_credential_error(err).with_context("auth_mode", self.auth_mode);
+            if let Some(code) = aad_error {
+                err = err.with_context("aad_error", code);
+            }
+            err
+        })?;
 
         if let Some(cred) = cred {
-            Ok(cred)
+            Ok(Some(cred))
+        } else if self.allow_anonymous {
+            // If allow_anonymous has been set, we will not sign the request.
+            Ok(None)
         } else {
-            Err(Error::new(
-                ErrorKind::ConfigInvalid,
-                "no valid credential found",
-            ))
+            Err(
+                Error::new(ErrorKind::ConfigInvalid, "no valid credential found")
+                    .with_context("auth_mode", self.auth_mode),
+            )
         }
     }
 
+    /// Build the client request id for a new request.
+    fn client_request_id(&self) -> Option<String> {
+        if let Some(id) = self.client_request_id_fn.as_ref().and_then(|f| f()) {
+            return Some(id);
+        }
+        self.client_request_id_prefix
+            .as_ref()
+            .map(|prefix| format!("{prefix}{}", Uuid::new_v4()))
+    }
+
     pub async fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
         let cred = self.load_credential().await?;
         // Insert x-ms-version header for normal requests.
@@ -87,25 +223,430 @@ impl AzdlsCore {
             // In the future, we could allow users to configure this value.
             HeaderValue::from_static("2022-11-02"),
         );
-        self.signer.sign(req, &cred).map_err(new_request_sign_error)
+        // Extra query parameters must be added before signing so that they
+        // are included in the canonicalized resource.
+        if let Some(extra) = &self.extra_query {
+            *req.uri_mut() = append_extra_query(req.uri(), extra)?;
+        }
+        // The client request id is covered by the shared key signature, so
+        // it must be set before signing. Keep the existing one while signing
+        // redirected requests again.
+        if !req.headers().contains_key(X_MS_CLIENT_REQUEST_ID) {
+            if let Some(id) = self.client_request_id() {
+                req.headers_mut()
+                    .insert(X_MS_CLIENT_REQUEST_ID, build_header_value(&id)?);
+            }
+        }
+
+        let Some(cred) = cred else {
+            // Anonymous access is only allowed for read requests.
+            return if req.method() == Method::GET || req.method() == Method::HEAD {
+                Ok(())
+            } else {
+                Err(Error::new(
+                    ErrorKind::PermissionDenied,
+                    "no valid credential found, anonymous access only allows read operations",
+                )
+                .with_context("method", req.method().as_str()))
+            };
+        };
+
+        self.signer.sign(req, &cred).map_err(|err| {
+            warn!(
+                "azdls sign request failed: auth_mode={}, method={}",
+                self.auth_mode,
+                req.method()
+            );
+
+            new_request_sign_error(err)
+                .with_context("auth_mode", self.auth_mode)
+                .with_context("method", req.method().as_str())
+        })
     }
 
     #[inline]
     pub async fn send(&self, req: Request<Buffer>) -> Result<Response<Buffer>> {
-        self.client.send(req).await
+        let (parts, mut body) = self.fetch(req).await?.into_parts();
+        let buffer = body.to_buffer().await?;
+        Ok(Response::from_parts(parts, buffer))
+    }
+
+    /// Fetch the request, retrying idempotent requests on temporary errors
+    /// at most `max_retries` times.
+    ///
+    /// Non-idempotent requests like append are never retried here, callers
+    /// must make sure the previous attempt didn't commit before retrying.
+    pub async fn fetch(&self, req: Request<Buffer>) -> Result<Response<HttpBody>> {
+        if self.max_retries == 0 || !is_idempotent(&req) {
+            return self.fetch_attempt(req).await;
+        }
+
+        let (parts, body) = req.into_parts();
+        let mut backoff = ExponentialBuilder::default()
+            .with_jitter()
+            .with_max_times(self.max_retries)
+            .build();
+        loop {
+            let mut req = Request::new(body.clone());
+            *req.method_mut() = parts.method.clone();
+            *req.uri_mut() = parts.uri.clone();
+            *req.headers_mut() = parts.headers.clone();
+
+            let res = self.fetch_attempt(req).await;
+            let retryable = match &res {
+                Ok(resp) => is_retryable_status(resp.status()),
+                Err(err) => err.is_temporary(),
+            };
+            match backoff.next() {
+                Some(delay) if retryable => {
+                    debug!(
+                        "azdls request {} {} failed temporarily, retry after {delay:?}",
+                        parts.method,
+                        redact_uri(&parts.uri)
+                    );
+                    tokio::time::sleep(delay).await;
+                }
+                _ => return res,
+            }
+        }
+    }
+
+    /// Fetch the request, failing fast if the response doesn't arrive within
+    /// `first_byte_timeout`.
+    ///
+    /// Requests with body are not limited, uploading the body happens before
+    /// the response arrives as well.
+    async fn fetch_with_timeout(&self, req: Request<Buffer>) -> Result<Response<HttpBody>> {
+        let timeout = match self.first_byte_timeout {
+            Some(v) if req.body().is_empty() => v,
+            _ => return self.client.fetch(req).await,
+        };
+
+        let uri = redact_uri(req.uri());
+        match tokio::time::timeout(timeout, self.client.fetch(req)).await {
+            Ok(res) => res,
+            Err(_) => Err(Error::new(
+                ErrorKind::Unexpected,
+                "response is not received within first byte timeout",
+            )
+            .with_operation("AzdlsCore::fetch")
+            .with_context("url", uri)
+            .with_context("timeout", format!("{timeout:?}"))
+            .set_temporary()),
+        }
+    }
+
+    /// Fetch the request, following `307`/`308` redirects of read requests
+    /// if `max_redirects` is set.
+    ///
+    /// Geo-redundant accounts could redirect reads to the secondary endpoint
+    /// during failover. Redirects are only followed to the same host or a
+    /// known secondary host, and the request will be signed again for the
+    /// new location.
+    async fn fetch_attempt(&self, req: Request<Buffer>) -> Result<Response<HttpBody>> {
+        // Requests failed without response can't be found by the id in the
+        // response, attach it here.
+        let client_request_id = req
+            .headers()
+            .get(X_MS_CLIENT_REQUEST_ID)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.to_string());
+        let with_client_request_id = |err: Error| match &client_request_id {
+            Some(id) => err.with_context("client_request_id", id),
+            None => err,
+        };
+
+        if self.max_redirects == 0 || !matches!(*req.method(), Method::GET | Method::HEAD) {
+            debug!(
+                "azdls send request: {} {}",
+                req.method(),
+                redact_uri(req.uri())
+            );
+            return self
+                .fetch_with_timeout(req)
+                .await
+                .map_err(with_client_request_id);
+        }
+
+        let (parts, body) = req.into_parts();
+        let mut uri = parts.uri;
+        let mut redirects = 0;
+        loop {
+            let mut req = Request::builder()
+                .method(parts.method.clone())
+                .uri(uri.clone())
+                .body(body.clone())
+                .map_err(new_request_build_error)?;
+            *req.headers_mut() = parts.headers.clone();
+            if redirects > 0 {
+                req.headers_mut().remove(AUTHORIZATION);
+                self.sign(&mut req).await?;
+            }
+
+            debug!(
+                "azdls send request: {} {}",
+                req.method(),
+                redact_uri(req.uri())
+            );
+            let resp = self
+                .fetch_with_timeout(req)
+                .await
+                .map_err(with_client_request_id)?;
+            if !matches!(
+                resp.status(),
+                StatusCode::TEMPORARY_REDIRECT | StatusCode::PERMANENT_REDIRECT
+            ) || redirects >= self.max_redirects
+            {
+                return Ok(resp);
+            }
+            let Some(location) = parse_location(resp.headers())? else {
+                return Ok(resp);
+            };
+
+            let next = resolve_redirect(&uri, location)?;
+            if next.host() != uri.host()
+                && !next
+                    .host()
+                    .is_some_and(|h| self.secondary_hosts.iter().any(|v| v == h))
+            {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "azdls redirects to an unknown host, refuse to follow",
+                )
+                .with_operation("AzdlsCore::fetch")
+                .with_context("uri", uri.to_string())
+                .with_context("location", location));
+            }
+
+            debug!("azdls request to {uri} is redirected to {next}");
+            uri = next;
+            redirects += 1;
+        }
+    }
+}
+
+/// Check if the request is safe to be retried blindly.
+///
+/// `PUT` is only idempotent with precondition, `PATCH` (like append and
+/// flush) and `POST` could be applied twice.
+fn is_idempotent(req: &Request<Buffer>) -> bool {
+    match *req.method() {
+        Method::GET | Method::HEAD | Method::DELETE => true,
+        Method::PUT => {
+            req.headers().contains_key(IF_MATCH) || req.headers().contains_key(IF_NONE_MATCH)
+        }
+        _ => false,
+    }
+}
+
+/// Status codes that `parse_error` treats as temporary.
+fn is_retryable_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::INTERNAL_SERVER_ERROR
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
+
+/// Redact the signature of SAS token in uri so that it's safe to be logged.
+///
+/// Other parts of the uri are kept as is, to make percent-encoding issues
+/// visible.
+fn redact_uri(uri: &Uri) -> String {
+    let s = uri.to_string();
+    let Some((base, query)) = s.split_once('?') else {
+        return s;
+    };
+
+    let query = query
+        .split('&')
+        .map(|kv| match kv.split_once('=') {
+            Some((k, _)) if k.eq_ignore_ascii_case("sig") => format!("{k}=<redacted>"),
+            _ => kv.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("&");
+    format!("{base}?{query}")
+}
+
+/// Append the extra query parameters to uri, parameters whose key is
+/// already present are skipped.
+///
+/// Requests could be signed again while following redirects, so this must
+/// be idempotent.
+fn append_extra_query(uri: &Uri, extra: &str) -> Result<Uri> {
+    let query = uri.query().unwrap_or_default();
+    let keys: Vec<&str> = query
+        .split('&')
+        .filter_map(|kv| kv.split('=').next())
+        .collect();
+    let extra: Vec<&str> = extra
+        .split('&')
+        .filter(|kv| !keys.contains(&kv.split('=').next().unwrap_or_default()))
+        .collect();
+    if extra.is_empty() {
+        return Ok(uri.clone());
+    }
+
+    let path_and_query = if query.is_empty() {
+        format!("{}?{}", uri.path(), extra.join("&"))
+    } else {
+        format!("{}?{query}&{}", uri.path(), extra.join("&"))
+    };
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = Some(
+        path_and_query
+            .parse()
+            .map_err(|err: http::uri::InvalidUri| new_request_build_error(err.into()))?,
+    );
+    Uri::from_parts(parts).map_err(|err| new_request_build_error(err.into()))
+}
+
+/// Format user metadata as `x-ms-properties`, which is a list of
+/// `name=value` with base64 encoded values.
+fn format_properties(metadata: &HashMap<String, String>) -> String {
+    let mut properties: Vec<_> = metadata
+        .iter()
+        .map(|(k, v)| format!("{k}={}", BASE64_STANDARD.encode(v)))
+        .collect();
+    properties.sort();
+    properties.join(",")
+}
+
+/// Parse user metadata from `x-ms-properties`.
+pub fn parse_properties(v: &str) -> Result<HashMap<String, String>> {
+    let mut metadata = HashMap::new();
+    for kv in v.split(',').filter(|kv| !kv.is_empty()) {
+        let parsed = kv.split_once('=').and_then(|(k, v)| {
+            let v = BASE64_STANDARD.decode(v.trim()).ok()?;
+            Some((k.trim().to_string(), String::from_utf8(v).ok()?))
+        });
+        let Some((k, v)) = parsed else {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "azdls returns invalid x-ms-properties",
+            )
+            .with_context("properties", v));
+        };
+        metadata.insert(k, v);
+    }
+    Ok(metadata)
+}
+
+/// Resolve the redirect location against the current uri, the location
+/// could be a relative one like `/filesystem/path`.
+/// Build the query to select a historical version or snapshot of the file.
+///
+/// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/get-blob#uri-parameters
+fn format_version_query(version: Option<&str>, snapshot: Option<&str>) -> Option<String> {
+    let mut query = Vec::new();
+    if let Some(v) = version {
+        query.push(format!("versionid={}", percent_encode_path(v)));
+    }
+    if let Some(v) = snapshot {
+        query.push(format!("snapshot={}", percent_encode_path(v)));
+    }
+    (!query.is_empty()).then(|| query.join("&"))
+}
+
+fn resolve_redirect(uri: &Uri, location: &str) -> Result<Uri> {
+    let location = if location.starts_with('/') {
+        format!(
+            "{}://{}{location}",
+            uri.scheme_str().unwrap_or("https"),
+            uri.authority().map(|v| v.as_str()).unwrap_or_default()
+        )
+    } else {
+        location.to_string()
+    };
+
+    location.parse().map_err(|err| {
+        Error::new(
+            ErrorKind::Unexpected,
+            "azdls returns invalid redirect location",
+        )
+        .with_context("location", location)
+        .set_source(err)
+    })
+}
+
+/// Build the `x-ms-expiry-option` and `x-ms-expiry-time` of file.
+///
+/// Relative expiry is in milliseconds, while absolute expiry is in RFC 1123
+/// format.
+fn build_expiry(args: &OpWrite) -> Option<(&'static str, String)> {
+    if let Some(v) = args.expires_in() {
+        return Some(("RelativeToNow", v.as_millis().to_string()));
+    }
+    args.expires_at().map(|v| {
+        (
+            "Absolute",
+            v.format("%a, %d %b %Y %H:%M:%S GMT").to_string(),
+        )
+    })
+}
+
+/// Attach the lease id to the request, writes and deletes on a leased path
+/// must carry the active lease id.
+pub fn insert_lease_id(req: &mut Request<Buffer>, lease_id: Option<&str>) -> Result<()> {
+    if let Some(v) = lease_id {
+        let value = HeaderValue::from_str(v).map_err(|err| {
+            Error::new(
+                ErrorKind::ConfigInvalid,
+                "lease id is not a valid header value",
+            )
+            .set_source(err)
+        })?;
+        req.headers_mut().insert(X_MS_LEASE_ID, value);
+    }
+    Ok(())
+}
+
+fn join_endpoint(endpoint: &str, filesystem: &str) -> String {
+    if endpoint.ends_with('/') {
+        format!("{endpoint}{filesystem}")
+    } else {
+        format!("{endpoint}/{filesystem}")
+    }
+}
+
+/// Format the `x-ms-lease-duration` in seconds, `-1` means infinite.
+fn format_lease_duration(duration: Option<Duration>) -> String {
+    match duration {
+        Some(v) => v.as_secs().to_string(),
+        None => "-1".to_string(),
     }
 }
 
 impl AzdlsCore {
-    pub async fn azdls_read(&self, path: &str, range: BytesRange) -> Result<Response<HttpBody>> {
+    /// Build `{endpoint}/{filesystem}` of the dfs endpoint.
+    ///
+    /// Endpoints could keep their trailing `/` while `preserve_endpoint` is
+    /// enabled, join them without a duplicated `/`.
+    fn filesystem_url(&self) -> String {
+        join_endpoint(&self.endpoint, &self.filesystem)
+    }
+
+    /// Build `{blob_endpoint}/{filesystem}` of the blob endpoint.
+    fn blob_filesystem_url(&self) -> String {
+        join_endpoint(&self.blob_endpoint, &self.filesystem)
+    }
+
+    pub async fn azdls_read(
+        &self,
+        path: &str,
+        range: BytesRange,
+        args: &OpRead,
+    ) -> Result<Response<HttpBody>> {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!(
-            "{}/{}/{}",
-            self.endpoint,
-            self.filesystem,
-            percent_encode_path(&p)
-        );
+        let mut url = format!("{}/{}", self.filesystem_url(), percent_encode_path(&p));
+        if let Some(query) = format_version_query(args.version(), args.snapshot()) {
+            url.push('?');
+            url.push_str(&query);
+        }
 
         let mut req = Request::get(&url);
 
@@ -113,10 +654,22 @@ impl AzdlsCore {
             req = req.header(http::header::RANGE, range.to_header());
         }
 
+        if let Some(if_match) = args.if_match() {
+            req = req.header(IF_MATCH, if_match);
+        }
+
+        if let Some(if_none_match) = args.if_none_match() {
+            req = req.header(IF_NONE_MATCH, if_none_match);
+        }
+
+        if let Some(v) = &self.accept_encoding {
+            req = req.header(ACCEPT_ENCODING, v);
+        }
+
         let mut req = req.body(Buffer::new()).map_err(new_request_build_error)?;
 
         self.sign(&mut req).await?;
-        self.client.fetch(req).await
+        self.fetch(req).await
     }
 
     /// resource should be one of `file` or `directory`
@@ -134,9 +687,8 @@ impl AzdlsCore {
             .to_string();
 
         let url = format!(
-            "{}/{}/{}?resource={resource}",
-            self.endpoint,
-            self.filesystem,
+            "{}/{}?resource={resource}",
+            self.filesystem_url(),
             percent_encode_path(&p)
         );
 
@@ -153,6 +705,24 @@ impl AzdlsCore {
             req = req.header(CONTENT_DISPOSITION, pos)
         }
 
+        if let Some(v) = args.user_metadata() {
+            req = req.header(X_MS_PROPERTIES, format_properties(v))
+        }
+
+        // Leased path can only be written with the active lease id.
+        if let Some(v) = args.lock_token() {
+            req = req.header(X_MS_LEASE_ID, v)
+        }
+
+        // Expiry can only be set on files.
+        if resource == "file" {
+            if let Some((option, time)) = build_expiry(args) {
+                req = req
+                    .header(X_MS_EXPIRY_OPTION, option)
+                    .header(X_MS_EXPIRY_TIME, time);
+            }
+        }
+
         // Set body
         let req = req.body(body).map_err(new_request_build_error)?;
 
@@ -163,12 +733,7 @@ impl AzdlsCore {
         let source = build_abs_path(&self.root, from);
         let target = build_abs_path(&self.root, to);
 
-        let url = format!(
-            "{}/{}/{}",
-            self.endpoint,
-            self.filesystem,
-            percent_encode_path(&target)
-        );
+        let url = format!("{}/{}", self.filesystem_url(), percent_encode_path(&target));
 
         let mut req = Request::put(&url)
             .header(
@@ -183,12 +748,62 @@ impl AzdlsCore {
         self.send(req).await
     }
 
+    /// Copy is not supported by the dfs endpoint, we use the blob service's
+    /// copy blob API instead.
+    ///
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/copy-blob
+    pub async fn azdls_copy(&self, from: &str, to: &str) -> Result<Response<Buffer>> {
+        let source = build_abs_path(&self.root, from);
+        let target = build_abs_path(&self.root, to);
+
+        let source = format!(
+            "{}/{}",
+            self.blob_filesystem_url(),
+            percent_encode_path(&source)
+        );
+        let target = format!(
+            "{}/{}",
+            self.blob_filesystem_url(),
+            percent_encode_path(&target)
+        );
+
+        let mut req = Request::put(&target)
+            .header(X_MS_COPY_SOURCE, source)
+            .header(CONTENT_LENGTH, 0)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
+    /// Get the blob properties of the copy target to check `x-ms-copy-status`.
+    ///
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/get-blob-properties
+    pub async fn azdls_get_copy_status(&self, to: &str) -> Result<Response<Buffer>> {
+        let target = build_abs_path(&self.root, to);
+
+        let url = format!(
+            "{}/{}",
+            self.blob_filesystem_url(),
+            percent_encode_path(&target)
+        );
+
+        let mut req = Request::head(&url)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
     /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/datalakestoragegen2/path/update
     pub fn azdls_update_request(
         &self,
         path: &str,
         size: Option<u64>,
         position: u64,
+        content_md5: Option<&str>,
         body: Buffer,
     ) -> Result<Request<Buffer>> {
         let p = build_abs_path(&self.root, path);
@@ -196,9 +811,8 @@ impl AzdlsCore {
         // - close: Make this is the final action to this file.
         // - flush: Flush the file directly.
         let url = format!(
-            "{}/{}/{}?action=append&close=true&flush=true&position={}",
-            self.endpoint,
-            self.filesystem,
+            "{}/{}?action=append&close=true&flush=true&position={}",
+            self.filesystem_url(),
             percent_encode_path(&p),
             position
         );
@@ -209,56 +823,453 @@ impl AzdlsCore {
             req = req.header(CONTENT_LENGTH, size)
         }
 
+        // The MD5 of the whole file will be stored and returned as
+        // `Content-MD5` by read and stat.
+        if let Some(v) = content_md5 {
+            req = req.header(X_MS_CONTENT_MD5, v)
+        }
+
+        req = req.header(X_MS_CONTENT_ENCODING, IDENTITY_CONTENT_ENCODING);
+
         // Set body
         let req = req.body(body).map_err(new_request_build_error)?;
 
         Ok(req)
     }
 
-    pub async fn azdls_get_properties(&self, path: &str) -> Result<Response<Buffer>> {
+    /// Append data to the file without flushing it.
+    ///
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/datalakestoragegen2/path/update
+    pub fn azdls_append_request(
+        &self,
+        path: &str,
+        size: u64,
+        position: u64,
+        body: Buffer,
+    ) -> Result<Request<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}?action=append&position={}",
+            self.filesystem_url(),
+            percent_encode_path(&p),
+            position
+        );
+
+        Request::patch(&url)
+            .header(CONTENT_LENGTH, size)
+            .body(body)
+            .map_err(new_request_build_error)
+    }
+
+    /// Flush appended data before `position` to the file.
+    ///
+    /// - retainUncommittedData: Keep the data after position instead of
+    ///   discarding it.
+    /// - close: Make this is the final action to this file.
+    ///
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/datalakestoragegen2/path/update
+    pub fn azdls_flush_request(
+        &self,
+        path: &str,
+        position: u64,
+        retain_uncommitted_data: bool,
+        close: bool,
+    ) -> Result<Request<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}?action=flush&position={}&retainUncommittedData={}&close={}",
+            self.filesystem_url(),
+            percent_encode_path(&p),
+            position,
+            retain_uncommitted_data,
+            close
+        );
+
+        Request::patch(&url)
+            .header(CONTENT_LENGTH, 0)
+            .header(X_MS_CONTENT_ENCODING, IDENTITY_CONTENT_ENCODING)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)
+    }
+
+    pub async fn azdls_get_properties(
+        &self,
+        path: &str,
+        args: &OpStat,
+    ) -> Result<Response<Buffer>> {
         let p = build_abs_path(&self.root, path)
             .trim_end_matches('/')
             .to_string();
 
-        let url = format!(
-            "{}/{}/{}?action=getStatus",
-            self.endpoint,
-            self.filesystem,
+        let mut url = format!(
+            "{}/{}?action=getStatus",
+            self.filesystem_url(),
             percent_encode_path(&p)
         );
+        if let Some(query) = format_version_query(args.version(), args.snapshot()) {
+            url.push('&');
+            url.push_str(&query);
+        }
 
-        let req = Request::head(&url);
+        let mut req = Request::head(&url);
+
+        if let Some(v) = args.if_modified_since() {
+            req = req.header(IF_MODIFIED_SINCE, format_datetime_into_http_date(v));
+        }
+
+        if let Some(v) = args.if_unmodified_since() {
+            req = req.header(IF_UNMODIFIED_SINCE, format_datetime_into_http_date(v));
+        }
 
         let mut req = req.body(Buffer::new()).map_err(new_request_build_error)?;
 
         self.sign(&mut req).await?;
-        self.client.send(req).await
+        self.send(req).await
+    }
+
+    fn azdls_tags_url(&self, path: &str) -> String {
+        let p = build_abs_path(&self.root, path);
+
+        format!(
+            "{}/{}?comp=tags",
+            self.blob_filesystem_url(),
+            percent_encode_path(&p)
+        )
+    }
+
+    /// Tags are not exposed by the dfs endpoint, we use the blob service's
+    /// get blob tags API instead.
+    ///
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/get-blob-tags
+    pub async fn azdls_get_tags(&self, path: &str) -> Result<HashMap<String, String>> {
+        let mut req = Request::get(self.azdls_tags_url(path))
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        let resp = self.send(req).await?;
+
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let tags: Tags = quick_xml::de::from_reader(resp.into_body().reader())
+            .map_err(new_xml_deserialize_error)?;
+        Ok(tags.into())
+    }
+
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/set-blob-tags
+    pub async fn azdls_set_tags(
+        &self,
+        path: &str,
+        tags: &HashMap<String, String>,
+        lease_id: Option<&str>,
+    ) -> Result<()> {
+        let content =
+            quick_xml::se::to_string(&Tags::from(tags)).map_err(new_xml_deserialize_error)?;
+
+        let mut req = Request::put(self.azdls_tags_url(path))
+            .header(CONTENT_TYPE, "application/xml")
+            .header(CONTENT_LENGTH, content.len())
+            .body(Buffer::from(content))
+            .map_err(new_request_build_error)?;
+        insert_lease_id(&mut req, lease_id)?;
+
+        self.sign(&mut req).await?;
+        let resp = self.send(req).await?;
+
+        match resp.status() {
+            StatusCode::NO_CONTENT | StatusCode::OK => Ok(()),
+            _ => Err(parse_error(resp).await?),
+        }
     }
 
-    pub async fn azdls_delete(&self, path: &str) -> Result<Response<Buffer>> {
+    /// Build the request to update properties of path in place, content
+    /// is not touched.
+    ///
+    /// setProperties clears every property that is not sent, so properties
+    /// not given in `props` are carried forward from `current`, which is
+    /// the response headers of `getStatus`. The request is conditioned on
+    /// the etag of `current` so that concurrent updates are not lost.
+    ///
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/datalakestoragegen2/path/update
+    pub fn azdls_set_properties_request(
+        &self,
+        path: &str,
+        props: &AzdlsProperties,
+        current: &HeaderMap,
+    ) -> Result<Request<Buffer>> {
         let p = build_abs_path(&self.root, path)
             .trim_end_matches('/')
             .to_string();
 
         let url = format!(
-            "{}/{}/{}",
-            self.endpoint,
-            self.filesystem,
+            "{}/{}?action=setProperties",
+            self.filesystem_url(),
             percent_encode_path(&p)
         );
 
+        let mut req = Request::patch(&url).header(CONTENT_LENGTH, 0);
+
+        let content_type = props.content_type().or(parse_content_type(current)?);
+        if let Some(v) = content_type {
+            req = req.header(X_MS_CONTENT_TYPE, v)
+        }
+        let content_disposition = props
+            .content_disposition()
+            .or(parse_content_disposition(current)?);
+        if let Some(v) = content_disposition {
+            req = req.header(X_MS_CONTENT_DISPOSITION, v)
+        }
+        let cache_control = props
+            .cache_control()
+            .or(parse_header_to_str(current, CACHE_CONTROL)?);
+        if let Some(v) = cache_control {
+            req = req.header(X_MS_CACHE_CONTROL, v)
+        }
+        if let Some(v) = parse_content_md5(current)? {
+            req = req.header(X_MS_CONTENT_MD5, v)
+        }
+        match props.user_metadata() {
+            Some(v) => req = req.header(X_MS_PROPERTIES, format_properties(v)),
+            None => {
+                if let Some(v) = parse_header_to_str(current, X_MS_PROPERTIES)? {
+                    req = req.header(X_MS_PROPERTIES, v)
+                }
+            }
+        }
+        if let Some(v) = parse_etag(current)? {
+            req = req.header(IF_MATCH, v)
+        }
+        if let Some(v) = props.lock_token() {
+            req = req.header(X_MS_LEASE_ID, v)
+        }
+
+        req.body(Buffer::new()).map_err(new_request_build_error)
+    }
+
+    /// Access tier is not exposed by the dfs endpoint, we use the blob
+    /// service's set blob tier API instead.
+    ///
+    /// Moving a blob out of `Archive` starts a rehydration that can take
+    /// hours, the service returns `202 Accepted` for it.
+    ///
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/set-blob-tier
+    pub async fn azdls_set_access_tier(
+        &self,
+        path: &str,
+        tier: &str,
+        rehydrate_priority: Option<&str>,
+    ) -> Result<()> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}?comp=tier",
+            self.blob_filesystem_url(),
+            percent_encode_path(&p)
+        );
+
+        let mut req = Request::put(&url)
+            .header(X_MS_ACCESS_TIER, tier)
+            .header(CONTENT_LENGTH, 0);
+        if let Some(v) = rehydrate_priority {
+            req = req.header(X_MS_REHYDRATE_PRIORITY, v);
+        }
+        let mut req = req.body(Buffer::new()).map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        let resp = self.send(req).await?;
+
+        match resp.status() {
+            StatusCode::OK | StatusCode::ACCEPTED => Ok(()),
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    /// Make sure the directory is visible via a follow-up get properties.
+    pub async fn azdls_confirm_directory(&self, path: &str) -> Result<()> {
+        let resp = self.azdls_get_properties(path, &OpStat::new()).await?;
+
+        match resp.status() {
+            StatusCode::OK => {}
+            // The directory is not visible yet, retrying it is ok.
+            StatusCode::NOT_FOUND => {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "directory is created but not visible yet",
+                )
+                .with_operation("AzdlsCore::azdls_confirm_directory")
+                .with_context("path", path)
+                .set_temporary())
+            }
+            _ => return Err(parse_error(resp).await?),
+        }
+
+        match parse_header_to_str(resp.headers(), "x-ms-resource-type")? {
+            Some("directory") => Ok(()),
+            v => Err(Error::new(
+                ErrorKind::NotADirectory,
+                "path exists but is not reported as a directory",
+            )
+            .with_operation("AzdlsCore::azdls_confirm_directory")
+            .with_context("path", path)
+            .with_context("resource_type", v.unwrap_or("<missing>"))),
+        }
+    }
+
+    fn azdls_lease_request(&self, path: &str, action: &str) -> http::request::Builder {
+        let p = build_abs_path(&self.root, path)
+            .trim_end_matches('/')
+            .to_string();
+
+        let url = format!("{}/{}", self.filesystem_url(), percent_encode_path(&p));
+
+        Request::post(&url)
+            .header(X_MS_LEASE_ACTION, action)
+            .header(CONTENT_LENGTH, 0)
+    }
+
+    /// Acquire a lease on the path, returns the lease id.
+    ///
+    /// The lease never expires if `duration` is `None`.
+    ///
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/datalakestoragegen2/path/lease
+    pub async fn azdls_acquire_lease(
+        &self,
+        path: &str,
+        duration: Option<Duration>,
+    ) -> Result<String> {
+        let mut req = self
+            .azdls_lease_request(path, "acquire")
+            .header(X_MS_LEASE_DURATION, format_lease_duration(duration))
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        let resp = self.send(req).await?;
+
+        match resp.status() {
+            StatusCode::CREATED | StatusCode::OK => {}
+            _ => return Err(parse_error(resp).await?),
+        }
+
+        parse_header_to_str(resp.headers(), X_MS_LEASE_ID)?
+            .map(|v| v.to_string())
+            .ok_or_else(|| {
+                Error::new(ErrorKind::Unexpected, "lease id is missing in response")
+                    .with_operation("AzdlsCore::azdls_acquire_lease")
+                    .with_context("path", path)
+            })
+    }
+
+    /// Renew the lease on the path, the lease duration will be reset.
+    pub async fn azdls_renew_lease(&self, path: &str, lease_id: &str) -> Result<()> {
+        let mut req = self
+            .azdls_lease_request(path, "renew")
+            .header(X_MS_LEASE_ID, lease_id)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        let resp = self.send(req).await?;
+
+        match resp.status() {
+            StatusCode::OK => Ok(()),
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    /// Release the lease on the path so that others can acquire it.
+    pub async fn azdls_release_lease(&self, path: &str, lease_id: &str) -> Result<()> {
+        let mut req = self
+            .azdls_lease_request(path, "release")
+            .header(X_MS_LEASE_ID, lease_id)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        let resp = self.send(req).await?;
+
+        match resp.status() {
+            StatusCode::OK => Ok(()),
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    pub async fn azdls_delete(
+        &self,
+        path: &str,
+        recursive: bool,
+        lease_id: Option<&str>,
+    ) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path)
+            .trim_end_matches('/')
+            .to_string();
+
+        let mut url = format!("{}/{}", self.filesystem_url(), percent_encode_path(&p));
+        if recursive {
+            url.push_str("?recursive=true");
+        }
+
         let req = Request::delete(&url);
 
         let mut req = req.body(Buffer::new()).map_err(new_request_build_error)?;
+        insert_lease_id(&mut req, lease_id)?;
 
         self.sign(&mut req).await?;
         self.send(req).await
     }
 
+    /// Create the filesystem if it doesn't exist.
+    ///
+    /// The creation happens at most once, concurrent callers will wait for
+    /// the same attempt. If the attempt fails, the next caller will try
+    /// again.
+    pub async fn azdls_ensure_filesystem(&self) -> Result<()> {
+        if !self.create_filesystem_if_not_exists {
+            return Ok(());
+        }
+
+        self.filesystem_created
+            .get_or_try_init(|| self.azdls_create_filesystem())
+            .await?;
+        Ok(())
+    }
+
+    async fn azdls_create_filesystem(&self) -> Result<()> {
+        let url = format!("{}?resource=filesystem", self.filesystem_url());
+
+        let mut req = Request::put(&url)
+            .header(CONTENT_LENGTH, 0)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        let resp = self.send(req).await?;
+
+        match resp.status() {
+            StatusCode::CREATED => Ok(()),
+            StatusCode::CONFLICT
+                if resp
+                    .headers()
+                    .get("x-ms-error-code")
+                    .is_some_and(|v| v == "FilesystemAlreadyExists") =>
+            {
+                Ok(())
+            }
+            _ => Err(parse_error(resp)
+                .await?
+                .with_operation("AzdlsCore::azdls_create_filesystem")),
+        }
+    }
+
     pub async fn azdls_list(
         &self,
         path: &str,
         continuation: &str,
+        recursive: bool,
         limit: Option<usize>,
     ) -> Result<Response<Buffer>> {
         let p = build_abs_path(&self.root, path)
@@ -266,8 +1277,8 @@ impl AzdlsCore {
             .to_string();
 
         let mut url = format!(
-            "{}/{}?resource=filesystem&recursive=false",
-            self.endpoint, self.filesystem
+            "{}?resource=filesystem&recursive={recursive}",
+            self.filesystem_url()
         );
         if !p.is_empty() {
             write!(url, "&directory={}", percent_encode_path(&p))
@@ -289,6 +1300,31 @@ impl AzdlsCore {
         self.send(req).await
     }
 
+    /// Versions and snapshots are not exposed by the dfs endpoint, we use the
+    /// blob service's list blobs API instead.
+    ///
+    /// ref: https://learn.microsoft.com/en-us/rest/api/storageservices/list-blobs
+    pub async fn azdls_list_versions(&self, path: &str, marker: &str) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = format!(
+            "{}?restype=container&comp=list&include=snapshots,versions&prefix={}",
+            self.blob_filesystem_url(),
+            percent_encode_path(&p)
+        );
+        if !marker.is_empty() {
+            write!(url, "&marker={}", percent_encode_path(marker))
+                .expect("write into string must succeed");
+        }
+
+        let mut req = Request::get(&url)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut req).await?;
+        self.send(req).await
+    }
+
     pub async fn azdls_ensure_parent_path(&self, path: &str) -> Result<Option<Response<Buffer>>> {
         let abs_target_path = path.trim_end_matches('/').to_string();
         let abs_target_path = abs_target_path.as_str();
@@ -318,3 +1354,248 @@ impl AzdlsCore {
         }
     }
 }
+
+/// Body of get/set blob tags.
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename = "Tags", rename_all = "PascalCase")]
+pub struct Tags {
+    pub tag_set: TagSet,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct TagSet {
+    pub tag: Vec<Tag>,
+}
+
+#[derive(Default, Debug, Serialize, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct Tag {
+    pub key: String,
+    pub value: String,
+}
+
+impl From<&HashMap<String, String>> for Tags {
+    fn from(tags: &HashMap<String, String>) -> Self {
+        Tags {
+            tag_set: TagSet {
+                tag: tags
+                    .iter()
+                    .map(|(key, value)| Tag {
+                        key: key.clone(),
+                        value: value.clone(),
+                    })
+                    .collect(),
+            },
+        }
+    }
+}
+
+impl From<Tags> for HashMap<String, String> {
+    fn from(tags: Tags) -> Self {
+        tags.tag_set
+            .tag
+            .into_iter()
+            .map(|t| (t.key, t.value))
+            .collect()
+    }
+}
+
+/// Pick the AAD error code (like `AADSTS700016`) out of the token
+/// acquisition error.
+///
+/// The whole message is not returned on purpose: it could contain the
+/// response body which we don't want to expose in context.
+fn parse_aad_error_code(msg: &str) -> Option<String> {
+    let start = msg.find("AADSTS")?;
+    let code: String = msg[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric())
+        .collect();
+    Some(code)
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::DateTime;
+    use chrono::Utc;
+
+    use super::*;
+
+    #[test]
+    fn test_format_version_query() {
+        assert_eq!(format_version_query(None, None), None);
+        assert_eq!(
+            format_version_query(Some("2024-01-01T00:00:00.1234567Z"), None).as_deref(),
+            Some("versionid=2024-01-01T00%3A00%3A00.1234567Z")
+        );
+        assert_eq!(
+            format_version_query(None, Some("2024-01-01T00:00:00.1234567Z")).as_deref(),
+            Some("snapshot=2024-01-01T00%3A00%3A00.1234567Z")
+        );
+    }
+
+    #[test]
+    fn test_parse_aad_error_code() {
+        let msg = r#"Failed to get token from workload identity credential, rsp_status = 400 Bad Request, rsp_body = {"error":"unauthorized_client","error_description":"AADSTS700016: Application with identifier 'xxx' was not found."}"#;
+        assert_eq!(parse_aad_error_code(msg), Some("AADSTS700016".to_string()));
+        assert_eq!(
+            parse_aad_error_code("Failed to get token from IMDS endpoint"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_redact_uri() {
+        let uri: Uri = "https://account.dfs.core.windows.net/fs/a%20b%2Bc?resource=file&sv=2022-11-02&sig=abc%2Fdef"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            redact_uri(&uri),
+            "https://account.dfs.core.windows.net/fs/a%20b%2Bc?resource=file&sv=2022-11-02&sig=<redacted>"
+        );
+
+        let uri: Uri = "https://account.dfs.core.windows.net/fs/a".parse().unwrap();
+        assert_eq!(
+            redact_uri(&uri),
+            "https://account.dfs.core.windows.net/fs/a"
+        );
+    }
+
+    #[test]
+    fn test_append_extra_query() {
+        let uri: Uri = "https://account.dfs.core.windows.net/fs/a".parse().unwrap();
+        assert_eq!(
+            append_extra_query(&uri, "x=1&y=a%20b").unwrap().to_string(),
+            "https://account.dfs.core.windows.net/fs/a?x=1&y=a%20b"
+        );
+
+        // Parameters set by opendal are kept, and appending again is a no-op.
+        let uri: Uri = "https://account.dfs.core.windows.net/fs/a?resource=file&x=1"
+            .parse()
+            .unwrap();
+        let uri = append_extra_query(&uri, "resource=directory&x=2&y=3").unwrap();
+        assert_eq!(
+            uri.to_string(),
+            "https://account.dfs.core.windows.net/fs/a?resource=file&x=1&y=3"
+        );
+        assert_eq!(append_extra_query(&uri, "y=3").unwrap(), uri);
+    }
+
+    #[test]
+    fn test_properties() {
+        let metadata = HashMap::from([
+            ("location".to_string(), "Hangzhou, China".to_string()),
+            ("owner".to_string(), "opendal".to_string()),
+        ]);
+
+        let v = format_properties(&metadata);
+        assert_eq!(v, "location=SGFuZ3pob3UsIENoaW5h,owner=b3BlbmRhbA==");
+        assert_eq!(parse_properties(&v).unwrap(), metadata);
+        assert!(parse_properties("").unwrap().is_empty());
+        assert!(parse_properties("owner").is_err());
+    }
+
+    #[test]
+    fn test_resolve_redirect() {
+        let uri: Uri = "https://account.dfs.core.windows.net/fs/a?x=1"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            resolve_redirect(&uri, "/fs/b").unwrap().to_string(),
+            "https://account.dfs.core.windows.net/fs/b"
+        );
+        assert_eq!(
+            resolve_redirect(&uri, "https://account-secondary.dfs.core.windows.net/fs/a")
+                .unwrap()
+                .host(),
+            Some("account-secondary.dfs.core.windows.net")
+        );
+        assert!(resolve_redirect(&uri, "https://exa mple.com").is_err());
+    }
+
+    #[test]
+    fn test_build_expiry() {
+        assert_eq!(build_expiry(&OpWrite::default()), None);
+
+        let args = OpWrite::default().with_expires_in(Duration::from_secs(90));
+        assert_eq!(
+            build_expiry(&args),
+            Some(("RelativeToNow", "90000".to_string()))
+        );
+
+        let t = DateTime::parse_from_rfc3339("2024-01-02T03:04:05Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let args = OpWrite::default().with_expires_at(t);
+        assert_eq!(
+            build_expiry(&args),
+            Some(("Absolute", "Tue, 02 Jan 2024 03:04:05 GMT".to_string()))
+        );
+
+        // The latter one wins.
+        let args = args.with_expires_in(Duration::from_secs(1));
+        assert_eq!(build_expiry(&args).unwrap().0, "RelativeToNow");
+    }
+
+    #[test]
+    fn test_format_lease_duration() {
+        assert_eq!(format_lease_duration(None), "-1");
+        assert_eq!(format_lease_duration(Some(Duration::from_secs(30))), "30");
+    }
+
+    #[test]
+    fn test_tags_xml() {
+        let body = r#"<?xml version="1.0" encoding="utf-8"?>
+<Tags>
+    <TagSet>
+        <Tag>
+            <Key>project</Key>
+            <Value>opendal</Value>
+        </Tag>
+        <Tag>
+            <Key>env</Key>
+            <Value>test</Value>
+        </Tag>
+    </TagSet>
+</Tags>"#;
+        let tags: Tags = quick_xml::de::from_str(body).expect("must succeed");
+        let tags: HashMap<String, String> = tags.into();
+        assert_eq!(tags.len(), 2);
+        assert_eq!(tags["project"], "opendal");
+        assert_eq!(tags["env"], "test");
+
+        let empty: Tags = quick_xml::de::from_str("<Tags><TagSet/></Tags>").expect("must succeed");
+        assert!(HashMap::from(empty).is_empty());
+
+        let tags = HashMap::from([("project".to_string(), "opendal".to_string())]);
+        let actual = quick_xml::se::to_string(&Tags::from(&tags)).expect("must succeed");
+        assert_eq!(
+            actual,
+            "<Tags><TagSet><Tag><Key>project</Key><Value>opendal</Value></Tag></TagSet></Tags>"
+        );
+    }
+
+    #[test]
+    fn test_is_idempotent() {
+        let req = |method: Method, header: Option<HeaderName>| {
+            let mut req = Request::builder()
+                .method(method)
+                .uri("https://example.com/fs/a");
+            if let Some(name) = header {
+                req = req.header(name, "*");
+            }
+            req.body(Buffer::new()).unwrap()
+        };
+
+        assert!(is_idempotent(&req(Method::GET, None)));
+        assert!(is_idempotent(&req(Method::HEAD, None)));
+        assert!(is_idempotent(&req(Method::DELETE, None)));
+        assert!(is_idempotent(&req(Method::PUT, Some(IF_NONE_MATCH))));
+        assert!(is_idempotent(&req(Method::PUT, Some(IF_MATCH))));
+        assert!(!is_idempotent(&req(Method::PUT, None)));
+        assert!(!is_idempotent(&req(Method::PATCH, None)));
+        assert!(!is_idempotent(&req(Method::POST, None)));
+    }
+}
@@ -23,6 +23,7 @@ use http::StatusCode;
 use quick_xml::de;
 use serde::Deserialize;
 
+use super::core::X_MS_CLIENT_REQUEST_ID;
 use crate::raw::*;
 use crate::*;
 
@@ -66,7 +67,10 @@ pub async fn parse_error(resp: Response<Buffer>) -> Result<Error> {
     let (kind, retryable) = match parts.status {
         StatusCode::NOT_FOUND => (ErrorKind::NotFound, false),
         StatusCode::FORBIDDEN => (ErrorKind::PermissionDenied, false),
-        StatusCode::PRECONDITION_FAILED => (ErrorKind::ConditionNotMatch, false),
+        StatusCode::PRECONDITION_FAILED | StatusCode::NOT_MODIFIED => {
+            (ErrorKind::ConditionNotMatch, false)
+        }
+        StatusCode::TOO_MANY_REQUESTS => (ErrorKind::RateLimited, true),
         StatusCode::INTERNAL_SERVER_ERROR
         | StatusCode::BAD_GATEWAY
         | StatusCode::SERVICE_UNAVAILABLE
@@ -74,6 +78,23 @@ pub async fn parse_error(resp: Response<Buffer>) -> Result<Error> {
         _ => (ErrorKind::Unexpected, false),
     };
 
+    let kind = match parts.headers.get("x-ms-error-code") {
+        // The position of append doesn't match the committed position.
+        Some(v) if v == "InvalidFlushPosition" => ErrorKind::ConditionNotMatch,
+        // The path has been leased by others.
+        Some(v) if v == "LeaseAlreadyPresent" => ErrorKind::ConditionNotMatch,
+        // The path is leased but the request carries no or another lease id.
+        Some(v) if is_lease_mismatch(v.as_bytes()) => ErrorKind::Locked,
+        // The file is in archive tier or still being rehydrated.
+        Some(v) if v == "BlobArchived" || v == "BlobBeingRehydrated" => ErrorKind::Archived,
+        // The filesystem doesn't exist, it's a config issue instead of the path not found.
+        Some(v) if v == "FilesystemNotFound" => ErrorKind::ConfigInvalid,
+        // The dfs endpoint can't serve this operation for the account.
+        Some(v) if v == "EndpointUnsupportedAccountFeatures" => ErrorKind::Unsupported,
+        _ if parts.status == StatusCode::NOT_IMPLEMENTED => ErrorKind::Unsupported,
+        _ => kind,
+    };
+
     let mut message = match de::from_reader::<_, AzdlsError>(bs.clone().reader()) {
         Ok(azdls_err) => format!("{azdls_err:?}"),
         Err(_) => String::from_utf8_lossy(&bs).into_owned(),
@@ -93,13 +114,279 @@ pub async fn parse_error(resp: Response<Buffer>) -> Result<Error> {
         }
     }
 
+    let code = parts
+        .headers
+        .get("x-ms-error-code")
+        .and_then(|v| v.to_str().ok());
+    let body = String::from_utf8_lossy(&bs);
+    let sas_expired = parts.status == StatusCode::FORBIDDEN && is_sas_expired(code, &body);
+    let network_blocked =
+        parts.status == StatusCode::FORBIDDEN && is_blocked_by_network_rules(code, &body);
+    let rehydrating = code == Some("BlobBeingRehydrated");
+    let lease_not_present = code.is_some_and(|v| v.starts_with("LeaseNotPresent"));
+
     let mut err = Error::new(kind, &message);
 
+    // Azure echoes the client request id back, attach it so that the error
+    // can be correlated with the caller's traces.
+    if let Some(v) = parts
+        .headers
+        .get(X_MS_CLIENT_REQUEST_ID)
+        .and_then(|v| v.to_str().ok())
+    {
+        err = err.with_context("client_request_id", v);
+    }
+
     err = with_error_response_context(err, parts);
 
+    // Expired SAS token needs to be refreshed instead of fixing permissions.
+    if sas_expired {
+        err = err.with_context("reason", "sas token expired, please refresh the token");
+    }
+    // Requests denied by the firewall look like credential failures, but
+    // rotating keys won't help.
+    if network_blocked {
+        err = err.with_context(
+            "reason",
+            "blocked by network rules, please check the firewall and virtual network settings of the storage account",
+        );
+    }
+
+    // Archived files will never be readable by retrying, they must be
+    // rehydrated to an online tier first.
+    if kind == ErrorKind::Archived {
+        err = err.with_context(
+            "reason",
+            if rehydrating {
+                "file is being rehydrated from archive tier, please read it after the rehydration finished, which may take up to 15 hours"
+            } else {
+                "file is in archive tier, please rehydrate it to an online tier via set_access_tier before reading"
+            },
+        );
+    }
+
+    // Operations on a leased path must carry the active lease id.
+    if kind == ErrorKind::Locked {
+        err = err.with_context(
+            "reason",
+            if lease_not_present {
+                "lease is not active, it may have expired or been released, please acquire it again"
+            } else {
+                "path is leased, please set the active lease id via lock_token"
+            },
+        );
+    }
+
     if retryable {
         err = err.set_temporary();
     }
 
     Ok(err)
 }
+
+/// Check if the error is caused by a missing or mismatched lease id.
+///
+/// Azure returns `412 Precondition Failed` with codes like `LeaseIdMissing`,
+/// `LeaseIdMismatchWithBlobOperation` or `LeaseNotPresentWithBlobOperation`.
+fn is_lease_mismatch(code: &[u8]) -> bool {
+    code == b"LeaseIdMissing"
+        || code.starts_with(b"LeaseIdMismatch")
+        || code.starts_with(b"LeaseNotPresent")
+}
+
+/// Check if the error is caused by an expired SAS token.
+///
+/// Azure returns `AuthenticationFailed` with detail like `Signed expiry time
+/// [...] must be after signed start time` or `Signature not valid in the
+/// specified time frame` for expired SAS tokens. HEAD requests don't carry
+/// a body, so we can't tell for them.
+fn is_sas_expired(code: Option<&str>, body: &str) -> bool {
+    if code.is_some_and(|v| v != "AuthenticationFailed") {
+        return false;
+    }
+
+    body.contains("Signature not valid in the specified time frame")
+        || body.contains("Signed expiry time")
+}
+
+/// Check if the request is blocked by the storage account's IP or VNet
+/// firewall.
+///
+/// Azure returns `AuthorizationFailure` with `This request is not authorized
+/// to perform this operation.` for such requests, while bad credentials are
+/// reported as `AuthenticationFailed` and missing RBAC permissions as
+/// `AuthorizationPermissionMismatch`.
+fn is_blocked_by_network_rules(code: Option<&str>, body: &str) -> bool {
+    match code {
+        Some(v) => v == "AuthorizationFailure",
+        // Fallback to the body if the error code header is missing.
+        None => body.contains("AuthorizationFailure"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_sas_expired() {
+        let body = r#"<?xml version="1.0" encoding="utf-8"?><Error><Code>AuthenticationFailed</Code><Message>Server failed to authenticate the request.</Message><AuthenticationErrorDetail>Signature not valid in the specified time frame: Start [Mon, 01 Jan 2024 00:00:00 GMT] - Expiry [Mon, 01 Jan 2024 01:00:00 GMT] - Current [Mon, 01 Jan 2024 02:00:00 GMT]</AuthenticationErrorDetail></Error>"#;
+        assert!(is_sas_expired(Some("AuthenticationFailed"), body));
+
+        let body = r#"{"error":{"code":"AuthenticationFailed","message":"Server failed to authenticate the request. Signature not valid in the specified time frame"}}"#;
+        assert!(is_sas_expired(Some("AuthenticationFailed"), body));
+
+        let body = r#"{"error":{"code":"AuthorizationPermissionMismatch","message":"This request is not authorized to perform this operation using this permission."}}"#;
+        assert!(!is_sas_expired(
+            Some("AuthorizationPermissionMismatch"),
+            body
+        ));
+        assert!(!is_sas_expired(Some("AuthenticationFailed"), ""));
+    }
+
+    #[test]
+    fn test_is_blocked_by_network_rules() {
+        let body = r#"<?xml version="1.0" encoding="utf-8"?><Error><Code>AuthorizationFailure</Code><Message>This request is not authorized to perform this operation.</Message></Error>"#;
+        assert!(is_blocked_by_network_rules(
+            Some("AuthorizationFailure"),
+            body
+        ));
+        assert!(is_blocked_by_network_rules(None, body));
+        assert!(is_blocked_by_network_rules(
+            Some("AuthorizationFailure"),
+            ""
+        ));
+
+        let body = r#"{"error":{"code":"AuthenticationFailed","message":"Server failed to authenticate the request."}}"#;
+        assert!(!is_blocked_by_network_rules(
+            Some("AuthenticationFailed"),
+            body
+        ));
+        assert!(!is_blocked_by_network_rules(None, ""));
+    }
+
+    #[tokio::test]
+    async fn test_parse_error_network_blocked() {
+        let resp = Response::builder()
+            .status(StatusCode::FORBIDDEN)
+            .header("x-ms-error-code", "AuthorizationFailure")
+            .body(Buffer::new())
+            .unwrap();
+
+        let err = parse_error(resp).await.unwrap();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        assert!(err.to_string().contains("blocked by network rules"));
+    }
+
+    #[tokio::test]
+    async fn test_parse_error_unsupported() {
+        let resp = Response::builder()
+            .status(StatusCode::CONFLICT)
+            .header("x-ms-error-code", "EndpointUnsupportedAccountFeatures")
+            .body(Buffer::new())
+            .unwrap();
+        let err = parse_error(resp).await.unwrap();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+
+        let resp = Response::builder()
+            .status(StatusCode::NOT_IMPLEMENTED)
+            .body(Buffer::new())
+            .unwrap();
+        let err = parse_error(resp).await.unwrap();
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+    }
+
+    #[tokio::test]
+    async fn test_parse_error_filesystem_not_found() {
+        let resp = Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .header("x-ms-error-code", "FilesystemNotFound")
+            .body(Buffer::new())
+            .unwrap();
+        let err = parse_error(resp).await.unwrap();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        let resp = Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .header("x-ms-error-code", "PathNotFound")
+            .body(Buffer::new())
+            .unwrap();
+        let err = parse_error(resp).await.unwrap();
+        assert_eq!(err.kind(), ErrorKind::NotFound);
+    }
+
+    #[tokio::test]
+    async fn test_parse_error_lease_already_present() {
+        let resp = Response::builder()
+            .status(StatusCode::CONFLICT)
+            .header("x-ms-error-code", "LeaseAlreadyPresent")
+            .body(Buffer::new())
+            .unwrap();
+        let err = parse_error(resp).await.unwrap();
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+    }
+
+    #[tokio::test]
+    async fn test_parse_error_archived() {
+        let resp = Response::builder()
+            .status(StatusCode::CONFLICT)
+            .header("x-ms-error-code", "BlobArchived")
+            .body(Buffer::new())
+            .unwrap();
+        let err = parse_error(resp).await.unwrap();
+        assert_eq!(err.kind(), ErrorKind::Archived);
+        assert!(!err.is_temporary());
+        assert!(err.to_string().contains("set_access_tier"));
+
+        let resp = Response::builder()
+            .status(StatusCode::CONFLICT)
+            .header("x-ms-error-code", "BlobBeingRehydrated")
+            .body(Buffer::new())
+            .unwrap();
+        let err = parse_error(resp).await.unwrap();
+        assert_eq!(err.kind(), ErrorKind::Archived);
+        assert!(err.to_string().contains("being rehydrated"));
+    }
+
+    #[tokio::test]
+    async fn test_parse_error_lease_mismatch() {
+        let resp = Response::builder()
+            .status(StatusCode::PRECONDITION_FAILED)
+            .header("x-ms-error-code", "LeaseIdMissing")
+            .body(Buffer::new())
+            .unwrap();
+        let err = parse_error(resp).await.unwrap();
+        assert_eq!(err.kind(), ErrorKind::Locked);
+        assert!(err.to_string().contains("lock_token"));
+
+        let resp = Response::builder()
+            .status(StatusCode::PRECONDITION_FAILED)
+            .header("x-ms-error-code", "LeaseNotPresentWithBlobOperation")
+            .body(Buffer::new())
+            .unwrap();
+        let err = parse_error(resp).await.unwrap();
+        assert_eq!(err.kind(), ErrorKind::Locked);
+        assert!(err.to_string().contains("lease is not active"));
+
+        // Other precondition failures are kept as is.
+        let resp = Response::builder()
+            .status(StatusCode::PRECONDITION_FAILED)
+            .header("x-ms-error-code", "ConditionNotMet")
+            .body(Buffer::new())
+            .unwrap();
+        let err = parse_error(resp).await.unwrap();
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+    }
+
+    #[tokio::test]
+    async fn test_parse_error_client_request_id() {
+        let resp = Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .header(X_MS_CLIENT_REQUEST_ID, "trace-1234")
+            .body(Buffer::new())
+            .unwrap();
+
+        let err = parse_error(resp).await.unwrap();
+        assert!(err.to_string().contains("client_request_id: trace-1234"));
+    }
+}
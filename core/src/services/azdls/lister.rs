@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::VecDeque;
 use std::sync::Arc;
 
 use bytes::Buf;
@@ -26,16 +27,37 @@ use super::error::parse_error;
 use crate::raw::*;
 use crate::*;
 
+/// AzdlsLister fetches one page per `next_page` call.
+///
+/// It never fetches ahead, the next page will only be requested after the
+/// entries of current page have been consumed.
 pub struct AzdlsLister {
     core: Arc<AzdlsCore>,
 
     path: String,
+    /// Only entries starting with this prefix will be returned.
+    prefix: Option<String>,
+    recursive: bool,
     limit: Option<usize>,
 }
 
 impl AzdlsLister {
-    pub fn new(core: Arc<AzdlsCore>, path: String, limit: Option<usize>) -> Self {
-        Self { core, path, limit }
+    pub fn new(core: Arc<AzdlsCore>, path: String, recursive: bool, limit: Option<usize>) -> Self {
+        // Azdls can only list directories, paths that are not directories
+        // are listed from their parent and filtered by prefix instead.
+        let (path, prefix) = if path.ends_with('/') {
+            (path, None)
+        } else {
+            (get_parent(&path).to_string(), Some(path))
+        };
+
+        Self {
+            core,
+            path,
+            prefix,
+            recursive,
+            limit,
+        }
     }
 }
 
@@ -43,7 +65,7 @@ impl oio::PageList for AzdlsLister {
     async fn next_page(&self, ctx: &mut oio::PageContext) -> Result<()> {
         let resp = self
             .core
-            .azdls_list(&self.path, &ctx.token, self.limit)
+            .azdls_list(&self.path, &ctx.token, self.recursive, self.limit)
             .await?;
 
         // azdls will return not found for not-exist path.
@@ -56,18 +78,9 @@ impl oio::PageList for AzdlsLister {
             return Err(parse_error(resp).await?);
         }
 
-        // Check whether this list is done.
-        if let Some(value) = resp.headers().get("x-ms-continuation") {
-            let value = value.to_str().map_err(|err| {
-                Error::new(ErrorKind::Unexpected, "header value is not valid string")
-                    .set_source(err)
-            })?;
-            ctx.token = value.to_string();
-        } else {
-            ctx.token = "".to_string();
-            ctx.done = true;
-        }
-
+        let token = parse_header_to_str(resp.headers(), "x-ms-continuation")?
+            .unwrap_or_default()
+            .to_string();
         let bs = resp.into_body();
 
         let output: Output = de::from_reader(bs.reader()).map_err(new_json_deserialize_error)?;
@@ -79,6 +92,9 @@ impl oio::PageList for AzdlsLister {
             } else {
                 EntryMode::FILE
             };
+            if mode == EntryMode::DIR && self.core.skip_list_dirs {
+                continue;
+            }
 
             let meta = Metadata::new(mode)
                 // Keep fit with ETag header.
@@ -93,16 +109,276 @@ impl oio::PageList for AzdlsLister {
             if mode == EntryMode::DIR {
                 path += "/"
             };
+            if let Some(prefix) = &self.prefix {
+                if !matches!(path.strip_prefix(prefix.as_str()), Some(v) if !v.is_empty()) {
+                    continue;
+                }
+            }
 
             let de = oio::Entry::new(&path, meta);
 
             ctx.entries.push_back(de);
         }
 
+        // Only advance the token after the whole page is parsed, so that a
+        // failed page can be fetched again with the same token.
+        ctx.done = token.is_empty();
+        ctx.token = token;
+
         Ok(())
     }
 }
 
+/// AzdlsPageLister lists entries page by page and exposes the continuation
+/// token, which allows to checkpoint and resume a long listing.
+///
+/// Created by [`AzdlsBackend::list_pages`](super::AzdlsBackend::list_pages).
+pub struct AzdlsPageLister {
+    lister: AzdlsLister,
+    ctx: oio::PageContext,
+}
+
+impl AzdlsPageLister {
+    pub(super) fn new(lister: AzdlsLister, continuation_token: Option<&str>) -> Self {
+        Self {
+            lister,
+            ctx: oio::PageContext {
+                done: false,
+                token: continuation_token.unwrap_or_default().to_string(),
+                entries: VecDeque::new(),
+            },
+        }
+    }
+
+    /// Fetch the next page of entries.
+    ///
+    /// Returns `None` if all pages have been fetched. The page could be empty
+    /// while the listing is not done yet.
+    pub async fn next_page(&mut self) -> Result<Option<Vec<Entry>>> {
+        if self.ctx.done {
+            return Ok(None);
+        }
+
+        self.ctx.entries.clear();
+        oio::PageList::next_page(&self.lister, &mut self.ctx).await?;
+        Ok(Some(
+            self.ctx.entries.drain(..).map(|e| e.into_entry()).collect(),
+        ))
+    }
+
+    /// The token to resume the listing after the last fetched page.
+    ///
+    /// Returns `None` if the listing is done or no page has been fetched.
+    pub fn continuation_token(&self) -> Option<&str> {
+        if self.ctx.done || self.ctx.token.is_empty() {
+            None
+        } else {
+            Some(&self.ctx.token)
+        }
+    }
+
+    /// Sum up files in all remaining pages, directories are returned as is.
+    pub(super) async fn sum(mut self) -> Result<(AzdlsDirSize, Vec<String>)> {
+        let mut size = AzdlsDirSize::default();
+        let mut dirs = vec![];
+        while let Some(entries) = self.next_page().await? {
+            for entry in entries {
+                if entry.metadata().is_dir() {
+                    dirs.push(entry.path().to_string());
+                } else {
+                    size.bytes += entry.metadata().content_length();
+                    size.objects += 1;
+                }
+            }
+        }
+        Ok((size, dirs))
+    }
+}
+
+/// Total size of files under a directory, returned by
+/// [`AzdlsBackend::dir_size`][super::AzdlsBackend::dir_size].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AzdlsDirSize {
+    bytes: u64,
+    objects: u64,
+}
+
+impl AzdlsDirSize {
+    /// Total bytes of all files.
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    /// Count of files, directories are not counted.
+    pub fn objects(&self) -> u64 {
+        self.objects
+    }
+}
+
+impl std::ops::Add for AzdlsDirSize {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self {
+            bytes: self.bytes + rhs.bytes,
+            objects: self.objects + rhs.objects,
+        }
+    }
+}
+
+/// AzdlsVersionLister lists versions and snapshots of a file page by page.
+///
+/// Created by [`AzdlsBackend::list_versions`](super::AzdlsBackend::list_versions).
+pub struct AzdlsVersionLister {
+    core: Arc<AzdlsCore>,
+
+    path: String,
+    marker: String,
+    done: bool,
+}
+
+impl AzdlsVersionLister {
+    pub(super) fn new(
+        core: Arc<AzdlsCore>,
+        path: String,
+        continuation_token: Option<&str>,
+    ) -> Self {
+        Self {
+            core,
+            path,
+            marker: continuation_token.unwrap_or_default().to_string(),
+            done: false,
+        }
+    }
+
+    /// Fetch the next page of versions.
+    ///
+    /// Returns `None` if all pages have been fetched. The page could be empty
+    /// while the listing is not done yet.
+    pub async fn next_page(&mut self) -> Result<Option<Vec<AzdlsVersion>>> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let resp = self
+            .core
+            .azdls_list_versions(&self.path, &self.marker)
+            .await?;
+
+        if resp.status() != http::StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let output: VersionsOutput = quick_xml::de::from_reader(resp.into_body().reader())
+            .map_err(new_xml_deserialize_error)?;
+
+        let abs_path = build_abs_path(&self.core.root, &self.path);
+        let mut versions = Vec::with_capacity(output.blobs.blob.len());
+        for blob in output.blobs.blob {
+            // The listing is by prefix, skip other files that start with the path.
+            if blob.name != abs_path {
+                continue;
+            }
+            versions.push(blob.try_into()?);
+        }
+
+        // Only advance the marker after the whole page is parsed, so that a
+        // failed page can be fetched again with the same marker.
+        let marker = output.next_marker.unwrap_or_default();
+        self.done = marker.is_empty();
+        self.marker = marker;
+
+        Ok(Some(versions))
+    }
+
+    /// The token to resume the listing after the last fetched page.
+    ///
+    /// Returns `None` if the listing is done or no page has been fetched.
+    pub fn continuation_token(&self) -> Option<&str> {
+        if self.done || self.marker.is_empty() {
+            None
+        } else {
+            Some(&self.marker)
+        }
+    }
+}
+
+/// A version or snapshot of a file, returned by [`AzdlsVersionLister`].
+#[derive(Debug, Clone)]
+pub struct AzdlsVersion {
+    version_id: Option<String>,
+    snapshot: Option<String>,
+    is_current: bool,
+    metadata: Metadata,
+}
+
+impl AzdlsVersion {
+    /// The version id, could be passed to `read_with().version()`.
+    ///
+    /// Returns `None` if versioning is not enabled on the account.
+    pub fn version_id(&self) -> Option<&str> {
+        self.version_id.as_deref()
+    }
+
+    /// The snapshot id, could be passed to `read_with().snapshot()`.
+    ///
+    /// Returns `None` if this is not a snapshot.
+    pub fn snapshot(&self) -> Option<&str> {
+        self.snapshot.as_deref()
+    }
+
+    /// Whether this is the current version of the file.
+    pub fn is_current(&self) -> bool {
+        self.is_current
+    }
+
+    /// The metadata of this version.
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+}
+
+impl TryFrom<VersionBlob> for AzdlsVersion {
+    type Error = Error;
+
+    fn try_from(blob: VersionBlob) -> Result<Self> {
+        let p = blob.properties;
+        // The blob list response carries all the properties we know about.
+        let mut metadata = Metadata::new(EntryMode::FILE)
+            .with_metakey(Metakey::Complete)
+            .with_etag(p.etag)
+            .with_content_length(p.content_length)
+            .with_last_modified(parse_datetime_from_rfc2822(&p.last_modified)?);
+        if !p.content_type.is_empty() {
+            metadata.set_content_type(&p.content_type);
+        }
+        if !p.content_md5.is_empty() {
+            metadata.set_content_md5(&p.content_md5);
+        }
+        if let Some(v) = &blob.version_id {
+            metadata.set_version(v);
+        }
+
+        // A snapshot taken before versioning is enabled doesn't have a
+        // version id, and the current blob doesn't have a snapshot id.
+        let version_id = blob.version_id.filter(|v| !v.is_empty());
+        let snapshot = blob.snapshot.filter(|v| !v.is_empty());
+        // IsCurrentVersion is only returned for the current version. Without
+        // versioning, the blob that is not a snapshot is the current one.
+        let is_current = match (&version_id, blob.is_current_version) {
+            (_, Some(v)) => v,
+            (Some(_), None) => false,
+            (None, None) => snapshot.is_none(),
+        };
+        Ok(Self {
+            version_id,
+            snapshot,
+            is_current,
+            metadata,
+        })
+    }
+}
+
 /// # Examples
 ///
 /// ```json
@@ -130,6 +406,43 @@ struct Path {
     name: String,
 }
 
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct VersionsOutput {
+    blobs: VersionBlobs,
+    next_marker: Option<String>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct VersionBlobs {
+    blob: Vec<VersionBlob>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct VersionBlob {
+    name: String,
+    snapshot: Option<String>,
+    version_id: Option<String>,
+    is_current_version: Option<bool>,
+    properties: VersionProperties,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+struct VersionProperties {
+    #[serde(rename = "Content-Length")]
+    content_length: u64,
+    #[serde(rename = "Last-Modified")]
+    last_modified: String,
+    #[serde(rename = "Content-MD5")]
+    content_md5: String,
+    #[serde(rename = "Content-Type")]
+    content_type: String,
+    etag: String,
+}
+
 #[cfg(test)]
 mod tests {
     use bytes::Bytes;
@@ -156,4 +469,76 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn test_parse_versions() {
+        let bs = r#"<?xml version="1.0" encoding="utf-8"?>
+<EnumerationResults ServiceEndpoint="https://test.blob.core.windows.net/" ContainerName="test">
+  <Prefix>dir/a.txt</Prefix>
+  <Blobs>
+    <Blob>
+      <Name>dir/a.txt</Name>
+      <VersionId>2024-01-01T00:00:00.0000000Z</VersionId>
+      <Properties>
+        <Last-Modified>Mon, 01 Jan 2024 00:00:00 GMT</Last-Modified>
+        <Etag>0x8DC0A0000000001</Etag>
+        <Content-Length>3</Content-Length>
+        <Content-Type>text/plain</Content-Type>
+        <Content-MD5 />
+      </Properties>
+    </Blob>
+    <Blob>
+      <Name>dir/a.txt</Name>
+      <VersionId>2024-01-02T00:00:00.0000000Z</VersionId>
+      <IsCurrentVersion>true</IsCurrentVersion>
+      <Properties>
+        <Last-Modified>Tue, 02 Jan 2024 00:00:00 GMT</Last-Modified>
+        <Etag>0x8DC0A0000000002</Etag>
+        <Content-Length>5</Content-Length>
+      </Properties>
+    </Blob>
+    <Blob>
+      <Name>dir/a.txt</Name>
+      <Snapshot>2024-01-03T00:00:00.0000000Z</Snapshot>
+      <Properties>
+        <Last-Modified>Tue, 02 Jan 2024 00:00:00 GMT</Last-Modified>
+        <Etag>0x8DC0A0000000002</Etag>
+        <Content-Length>5</Content-Length>
+      </Properties>
+    </Blob>
+  </Blobs>
+  <NextMarker>marker</NextMarker>
+</EnumerationResults>"#;
+
+        let out: VersionsOutput = quick_xml::de::from_str(bs).expect("must success");
+        assert_eq!(out.next_marker.as_deref(), Some("marker"));
+
+        let versions = out
+            .blobs
+            .blob
+            .into_iter()
+            .map(AzdlsVersion::try_from)
+            .collect::<Result<Vec<_>>>()
+            .expect("must success");
+        assert_eq!(versions.len(), 3);
+
+        assert_eq!(
+            versions[0].version_id(),
+            Some("2024-01-01T00:00:00.0000000Z")
+        );
+        assert!(!versions[0].is_current());
+        assert_eq!(versions[0].metadata().content_length(), 3);
+        assert_eq!(versions[0].metadata().content_type(), Some("text/plain"));
+        assert_eq!(versions[0].metadata().content_md5(), None);
+
+        assert!(versions[1].is_current());
+        assert_eq!(
+            versions[1].metadata().version(),
+            Some("2024-01-02T00:00:00.0000000Z")
+        );
+
+        assert_eq!(versions[2].version_id(), None);
+        assert_eq!(versions[2].snapshot(), Some("2024-01-03T00:00:00.0000000Z"));
+        assert!(!versions[2].is_current());
+    }
 }
@@ -16,10 +16,18 @@
 // under the License.
 
 mod backend;
+pub use backend::AzdlsBackend;
 pub use backend::AzdlsBuilder as Azdls;
 pub use backend::AzdlsConfig;
+pub use backend::AzdlsProperties;
+pub use backend::AzdlsPropertiesExt;
 
 mod core;
 mod error;
 mod lister;
+pub use lister::AzdlsDirSize;
+pub use lister::AzdlsPageLister;
+pub use lister::AzdlsVersion;
+pub use lister::AzdlsVersionLister;
+mod reader;
 mod writer;
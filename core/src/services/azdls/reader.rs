@@ -0,0 +1,365 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+use std::io::Write;
+
+use flate2::write::GzDecoder;
+use flate2::write::ZlibDecoder;
+use http::header::CONTENT_ENCODING;
+use http::HeaderMap;
+
+use crate::raw::*;
+use crate::*;
+
+/// The content encodings that azdls can decompress.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContentEncoding {
+    Gzip,
+    Deflate,
+}
+
+impl ContentEncoding {
+    /// Parse content encoding from headers, returns `None` if the content
+    /// is not compressed or compressed in unknown encodings.
+    pub fn from_headers(headers: &HeaderMap) -> Result<Option<Self>> {
+        let Some(v) = parse_header_to_str(headers, CONTENT_ENCODING)? else {
+            return Ok(None);
+        };
+
+        let encoding = match v.trim().to_ascii_lowercase().as_str() {
+            "gzip" | "x-gzip" => Some(ContentEncoding::Gzip),
+            "deflate" => Some(ContentEncoding::Deflate),
+            _ => None,
+        };
+        Ok(encoding)
+    }
+}
+
+enum Decoder {
+    Gzip(GzDecoder<Vec<u8>>),
+    Deflate(ZlibDecoder<Vec<u8>>),
+}
+
+impl Decoder {
+    fn write_all(&mut self, bs: &[u8]) -> std::io::Result<()> {
+        match self {
+            Decoder::Gzip(d) => d.write_all(bs),
+            Decoder::Deflate(d) => d.write_all(bs),
+        }
+    }
+
+    fn finish(self) -> std::io::Result<Vec<u8>> {
+        match self {
+            Decoder::Gzip(d) => d.finish(),
+            Decoder::Deflate(d) => d.finish(),
+        }
+    }
+
+    /// Take the decompressed data out.
+    fn take(&mut self) -> Vec<u8> {
+        match self {
+            Decoder::Gzip(d) => std::mem::take(d.get_mut()),
+            Decoder::Deflate(d) => std::mem::take(d.get_mut()),
+        }
+    }
+}
+
+/// AzdlsDecompressReader decompresses the response body while reading.
+///
+/// The body must start from the beginning of the compressed content, only
+/// the decompressed bytes within `range` will be returned.
+pub struct AzdlsDecompressReader {
+    body: HttpBody,
+    decoder: Option<Decoder>,
+    /// Decompressed bytes to discard before the range.
+    skip: u64,
+    /// Decompressed bytes left to return, `None` means till the end.
+    remaining: Option<u64>,
+}
+
+impl AzdlsDecompressReader {
+    pub fn new(body: HttpBody, encoding: ContentEncoding, range: BytesRange) -> Self {
+        let decoder = match encoding {
+            ContentEncoding::Gzip => Decoder::Gzip(GzDecoder::new(Vec::new())),
+            ContentEncoding::Deflate => Decoder::Deflate(ZlibDecoder::new(Vec::new())),
+        };
+
+        AzdlsDecompressReader {
+            body,
+            decoder: Some(decoder),
+            skip: range.offset(),
+            remaining: range.size(),
+        }
+    }
+
+    /// Cut the decompressed bytes to the range.
+    fn cut(&mut self, mut bs: Vec<u8>) -> Vec<u8> {
+        let skip = self.skip.min(bs.len() as u64) as usize;
+        bs.drain(..skip);
+        self.skip -= skip as u64;
+
+        if let Some(remaining) = self.remaining.as_mut() {
+            let n = (*remaining).min(bs.len() as u64);
+            bs.truncate(n as usize);
+            *remaining -= n;
+        }
+        bs
+    }
+}
+
+fn new_decompress_error(err: std::io::Error) -> Error {
+    Error::new(ErrorKind::Unexpected, "failed to decompress content").set_source(err)
+}
+
+impl oio::Read for AzdlsDecompressReader {
+    async fn read(&mut self) -> Result<Buffer> {
+        loop {
+            // Stop fetching once the range has been returned.
+            if self.remaining == Some(0) {
+                self.decoder = None;
+            }
+            let Some(decoder) = self.decoder.as_mut() else {
+                return Ok(Buffer::new());
+            };
+
+            let buf = self.body.read().await?;
+            if buf.is_empty() {
+                let decoder = self.decoder.take().expect("decoder must be valid");
+                let bs = decoder.finish().map_err(new_decompress_error)?;
+                return Ok(Buffer::from(self.cut(bs)));
+            }
+
+            for bs in buf {
+                decoder.write_all(&bs).map_err(new_decompress_error)?;
+            }
+
+            let bs = decoder.take();
+            let bs = self.cut(bs);
+            if !bs.is_empty() {
+                return Ok(Buffer::from(bs));
+            }
+        }
+    }
+}
+
+/// AzdlsPrefetchReader reads ahead from the inner reader so that consumers
+/// reading small chunks don't wait for the network on every read.
+///
+/// Blocks of at least `prefetch_size` bytes are fetched in background by the
+/// executor: once a block has been returned, the next one starts fetching
+/// while the caller is consuming the current one. A `prefetch_size` of `0`
+/// disables prefetching.
+///
+/// The reader only moves forward, seeking outside of the returned buffer
+/// will drop this reader which cancels the ongoing prefetch as well.
+pub struct AzdlsPrefetchReader<R> {
+    executor: Executor,
+    prefetch_size: usize,
+    /// The inner reader, it's moved into `task` while prefetching.
+    inner: Option<R>,
+    task: Option<Task<(R, Result<Buffer>)>>,
+    eof: bool,
+}
+
+impl<R: oio::Read + 'static> AzdlsPrefetchReader<R> {
+    pub fn new(inner: R, prefetch_size: usize, executor: Executor) -> Self {
+        AzdlsPrefetchReader {
+            executor,
+            prefetch_size,
+            inner: Some(inner),
+            task: None,
+            eof: false,
+        }
+    }
+
+    /// Start fetching the next block in background.
+    fn prefetch(&mut self) {
+        let Some(mut inner) = self.inner.take() else {
+            return;
+        };
+        let size = self.prefetch_size;
+        self.task = Some(self.executor.execute(async move {
+            let res = read_block(&mut inner, size).await;
+            (inner, res)
+        }));
+    }
+}
+
+/// Keep pulling from the reader until at least `size` bytes are collected
+/// or the reader is exhausted.
+async fn read_block<R: oio::Read>(r: &mut R, size: usize) -> Result<Buffer> {
+    let mut bufs = vec![];
+    let mut total = 0;
+    while total < size {
+        let buf = r.read().await?;
+        if buf.is_empty() {
+            break;
+        }
+        total += buf.len();
+        bufs.push(buf);
+    }
+    Ok(bufs.into_iter().flatten().collect())
+}
+
+impl<R: oio::Read + 'static> oio::Read for AzdlsPrefetchReader<R> {
+    async fn read(&mut self) -> Result<Buffer> {
+        if self.prefetch_size == 0 {
+            let inner = self.inner.as_mut().expect("inner reader must be valid");
+            return inner.read().await;
+        }
+        if self.eof {
+            return Ok(Buffer::new());
+        }
+
+        if self.task.is_none() {
+            self.prefetch();
+        }
+        let task = self.task.take().expect("prefetch task must be valid");
+        let (inner, res) = task.await;
+        self.inner = Some(inner);
+
+        let buf = res?;
+        if buf.is_empty() {
+            self.eof = true;
+        } else {
+            self.prefetch();
+        }
+        Ok(buf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use http::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn test_content_encoding_from_headers() {
+        let cases = vec![
+            (Some("gzip"), Some(ContentEncoding::Gzip)),
+            (Some("x-gzip"), Some(ContentEncoding::Gzip)),
+            (Some("Deflate"), Some(ContentEncoding::Deflate)),
+            (Some("br"), None),
+            (Some("identity"), None),
+            (None, None),
+        ];
+
+        for (input, expected) in cases {
+            let mut headers = HeaderMap::new();
+            if let Some(v) = input {
+                headers.insert(CONTENT_ENCODING, HeaderValue::from_static(v));
+            }
+            assert_eq!(
+                ContentEncoding::from_headers(&headers).unwrap(),
+                expected,
+                "{input:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_gzip_decoder() {
+        let content = "hello, world! ".repeat(1024);
+
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(content.as_bytes()).unwrap();
+        let compressed = Bytes::from(encoder.finish().unwrap());
+
+        let mut decoder = Decoder::Gzip(GzDecoder::new(Vec::new()));
+        let mut actual = Vec::new();
+        for chunk in compressed.chunks(7) {
+            decoder.write_all(chunk).unwrap();
+            actual.extend(decoder.take());
+        }
+        actual.extend(decoder.finish().unwrap());
+
+        assert_eq!(actual, content.as_bytes());
+    }
+
+    struct ChunkReader(Vec<Buffer>);
+
+    impl oio::Read for ChunkReader {
+        async fn read(&mut self) -> Result<Buffer> {
+            if self.0.is_empty() {
+                return Ok(Buffer::new());
+            }
+            Ok(self.0.remove(0))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_reader() {
+        let chunks: Vec<_> = (0..10u8).map(|i| Buffer::from(vec![i; 3])).collect();
+
+        let mut r = AzdlsPrefetchReader::new(ChunkReader(chunks.clone()), 8, Executor::new());
+        let mut sizes = vec![];
+        let mut content = vec![];
+        loop {
+            let buf = oio::Read::read(&mut r).await.unwrap();
+            if buf.is_empty() {
+                break;
+            }
+            sizes.push(buf.len());
+            content.extend_from_slice(&buf.to_vec());
+        }
+        assert_eq!(sizes, vec![9, 9, 9, 3]);
+        assert_eq!(
+            content,
+            chunks.iter().flat_map(|b| b.to_vec()).collect::<Vec<_>>()
+        );
+        // Stay at EOF without reading the inner reader again.
+        assert!(oio::Read::read(&mut r).await.unwrap().is_empty());
+
+        // Prefetch disabled.
+        let mut r = AzdlsPrefetchReader::new(ChunkReader(chunks), 0, Executor::new());
+        assert_eq!(oio::Read::read(&mut r).await.unwrap().len(), 3);
+    }
+
+    /// Count the bytes that have been read from the inner reader.
+    struct CountingReader(Arc<AtomicUsize>);
+
+    impl oio::Read for CountingReader {
+        async fn read(&mut self) -> Result<Buffer> {
+            self.0.fetch_add(4, Ordering::SeqCst);
+            Ok(Buffer::from(vec![0; 4]))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prefetch_reader_reads_ahead() {
+        let read = Arc::new(AtomicUsize::new(0));
+        let mut r = AzdlsPrefetchReader::new(CountingReader(read.clone()), 8, Executor::new());
+
+        assert_eq!(oio::Read::read(&mut r).await.unwrap().len(), 8);
+        // The next block is fetched in background before it's asked for.
+        for _ in 0..100 {
+            if read.load(Ordering::SeqCst) == 16 {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert_eq!(read.load(Ordering::SeqCst), 16);
+    }
+}
@@ -17,8 +17,13 @@
 
 use std::sync::Arc;
 
+use backon::BackoffBuilder;
+use backon::ExponentialBuilder;
+use futures::AsyncRead;
+use futures::AsyncReadExt;
 use http::StatusCode;
 
+use super::core::insert_lease_id;
 use super::core::AzdlsCore;
 use super::error::parse_error;
 use crate::raw::*;
@@ -40,28 +45,80 @@ impl AzdlsWriter {
 }
 
 impl oio::OneShotWrite for AzdlsWriter {
-    async fn write_once(&self, bs: Buffer) -> Result<()> {
+    async fn write_once(&self, bs: Buffer) -> Result<Metadata> {
+        self.create_file().await?;
+
+        // The whole content is known here, store its MD5 as a stable
+        // content hash. Appended files don't have one.
+        let content_md5 = format_content_md5(&bs.to_bytes());
+        let size = bs.len() as u64;
         let mut req =
             self.core
-                .azdls_create_request(&self.path, "file", &self.op, Buffer::new())?;
+                .azdls_update_request(&self.path, Some(size), 0, Some(&content_md5), bs)?;
 
+        insert_lease_id(&mut req, self.op.lock_token())?;
         self.core.sign(&mut req).await?;
 
         let resp = self.core.send(req).await?;
 
         let status = resp.status();
-        match status {
-            StatusCode::CREATED | StatusCode::OK => {}
+        let mut meta = match status {
+            StatusCode::OK | StatusCode::ACCEPTED => parse_into_write_metadata(resp.headers())?,
             _ => {
                 return Err(parse_error(resp)
                     .await?
-                    .with_operation("Backend::azdls_create_request"));
+                    .with_operation("Backend::azdls_update_request"));
             }
+        };
+        meta.set_content_md5(&content_md5);
+        self.verify(size).await?;
+
+        self.set_tags().await?;
+        Ok(meta)
+    }
+}
+
+impl oio::AppendWrite for AzdlsWriter {
+    async fn offset(&self) -> Result<u64> {
+        // Continue from the given position directly, azdls will reject the
+        // append if it doesn't match the committed position.
+        if let Some(position) = self.op.position() {
+            return Ok(position);
         }
 
-        let mut req = self
+        let resp = self
             .core
-            .azdls_update_request(&self.path, Some(bs.len() as u64), 0, bs)?;
+            .azdls_get_properties(&self.path, &OpStat::new())
+            .await?;
+
+        let status = resp.status();
+        let headers = resp.headers();
+
+        match status {
+            StatusCode::OK => Ok(parse_content_length(headers)?.unwrap_or_default()),
+            StatusCode::NOT_FOUND => Ok(0),
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn append(&self, offset: u64, size: u64, body: Buffer) -> Result<Metadata> {
+        // All data before offset has been flushed, attach it to errors so that
+        // users can resume from this position.
+        let meta = self
+            .append_at(offset, size, body)
+            .await
+            .map_err(|err| err.with_context("flushed_position", offset.to_string()))?;
+        self.verify(offset + size).await?;
+        Ok(meta)
+    }
+}
+
+impl AzdlsWriter {
+    /// Create the file, the existing one will be truncated.
+    async fn create_file(&self) -> Result<()> {
+        let mut req =
+            self.core
+                .azdls_create_request(&self.path, "file", &self.op, Buffer::new())?;
 
         self.core.sign(&mut req).await?;
 
@@ -69,63 +126,235 @@ impl oio::OneShotWrite for AzdlsWriter {
 
         let status = resp.status();
         match status {
-            StatusCode::OK | StatusCode::ACCEPTED => Ok(()),
+            StatusCode::CREATED | StatusCode::OK => Ok(()),
             _ => Err(parse_error(resp)
                 .await?
-                .with_operation("Backend::azdls_update_request")),
+                .with_operation("Backend::azdls_create_request")),
         }
     }
-}
 
-impl oio::AppendWrite for AzdlsWriter {
-    async fn offset(&self) -> Result<u64> {
-        let resp = self.core.azdls_get_properties(&self.path).await?;
+    /// Write all content of `reader` without knowing its size in advance.
+    ///
+    /// Content is appended in chunks of `chunk` bytes as it arrives, and
+    /// flushed at once after `reader` returns EOF. Appended data is not
+    /// visible before the flush, the file will stay empty if failed.
+    pub async fn write_from_reader<R>(&self, reader: &mut R, chunk: usize) -> Result<Metadata>
+    where
+        R: AsyncRead + Unpin + ?Sized,
+    {
+        assert!(
+            chunk > 0,
+            "chunk of write_from_reader must be greater than 0"
+        );
 
-        let status = resp.status();
-        let headers = resp.headers();
+        self.create_file().await?;
+        self.set_tags().await?;
 
-        match status {
-            StatusCode::OK => Ok(parse_content_length(headers)?.unwrap_or_default()),
-            StatusCode::NOT_FOUND => Ok(0),
-            _ => Err(parse_error(resp).await?),
+        // Advanced only after an append is acknowledged, so that it's always
+        // the length of the appended data.
+        let mut position = 0;
+        loop {
+            let mut buf = vec![0; chunk];
+            let mut filled = 0;
+            while filled < chunk {
+                let n = reader
+                    .read(&mut buf[filled..])
+                    .await
+                    .map_err(new_std_io_error)?;
+                if n == 0 {
+                    break;
+                }
+                filled += n;
+            }
+            if filled == 0 {
+                break;
+            }
+            buf.truncate(filled);
+
+            self.append_uncommitted(position, Buffer::from(buf)).await?;
+            position += filled as u64;
+
+            // Reader returned EOF before filling the chunk.
+            if filled < chunk {
+                break;
+            }
+        }
+
+        let mut req = self
+            .core
+            .azdls_flush_request(&self.path, position, false, true)?;
+        insert_lease_id(&mut req, self.op.lock_token())?;
+        self.core.sign(&mut req).await?;
+        let resp = self.core.send(req).await?;
+        match resp.status() {
+            StatusCode::OK | StatusCode::ACCEPTED => {
+                let meta = parse_into_write_metadata(resp.headers())?.with_content_length(position);
+                self.verify(position).await?;
+                Ok(meta)
+            }
+            _ => Err(parse_error(resp)
+                .await?
+                .with_operation("Backend::azdls_flush_request")),
         }
     }
 
-    async fn append(&self, offset: u64, size: u64, body: Buffer) -> Result<()> {
-        if offset == 0 {
+    /// Append data at `position` without flushing it.
+    ///
+    /// Data appended without flush is only staged at `position`, sending it
+    /// again at the same position replaces the staged data. So it's safe to
+    /// retry even if the previous attempt succeeded but its response is lost.
+    async fn append_uncommitted(&self, position: u64, body: Buffer) -> Result<()> {
+        let size = body.len() as u64;
+        let mut backoff = ExponentialBuilder::default()
+            .with_jitter()
+            .with_max_times(self.core.max_retries)
+            .build();
+        loop {
             let mut req =
                 self.core
-                    .azdls_create_request(&self.path, "file", &self.op, Buffer::new())?;
-
+                    .azdls_append_request(&self.path, size, position, body.clone())?;
+            insert_lease_id(&mut req, self.op.lock_token())?;
             self.core.sign(&mut req).await?;
 
-            let resp = self.core.send(req).await?;
+            let err = match self.core.send(req).await {
+                Ok(resp) => match resp.status() {
+                    StatusCode::OK | StatusCode::ACCEPTED => return Ok(()),
+                    _ => parse_error(resp)
+                        .await?
+                        .with_operation("Backend::azdls_append_request")
+                        .with_context("position", position.to_string()),
+                },
+                Err(err) => err,
+            };
+            match backoff.next() {
+                Some(delay) if err.is_temporary() => tokio::time::sleep(delay).await,
+                _ => return Err(err),
+            }
+        }
+    }
+
+    /// Check that the flushed file has `expected` bytes if `verify_on_write`
+    /// is enabled.
+    async fn verify(&self, expected: u64) -> Result<()> {
+        if !self.core.verify_on_write {
+            return Ok(());
+        }
 
-            let status = resp.status();
-            match status {
-                StatusCode::CREATED | StatusCode::OK => {}
-                _ => {
-                    return Err(parse_error(resp)
+        let resp = self
+            .core
+            .azdls_get_properties(&self.path, &OpStat::new())
+            .await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp)
+                .await?
+                .with_operation("Backend::azdls_verify_write"));
+        }
+
+        let actual = parse_content_length(resp.headers())?.unwrap_or_default();
+        if actual != expected {
+            return Err(Error::new(
+                ErrorKind::Unexpected,
+                "written content length doesn't match the stored one",
+            )
+            .with_operation("Backend::azdls_verify_write")
+            .with_context("path", &self.path)
+            .with_context("expected", expected.to_string())
+            .with_context("actual", actual.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Tags can't be carried by the dfs create request, set them via the
+    /// blob service once the file exists.
+    async fn set_tags(&self) -> Result<()> {
+        match self.op.tags() {
+            Some(tags) => self
+                .core
+                .azdls_set_tags(&self.path, tags, self.op.lock_token())
+                .await
+                .map_err(|err| err.with_operation("Backend::azdls_set_tags")),
+            None => Ok(()),
+        }
+    }
+
+    async fn append_at(&self, offset: u64, size: u64, body: Buffer) -> Result<Metadata> {
+        if offset == 0 {
+            self.create_file().await?;
+            self.set_tags().await?;
+        }
+
+        if self.op.retain_uncommitted_data() {
+            return self.append_and_flush(offset, size, body).await;
+        }
+
+        let mut backoff = ExponentialBuilder::default()
+            .with_jitter()
+            .with_max_times(self.core.max_retries)
+            .build();
+        loop {
+            let mut req = self.core.azdls_update_request(
+                &self.path,
+                Some(size),
+                offset,
+                None,
+                body.clone(),
+            )?;
+
+            insert_lease_id(&mut req, self.op.lock_token())?;
+            self.core.sign(&mut req).await?;
+
+            let err = match self.core.send(req).await {
+                Ok(resp) => match resp.status() {
+                    StatusCode::OK | StatusCode::ACCEPTED => {
+                        return parse_into_write_metadata(resp.headers())
+                    }
+                    _ => parse_error(resp)
                         .await?
-                        .with_operation("Backend::azdls_create_request"));
-                }
+                        .with_operation("Backend::azdls_update_request"),
+                },
+                Err(err) => err,
+            };
+            let delay = match backoff.next() {
+                Some(delay) if err.is_temporary() => delay,
+                _ => return Err(err),
+            };
+            tokio::time::sleep(delay).await;
+
+            // Append is not idempotent, only retry if the committed length
+            // shows the failed attempt wasn't applied.
+            let resp = match self
+                .core
+                .azdls_get_properties(&self.path, &OpStat::new())
+                .await
+            {
+                Ok(resp) if resp.status() == StatusCode::OK => resp,
+                _ => return Err(err),
+            };
+            match parse_content_length(resp.headers())? {
+                Some(v) if v == offset => continue,
+                // The attempt has been committed, but its response is lost.
+                Some(v) if v == offset + size => return parse_into_write_metadata(resp.headers()),
+                _ => return Err(err),
             }
         }
+    }
+
+    /// Append data and flush it while retaining uncommitted data, the file
+    /// won't be closed so that users can keep appending.
+    async fn append_and_flush(&self, offset: u64, size: u64, body: Buffer) -> Result<Metadata> {
+        self.append_uncommitted(offset, body).await?;
 
         let mut req = self
             .core
-            .azdls_update_request(&self.path, Some(size), offset, body)?;
-
+            .azdls_flush_request(&self.path, offset + size, true, false)?;
+        insert_lease_id(&mut req, self.op.lock_token())?;
         self.core.sign(&mut req).await?;
-
         let resp = self.core.send(req).await?;
-
-        let status = resp.status();
-        match status {
-            StatusCode::OK | StatusCode::ACCEPTED => Ok(()),
+        match resp.status() {
+            StatusCode::OK | StatusCode::ACCEPTED => parse_into_write_metadata(resp.headers()),
             _ => Err(parse_error(resp)
                 .await?
-                .with_operation("Backend::azdls_update_request")),
+                .with_operation("Backend::azdls_flush_request")),
         }
     }
 }
@@ -284,6 +284,8 @@ impl Access for AzfileBackend {
                 read: true,
 
                 write: true,
+                write_returns_etag: true,
+                write_returns_last_modified: true,
                 create_dir: true,
                 delete: true,
                 rename: true,
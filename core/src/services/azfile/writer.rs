@@ -39,7 +39,7 @@ impl AzfileWriter {
 }
 
 impl oio::OneShotWrite for AzfileWriter {
-    async fn write_once(&self, bs: Buffer) -> Result<()> {
+    async fn write_once(&self, bs: Buffer) -> Result<Metadata> {
         let resp = self
             .core
             .azfile_create_file(&self.path, bs.len(), &self.op)
@@ -61,7 +61,7 @@ impl oio::OneShotWrite for AzfileWriter {
             .await?;
         let status = resp.status();
         match status {
-            StatusCode::OK | StatusCode::CREATED => Ok(()),
+            StatusCode::OK | StatusCode::CREATED => parse_into_write_metadata(resp.headers()),
             _ => Err(parse_error(resp)
                 .await?
                 .with_operation("Backend::azfile_update")),
@@ -81,7 +81,7 @@ impl oio::AppendWrite for AzfileWriter {
         }
     }
 
-    async fn append(&self, offset: u64, size: u64, body: Buffer) -> Result<()> {
+    async fn append(&self, offset: u64, size: u64, body: Buffer) -> Result<Metadata> {
         let resp = self
             .core
             .azfile_update(&self.path, size, offset, body)
@@ -89,7 +89,7 @@ impl oio::AppendWrite for AzfileWriter {
 
         let status = resp.status();
         match status {
-            StatusCode::OK | StatusCode::CREATED => Ok(()),
+            StatusCode::OK | StatusCode::CREATED => parse_into_write_metadata(resp.headers()),
             _ => Err(parse_error(resp)
                 .await?
                 .with_operation("Backend::azfile_update")),
@@ -514,6 +514,10 @@ impl Access for B2Backend {
                     parts.headers,
                 )))
             }
+            _ => Err(Error::new(
+                ErrorKind::Unsupported,
+                "presign operation is not supported",
+            )),
         }
     }
 }
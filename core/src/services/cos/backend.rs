@@ -282,10 +282,13 @@ impl Access for CosBackend {
                 write: true,
                 write_can_empty: true,
                 write_can_append: true,
+                write_returns_append_offset: true,
                 write_can_multi: true,
                 write_with_content_type: true,
                 write_with_cache_control: true,
                 write_with_content_disposition: true,
+                write_returns_etag: true,
+                write_returns_version: true,
                 // The min multipart size of COS is 1 MiB.
                 //
                 // ref: <https://www.tencentcloud.com/document/product/436/14112>
@@ -400,6 +403,12 @@ impl Access for CosBackend {
                 self.core
                     .cos_put_object_request(path, None, v, Buffer::new())?
             }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "presign operation is not supported",
+                ))
+            }
         };
         self.core.sign_query(&mut req, args.expire()).await?;
 
@@ -431,6 +431,14 @@ pub struct InitiateMultipartUploadResult {
     pub upload_id: String,
 }
 
+/// Result of CompleteMultipartUpload
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct CompleteMultipartUploadResult {
+    #[serde(rename = "ETag")]
+    pub etag: String,
+}
+
 /// Request of CompleteMultipartUploadRequest
 #[derive(Default, Debug, Serialize)]
 #[serde(default, rename = "CompleteMultipartUpload", rename_all = "PascalCase")]
@@ -18,6 +18,7 @@
 use std::fmt::Debug;
 use std::fmt::Formatter;
 
+use bytes::Bytes;
 use dashmap::DashMap;
 use serde::Deserialize;
 use serde::Serialize;
@@ -35,7 +36,10 @@ pub struct DashmapConfig {
 
 impl Configurator for DashmapConfig {
     fn into_builder(self) -> impl Builder {
-        DashmapBuilder { config: self }
+        DashmapBuilder {
+            config: self,
+            snapshot: None,
+        }
     }
 }
 
@@ -44,14 +48,43 @@ impl Configurator for DashmapConfig {
 #[derive(Default)]
 pub struct DashmapBuilder {
     config: DashmapConfig,
+    snapshot: Option<Bytes>,
 }
 
 impl DashmapBuilder {
+    /// Create a builder that restores entries from a snapshot created by
+    /// [`DashmapBackend::snapshot`].
+    ///
+    /// The snapshot is decoded while building, and building fails if it's
+    /// malformed or written by a newer format version.
+    pub fn from_snapshot(bs: Bytes) -> Self {
+        Self {
+            snapshot: Some(bs),
+            ..Default::default()
+        }
+    }
+
     /// Set the root for dashmap.
     pub fn root(mut self, path: &str) -> Self {
         self.config.root = Some(path.into());
         self
     }
+
+    /// Build the dashmap backend.
+    ///
+    /// Unlike [`Builder::build`], this returns the concrete backend so that
+    /// callers can take a snapshot of it later.
+    pub fn build_backend(self) -> Result<DashmapBackend> {
+        let inner = DashMap::default();
+        if let Some(bs) = self.snapshot {
+            for (path, value) in typed_kv::decode_snapshot(bs)? {
+                inner.insert(path, value);
+            }
+        }
+
+        Ok(DashmapBackend::new(Adapter { inner })
+            .with_root(self.config.root.as_deref().unwrap_or_default()))
+    }
 }
 
 impl Builder for DashmapBuilder {
@@ -59,10 +92,7 @@ impl Builder for DashmapBuilder {
     type Config = DashmapConfig;
 
     fn build(self) -> Result<impl Access> {
-        Ok(DashmapBackend::new(Adapter {
-            inner: DashMap::default(),
-        })
-        .with_root(self.config.root.as_deref().unwrap_or_default()))
+        self.build_backend()
     }
 }
 
@@ -107,6 +137,14 @@ impl typed_kv::Adapter for Adapter {
         }
     }
 
+    async fn exists(&self, path: &str) -> Result<bool> {
+        self.blocking_exists(path)
+    }
+
+    fn blocking_exists(&self, path: &str) -> Result<bool> {
+        Ok(self.inner.contains_key(path))
+    }
+
     async fn set(&self, path: &str, value: typed_kv::Value) -> Result<()> {
         self.blocking_set(path, value)
     }
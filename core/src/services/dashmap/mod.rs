@@ -16,5 +16,6 @@
 // under the License.
 
 mod backend;
+pub use backend::DashmapBackend;
 pub use backend::DashmapBuilder as Dashmap;
 pub use backend::DashmapConfig;
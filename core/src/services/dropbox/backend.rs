@@ -30,9 +30,68 @@ use super::writer::DropboxWriter;
 use crate::raw::*;
 use crate::*;
 
+/// Dropbox allows up to 1000 entries in one batch request.
+const MAX_BATCH_OPERATIONS: usize = 1000;
+
+/// Backend for dropbox services.
 #[derive(Clone, Debug)]
 pub struct DropboxBackend {
-    pub core: Arc<DropboxCore>,
+    pub(super) core: Arc<DropboxCore>,
+}
+
+impl DropboxBackend {
+    /// Move files in batch via `files/move_batch_v2`.
+    ///
+    /// Returns the result for every source path in the same order as
+    /// `pairs`. Dropbox may run the batch as an async job, in which case
+    /// we poll the job status till it's done.
+    pub async fn rename_many(
+        &self,
+        pairs: Vec<(String, String)>,
+    ) -> Result<Vec<(String, Result<()>)>> {
+        if pairs.len() > MAX_BATCH_OPERATIONS {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "dropbox services only allow move up to 1000 entries at once",
+            )
+            .with_context("length", pairs.len().to_string()));
+        }
+        if pairs.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let resp = self.core.dropbox_move_batch(&pairs).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body();
+        let decoded_response: DropboxMoveBatchResponse =
+            serde_json::from_reader(bs.reader()).map_err(new_json_deserialize_error)?;
+
+        match decoded_response.tag.as_str() {
+            "complete" => {
+                let entries = decoded_response.entries.unwrap_or_default();
+                handle_batch_move_complete_result(&pairs, entries)
+            }
+            "async_job_id" => {
+                let job_id = decoded_response
+                    .async_job_id
+                    .expect("async_job_id should be present");
+                { || self.core.dropbox_move_batch_check(job_id.clone(), &pairs) }
+                    .retry(&*BACKOFF)
+                    .when(|e| e.is_temporary())
+                    .await
+            }
+            _ => Err(Error::new(
+                ErrorKind::Unexpected,
+                format!(
+                    "move batch failed with unexpected tag {}",
+                    decoded_response.tag
+                ),
+            )),
+        }
+    }
 }
 
 impl Access for DropboxBackend {
@@ -67,6 +126,7 @@ impl Access for DropboxBackend {
 
                 batch: true,
                 batch_delete: true,
+                batch_max_operations: Some(MAX_BATCH_OPERATIONS),
 
                 ..Default::default()
             });
@@ -233,18 +293,16 @@ impl Access for DropboxBackend {
     }
 
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
-        let ops = args.into_operation();
-        if ops.len() > 1000 {
+        let paths = args.into_delete_paths()?;
+        if paths.len() > MAX_BATCH_OPERATIONS {
             return Err(Error::new(
                 ErrorKind::Unsupported,
                 "dropbox services only allow delete up to 1000 keys at once",
             )
-            .with_context("length", ops.len().to_string()));
+            .with_context("length", paths.len().to_string()));
         }
 
-        let paths = ops.into_iter().map(|(p, _)| p).collect::<Vec<_>>();
-
-        let resp = self.core.dropbox_delete_batch(paths).await?;
+        let resp = self.core.dropbox_delete_batch(paths.clone()).await?;
         if resp.status() != StatusCode::OK {
             return Err(parse_error(resp).await?);
         }
@@ -256,14 +314,14 @@ impl Access for DropboxBackend {
         match decoded_response.tag.as_str() {
             "complete" => {
                 let entries = decoded_response.entries.unwrap_or_default();
-                let results = self.core.handle_batch_delete_complete_result(entries);
+                let results = handle_batch_delete_complete_result(&paths, entries)?;
                 Ok(RpBatch::new(results))
             }
             "async_job_id" => {
                 let job_id = decoded_response
                     .async_job_id
                     .expect("async_job_id should be present");
-                let res = { || self.core.dropbox_delete_batch_check(job_id.clone()) }
+                let res = { || self.core.dropbox_delete_batch_check(job_id.clone(), &paths) }
                     .retry(&*BACKOFF)
                     .when(|e| e.is_temporary())
                     .await?;
@@ -18,6 +18,7 @@
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
+use std::time::Duration;
 
 use chrono::DateTime;
 use chrono::Utc;
@@ -46,12 +47,16 @@ pub struct DropboxConfig {
     pub client_id: Option<String>,
     /// client_secret for dropbox.
     pub client_secret: Option<String>,
+    /// Refresh the access token this long before it expires, default to
+    /// 2 minutes.
+    pub token_refresh_skew: Option<Duration>,
 }
 
 impl Debug for DropboxConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DropBoxConfig")
             .field("root", &self.root)
+            .field("token_refresh_skew", &self.token_refresh_skew)
             .finish_non_exhaustive()
     }
 }
@@ -61,6 +66,7 @@ impl Configurator for DropboxConfig {
         DropboxBuilder {
             config: self,
             http_client: None,
+            refresh_token_callback: None,
         }
     }
 }
@@ -72,6 +78,7 @@ pub struct DropboxBuilder {
     config: DropboxConfig,
 
     http_client: Option<HttpClient>,
+    refresh_token_callback: Option<RefreshTokenCallback>,
 }
 
 impl Debug for DropboxBuilder {
@@ -128,6 +135,27 @@ impl DropboxBuilder {
         self
     }
 
+    /// Refresh the access token this long before it expires.
+    ///
+    /// The skew is capped to the half of the token lifetime.
+    ///
+    /// Default to 2 minutes.
+    pub fn token_refresh_skew(mut self, skew: Duration) -> Self {
+        self.config.token_refresh_skew = Some(skew);
+        self
+    }
+
+    /// Register a callback to persist the rotated refresh token.
+    ///
+    /// Some OAuth servers issue a new refresh token on every refresh and
+    /// invalidate the old one. The callback will be called with the new
+    /// refresh token once it's rotated, so that it can be stored and used
+    /// the next time this service is built.
+    pub fn refresh_token_callback(mut self, f: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        self.refresh_token_callback = Some(Arc::new(f));
+        self
+    }
+
     /// Specify the http client that used by this service.
     ///
     /// # Notes
@@ -140,11 +168,13 @@ impl DropboxBuilder {
     }
 }
 
-impl Builder for DropboxBuilder {
-    const SCHEME: Scheme = Scheme::Dropbox;
-    type Config = DropboxConfig;
-
-    fn build(self) -> Result<impl Access> {
+impl DropboxBuilder {
+    /// Build a [`DropboxBackend`] directly.
+    ///
+    /// Most users should use [`Builder::build`] via `Operator::new`
+    /// instead, this is useful to move files in batch via
+    /// [`DropboxBackend::rename_many`].
+    pub fn build_backend(self) -> Result<DropboxBackend> {
         let root = normalize_root(&self.config.root.unwrap_or_default());
         let client = if let Some(client) = self.http_client {
             client
@@ -155,7 +185,7 @@ impl Builder for DropboxBuilder {
             })?
         };
 
-        let signer = match (self.config.access_token, self.config.refresh_token) {
+        let mut signer = match (self.config.access_token, self.config.refresh_token) {
             (Some(access_token), None) => DropboxSigner {
                 access_token,
                 // We will never expire user specified token.
@@ -201,6 +231,11 @@ impl Builder for DropboxBuilder {
             }
         };
 
+        if let Some(skew) = self.config.token_refresh_skew {
+            signer.refresh_skew = skew;
+        }
+        signer.refresh_token_callback = self.refresh_token_callback;
+
         Ok(DropboxBackend {
             core: Arc::new(DropboxCore {
                 root,
@@ -210,3 +245,12 @@ impl Builder for DropboxBuilder {
         })
     }
 }
+
+impl Builder for DropboxBuilder {
+    const SCHEME: Scheme = Scheme::Dropbox;
+    type Config = DropboxConfig;
+
+    fn build(self) -> Result<impl Access> {
+        self.build_backend()
+    }
+}
@@ -38,6 +38,7 @@ use serde::Deserialize;
 use serde::Serialize;
 use tokio::sync::Mutex;
 
+use super::error::parse_dropbox_error_summary;
 use super::error::parse_error;
 use crate::raw::*;
 use crate::*;
@@ -87,6 +88,10 @@ impl DropboxCore {
         }
 
         // Refresh invalid token.
+        //
+        // The signer is locked during the refresh, so concurrent requests
+        // will wait for the same refresh instead of hitting the token
+        // endpoint again.
         let url = "https://api.dropboxapi.com/oauth2/token".to_string();
 
         let content = format!(
@@ -102,6 +107,13 @@ impl DropboxCore {
             .map_err(new_request_build_error)?;
 
         let resp = self.client.send(request).await?;
+        if resp.status() != StatusCode::OK {
+            signer.refreshed = false;
+            return Err(parse_error(resp)
+                .await?
+                .with_operation("DropboxCore::refresh_token")
+                .with_context("token_refreshed", "false"));
+        }
         let body = resp.into_body();
 
         let token: DropboxTokenResponse =
@@ -109,12 +121,15 @@ impl DropboxCore {
 
         // Update signer after token refreshed.
         signer.access_token.clone_from(&token.access_token);
-
-        // Refresh it 2 minutes earlier.
-        signer.expires_in = Utc::now()
-            + chrono::TimeDelta::try_seconds(token.expires_in as i64)
-                .expect("expires_in must be valid seconds")
-            - chrono::TimeDelta::try_seconds(120).expect("120 must be valid seconds");
+        signer.expires_in = token_expires_at(token.expires_in, signer.refresh_skew);
+        signer.refreshed = true;
+
+        let callback = signer.refresh_token_callback.clone();
+        rotate_refresh_token(
+            &mut signer.refresh_token,
+            token.refresh_token,
+            callback.as_ref(),
+        );
 
         let value = format!("Bearer {}", token.access_token)
             .parse()
@@ -124,6 +139,37 @@ impl DropboxCore {
         Ok(())
     }
 
+    /// Send request to dropbox.
+    ///
+    /// Rate limited responses are returned as is, the `Retry-After` carried
+    /// by their errors will be honored by `RetryLayer`.
+    ///
+    /// Unauthorized responses are returned as error directly, along with
+    /// whether the access token in use comes from a successful refresh.
+    pub async fn send(&self, req: Request<Buffer>) -> Result<Response<Buffer>> {
+        let resp = self.client.send(req).await?;
+        if resp.status() == StatusCode::UNAUTHORIZED {
+            let refreshed = self.invalidate_token().await;
+            return Err(parse_error(resp)
+                .await?
+                .with_context("token_refreshed", refreshed.to_string()));
+        }
+
+        Ok(resp)
+    }
+
+    /// Invalidate the access token so that the next request will refresh
+    /// it, returns whether the invalidated token comes from a refresh.
+    ///
+    /// User specified access token can't be refreshed, so we keep it as is.
+    async fn invalidate_token(&self) -> bool {
+        let mut signer = self.signer.lock().await;
+        if !signer.refresh_token.is_empty() {
+            signer.expires_in = DateTime::<Utc>::MIN_UTC;
+        }
+        signer.refreshed
+    }
+
     pub async fn dropbox_get(
         &self,
         path: &str,
@@ -181,7 +227,7 @@ impl DropboxCore {
             .map_err(new_request_build_error)?;
 
         self.sign(&mut request).await?;
-        self.client.send(request).await
+        self.send(request).await
     }
 
     pub async fn dropbox_delete(&self, path: &str) -> Result<Response<Buffer>> {
@@ -199,7 +245,7 @@ impl DropboxCore {
             .map_err(new_request_build_error)?;
 
         self.sign(&mut request).await?;
-        self.client.send(request).await
+        self.send(request).await
     }
 
     pub async fn dropbox_delete_batch(&self, paths: Vec<String>) -> Result<Response<Buffer>> {
@@ -222,12 +268,16 @@ impl DropboxCore {
             .map_err(new_request_build_error)?;
 
         self.sign(&mut request).await?;
-        self.client.send(request).await
+        self.send(request).await
     }
 
-    pub async fn dropbox_delete_batch_check(&self, async_job_id: String) -> Result<RpBatch> {
+    pub async fn dropbox_delete_batch_check(
+        &self,
+        async_job_id: String,
+        paths: &[String],
+    ) -> Result<RpBatch> {
         let url = "https://api.dropboxapi.com/2/files/delete_batch/check".to_string();
-        let args = DropboxDeleteBatchCheckArgs { async_job_id };
+        let args = DropboxBatchCheckArgs { async_job_id };
 
         let bs = Bytes::from(serde_json::to_vec(&args).map_err(new_json_serialize_error)?);
 
@@ -239,7 +289,7 @@ impl DropboxCore {
 
         self.sign(&mut request).await?;
 
-        let resp = self.client.send(request).await?;
+        let resp = self.send(request).await?;
         if resp.status() != StatusCode::OK {
             return Err(parse_error(resp).await?);
         }
@@ -256,7 +306,7 @@ impl DropboxCore {
             .set_temporary()),
             "complete" => {
                 let entries = decoded_response.entries.unwrap_or_default();
-                let results = self.handle_batch_delete_complete_result(entries);
+                let results = handle_batch_delete_complete_result(paths, entries)?;
                 Ok(RpBatch::new(results))
             }
             _ => Err(Error::new(
@@ -269,6 +319,78 @@ impl DropboxCore {
         }
     }
 
+    pub async fn dropbox_move_batch(&self, pairs: &[(String, String)]) -> Result<Response<Buffer>> {
+        let url = "https://api.dropboxapi.com/2/files/move_batch_v2".to_string();
+        let args = DropboxMoveBatchArgs {
+            entries: pairs
+                .iter()
+                .map(|(from, to)| DropboxMoveArgs {
+                    from_path: self.build_path(from),
+                    to_path: self.build_path(to),
+                })
+                .collect(),
+            autorename: false,
+        };
+
+        let bs = Bytes::from(serde_json::to_string(&args).map_err(new_json_serialize_error)?);
+
+        let mut request = Request::post(&url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(CONTENT_LENGTH, bs.len())
+            .body(Buffer::from(bs))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut request).await?;
+        self.send(request).await
+    }
+
+    pub async fn dropbox_move_batch_check(
+        &self,
+        async_job_id: String,
+        pairs: &[(String, String)],
+    ) -> Result<Vec<(String, Result<()>)>> {
+        let url = "https://api.dropboxapi.com/2/files/move_batch/check_v2".to_string();
+        let args = DropboxBatchCheckArgs { async_job_id };
+
+        let bs = Bytes::from(serde_json::to_vec(&args).map_err(new_json_serialize_error)?);
+
+        let mut request = Request::post(&url)
+            .header(CONTENT_TYPE, "application/json")
+            .header(CONTENT_LENGTH, bs.len())
+            .body(Buffer::from(bs))
+            .map_err(new_request_build_error)?;
+
+        self.sign(&mut request).await?;
+
+        let resp = self.send(request).await?;
+        if resp.status() != StatusCode::OK {
+            return Err(parse_error(resp).await?);
+        }
+
+        let bs = resp.into_body();
+
+        let decoded_response: DropboxMoveBatchResponse =
+            serde_json::from_reader(bs.reader()).map_err(new_json_deserialize_error)?;
+        match decoded_response.tag.as_str() {
+            "in_progress" => Err(Error::new(
+                ErrorKind::Unexpected,
+                "move batch job still in progress",
+            )
+            .set_temporary()),
+            "complete" => {
+                let entries = decoded_response.entries.unwrap_or_default();
+                handle_batch_move_complete_result(pairs, entries)
+            }
+            _ => Err(Error::new(
+                ErrorKind::Unexpected,
+                format!(
+                    "move batch check failed with unexpected tag {}",
+                    decoded_response.tag
+                ),
+            )),
+        }
+    }
+
     pub async fn dropbox_create_folder(&self, path: &str) -> Result<RpCreateDir> {
         let url = "https://api.dropboxapi.com/2/files/create_folder_v2".to_string();
         let args = DropboxCreateFolderArgs {
@@ -284,7 +406,7 @@ impl DropboxCore {
             .map_err(new_request_build_error)?;
 
         self.sign(&mut request).await?;
-        let resp = self.client.send(request).await?;
+        let resp = self.send(request).await?;
         let status = resp.status();
         match status {
             StatusCode::OK => Ok(RpCreateDir::default()),
@@ -323,7 +445,7 @@ impl DropboxCore {
             .map_err(new_request_build_error)?;
 
         self.sign(&mut request).await?;
-        self.client.send(request).await
+        self.send(request).await
     }
 
     pub async fn dropbox_list_continue(&self, cursor: &str) -> Result<Response<Buffer>> {
@@ -342,7 +464,7 @@ impl DropboxCore {
             .map_err(new_request_build_error)?;
 
         self.sign(&mut request).await?;
-        self.client.send(request).await
+        self.send(request).await
     }
 
     pub async fn dropbox_copy(&self, from: &str, to: &str) -> Result<Response<Buffer>> {
@@ -362,7 +484,7 @@ impl DropboxCore {
             .map_err(new_request_build_error)?;
 
         self.sign(&mut request).await?;
-        self.client.send(request).await
+        self.send(request).await
     }
 
     pub async fn dropbox_move(&self, from: &str, to: &str) -> Result<Response<Buffer>> {
@@ -382,7 +504,7 @@ impl DropboxCore {
             .map_err(new_request_build_error)?;
 
         self.sign(&mut request).await?;
-        self.client.send(request).await
+        self.send(request).await
     }
 
     pub async fn dropbox_get_metadata(&self, path: &str) -> Result<Response<Buffer>> {
@@ -402,55 +524,101 @@ impl DropboxCore {
 
         self.sign(&mut request).await?;
 
-        self.client.send(request).await
+        self.send(request).await
     }
+}
 
-    pub fn handle_batch_delete_complete_result(
-        &self,
-        entries: Vec<DropboxDeleteBatchResponseEntry>,
-    ) -> Vec<(String, Result<BatchedReply>)> {
-        let mut results = Vec::with_capacity(entries.len());
-        for entry in entries {
-            let result = match entry.tag.as_str() {
-                // Only success response has metadata and then path,
-                // so we cannot tell which path failed.
-                "success" => {
-                    let path = entry
-                        .metadata
-                        .expect("metadata should be present")
-                        .path_display;
-                    (path, Ok(RpDelete::default().into()))
-                }
-                "failure" => {
-                    let error = entry.failure.expect("error should be present");
-                    let error_cause = &error
-                        .failure_cause_map
-                        .get(&error.tag)
-                        .expect("error should be present")
-                        .tag;
-                    // Ignore errors about path lookup not found and report others.
-                    if error.tag == "path_lookup" && error_cause == "not_found" {
-                        ("".to_string(), Ok(RpDelete::default().into()))
-                    } else {
-                        let err = Error::new(
-                            ErrorKind::Unexpected,
-                            &format!("delete failed with error {} {}", error.tag, error_cause),
-                        );
-                        ("".to_string(), Err(err))
-                    }
-                }
-                _ => (
-                    "".to_string(),
-                    Err(Error::new(
-                        ErrorKind::Unexpected,
-                        &format!("delete failed with unexpected tag {}", entry.tag),
-                    )),
-                ),
-            };
-            results.push(result);
-        }
-        results
+/// Dropbox returns the entries of batch operations in the same order as
+/// requested, but only success entries carry the metadata, so we map them
+/// back to the input paths by position.
+pub fn handle_batch_delete_complete_result(
+    paths: &[String],
+    entries: Vec<DropboxDeleteBatchResponseEntry>,
+) -> Result<Vec<(String, Result<BatchedReply>)>> {
+    if paths.len() != entries.len() {
+        return Err(Error::new(
+            ErrorKind::Unexpected,
+            "delete batch returned entries mismatch with requested paths",
+        )
+        .with_context("expected", paths.len().to_string())
+        .with_context("actual", entries.len().to_string()));
+    }
+
+    let mut results = Vec::with_capacity(entries.len());
+    for (path, entry) in paths.iter().zip(entries) {
+        let result = match entry.tag.as_str() {
+            "success" => Ok(RpDelete::default().into()),
+            "failure" => match entry.failure.map(parse_batch_failure) {
+                // Ignore errors about path lookup not found and report others.
+                Some(err) if err.kind() == ErrorKind::NotFound => Ok(RpDelete::default().into()),
+                Some(err) => Err(err.with_operation("delete_batch")),
+                None => Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "delete failed without failure reason",
+                )),
+            },
+            _ => Err(Error::new(
+                ErrorKind::Unexpected,
+                format!("delete failed with unexpected tag {}", entry.tag),
+            )),
+        };
+        results.push((path.clone(), result));
+    }
+    Ok(results)
+}
+
+/// Map the entries of move batch back to the source paths by position.
+pub fn handle_batch_move_complete_result(
+    pairs: &[(String, String)],
+    entries: Vec<DropboxMoveBatchResponseEntry>,
+) -> Result<Vec<(String, Result<()>)>> {
+    if pairs.len() != entries.len() {
+        return Err(Error::new(
+            ErrorKind::Unexpected,
+            "move batch returned entries mismatch with requested paths",
+        )
+        .with_context("expected", pairs.len().to_string())
+        .with_context("actual", entries.len().to_string()));
+    }
+
+    let mut results = Vec::with_capacity(entries.len());
+    for ((from, to), entry) in pairs.iter().zip(entries) {
+        let result = match entry.tag.as_str() {
+            "success" => Ok(()),
+            "failure" => match entry.failure.map(parse_batch_failure) {
+                Some(err) => Err(err.with_operation("move_batch").with_context("to", to)),
+                None => Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "move failed without failure reason",
+                )),
+            },
+            _ => Err(Error::new(
+                ErrorKind::Unexpected,
+                format!("move failed with unexpected tag {}", entry.tag),
+            )),
+        };
+        results.push((from.clone(), result));
+    }
+    Ok(results)
+}
+
+/// Convert the failure of a batch entry into error.
+///
+/// The failure is something like `path_lookup/not_found`, which shares the
+/// same format with the error summary.
+fn parse_batch_failure(failure: DropboxBatchFailureResponse) -> Error {
+    let summary = match failure.failure_cause_map.get(&failure.tag) {
+        Some(cause) => format!("{}/{}", failure.tag, cause.tag),
+        None => failure.tag,
+    };
+    let (kind, retryable) =
+        parse_dropbox_error_summary(&summary).unwrap_or((ErrorKind::Unexpected, false));
+
+    let mut err = Error::new(kind, format!("batch entry failed with error {summary}"));
+    if retryable {
+        err = err.set_temporary();
     }
+    err
 }
 
 #[derive(Clone)]
@@ -461,6 +629,12 @@ pub struct DropboxSigner {
 
     pub access_token: String,
     pub expires_in: DateTime<Utc>,
+
+    /// Refresh the access token this long before it expires.
+    pub refresh_skew: Duration,
+    pub refresh_token_callback: Option<RefreshTokenCallback>,
+    /// Whether the access token in use comes from a successful refresh.
+    pub refreshed: bool,
 }
 
 impl Default for DropboxSigner {
@@ -472,6 +646,10 @@ impl Default for DropboxSigner {
 
             access_token: String::new(),
             expires_in: DateTime::<Utc>::MIN_UTC,
+
+            refresh_skew: DEFAULT_TOKEN_REFRESH_SKEW,
+            refresh_token_callback: None,
+            refreshed: false,
         }
     }
 }
@@ -518,7 +696,7 @@ struct DropboxDeleteBatchArgs {
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
-struct DropboxDeleteBatchCheckArgs {
+struct DropboxBatchCheckArgs {
     async_job_id: String,
 }
 
@@ -551,6 +729,12 @@ struct DropboxMoveArgs {
     to_path: String,
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct DropboxMoveBatchArgs {
+    entries: Vec<DropboxMoveArgs>,
+    autorename: bool,
+}
+
 #[derive(Default, Clone, Debug, Deserialize, Serialize)]
 struct DropboxMetadataArgs {
     include_deleted: bool,
@@ -562,7 +746,8 @@ struct DropboxMetadataArgs {
 #[derive(Clone, Deserialize)]
 struct DropboxTokenResponse {
     access_token: String,
-    expires_in: usize,
+    expires_in: u64,
+    refresh_token: Option<String>,
 }
 
 #[derive(Default, Debug, Deserialize)]
@@ -642,26 +827,103 @@ pub struct DropboxDeleteBatchResponseEntry {
     #[serde(rename(deserialize = ".tag"))]
     pub tag: String,
     pub metadata: Option<DropboxMetadataResponse>,
-    pub failure: Option<DropboxDeleteBatchFailureResponse>,
+    pub failure: Option<DropboxBatchFailureResponse>,
 }
 
 #[derive(Default, Debug, Deserialize)]
 #[serde(default)]
-pub struct DropboxDeleteBatchFailureResponse {
+pub struct DropboxMoveBatchResponse {
     #[serde(rename(deserialize = ".tag"))]
     pub tag: String,
-    // During the batch deletion process, Dropbox returns
+    pub async_job_id: Option<String>,
+    pub entries: Option<Vec<DropboxMoveBatchResponseEntry>>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+pub struct DropboxMoveBatchResponseEntry {
+    #[serde(rename(deserialize = ".tag"))]
+    pub tag: String,
+    pub failure: Option<DropboxBatchFailureResponse>,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+pub struct DropboxBatchFailureResponse {
+    #[serde(rename(deserialize = ".tag"))]
+    pub tag: String,
+    // During the batch process, Dropbox returns
     // part of the error information in the form of a JSON key.
     // Since it is impossible to determine the JSON key in advance,
     // the error information is parsed into a HashMap here.
     // The key of the HashMap is equal to the value of the tag above.
     #[serde(flatten)]
-    pub failure_cause_map: HashMap<String, DropboxDeleteBatchFailureResponseCause>,
+    pub failure_cause_map: HashMap<String, DropboxBatchFailureResponseCause>,
 }
 
 #[derive(Default, Debug, Deserialize)]
 #[serde(default)]
-pub struct DropboxDeleteBatchFailureResponseCause {
+pub struct DropboxBatchFailureResponseCause {
     #[serde(rename(deserialize = ".tag"))]
     pub tag: String,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handle_batch_delete_complete_result() {
+        let bs = r#"{".tag": "complete", "entries": [
+            {".tag": "success", "metadata": {".tag": "file", "name": "A.txt", "path_display": "/Root/A.txt"}},
+            {".tag": "failure", "failure": {".tag": "path_lookup", "path_lookup": {".tag": "not_found"}}},
+            {".tag": "failure", "failure": {".tag": "too_many_write_operations"}},
+            {".tag": "failure", "failure": {".tag": "path_write", "path_write": {".tag": "insufficient_space"}}}
+        ]}"#;
+        let resp: DropboxDeleteBatchResponse = serde_json::from_str(bs).unwrap();
+        let paths = ["a.txt", "b.txt", "c.txt", "d.txt"].map(String::from);
+
+        let results = handle_batch_delete_complete_result(&paths, resp.entries.unwrap()).unwrap();
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].0, "a.txt");
+        assert!(results[0].1.is_ok());
+        assert_eq!(results[1].0, "b.txt");
+        assert!(results[1].1.is_ok());
+        assert_eq!(results[2].0, "c.txt");
+        let err = results[2].1.as_ref().err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+        assert!(err.is_temporary());
+        assert_eq!(results[3].0, "d.txt");
+        let err = results[3].1.as_ref().err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert!(err.to_string().contains("path_write/insufficient_space"));
+
+        // Mismatched entries can't be mapped back.
+        let err = handle_batch_delete_complete_result(&paths[..1], vec![])
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
+
+    #[test]
+    fn test_handle_batch_move_complete_result() {
+        let bs = r#"{".tag": "complete", "entries": [
+            {".tag": "success", "success": {".tag": "file", "name": "b.txt", "path_display": "/b.txt"}},
+            {".tag": "failure", "failure": {".tag": "from_lookup", "from_lookup": {".tag": "not_found"}}}
+        ]}"#;
+        let resp: DropboxMoveBatchResponse = serde_json::from_str(bs).unwrap();
+        let pairs = [
+            ("a.txt".to_string(), "b.txt".to_string()),
+            ("c.txt".to_string(), "d.txt".to_string()),
+        ];
+
+        let results = handle_batch_move_complete_result(&pairs, resp.entries.unwrap()).unwrap();
+        assert_eq!(results[0].0, "a.txt");
+        assert!(results[0].1.is_ok());
+        assert_eq!(results[1].0, "c.txt");
+        assert_eq!(
+            results[1].1.as_ref().unwrap_err().kind(),
+            ErrorKind::NotFound
+        );
+    }
+}
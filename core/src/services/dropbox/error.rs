@@ -56,6 +56,7 @@ pub async fn parse_error(resp: Response<Buffer>) -> Result<Error> {
 
     let mut err = Error::new(kind, message);
 
+    // `Retry-After` is carried by the error so that `RetryLayer` can honor it.
     err = with_error_response_context(err, parts);
 
     if retryable {
@@ -84,3 +85,28 @@ pub fn parse_dropbox_error_summary(summary: &str) -> Option<(ErrorKind, bool)> {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use http::header::RETRY_AFTER;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_parse_error_rate_limited() {
+        let resp = Response::builder()
+            .status(StatusCode::TOO_MANY_REQUESTS)
+            .header(RETRY_AFTER, "3")
+            .body(Buffer::from(
+                r#"{"error_summary": "too_many_requests/..", "error": {"reason": {".tag": "too_many_requests"}, "retry_after": 3}}"#,
+            ))
+            .unwrap();
+
+        let err = parse_error(resp).await.unwrap();
+        assert_eq!(err.kind(), ErrorKind::RateLimited);
+        assert!(err.is_temporary());
+        assert_eq!(err.retry_after(), Some(Duration::from_secs(3)));
+    }
+}
@@ -22,5 +22,6 @@ mod error;
 mod lister;
 mod writer;
 
+pub use backend::DropboxBackend;
 pub use builder::DropboxBuilder as Dropbox;
 pub use builder::DropboxConfig;
@@ -27,6 +27,8 @@ use etcd_client::Error as EtcdError;
 use etcd_client::GetOptions;
 use etcd_client::Identity;
 use etcd_client::TlsOptions;
+use etcd_client::Txn;
+use etcd_client::TxnOp;
 use serde::Deserialize;
 use serde::Serialize;
 use tokio::sync::OnceCell;
@@ -36,6 +38,8 @@ use crate::raw::*;
 use crate::*;
 
 const DEFAULT_ETCD_ENDPOINTS: &str = "http://127.0.0.1:2379";
+/// The default max operations in one transaction, see `--max-txn-ops` of etcd.
+const DEFAULT_ETCD_MAX_TXN_OPS: usize = 128;
 
 /// Config for Etcd services support.
 #[derive(Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
@@ -329,6 +333,24 @@ impl Adapter {
             }
         })
     }
+
+    /// Apply all changes in one etcd transaction, a `None` value means delete.
+    async fn txn(&self, batch: Vec<(String, Option<Buffer>)>) -> Result<()> {
+        let ops: Vec<_> = batch
+            .into_iter()
+            .map(|(key, value)| match value {
+                Some(value) => TxnOp::put(key, value.to_vec(), None),
+                None => TxnOp::delete(key, None),
+            })
+            .collect();
+
+        let mut client = self.conn().await?;
+        let resp = client
+            .txn(Txn::new().and_then(ops))
+            .await
+            .map_err(format_etcd_error)?;
+        check_txn_succeeded(resp.succeeded())
+    }
 }
 
 impl kv::Adapter for Adapter {
@@ -340,6 +362,10 @@ impl kv::Adapter for Adapter {
                 read: true,
                 write: true,
                 list: true,
+                batch: true,
+                batch_delete: true,
+                batch_write: true,
+                batch_max_operations: Some(DEFAULT_ETCD_MAX_TXN_OPS),
 
                 ..Default::default()
             },
@@ -371,6 +397,15 @@ impl kv::Adapter for Adapter {
         Ok(())
     }
 
+    async fn batch_delete(&self, paths: &[String]) -> Result<()> {
+        self.txn(paths.iter().map(|p| (p.clone(), None)).collect())
+            .await
+    }
+
+    async fn write_batch(&self, batch: Vec<(String, Option<Buffer>)>) -> Result<()> {
+        self.txn(batch).await
+    }
+
     async fn scan(&self, path: &str) -> Result<Vec<String>> {
         let mut client = self.conn().await?;
         let get_options = Some(GetOptions::new().with_prefix().with_keys_only());
@@ -394,8 +429,36 @@ impl kv::Adapter for Adapter {
     }
 }
 
+/// A txn that is not succeeded conflicts with a concurrent one, it's safe to
+/// retry since none of its operations are applied.
+fn check_txn_succeeded(succeeded: bool) -> Result<()> {
+    if succeeded {
+        return Ok(());
+    }
+    Err(Error::new(
+        ErrorKind::ConditionNotMatch,
+        "etcd txn conflicts with a concurrent one",
+    )
+    .with_operation("etcd::txn")
+    .set_temporary())
+}
+
 pub fn format_etcd_error(e: EtcdError) -> Error {
     Error::new(ErrorKind::Unexpected, e.to_string().as_str())
         .set_source(e)
         .set_temporary()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_txn_succeeded() {
+        assert!(check_txn_succeeded(true).is_ok());
+
+        let err = check_txn_succeeded(false).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+        assert!(err.is_temporary());
+    }
+}
@@ -17,7 +17,6 @@
 
 use std::io::SeekFrom;
 use std::path::Path;
-use std::path::PathBuf;
 use std::sync::Arc;
 
 use chrono::DateTime;
@@ -27,6 +26,7 @@ use serde::Serialize;
 
 use super::core::*;
 use super::lister::FsLister;
+use super::lister::FsSortedLister;
 use super::reader::FsReader;
 use super::writer::FsWriter;
 use super::writer::FsWriters;
@@ -43,6 +43,25 @@ pub struct FsConfig {
 
     /// tmp dir for atomic write
     pub atomic_write_dir: Option<String>,
+
+    /// disable reflink and `copy_file_range` while copying files.
+    pub disable_copy_acceleration: bool,
+
+    /// sync file content before the write is reported complete, default to true.
+    pub sync_on_close: Option<bool>,
+
+    /// sync the parent dir after the file has been written.
+    pub sync_dir: bool,
+
+    /// how to handle symlinks, available values:
+    ///
+    /// - follow (default)
+    /// - deny
+    /// - no_follow
+    pub symlink_policy: Option<String>,
+
+    /// return list entries in lexicographic order.
+    pub enable_sorted_list: bool,
 }
 
 impl Configurator for FsConfig {
@@ -81,6 +100,74 @@ impl FsBuilder {
 
         self
     }
+
+    /// Disable copy acceleration.
+    ///
+    /// By default, copy will try reflink (`FICLONE`) and `copy_file_range`
+    /// on Linux before falling back to reading and writing the whole file.
+    /// Disable it if the filesystem behaves badly with them.
+    pub fn disable_copy_acceleration(mut self) -> Self {
+        self.config.disable_copy_acceleration = true;
+        self
+    }
+
+    /// Set whether to sync file content before the write is reported complete.
+    ///
+    /// When enabled, the writer calls `File::sync_all` on close, so that
+    /// the written data survives a crash once `close` returns. This is
+    /// enabled by default, disable it to trade durability for throughput
+    /// if the data can be rebuilt after a crash.
+    pub fn sync_on_close(mut self, enabled: bool) -> Self {
+        self.config.sync_on_close = Some(enabled);
+        self
+    }
+
+    /// Sync the parent dir after the file has been written.
+    ///
+    /// Enable this to also fsync the parent dir after the file is closed
+    /// (and renamed if `atomic_write_dir` is in use), so that the directory
+    /// entry itself survives a crash.
+    ///
+    /// This is off by default since it adds an extra fsync per write.
+    pub fn sync_dir(mut self) -> Self {
+        self.config.sync_dir = true;
+        self
+    }
+
+    /// Set how to handle symlinks inside root.
+    ///
+    /// Available values:
+    ///
+    /// - `follow`: Follow symlinks like other files, this is the default.
+    /// - `deny`: Return `PermissionDenied` while encountering symlinks.
+    /// - `no_follow`: Don't follow symlinks, stat and list will report them
+    ///   as `EntryMode::Unknown`.
+    ///
+    /// For policies other than `follow`, paths will be checked to make
+    /// sure that they can't reach outside of root via symlinks.
+    pub fn symlink_policy(mut self, policy: &str) -> Self {
+        if !policy.is_empty() {
+            self.config.symlink_policy = Some(policy.to_string());
+        }
+        self
+    }
+
+    /// Return list entries in lexicographic order of their paths.
+    ///
+    /// By default, entries are returned in `readdir` order which could be
+    /// different between runs. Enable this to get a stable order like
+    /// object storage services.
+    ///
+    /// # Notes
+    ///
+    /// To keep memory bounded, the dir will be scanned once for every 1000
+    /// entries returned, which makes listing large dirs slower.
+    ///
+    /// Listing with `start_after` always returns sorted entries.
+    pub fn enable_sorted_list(mut self) -> Self {
+        self.config.enable_sorted_list = true;
+        self
+    }
 }
 
 impl Builder for FsBuilder {
@@ -90,7 +177,7 @@ impl Builder for FsBuilder {
     fn build(self) -> Result<impl Access> {
         debug!("backend build started: {:?}", &self);
 
-        let root = match self.config.root.map(PathBuf::from) {
+        let root = match self.config.root.as_deref().map(root_path_of) {
             Some(root) => Ok(root),
             None => Err(Error::new(
                 ErrorKind::ConfigInvalid,
@@ -99,6 +186,19 @@ impl Builder for FsBuilder {
         }?;
         debug!("backend use root {}", root.to_string_lossy());
 
+        let symlink_policy = match self.config.symlink_policy.as_deref() {
+            None | Some("follow") => Ok(SymlinkPolicy::Follow),
+            Some("deny") => Ok(SymlinkPolicy::Deny),
+            Some("no_follow") => Ok(SymlinkPolicy::NoFollow),
+            Some(v) => Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                format!("unknown symlink_policy: {v}").as_str(),
+            )
+            .with_operation("Builder::build")
+            .with_context("service", Scheme::Fs)),
+        }?;
+        debug!("backend use symlink_policy {:?}", symlink_policy);
+
         // If root dir is not exist, we must create it.
         if let Err(e) = std::fs::metadata(&root) {
             if e.kind() == std::io::ErrorKind::NotFound {
@@ -111,7 +211,7 @@ impl Builder for FsBuilder {
             }
         }
 
-        let atomic_write_dir = self.config.atomic_write_dir.map(PathBuf::from);
+        let atomic_write_dir = self.config.atomic_write_dir.as_deref().map(root_path_of);
 
         // If atomic write dir is not exist, we must create it.
         if let Some(d) = &atomic_write_dir {
@@ -160,6 +260,11 @@ impl Builder for FsBuilder {
                 root,
                 atomic_write_dir,
                 buf_pool: oio::PooledBuf::new(16).with_initial_capacity(256 * 1024),
+                copy_acceleration: !self.config.disable_copy_acceleration,
+                sync_on_close: self.config.sync_on_close.unwrap_or(true),
+                sync_dir: self.config.sync_dir,
+                symlink_policy,
+                sorted_list: self.config.enable_sorted_list,
             }),
         })
     }
@@ -174,10 +279,10 @@ pub struct FsBackend {
 impl Access for FsBackend {
     type Reader = FsReader<tokio::fs::File>;
     type Writer = FsWriters;
-    type Lister = Option<FsLister<tokio::fs::ReadDir>>;
+    type Lister = Option<TwoWays<FsLister<tokio::fs::ReadDir>, FsSortedLister>>;
     type BlockingReader = FsReader<std::fs::File>;
     type BlockingWriter = FsWriter<std::fs::File>;
-    type BlockingLister = Option<FsLister<std::fs::ReadDir>>;
+    type BlockingLister = Option<TwoWays<FsLister<std::fs::ReadDir>, FsSortedLister>>;
 
     fn info(&self) -> Arc<AccessorInfo> {
         let mut am = AccessorInfo::default();
@@ -193,9 +298,11 @@ impl Access for FsBackend {
                 write_can_append: true,
                 write_can_multi: true,
                 create_dir: true,
+                create_dir_rejects_file: true,
                 delete: true,
 
                 list: true,
+                list_with_start_after: true,
 
                 copy: true,
                 rename: true,
@@ -208,19 +315,20 @@ impl Access for FsBackend {
     }
 
     async fn create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
-        let p = self.core.root.join(path.trim_end_matches('/'));
+        let p = join_path(&self.core.root, path);
+        self.core.ensure_within_root(&p).await?;
 
         tokio::fs::create_dir_all(&p)
             .await
-            .map_err(new_std_io_error)?;
+            .map_err(new_create_dir_error)?;
 
         Ok(RpCreateDir::default())
     }
 
     async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
-        let p = self.core.root.join(path.trim_end_matches('/'));
+        let p = join_path(&self.core.root, path);
 
-        let meta = tokio::fs::metadata(&p).await.map_err(new_std_io_error)?;
+        let meta = self.core.metadata(&p).await?;
 
         let mode = if meta.is_dir() {
             EntryMode::DIR
@@ -250,7 +358,8 @@ impl Access for FsBackend {
     ///
     /// Benchmark could be found [here](https://gist.github.com/Xuanwo/48f9cfbc3022ea5f865388bb62e1a70f)
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let p = self.core.root.join(path.trim_end_matches('/'));
+        let p = join_path(&self.core.root, path);
+        self.core.ensure_within_root(&p).await?;
 
         let mut f = tokio::fs::OpenOptions::new()
             .read(true)
@@ -317,7 +426,9 @@ impl Access for FsBackend {
             .await
             .map_err(new_std_io_error)?;
 
-        let w = FsWriter::new(target_path, tmp_path, f);
+        let w = FsWriter::new(target_path, tmp_path, f)
+            .with_sync_on_close(self.core.sync_on_close)
+            .with_sync_dir(self.core.sync_dir);
 
         let w = if op.append() {
             FsWriters::One(w)
@@ -333,7 +444,10 @@ impl Access for FsBackend {
     }
 
     async fn delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
-        let p = self.core.root.join(path.trim_end_matches('/'));
+        let p = join_path(&self.core.root, path);
+        if let Some(parent) = p.parent() {
+            self.core.ensure_within_root(parent).await?;
+        }
 
         let meta = tokio::fs::metadata(&p).await;
 
@@ -352,8 +466,25 @@ impl Access for FsBackend {
         }
     }
 
-    async fn list(&self, path: &str, _: OpList) -> Result<(RpList, Self::Lister)> {
-        let p = self.core.root.join(path.trim_end_matches('/'));
+    async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+        let p = join_path(&self.core.root, path);
+        self.core.ensure_within_root(&p).await?;
+
+        if self.core.sorted_list || args.start_after().is_some() {
+            return match tokio::fs::metadata(&p).await {
+                Ok(_) => {
+                    let l = FsSortedLister::new(
+                        &self.core.root,
+                        &p,
+                        self.core.symlink_policy,
+                        args.start_after(),
+                    );
+                    Ok((RpList::default(), Some(TwoWays::Two(l))))
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok((RpList::default(), None)),
+                Err(e) => Err(new_std_io_error(e)),
+            };
+        }
 
         let f = match tokio::fs::read_dir(&p).await {
             Ok(rd) => rd,
@@ -366,13 +497,14 @@ impl Access for FsBackend {
             }
         };
 
-        let rd = FsLister::new(&self.core.root, f);
+        let rd = FsLister::new(&self.core.root, f, self.core.symlink_policy);
 
-        Ok((RpList::default(), Some(rd)))
+        Ok((RpList::default(), Some(TwoWays::One(rd))))
     }
 
     async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
-        let from = self.core.root.join(from.trim_end_matches('/'));
+        let from = join_path(&self.core.root, from);
+        self.core.ensure_within_root(&from).await?;
 
         // try to get the metadata of the source file to ensure it exists
         tokio::fs::metadata(&from).await.map_err(new_std_io_error)?;
@@ -382,13 +514,17 @@ impl Access for FsBackend {
             .ensure_write_abs_path(&self.core.root, to.trim_end_matches('/'))
             .await?;
 
-        tokio::fs::copy(from, to).await.map_err(new_std_io_error)?;
+        let core = self.core.clone();
+        tokio::task::spawn_blocking(move || core.copy_file(&from, &to))
+            .await
+            .map_err(new_task_join_error)??;
 
         Ok(RpCopy::default())
     }
 
     async fn rename(&self, from: &str, to: &str, _args: OpRename) -> Result<RpRename> {
-        let from = self.core.root.join(from.trim_end_matches('/'));
+        let from = join_path(&self.core.root, from);
+        self.core.ensure_within_root(&from).await?;
 
         // try to get the metadata of the source file to ensure it exists
         tokio::fs::metadata(&from).await.map_err(new_std_io_error)?;
@@ -406,17 +542,18 @@ impl Access for FsBackend {
     }
 
     fn blocking_create_dir(&self, path: &str, _: OpCreateDir) -> Result<RpCreateDir> {
-        let p = self.core.root.join(path.trim_end_matches('/'));
+        let p = join_path(&self.core.root, path);
+        self.core.blocking_ensure_within_root(&p)?;
 
-        std::fs::create_dir_all(p).map_err(new_std_io_error)?;
+        std::fs::create_dir_all(p).map_err(new_create_dir_error)?;
 
         Ok(RpCreateDir::default())
     }
 
     fn blocking_stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
-        let p = self.core.root.join(path.trim_end_matches('/'));
+        let p = join_path(&self.core.root, path);
 
-        let meta = std::fs::metadata(p).map_err(new_std_io_error)?;
+        let meta = self.core.blocking_metadata(&p)?;
 
         let mode = if meta.is_dir() {
             EntryMode::DIR
@@ -437,7 +574,8 @@ impl Access for FsBackend {
     }
 
     fn blocking_read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::BlockingReader)> {
-        let p = self.core.root.join(path.trim_end_matches('/'));
+        let p = join_path(&self.core.root, path);
+        self.core.blocking_ensure_within_root(&p)?;
 
         let mut f = std::fs::OpenOptions::new()
             .read(true)
@@ -499,11 +637,18 @@ impl Access for FsBackend {
             .open(tmp_path.as_ref().unwrap_or(&target_path))
             .map_err(new_std_io_error)?;
 
-        Ok((RpWrite::new(), FsWriter::new(target_path, tmp_path, f)))
+        let w = FsWriter::new(target_path, tmp_path, f)
+            .with_sync_on_close(self.core.sync_on_close)
+            .with_sync_dir(self.core.sync_dir);
+
+        Ok((RpWrite::new(), w))
     }
 
     fn blocking_delete(&self, path: &str, _: OpDelete) -> Result<RpDelete> {
-        let p = self.core.root.join(path.trim_end_matches('/'));
+        let p = join_path(&self.core.root, path);
+        if let Some(parent) = p.parent() {
+            self.core.blocking_ensure_within_root(parent)?;
+        }
 
         let meta = std::fs::metadata(&p);
 
@@ -522,8 +667,25 @@ impl Access for FsBackend {
         }
     }
 
-    fn blocking_list(&self, path: &str, _: OpList) -> Result<(RpList, Self::BlockingLister)> {
-        let p = self.core.root.join(path.trim_end_matches('/'));
+    fn blocking_list(&self, path: &str, args: OpList) -> Result<(RpList, Self::BlockingLister)> {
+        let p = join_path(&self.core.root, path);
+        self.core.blocking_ensure_within_root(&p)?;
+
+        if self.core.sorted_list || args.start_after().is_some() {
+            return match std::fs::metadata(&p) {
+                Ok(_) => {
+                    let l = FsSortedLister::new(
+                        &self.core.root,
+                        &p,
+                        self.core.symlink_policy,
+                        args.start_after(),
+                    );
+                    Ok((RpList::default(), Some(TwoWays::Two(l))))
+                }
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok((RpList::default(), None)),
+                Err(e) => Err(new_std_io_error(e)),
+            };
+        }
 
         let f = match std::fs::read_dir(p) {
             Ok(rd) => rd,
@@ -536,13 +698,14 @@ impl Access for FsBackend {
             }
         };
 
-        let rd = FsLister::new(&self.core.root, f);
+        let rd = FsLister::new(&self.core.root, f, self.core.symlink_policy);
 
-        Ok((RpList::default(), Some(rd)))
+        Ok((RpList::default(), Some(TwoWays::One(rd))))
     }
 
     fn blocking_copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
-        let from = self.core.root.join(from.trim_end_matches('/'));
+        let from = join_path(&self.core.root, from);
+        self.core.blocking_ensure_within_root(&from)?;
 
         // try to get the metadata of the source file to ensure it exists
         std::fs::metadata(&from).map_err(new_std_io_error)?;
@@ -551,13 +714,14 @@ impl Access for FsBackend {
             .core
             .blocking_ensure_write_abs_path(&self.core.root, to.trim_end_matches('/'))?;
 
-        std::fs::copy(from, to).map_err(new_std_io_error)?;
+        self.core.copy_file(&from, &to)?;
 
         Ok(RpCopy::default())
     }
 
     fn blocking_rename(&self, from: &str, to: &str, _args: OpRename) -> Result<RpRename> {
-        let from = self.core.root.join(from.trim_end_matches('/'));
+        let from = join_path(&self.core.root, from);
+        self.core.blocking_ensure_within_root(&from)?;
 
         // try to get the metadata of the source file to ensure it exists
         std::fs::metadata(&from).map_err(new_std_io_error)?;
@@ -572,6 +736,19 @@ impl Access for FsBackend {
     }
 }
 
+/// `create_dir_all` returns `AlreadyExists` only if the path exists but is
+/// not a directory.
+fn new_create_dir_error(err: std::io::Error) -> Error {
+    if err.kind() == std::io::ErrorKind::AlreadyExists {
+        return Error::new(
+            ErrorKind::NotADirectory,
+            "a file already exists at the path trying to create dir",
+        )
+        .set_source(err);
+    }
+    new_std_io_error(err)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -590,4 +767,76 @@ mod tests {
             assert!(tmp_file.starts_with(expected_prefix));
         }
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_policy() {
+        let base = std::env::temp_dir().join(format!("opendal-{}", uuid::Uuid::new_v4()));
+        let root = base.join("root");
+        let outside = base.join("outside");
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::create_dir_all(&outside).unwrap();
+        std::fs::write(outside.join("secret"), "secret").unwrap();
+        std::os::unix::fs::symlink(&outside, root.join("link")).unwrap();
+
+        let build = |policy: &str| {
+            let builder = FsBuilder::default()
+                .root(&root.to_string_lossy())
+                .symlink_policy(policy);
+            Operator::new(builder).unwrap().finish().blocking()
+        };
+
+        let op = build("follow");
+        assert_eq!(op.stat("link").unwrap().mode(), EntryMode::DIR);
+        assert_eq!(op.read("link/secret").unwrap().to_vec(), b"secret");
+
+        let op = build("no_follow");
+        assert_eq!(op.stat("link").unwrap().mode(), EntryMode::Unknown);
+        let err = op.read("link/secret").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = op.write("link/new", "data").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = op.rename("link/secret", "moved").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        assert!(outside.join("secret").exists());
+
+        let op = build("deny");
+        let err = op.stat("link").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = op.list("/").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        let err = op.rename("link/secret", "moved").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::PermissionDenied);
+        assert!(outside.join("secret").exists());
+
+        assert!(FsBuilder::default()
+            .root(&root.to_string_lossy())
+            .symlink_policy("unknown")
+            .build()
+            .is_err());
+
+        std::fs::remove_dir_all(&base).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_sync_on_close() {
+        let root = std::env::temp_dir().join(format!("opendal-{}", uuid::Uuid::new_v4()));
+
+        // Keep syncing on close unless disabled explicitly.
+        assert_eq!(FsConfig::default().sync_on_close, None);
+
+        let builder = FsBuilder::default()
+            .root(&root.to_string_lossy())
+            .sync_on_close(false);
+        assert_eq!(builder.config.sync_on_close, Some(false));
+
+        let op = Operator::new(builder).unwrap().finish();
+        op.write("async", "hello").await.unwrap();
+        assert_eq!(op.read("async").await.unwrap().to_vec(), b"hello");
+        let op = op.blocking();
+        op.write("blocking", "world").unwrap();
+        assert_eq!(op.read("blocking").unwrap().to_vec(), b"world");
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
 }
@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::io;
 use std::path::Path;
 use std::path::PathBuf;
 
@@ -23,17 +24,36 @@ use uuid::Uuid;
 use crate::raw::*;
 use crate::*;
 
+/// How to handle symlinks inside root.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SymlinkPolicy {
+    /// Follow symlinks, this is the default behavior.
+    Follow,
+    /// Return an error while encountering symlinks.
+    Deny,
+    /// Don't follow symlinks, report them as `EntryMode::Unknown`.
+    NoFollow,
+}
+
 #[derive(Debug)]
 pub struct FsCore {
     pub root: PathBuf,
     pub atomic_write_dir: Option<PathBuf>,
     pub buf_pool: oio::PooledBuf,
+    pub copy_acceleration: bool,
+    pub sync_on_close: bool,
+    pub sync_dir: bool,
+    pub symlink_policy: SymlinkPolicy,
+    pub sorted_list: bool,
 }
 
 impl FsCore {
     // Synchronously build write path and ensure the parent dirs created
     pub fn blocking_ensure_write_abs_path(&self, parent: &Path, path: &str) -> Result<PathBuf> {
-        let p = parent.join(path);
+        let p = join_path(parent, path);
+        if parent == self.root {
+            self.blocking_ensure_within_root(&p)?;
+        }
 
         // Create dir before write path.
         //
@@ -59,7 +79,10 @@ impl FsCore {
 
     // Build write path and ensure the parent dirs created
     pub async fn ensure_write_abs_path(&self, parent: &Path, path: &str) -> Result<PathBuf> {
-        let p = parent.join(path);
+        let p = join_path(parent, path);
+        if parent == self.root {
+            self.ensure_within_root(&p).await?;
+        }
 
         // Create dir before write path.
         //
@@ -84,6 +107,275 @@ impl FsCore {
 
         Ok(p)
     }
+
+    /// Make sure `p` doesn't reach outside of root via symlinks.
+    ///
+    /// - `Deny`: every existing ancestor of `p` under root must not be a symlink.
+    /// - `NoFollow`: the nearest existing ancestor of `p` must resolve into root.
+    ///
+    /// `p` may not exist yet (for example, while writing), so missing
+    /// components are skipped.
+    pub fn blocking_ensure_within_root(&self, p: &Path) -> Result<()> {
+        let mut cur = Some(p);
+        while let Some(v) = cur {
+            match self.symlink_policy {
+                SymlinkPolicy::Follow => return Ok(()),
+                SymlinkPolicy::Deny => {
+                    if !self.is_under_root(v) {
+                        return Ok(());
+                    }
+                    match std::fs::symlink_metadata(v) {
+                        Ok(meta) if meta.is_symlink() => return Err(new_symlink_denied_error(v)),
+                        Err(err) if err.kind() != io::ErrorKind::NotFound => {
+                            return Err(new_std_io_error(err))
+                        }
+                        _ => cur = v.parent(),
+                    }
+                }
+                SymlinkPolicy::NoFollow => match std::fs::canonicalize(v) {
+                    Ok(real) => return self.check_resolved_path(v, &real),
+                    Err(err) if err.kind() == io::ErrorKind::NotFound => cur = v.parent(),
+                    Err(err) => return Err(new_std_io_error(err)),
+                },
+            }
+        }
+        Ok(())
+    }
+
+    /// Make sure `p` doesn't reach outside of root via symlinks.
+    ///
+    /// Async version of [`FsCore::blocking_ensure_within_root`].
+    pub async fn ensure_within_root(&self, p: &Path) -> Result<()> {
+        let mut cur = Some(p);
+        while let Some(v) = cur {
+            match self.symlink_policy {
+                SymlinkPolicy::Follow => return Ok(()),
+                SymlinkPolicy::Deny => {
+                    if !self.is_under_root(v) {
+                        return Ok(());
+                    }
+                    match tokio::fs::symlink_metadata(v).await {
+                        Ok(meta) if meta.is_symlink() => return Err(new_symlink_denied_error(v)),
+                        Err(err) if err.kind() != io::ErrorKind::NotFound => {
+                            return Err(new_std_io_error(err))
+                        }
+                        _ => cur = v.parent(),
+                    }
+                }
+                SymlinkPolicy::NoFollow => match tokio::fs::canonicalize(v).await {
+                    Ok(real) => return self.check_resolved_path(v, &real),
+                    Err(err) if err.kind() == io::ErrorKind::NotFound => cur = v.parent(),
+                    Err(err) => return Err(new_std_io_error(err)),
+                },
+            }
+        }
+        Ok(())
+    }
+
+    /// Root itself is not checked since it has been canonicalized while building.
+    fn is_under_root(&self, p: &Path) -> bool {
+        p != self.root && p.starts_with(&self.root)
+    }
+
+    fn check_resolved_path(&self, p: &Path, real: &Path) -> Result<()> {
+        if !real.starts_with(&self.root) {
+            return Err(Error::new(
+                ErrorKind::PermissionDenied,
+                "path resolves outside of root via symlink",
+            )
+            .with_context("path", p.to_string_lossy())
+            .with_context("resolved", real.to_string_lossy()));
+        }
+        Ok(())
+    }
+
+    /// Fetch the metadata of `p` according to symlink policy.
+    pub fn blocking_metadata(&self, p: &Path) -> Result<std::fs::Metadata> {
+        if self.symlink_policy == SymlinkPolicy::Follow {
+            return std::fs::metadata(p).map_err(new_std_io_error);
+        }
+
+        if let Some(parent) = p.parent() {
+            self.blocking_ensure_within_root(parent)?;
+        }
+        let meta = std::fs::symlink_metadata(p).map_err(new_std_io_error)?;
+        if self.symlink_policy == SymlinkPolicy::Deny && meta.is_symlink() {
+            return Err(new_symlink_denied_error(p));
+        }
+        Ok(meta)
+    }
+
+    /// Fetch the metadata of `p` according to symlink policy.
+    pub async fn metadata(&self, p: &Path) -> Result<std::fs::Metadata> {
+        if self.symlink_policy == SymlinkPolicy::Follow {
+            return tokio::fs::metadata(p).await.map_err(new_std_io_error);
+        }
+
+        if let Some(parent) = p.parent() {
+            self.ensure_within_root(parent).await?;
+        }
+        let meta = tokio::fs::symlink_metadata(p)
+            .await
+            .map_err(new_std_io_error)?;
+        if self.symlink_policy == SymlinkPolicy::Deny && meta.is_symlink() {
+            return Err(new_symlink_denied_error(p));
+        }
+        Ok(meta)
+    }
+
+    /// Copy file from `from` to `to`.
+    ///
+    /// If copy acceleration is enabled, we will try reflink and
+    /// `copy_file_range` first and fallback to `std::fs::copy` if the
+    /// underlying filesystem doesn't support them.
+    pub fn copy_file(&self, from: &Path, to: &Path) -> Result<()> {
+        if self.copy_acceleration && accelerated_copy(from, to).map_err(new_std_io_error)? {
+            return Ok(());
+        }
+
+        std::fs::copy(from, to).map_err(new_std_io_error)?;
+        Ok(())
+    }
+}
+
+/// Try to copy file via reflink (`FICLONE`) and `copy_file_range`.
+///
+/// Returns `Ok(false)` if both of them are not supported, for example
+/// the files are on different devices (`EXDEV`), so that caller can
+/// fallback to generic copy.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn accelerated_copy(from: &Path, to: &Path) -> io::Result<bool> {
+    use std::os::unix::io::AsRawFd;
+
+    let src = std::fs::File::open(from)?;
+    let meta = src.metadata()?;
+    if !meta.is_file() {
+        return Ok(false);
+    }
+    let dst = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(true)
+        .open(to)?;
+    // Keep the same behavior as `std::fs::copy`: only permissions will
+    // be copied, mtime will be the time of copy.
+    dst.set_permissions(meta.permissions())?;
+
+    // SAFETY: both fds are valid until the end of this function.
+    let ret = unsafe { libc::ioctl(dst.as_raw_fd(), libc::FICLONE as _, src.as_raw_fd()) };
+    if ret == 0 {
+        return Ok(true);
+    }
+    // Reflink is not supported, try `copy_file_range` instead.
+
+    let mut off_in: libc::loff_t = 0;
+    let mut off_out: libc::loff_t = 0;
+    let mut remaining = meta.len();
+    while remaining > 0 {
+        let len = remaining.min(1 << 30) as usize;
+        // SAFETY: both fds are valid and offsets point to living values.
+        let n = unsafe {
+            libc::copy_file_range(
+                src.as_raw_fd(),
+                &mut off_in,
+                dst.as_raw_fd(),
+                &mut off_out,
+                len,
+                0,
+            )
+        };
+        if n < 0 {
+            let err = io::Error::last_os_error();
+            // Nothing has been copied yet, it's safe to fallback.
+            if off_out == 0
+                && matches!(
+                    err.raw_os_error(),
+                    Some(
+                        libc::EXDEV
+                            | libc::ENOSYS
+                            | libc::EOPNOTSUPP
+                            | libc::EINVAL
+                            | libc::EPERM
+                            | libc::EBADF
+                    )
+                )
+            {
+                return Ok(false);
+            }
+            return Err(err);
+        }
+        // The source file has been truncated during copy.
+        if n == 0 {
+            break;
+        }
+        remaining -= n as u64;
+    }
+
+    Ok(true)
+}
+
+/// macOS's `std::fs::copy` already tries `fclonefileat` before copying
+/// data, so we don't need to do anything here.
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn accelerated_copy(_: &Path, _: &Path) -> io::Result<bool> {
+    Ok(false)
+}
+
+pub fn new_symlink_denied_error(p: &Path) -> Error {
+    Error::new(
+        ErrorKind::PermissionDenied,
+        "symlink is denied by symlink_policy",
+    )
+    .with_context("path", p.to_string_lossy())
+}
+
+/// Join OpenDAL's `path` to `root` component by component.
+///
+/// On Windows, root is canonicalized with the extended-length prefix
+/// (`\\?\` or `\\?\UNC\` for UNC shares), under which `/` is not
+/// treated as a separator anymore, so we can't join the path directly.
+pub fn join_path(root: &Path, path: &str) -> PathBuf {
+    let mut p = root.to_path_buf();
+    for seg in path.split('/').filter(|v| !v.is_empty()) {
+        p.push(seg);
+    }
+    p
+}
+
+/// Build root path from user input.
+///
+/// On Windows, absolute paths will be converted into the extended-length
+/// form so that roots longer than `MAX_PATH` (260) or on UNC shares can be
+/// created before canonicalizing.
+pub fn root_path_of(v: &str) -> PathBuf {
+    if cfg!(windows) {
+        PathBuf::from(extended_length_path(v))
+    } else {
+        PathBuf::from(v)
+    }
+}
+
+/// Convert an absolute windows path into the extended-length form:
+///
+/// - `C:\data` => `\\?\C:\data`
+/// - `\\server\share\data` => `\\?\UNC\server\share\data`
+///
+/// Paths already with prefix, relative and drive-relative (`C:data`) paths
+/// are returned as is, since the prefix can't be applied to them.
+fn extended_length_path(v: &str) -> String {
+    if v.starts_with(r"\\?\") || v.starts_with(r"\\.\") {
+        return v.to_string();
+    }
+
+    let v = v.replace('/', "\\");
+    if let Some(rest) = v.strip_prefix(r"\\") {
+        return format!(r"\\?\UNC\{rest}");
+    }
+    let bs = v.as_bytes();
+    if bs.len() >= 3 && bs[0].is_ascii_alphabetic() && bs[1] == b':' && bs[2] == b'\\' {
+        return format!(r"\\?\{v}");
+    }
+    v
 }
 
 #[inline]
@@ -93,3 +385,48 @@ pub fn tmp_file_of(path: &str) -> String {
 
     format!("{name}.{uuid}")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_join_path() {
+        let root = Path::new("/tmp/root");
+        assert_eq!(join_path(root, "a/b/c"), root.join("a").join("b").join("c"));
+        assert_eq!(join_path(root, "a/b/"), root.join("a").join("b"));
+        assert_eq!(join_path(root, "/"), root.to_path_buf());
+
+        // Path longer than MAX_PATH should be joined as is.
+        let long = (0..30).map(|i| format!("dir-{i:05}")).collect::<Vec<_>>();
+        let p = join_path(root, &long.join("/"));
+        assert!(p.to_string_lossy().len() > 260);
+        assert_eq!(p.components().count(), root.components().count() + 30);
+    }
+
+    #[test]
+    fn test_extended_length_path() {
+        let cases = vec![
+            ("drive", r"C:\data", r"\\?\C:\data"),
+            ("drive with slash", "C:/data/dir", r"\\?\C:\data\dir"),
+            ("unc", r"\\server\share\data", r"\\?\UNC\server\share\data"),
+            ("unc with slash", "//server/share", r"\\?\UNC\server\share"),
+            ("verbatim", r"\\?\C:\data", r"\\?\C:\data"),
+            (
+                "verbatim unc",
+                r"\\?\UNC\server\share",
+                r"\\?\UNC\server\share",
+            ),
+            ("device", r"\\.\pipe\name", r"\\.\pipe\name"),
+            ("drive relative", "C:data", "C:data"),
+            ("relative", r"data\dir", r"data\dir"),
+        ];
+
+        for (name, input, expected) in cases {
+            assert_eq!(extended_length_path(input), expected, "{name}");
+        }
+
+        let long = format!(r"C:\{}", "a".repeat(300));
+        assert_eq!(extended_length_path(&long), format!(r"\\?\{long}"));
+    }
+}
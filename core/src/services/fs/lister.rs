@@ -15,9 +15,14 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::collections::VecDeque;
 use std::path::Path;
 use std::path::PathBuf;
 
+use super::core::new_symlink_denied_error;
+use super::core::SymlinkPolicy;
 use crate::raw::*;
 use crate::EntryMode;
 use crate::Metadata;
@@ -25,14 +30,16 @@ use crate::Result;
 
 pub struct FsLister<P> {
     root: PathBuf,
+    symlink_policy: SymlinkPolicy,
 
     rd: P,
 }
 
 impl<P> FsLister<P> {
-    pub fn new(root: &Path, rd: P) -> Self {
+    pub fn new(root: &Path, rd: P, symlink_policy: SymlinkPolicy) -> Self {
         Self {
             root: root.to_owned(),
+            symlink_policy,
             rd,
         }
     }
@@ -49,7 +56,7 @@ impl oio::List for FsLister<tokio::fs::ReadDir> {
             return Ok(None);
         };
 
-        let ft = de.file_type().await.map_err(new_std_io_error)?;
+        let mut ft = de.file_type().await.map_err(new_std_io_error)?;
 
         let entry_path = de.path();
         let rel_path = normalize_path(
@@ -60,6 +67,20 @@ impl oio::List for FsLister<tokio::fs::ReadDir> {
                 .replace('\\', "/"),
         );
 
+        if ft.is_symlink() {
+            match self.symlink_policy {
+                SymlinkPolicy::Follow => {
+                    // Use the type of target instead, broken symlinks will be
+                    // reported as unknown.
+                    if let Ok(meta) = tokio::fs::metadata(&entry_path).await {
+                        ft = meta.file_type();
+                    }
+                }
+                SymlinkPolicy::Deny => return Err(new_symlink_denied_error(&entry_path)),
+                SymlinkPolicy::NoFollow => {}
+            }
+        }
+
         let d = if ft.is_file() {
             oio::Entry::new(&rel_path, Metadata::new(EntryMode::FILE))
         } else if ft.is_dir() {
@@ -80,30 +101,197 @@ impl oio::BlockingList for FsLister<std::fs::ReadDir> {
             None => return Ok(None),
         };
 
-        let entry_path = de.path();
-        let rel_path = normalize_path(
-            &entry_path
-                .strip_prefix(&self.root)
-                .expect("cannot fail because the prefix is iterated")
-                .to_string_lossy()
-                .replace('\\', "/"),
-        );
+        blocking_entry_of(&self.root, &de, self.symlink_policy).map(Some)
+    }
+}
 
-        // On Windows and most Unix platforms this function is free
-        // (no extra system calls needed), but some Unix platforms may
-        // require the equivalent call to symlink_metadata to learn about
-        // the target file type.
-        let file_type = de.file_type().map_err(new_std_io_error)?;
+/// The max entries that [`FsSortedLister`] will keep in memory.
+const SORTED_LIST_CHUNK_SIZE: usize = 1000;
 
-        let entry = if file_type.is_file() {
-            oio::Entry::new(&rel_path, Metadata::new(EntryMode::FILE))
-        } else if file_type.is_dir() {
-            // Make sure we are returning the correct path.
-            oio::Entry::new(&format!("{rel_path}/"), Metadata::new(EntryMode::DIR))
-        } else {
-            oio::Entry::new(&rel_path, Metadata::new(EntryMode::Unknown))
-        };
+/// FsSortedLister returns entries in lexicographic order of their paths.
+///
+/// Instead of loading the whole dir into memory, every chunk is built by
+/// scanning the dir again and keeping only the smallest
+/// `SORTED_LIST_CHUNK_SIZE` entries after the last returned one. So the
+/// memory usage is bounded no matter how large the dir is, and listing can
+/// be resumed by `start_after`.
+pub struct FsSortedLister {
+    root: PathBuf,
+    path: PathBuf,
+    symlink_policy: SymlinkPolicy,
+
+    /// The last path we have scanned, entries must be greater than it.
+    start_after: Option<String>,
+    entries: VecDeque<oio::Entry>,
+    done: bool,
+}
+
+impl FsSortedLister {
+    pub fn new(
+        root: &Path,
+        path: &Path,
+        symlink_policy: SymlinkPolicy,
+        start_after: Option<&str>,
+    ) -> Self {
+        Self {
+            root: root.to_owned(),
+            path: path.to_owned(),
+            symlink_policy,
+
+            start_after: start_after.map(|v| v.to_string()),
+            entries: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    fn blocking_scan(&mut self) -> Result<()> {
+        let chunk = blocking_scan_sorted(
+            &self.root,
+            &self.path,
+            self.symlink_policy,
+            self.start_after.as_deref(),
+        )?;
+        self.extend(chunk);
+        Ok(())
+    }
+
+    fn extend(&mut self, chunk: Vec<oio::Entry>) {
+        self.done = chunk.len() < SORTED_LIST_CHUNK_SIZE;
+        if let Some(entry) = chunk.last() {
+            self.start_after = Some(entry.path().to_string());
+        }
+        self.entries.extend(chunk);
+    }
+}
+
+impl oio::List for FsSortedLister {
+    async fn next(&mut self) -> Result<Option<oio::Entry>> {
+        if self.entries.is_empty() && !self.done {
+            let root = self.root.clone();
+            let path = self.path.clone();
+            let symlink_policy = self.symlink_policy;
+            let start_after = self.start_after.clone();
+
+            let chunk = tokio::task::spawn_blocking(move || {
+                blocking_scan_sorted(&root, &path, symlink_policy, start_after.as_deref())
+            })
+            .await
+            .map_err(new_task_join_error)??;
+            self.extend(chunk);
+        }
+
+        Ok(self.entries.pop_front())
+    }
+}
+
+impl oio::BlockingList for FsSortedLister {
+    fn next(&mut self) -> Result<Option<oio::Entry>> {
+        if self.entries.is_empty() && !self.done {
+            self.blocking_scan()?;
+        }
 
-        Ok(Some(entry))
+        Ok(self.entries.pop_front())
     }
 }
+
+/// Entry ordered by its path only.
+struct SortedEntry(oio::Entry);
+
+impl PartialEq for SortedEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.path() == other.0.path()
+    }
+}
+
+impl Eq for SortedEntry {}
+
+impl PartialOrd for SortedEntry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for SortedEntry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.path().cmp(other.0.path())
+    }
+}
+
+/// Scan the whole dir and return the smallest `SORTED_LIST_CHUNK_SIZE`
+/// entries whose path are greater than `start_after` in order.
+fn blocking_scan_sorted(
+    root: &Path,
+    path: &Path,
+    symlink_policy: SymlinkPolicy,
+    start_after: Option<&str>,
+) -> Result<Vec<oio::Entry>> {
+    let rd = match std::fs::read_dir(path) {
+        Ok(rd) => rd,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(err) => return Err(new_std_io_error(err)),
+    };
+
+    // Max heap so that we can drop the largest one while it's full.
+    let mut heap = BinaryHeap::with_capacity(SORTED_LIST_CHUNK_SIZE + 1);
+    for de in rd {
+        let de = de.map_err(new_std_io_error)?;
+        let entry = SortedEntry(blocking_entry_of(root, &de, symlink_policy)?);
+        if start_after.is_some_and(|v| entry.0.path() <= v) {
+            continue;
+        }
+
+        if heap.len() < SORTED_LIST_CHUNK_SIZE {
+            heap.push(entry);
+        } else if heap.peek().is_some_and(|top| &entry < top) {
+            heap.pop();
+            heap.push(entry);
+        }
+    }
+
+    Ok(heap.into_sorted_vec().into_iter().map(|v| v.0).collect())
+}
+
+fn blocking_entry_of(
+    root: &Path,
+    de: &std::fs::DirEntry,
+    symlink_policy: SymlinkPolicy,
+) -> Result<oio::Entry> {
+    let entry_path = de.path();
+    let rel_path = normalize_path(
+        &entry_path
+            .strip_prefix(root)
+            .expect("cannot fail because the prefix is iterated")
+            .to_string_lossy()
+            .replace('\\', "/"),
+    );
+
+    // On Windows and most Unix platforms this function is free
+    // (no extra system calls needed), but some Unix platforms may
+    // require the equivalent call to symlink_metadata to learn about
+    // the target file type.
+    let mut file_type = de.file_type().map_err(new_std_io_error)?;
+    if file_type.is_symlink() {
+        match symlink_policy {
+            SymlinkPolicy::Follow => {
+                // Use the type of target instead, broken symlinks will be
+                // reported as unknown.
+                if let Ok(meta) = std::fs::metadata(&entry_path) {
+                    file_type = meta.file_type();
+                }
+            }
+            SymlinkPolicy::Deny => return Err(new_symlink_denied_error(&entry_path)),
+            SymlinkPolicy::NoFollow => {}
+        }
+    }
+
+    let entry = if file_type.is_file() {
+        oio::Entry::new(&rel_path, Metadata::new(EntryMode::FILE))
+    } else if file_type.is_dir() {
+        // Make sure we are returning the correct path.
+        oio::Entry::new(&format!("{rel_path}/"), Metadata::new(EntryMode::DIR))
+    } else {
+        oio::Entry::new(&rel_path, Metadata::new(EntryMode::Unknown))
+    };
+
+    Ok(entry)
+}
@@ -17,6 +17,7 @@
 
 use std::fs::File;
 use std::io::Write;
+use std::path::Path;
 use std::path::PathBuf;
 
 use bytes::Buf;
@@ -31,6 +32,8 @@ pub type FsWriters =
 pub struct FsWriter<F> {
     target_path: PathBuf,
     tmp_path: Option<PathBuf>,
+    sync_on_close: bool,
+    sync_dir: bool,
 
     f: Option<F>,
 }
@@ -40,10 +43,24 @@ impl<F> FsWriter<F> {
         Self {
             target_path,
             tmp_path,
+            sync_on_close: true,
+            sync_dir: false,
 
             f: Some(f),
         }
     }
+
+    /// Sync the file content before reporting the write complete.
+    pub fn with_sync_on_close(mut self, sync_on_close: bool) -> Self {
+        self.sync_on_close = sync_on_close;
+        self
+    }
+
+    /// Sync the parent dir of target path after the file has been closed.
+    pub fn with_sync_dir(mut self, sync_dir: bool) -> Self {
+        self.sync_dir = sync_dir;
+        self
+    }
 }
 
 /// # Safety
@@ -63,17 +80,22 @@ impl oio::Write for FsWriter<tokio::fs::File> {
         Ok(())
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         let f = self.f.as_mut().expect("FsWriter must be initialized");
         f.flush().await.map_err(new_std_io_error)?;
-        f.sync_all().await.map_err(new_std_io_error)?;
+        if self.sync_on_close {
+            f.sync_all().await.map_err(new_std_io_error)?;
+        }
 
         if let Some(tmp_path) = &self.tmp_path {
             tokio::fs::rename(tmp_path, &self.target_path)
                 .await
                 .map_err(new_std_io_error)?;
         }
-        Ok(())
+        if self.sync_dir {
+            sync_parent_dir(self.target_path.clone()).await?;
+        }
+        Ok(Metadata::new(EntryMode::FILE))
     }
 
     async fn abort(&mut self) -> Result<()> {
@@ -102,16 +124,21 @@ impl oio::BlockingWrite for FsWriter<std::fs::File> {
         Ok(())
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         if let Some(f) = self.f.take() {
-            f.sync_all().map_err(new_std_io_error)?;
+            if self.sync_on_close {
+                f.sync_all().map_err(new_std_io_error)?;
+            }
 
             if let Some(tmp_path) = &self.tmp_path {
                 std::fs::rename(tmp_path, &self.target_path).map_err(new_std_io_error)?;
             }
+            if self.sync_dir {
+                blocking_sync_parent_dir(&self.target_path)?;
+            }
         }
 
-        Ok(())
+        Ok(Metadata::new(EntryMode::FILE))
     }
 }
 
@@ -144,7 +171,7 @@ impl oio::PositionWrite for FsWriter<tokio::fs::File> {
         .map_err(new_task_join_error)?
     }
 
-    async fn close(&self) -> Result<()> {
+    async fn close(&self) -> Result<Metadata> {
         let f = self.f.as_ref().expect("FsWriter must be initialized");
 
         let mut f = f
@@ -155,14 +182,19 @@ impl oio::PositionWrite for FsWriter<tokio::fs::File> {
             .await;
 
         f.flush().map_err(new_std_io_error)?;
-        f.sync_all().map_err(new_std_io_error)?;
+        if self.sync_on_close {
+            f.sync_all().map_err(new_std_io_error)?;
+        }
 
         if let Some(tmp_path) = &self.tmp_path {
             tokio::fs::rename(tmp_path, &self.target_path)
                 .await
                 .map_err(new_std_io_error)?;
         }
-        Ok(())
+        if self.sync_dir {
+            sync_parent_dir(self.target_path.clone()).await?;
+        }
+        Ok(Metadata::new(EntryMode::FILE))
     }
 
     async fn abort(&self) -> Result<()> {
@@ -179,6 +211,31 @@ impl oio::PositionWrite for FsWriter<tokio::fs::File> {
     }
 }
 
+async fn sync_parent_dir(path: PathBuf) -> Result<()> {
+    tokio::task::spawn_blocking(move || blocking_sync_parent_dir(&path))
+        .await
+        .map_err(new_task_join_error)?
+}
+
+/// Sync the parent dir so that the newly created or renamed entry is durable.
+#[cfg(unix)]
+fn blocking_sync_parent_dir(path: &Path) -> Result<()> {
+    let Some(parent) = path.parent() else {
+        return Ok(());
+    };
+
+    File::open(parent)
+        .and_then(|dir| dir.sync_all())
+        .map_err(|err| new_std_io_error(err).with_context("dir", parent.to_string_lossy()))
+}
+
+/// Directories can't be opened as files on windows, and entries are
+/// persisted by NTFS journal, so there is nothing to do here.
+#[cfg(not(unix))]
+fn blocking_sync_parent_dir(_: &Path) -> Result<()> {
+    Ok(())
+}
+
 #[cfg(windows)]
 fn write_at(f: &File, buf: &[u8], offset: u64) -> Result<usize> {
     use std::os::windows::fs::FileExt;
@@ -20,12 +20,19 @@ use std::fmt::Formatter;
 use std::str;
 use std::str::FromStr;
 use std::sync::Arc;
+use std::time::SystemTime;
 
 use async_tls::TlsConnector;
 use bb8::PooledConnection;
 use bb8::RunError;
 use http::Uri;
 use log::debug;
+use rustls::client::ServerCertVerified;
+use rustls::client::ServerCertVerifier;
+use rustls::Certificate;
+use rustls::ClientConfig;
+use rustls::RootCertStore;
+use rustls::ServerName;
 use serde::Deserialize;
 use serde::Serialize;
 use suppaftp::list::File;
@@ -35,6 +42,7 @@ use suppaftp::AsyncRustlsConnector;
 use suppaftp::AsyncRustlsFtpStream;
 use suppaftp::FtpError;
 use suppaftp::ImplAsyncFtpStream;
+use suppaftp::Mode;
 use suppaftp::Status;
 use tokio::sync::OnceCell;
 use uuid::Uuid;
@@ -59,6 +67,16 @@ pub struct FtpConfig {
     pub user: Option<String>,
     /// password of this backend
     pub password: Option<String>,
+    /// Accept invalid certificates while upgrading to TLS.
+    ///
+    /// This is dangerous and should only be used in lab setups.
+    pub accept_invalid_certs: bool,
+    /// Path to the PEM encoded root CA certificates used to verify server.
+    pub root_ca_path: Option<String>,
+    /// Use EPSV instead of PASV for data connections.
+    pub enable_epsv: bool,
+    /// Don't fall back to PASV if server doesn't support EPSV.
+    pub disable_epsv_fallback: bool,
 }
 
 impl Debug for FtpConfig {
@@ -66,6 +84,10 @@ impl Debug for FtpConfig {
         f.debug_struct("FtpConfig")
             .field("endpoint", &self.endpoint)
             .field("root", &self.root)
+            .field("accept_invalid_certs", &self.accept_invalid_certs)
+            .field("root_ca_path", &self.root_ca_path)
+            .field("enable_epsv", &self.enable_epsv)
+            .field("disable_epsv_fallback", &self.disable_epsv_fallback)
             .finish_non_exhaustive()
     }
 }
@@ -135,6 +157,48 @@ impl FtpBuilder {
 
         self
     }
+
+    /// Accept invalid certificates while upgrading to TLS via `AUTH TLS`.
+    ///
+    /// # Warning
+    ///
+    /// This disables certificate verification entirely, please only use it
+    /// in lab setups.
+    pub fn accept_invalid_certs(mut self) -> Self {
+        self.config.accept_invalid_certs = true;
+        self
+    }
+
+    /// set the path to PEM encoded root CA certificates for ftps backend.
+    ///
+    /// If set, only these certificates will be trusted while verifying server.
+    pub fn root_ca_path(mut self, path: &str) -> Self {
+        self.config.root_ca_path = if path.is_empty() {
+            None
+        } else {
+            Some(path.to_string())
+        };
+
+        self
+    }
+
+    /// Use EPSV instead of PASV for data connections.
+    ///
+    /// EPSV only returns a port, so the data connection will always go to
+    /// the same host as the control connection, which works better behind NAT.
+    ///
+    /// If server doesn't advertise EPSV in `FEAT`, we will fall back to PASV
+    /// unless `disable_epsv_fallback` is set.
+    pub fn enable_epsv(mut self) -> Self {
+        self.config.enable_epsv = true;
+        self
+    }
+
+    /// Don't fall back to PASV if server doesn't support EPSV.
+    pub fn disable_epsv_fallback(mut self) -> Self {
+        self.config.disable_epsv_fallback = true;
+        self
+    }
 }
 
 impl Builder for FtpBuilder {
@@ -161,6 +225,7 @@ impl Builder for FtpBuilder {
         let port = endpoint_uri.port_u16().unwrap_or(21);
 
         let endpoint = format!("{host}:{port}");
+        let host = host.to_string();
 
         let enable_secure = match endpoint_uri.scheme_str() {
             Some("ftp") => false,
@@ -189,23 +254,104 @@ impl Builder for FtpBuilder {
             Some(v) => v.clone(),
         };
 
+        let tls_config = if enable_secure {
+            build_tls_config(
+                self.config.accept_invalid_certs,
+                self.config.root_ca_path.as_deref(),
+            )?
+        } else {
+            None
+        };
+
         Ok(FtpBackend {
             endpoint,
+            host,
             root,
             user,
             password,
             enable_secure,
+            tls_config,
+            enable_epsv: self.config.enable_epsv,
+            disable_epsv_fallback: self.config.disable_epsv_fallback,
             pool: OnceCell::new(),
         })
     }
 }
 
+/// Build tls config for ftps, returns `None` if the default config should be used.
+fn build_tls_config(
+    accept_invalid_certs: bool,
+    root_ca_path: Option<&str>,
+) -> Result<Option<Arc<ClientConfig>>> {
+    if !accept_invalid_certs && root_ca_path.is_none() {
+        return Ok(None);
+    }
+
+    let mut roots = RootCertStore::empty();
+    if let Some(path) = root_ca_path {
+        let content = std::fs::read(path).map_err(|err| {
+            Error::new(ErrorKind::ConfigInvalid, "failed to read root ca")
+                .with_context("service", Scheme::Ftp)
+                .with_context("root_ca_path", path)
+                .set_source(err)
+        })?;
+        let certs = rustls_pemfile::certs(&mut content.as_slice()).map_err(|err| {
+            Error::new(ErrorKind::ConfigInvalid, "root ca is not valid PEM")
+                .with_context("service", Scheme::Ftp)
+                .with_context("root_ca_path", path)
+                .set_source(err)
+        })?;
+        let (valid, _) = roots.add_parsable_certificates(&certs);
+        if valid == 0 {
+            return Err(Error::new(
+                ErrorKind::ConfigInvalid,
+                "root ca contains no valid certificate",
+            )
+            .with_context("service", Scheme::Ftp)
+            .with_context("root_ca_path", path));
+        }
+    }
+
+    let mut config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    if accept_invalid_certs {
+        config
+            .dangerous()
+            .set_certificate_verifier(Arc::new(NoCertificateVerification));
+    }
+
+    Ok(Some(Arc::new(config)))
+}
+
+/// NoCertificateVerification accepts any certificate from server.
+struct NoCertificateVerification;
+
+impl ServerCertVerifier for NoCertificateVerification {
+    fn verify_server_cert(
+        &self,
+        _: &Certificate,
+        _: &[Certificate],
+        _: &ServerName,
+        _: &mut dyn Iterator<Item = &[u8]>,
+        _: &[u8],
+        _: SystemTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
 pub struct Manager {
     endpoint: String,
+    host: String,
     root: String,
     user: String,
     password: String,
     enable_secure: bool,
+    tls_config: Option<Arc<ClientConfig>>,
+    enable_epsv: bool,
+    disable_epsv_fallback: bool,
 }
 
 #[async_trait::async_trait]
@@ -217,16 +363,35 @@ impl bb8::ManageConnection for Manager {
         let stream = ImplAsyncFtpStream::connect(&self.endpoint).await?;
         // switch to secure mode if ssl/tls is on.
         let mut ftp_stream = if self.enable_secure {
+            let connector = match &self.tls_config {
+                Some(config) => TlsConnector::from(config.clone()),
+                None => TlsConnector::default(),
+            };
+            // Mark all failures during upgrade as secure error so that they
+            // can be distinguished from plain connection errors.
             stream
-                .into_secure(
-                    AsyncRustlsConnector::from(TlsConnector::default()),
-                    &self.endpoint,
-                )
-                .await?
+                .into_secure(AsyncRustlsConnector::from(connector), &self.host)
+                .await
+                .map_err(|err| match err {
+                    FtpError::SecureError(_) => err,
+                    err => FtpError::SecureError(format!("AUTH TLS failed: {err}")),
+                })?
         } else {
             stream
         };
 
+        if self.enable_epsv {
+            let support_epsv = ftp_stream.feat().await?.contains_key("EPSV");
+            if support_epsv {
+                ftp_stream.set_mode(Mode::ExtendedPassive);
+            } else if self.disable_epsv_fallback {
+                return Err(FtpError::UnexpectedResponse(Response::new(
+                    Status::CommandNotImplemented,
+                    b"EPSV is not supported by server and fallback is disabled".to_vec(),
+                )));
+            }
+        }
+
         // login if needed
         if !self.user.is_empty() {
             ftp_stream.login(&self.user, &self.password).await?;
@@ -266,10 +431,14 @@ impl bb8::ManageConnection for Manager {
 #[derive(Clone)]
 pub struct FtpBackend {
     endpoint: String,
+    host: String,
     root: String,
     user: String,
     password: String,
     enable_secure: bool,
+    tls_config: Option<Arc<ClientConfig>>,
+    enable_epsv: bool,
+    disable_epsv_fallback: bool,
     pool: OnceCell<bb8::Pool<Manager>>,
 }
 
@@ -446,10 +615,14 @@ impl FtpBackend {
                     .max_size(64)
                     .build(Manager {
                         endpoint: self.endpoint.to_string(),
+                        host: self.host.to_string(),
                         root: self.root.to_string(),
                         user: self.user.to_string(),
                         password: self.password.to_string(),
                         enable_secure: self.enable_secure,
+                        tls_config: self.tls_config.clone(),
+                        enable_epsv: self.enable_epsv,
+                        disable_epsv_fallback: self.disable_epsv_fallback,
                     })
                     .await
             })
@@ -524,4 +697,27 @@ mod build_test {
         let e = b.unwrap_err();
         assert_eq!(e.kind(), ErrorKind::ConfigInvalid);
     }
+
+    #[test]
+    fn test_build_with_tls_options() {
+        let b = FtpBuilder::default()
+            .endpoint("ftps://ftp_server.local")
+            .accept_invalid_certs()
+            .build();
+        assert!(b.is_ok());
+
+        // root ca not exist
+        let b = FtpBuilder::default()
+            .endpoint("ftps://ftp_server.local")
+            .root_ca_path("/path/to/not_exist.pem")
+            .build();
+        assert_eq!(b.unwrap_err().kind(), ErrorKind::ConfigInvalid);
+
+        // tls options are ignored for plain ftp
+        let b = FtpBuilder::default()
+            .endpoint("ftp://ftp_server.local")
+            .root_ca_path("/path/to/not_exist.pem")
+            .build();
+        assert!(b.is_ok());
+    }
 }
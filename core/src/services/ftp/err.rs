@@ -37,7 +37,13 @@ pub fn parse_error(err: FtpError) -> Error {
         _ => (ErrorKind::Unexpected, false),
     };
 
-    let mut err = Error::new(kind, "ftp error").set_source(err);
+    let message = match err {
+        FtpError::SecureError(_) => "ftp tls upgrade failed",
+        FtpError::ConnectionError(_) => "ftp connection failed",
+        _ => "ftp error",
+    };
+
+    let mut err = Error::new(kind, message).set_source(err);
 
     if retryable {
         err = err.set_temporary();
@@ -85,7 +85,7 @@ impl oio::Write for FtpWriter {
         Ok(())
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         let data_stream = self.data_stream.take();
         if let Some(mut data_stream) = data_stream {
             data_stream.flush().await.map_err(|err| {
@@ -105,7 +105,7 @@ impl oio::Write for FtpWriter {
             }
         }
 
-        Ok(())
+        Ok(Metadata::new(EntryMode::FILE))
     }
 
     async fn abort(&mut self) -> Result<()> {
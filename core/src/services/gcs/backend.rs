@@ -347,6 +347,8 @@ impl Access for GcsBackend {
                 stat: true,
                 stat_with_if_match: true,
                 stat_with_if_none_match: true,
+                stat_with_if_modified_since: true,
+                stat_with_if_unmodified_since: true,
 
                 read: true,
 
@@ -357,6 +359,9 @@ impl Access for GcsBackend {
                 write_can_empty: true,
                 write_can_multi: true,
                 write_with_content_type: true,
+                write_with_content_md5: true,
+                write_returns_etag: true,
+                write_returns_version: true,
                 // The min multipart size of Gcs is 5 MiB.
                 //
                 // ref: <https://cloud.google.com/storage/docs/xml-api/put-object-multipart>
@@ -372,11 +377,16 @@ impl Access for GcsBackend {
 
                 delete: true,
                 copy: true,
+                copy_with_metadata_directive: true,
+                copy_with_content_type: true,
+                copy_with_cache_control: true,
+                copy_with_if_not_exists: true,
 
                 list: true,
                 list_with_limit: true,
                 list_with_start_after: true,
                 list_with_recursive: true,
+                list_ordered: true,
 
                 batch: true,
                 batch_max_operations: Some(100),
@@ -384,6 +394,10 @@ impl Access for GcsBackend {
                 presign_stat: true,
                 presign_read: true,
                 presign_write: true,
+                presign_delete: true,
+                presign_upload_part: true,
+
+                multipart: true,
 
                 ..Default::default()
             });
@@ -402,21 +416,11 @@ impl Access for GcsBackend {
         let meta: GetObjectJsonResponse =
             serde_json::from_reader(slc.reader()).map_err(new_json_deserialize_error)?;
 
-        let mut m = Metadata::new(EntryMode::FILE);
-
-        m.set_etag(&meta.etag);
-        m.set_content_md5(&meta.md5_hash);
+        let m = meta.into_metadata()?;
 
-        let size = meta
-            .size
-            .parse::<u64>()
-            .map_err(|e| Error::new(ErrorKind::Unexpected, "parse u64").set_source(e))?;
-        m.set_content_length(size);
-        if !meta.content_type.is_empty() {
-            m.set_content_type(&meta.content_type);
-        }
-
-        m.set_last_modified(parse_datetime_from_rfc3339(&meta.updated)?);
+        // The JSON API doesn't support time based conditions, check them
+        // against `updated` instead.
+        args.check_modified_since(m.last_modified())?;
 
         Ok(RpStat::new(m))
     }
@@ -428,7 +432,8 @@ impl Access for GcsBackend {
 
         match status {
             StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
-                Ok((RpRead::default(), resp.into_body()))
+                let etag = parse_etag(resp.headers())?.map(|v| v.to_string());
+                Ok((RpRead::new().with_etag(etag), resp.into_body()))
             }
             _ => {
                 let (part, mut body) = resp.into_parts();
@@ -470,8 +475,8 @@ impl Access for GcsBackend {
         Ok((RpList::default(), oio::PageLister::new(l)))
     }
 
-    async fn copy(&self, from: &str, to: &str, _: OpCopy) -> Result<RpCopy> {
-        let resp = self.core.gcs_copy_object(from, to).await?;
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let resp = self.core.gcs_copy_object(from, to, &args).await?;
 
         if resp.status().is_success() {
             Ok(RpCopy::default())
@@ -489,7 +494,16 @@ impl Access for GcsBackend {
                 self.core
                     .gcs_insert_object_xml_request(path, v, Buffer::new())?
             }
+            PresignOperation::Delete(_) => self.core.gcs_delete_object_xml_request(path)?,
+            PresignOperation::UploadPart(v) => self.core.gcs_upload_part_request(
+                path,
+                v.upload_id(),
+                v.part_number(),
+                None,
+                Buffer::new(),
+            )?,
         };
+        req.headers_mut().extend(args.signed_headers().clone());
 
         self.core.sign_query(&mut req, args.expire()).await?;
 
@@ -503,17 +517,52 @@ impl Access for GcsBackend {
         )))
     }
 
+    async fn initiate_multipart(&self, path: &str, args: OpWrite) -> Result<RpInitiateMultipart> {
+        let w = GcsWriter::new(self.core.clone(), path, args);
+        let upload_id = oio::MultipartWrite::initiate_part(&w).await?;
+        Ok(RpInitiateMultipart::new(&upload_id))
+    }
+
+    async fn complete_multipart(
+        &self,
+        path: &str,
+        args: OpCompleteMultipart,
+    ) -> Result<RpCompleteMultipart> {
+        let parts: Vec<_> = args
+            .parts()
+            .iter()
+            .map(|p| oio::MultipartPart {
+                part_number: p.part_number(),
+                etag: p.etag().to_string(),
+                checksum: None,
+            })
+            .collect();
+
+        let w = GcsWriter::new(self.core.clone(), path, OpWrite::default());
+        let meta = oio::MultipartWrite::complete_part(&w, args.upload_id(), &parts).await?;
+        Ok(RpCompleteMultipart::new(meta))
+    }
+
+    async fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> Result<RpAbortMultipart> {
+        let w = GcsWriter::new(self.core.clone(), path, OpWrite::default());
+        oio::MultipartWrite::abort_part(&w, args.upload_id()).await?;
+        Ok(RpAbortMultipart::new())
+    }
+
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
-        let ops = args.into_operation();
-        if ops.len() > 100 {
+        let paths = args.into_delete_paths()?;
+        if paths.len() > 100 {
             return Err(Error::new(
                 ErrorKind::Unsupported,
                 "gcs services only allow delete less than 100 keys at once",
             )
-            .with_context("length", ops.len().to_string()));
+            .with_context("length", paths.len().to_string()));
         }
 
-        let paths: Vec<String> = ops.into_iter().map(|(p, _)| p).collect();
         let resp = self.core.gcs_delete_objects(paths.clone()).await?;
 
         let status = resp.status();
@@ -563,32 +612,6 @@ impl Access for GcsBackend {
     }
 }
 
-/// The raw json response returned by [`get`](https://cloud.google.com/storage/docs/json_api/v1/objects/get)
-#[derive(Debug, Default, Deserialize)]
-#[serde(default, rename_all = "camelCase")]
-struct GetObjectJsonResponse {
-    /// GCS will return size in string.
-    ///
-    /// For example: `"size": "56535"`
-    size: String,
-    /// etag is not quoted.
-    ///
-    /// For example: `"etag": "CKWasoTgyPkCEAE="`
-    etag: String,
-    /// RFC3339 styled datetime string.
-    ///
-    /// For example: `"updated": "2022-08-15T11:33:34.866Z"`
-    updated: String,
-    /// Content md5 hash
-    ///
-    /// For example: `"md5Hash": "fHcEH1vPwA6eTPqxuasXcg=="`
-    md5_hash: String,
-    /// Content type of this object.
-    ///
-    /// For example: `"contentType": "image/png",`
-    content_type: String,
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -623,5 +646,6 @@ mod tests {
         assert_eq!(meta.md5_hash, "fHcEH1vPwA6eTPqxuasXcg==");
         assert_eq!(meta.etag, "CKWasoTgyPkCEAE=");
         assert_eq!(meta.content_type, "image/png");
+        assert_eq!(meta.generation, "1660563214863653");
     }
 }
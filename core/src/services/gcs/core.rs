@@ -28,7 +28,9 @@ use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
 use http::header::HOST;
 use http::header::IF_MATCH;
+use http::header::IF_MODIFIED_SINCE;
 use http::header::IF_NONE_MATCH;
+use http::header::IF_UNMODIFIED_SINCE;
 use http::Request;
 use http::Response;
 use once_cell::sync::Lazy;
@@ -228,6 +230,10 @@ impl GcsCore {
         if let Some(cache_control) = op.cache_control() {
             metadata.insert("cacheControl", cache_control);
         }
+        // GCS verifies the uploaded content against md5Hash.
+        if let Some(md5) = op.content_md5() {
+            metadata.insert("md5Hash", md5);
+        }
 
         let mut url = format!(
             "{}/upload/storage/v1/b/{}/o?uploadType={}&name={}",
@@ -360,6 +366,14 @@ impl GcsCore {
             req = req.header(IF_MATCH, if_match);
         }
 
+        if let Some(v) = args.if_modified_since() {
+            req = req.header(IF_MODIFIED_SINCE, format_datetime_into_http_date(v));
+        }
+
+        if let Some(v) = args.if_unmodified_since() {
+            req = req.header(IF_UNMODIFIED_SINCE, format_datetime_into_http_date(v));
+        }
+
         let req = req.body(Buffer::new()).map_err(new_request_build_error)?;
 
         Ok(req)
@@ -399,6 +413,17 @@ impl GcsCore {
             .map_err(new_request_build_error)
     }
 
+    // It's for presign operation. Gcs only supports query sign over XML API.
+    pub fn gcs_delete_object_xml_request(&self, path: &str) -> Result<Request<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!("{}/{}/{}", self.endpoint, self.bucket, p);
+
+        Request::delete(&url)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)
+    }
+
     pub async fn gcs_delete_objects(&self, paths: Vec<String>) -> Result<Response<Buffer>> {
         let uri = format!("{}/batch/storage/v1", self.endpoint);
 
@@ -419,11 +444,16 @@ impl GcsCore {
         self.send(req).await
     }
 
-    pub async fn gcs_copy_object(&self, from: &str, to: &str) -> Result<Response<Buffer>> {
+    pub async fn gcs_copy_object(
+        &self,
+        from: &str,
+        to: &str,
+        args: &OpCopy,
+    ) -> Result<Response<Buffer>> {
         let source = build_abs_path(&self.root, from);
         let dest = build_abs_path(&self.root, to);
 
-        let req_uri = format!(
+        let mut req_uri = format!(
             "{}/storage/v1/b/{}/o/{}/copyTo/b/{}/o/{}",
             self.endpoint,
             self.bucket,
@@ -431,11 +461,29 @@ impl GcsCore {
             self.bucket,
             percent_encode_path(&dest)
         );
+        // Generation `0` means the target must not exist.
+        if args.if_not_exists() {
+            req_uri.push_str("?ifGenerationMatch=0");
+        }
 
-        let mut req = Request::post(req_uri)
-            .header(CONTENT_LENGTH, 0)
-            .body(Buffer::new())
-            .map_err(new_request_build_error)?;
+        // Metadata in the request body replaces the one of source.
+        let mut req = if args.replace_metadata() {
+            let metadata = CopyObjectMetadata {
+                content_type: args.content_type(),
+                cache_control: args.cache_control(),
+            };
+            let bs = serde_json::to_vec(&metadata).map_err(new_json_serialize_error)?;
+            Request::post(req_uri)
+                .header(CONTENT_TYPE, "application/json")
+                .header(CONTENT_LENGTH, bs.len())
+                .body(Buffer::from(bs))
+                .map_err(new_request_build_error)?
+        } else {
+            Request::post(req_uri)
+                .header(CONTENT_LENGTH, 0)
+                .body(Buffer::new())
+                .map_err(new_request_build_error)?
+        };
 
         self.sign(&mut req).await?;
         self.send(req).await
@@ -503,14 +551,14 @@ impl GcsCore {
         self.send(req).await
     }
 
-    pub async fn gcs_upload_part(
+    pub fn gcs_upload_part_request(
         &self,
         path: &str,
         upload_id: &str,
         part_number: usize,
-        size: u64,
+        size: Option<u64>,
         body: Buffer,
-    ) -> Result<Response<Buffer>> {
+    ) -> Result<Request<Buffer>> {
         let p = build_abs_path(&self.root, path);
 
         let url = format!(
@@ -524,9 +572,23 @@ impl GcsCore {
 
         let mut req = Request::put(&url);
 
-        req = req.header(CONTENT_LENGTH, size);
+        if let Some(size) = size {
+            req = req.header(CONTENT_LENGTH, size);
+        }
+
+        req.body(body).map_err(new_request_build_error)
+    }
 
-        let mut req = req.body(body).map_err(new_request_build_error)?;
+    pub async fn gcs_upload_part(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number: usize,
+        size: u64,
+        body: Buffer,
+    ) -> Result<Response<Buffer>> {
+        let mut req =
+            self.gcs_upload_part_request(path, upload_id, part_number, Some(size), body)?;
 
         self.sign(&mut req).await?;
         self.send(req).await
@@ -624,6 +686,14 @@ pub struct InitiateMultipartUploadResult {
     pub upload_id: String,
 }
 
+/// Result of CompleteMultipartUpload
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct CompleteMultipartUploadResult {
+    #[serde(rename = "ETag")]
+    pub etag: String,
+}
+
 /// Request of CompleteMultipartUploadRequest
 #[derive(Default, Debug, Serialize)]
 #[serde(default, rename = "CompleteMultipartUpload", rename_all = "PascalCase")]
@@ -640,10 +710,88 @@ pub struct CompleteMultipartUploadRequestPart {
     pub etag: String,
 }
 
+/// Metadata of the target object in copy request.
+#[derive(Default, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CopyObjectMetadata<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_control: Option<&'a str>,
+}
+
+/// The raw json response returned by [`get`](https://cloud.google.com/storage/docs/json_api/v1/objects/get)
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct GetObjectJsonResponse {
+    /// GCS will return size in string.
+    ///
+    /// For example: `"size": "56535"`
+    pub size: String,
+    /// etag is not quoted.
+    ///
+    /// For example: `"etag": "CKWasoTgyPkCEAE="`
+    pub etag: String,
+    /// RFC3339 styled datetime string.
+    ///
+    /// For example: `"updated": "2022-08-15T11:33:34.866Z"`
+    pub updated: String,
+    /// Content md5 hash
+    ///
+    /// For example: `"md5Hash": "fHcEH1vPwA6eTPqxuasXcg=="`
+    pub md5_hash: String,
+    /// Content type of this object.
+    ///
+    /// For example: `"contentType": "image/png",`
+    pub content_type: String,
+    /// The generation of this object.
+    ///
+    /// For example: `"generation": "1660563214863653"`
+    pub generation: String,
+}
+
+impl GetObjectJsonResponse {
+    /// Convert the json response into Metadata.
+    pub fn into_metadata(self) -> Result<Metadata> {
+        let mut m = Metadata::new(EntryMode::FILE);
+
+        m.set_etag(&self.etag);
+        m.set_content_md5(&self.md5_hash);
+
+        let size = self
+            .size
+            .parse::<u64>()
+            .map_err(|e| Error::new(ErrorKind::Unexpected, "parse u64").set_source(e))?;
+        m.set_content_length(size);
+        if !self.content_type.is_empty() {
+            m.set_content_type(&self.content_type);
+        }
+        if !self.generation.is_empty() {
+            m.set_version(&self.generation);
+        }
+
+        m.set_last_modified(parse_datetime_from_rfc3339(&self.updated)?);
+
+        Ok(m)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_serialize_copy_object_metadata() {
+        let metadata = CopyObjectMetadata {
+            content_type: Some("application/json"),
+            cache_control: None,
+        };
+        assert_eq!(
+            serde_json::to_string(&metadata).unwrap(),
+            r#"{"contentType":"application/json"}"#
+        );
+    }
+
     #[test]
     fn test_deserialize_list_response() {
         let content = r#"
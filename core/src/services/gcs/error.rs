@@ -58,6 +58,7 @@ pub fn parse_error(resp: Response<Buffer>) -> Error {
         StatusCode::PRECONDITION_FAILED | StatusCode::NOT_MODIFIED => {
             (ErrorKind::ConditionNotMatch, false)
         }
+        StatusCode::TOO_MANY_REQUESTS => (ErrorKind::RateLimited, true),
         StatusCode::INTERNAL_SERVER_ERROR
         | StatusCode::BAD_GATEWAY
         | StatusCode::SERVICE_UNAVAILABLE
@@ -70,6 +71,19 @@ pub fn parse_error(resp: Response<Buffer>) -> Error {
         Err(_) => String::from_utf8_lossy(&bs).into_owned(),
     };
 
+    // GCS returns 404 for a missing bucket too, it's a config invalid error
+    // instead of the object not found, otherwise `exists` would report
+    // `false` for every path.
+    //
+    // Note that missing objects are reported as 403 if the caller doesn't
+    // have `storage.objects.list` permission, we keep it as permission
+    // denied since the existence is unknown.
+    let kind = if kind == ErrorKind::NotFound && is_bucket_not_found(&message) {
+        ErrorKind::ConfigInvalid
+    } else {
+        kind
+    };
+
     let mut err = Error::new(kind, message);
 
     err = with_error_response_context(err, parts);
@@ -81,6 +95,14 @@ pub fn parse_error(resp: Response<Buffer>) -> Error {
     err
 }
 
+/// Check if the error is caused by a missing bucket.
+///
+/// The JSON API returns `The specified bucket does not exist.` while the XML
+/// API returns `NoSuchBucket`.
+fn is_bucket_not_found(message: &str) -> bool {
+    message.contains("The specified bucket does not exist") || message.contains("NoSuchBucket")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -118,4 +140,23 @@ mod tests {
         assert_eq!(out.error.errors[0].location_type, "header");
         assert_eq!(out.error.errors[0].location, "Authorization");
     }
+
+    #[test]
+    fn test_parse_error_bucket_not_found() {
+        let resp = Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Buffer::from(
+                r#"{"error":{"code":404,"message":"The specified bucket does not exist.","errors":[{"message":"The specified bucket does not exist.","domain":"global","reason":"notFound"}]}}"#,
+            ))
+            .unwrap();
+        assert_eq!(parse_error(resp).kind(), ErrorKind::ConfigInvalid);
+
+        let resp = Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Buffer::from(
+                r#"{"error":{"code":404,"message":"No such object: bucket/path","errors":[{"message":"No such object: bucket/path","domain":"global","reason":"notFound"}]}}"#,
+            ))
+            .unwrap();
+        assert_eq!(parse_error(resp).kind(), ErrorKind::NotFound);
+    }
 }
@@ -131,6 +131,10 @@ impl oio::PageList for GcsLister {
             ctx.entries.push_back(de);
         }
 
+        // Prefixes and objects are sorted separately, merge them to return
+        // entries in order.
+        ctx.sort_entries();
+
         Ok(())
     }
 }
@@ -21,7 +21,9 @@ use bytes::Buf;
 use http::StatusCode;
 
 use super::core::CompleteMultipartUploadRequestPart;
+use super::core::CompleteMultipartUploadResult;
 use super::core::GcsCore;
+use super::core::GetObjectJsonResponse;
 use super::core::InitiateMultipartUploadResult;
 use super::error::parse_error;
 use crate::raw::*;
@@ -46,7 +48,7 @@ impl GcsWriter {
 }
 
 impl oio::MultipartWrite for GcsWriter {
-    async fn write_once(&self, _: u64, body: Buffer) -> Result<()> {
+    async fn write_once(&self, _: u64, body: Buffer) -> Result<Metadata> {
         let size = body.len() as u64;
         let mut req = self.core.gcs_insert_object_request(
             &percent_encode_path(&self.path),
@@ -62,7 +64,12 @@ impl oio::MultipartWrite for GcsWriter {
         let status = resp.status();
 
         match status {
-            StatusCode::CREATED | StatusCode::OK => Ok(()),
+            StatusCode::CREATED | StatusCode::OK => {
+                let meta: GetObjectJsonResponse =
+                    serde_json::from_reader(resp.into_body().reader())
+                        .map_err(new_json_deserialize_error)?;
+                meta.into_metadata()
+            }
             _ => Err(parse_error(resp)),
         }
     }
@@ -118,7 +125,11 @@ impl oio::MultipartWrite for GcsWriter {
         })
     }
 
-    async fn complete_part(&self, upload_id: &str, parts: &[oio::MultipartPart]) -> Result<()> {
+    async fn complete_part(
+        &self,
+        upload_id: &str,
+        parts: &[oio::MultipartPart],
+    ) -> Result<Metadata> {
         let parts = parts
             .iter()
             .map(|p| CompleteMultipartUploadRequestPart {
@@ -135,7 +146,18 @@ impl oio::MultipartWrite for GcsWriter {
         if !resp.status().is_success() {
             return Err(parse_error(resp));
         }
-        Ok(())
+
+        let (parts, body) = resp.into_parts();
+        let mut meta = parse_into_write_metadata(&parts.headers)?;
+        if let Some(v) = parse_header_to_str(&parts.headers, "x-goog-generation")? {
+            meta.set_version(v);
+        }
+        let result: CompleteMultipartUploadResult =
+            quick_xml::de::from_reader(body.reader()).map_err(new_xml_deserialize_error)?;
+        if !result.etag.is_empty() {
+            meta.set_etag(&result.etag);
+        }
+        Ok(meta)
     }
 
     async fn abort_part(&self, upload_id: &str) -> Result<()> {
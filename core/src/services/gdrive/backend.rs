@@ -34,9 +34,29 @@ use super::writer::GdriveWriter;
 use crate::raw::*;
 use crate::*;
 
+/// Backend for gdrive services.
 #[derive(Clone, Debug)]
 pub struct GdriveBackend {
-    pub core: Arc<GdriveCore>,
+    pub(super) core: Arc<GdriveCore>,
+}
+
+impl GdriveBackend {
+    /// Export the cached path to file id mappings.
+    ///
+    /// Paths are absolute ones that include `root`, the entries can be
+    /// loaded into a new backend via [`Gdrive::path_cache_entries`][super::Gdrive::path_cache_entries].
+    pub fn path_cache_entries(&self) -> Vec<(String, String)> {
+        self.core.path_cache.entries()
+    }
+
+    /// Invalidate the cached file id of path once gdrive reports it's gone,
+    /// so that the next request could resolve it again.
+    async fn invalidate_path_cache(&self, path: &str) {
+        self.core
+            .path_cache
+            .remove(&build_abs_path(&self.core.root, path))
+            .await
+    }
 }
 
 impl Access for GdriveBackend {
@@ -80,8 +100,13 @@ impl Access for GdriveBackend {
     async fn stat(&self, path: &str, _args: OpStat) -> Result<RpStat> {
         let resp = self.core.gdrive_stat(path).await?;
 
-        if resp.status() != StatusCode::OK {
-            return Err(parse_error(resp).await?);
+        match resp.status() {
+            StatusCode::OK => {}
+            StatusCode::NOT_FOUND => {
+                self.invalidate_path_cache(path).await;
+                return Err(parse_error(resp).await?);
+            }
+            _ => return Err(parse_error(resp).await?),
         }
 
         let bs = resp.into_body();
@@ -113,6 +138,9 @@ impl Access for GdriveBackend {
         match status {
             StatusCode::OK | StatusCode::PARTIAL_CONTENT => Ok((RpRead::new(), resp.into_body())),
             _ => {
+                if status == StatusCode::NOT_FOUND {
+                    self.invalidate_path_cache(path).await;
+                }
                 let (part, mut body) = resp.into_parts();
                 let buf = body.to_buffer().await?;
                 Err(parse_error(Response::from_parts(part, buf)).await?)
@@ -186,9 +214,12 @@ impl Access for GdriveBackend {
             self.core.path_cache.remove(&to_path).await;
         }
 
-        let url = format!(
-            "https://www.googleapis.com/drive/v3/files/{}/copy",
-            from_file_id
+        let url = self.core.drive_url(
+            format!(
+                "https://www.googleapis.com/drive/v3/files/{}/copy",
+                from_file_id
+            ),
+            false,
         );
 
         let request_body = &json!({
@@ -202,7 +233,7 @@ impl Access for GdriveBackend {
             .map_err(new_request_build_error)?;
         self.core.sign(&mut req).await?;
 
-        let resp = self.core.client.send(req).await?;
+        let resp = self.core.send(req).await?;
 
         match resp.status() {
             StatusCode::OK => Ok(RpCopy::default()),
@@ -18,6 +18,7 @@
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
+use std::time::Duration;
 
 use chrono::DateTime;
 use chrono::Utc;
@@ -31,6 +32,7 @@ use crate::raw::normalize_root;
 use crate::raw::Access;
 use crate::raw::HttpClient;
 use crate::raw::PathCacher;
+use crate::raw::RefreshTokenCallback;
 use crate::services::gdrive::core::GdriveCore;
 use crate::services::gdrive::core::GdrivePathQuery;
 use crate::services::gdrive::core::GdriveSigner;
@@ -52,12 +54,22 @@ pub struct GdriveConfig {
     pub client_id: Option<String>,
     /// Client secret for gdrive.
     pub client_secret: Option<String>,
+    /// The shared drive id for gdrive, My Drive will be used if not set.
+    pub drive_id: Option<String>,
+    /// The max number of path to file id mappings to cache.
+    pub path_cache_capacity: Option<u64>,
+    /// Refresh the access token this long before it expires, default to
+    /// 2 minutes.
+    pub token_refresh_skew: Option<Duration>,
 }
 
 impl Debug for GdriveConfig {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("GdriveConfig")
             .field("root", &self.root)
+            .field("drive_id", &self.drive_id)
+            .field("path_cache_capacity", &self.path_cache_capacity)
+            .field("token_refresh_skew", &self.token_refresh_skew)
             .finish_non_exhaustive()
     }
 }
@@ -67,6 +79,8 @@ impl Configurator for GdriveConfig {
         GdriveBuilder {
             config: self,
             http_client: None,
+            path_cache_entries: Vec::new(),
+            refresh_token_callback: None,
         }
     }
 }
@@ -78,6 +92,8 @@ pub struct GdriveBuilder {
     config: GdriveConfig,
 
     http_client: Option<HttpClient>,
+    path_cache_entries: Vec<(String, String)>,
+    refresh_token_callback: Option<RefreshTokenCallback>,
 }
 
 impl Debug for GdriveBuilder {
@@ -136,6 +152,61 @@ impl GdriveBuilder {
         self
     }
 
+    /// Set the shared drive (Team Drive) id.
+    ///
+    /// All requests will be scoped to the given shared drive, and `root` will
+    /// be resolved from the root folder of it.
+    pub fn drive_id(mut self, drive_id: &str) -> Self {
+        if !drive_id.is_empty() {
+            self.config.drive_id = Some(drive_id.to_string());
+        }
+        self
+    }
+
+    /// Set the max number of path to file id mappings to cache.
+    ///
+    /// default: 65536
+    pub fn path_cache_capacity(mut self, capacity: u64) -> Self {
+        self.config.path_cache_capacity = Some(capacity);
+        self
+    }
+
+    /// Preload the path to file id cache.
+    ///
+    /// The entries are usually exported by [`GdriveBackend::path_cache_entries`]
+    /// of a previous backend with the same `root` and `drive_id`, so that
+    /// new operators don't need to walk deep hierarchies again.
+    ///
+    /// Stale entries will be invalidated once Google Drive reports them
+    /// as not found.
+    pub fn path_cache_entries(
+        mut self,
+        entries: impl IntoIterator<Item = (String, String)>,
+    ) -> Self {
+        self.path_cache_entries.extend(entries);
+        self
+    }
+
+    /// Refresh the access token this long before it expires.
+    ///
+    /// The skew is capped to the half of the token lifetime.
+    ///
+    /// Default to 2 minutes.
+    pub fn token_refresh_skew(mut self, skew: Duration) -> Self {
+        self.config.token_refresh_skew = Some(skew);
+        self
+    }
+
+    /// Register a callback to persist the rotated refresh token.
+    ///
+    /// The callback will be called with the new refresh token once the
+    /// token endpoint returns a different one, so that it can be stored and
+    /// used the next time this service is built.
+    pub fn refresh_token_callback(mut self, f: impl Fn(&str) + Send + Sync + 'static) -> Self {
+        self.refresh_token_callback = Some(Arc::new(f));
+        self
+    }
+
     /// Specify the http client that used by this service.
     ///
     /// # Notes
@@ -148,11 +219,13 @@ impl GdriveBuilder {
     }
 }
 
-impl Builder for GdriveBuilder {
-    const SCHEME: Scheme = Scheme::Gdrive;
-    type Config = GdriveConfig;
-
-    fn build(self) -> Result<impl Access> {
+impl GdriveBuilder {
+    /// Build a [`GdriveBackend`] directly.
+    ///
+    /// Most users should use [`Builder::build`] via `Operator::new`
+    /// instead, this is useful to export the path cache via
+    /// [`GdriveBackend::path_cache_entries`].
+    pub fn build_backend(self) -> Result<GdriveBackend> {
         let root = normalize_root(&self.config.root.unwrap_or_default());
         debug!("backend use root {}", root);
 
@@ -209,14 +282,40 @@ impl Builder for GdriveBuilder {
             }
         };
 
+        if let Some(skew) = self.config.token_refresh_skew {
+            signer.refresh_skew = skew;
+        }
+        signer.refresh_token_callback = self.refresh_token_callback;
+
         let signer = Arc::new(Mutex::new(signer));
+        let mut path_cache = PathCacher::new(GdrivePathQuery::new(
+            client.clone(),
+            signer.clone(),
+            self.config.drive_id.clone(),
+        ))
+        .with_lock();
+        if let Some(capacity) = self.config.path_cache_capacity {
+            path_cache = path_cache.with_capacity(capacity);
+        }
+        path_cache.extend(self.path_cache_entries);
+
         Ok(GdriveBackend {
             core: Arc::new(GdriveCore {
                 root,
-                signer: signer.clone(),
-                client: client.clone(),
-                path_cache: PathCacher::new(GdrivePathQuery::new(client, signer)).with_lock(),
+                signer,
+                client,
+                drive_id: self.config.drive_id,
+                path_cache,
             }),
         })
     }
 }
+
+impl Builder for GdriveBuilder {
+    const SCHEME: Scheme = Scheme::Gdrive;
+    type Config = GdriveConfig;
+
+    fn build(self) -> Result<impl Access> {
+        self.build_backend()
+    }
+}
@@ -18,6 +18,7 @@
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
+use std::time::Duration;
 
 use bytes;
 use bytes::Buf;
@@ -43,6 +44,9 @@ pub struct GdriveCore {
 
     pub signer: Arc<Mutex<GdriveSigner>>,
 
+    /// The shared drive to scope all requests to, `None` means My Drive.
+    pub drive_id: Option<String>,
+
     /// Cache the mapping from path to file id
     pub path_cache: PathCacher<GdrivePathQuery>,
 }
@@ -51,6 +55,7 @@ impl Debug for GdriveCore {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut de = f.debug_struct("GdriveCore");
         de.field("root", &self.root);
+        de.field("drive_id", &self.drive_id);
         de.finish()
     }
 }
@@ -65,15 +70,19 @@ impl GdriveCore {
 
         // The file metadata in the Google Drive API is very complex.
         // For now, we only need the file id, name, mime type and modified time.
-        let mut req = Request::get(&format!(
-            "https://www.googleapis.com/drive/v3/files/{}?fields=id,name,mimeType,size,modifiedTime",
-            file_id
-        ))
+        let url = self.drive_url(
+            format!(
+                "https://www.googleapis.com/drive/v3/files/{}?fields=id,name,mimeType,size,modifiedTime",
+                file_id
+            ),
+            false,
+        );
+        let mut req = Request::get(&url)
             .body(Buffer::new())
             .map_err(new_request_build_error)?;
         self.sign(&mut req).await?;
 
-        self.client.send(req).await
+        self.send(req).await
     }
 
     pub async fn gdrive_get(&self, path: &str, range: BytesRange) -> Result<Response<HttpBody>> {
@@ -83,9 +92,12 @@ impl GdriveCore {
             format!("path not found: {}", path),
         ))?;
 
-        let url: String = format!(
-            "https://www.googleapis.com/drive/v3/files/{}?alt=media",
-            path_id
+        let url = self.drive_url(
+            format!(
+                "https://www.googleapis.com/drive/v3/files/{}?alt=media",
+                path_id
+            ),
+            false,
         );
 
         let mut req = Request::get(&url)
@@ -112,13 +124,14 @@ impl GdriveCore {
         if !next_page_token.is_empty() {
             url += &format!("&pageToken={next_page_token}");
         };
+        let url = self.drive_url(url, true);
 
         let mut req = Request::get(&url)
             .body(Buffer::new())
             .map_err(new_request_build_error)?;
         self.sign(&mut req).await?;
 
-        self.client.send(req).await
+        self.send(req).await
     }
 
     // Update with content and metadata
@@ -147,9 +160,12 @@ impl GdriveCore {
             "addParents": [target_parent_id],
         });
 
-        let url = format!(
-            "https://www.googleapis.com/drive/v3/files/{}",
-            source_file_id
+        let url = self.drive_url(
+            format!(
+                "https://www.googleapis.com/drive/v3/files/{}",
+                source_file_id
+            ),
+            false,
         );
         let mut req = Request::patch(url)
             .body(Buffer::from(Bytes::from(metadata.to_string())))
@@ -157,11 +173,14 @@ impl GdriveCore {
 
         self.sign(&mut req).await?;
 
-        self.client.send(req).await
+        self.send(req).await
     }
 
     pub async fn gdrive_trash(&self, file_id: &str) -> Result<Response<Buffer>> {
-        let url = format!("https://www.googleapis.com/drive/v3/files/{}", file_id);
+        let url = self.drive_url(
+            format!("https://www.googleapis.com/drive/v3/files/{}", file_id),
+            false,
+        );
 
         let body = serde_json::to_vec(&json!({
             "trashed": true
@@ -174,7 +193,7 @@ impl GdriveCore {
 
         self.sign(&mut req).await?;
 
-        self.client.send(req).await
+        self.send(req).await
     }
 
     /// Create a file with the content.
@@ -186,7 +205,10 @@ impl GdriveCore {
     ) -> Result<Response<Buffer>> {
         let parent = self.path_cache.ensure_dir(get_parent(path)).await?;
 
-        let url = "https://www.googleapis.com/upload/drive/v3/files?uploadType=multipart";
+        let url = self.drive_url(
+            "https://www.googleapis.com/upload/drive/v3/files?uploadType=multipart".to_string(),
+            false,
+        );
 
         let file_name = get_basename(path);
 
@@ -220,7 +242,7 @@ impl GdriveCore {
 
         self.sign(&mut req).await?;
 
-        self.client.send(req).await
+        self.send(req).await
     }
 
     /// Overwrite the file with the content.
@@ -234,9 +256,12 @@ impl GdriveCore {
         size: u64,
         body: Buffer,
     ) -> Result<Response<Buffer>> {
-        let url = format!(
-            "https://www.googleapis.com/upload/drive/v3/files/{}?uploadType=media",
-            file_id
+        let url = self.drive_url(
+            format!(
+                "https://www.googleapis.com/upload/drive/v3/files/{}?uploadType=media",
+                file_id
+            ),
+            false,
         );
 
         let mut req = Request::patch(url)
@@ -248,13 +273,62 @@ impl GdriveCore {
 
         self.sign(&mut req).await?;
 
-        self.client.send(req).await
+        self.send(req).await
     }
 
     pub async fn sign<T>(&self, req: &mut Request<T>) -> Result<()> {
         let mut signer = self.signer.lock().await;
         signer.sign(req).await
     }
+
+    /// Send request to gdrive.
+    ///
+    /// Unauthorized responses are returned as error directly, along with
+    /// whether the access token in use comes from a successful refresh.
+    pub async fn send(&self, req: Request<Buffer>) -> Result<Response<Buffer>> {
+        let resp = self.client.send(req).await?;
+        if resp.status() != StatusCode::UNAUTHORIZED {
+            return Ok(resp);
+        }
+
+        // Invalidate the access token so that the next request will
+        // refresh it. User specified access token can't be refreshed, so
+        // we keep it as is.
+        let refreshed = {
+            let mut signer = self.signer.lock().await;
+            if !signer.refresh_token.is_empty() {
+                signer.expires_in = DateTime::<Utc>::MIN_UTC;
+            }
+            signer.refreshed
+        };
+        Err(parse_error(resp)
+            .await?
+            .with_context("token_refreshed", refreshed.to_string()))
+    }
+
+    pub fn drive_url(&self, url: String, list: bool) -> String {
+        build_drive_url(url, self.drive_id.as_deref(), list)
+    }
+}
+
+/// Append the query parameters required by shared drives to url.
+///
+/// Items in shared drives are invisible unless `supportsAllDrives` is set,
+/// and list requests should also be scoped to the drive via `driveId`.
+///
+/// ref: <https://developers.google.com/drive/api/guides/enable-shareddrives>
+fn build_drive_url(mut url: String, drive_id: Option<&str>, list: bool) -> String {
+    let Some(drive_id) = drive_id else {
+        return url;
+    };
+
+    url.push(if url.contains('?') { '&' } else { '?' });
+    url.push_str("supportsAllDrives=true");
+    if list {
+        url.push_str("&includeItemsFromAllDrives=true&corpora=drive&driveId=");
+        url.push_str(&percent_encode_path(drive_id));
+    }
+    url
 }
 
 #[derive(Clone)]
@@ -267,6 +341,12 @@ pub struct GdriveSigner {
 
     pub access_token: String,
     pub expires_in: DateTime<Utc>,
+
+    /// Refresh the access token this long before it expires.
+    pub refresh_skew: Duration,
+    pub refresh_token_callback: Option<RefreshTokenCallback>,
+    /// Whether the access token in use comes from a successful refresh.
+    pub refreshed: bool,
 }
 
 impl GdriveSigner {
@@ -280,10 +360,18 @@ impl GdriveSigner {
             refresh_token: "".to_string(),
             access_token: "".to_string(),
             expires_in: DateTime::<Utc>::MIN_UTC,
+
+            refresh_skew: DEFAULT_TOKEN_REFRESH_SKEW,
+            refresh_token_callback: None,
+            refreshed: false,
         }
     }
 
     /// Sign a request.
+    ///
+    /// The signer is locked by the caller during the refresh, so concurrent
+    /// requests will wait for the same refresh instead of hitting the token
+    /// endpoint again.
     pub async fn sign<T>(&mut self, req: &mut Request<T>) -> Result<()> {
         if !self.access_token.is_empty() && self.expires_in > Utc::now() {
             let value = format!("Bearer {}", self.access_token)
@@ -314,13 +402,20 @@ impl GdriveSigner {
                     let token: GdriveTokenResponse = serde_json::from_reader(resp_body.reader())
                         .map_err(new_json_deserialize_error)?;
                     self.access_token.clone_from(&token.access_token);
-                    self.expires_in = Utc::now()
-                        + chrono::TimeDelta::try_seconds(token.expires_in)
-                            .expect("expires_in must be valid seconds")
-                        - chrono::TimeDelta::try_seconds(120).expect("120 must be valid seconds");
+                    self.expires_in = token_expires_at(token.expires_in, self.refresh_skew);
+                    self.refreshed = true;
+                    rotate_refresh_token(
+                        &mut self.refresh_token,
+                        token.refresh_token,
+                        self.refresh_token_callback.as_ref(),
+                    );
                 }
                 _ => {
-                    return Err(parse_error(resp).await?);
+                    self.refreshed = false;
+                    return Err(parse_error(resp)
+                        .await?
+                        .with_operation("GdriveSigner::refresh_token")
+                        .with_context("token_refreshed", "false"));
                 }
             }
         }
@@ -336,17 +431,30 @@ impl GdriveSigner {
 pub struct GdrivePathQuery {
     pub client: HttpClient,
     pub signer: Arc<Mutex<GdriveSigner>>,
+    pub drive_id: Option<String>,
 }
 
 impl GdrivePathQuery {
-    pub fn new(client: HttpClient, signer: Arc<Mutex<GdriveSigner>>) -> Self {
-        GdrivePathQuery { client, signer }
+    pub fn new(
+        client: HttpClient,
+        signer: Arc<Mutex<GdriveSigner>>,
+        drive_id: Option<String>,
+    ) -> Self {
+        GdrivePathQuery {
+            client,
+            signer,
+            drive_id,
+        }
     }
 }
 
 impl PathQuery for GdrivePathQuery {
     async fn root(&self) -> Result<String> {
-        Ok("root".to_string())
+        // The root folder of a shared drive shares the same id with the drive.
+        match &self.drive_id {
+            Some(drive_id) => Ok(drive_id.clone()),
+            None => Ok("root".to_string()),
+        }
     }
 
     async fn query(&self, parent_id: &str, name: &str) -> Result<Option<String>> {
@@ -363,9 +471,13 @@ impl PathQuery for GdrivePathQuery {
         }
         let query = queries.join(" and ");
 
-        let url = format!(
-            "https://www.googleapis.com/drive/v3/files?q={}",
-            percent_encode_path(query.as_str())
+        let url = build_drive_url(
+            format!(
+                "https://www.googleapis.com/drive/v3/files?q={}",
+                percent_encode_path(query.as_str())
+            ),
+            self.drive_id.as_deref(),
+            true,
         );
 
         let mut req = Request::get(&url)
@@ -383,10 +495,24 @@ impl PathQuery for GdrivePathQuery {
                 let meta: GdriveFileList =
                     serde_json::from_reader(body.reader()).map_err(new_json_deserialize_error)?;
 
-                if let Some(f) = meta.files.first() {
-                    Ok(Some(f.id.clone()))
-                } else {
-                    Ok(None)
+                match meta.files.as_slice() {
+                    [] => Ok(None),
+                    [f] => Ok(Some(f.id.clone())),
+                    files => Err(Error::new(
+                        ErrorKind::Ambiguous,
+                        "multiple files with the same name exist in the folder",
+                    )
+                    .with_operation("GdrivePathQuery::query")
+                    .with_context("parent_id", parent_id)
+                    .with_context("name", name)
+                    .with_context(
+                        "file_ids",
+                        files
+                            .iter()
+                            .map(|f| f.id.as_str())
+                            .collect::<Vec<_>>()
+                            .join(","),
+                    )),
                 }
             }
             _ => Err(parse_error(resp).await?),
@@ -394,7 +520,11 @@ impl PathQuery for GdrivePathQuery {
     }
 
     async fn create_dir(&self, parent_id: &str, name: &str) -> Result<String> {
-        let url = "https://www.googleapis.com/drive/v3/files";
+        let url = build_drive_url(
+            "https://www.googleapis.com/drive/v3/files".to_string(),
+            self.drive_id.as_deref(),
+            false,
+        );
 
         let content = serde_json::to_vec(&json!({
             "name": name,
@@ -426,7 +556,8 @@ impl PathQuery for GdrivePathQuery {
 #[derive(Deserialize)]
 pub struct GdriveTokenResponse {
     access_token: String,
-    expires_in: i64,
+    expires_in: u64,
+    refresh_token: Option<String>,
 }
 
 /// This is the file struct returned by the Google Drive API.
@@ -454,3 +585,22 @@ pub(crate) struct GdriveFileList {
     pub(crate) files: Vec<GdriveFile>,
     pub(crate) next_page_token: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_drive_url() {
+        let url = "https://www.googleapis.com/drive/v3/files".to_string();
+        assert_eq!(build_drive_url(url.clone(), None, true), url);
+        assert_eq!(
+            build_drive_url(url.clone(), Some("0AAbc"), false),
+            "https://www.googleapis.com/drive/v3/files?supportsAllDrives=true"
+        );
+        assert_eq!(
+            build_drive_url(format!("{url}?q=x"), Some("0AAbc"), true),
+            "https://www.googleapis.com/drive/v3/files?q=x&supportsAllDrives=true&includeItemsFromAllDrives=true&corpora=drive&driveId=0AAbc"
+        );
+    }
+}
@@ -20,6 +20,7 @@ mod builder;
 mod core;
 mod error;
 
+pub use backend::GdriveBackend;
 pub use builder::GdriveBuilder as Gdrive;
 pub use builder::GdriveConfig;
 mod lister;
@@ -46,7 +46,7 @@ impl GdriveWriter {
 }
 
 impl oio::OneShotWrite for GdriveWriter {
-    async fn write_once(&self, bs: Buffer) -> Result<()> {
+    async fn write_once(&self, bs: Buffer) -> Result<Metadata> {
         let size = bs.len();
 
         let resp = if let Some(file_id) = &self.file_id {
@@ -69,7 +69,13 @@ impl oio::OneShotWrite for GdriveWriter {
                         serde_json::from_reader(bs.reader()).map_err(new_json_deserialize_error)?;
                     self.core.path_cache.insert(&self.path, &file.id).await;
                 }
-                Ok(())
+                Ok(Metadata::new(EntryMode::FILE))
+            }
+            // The cached file id is stale, invalidate it so that the next
+            // write could create the file again.
+            StatusCode::NOT_FOUND if self.file_id.is_some() => {
+                self.core.path_cache.remove(&self.path).await;
+                Err(parse_error(resp).await?)
             }
             _ => Err(parse_error(resp).await?),
         }
@@ -68,12 +68,12 @@ impl oio::Write for GhacWriter {
         Ok(())
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         let req = self.backend.ghac_commit(self.cache_id, self.size).await?;
         let resp = self.backend.client.send(req).await?;
 
         if resp.status().is_success() {
-            Ok(())
+            Ok(Metadata::new(EntryMode::FILE))
         } else {
             Err(parse_error(resp)
                 .await
@@ -64,7 +64,7 @@ impl oio::Write for HdfsWriter<hdrs::AsyncFile> {
         Ok(())
     }
 
-    async fn close(&mut self) -> Result<()> {
+    async fn close(&mut self) -> Result<Metadata> {
         let f = self.f.as_mut().expect("HdfsWriter must be initialized");
         f.close().await.map_err(new_std_io_error)?;
 
@@ -75,7 +75,7 @@ impl oio::Write for HdfsWriter<hdrs::AsyncFile> {
                 .map_err(new_std_io_error)?
         }
 
-        Ok(())
+        Ok(Metadata::new(EntryMode::FILE))
     }
 
     async fn abort(&mut self) -> Result<()> {
@@ -97,7 +97,7 @@ impl oio::BlockingWrite for HdfsWriter<hdrs::File> {
         Ok(())
     }
 
-    fn close(&mut self) -> Result<()> {
+    fn close(&mut self) -> Result<Metadata> {
         let f = self.f.as_mut().expect("HdfsWriter must be initialized");
         f.flush().map_err(new_std_io_error)?;
 
@@ -107,6 +107,6 @@ impl oio::BlockingWrite for HdfsWriter<hdrs::File> {
                 .map_err(new_std_io_error)?;
         }
 
-        Ok(())
+        Ok(Metadata::new(EntryMode::FILE))
     }
 }
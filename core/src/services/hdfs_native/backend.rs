@@ -24,7 +24,8 @@ use log::debug;
 use serde::Deserialize;
 use serde::Serialize;
 
-// use uuid::Uuid;
+use uuid::Uuid;
+
 use super::error::parse_hdfs_error;
 use super::lister::HdfsNativeLister;
 use super::reader::HdfsNativeReader;
@@ -46,6 +47,8 @@ pub struct HdfsNativeConfig {
     pub url: Option<String>,
     /// enable the append capacity
     pub enable_append: bool,
+    /// atomic_write_dir of this backend
+    pub atomic_write_dir: Option<String>,
 }
 
 impl Debug for HdfsNativeConfig {
@@ -54,6 +57,7 @@ impl Debug for HdfsNativeConfig {
             .field("root", &self.root)
             .field("url", &self.url)
             .field("enable_append", &self.enable_append)
+            .field("atomic_write_dir", &self.atomic_write_dir)
             .finish_non_exhaustive()
     }
 }
@@ -114,6 +118,22 @@ impl HdfsNativeBuilder {
         self.config.enable_append = enable_append;
         self
     }
+
+    /// Set temp dir for atomic write.
+    ///
+    /// # Notes
+    ///
+    /// If set, writes will go to a temp file under this dir first and be
+    /// renamed to the target path while closing. Readers will never see
+    /// a truncated file left by crashed writers.
+    pub fn atomic_write_dir(mut self, dir: &str) -> Self {
+        self.config.atomic_write_dir = if dir.is_empty() {
+            None
+        } else {
+            Some(String::from(dir))
+        };
+        self
+    }
 }
 
 impl Builder for HdfsNativeBuilder {
@@ -138,21 +158,24 @@ impl Builder for HdfsNativeBuilder {
 
         // need to check if root dir exists, create if not
 
+        let atomic_write_dir = self.config.atomic_write_dir.map(|v| normalize_root(&v));
+
         Ok(HdfsNativeBackend {
             root,
             client: Arc::new(client),
             _enable_append: self.config.enable_append,
+            atomic_write_dir,
         })
     }
 }
 
-// #[inline]
-// fn tmp_file_of(path: &str) -> String {
-//     let name = get_basename(path);
-//     let uuid = Uuid::new_v4().to_string();
-//
-//     format!("{name}.{uuid}")
-// }
+#[inline]
+fn tmp_file_of(path: &str) -> String {
+    let name = get_basename(path);
+    let uuid = Uuid::new_v4().to_string();
+
+    format!("{name}.{uuid}")
+}
 
 /// Backend for hdfs-native services.
 #[derive(Debug, Clone)]
@@ -160,6 +183,7 @@ pub struct HdfsNativeBackend {
     root: String,
     client: Arc<hdfs_native::Client>,
     _enable_append: bool,
+    atomic_write_dir: Option<String>,
 }
 
 /// hdfs_native::Client is thread-safe.
@@ -181,6 +205,9 @@ impl Access for HdfsNativeBackend {
             .set_native_capability(Capability {
                 stat: true,
 
+                write: true,
+                write_can_multi: true,
+
                 delete: true,
                 rename: true,
                 blocking: true,
@@ -212,15 +239,24 @@ impl Access for HdfsNativeBackend {
     }
 
     async fn write(&self, path: &str, _args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
-        let p = build_rooted_abs_path(&self.root, path);
-
+        let target_path = build_rooted_abs_path(&self.root, path);
+        let tmp_path = self
+            .atomic_write_dir
+            .as_ref()
+            .map(|dir| build_rooted_abs_path(dir, &tmp_file_of(path)));
+
+        let opts = WriteOptions {
+            overwrite: true,
+            create_parent: true,
+            ..Default::default()
+        };
         let f = self
             .client
-            .create(&p, WriteOptions::default())
+            .create(tmp_path.as_deref().unwrap_or(&target_path), opts)
             .await
             .map_err(parse_hdfs_error)?;
 
-        let w = HdfsNativeWriter::new(f);
+        let w = HdfsNativeWriter::new(target_path, tmp_path, f, self.client.clone());
 
         Ok((RpWrite::new(), w))
     }
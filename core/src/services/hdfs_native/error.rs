@@ -50,3 +50,16 @@ pub fn parse_hdfs_error(hdfs_error: HdfsError) -> Error {
 
     err
 }
+
+/// Check if the error is caused by renaming across encryption zones.
+///
+/// HDFS rejects such renames with messages like `/a can't be moved from
+/// encryption zone /ez1 to encryption zone /ez2`.
+pub fn is_encryption_zone_error(err: &HdfsError) -> bool {
+    match err {
+        HdfsError::RPCError(_, msg) | HdfsError::OperationFailed(msg) => {
+            msg.contains("encryption zone")
+        }
+        _ => false,
+    }
+}
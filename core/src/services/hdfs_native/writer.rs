@@ -15,34 +15,149 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::sync::Arc;
+
 use hdfs_native::file::FileWriter;
+use hdfs_native::Client;
+use hdfs_native::WriteOptions;
+use log::warn;
 
+use super::error::is_encryption_zone_error;
+use super::error::parse_hdfs_error;
 use crate::raw::oio;
 use crate::*;
 
+/// The size of each read while copying tmp file to target.
+const COPY_CHUNK_SIZE: usize = 8 * 1024 * 1024;
+
 pub struct HdfsNativeWriter {
-    _f: FileWriter,
+    target_path: String,
+    tmp_path: Option<String>,
+    f: FileWriter,
+    client: Arc<Client>,
+    /// Whether the write has been closed or aborted.
+    finished: bool,
 }
 
 impl HdfsNativeWriter {
-    pub fn new(f: FileWriter) -> Self {
-        HdfsNativeWriter { _f: f }
+    pub fn new(
+        target_path: String,
+        tmp_path: Option<String>,
+        f: FileWriter,
+        client: Arc<Client>,
+    ) -> Self {
+        HdfsNativeWriter {
+            target_path,
+            tmp_path,
+            f,
+            client,
+            finished: false,
+        }
+    }
+
+    /// Copy tmp file into target path and remove tmp file.
+    ///
+    /// This is used as the fallback when rename across encryption zones.
+    async fn copy_and_delete(&self, tmp_path: &str) -> Result<()> {
+        let mut r = self.client.read(tmp_path).await.map_err(parse_hdfs_error)?;
+        let opts = WriteOptions {
+            overwrite: true,
+            create_parent: true,
+            ..Default::default()
+        };
+        let mut w = self
+            .client
+            .create(&self.target_path, opts)
+            .await
+            .map_err(parse_hdfs_error)?;
+
+        while r.remaining() > 0 {
+            let bs = r
+                .read(r.remaining().min(COPY_CHUNK_SIZE))
+                .await
+                .map_err(parse_hdfs_error)?;
+            w.write(bs).await.map_err(parse_hdfs_error)?;
+        }
+        w.close().await.map_err(parse_hdfs_error)?;
+
+        self.client
+            .delete(tmp_path, false)
+            .await
+            .map_err(parse_hdfs_error)?;
+        Ok(())
     }
 }
 
 impl oio::Write for HdfsNativeWriter {
-    async fn write(&mut self, _bs: Buffer) -> Result<()> {
-        todo!()
+    async fn write(&mut self, bs: Buffer) -> Result<()> {
+        for bs in bs {
+            self.f.write(bs).await.map_err(parse_hdfs_error)?;
+        }
+        Ok(())
     }
 
-    async fn close(&mut self) -> Result<()> {
-        todo!()
+    async fn close(&mut self) -> Result<Metadata> {
+        self.f.close().await.map_err(parse_hdfs_error)?;
+        self.finished = true;
+
+        let Some(tmp_path) = &self.tmp_path else {
+            return Ok(Metadata::new(EntryMode::FILE));
+        };
+
+        match self.client.rename(tmp_path, &self.target_path, true).await {
+            Ok(()) => {}
+            Err(err) if is_encryption_zone_error(&err) => {
+                self.copy_and_delete(tmp_path).await.map_err(|e| {
+                    e.with_context("tmp_path", tmp_path)
+                        .with_context("fallback", "copy and delete tmp file")
+                        .with_context("rename_error", err.to_string())
+                })?;
+            }
+            Err(err) => return Err(parse_hdfs_error(err).with_context("tmp_path", tmp_path)),
+        }
+
+        Ok(Metadata::new(EntryMode::FILE))
     }
 
     async fn abort(&mut self) -> Result<()> {
-        Err(Error::new(
-            ErrorKind::Unsupported,
-            "HdfsNativeWriter doesn't support abort",
-        ))
+        let Some(tmp_path) = &self.tmp_path else {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "HdfsNativeWriter doesn't support abort without atomic_write_dir",
+            ));
+        };
+
+        // Ignore the close error since we are going to remove the file anyway.
+        let _ = self.f.close().await;
+        self.finished = true;
+
+        self.client
+            .delete(tmp_path, false)
+            .await
+            .map_err(parse_hdfs_error)?;
+        Ok(())
+    }
+}
+
+impl Drop for HdfsNativeWriter {
+    /// Remove the tmp file if the writer is dropped without close or abort.
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+        let Some(tmp_path) = self.tmp_path.take() else {
+            return;
+        };
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            warn!("hdfs native writer dropped without runtime, tmp file {tmp_path} is left");
+            return;
+        };
+
+        let client = self.client.clone();
+        handle.spawn(async move {
+            if let Err(err) = client.delete(&tmp_path, false).await {
+                warn!("failed to remove tmp file {tmp_path} of dropped writer: {err}");
+            }
+        });
     }
 }
@@ -30,6 +30,7 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use super::error::parse_error;
+use super::reader::HttpReader;
 use crate::raw::*;
 use crate::*;
 
@@ -48,6 +49,10 @@ pub struct HttpConfig {
     pub token: Option<String>,
     /// root of this backend
     pub root: Option<String>,
+    /// The max times to resume an interrupted read via range request.
+    ///
+    /// Default to 3, set to 0 to disable resuming.
+    pub max_read_resumes: Option<usize>,
 }
 
 impl Debug for HttpConfig {
@@ -140,6 +145,21 @@ impl HttpBuilder {
         self
     }
 
+    /// Set the max times to resume an interrupted read.
+    ///
+    /// If the body stream errors in the middle of read, OpenDAL will send a
+    /// range request to continue from the last delivered offset. Servers that
+    /// don't support range requests will return `Unsupported` error instead.
+    /// The range request carries `If-Match` with the ETag of the first
+    /// response, so the read fails with `ConditionNotMatch` if the content
+    /// has been changed.
+    ///
+    /// Default to 3, set to 0 to disable resuming.
+    pub fn max_read_resumes(mut self, times: usize) -> Self {
+        self.config.max_read_resumes = Some(times);
+        self
+    }
+
     /// Specify the http client that used by this service.
     ///
     /// # Notes
@@ -190,21 +210,31 @@ impl Builder for HttpBuilder {
             auth = Some(format_authorization_by_bearer(token)?)
         }
 
+        let max_read_resumes = self
+            .config
+            .max_read_resumes
+            .unwrap_or(DEFAULT_MAX_READ_RESUMES);
+
         Ok(HttpBackend {
             endpoint: endpoint.to_string(),
             authorization: auth,
             root,
             client,
+            max_read_resumes,
         })
     }
 }
 
+/// The default max times to resume an interrupted read.
+const DEFAULT_MAX_READ_RESUMES: usize = 3;
+
 /// Backend is used to serve `Accessor` support for http.
 #[derive(Clone)]
 pub struct HttpBackend {
     endpoint: String,
     root: String,
     client: HttpClient,
+    max_read_resumes: usize,
 
     authorization: Option<String>,
 }
@@ -215,12 +245,13 @@ impl Debug for HttpBackend {
             .field("endpoint", &self.endpoint)
             .field("root", &self.root)
             .field("client", &self.client)
+            .field("max_read_resumes", &self.max_read_resumes)
             .finish()
     }
 }
 
 impl Access for HttpBackend {
-    type Reader = HttpBody;
+    type Reader = HttpReader;
     type Writer = ();
     type Lister = ();
     type BlockingReader = ();
@@ -264,6 +295,11 @@ impl Access for HttpBackend {
             StatusCode::NOT_FOUND | StatusCode::FORBIDDEN if path.ends_with('/') => {
                 Ok(RpStat::new(Metadata::new(EntryMode::DIR)))
             }
+            // Some servers (like signed CDN URLs) reject HEAD, fallback to GET
+            // the first byte instead.
+            StatusCode::METHOD_NOT_ALLOWED | StatusCode::FORBIDDEN => {
+                self.stat_by_get(path, &args).await.map(RpStat::new)
+            }
             _ => Err(parse_error(resp).await?),
         }
     }
@@ -275,7 +311,26 @@ impl Access for HttpBackend {
 
         match status {
             StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
-                Ok((RpRead::default(), resp.into_body()))
+                // Server accepts range requests if it returns partial content
+                // or claims `Accept-Ranges: bytes` explicitly.
+                let accept_ranges = status == StatusCode::PARTIAL_CONTENT
+                    || resp
+                        .headers()
+                        .get(header::ACCEPT_RANGES)
+                        .map(|v| v == "bytes")
+                        .unwrap_or_default();
+
+                let etag = parse_etag(resp.headers())?.map(|v| v.to_string());
+                let r = HttpReader::new(
+                    self.clone(),
+                    path,
+                    args,
+                    accept_ranges,
+                    etag.as_deref(),
+                    self.max_read_resumes,
+                    resp.into_body(),
+                );
+                Ok((RpRead::default(), r))
             }
             _ => {
                 let (part, mut body) = resp.into_parts();
@@ -320,6 +375,55 @@ impl HttpBackend {
         self.client.fetch(req).await
     }
 
+    /// Stat the file by sending `GET` with `Range: bytes=0-0`.
+    ///
+    /// The body is dropped without reading, so only one byte will be
+    /// transferred if server supports range requests.
+    async fn stat_by_get(&self, path: &str, args: &OpStat) -> Result<Metadata> {
+        let mut op = OpRead::new();
+        if let Some(v) = args.if_match() {
+            op = op.with_if_match(v);
+        }
+        if let Some(v) = args.if_none_match() {
+            op = op.with_if_none_match(v);
+        }
+
+        let resp = self
+            .http_get(path, BytesRange::new(0, Some(1)), &op)
+            .await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::PARTIAL_CONTENT => {
+                let mut meta = parse_into_metadata(path, resp.headers())?;
+                let size = parse_content_range(resp.headers())?
+                    .and_then(|v| v.size())
+                    .ok_or_else(|| {
+                        Error::new(
+                            ErrorKind::Unexpected,
+                            "content range of partial content response is invalid",
+                        )
+                    })?;
+                meta.set_content_length(size);
+                Ok(meta)
+            }
+            // Server doesn't support range, the content length is the full size.
+            StatusCode::OK => parse_into_metadata(path, resp.headers()),
+            // The file is empty so that the range can't be satisfied.
+            StatusCode::RANGE_NOT_SATISFIABLE => {
+                let mut meta = parse_into_metadata(path, resp.headers())?;
+                meta.set_content_length(0);
+                Ok(meta)
+            }
+            _ => {
+                let (part, mut body) = resp.into_parts();
+                let buf = body.to_buffer().await?;
+                Err(parse_error(Response::from_parts(part, buf)).await?)
+            }
+        }
+    }
+
     async fn http_head(&self, path: &str, args: &OpStat) -> Result<Response<Buffer>> {
         let p = build_rooted_abs_path(&self.root, path);
 
@@ -344,3 +448,86 @@ impl HttpBackend {
         self.client.send(req).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_backend(endpoint: &str) -> HttpBackend {
+        HttpBackend {
+            endpoint: endpoint.to_string(),
+            root: "/".to_string(),
+            client: HttpClient::new().unwrap(),
+            max_read_resumes: 1,
+            authorization: None,
+        }
+    }
+
+    /// A body that delivers `hello` and then breaks.
+    fn broken_body() -> HttpBody {
+        HttpBody::new(
+            futures::stream::iter(vec![
+                Ok(Buffer::from("hello")),
+                Err(Error::new(ErrorKind::Unexpected, "connection reset").set_temporary()),
+            ]),
+            Some(10),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_resume_read_with_etag() {
+        let server = MockServer::start(|req| match req.header("if-match") {
+            Some("\"v1\"") => MockResponse::new(StatusCode::PARTIAL_CONTENT)
+                .with_header("content-range", "bytes 5-9/10")
+                .with_body("world"),
+            _ => MockResponse::new(StatusCode::PRECONDITION_FAILED),
+        });
+
+        let mut r = HttpReader::new(
+            new_backend(server.endpoint()),
+            "file",
+            OpRead::new(),
+            true,
+            Some("\"v1\""),
+            1,
+            broken_body(),
+        );
+        let mut bs = vec![];
+        loop {
+            let buf = oio::Read::read(&mut r).await.unwrap();
+            if buf.is_empty() {
+                break;
+            }
+            bs.extend_from_slice(&buf.to_bytes());
+        }
+        assert_eq!(bs, b"helloworld");
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].header("range"), Some("bytes=5-"));
+        assert_eq!(requests[0].header("if-match"), Some("\"v1\""));
+    }
+
+    #[tokio::test]
+    async fn test_resume_read_content_changed() {
+        let server = MockServer::start(|req| match req.header("if-match") {
+            Some("\"v2\"") => MockResponse::new(StatusCode::PARTIAL_CONTENT)
+                .with_header("content-range", "bytes 5-9/10")
+                .with_body("WORLD"),
+            _ => MockResponse::new(StatusCode::PRECONDITION_FAILED),
+        });
+
+        let mut r = HttpReader::new(
+            new_backend(server.endpoint()),
+            "file",
+            OpRead::new(),
+            true,
+            Some("\"v1\""),
+            1,
+            broken_body(),
+        );
+        assert_eq!(oio::Read::read(&mut r).await.unwrap().to_bytes(), "hello");
+        let err = oio::Read::read(&mut r).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+    }
+}
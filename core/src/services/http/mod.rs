@@ -20,3 +20,4 @@ pub use backend::HttpBuilder as Http;
 pub use backend::HttpConfig;
 
 mod error;
+mod reader;
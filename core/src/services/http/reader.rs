@@ -0,0 +1,129 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use http::StatusCode;
+
+use super::backend::HttpBackend;
+use super::error::parse_error;
+use crate::raw::*;
+use crate::*;
+
+/// HttpReader wraps the response body and resumes the read from the last
+/// delivered offset with a range request if the body stream errors.
+///
+/// Resumed requests carry `If-Match` with the ETag of the first response, so
+/// that content changed in the middle won't be stitched together.
+pub struct HttpReader {
+    backend: HttpBackend,
+    path: String,
+    args: OpRead,
+
+    accept_ranges: bool,
+    max_resumes: usize,
+    resumes: usize,
+
+    /// The range that not delivered yet.
+    range: BytesRange,
+    body: HttpBody,
+}
+
+impl HttpReader {
+    pub fn new(
+        backend: HttpBackend,
+        path: &str,
+        mut args: OpRead,
+        accept_ranges: bool,
+        etag: Option<&str>,
+        max_resumes: usize,
+        body: HttpBody,
+    ) -> Self {
+        if let (None, Some(etag)) = (args.if_match(), etag) {
+            args = args.with_if_match(etag);
+        }
+
+        HttpReader {
+            backend,
+            path: path.to_string(),
+            range: args.range(),
+            args,
+            accept_ranges,
+            max_resumes,
+            resumes: 0,
+            body,
+        }
+    }
+
+    /// Resume the read from the last delivered offset.
+    async fn resume(&mut self, err: Error) -> Result<()> {
+        if !self.accept_ranges {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "server doesn't support range requests, read can't be resumed",
+            )
+            .with_context("path", &self.path)
+            .set_source(err));
+        }
+
+        let resp = self
+            .backend
+            .http_get(&self.path, self.range, &self.args)
+            .await?;
+
+        match resp.status() {
+            StatusCode::PARTIAL_CONTENT => {
+                self.body = resp.into_body();
+                Ok(())
+            }
+            // Server ignores our range request and returns the whole content.
+            StatusCode::OK => Err(Error::new(
+                ErrorKind::Unsupported,
+                "server ignores range request, read can't be resumed",
+            )
+            .with_context("path", &self.path)
+            .set_source(err)),
+            _ => {
+                let (part, mut body) = resp.into_parts();
+                let buf = body.to_buffer().await?;
+                Err(parse_error(http::Response::from_parts(part, buf)).await?)
+            }
+        }
+    }
+}
+
+impl oio::Read for HttpReader {
+    async fn read(&mut self) -> Result<Buffer> {
+        loop {
+            match self.body.read().await {
+                Ok(bs) => {
+                    // Ignore the data that exceeds the range, HttpBody will
+                    // report the error if needed.
+                    let n = match self.range.size() {
+                        Some(size) => (bs.len() as u64).min(size),
+                        None => bs.len() as u64,
+                    };
+                    self.range.advance(n);
+                    return Ok(bs);
+                }
+                Err(err) if err.is_temporary() && self.resumes < self.max_resumes => {
+                    self.resumes += 1;
+                    self.resume(err).await?;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
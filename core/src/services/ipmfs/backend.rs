@@ -110,23 +110,7 @@ impl Access for IpmfsBackend {
         let status = resp.status();
 
         match status {
-            StatusCode::OK => {
-                let bs = resp.into_body();
-
-                let res: IpfsStatResponse =
-                    serde_json::from_reader(bs.reader()).map_err(new_json_deserialize_error)?;
-
-                let mode = match res.file_type.as_str() {
-                    "file" => EntryMode::FILE,
-                    "directory" => EntryMode::DIR,
-                    _ => EntryMode::Unknown,
-                };
-
-                let mut meta = Metadata::new(mode);
-                meta.set_content_length(res.size);
-
-                Ok(RpStat::new(meta))
-            }
+            StatusCode::OK => Ok(RpStat::new(parse_stat_response(resp.into_body())?)),
             _ => Err(parse_error(resp).await?),
         }
     }
@@ -173,7 +157,7 @@ impl Access for IpmfsBackend {
 }
 
 impl IpmfsBackend {
-    async fn ipmfs_stat(&self, path: &str) -> Result<Response<Buffer>> {
+    pub async fn ipmfs_stat(&self, path: &str) -> Result<Response<Buffer>> {
         let p = build_rooted_abs_path(&self.root, path);
 
         let url = format!(
@@ -272,9 +256,32 @@ impl IpmfsBackend {
     }
 }
 
+/// Parse the response of `files/stat` into metadata.
+pub(super) fn parse_stat_response(bs: Buffer) -> Result<Metadata> {
+    let res: IpfsStatResponse =
+        serde_json::from_reader(bs.reader()).map_err(new_json_deserialize_error)?;
+
+    let mode = match res.file_type.as_str() {
+        "file" => EntryMode::FILE,
+        "directory" => EntryMode::DIR,
+        _ => EntryMode::Unknown,
+    };
+
+    let mut meta = Metadata::new(mode);
+    meta.set_content_length(res.size);
+    // Content in ipfs is addressed by CID, use it as etag.
+    if !res.hash.is_empty() {
+        meta.set_etag(&res.hash);
+    }
+
+    Ok(meta)
+}
+
 #[derive(Deserialize, Default, Debug)]
 #[serde(default)]
 struct IpfsStatResponse {
+    #[serde(rename = "Hash")]
+    hash: String,
     #[serde(rename = "Size")]
     size: u64,
     #[serde(rename = "Type")]
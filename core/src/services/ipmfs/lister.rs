@@ -68,10 +68,12 @@ impl oio::PageList for IpmfsLister {
 
             let path = build_rel_path(&self.root, &path);
 
-            ctx.entries.push_back(oio::Entry::new(
-                &path,
-                Metadata::new(object.mode()).with_content_length(object.size),
-            ));
+            let mut meta = Metadata::new(object.mode()).with_content_length(object.size);
+            if !object.hash.is_empty() {
+                meta.set_etag(&object.hash);
+            }
+
+            ctx.entries.push_back(oio::Entry::new(&path, meta));
         }
 
         Ok(())
@@ -87,6 +89,8 @@ struct IpfsLsResponseEntry {
     file_type: i64,
     #[serde(rename = "Size")]
     size: u64,
+    #[serde(rename = "Hash")]
+    hash: String,
 }
 
 impl IpfsLsResponseEntry {
@@ -117,3 +121,22 @@ struct IpfsLsResponse {
     #[serde(rename = "Entries")]
     entries: Option<Vec<IpfsLsResponseEntry>>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ls_response() {
+        let bs = r#"{"Entries":[{"Name":"hello.txt","Type":0,"Size":12,"Hash":"QmfM2r8seH2GiRaC4esTjeraXEachRt8ZsSeGaWTPLyMoG"},{"Name":"dir","Type":1,"Size":0,"Hash":"QmUNLLsPACCz1vLxQVkXqqLX5R1X345qqfHbsf67hvA3Nn"}]}"#;
+        let resp: IpfsLsResponse = serde_json::from_str(bs).expect("must succeed");
+        let entries = resp.entries.unwrap();
+
+        assert_eq!(entries[0].mode(), EntryMode::FILE);
+        assert_eq!(
+            entries[0].hash,
+            "QmfM2r8seH2GiRaC4esTjeraXEachRt8ZsSeGaWTPLyMoG"
+        );
+        assert_eq!(entries[1].mode(), EntryMode::DIR);
+    }
+}
@@ -17,6 +17,7 @@
 
 use http::StatusCode;
 
+use super::backend::parse_stat_response;
 use super::backend::IpmfsBackend;
 use super::error::parse_error;
 use crate::raw::*;
@@ -35,14 +36,56 @@ impl IpmfsWriter {
 }
 
 impl oio::OneShotWrite for IpmfsWriter {
-    async fn write_once(&self, bs: Buffer) -> Result<()> {
+    async fn write_once(&self, bs: Buffer) -> Result<Metadata> {
         let resp = self.backend.ipmfs_write(&self.path, bs).await?;
 
         let status = resp.status();
 
         match status {
-            StatusCode::CREATED | StatusCode::OK => Ok(()),
+            StatusCode::CREATED | StatusCode::OK => {}
+            _ => return Err(parse_error(resp).await?),
+        }
+
+        // `files/write` returns nothing, stat the file to get its CID.
+        let resp = self.backend.ipmfs_stat(&self.path).await?;
+
+        match resp.status() {
+            StatusCode::OK => parse_stat_response(resp.into_body()),
             _ => Err(parse_error(resp).await?),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raw::oio::OneShotWrite;
+
+    #[tokio::test]
+    async fn test_write_returns_cid() {
+        let server = MockServer::start(|req| {
+            if req.uri.starts_with("/api/v0/files/stat") {
+                return MockResponse::new(StatusCode::OK).with_body(
+                    r#"{"Hash":"QmfM2r8seH2GiRaC4esTjeraXEachRt8ZsSeGaWTPLyMoG","Size":12,"Type":"file"}"#,
+                );
+            }
+            MockResponse::new(StatusCode::OK)
+        });
+        let backend = IpmfsBackend::new(
+            "/".to_string(),
+            HttpClient::new().unwrap(),
+            server.endpoint().to_string(),
+        );
+
+        let meta = IpmfsWriter::new(backend, "hello.txt".to_string())
+            .write_once(Buffer::from("hello world\n"))
+            .await
+            .unwrap();
+        assert_eq!(meta.mode(), EntryMode::FILE);
+        assert_eq!(meta.content_length(), 12);
+        assert_eq!(
+            meta.etag(),
+            Some("QmfM2r8seH2GiRaC4esTjeraXEachRt8ZsSeGaWTPLyMoG")
+        );
+    }
+}
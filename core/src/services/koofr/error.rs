@@ -17,10 +17,36 @@
 
 use bytes::Buf;
 use http::Response;
+use serde::Deserialize;
 
 use crate::raw::*;
 use crate::*;
 
+/// KoofrError is the error returned by Koofr service.
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct KoofrError {
+    error: KoofrErrorDetail,
+}
+
+#[derive(Default, Debug, Deserialize)]
+#[serde(default)]
+struct KoofrErrorDetail {
+    code: String,
+    message: String,
+}
+
+impl KoofrError {
+    fn is_already_exists(&self) -> bool {
+        self.error.code == "AlreadyExists"
+            || self
+                .error
+                .message
+                .to_ascii_lowercase()
+                .contains("already exists")
+    }
+}
+
 /// Parse error response into Error.
 pub async fn parse_error(resp: Response<Buffer>) -> Result<Error> {
     let (parts, mut body) = resp.into_parts();
@@ -37,7 +63,18 @@ pub async fn parse_error(resp: Response<Buffer>) -> Result<Error> {
         _ => (ErrorKind::Unexpected, false),
     };
 
-    let message = String::from_utf8_lossy(&bs).into_owned();
+    let (message, koofr_err) = serde_json::from_slice::<KoofrError>(&bs)
+        .map(|koofr_err| (format!("{koofr_err:?}"), Some(koofr_err)))
+        .unwrap_or_else(|_| (String::from_utf8_lossy(&bs).into_owned(), None));
+
+    // Koofr returns 409 for other conflicts besides an existing destination
+    // of copy or move, only the error body can tell.
+    let kind = match koofr_err {
+        Some(e) if parts.status.as_u16() == 409 && e.is_already_exists() => {
+            ErrorKind::AlreadyExists
+        }
+        _ => kind,
+    };
 
     let mut err = Error::new(kind, message);
 
@@ -58,7 +95,25 @@ mod test {
 
     #[tokio::test]
     async fn test_parse_error() {
-        let err_res = vec![(r#""#, ErrorKind::NotFound, StatusCode::NOT_FOUND)];
+        let err_res = vec![
+            (r#""#, ErrorKind::NotFound, StatusCode::NOT_FOUND),
+            (
+                r#"{"error":{"code":"AlreadyExists","message":"Object already exists"}}"#,
+                ErrorKind::AlreadyExists,
+                StatusCode::CONFLICT,
+            ),
+            (
+                r#"{"error":{"code":"Conflict","message":"File already exists"}}"#,
+                ErrorKind::AlreadyExists,
+                StatusCode::CONFLICT,
+            ),
+            (
+                r#"{"error":{"code":"Conflict","message":"Conflict"}}"#,
+                ErrorKind::Unexpected,
+                StatusCode::CONFLICT,
+            ),
+            (r#"Conflict"#, ErrorKind::Unexpected, StatusCode::CONFLICT),
+        ];
 
         for res in err_res {
             let bs = bytes::Bytes::from(res.0);
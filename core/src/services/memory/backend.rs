@@ -20,6 +20,7 @@ use std::fmt::Debug;
 use std::sync::Arc;
 use std::sync::Mutex;
 
+use bytes::Bytes;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -34,11 +35,20 @@ use crate::*;
 pub struct MemoryConfig {
     /// root of the backend.
     pub root: Option<String>,
+    /// max bytes of all entries, least recently used entries will be evicted
+    /// if exceeded.
+    pub max_capacity_bytes: Option<usize>,
+    /// max number of entries, least recently used entries will be evicted
+    /// if exceeded.
+    pub max_entries: Option<usize>,
 }
 
 impl Configurator for MemoryConfig {
     fn into_builder(self) -> impl Builder {
-        MemoryBuilder { config: self }
+        MemoryBuilder {
+            config: self,
+            snapshot: None,
+        }
     }
 }
 
@@ -47,14 +57,67 @@ impl Configurator for MemoryConfig {
 #[derive(Default)]
 pub struct MemoryBuilder {
     config: MemoryConfig,
+    snapshot: Option<Bytes>,
 }
 
 impl MemoryBuilder {
+    /// Create a builder that restores entries from a snapshot created by
+    /// [`MemoryBackend::snapshot`].
+    ///
+    /// The snapshot is decoded while building, and building fails if it's
+    /// malformed or written by a newer format version.
+    pub fn from_snapshot(bs: Bytes) -> Self {
+        Self {
+            snapshot: Some(bs),
+            ..Default::default()
+        }
+    }
+
     /// Set the root for BTreeMap.
     pub fn root(mut self, path: &str) -> Self {
         self.config.root = Some(path.into());
         self
     }
+
+    /// Set the max bytes of all entries.
+    ///
+    /// The size of an entry is the length of its key plus the length of its
+    /// value. Least recently used entries will be evicted once exceeded, and
+    /// writing an entry larger than the whole capacity will fail.
+    pub fn max_capacity_bytes(mut self, v: usize) -> Self {
+        self.config.max_capacity_bytes = Some(v);
+        self
+    }
+
+    /// Set the max number of entries.
+    ///
+    /// Least recently used entries will be evicted once exceeded.
+    pub fn max_entries(mut self, v: usize) -> Self {
+        self.config.max_entries = Some(v);
+        self
+    }
+
+    /// Build the memory backend.
+    ///
+    /// Unlike [`Builder::build`], this returns the concrete backend so that
+    /// callers can take a snapshot of it later.
+    pub fn build_backend(self) -> Result<MemoryBackend> {
+        let mut store = Store {
+            max_capacity_bytes: self.config.max_capacity_bytes,
+            max_entries: self.config.max_entries,
+            ..Default::default()
+        };
+        if let Some(bs) = self.snapshot {
+            for (path, value) in typed_kv::decode_snapshot(bs)? {
+                store.set(&path, value)?;
+            }
+        }
+
+        let adapter = Adapter {
+            inner: Arc::new(Mutex::new(store)),
+        };
+        Ok(MemoryBackend::new(adapter).with_root(self.config.root.as_deref().unwrap_or_default()))
+    }
 }
 
 impl Builder for MemoryBuilder {
@@ -62,11 +125,7 @@ impl Builder for MemoryBuilder {
     type Config = MemoryConfig;
 
     fn build(self) -> Result<impl Access> {
-        let adapter = Adapter {
-            inner: Arc::new(Mutex::new(BTreeMap::default())),
-        };
-
-        Ok(MemoryBackend::new(adapter).with_root(self.config.root.as_deref().unwrap_or_default()))
+        self.build_backend()
     }
 }
 
@@ -75,7 +134,122 @@ pub type MemoryBackend = typed_kv::Backend<Adapter>;
 
 #[derive(Clone)]
 pub struct Adapter {
-    inner: Arc<Mutex<BTreeMap<String, typed_kv::Value>>>,
+    inner: Arc<Mutex<Store>>,
+}
+
+impl MemoryBackend {
+    /// Get the current stats of this memory backend.
+    ///
+    /// The stats cover the whole backend regardless of root.
+    pub fn stats(&self) -> MemoryStats {
+        self.adapter().stats()
+    }
+}
+
+impl Adapter {
+    fn stats(&self) -> MemoryStats {
+        let inner = self.inner.lock().unwrap();
+        MemoryStats {
+            entries: inner.map.len(),
+            bytes: inner.bytes,
+            evictions: inner.evictions,
+        }
+    }
+}
+
+/// Stats of memory backend.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Number of entries.
+    pub entries: usize,
+    /// Total size of entries in bytes.
+    pub bytes: usize,
+    /// Number of entries that have been evicted.
+    pub evictions: u64,
+}
+
+/// Store keeps entries in a BTreeMap for scanning, and tracks the access
+/// order of them for LRU eviction.
+#[derive(Default)]
+struct Store {
+    max_capacity_bytes: Option<usize>,
+    max_entries: Option<usize>,
+
+    map: BTreeMap<String, Entry>,
+    /// Access order of entries, the smallest tick is the least recently used.
+    lru: BTreeMap<u64, String>,
+    tick: u64,
+    bytes: usize,
+    evictions: u64,
+}
+
+struct Entry {
+    value: typed_kv::Value,
+    tick: u64,
+}
+
+impl Store {
+    fn entry_size(path: &str, value: &typed_kv::Value) -> usize {
+        path.len() + value.value.len()
+    }
+
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn get(&mut self, path: &str) -> Option<typed_kv::Value> {
+        let tick = self.next_tick();
+        let entry = self.map.get_mut(path)?;
+        self.lru.remove(&entry.tick);
+        self.lru.insert(tick, path.to_string());
+        entry.tick = tick;
+        Some(entry.value.clone())
+    }
+
+    fn set(&mut self, path: &str, value: typed_kv::Value) -> Result<()> {
+        let size = Self::entry_size(path, &value);
+        if let Some(max) = self.max_capacity_bytes {
+            if size > max {
+                return Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "entry is larger than the max capacity of memory backend",
+                )
+                .with_context("path", path)
+                .with_context("size", size.to_string())
+                .with_context("max_capacity_bytes", max.to_string()));
+            }
+        }
+
+        self.delete(path);
+        let tick = self.next_tick();
+        self.map.insert(path.to_string(), Entry { value, tick });
+        self.lru.insert(tick, path.to_string());
+        self.bytes += size;
+
+        while self.is_exceeded() && self.map.len() > 1 {
+            let Some((_, key)) = self.lru.pop_first() else {
+                break;
+            };
+            if let Some(entry) = self.map.remove(&key) {
+                self.bytes -= Self::entry_size(&key, &entry.value);
+                self.evictions += 1;
+            }
+        }
+        Ok(())
+    }
+
+    fn delete(&mut self, path: &str) {
+        if let Some(entry) = self.map.remove(path) {
+            self.lru.remove(&entry.tick);
+            self.bytes -= Self::entry_size(path, &entry.value);
+        }
+    }
+
+    fn is_exceeded(&self) -> bool {
+        self.max_capacity_bytes.is_some_and(|v| self.bytes > v)
+            || self.max_entries.is_some_and(|v| self.map.len() > v)
+    }
 }
 
 impl Debug for Adapter {
@@ -103,10 +277,17 @@ impl typed_kv::Adapter for Adapter {
     }
 
     fn blocking_get(&self, path: &str) -> Result<Option<typed_kv::Value>> {
-        match self.inner.lock().unwrap().get(path) {
-            None => Ok(None),
-            Some(bs) => Ok(Some(bs.to_owned())),
-        }
+        Ok(self.inner.lock().unwrap().get(path))
+    }
+
+    async fn exists(&self, path: &str) -> Result<bool> {
+        self.blocking_exists(path)
+    }
+
+    /// Check the key directly without cloning the value, this doesn't count
+    /// as an access for LRU eviction either.
+    fn blocking_exists(&self, path: &str) -> Result<bool> {
+        Ok(self.inner.lock().unwrap().map.contains_key(path))
     }
 
     async fn set(&self, path: &str, value: typed_kv::Value) -> Result<()> {
@@ -114,9 +295,7 @@ impl typed_kv::Adapter for Adapter {
     }
 
     fn blocking_set(&self, path: &str, value: typed_kv::Value) -> Result<()> {
-        self.inner.lock().unwrap().insert(path.to_string(), value);
-
-        Ok(())
+        self.inner.lock().unwrap().set(path, value)
     }
 
     async fn delete(&self, path: &str) -> Result<()> {
@@ -124,7 +303,7 @@ impl typed_kv::Adapter for Adapter {
     }
 
     fn blocking_delete(&self, path: &str) -> Result<()> {
-        self.inner.lock().unwrap().remove(path);
+        self.inner.lock().unwrap().delete(path);
 
         Ok(())
     }
@@ -136,7 +315,7 @@ impl typed_kv::Adapter for Adapter {
     fn blocking_scan(&self, path: &str) -> Result<Vec<String>> {
         let inner = self.inner.lock().unwrap();
         let keys: Vec<_> = if path.is_empty() {
-            inner.keys().cloned().collect()
+            inner.map.keys().cloned().collect()
         } else {
             let right_range = if let Some(path) = path.strip_suffix('/') {
                 format!("{}0", path)
@@ -144,6 +323,7 @@ impl typed_kv::Adapter for Adapter {
                 format!("{}{}", path, std::char::MAX)
             };
             inner
+                .map
                 .range(path.to_string()..right_range)
                 .filter(|(k, _)| k.as_str() != path)
                 .map(|(k, _)| k.to_string())
@@ -166,4 +346,118 @@ mod tests {
         let b2 = MemoryBuilder::default().build().unwrap();
         assert_ne!(b1.info().name(), b2.info().name())
     }
+
+    #[test]
+    fn test_lru_eviction() {
+        let adapter = Adapter {
+            inner: Arc::new(Mutex::new(Store {
+                max_capacity_bytes: Some(30),
+                max_entries: Some(3),
+                ..Default::default()
+            })),
+        };
+        let value = |v: &str| typed_kv::Value {
+            metadata: Metadata::new(EntryMode::FILE),
+            value: Buffer::from(v.to_string()),
+        };
+
+        use typed_kv::Adapter as _;
+        adapter.blocking_set("a", value("aaaa")).unwrap();
+        adapter.blocking_set("b", value("bbbb")).unwrap();
+        adapter.blocking_set("c", value("cccc")).unwrap();
+        // Touch `a` so that `b` becomes the least recently used one.
+        assert!(adapter.blocking_get("a").unwrap().is_some());
+
+        // Exceeds max entries.
+        adapter.blocking_set("d", value("dddd")).unwrap();
+        assert!(adapter.blocking_get("b").unwrap().is_none());
+        assert_eq!(
+            adapter.stats(),
+            MemoryStats {
+                entries: 3,
+                bytes: 15,
+                evictions: 1
+            }
+        );
+
+        // Exceeds max capacity, `c` and `a` will be evicted.
+        adapter.blocking_set("e", value(&"e".repeat(20))).unwrap();
+        assert!(adapter.blocking_get("c").unwrap().is_none());
+        assert!(adapter.blocking_get("a").unwrap().is_none());
+        assert_eq!(
+            adapter.stats(),
+            MemoryStats {
+                entries: 2,
+                bytes: 26,
+                evictions: 3
+            }
+        );
+
+        // Overwrite should not count twice.
+        adapter.blocking_set("d", value("dd")).unwrap();
+        assert_eq!(adapter.stats().bytes, 24);
+
+        // Larger than the whole capacity.
+        let err = adapter
+            .blocking_set("f", value(&"f".repeat(30)))
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+        assert_eq!(adapter.stats().entries, 2);
+    }
+
+    #[test]
+    fn test_backend_stats() {
+        let backend = MemoryBuilder::default()
+            .max_entries(1)
+            .build_backend()
+            .unwrap();
+        let op = OperatorBuilder::new(backend.clone()).finish().blocking();
+        op.write("a", "hello").unwrap();
+        op.write("b", "world").unwrap();
+
+        let stats = backend.stats();
+        assert_eq!(stats.entries, 1);
+        assert_eq!(stats.evictions, 1);
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let backend = MemoryBuilder::default().build_backend().unwrap();
+        let op = OperatorBuilder::new(backend.clone()).finish().blocking();
+        op.write_with("a/b/c.txt", "hello")
+            .content_type("text/plain")
+            .call()
+            .unwrap();
+        op.write("a/empty", "").unwrap();
+        op.write("d", "world").unwrap();
+
+        let bs = backend.snapshot().unwrap();
+        let restored = MemoryBuilder::from_snapshot(bs).build_backend().unwrap();
+        let op = OperatorBuilder::new(restored).finish().blocking();
+
+        let meta = op.stat("a/b/c.txt").unwrap();
+        assert_eq!(meta.content_type(), Some("text/plain"));
+        assert_eq!(op.read("a/b/c.txt").unwrap().to_bytes(), "hello");
+        assert!(op.read("a/empty").unwrap().is_empty());
+        assert_eq!(op.read("d").unwrap().to_bytes(), "world");
+
+        let mut entries: Vec<_> = op
+            .list_with("a/")
+            .recursive(true)
+            .call()
+            .unwrap()
+            .into_iter()
+            .map(|e| e.path().to_string())
+            .collect();
+        entries.sort();
+        assert_eq!(entries, vec!["a/b/c.txt", "a/empty"]);
+    }
+
+    #[test]
+    fn test_snapshot_invalid() {
+        let err = MemoryBuilder::from_snapshot(Bytes::from("not a snapshot"))
+            .build_backend()
+            .unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Unexpected);
+    }
 }
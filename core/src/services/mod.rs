@@ -38,7 +38,21 @@ mod azdls;
 #[cfg(feature = "services-azdls")]
 pub use azdls::Azdls;
 #[cfg(feature = "services-azdls")]
+pub use azdls::AzdlsBackend;
+#[cfg(feature = "services-azdls")]
 pub use azdls::AzdlsConfig;
+#[cfg(feature = "services-azdls")]
+pub use azdls::AzdlsDirSize;
+#[cfg(feature = "services-azdls")]
+pub use azdls::AzdlsPageLister;
+#[cfg(feature = "services-azdls")]
+pub use azdls::AzdlsProperties;
+#[cfg(feature = "services-azdls")]
+pub use azdls::AzdlsPropertiesExt;
+#[cfg(feature = "services-azdls")]
+pub use azdls::AzdlsVersion;
+#[cfg(feature = "services-azdls")]
+pub use azdls::AzdlsVersionLister;
 
 #[cfg(feature = "services-cloudflare-kv")]
 mod cloudflare_kv;
@@ -59,6 +73,8 @@ mod dashmap;
 #[cfg(feature = "services-dashmap")]
 pub use self::dashmap::Dashmap;
 #[cfg(feature = "services-dashmap")]
+pub use self::dashmap::DashmapBackend;
+#[cfg(feature = "services-dashmap")]
 pub use self::dashmap::DashmapConfig;
 
 #[cfg(feature = "services-etcd")]
@@ -164,7 +180,11 @@ mod memory;
 #[cfg(feature = "services-memory")]
 pub use self::memory::Memory;
 #[cfg(feature = "services-memory")]
+pub use self::memory::MemoryBackend;
+#[cfg(feature = "services-memory")]
 pub use self::memory::MemoryConfig;
+#[cfg(feature = "services-memory")]
+pub use self::memory::MemoryStats;
 
 #[cfg(feature = "services-mini-moka")]
 mod mini_moka;
@@ -276,6 +296,8 @@ mod gdrive;
 #[cfg(feature = "services-gdrive")]
 pub use gdrive::Gdrive;
 #[cfg(feature = "services-gdrive")]
+pub use gdrive::GdriveBackend;
+#[cfg(feature = "services-gdrive")]
 pub use gdrive::GdriveConfig;
 
 #[cfg(feature = "services-github")]
@@ -290,6 +312,8 @@ mod dropbox;
 #[cfg(feature = "services-dropbox")]
 pub use dropbox::Dropbox;
 #[cfg(feature = "services-dropbox")]
+pub use dropbox::DropboxBackend;
+#[cfg(feature = "services-dropbox")]
 pub use dropbox::DropboxConfig;
 
 #[cfg(feature = "services-vercel-artifacts")]
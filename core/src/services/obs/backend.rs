@@ -291,9 +291,12 @@ impl Access for ObsBackend {
                 write: true,
                 write_can_empty: true,
                 write_can_append: true,
+                write_returns_append_offset: true,
                 write_can_multi: true,
                 write_with_content_type: true,
                 write_with_cache_control: true,
+                write_returns_etag: true,
+                write_returns_version: true,
                 // The min multipart size of OBS is 5 MiB.
                 //
                 // ref: <https://support.huaweicloud.com/intl/en-us/ugobs-obs/obs_41_0021.html>
@@ -317,6 +320,7 @@ impl Access for ObsBackend {
                 presign_stat: true,
                 presign_read: true,
                 presign_write: true,
+                presign_delete: true,
 
                 ..Default::default()
             });
@@ -412,7 +416,15 @@ impl Access for ObsBackend {
                 self.core
                     .obs_put_object_request(path, None, v, Buffer::new())?
             }
+            PresignOperation::Delete(_) => self.core.obs_delete_object_request(path)?,
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "presign operation is not supported",
+                ))
+            }
         };
+        req.headers_mut().extend(args.signed_headers().clone());
         self.core.sign_query(&mut req, args.expire()).await?;
 
         // We don't need this request anymore, consume it directly.
@@ -63,7 +63,7 @@ impl Debug for OnedriveBackend {
 
 impl Access for OnedriveBackend {
     type Reader = HttpBody;
-    type Writer = oio::OneShotWriter<OneDriveWriter>;
+    type Writer = oio::RangeWriter<OneDriveWriter>;
     type Lister = oio::PageLister<OnedriveLister>;
     type BlockingReader = ();
     type BlockingWriter = ();
@@ -76,6 +76,10 @@ impl Access for OnedriveBackend {
             .set_native_capability(Capability {
                 read: true,
                 write: true,
+                write_can_multi: true,
+                write_multi_min_size: Some(OneDriveWriter::DEFAULT_CHUNK_SIZE),
+                write_multi_max_size: Some(OneDriveWriter::MAX_CHUNK_SIZE),
+                write_multi_align_size: Some(OneDriveWriter::CHUNK_SIZE_FACTOR),
                 stat: true,
                 delete: true,
                 create_dir: true,
@@ -168,11 +172,12 @@ impl Access for OnedriveBackend {
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
         let path = build_rooted_abs_path(&self.root, path);
+        let executor = args.executor().cloned();
+        let w = OneDriveWriter::new(self.clone(), args, path);
+        // Ranges of an upload session must be uploaded in order.
+        let w = oio::RangeWriter::new(w, executor, 1);
 
-        Ok((
-            RpWrite::default(),
-            oio::OneShotWriter::new(OneDriveWriter::new(self.clone(), args, path)),
-        ))
+        Ok((RpWrite::default(), w))
     }
 
     /// Delete operation
@@ -279,31 +284,52 @@ impl OnedriveBackend {
         self.client.send(req).await
     }
 
+    /// Upload a range of bytes to the upload session.
+    ///
+    /// `total` is `None` if the total size is unknown yet, which is the case
+    /// for all ranges except the last one.
+    ///
+    /// The upload url is pre-authenticated, the `Authorization` header must
+    /// not be sent.
     pub(crate) async fn onedrive_chunked_upload(
         &self,
         url: &str,
-        args: &OpWrite,
-        offset: usize,
-        chunk_end: usize,
-        total_len: usize,
+        offset: u64,
+        total: Option<u64>,
         body: Buffer,
     ) -> Result<Response<Buffer>> {
-        let mut req = Request::put(url);
-
-        let auth_header_content = format!("Bearer {}", self.access_token);
-        req = req.header(header::AUTHORIZATION, auth_header_content);
+        let size = body.len() as u64;
+        let range = match total {
+            Some(total) => format!("bytes {}-{}/{}", offset, offset + size - 1, total),
+            None => format!("bytes {}-{}/*", offset, offset + size - 1),
+        };
+
+        let req = Request::put(url)
+            .header(header::CONTENT_RANGE, range)
+            .header(header::CONTENT_LENGTH, size)
+            .body(body)
+            .map_err(new_request_build_error)?;
 
-        let range = format!("bytes {}-{}/{}", offset, chunk_end, total_len);
-        req = req.header("Content-Range".to_string(), range);
+        self.client.send(req).await
+    }
 
-        let size = chunk_end - offset + 1;
-        req = req.header(header::CONTENT_LENGTH, size.to_string());
+    /// Reference: <https://learn.microsoft.com/en-us/onedrive/developer/rest-api/api/driveitem_createuploadsession?view=odsp-graph-online#resuming-an-in-progress-upload>
+    pub(crate) async fn onedrive_get_upload_session(&self, url: &str) -> Result<Response<Buffer>> {
+        let req = Request::get(url)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
 
-        if let Some(mime) = args.content_type() {
-            req = req.header(header::CONTENT_TYPE, mime)
-        }
+        self.client.send(req).await
+    }
 
-        let req = req.body(body).map_err(new_request_build_error)?;
+    /// Reference: <https://learn.microsoft.com/en-us/onedrive/developer/rest-api/api/driveitem_createuploadsession?view=odsp-graph-online#cancel-the-upload-session>
+    pub(crate) async fn onedrive_cancel_upload_session(
+        &self,
+        url: &str,
+    ) -> Result<Response<Buffer>> {
+        let req = Request::delete(url)
+            .body(Buffer::new())
+            .map_err(new_request_build_error)?;
 
         self.client.send(req).await
     }
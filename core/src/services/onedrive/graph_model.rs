@@ -125,6 +125,26 @@ pub struct OneDriveUploadSessionCreationResponseBody {
     pub expiration_date_time: String,
 }
 
+/// The status of an upload session, returned while uploading a range or
+/// querying the session.
+///
+/// Reference: <https://learn.microsoft.com/en-us/onedrive/developer/rest-api/api/driveitem_createuploadsession?view=odsp-graph-online#resuming-an-in-progress-upload>
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OneDriveUploadSessionStatus {
+    /// Ranges that the server is still missing, like `["12345-55232", "77829-99375"]`.
+    #[serde(default)]
+    pub next_expected_ranges: Vec<String>,
+}
+
+impl OneDriveUploadSessionStatus {
+    /// The offset of the first byte the server expects next.
+    pub fn next_expected_offset(&self) -> Option<u64> {
+        let range = self.next_expected_ranges.first()?;
+        range.split('-').next()?.parse().ok()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OneDriveUploadSessionCreationRequestBody {
     item: FileUploadItem,
@@ -291,3 +311,21 @@ fn test_parse_folder_single() {
         panic!("item_type is not folder");
     }
 }
+
+#[test]
+fn test_parse_upload_session_status() {
+    let data = r#"{
+        "expirationDateTime": "2015-01-29T09:21:55.523Z",
+        "nextExpectedRanges": ["12345-55232", "77829-99375"]
+    }"#;
+
+    let status: OneDriveUploadSessionStatus = serde_json::from_str(data).unwrap();
+    assert_eq!(status.next_expected_offset(), Some(12345));
+
+    let status: OneDriveUploadSessionStatus =
+        serde_json::from_str(r#"{"nextExpectedRanges": ["0-"]}"#).unwrap();
+    assert_eq!(status.next_expected_offset(), Some(0));
+
+    let status: OneDriveUploadSessionStatus = serde_json::from_str("{}").unwrap();
+    assert_eq!(status.next_expected_offset(), None);
+}
@@ -16,13 +16,13 @@
 // under the License.
 
 use bytes::Buf;
-use bytes::Bytes;
 use http::StatusCode;
 
 use super::backend::OnedriveBackend;
 use super::error::parse_error;
 use super::graph_model::OneDriveUploadSessionCreationRequestBody;
 use super::graph_model::OneDriveUploadSessionCreationResponseBody;
+use super::graph_model::OneDriveUploadSessionStatus;
 use crate::raw::*;
 use crate::*;
 
@@ -37,28 +37,61 @@ impl OneDriveWriter {
     const MAX_SIMPLE_SIZE: usize = 4 * 1024 * 1024;
     // If your app splits a file into multiple byte ranges, the size of each byte range MUST be a multiple of 320 KiB (327,680 bytes). Using a fragment size that does not divide evenly by 320 KiB will result in errors committing some files.
     // https://learn.microsoft.com/en-us/onedrive/developer/rest-api/api/driveitem_createuploadsession?view=odsp-graph-online#upload-bytes-to-the-upload-session
-    const CHUNK_SIZE_FACTOR: usize = 327_680;
+    pub(crate) const CHUNK_SIZE_FACTOR: usize = 327_680;
+    /// 5 MiB, which is 16 times of `CHUNK_SIZE_FACTOR`.
+    pub(crate) const DEFAULT_CHUNK_SIZE: usize = 16 * Self::CHUNK_SIZE_FACTOR;
+    /// Each request of an upload session must be less than 60 MiB.
+    pub(crate) const MAX_CHUNK_SIZE: usize = 192 * Self::CHUNK_SIZE_FACTOR;
+
     pub fn new(backend: OnedriveBackend, op: OpWrite, path: String) -> Self {
         OneDriveWriter { backend, op, path }
     }
 }
 
-impl oio::OneShotWrite for OneDriveWriter {
-    async fn write_once(&self, bs: Buffer) -> Result<()> {
-        let size = bs.len();
-
-        if size <= Self::MAX_SIMPLE_SIZE {
-            self.write_simple(bs).await?;
-        } else {
-            self.write_chunked(bs.to_bytes()).await?;
+impl oio::RangeWrite for OneDriveWriter {
+    async fn write_once(&self, body: Buffer) -> Result<Metadata> {
+        if body.len() <= Self::MAX_SIMPLE_SIZE {
+            return self.write_simple(body).await;
         }
 
-        Ok(())
+        // The simple upload endpoint caps at 4 MiB, upload the whole content
+        // as the only range of a session instead.
+        let location = self.initiate_range().await?;
+        self.complete_range(&location, 0, body).await
+    }
+
+    async fn initiate_range(&self) -> Result<String> {
+        let session = self.create_upload_session().await?;
+        Ok(session.upload_url)
+    }
+
+    async fn write_range(&self, location: &str, offset: u64, body: Buffer) -> Result<()> {
+        self.upload_range(location, offset, None, body).await
+    }
+
+    async fn complete_range(&self, location: &str, offset: u64, body: Buffer) -> Result<Metadata> {
+        let total = offset + body.len() as u64;
+        self.upload_range(location, offset, Some(total), body)
+            .await?;
+        Ok(Metadata::new(EntryMode::FILE))
+    }
+
+    async fn abort_range(&self, location: &str) -> Result<()> {
+        let resp = self
+            .backend
+            .onedrive_cancel_upload_session(location)
+            .await?;
+
+        match resp.status() {
+            // The session could have been expired or cleaned up already.
+            StatusCode::NO_CONTENT | StatusCode::NOT_FOUND => Ok(()),
+            _ => Err(parse_error(resp).await?),
+        }
     }
 }
 
 impl OneDriveWriter {
-    async fn write_simple(&self, bs: Buffer) -> Result<()> {
+    async fn write_simple(&self, bs: Buffer) -> Result<Metadata> {
         let resp = self
             .backend
             .onedrive_upload_simple(&self.path, Some(bs.len()), &self.op, bs)
@@ -69,71 +102,90 @@ impl OneDriveWriter {
         match status {
             // Typical response code: 201 Created
             // Reference: https://learn.microsoft.com/en-us/onedrive/developer/rest-api/api/driveitem_put_content?view=odsp-graph-online#response
-            StatusCode::CREATED | StatusCode::OK => Ok(()),
+            StatusCode::CREATED | StatusCode::OK => Ok(Metadata::new(EntryMode::FILE)),
             _ => Err(parse_error(resp).await?),
         }
     }
 
-    pub(crate) async fn write_chunked(&self, total_bytes: Bytes) -> Result<()> {
-        // Upload large files via sessions: https://learn.microsoft.com/en-us/onedrive/developer/rest-api/api/driveitem_createuploadsession?view=odsp-graph-online#upload-bytes-to-the-upload-session
-        // 1. Create an upload session
-        // 2. Upload the bytes of each chunk
-        // 3. Commit the session
+    /// Upload a range, and resume from `nextExpectedRanges` if the upload
+    /// failed after the server received part of it.
+    async fn upload_range(
+        &self,
+        location: &str,
+        offset: u64,
+        total: Option<u64>,
+        body: Buffer,
+    ) -> Result<()> {
+        let err = match self.send_range(location, offset, total, body.clone()).await {
+            Ok(()) => return Ok(()),
+            Err(err) => err,
+        };
+
+        let size = body.len() as u64;
+        let next = match self.next_expected_offset(location).await {
+            Ok(Some(next)) => next,
+            // Return the original error if the session status is unknown.
+            _ => return Err(err),
+        };
+
+        if next <= offset || next > offset + size {
+            return Err(err);
+        }
+        // All bytes of this range have been received for non-last ranges.
+        if next == offset + size && total.is_none() {
+            return Ok(());
+        }
+
+        let body = body.slice((next - offset) as usize..);
+        self.send_range(location, next, total, body)
+            .await
+            .map_err(|err| err.with_context("resumed_offset", next.to_string()))
+    }
 
-        let session_response = self.create_upload_session().await?;
+    async fn send_range(
+        &self,
+        location: &str,
+        offset: u64,
+        total: Option<u64>,
+        body: Buffer,
+    ) -> Result<()> {
+        let resp = self
+            .backend
+            .onedrive_chunked_upload(location, offset, total, body)
+            .await?;
 
-        let mut offset = 0;
+        match resp.status() {
+            // 202 Accepted for the intermediate ranges, 200 OK or 201 Created for the last one.
+            // Reference: https://learn.microsoft.com/en-us/onedrive/developer/rest-api/api/driveitem_createuploadsession?view=odsp-graph-online#completing-a-file
+            StatusCode::ACCEPTED | StatusCode::CREATED | StatusCode::OK => Ok(()),
+            _ => Err(parse_error(resp)
+                .await?
+                .with_context("offset", offset.to_string())),
+        }
+    }
 
-        let iter = total_bytes.chunks(OneDriveWriter::CHUNK_SIZE_FACTOR);
+    async fn next_expected_offset(&self, location: &str) -> Result<Option<u64>> {
+        let resp = self.backend.onedrive_get_upload_session(location).await?;
 
-        for chunk in iter {
-            let mut end = offset + OneDriveWriter::CHUNK_SIZE_FACTOR;
-            if end > total_bytes.len() {
-                end = total_bytes.len();
-            }
-            let total_len = total_bytes.len();
-            let chunk_end = end - 1;
-
-            let resp = self
-                .backend
-                .onedrive_chunked_upload(
-                    &session_response.upload_url,
-                    &OpWrite::default(),
-                    offset,
-                    chunk_end,
-                    total_len,
-                    Buffer::from(Bytes::copy_from_slice(chunk)),
-                )
-                .await?;
-
-            let status = resp.status();
-
-            match status {
-                // Typical response code: 202 Accepted
-                // Reference: https://learn.microsoft.com/en-us/onedrive/developer/rest-api/api/driveitem_put_content?view=odsp-graph-online#response
-                StatusCode::ACCEPTED | StatusCode::CREATED | StatusCode::OK => {}
-                _ => return Err(parse_error(resp).await?),
+        match resp.status() {
+            StatusCode::OK => {
+                let bs = resp.into_body();
+                let status: OneDriveUploadSessionStatus =
+                    serde_json::from_reader(bs.reader()).map_err(new_json_deserialize_error)?;
+                Ok(status.next_expected_offset())
             }
-
-            offset += OneDriveWriter::CHUNK_SIZE_FACTOR;
+            _ => Err(parse_error(resp).await?),
         }
-
-        Ok(())
     }
 
     async fn create_upload_session(&self) -> Result<OneDriveUploadSessionCreationResponseBody> {
-        let file_name_from_path = self.path.split('/').last().ok_or_else(|| {
-            Error::new(
-                ErrorKind::Unexpected,
-                "connection string must have AccountName",
-            )
-        })?;
+        let file_name = get_basename(&self.path);
         let url = format!(
             "{}/drive/root:{}:/createUploadSession",
             OnedriveBackend::BASE_URL,
             percent_encode_path(&self.path)
         );
-        let body = OneDriveUploadSessionCreationRequestBody::new(file_name_from_path.to_string());
+        let body = OneDriveUploadSessionCreationRequestBody::new(file_name.to_string());
 
         let resp = self
             .backend
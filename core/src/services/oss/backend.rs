@@ -416,6 +416,7 @@ impl Access for OssBackend {
                 write: true,
                 write_can_empty: true,
                 write_can_append: true,
+                write_returns_append_offset: true,
                 write_can_multi: true,
                 write_with_cache_control: true,
                 write_with_content_type: true,
@@ -433,6 +434,8 @@ impl Access for OssBackend {
                     Some(usize::MAX)
                 },
                 write_with_user_metadata: true,
+                write_returns_etag: true,
+                write_returns_version: true,
 
                 delete: true,
                 copy: true,
@@ -550,6 +553,12 @@ impl Access for OssBackend {
                 self.core
                     .oss_put_object_request(path, None, v, Buffer::new(), true)?
             }
+            _ => {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "presign operation is not supported",
+                ))
+            }
         };
 
         self.core.sign_query(&mut req, args.expire()).await?;
@@ -565,12 +574,9 @@ impl Access for OssBackend {
     }
 
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
-        let ops = args.into_operation();
-        // Sadly, OSS will not return failed keys, so we will build
-        // a set to calculate the failed keys.
-        let mut keys = HashSet::new();
+        let paths = args.into_delete_paths()?;
 
-        let ops_len = ops.len();
+        let ops_len = paths.len();
         if ops_len > 1000 {
             return Err(Error::new(
                 ErrorKind::Unsupported,
@@ -579,13 +585,9 @@ impl Access for OssBackend {
             .with_context("length", ops_len.to_string()));
         }
 
-        let paths = ops
-            .into_iter()
-            .map(|(p, _)| {
-                keys.insert(p.clone());
-                p
-            })
-            .collect();
+        // Sadly, OSS will not return failed keys, so we will build
+        // a set to calculate the failed keys.
+        let mut keys: HashSet<String> = paths.iter().cloned().collect();
 
         let resp = self.core.oss_delete_objects(paths).await?;
 
@@ -44,7 +44,7 @@ impl OssWriter {
 }
 
 impl oio::MultipartWrite for OssWriter {
-    async fn write_once(&self, size: u64, body: Buffer) -> Result<()> {
+    async fn write_once(&self, size: u64, body: Buffer) -> Result<Metadata> {
         let mut req =
             self.core
                 .oss_put_object_request(&self.path, Some(size), &self.op, body, false)?;
@@ -56,7 +56,7 @@ impl oio::MultipartWrite for OssWriter {
         let status = resp.status();
 
         match status {
-            StatusCode::CREATED | StatusCode::OK => Ok(()),
+            StatusCode::CREATED | StatusCode::OK => parse_write_metadata(resp.headers()),
             _ => Err(parse_error(resp).await?),
         }
     }
@@ -127,7 +127,11 @@ impl oio::MultipartWrite for OssWriter {
         }
     }
 
-    async fn complete_part(&self, upload_id: &str, parts: &[oio::MultipartPart]) -> Result<()> {
+    async fn complete_part(
+        &self,
+        upload_id: &str,
+        parts: &[oio::MultipartPart],
+    ) -> Result<Metadata> {
         let parts = parts
             .iter()
             .map(|p| MultipartUploadPart {
@@ -144,7 +148,17 @@ impl oio::MultipartWrite for OssWriter {
         let status = resp.status();
 
         match status {
-            StatusCode::OK => Ok(()),
+            StatusCode::OK => {
+                let (parts, body) = resp.into_parts();
+                let mut meta = parse_write_metadata(&parts.headers)?;
+                let result: CompleteMultipartUploadResult =
+                    quick_xml::de::from_reader(bytes::Buf::reader(body))
+                        .map_err(new_xml_deserialize_error)?;
+                if !result.etag.is_empty() {
+                    meta.set_etag(&result.etag);
+                }
+                Ok(meta)
+            }
             _ => Err(parse_error(resp).await?),
         }
     }
@@ -185,7 +199,7 @@ impl oio::AppendWrite for OssWriter {
         }
     }
 
-    async fn append(&self, offset: u64, size: u64, body: Buffer) -> Result<()> {
+    async fn append(&self, offset: u64, size: u64, body: Buffer) -> Result<Metadata> {
         let mut req = self
             .core
             .oss_append_object_request(&self.path, offset, size, &self.op, body)?;
@@ -197,8 +211,17 @@ impl oio::AppendWrite for OssWriter {
         let status = resp.status();
 
         match status {
-            StatusCode::OK => Ok(()),
+            StatusCode::OK => parse_write_metadata(resp.headers()),
             _ => Err(parse_error(resp).await?),
         }
     }
 }
+
+/// Parse the metadata of written object from response headers.
+fn parse_write_metadata(headers: &http::HeaderMap) -> Result<Metadata> {
+    let mut meta = parse_into_write_metadata(headers)?;
+    if let Some(v) = parse_header_to_str(headers, "x-oss-version-id")? {
+        meta.set_version(v);
+    }
+    Ok(meta)
+}
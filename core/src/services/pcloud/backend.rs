@@ -361,6 +361,10 @@ impl Access for PcloudBackend {
                 if result == 2009 || result == 2010 || result == 2055 || result == 2002 {
                     return Err(Error::new(ErrorKind::NotFound, format!("{resp:?}")));
                 }
+                // pCloud returns 2004 if the destination already exists.
+                if result == 2004 {
+                    return Err(Error::new(ErrorKind::AlreadyExists, format!("{resp:?}")));
+                }
                 if result != 0 {
                     return Err(Error::new(ErrorKind::Unexpected, format!("{resp:?}")));
                 }
@@ -391,6 +395,10 @@ impl Access for PcloudBackend {
                 if result == 2009 || result == 2010 || result == 2055 || result == 2002 {
                     return Err(Error::new(ErrorKind::NotFound, format!("{resp:?}")));
                 }
+                // pCloud returns 2004 if the destination already exists.
+                if result == 2004 {
+                    return Err(Error::new(ErrorKind::AlreadyExists, format!("{resp:?}")));
+                }
                 if result != 0 {
                     return Err(Error::new(ErrorKind::Unexpected, format!("{resp:?}")));
                 }
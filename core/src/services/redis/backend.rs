@@ -18,6 +18,7 @@
 use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::time::Duration;
 
 use http::Uri;
@@ -25,14 +26,18 @@ use redis::aio::ConnectionManager;
 use redis::cluster::ClusterClient;
 use redis::cluster::ClusterClientBuilder;
 use redis::cluster_async::ClusterConnection;
+use redis::sentinel::Sentinel;
+use redis::sentinel::SentinelNodeConnectionInfo;
 use redis::AsyncCommands;
 use redis::Client;
 use redis::ConnectionAddr;
 use redis::ConnectionInfo;
 use redis::RedisConnectionInfo;
 use redis::RedisError;
+use redis::TlsMode;
 use serde::Deserialize;
 use serde::Serialize;
+use tokio::sync::Mutex;
 use tokio::sync::OnceCell;
 
 use crate::raw::adapters::kv;
@@ -55,6 +60,14 @@ pub struct RedisConfig {
     ///
     /// default is None
     pub cluster_endpoints: Option<String>,
+    /// network address of the Redis sentinel service. Can be "tcp://127.0.0.1:26379,tcp://127.0.0.1:26380", e.g.
+    ///
+    /// default is None
+    pub sentinel_endpoints: Option<String>,
+    /// the master name monitored by sentinels, required if `sentinel_endpoints` is set.
+    ///
+    /// default is None
+    pub sentinel_master: Option<String>,
     /// the username to connect redis service.
     ///
     /// default is None
@@ -87,6 +100,12 @@ impl Debug for RedisConfig {
         if let Some(cluster_endpoints) = self.cluster_endpoints.clone() {
             d.field("cluster_endpoints", &cluster_endpoints);
         }
+        if let Some(sentinel_endpoints) = self.sentinel_endpoints.clone() {
+            d.field("sentinel_endpoints", &sentinel_endpoints);
+        }
+        if let Some(sentinel_master) = self.sentinel_master.clone() {
+            d.field("sentinel_master", &sentinel_master);
+        }
         if let Some(username) = self.username.clone() {
             d.field("username", &username);
         }
@@ -150,6 +169,34 @@ impl RedisBuilder {
         self
     }
 
+    /// set the network address of redis sentinel service.
+    /// This parameter is mutually exclusive with the endpoint and cluster_endpoints parameters,
+    /// and must be used together with `sentinel_master`.
+    ///
+    /// The master is resolved through sentinels while connecting, and resolved
+    /// again after connection errors or `READONLY` replies caused by failover.
+    /// If any of the sentinel endpoints uses "rediss", the connection to master
+    /// will use TLS too.
+    ///
+    /// currently supported schemes:
+    /// - no scheme: will be seen as "tcp"
+    /// - "tcp" or "redis": unsecured redis connections
+    /// - "rediss": secured redis connections
+    pub fn sentinel_endpoints(mut self, sentinel_endpoints: &str) -> Self {
+        if !sentinel_endpoints.is_empty() {
+            self.config.sentinel_endpoints = Some(sentinel_endpoints.to_owned());
+        }
+        self
+    }
+
+    /// set the master name monitored by redis sentinels.
+    pub fn sentinel_master(mut self, sentinel_master: &str) -> Self {
+        if !sentinel_master.is_empty() {
+            self.config.sentinel_master = Some(sentinel_master.to_owned());
+        }
+        self
+    }
+
     /// set the username for redis
     ///
     /// default: no username
@@ -210,7 +257,57 @@ impl Builder for RedisBuilder {
                 .as_str(),
         );
 
-        if let Some(endpoints) = self.config.cluster_endpoints.clone() {
+        if let Some(endpoints) = self.config.sentinel_endpoints.clone() {
+            let Some(master) = self.config.sentinel_master.clone() else {
+                return Err(Error::new(
+                    ErrorKind::ConfigInvalid,
+                    "sentinel_master is required when sentinel_endpoints is set",
+                )
+                .with_context("service", Scheme::Redis)
+                .with_context("sentinel_endpoints", endpoints));
+            };
+
+            let mut sentinel_endpoints: Vec<ConnectionInfo> = Vec::default();
+            for endpoint in endpoints.split(',') {
+                let mut info = self.get_connection_info(endpoint.to_string())?;
+                // Sentinels don't share db and credentials with the master.
+                info.redis = RedisConnectionInfo::default();
+                sentinel_endpoints.push(info);
+            }
+            let tls_mode = sentinel_endpoints
+                .iter()
+                .any(|info| matches!(info.addr, ConnectionAddr::TcpTls { .. }))
+                .then_some(TlsMode::Secure);
+
+            let sentinel = Sentinel::build(sentinel_endpoints).map_err(|e| {
+                Error::new(ErrorKind::ConfigInvalid, "invalid sentinel endpoints")
+                    .with_context("service", Scheme::Redis)
+                    .with_context("sentinel_endpoints", &endpoints)
+                    .set_source(e)
+            })?;
+
+            Ok(RedisBackend::new(Adapter {
+                addr: format!("{master}@{endpoints}"),
+                client: None,
+                cluster_client: None,
+                sentinel: Some(RedisSentinel {
+                    sentinel: Arc::new(Mutex::new(sentinel)),
+                    master,
+                    node_connection_info: SentinelNodeConnectionInfo {
+                        tls_mode,
+                        redis_connection_info: Some(RedisConnectionInfo {
+                            db: self.config.db,
+                            username: self.config.username.clone(),
+                            password: self.config.password.clone(),
+                        }),
+                    },
+                    conn: Arc::new(Mutex::new(None)),
+                }),
+                conn: OnceCell::new(),
+                default_ttl: self.config.default_ttl,
+            })
+            .with_root(&root))
+        } else if let Some(endpoints) = self.config.cluster_endpoints.clone() {
             let mut cluster_endpoints: Vec<ConnectionInfo> = Vec::default();
             for endpoint in endpoints.split(',') {
                 cluster_endpoints.push(self.get_connection_info(endpoint.to_string())?);
@@ -230,6 +327,7 @@ impl Builder for RedisBuilder {
                 addr: endpoints,
                 client: None,
                 cluster_client: Some(client),
+                sentinel: None,
                 conn,
                 default_ttl: self.config.default_ttl,
             })
@@ -255,6 +353,7 @@ impl Builder for RedisBuilder {
                 addr: endpoint,
                 client: Some(client),
                 cluster_client: None,
+                sentinel: None,
                 conn,
                 default_ttl: self.config.default_ttl,
             })
@@ -329,11 +428,56 @@ enum RedisConnection {
     Cluster(ClusterConnection),
 }
 
+/// RedisSentinel resolves the master address through sentinels.
+///
+/// The connection to master is dropped on connection errors and `READONLY`
+/// replies, so that the new master will be resolved after a failover.
+#[derive(Clone)]
+struct RedisSentinel {
+    sentinel: Arc<Mutex<Sentinel>>,
+    master: String,
+    node_connection_info: SentinelNodeConnectionInfo,
+    conn: Arc<Mutex<Option<ConnectionManager>>>,
+}
+
+impl RedisSentinel {
+    async fn conn(&self) -> Result<ConnectionManager> {
+        let mut conn = self.conn.lock().await;
+        if let Some(conn) = conn.as_ref() {
+            return Ok(conn.clone());
+        }
+
+        let client = self
+            .sentinel
+            .lock()
+            .await
+            .async_master_for(&self.master, Some(&self.node_connection_info))
+            .await
+            .map_err(format_redis_error)?;
+        let manager = ConnectionManager::new(client)
+            .await
+            .map_err(format_redis_error)?;
+        *conn = Some(manager.clone());
+        Ok(manager)
+    }
+
+    async fn check_error(&self, err: &RedisError) {
+        let master_changed = err.kind() == redis::ErrorKind::ReadOnly
+            || err.is_io_error()
+            || err.is_connection_dropped()
+            || err.is_connection_refusal();
+        if master_changed {
+            self.conn.lock().await.take();
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Adapter {
     addr: String,
     client: Option<Client>,
     cluster_client: Option<ClusterClient>,
+    sentinel: Option<RedisSentinel>,
     conn: OnceCell<RedisConnection>,
 
     default_ttl: Option<Duration>,
@@ -351,6 +495,10 @@ impl Debug for Adapter {
 
 impl Adapter {
     async fn conn(&self) -> Result<RedisConnection> {
+        if let Some(sentinel) = &self.sentinel {
+            return sentinel.conn().await.map(RedisConnection::Normal);
+        }
+
         Ok(self
             .conn
             .get_or_try_init(|| async {
@@ -371,6 +519,20 @@ impl Adapter {
             .map_err(format_redis_error)?
             .clone())
     }
+
+    /// Convert the result of a redis command, the connection to master will
+    /// be re-resolved via sentinels if it's gone.
+    async fn handle<T>(&self, result: redis::RedisResult<T>) -> Result<T> {
+        match result {
+            Ok(v) => Ok(v),
+            Err(err) => {
+                if let Some(sentinel) = &self.sentinel {
+                    sentinel.check_error(&err).await;
+                }
+                Err(format_redis_error(err))
+            }
+        }
+    }
 }
 
 impl kv::Adapter for Adapter {
@@ -389,63 +551,56 @@ impl kv::Adapter for Adapter {
 
     async fn get(&self, key: &str) -> Result<Option<Buffer>> {
         let conn = self.conn().await?;
-        let result: Option<bytes::Bytes> = match conn {
-            RedisConnection::Normal(mut conn) => conn.get(key).await.map_err(format_redis_error),
-            RedisConnection::Cluster(mut conn) => conn.get(key).await.map_err(format_redis_error),
-        }?;
+        let result: Option<bytes::Bytes> = self
+            .handle(match conn {
+                RedisConnection::Normal(mut conn) => conn.get(key).await,
+                RedisConnection::Cluster(mut conn) => conn.get(key).await,
+            })
+            .await?;
         Ok(result.map(Buffer::from))
     }
 
+    async fn exists(&self, key: &str) -> Result<bool> {
+        let conn = self.conn().await?;
+        self.handle(match conn {
+            RedisConnection::Normal(mut conn) => conn.exists(key).await,
+            RedisConnection::Cluster(mut conn) => conn.exists(key).await,
+        })
+        .await
+    }
+
     async fn set(&self, key: &str, value: Buffer) -> Result<()> {
         let conn = self.conn().await?;
         let value = value.to_vec();
-        match self.default_ttl {
+        let result = match self.default_ttl {
             Some(ttl) => match conn {
-                RedisConnection::Normal(mut conn) => conn
-                    .set_ex(key, value, ttl.as_secs())
-                    .await
-                    .map_err(format_redis_error)?,
-                RedisConnection::Cluster(mut conn) => conn
-                    .set_ex(key, value, ttl.as_secs())
-                    .await
-                    .map_err(format_redis_error)?,
+                RedisConnection::Normal(mut conn) => conn.set_ex(key, value, ttl.as_secs()).await,
+                RedisConnection::Cluster(mut conn) => conn.set_ex(key, value, ttl.as_secs()).await,
             },
             None => match conn {
-                RedisConnection::Normal(mut conn) => {
-                    conn.set(key, value).await.map_err(format_redis_error)?
-                }
-                RedisConnection::Cluster(mut conn) => {
-                    conn.set(key, value).await.map_err(format_redis_error)?
-                }
+                RedisConnection::Normal(mut conn) => conn.set(key, value).await,
+                RedisConnection::Cluster(mut conn) => conn.set(key, value).await,
             },
-        }
-        Ok(())
+        };
+        self.handle(result).await
     }
 
     async fn delete(&self, key: &str) -> Result<()> {
         let conn = self.conn().await?;
-        match conn {
-            RedisConnection::Normal(mut conn) => {
-                let _: () = conn.del(key).await.map_err(format_redis_error)?;
-            }
-            RedisConnection::Cluster(mut conn) => {
-                let _: () = conn.del(key).await.map_err(format_redis_error)?;
-            }
-        }
-        Ok(())
+        self.handle(match conn {
+            RedisConnection::Normal(mut conn) => conn.del(key).await,
+            RedisConnection::Cluster(mut conn) => conn.del(key).await,
+        })
+        .await
     }
 
     async fn append(&self, key: &str, value: &[u8]) -> Result<()> {
         let conn = self.conn().await?;
-        match conn {
-            RedisConnection::Normal(mut conn) => {
-                conn.append(key, value).await.map_err(format_redis_error)?;
-            }
-            RedisConnection::Cluster(mut conn) => {
-                conn.append(key, value).await.map_err(format_redis_error)?;
-            }
-        }
-        Ok(())
+        self.handle(match conn {
+            RedisConnection::Normal(mut conn) => conn.append(key, value).await,
+            RedisConnection::Cluster(mut conn) => conn.append(key, value).await,
+        })
+        .await
     }
 }
 
@@ -454,3 +609,133 @@ pub fn format_redis_error(e: RedisError) -> Error {
         .set_source(e)
         .set_temporary()
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufRead;
+    use std::io::BufReader;
+    use std::io::Write;
+    use std::net::TcpListener;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::atomic::Ordering;
+
+    use super::*;
+
+    /// Start a fake redis server which replies every command with `handler`.
+    fn fake_redis(handler: impl Fn(&[String]) -> String + Send + Sync + 'static) -> u16 {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handler = Arc::new(handler);
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(stream) = stream else { continue };
+                let handler = handler.clone();
+                std::thread::spawn(move || {
+                    let mut writer = stream.try_clone().unwrap();
+                    let mut reader = BufReader::new(stream);
+                    while let Some(cmd) = read_command(&mut reader) {
+                        if writer.write_all(handler(&cmd).as_bytes()).is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+
+        port
+    }
+
+    fn read_command(reader: &mut impl BufRead) -> Option<Vec<String>> {
+        let mut line = String::new();
+        reader.read_line(&mut line).ok()?;
+        let n: usize = line.trim().strip_prefix('*')?.parse().ok()?;
+
+        let mut cmd = Vec::with_capacity(n);
+        for _ in 0..n {
+            line.clear();
+            reader.read_line(&mut line).ok()?;
+            let len: usize = line.trim().strip_prefix('$')?.parse().ok()?;
+            let mut buf = vec![0; len + 2];
+            reader.read_exact(&mut buf).ok()?;
+            buf.truncate(len);
+            cmd.push(String::from_utf8(buf).ok()?);
+        }
+        Some(cmd)
+    }
+
+    fn bulk_array(values: &[&str]) -> String {
+        let mut s = format!("*{}\r\n", values.len());
+        for v in values {
+            s.push_str(&format!("${}\r\n{v}\r\n", v.len()));
+        }
+        s
+    }
+
+    /// A fake master which replies `READONLY` to writes once it's demoted.
+    fn fake_master(demoted: Arc<AtomicBool>, writes: Arc<std::sync::Mutex<usize>>) -> u16 {
+        fake_redis(move |cmd| match cmd[0].to_uppercase().as_str() {
+            "ROLE" if demoted.load(Ordering::SeqCst) => bulk_array(&["slave"]),
+            "ROLE" => bulk_array(&["master"]),
+            "SET" if demoted.load(Ordering::SeqCst) => {
+                "-READONLY You can't write against a read only replica.\r\n".to_string()
+            }
+            "SET" => {
+                *writes.lock().unwrap() += 1;
+                "+OK\r\n".to_string()
+            }
+            _ => "+OK\r\n".to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_sentinel_failover() {
+        let failed_over = Arc::new(AtomicBool::new(false));
+        let old_writes = Arc::new(std::sync::Mutex::new(0));
+        let new_writes = Arc::new(std::sync::Mutex::new(0));
+        let old_master = fake_master(failed_over.clone(), old_writes.clone());
+        let new_master = fake_master(Arc::new(AtomicBool::new(false)), new_writes.clone());
+
+        let current = failed_over.clone();
+        let sentinel = fake_redis(move |cmd| {
+            let port = if current.load(Ordering::SeqCst) {
+                new_master
+            } else {
+                old_master
+            };
+            match cmd.join(" ").to_uppercase().as_str() {
+                "SENTINEL MASTERS" => format!(
+                    "*1\r\n{}",
+                    bulk_array(&[
+                        "name",
+                        "mymaster",
+                        "ip",
+                        "127.0.0.1",
+                        "port",
+                        &port.to_string(),
+                        "flags",
+                        "master",
+                    ])
+                ),
+                _ => "+OK\r\n".to_string(),
+            }
+        });
+
+        let builder = RedisBuilder::default()
+            .sentinel_endpoints(&format!("tcp://127.0.0.1:{sentinel}"))
+            .sentinel_master("mymaster");
+        let op = Operator::new(builder).unwrap().finish();
+
+        op.write("test", "hello").await.unwrap();
+        assert_eq!(*old_writes.lock().unwrap(), 1);
+
+        failed_over.store(true, Ordering::SeqCst);
+        let err = op.write("test", "hello").await.unwrap_err();
+        assert!(err.is_temporary(), "READONLY must be retryable");
+
+        // The new master must be resolved via sentinels on the next call.
+        op.write("test", "hello").await.unwrap();
+        assert_eq!(*old_writes.lock().unwrap(), 1);
+        assert_eq!(*new_writes.lock().unwrap(), 1);
+    }
+}
@@ -19,6 +19,9 @@ use std::fmt::Debug;
 use std::fmt::Formatter;
 use std::sync::Arc;
 
+use rocksdb::ColumnFamily;
+use rocksdb::Options;
+use rocksdb::WriteBatch;
 use rocksdb::DB;
 use serde::Deserialize;
 use serde::Serialize;
@@ -40,6 +43,12 @@ pub struct RocksdbConfig {
     ///
     /// default is "/"
     pub root: Option<String>,
+    /// The column family to use, default column family will be used if not set.
+    pub column_family: Option<String>,
+    /// Create the column family if it's missing.
+    ///
+    /// default is false
+    pub create_if_missing: bool,
 }
 
 impl Configurator for RocksdbConfig {
@@ -71,6 +80,25 @@ impl RocksdbBuilder {
         }
         self
     }
+
+    /// Set the column family to use.
+    ///
+    /// The column family must exist unless `create_if_missing` is enabled,
+    /// otherwise building will fail.
+    ///
+    /// default: the default column family
+    pub fn column_family(mut self, cf: &str) -> Self {
+        if !cf.is_empty() {
+            self.config.column_family = Some(cf.to_owned());
+        }
+        self
+    }
+
+    /// Create the column family if it's missing.
+    pub fn create_if_missing(mut self) -> Self {
+        self.config.create_if_missing = true;
+        self
+    }
 }
 
 impl Builder for RocksdbBuilder {
@@ -82,14 +110,42 @@ impl Builder for RocksdbBuilder {
             Error::new(ErrorKind::ConfigInvalid, "datadir is required but not set")
                 .with_context("service", Scheme::Rocksdb)
         })?;
-        let db = DB::open_default(&path).map_err(|e| {
+        let db = match &self.config.column_family {
+            None => DB::open_default(&path),
+            Some(cf) => {
+                // RocksDB requires all existing column families to be opened,
+                // the list will fail if the db doesn't exist yet.
+                let mut cfs = DB::list_cf(&Options::default(), &path).unwrap_or_default();
+                if !cfs.contains(cf) {
+                    if !self.config.create_if_missing {
+                        return Err(Error::new(
+                            ErrorKind::ConfigInvalid,
+                            "column family doesn't exist and create_if_missing is not enabled",
+                        )
+                        .with_context("service", Scheme::Rocksdb)
+                        .with_context("datadir", path)
+                        .with_context("column_family", cf));
+                    }
+                    cfs.push(cf.clone());
+                }
+
+                let mut opts = Options::default();
+                opts.create_if_missing(true);
+                opts.create_missing_column_families(self.config.create_if_missing);
+                DB::open_cf(&opts, &path, cfs)
+            }
+        }
+        .map_err(|e| {
             Error::new(ErrorKind::ConfigInvalid, "open default transaction db")
                 .with_context("service", Scheme::Rocksdb)
-                .with_context("datadir", path)
+                .with_context("datadir", &path)
                 .set_source(e)
         })?;
 
-        Ok(RocksdbBackend::new(Adapter { db: Arc::new(db) }))
+        Ok(RocksdbBackend::new(Adapter {
+            db: Arc::new(db),
+            cf: self.config.column_family,
+        }))
     }
 }
 
@@ -99,12 +155,26 @@ pub type RocksdbBackend = kv::Backend<Adapter>;
 #[derive(Clone)]
 pub struct Adapter {
     db: Arc<DB>,
+    cf: Option<String>,
+}
+
+impl Adapter {
+    fn cf(&self) -> Result<Option<&ColumnFamily>> {
+        let Some(name) = &self.cf else {
+            return Ok(None);
+        };
+        self.db.cf_handle(name).map(Some).ok_or_else(|| {
+            Error::new(ErrorKind::Unexpected, "column family not found")
+                .with_context("column_family", name)
+        })
+    }
 }
 
 impl Debug for Adapter {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let mut ds = f.debug_struct("Adapter");
         ds.field("path", &self.db.path());
+        ds.field("column_family", &self.cf);
         ds.finish()
     }
 }
@@ -118,6 +188,8 @@ impl kv::Adapter for Adapter {
                 read: true,
                 write: true,
                 list: true,
+                batch: true,
+                batch_delete: true,
                 blocking: true,
                 ..Default::default()
             },
@@ -134,10 +206,44 @@ impl kv::Adapter for Adapter {
     }
 
     fn blocking_get(&self, path: &str) -> Result<Option<Buffer>> {
-        let result = self.db.get(path).map_err(parse_rocksdb_error)?;
+        let result = match self.cf()? {
+            Some(cf) => self.db.get_cf(cf, path),
+            None => self.db.get(path),
+        }
+        .map_err(parse_rocksdb_error)?;
         Ok(result.map(Buffer::from))
     }
 
+    async fn exists(&self, path: &str) -> Result<bool> {
+        let cloned_self = self.clone();
+        let cloned_path = path.to_string();
+
+        task::spawn_blocking(move || cloned_self.blocking_exists(cloned_path.as_str()))
+            .await
+            .map_err(new_task_join_error)?
+    }
+
+    fn blocking_exists(&self, path: &str) -> Result<bool> {
+        let cf = self.cf()?;
+        // `key_may_exist` is answered by memtables and bloom filters, it never
+        // returns false for existing keys but may have false positives.
+        let may_exist = match cf {
+            Some(cf) => self.db.key_may_exist_cf(cf, path),
+            None => self.db.key_may_exist(path),
+        };
+        if !may_exist {
+            return Ok(false);
+        }
+
+        // Pinned get avoids copying the value out of the block cache.
+        let result = match cf {
+            Some(cf) => self.db.get_pinned_cf(cf, path),
+            None => self.db.get_pinned(path),
+        }
+        .map_err(parse_rocksdb_error)?;
+        Ok(result.is_some())
+    }
+
     async fn set(&self, path: &str, value: Buffer) -> Result<()> {
         let cloned_self = self.clone();
         let cloned_path = path.to_string();
@@ -148,9 +254,11 @@ impl kv::Adapter for Adapter {
     }
 
     fn blocking_set(&self, path: &str, value: Buffer) -> Result<()> {
-        self.db
-            .put(path, value.to_vec())
-            .map_err(parse_rocksdb_error)
+        match self.cf()? {
+            Some(cf) => self.db.put_cf(cf, path, value.to_vec()),
+            None => self.db.put(path, value.to_vec()),
+        }
+        .map_err(parse_rocksdb_error)
     }
 
     async fn delete(&self, path: &str) -> Result<()> {
@@ -163,7 +271,30 @@ impl kv::Adapter for Adapter {
     }
 
     fn blocking_delete(&self, path: &str) -> Result<()> {
-        self.db.delete(path).map_err(parse_rocksdb_error)
+        match self.cf()? {
+            Some(cf) => self.db.delete_cf(cf, path),
+            None => self.db.delete(path),
+        }
+        .map_err(parse_rocksdb_error)
+    }
+
+    async fn batch_delete(&self, paths: &[String]) -> Result<()> {
+        let cloned_self = self.clone();
+        let cloned_paths = paths.to_vec();
+
+        task::spawn_blocking(move || {
+            let mut batch = WriteBatch::default();
+            let cf = cloned_self.cf()?;
+            for path in cloned_paths {
+                match cf {
+                    Some(cf) => batch.delete_cf(cf, path),
+                    None => batch.delete(path),
+                }
+            }
+            cloned_self.db.write(batch).map_err(parse_rocksdb_error)
+        })
+        .await
+        .map_err(new_task_join_error)?
     }
 
     async fn scan(&self, path: &str) -> Result<Vec<String>> {
@@ -177,15 +308,21 @@ impl kv::Adapter for Adapter {
 
     /// TODO: we only need key here.
     fn blocking_scan(&self, path: &str) -> Result<Vec<String>> {
-        let it = self.db.prefix_iterator(path).map(|r| r.map(|(k, _)| k));
+        let it = match self.cf()? {
+            Some(cf) => self.db.prefix_iterator_cf(cf, path),
+            None => self.db.prefix_iterator(path),
+        }
+        .map(|r| r.map(|(k, _)| k));
         let mut res = Vec::default();
 
         for key in it {
             let key = key.map_err(parse_rocksdb_error)?;
             let key = String::from_utf8_lossy(&key);
-            // FIXME: it's must a bug that rocksdb returns key that not start with path.
+            // Without a prefix extractor, the iterator only seeks to path and
+            // keeps going till the end. Keys are sorted, so we can stop at the
+            // first key that doesn't start with path.
             if !key.starts_with(path) {
-                continue;
+                break;
             }
             // List should skip the path itself.
             if key == path {
@@ -204,6 +204,9 @@ pub struct S3Config {
     ///
     /// For example, R2 doesn't support stat with `response_content_type` query.
     pub disable_stat_with_override: bool,
+    /// Set to true if versioning is enabled on the bucket, so that the
+    /// version id of written objects will be returned.
+    pub enable_versioning: bool,
     /// Checksum Algorithm to use when sending checksums in HTTP headers.
     /// This is necessary when writing to AWS S3 Buckets with Object Lock enabled for example.
     ///
@@ -589,6 +592,16 @@ impl S3Builder {
         self
     }
 
+    /// Set bucket versioning status for this backend.
+    ///
+    /// S3 only returns the version id of written objects if versioning is
+    /// enabled on the bucket, `write_returns_version` will be advertised
+    /// only if this is set.
+    pub fn enable_versioning(mut self, enabled: bool) -> Self {
+        self.config.enable_versioning = enabled;
+        self
+    }
+
     /// Adding a customized credential load for service.
     ///
     /// If customized_credential_load has been set, we will ignore all other
@@ -685,6 +698,9 @@ impl S3Builder {
     ///
     /// Available options:
     /// - "crc32c"
+    ///
+    /// Writes with `content_sha256` will be rejected once it's set, since S3
+    /// only accepts one checksum algorithm per request.
     pub fn checksum_algorithm(mut self, checksum_algorithm: &str) -> Self {
         self.config.checksum_algorithm = Some(checksum_algorithm.to_string());
 
@@ -1002,6 +1018,7 @@ impl Builder for S3Builder {
                 default_storage_class,
                 allow_anonymous: self.config.allow_anonymous,
                 disable_stat_with_override: self.config.disable_stat_with_override,
+                enable_versioning: self.config.enable_versioning,
                 signer,
                 loader,
                 credential_loaded: AtomicBool::new(false),
@@ -1036,6 +1053,8 @@ impl Access for S3Backend {
                 stat: true,
                 stat_with_if_match: true,
                 stat_with_if_none_match: true,
+                stat_with_if_modified_since: true,
+                stat_with_if_unmodified_since: true,
                 stat_with_override_cache_control: !self.core.disable_stat_with_override,
                 stat_with_override_content_disposition: !self.core.disable_stat_with_override,
                 stat_with_override_content_type: !self.core.disable_stat_with_override,
@@ -1053,6 +1072,11 @@ impl Access for S3Backend {
                 write_can_multi: true,
                 write_with_cache_control: true,
                 write_with_content_type: true,
+                write_with_content_md5: true,
+                write_with_content_sha256: true,
+                write_returns_etag: true,
+                // Version id is only returned by buckets with versioning enabled.
+                write_returns_version: self.core.enable_versioning,
                 // The min multipart size of S3 is 5 MiB.
                 //
                 // ref: <https://docs.aws.amazon.com/AmazonS3/latest/userguide/qfacts.html>
@@ -1068,16 +1092,24 @@ impl Access for S3Backend {
 
                 delete: true,
                 copy: true,
+                copy_with_metadata_directive: true,
+                copy_with_content_type: true,
+                copy_with_cache_control: true,
 
                 list: true,
                 list_with_limit: true,
                 list_with_start_after: true,
                 list_with_recursive: true,
+                list_ordered: true,
 
                 presign: true,
                 presign_stat: true,
                 presign_read: true,
                 presign_write: true,
+                presign_delete: true,
+                presign_upload_part: true,
+
+                multipart: true,
 
                 batch: true,
                 batch_max_operations: Some(self.core.batch_max_operations),
@@ -1114,7 +1146,8 @@ impl Access for S3Backend {
         let status = resp.status();
         match status {
             StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
-                Ok((RpRead::default(), resp.into_body()))
+                let etag = parse_etag(resp.headers())?.map(|v| v.to_string());
+                Ok((RpRead::new().with_etag(etag), resp.into_body()))
             }
             _ => {
                 let (part, mut body) = resp.into_parts();
@@ -1125,6 +1158,15 @@ impl Access for S3Backend {
     }
 
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+        // S3 only accepts one checksum algorithm per request, the content
+        // sha256 can't be sent along with the one of `checksum_algorithm`.
+        if args.content_sha256().is_some() && self.core.checksum_algorithm.is_some() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "content_sha256 can't be used with checksum_algorithm",
+            ));
+        }
+
         let concurrent = args.concurrent();
         let executor = args.executor().cloned();
         let writer = S3Writer::new(self.core.clone(), path, args);
@@ -1165,8 +1207,8 @@ impl Access for S3Backend {
         Ok((RpList::default(), oio::PageLister::new(l)))
     }
 
-    async fn copy(&self, from: &str, to: &str, _args: OpCopy) -> Result<RpCopy> {
-        let resp = self.core.s3_copy_object(from, to).await?;
+    async fn copy(&self, from: &str, to: &str, args: OpCopy) -> Result<RpCopy> {
+        let resp = self.core.s3_copy_object(from, to, &args).await?;
 
         let status = resp.status();
 
@@ -1177,22 +1219,30 @@ impl Access for S3Backend {
     }
 
     async fn presign(&self, path: &str, args: OpPresign) -> Result<RpPresign> {
-        let (expire, op) = args.into_parts();
-
         // We will not send this request out, just for signing.
-        let mut req = match op {
-            PresignOperation::Stat(v) => self.core.s3_head_object_request(path, v)?,
+        let mut req = match args.operation() {
+            PresignOperation::Stat(v) => self.core.s3_head_object_request(path, v.clone())?,
             PresignOperation::Read(v) => {
                 self.core
-                    .s3_get_object_request(path, BytesRange::default(), &v)?
+                    .s3_get_object_request(path, BytesRange::default(), v)?
             }
-            PresignOperation::Write(_) => {
+            PresignOperation::Write(v) => {
                 self.core
-                    .s3_put_object_request(path, None, &OpWrite::default(), Buffer::new())?
+                    .s3_put_object_request(path, None, v, Buffer::new())?
             }
+            PresignOperation::Delete(v) => self.core.s3_delete_object_request(path, v)?,
+            PresignOperation::UploadPart(v) => self.core.s3_upload_part_request(
+                path,
+                v.upload_id(),
+                v.part_number(),
+                None,
+                Buffer::new(),
+                None,
+            )?,
         };
+        req.headers_mut().extend(args.signed_headers().clone());
 
-        self.core.sign_query(&mut req, expire).await?;
+        self.core.sign_query(&mut req, args.expire()).await?;
 
         // We don't need this request anymore, consume it directly.
         let (parts, _) = req.into_parts();
@@ -1204,18 +1254,61 @@ impl Access for S3Backend {
         )))
     }
 
+    async fn initiate_multipart(&self, path: &str, args: OpWrite) -> Result<RpInitiateMultipart> {
+        // Parts uploaded via presigned requests can't carry the checksum
+        // required by `checksum_algorithm`.
+        if self.core.checksum_algorithm.is_some() {
+            return Err(Error::new(
+                ErrorKind::Unsupported,
+                "presigned multipart upload doesn't support checksum_algorithm",
+            ));
+        }
+
+        let w = S3Writer::new(self.core.clone(), path, args);
+        let upload_id = oio::MultipartWrite::initiate_part(&w).await?;
+        Ok(RpInitiateMultipart::new(&upload_id))
+    }
+
+    async fn complete_multipart(
+        &self,
+        path: &str,
+        args: OpCompleteMultipart,
+    ) -> Result<RpCompleteMultipart> {
+        let parts: Vec<_> = args
+            .parts()
+            .iter()
+            .map(|p| oio::MultipartPart {
+                part_number: p.part_number(),
+                etag: p.etag().to_string(),
+                checksum: None,
+            })
+            .collect();
+
+        let w = S3Writer::new(self.core.clone(), path, OpWrite::default());
+        let meta = oio::MultipartWrite::complete_part(&w, args.upload_id(), &parts).await?;
+        Ok(RpCompleteMultipart::new(meta))
+    }
+
+    async fn abort_multipart(
+        &self,
+        path: &str,
+        args: OpAbortMultipart,
+    ) -> Result<RpAbortMultipart> {
+        let w = S3Writer::new(self.core.clone(), path, OpWrite::default());
+        oio::MultipartWrite::abort_part(&w, args.upload_id()).await?;
+        Ok(RpAbortMultipart::new())
+    }
+
     async fn batch(&self, args: OpBatch) -> Result<RpBatch> {
-        let ops = args.into_operation();
-        if ops.len() > 1000 {
+        let paths = args.into_delete_paths()?;
+        if paths.len() > 1000 {
             return Err(Error::new(
                 ErrorKind::Unsupported,
                 "s3 services only allow delete up to 1000 keys at once",
             )
-            .with_context("length", ops.len().to_string()));
+            .with_context("length", paths.len().to_string()));
         }
 
-        let paths = ops.into_iter().map(|(p, _)| p).collect();
-
         let resp = self.core.s3_delete_objects(paths).await?;
 
         let status = resp.status();
@@ -1357,4 +1450,53 @@ mod tests {
             assert_eq!(region.as_deref(), expected, "{}", name);
         }
     }
+
+    #[tokio::test]
+    async fn test_batch_only_deletes() {
+        let server = MockServer::start(|_| {
+            MockResponse::new(StatusCode::OK)
+                .with_header("content-type", "application/xml")
+                .with_body("<DeleteResult><Deleted><Key>a</Key></Deleted></DeleteResult>")
+        });
+        let backend = S3Builder::default()
+            .bucket("test")
+            .region("us-east-1")
+            .endpoint(server.endpoint())
+            .access_key_id("access_key_id")
+            .secret_access_key("secret_access_key")
+            .disable_config_load()
+            .build()
+            .expect("build s3 backend must succeed");
+
+        let ops = vec![
+            ("a".to_string(), OpDelete::default().into()),
+            (
+                "b".to_string(),
+                (OpWrite::default(), Buffer::from("hello")).into(),
+            ),
+        ];
+        let err = match backend.batch(OpBatch::new(ops)).await {
+            Ok(_) => panic!("batch write must be rejected"),
+            Err(err) => err,
+        };
+
+        assert_eq!(err.kind(), ErrorKind::Unsupported);
+        assert!(server.requests().is_empty());
+
+        let rp = backend
+            .batch(OpBatch::new(vec![(
+                "a".to_string(),
+                OpDelete::default().into(),
+            )]))
+            .await
+            .expect("batch delete must succeed");
+        assert_eq!(rp.results().len(), 1);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, http::Method::POST);
+        assert_eq!(requests[0].uri, "/test/?delete");
+        assert!(requests[0].header("content-md5").is_some());
+        assert!(String::from_utf8_lossy(&requests[0].body).contains("<Key>a</Key>"));
+    }
 }
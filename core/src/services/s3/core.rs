@@ -34,7 +34,9 @@ use http::header::CONTENT_LENGTH;
 use http::header::CONTENT_TYPE;
 use http::header::HOST;
 use http::header::IF_MATCH;
+use http::header::IF_MODIFIED_SINCE;
 use http::header::IF_NONE_MATCH;
+use http::header::IF_UNMODIFIED_SINCE;
 use http::HeaderValue;
 use http::Request;
 use http::Response;
@@ -49,6 +51,7 @@ use crate::*;
 
 mod constants {
     pub const X_AMZ_COPY_SOURCE: &str = "x-amz-copy-source";
+    pub const X_AMZ_METADATA_DIRECTIVE: &str = "x-amz-metadata-directive";
 
     pub const X_AMZ_SERVER_SIDE_ENCRYPTION: &str = "x-amz-server-side-encryption";
     pub const X_AMZ_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM: &str =
@@ -60,6 +63,7 @@ mod constants {
     pub const X_AMZ_SERVER_SIDE_ENCRYPTION_AWS_KMS_KEY_ID: &str =
         "x-amz-server-side-encryption-aws-kms-key-id";
     pub const X_AMZ_STORAGE_CLASS: &str = "x-amz-storage-class";
+    pub const X_AMZ_CHECKSUM_SHA256: &str = "x-amz-checksum-sha256";
 
     pub const X_AMZ_COPY_SOURCE_SERVER_SIDE_ENCRYPTION_CUSTOMER_ALGORITHM: &str =
         "x-amz-copy-source-server-side-encryption-customer-algorithm";
@@ -87,6 +91,7 @@ pub struct S3Core {
     pub default_storage_class: Option<HeaderValue>,
     pub allow_anonymous: bool,
     pub disable_stat_with_override: bool,
+    pub enable_versioning: bool,
 
     pub signer: AwsV4Signer,
     pub loader: Box<dyn AwsCredentialLoad>,
@@ -337,6 +342,14 @@ impl S3Core {
             req = req.header(IF_MATCH, if_match);
         }
 
+        if let Some(v) = args.if_modified_since() {
+            req = req.header(IF_MODIFIED_SINCE, format_datetime_into_http_date(v));
+        }
+
+        if let Some(v) = args.if_unmodified_since() {
+            req = req.header(IF_UNMODIFIED_SINCE, format_datetime_into_http_date(v));
+        }
+
         let req = req.body(Buffer::new()).map_err(new_request_build_error)?;
 
         Ok(req)
@@ -459,10 +472,17 @@ impl S3Core {
         // Set SSE headers.
         req = self.insert_sse_headers(req, true);
 
+        if let Some(md5) = args.content_md5() {
+            req = req.header("Content-MD5", md5)
+        }
+
         // Calculate Checksum.
         if let Some(checksum) = self.calculate_checksum(&body) {
             // Set Checksum header.
             req = self.insert_checksum_header(req, &checksum);
+        } else if let Some(sha256) = args.content_sha256() {
+            // Conflicts with `checksum_algorithm` are rejected by `write`.
+            req = req.header(constants::X_AMZ_CHECKSUM_SHA256, sha256);
         }
 
         // Set body
@@ -479,7 +499,7 @@ impl S3Core {
         self.send(req).await
     }
 
-    pub async fn s3_delete_object(&self, path: &str, args: &OpDelete) -> Result<Response<Buffer>> {
+    pub fn s3_delete_object_request(&self, path: &str, args: &OpDelete) -> Result<Request<Buffer>> {
         let p = build_abs_path(&self.root, path);
 
         let mut url = format!("{}/{}", self.endpoint, percent_encode_path(&p));
@@ -498,16 +518,25 @@ impl S3Core {
             url.push_str(&format!("?{}", query_args.join("&")));
         }
 
-        let mut req = Request::delete(&url)
+        Request::delete(&url)
             .body(Buffer::new())
-            .map_err(new_request_build_error)?;
+            .map_err(new_request_build_error)
+    }
+
+    pub async fn s3_delete_object(&self, path: &str, args: &OpDelete) -> Result<Response<Buffer>> {
+        let mut req = self.s3_delete_object_request(path, args)?;
 
         self.sign(&mut req).await?;
 
         self.send(req).await
     }
 
-    pub async fn s3_copy_object(&self, from: &str, to: &str) -> Result<Response<Buffer>> {
+    pub async fn s3_copy_object(
+        &self,
+        from: &str,
+        to: &str,
+        args: &OpCopy,
+    ) -> Result<Response<Buffer>> {
         let from = build_abs_path(&self.root, from);
         let to = build_abs_path(&self.root, to);
 
@@ -555,6 +584,18 @@ impl S3Core {
             )
         }
 
+        // Content type and cache control are only applied while replacing
+        // the metadata of source.
+        if args.replace_metadata() {
+            req = req.header(constants::X_AMZ_METADATA_DIRECTIVE, "REPLACE");
+            if let Some(mime) = args.content_type() {
+                req = req.header(CONTENT_TYPE, mime)
+            }
+            if let Some(cache_control) = args.cache_control() {
+                req = req.header(CACHE_CONTROL, cache_control)
+            }
+        }
+
         let mut req = req
             .header(constants::X_AMZ_COPY_SOURCE, &source)
             .body(Buffer::new())
@@ -659,7 +700,7 @@ impl S3Core {
         path: &str,
         upload_id: &str,
         part_number: usize,
-        size: u64,
+        size: Option<u64>,
         body: Buffer,
         checksum: Option<String>,
     ) -> Result<Request<Buffer>> {
@@ -675,7 +716,9 @@ impl S3Core {
 
         let mut req = Request::put(&url);
 
-        req = req.header(CONTENT_LENGTH, size);
+        if let Some(size) = size {
+            req = req.header(CONTENT_LENGTH, size);
+        }
 
         // Set SSE headers.
         req = self.insert_sse_headers(req, true);
@@ -788,6 +831,14 @@ pub struct InitiateMultipartUploadResult {
     pub upload_id: String,
 }
 
+/// Result of CompleteMultipartUpload
+#[derive(Default, Debug, Deserialize)]
+#[serde(default, rename_all = "PascalCase")]
+pub struct CompleteMultipartUploadResult {
+    #[serde(rename = "ETag")]
+    pub etag: String,
+}
+
 /// Request of CompleteMultipartUploadRequest
 #[derive(Default, Debug, Serialize)]
 #[serde(default, rename = "CompleteMultipartUpload", rename_all = "PascalCase")]
@@ -953,6 +1004,25 @@ mod tests {
         )
     }
 
+    /// This example is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_CompleteMultipartUpload.html#API_CompleteMultipartUpload_Examples
+    #[test]
+    fn test_deserialize_complete_multipart_upload_result() {
+        let bs = Bytes::from(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <CompleteMultipartUploadResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+              <Location>http://Example-Bucket.s3.region.amazonaws.com/Example-Object</Location>
+              <Bucket>Example-Bucket</Bucket>
+              <Key>Example-Object</Key>
+              <ETag>"3858f62230ac3c915f300c664312c11f-9"</ETag>
+            </CompleteMultipartUploadResult>"#,
+        );
+
+        let out: CompleteMultipartUploadResult =
+            quick_xml::de::from_reader(bs.reader()).expect("must success");
+
+        assert_eq!(out.etag, "\"3858f62230ac3c915f300c664312c11f-9\"")
+    }
+
     /// This example is from https://docs.aws.amazon.com/AmazonS3/latest/API/API_CompleteMultipartUpload.html#API_CompleteMultipartUpload_Examples
     #[test]
     fn test_serialize_complete_multipart_upload_request() {
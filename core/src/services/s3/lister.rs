@@ -133,6 +133,10 @@ impl oio::PageList for S3Lister {
             ctx.entries.push_back(de);
         }
 
+        // Prefixes and objects are sorted separately, merge them to return
+        // entries in order.
+        ctx.sort_entries();
+
         Ok(())
     }
 }
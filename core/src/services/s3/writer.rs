@@ -47,7 +47,7 @@ impl S3Writer {
 }
 
 impl oio::MultipartWrite for S3Writer {
-    async fn write_once(&self, size: u64, body: Buffer) -> Result<()> {
+    async fn write_once(&self, size: u64, body: Buffer) -> Result<Metadata> {
         let mut req = self
             .core
             .s3_put_object_request(&self.path, Some(size), &self.op, body)?;
@@ -59,7 +59,7 @@ impl oio::MultipartWrite for S3Writer {
         let status = resp.status();
 
         match status {
-            StatusCode::CREATED | StatusCode::OK => Ok(()),
+            StatusCode::CREATED | StatusCode::OK => parse_write_metadata(resp.headers()),
             _ => Err(parse_error(resp)),
         }
     }
@@ -101,7 +101,7 @@ impl oio::MultipartWrite for S3Writer {
             &self.path,
             upload_id,
             part_number,
-            size,
+            Some(size),
             body,
             checksum.clone(),
         )?;
@@ -133,7 +133,11 @@ impl oio::MultipartWrite for S3Writer {
         }
     }
 
-    async fn complete_part(&self, upload_id: &str, parts: &[oio::MultipartPart]) -> Result<()> {
+    async fn complete_part(
+        &self,
+        upload_id: &str,
+        parts: &[oio::MultipartPart],
+    ) -> Result<Metadata> {
         let parts = parts
             .iter()
             .map(|p| match &self.core.checksum_algorithm {
@@ -164,13 +168,19 @@ impl oio::MultipartWrite for S3Writer {
                 // still check if there is any error because S3 might return error for status code 200
                 // https://docs.aws.amazon.com/AmazonS3/latest/API/API_CompleteMultipartUpload.html#API_CompleteMultipartUpload_Example_4
                 let (parts, body) = resp.into_parts();
-                let maybe_error: S3Error =
-                    quick_xml::de::from_reader(body.reader()).map_err(new_xml_deserialize_error)?;
+                let maybe_error: S3Error = quick_xml::de::from_reader(body.clone().reader())
+                    .map_err(new_xml_deserialize_error)?;
                 if !maybe_error.code.is_empty() {
                     return Err(from_s3_error(maybe_error, parts));
                 }
 
-                Ok(())
+                let mut meta = parse_write_metadata(&parts.headers)?;
+                let result: CompleteMultipartUploadResult =
+                    quick_xml::de::from_reader(body.reader()).map_err(new_xml_deserialize_error)?;
+                if !result.etag.is_empty() {
+                    meta.set_etag(&result.etag);
+                }
+                Ok(meta)
             }
             _ => Err(parse_error(resp)),
         }
@@ -188,3 +198,12 @@ impl oio::MultipartWrite for S3Writer {
         }
     }
 }
+
+/// Parse the metadata of written object from response headers.
+fn parse_write_metadata(headers: &http::HeaderMap) -> Result<Metadata> {
+    let mut meta = parse_into_write_metadata(headers)?;
+    if let Some(v) = parse_header_to_str(headers, "x-amz-version-id")? {
+        meta.set_version(v);
+    }
+    Ok(meta)
+}
@@ -290,14 +290,21 @@ impl Access for SeafileBackend {
     }
 
     async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
-        let resp = self.core.download_file(path, args.range()).await?;
+        let range = args.range();
+        let resp = self.core.download_file(path, range).await?;
 
         let status = resp.status();
 
         match status {
-            StatusCode::OK | StatusCode::PARTIAL_CONTENT => {
-                Ok((RpRead::default(), resp.into_body()))
-            }
+            StatusCode::OK if range.is_full() => Ok((RpRead::default(), resp.into_body())),
+            StatusCode::PARTIAL_CONTENT => Ok((RpRead::default(), resp.into_body())),
+            // The file server ignored the range, return an error instead of
+            // silently returning the whole file.
+            StatusCode::OK => Err(Error::new(
+                ErrorKind::Unexpected,
+                "seafile file server doesn't support range read",
+            )
+            .with_context("range", range.to_string())),
             _ => {
                 let (part, mut body) = resp.into_parts();
                 let buf = body.to_buffer().await?;
@@ -221,12 +221,13 @@ impl SeafileCore {
     pub async fn download_file(&self, path: &str, range: BytesRange) -> Result<Response<HttpBody>> {
         let download_url = self.get_download_url(path).await?;
 
-        let req = Request::get(download_url);
+        let mut req = Request::get(download_url);
 
-        let req = req
-            .header(header::RANGE, range.to_header())
-            .body(Buffer::new())
-            .map_err(new_request_build_error)?;
+        if !range.is_full() {
+            req = req.header(header::RANGE, range.to_header());
+        }
+
+        let req = req.body(Buffer::new()).map_err(new_request_build_error)?;
 
         self.client.fetch(req).await
     }
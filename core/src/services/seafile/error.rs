@@ -37,6 +37,7 @@ pub async fn parse_error(resp: Response<Buffer>) -> Result<Error> {
     let (kind, _retryable) = match parts.status.as_u16() {
         403 => (ErrorKind::PermissionDenied, false),
         404 => (ErrorKind::NotFound, false),
+        416 => (ErrorKind::RangeNotSatisfied, false),
         520 => (ErrorKind::Unexpected, false),
         _ => (ErrorKind::Unexpected, false),
     };
@@ -71,6 +72,11 @@ mod test {
                 ErrorKind::NotFound,
                 StatusCode::NOT_FOUND,
             ),
+            (
+                "",
+                ErrorKind::RangeNotSatisfied,
+                StatusCode::RANGE_NOT_SATISFIABLE,
+            ),
         ];
 
         for res in err_res {
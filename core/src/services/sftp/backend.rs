@@ -27,6 +27,7 @@ use bb8::RunError;
 use log::debug;
 use openssh::KnownHosts;
 use openssh::SessionBuilder;
+use openssh_sftp_client::metadata::MetaDataBuilder;
 use openssh_sftp_client::Sftp;
 use openssh_sftp_client::SftpOptions;
 use serde::Deserialize;
@@ -40,6 +41,8 @@ use super::error::parse_sftp_error;
 use super::error::parse_ssh_error;
 use super::lister::SftpLister;
 use super::reader::SftpReader;
+use super::utils::mode_to_permissions;
+use super::utils::parse_mode;
 use super::writer::SftpWriter;
 use crate::raw::*;
 use crate::*;
@@ -61,6 +64,12 @@ pub struct SftpConfig {
     pub known_hosts_strategy: Option<String>,
     /// enable_copy of this backend
     pub enable_copy: bool,
+    /// Preserve permissions, owner and mtime of source while copying.
+    pub enable_preserve: bool,
+    /// The mode in octal (like `644`) applied to written files.
+    pub default_file_mode: Option<String>,
+    /// The mode in octal (like `755`) applied to directories after created.
+    pub default_dir_mode: Option<String>,
 }
 
 impl Debug for SftpConfig {
@@ -170,6 +179,39 @@ impl SftpBuilder {
 
         self
     }
+
+    /// set enable_preserve for sftp backend.
+    /// Copy will preserve permissions, owner and mtime of the source file.
+    pub fn enable_preserve(mut self, enable_preserve: bool) -> Self {
+        self.config.enable_preserve = enable_preserve;
+
+        self
+    }
+
+    /// set default file mode in octal (like `644`) for sftp backend.
+    /// The mode will be applied to written files.
+    pub fn default_file_mode(mut self, mode: &str) -> Self {
+        self.config.default_file_mode = if mode.is_empty() {
+            None
+        } else {
+            Some(mode.to_string())
+        };
+
+        self
+    }
+
+    /// set default dir mode in octal (like `755`) for sftp backend.
+    /// The mode will be applied to directories created by `create_dir`, `write`,
+    /// `copy` and `rename`.
+    pub fn default_dir_mode(mut self, mode: &str) -> Self {
+        self.config.default_dir_mode = if mode.is_empty() {
+            None
+        } else {
+            Some(mode.to_string())
+        };
+
+        self
+    }
 }
 
 impl Builder for SftpBuilder {
@@ -211,6 +253,23 @@ impl Builder for SftpBuilder {
             None => KnownHosts::Strict,
         };
 
+        let default_file_mode = match &self.config.default_file_mode {
+            Some(v) => Some(parse_mode(v).ok_or_else(|| {
+                Error::new(ErrorKind::ConfigInvalid, "default_file_mode is invalid")
+                    .with_context("service", Scheme::Sftp)
+                    .with_context("default_file_mode", v)
+            })?),
+            None => None,
+        };
+        let default_dir_mode = match &self.config.default_dir_mode {
+            Some(v) => Some(parse_mode(v).ok_or_else(|| {
+                Error::new(ErrorKind::ConfigInvalid, "default_dir_mode is invalid")
+                    .with_context("service", Scheme::Sftp)
+                    .with_context("default_dir_mode", v)
+            })?),
+            None => None,
+        };
+
         debug!("sftp backend finished: {:?}", &self);
 
         Ok(SftpBackend {
@@ -220,6 +279,9 @@ impl Builder for SftpBuilder {
             key: self.config.key.clone(),
             known_hosts_strategy,
             copyable: self.config.enable_copy,
+            preserve: self.config.enable_preserve,
+            default_file_mode,
+            default_dir_mode,
 
             client: OnceCell::new(),
         })
@@ -230,6 +292,9 @@ impl Builder for SftpBuilder {
 #[derive(Clone)]
 pub struct SftpBackend {
     copyable: bool,
+    preserve: bool,
+    default_file_mode: Option<u32>,
+    default_dir_mode: Option<u32>,
     endpoint: String,
     root: String,
     user: Option<String>,
@@ -389,11 +454,17 @@ impl Access for SftpBackend {
             current = current.join(p);
             let res = fs.create_dir(p).await;
 
-            if let Err(e) = res {
-                // ignore error if dir already exists
-                if !is_sftp_protocol_error(&e) {
-                    return Err(parse_sftp_error(e));
+            match res {
+                Ok(()) => {
+                    if let Some(mode) = self.default_dir_mode {
+                        fs.set_permissions(p, mode_to_permissions(mode))
+                            .await
+                            .map_err(parse_sftp_error)?;
+                    }
                 }
+                // ignore error if dir already exists
+                Err(e) if is_sftp_protocol_error(&e) => {}
+                Err(e) => return Err(parse_sftp_error(e)),
             }
             fs.set_cwd(&current);
         }
@@ -455,7 +526,15 @@ impl Access for SftpBackend {
             option.write(true).truncate(true);
         }
 
-        let file = option.open(path).await.map_err(parse_sftp_error)?;
+        let mut file = option.open(path).await.map_err(parse_sftp_error)?;
+
+        // Apply mode via the opened handle so that the data written later
+        // will not be affected.
+        if let Some(mode) = self.default_file_mode {
+            file.set_permissions(mode_to_permissions(mode))
+                .await
+                .map_err(parse_sftp_error)?;
+        }
 
         Ok((RpWrite::new(), SftpWriter::new(file)))
     }
@@ -524,6 +603,31 @@ impl Access for SftpBackend {
             .await
             .map_err(parse_sftp_error)?;
 
+        if self.preserve {
+            let meta = src_file.metadata().await.map_err(parse_sftp_error)?;
+
+            let mut builder = MetaDataBuilder::new();
+            if let Some(perm) = meta.permissions() {
+                builder.permissions(perm);
+            }
+            if let (Some(uid), Some(gid)) = (meta.uid(), meta.gid()) {
+                builder.id((uid, gid));
+            }
+            if let (Some(accessed), Some(modified)) = (meta.accessed(), meta.modified()) {
+                builder.time(accessed, modified);
+            }
+
+            dst_file
+                .set_metadata(builder.create())
+                .await
+                .map_err(parse_sftp_error)?;
+        } else if let Some(mode) = self.default_file_mode {
+            dst_file
+                .set_permissions(mode_to_permissions(mode))
+                .await
+                .map_err(parse_sftp_error)?;
+        }
+
         Ok(RpCopy::default())
     }
 
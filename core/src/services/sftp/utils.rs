@@ -16,6 +16,7 @@
 // under the License.
 
 use openssh_sftp_client::metadata::MetaData as SftpMeta;
+use openssh_sftp_client::metadata::Permissions;
 
 use crate::EntryMode;
 use crate::Metadata;
@@ -46,6 +47,68 @@ impl From<SftpMeta> for Metadata {
             metadata.set_last_modified(modified.as_system_time().into());
         }
 
+        if let Some(perm) = meta.permissions() {
+            metadata.set_permissions(permissions_to_mode(&perm));
+        }
+
+        if let (Some(uid), Some(gid)) = (meta.uid(), meta.gid()) {
+            metadata.set_owner(uid, gid);
+        }
+
         metadata
     }
 }
+
+/// Convert sftp permissions into POSIX mode bits like `0o644`.
+pub fn permissions_to_mode(perm: &Permissions) -> u32 {
+    [
+        (perm.suid(), 0o4000),
+        (perm.sgid(), 0o2000),
+        (perm.svtx(), 0o1000),
+        (perm.read_by_owner(), 0o400),
+        (perm.write_by_owner(), 0o200),
+        (perm.execute_by_owner(), 0o100),
+        (perm.read_by_group(), 0o40),
+        (perm.write_by_group(), 0o20),
+        (perm.execute_by_group(), 0o10),
+        (perm.read_by_other(), 0o4),
+        (perm.write_by_other(), 0o2),
+        (perm.execute_by_other(), 0o1),
+    ]
+    .iter()
+    .filter(|(set, _)| *set)
+    .fold(0, |mode, (_, bit)| mode | bit)
+}
+
+/// Convert POSIX mode bits into sftp permissions.
+pub fn mode_to_permissions(mode: u32) -> Permissions {
+    Permissions::from((mode & 0o7777) as u16)
+}
+
+/// Parse mode in octal string like `755` or `0o755` into POSIX mode bits.
+pub fn parse_mode(s: &str) -> Option<u32> {
+    let s = s.trim();
+    let s = s.strip_prefix("0o").unwrap_or(s);
+    u32::from_str_radix(s, 8).ok().filter(|v| *v <= 0o7777)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mode() {
+        assert_eq!(parse_mode("755"), Some(0o755));
+        assert_eq!(parse_mode("0644"), Some(0o644));
+        assert_eq!(parse_mode("0o600"), Some(0o600));
+        assert_eq!(parse_mode("888"), None);
+        assert_eq!(parse_mode("17777"), None);
+    }
+
+    #[test]
+    fn test_mode_permissions_round_trip() {
+        for mode in [0o644, 0o755, 0o600, 0o1777] {
+            assert_eq!(permissions_to_mode(&mode_to_permissions(mode)), mode);
+        }
+    }
+}
@@ -186,6 +186,19 @@ impl kv::Adapter for Adapter {
             .map(|v| Buffer::from(v.to_vec())))
     }
 
+    async fn exists(&self, path: &str) -> Result<bool> {
+        let cloned_self = self.clone();
+        let cloned_path = path.to_string();
+
+        task::spawn_blocking(move || cloned_self.blocking_exists(cloned_path.as_str()))
+            .await
+            .map_err(new_task_join_error)?
+    }
+
+    fn blocking_exists(&self, path: &str) -> Result<bool> {
+        self.tree.contains_key(path).map_err(parse_error)
+    }
+
     async fn set(&self, path: &str, value: Buffer) -> Result<()> {
         let cloned_self = self.clone();
         let cloned_path = path.to_string();
@@ -226,6 +239,8 @@ impl kv::Adapter for Adapter {
             .map_err(new_task_join_error)?
     }
 
+    /// Keys are collected eagerly, so the sled iterator never outlives this
+    /// call and is never held across an await point by the listers.
     fn blocking_scan(&self, path: &str) -> Result<Vec<String>> {
         let it = self.tree.scan_prefix(path).keys();
         let mut res = Vec::default();
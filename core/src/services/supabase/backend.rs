@@ -188,6 +188,8 @@ impl Access for SupabaseBackend {
                 read: true,
 
                 write: true,
+                write_with_content_type: true,
+                write_with_if_not_exists: true,
                 delete: true,
 
                 ..Default::default()
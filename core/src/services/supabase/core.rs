@@ -26,6 +26,8 @@ use http::Response;
 use crate::raw::*;
 use crate::*;
 
+const X_UPSERT: &str = "x-upsert";
+
 pub struct SupabaseCore {
     pub root: String,
     pub bucket: String,
@@ -83,7 +85,7 @@ impl SupabaseCore {
         &self,
         path: &str,
         size: Option<usize>,
-        content_type: Option<&str>,
+        args: &OpWrite,
         body: Buffer,
     ) -> Result<Request<Buffer>> {
         let p = build_abs_path(&self.root, path);
@@ -100,10 +102,21 @@ impl SupabaseCore {
             req = req.header(CONTENT_LENGTH, size)
         }
 
-        if let Some(mime) = content_type {
+        if let Some(mime) = args.content_type() {
             req = req.header(CONTENT_TYPE, mime)
         }
 
+        // Supabase rejects uploads to existing objects unless upsert is
+        // enabled, which is exactly the create-if-absent semantics.
+        req = req.header(
+            X_UPSERT,
+            if args.if_not_exists() {
+                "false"
+            } else {
+                "true"
+            },
+        );
+
         let req = req.body(body).map_err(new_request_build_error)?;
 
         Ok(req)
@@ -262,3 +275,32 @@ impl SupabaseCore {
         self.send(req).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upload_object_request_upsert() {
+        let core = SupabaseCore::new(
+            "/",
+            "bucket",
+            "http://127.0.0.1:54321",
+            None,
+            HttpClient::new().unwrap(),
+        );
+
+        let args = OpWrite::new().with_content_type("text/plain");
+        let req = core
+            .supabase_upload_object_request("a.txt", Some(5), &args, Buffer::from("hello"))
+            .unwrap();
+        assert_eq!(req.headers()[CONTENT_TYPE], "text/plain");
+        assert_eq!(req.headers()[X_UPSERT], "true");
+
+        let args = OpWrite::new().with_if_not_exists(true);
+        let req = core
+            .supabase_upload_object_request("a.txt", Some(5), &args, Buffer::from("hello"))
+            .unwrap();
+        assert_eq!(req.headers()[X_UPSERT], "false");
+    }
+}
@@ -42,21 +42,28 @@ impl SupabaseWriter {
 }
 
 impl oio::OneShotWrite for SupabaseWriter {
-    async fn write_once(&self, bs: Buffer) -> Result<()> {
-        let mut req = self.core.supabase_upload_object_request(
-            &self.path,
-            Some(bs.len()),
-            self.op.content_type(),
-            bs,
-        )?;
+    async fn write_once(&self, bs: Buffer) -> Result<Metadata> {
+        let mut req =
+            self.core
+                .supabase_upload_object_request(&self.path, Some(bs.len()), &self.op, bs)?;
 
         self.core.sign(&mut req)?;
 
         let resp = self.core.send(req).await?;
 
         match resp.status() {
-            StatusCode::OK => Ok(()),
-            _ => Err(parse_error(resp).await?),
+            StatusCode::OK => Ok(Metadata::new(EntryMode::FILE)),
+            _ => {
+                let err = parse_error(resp).await?;
+                if self.op.if_not_exists() && err.kind() == ErrorKind::AlreadyExists {
+                    return Err(
+                        Error::new(ErrorKind::ConditionNotMatch, "object already exists")
+                            .with_context("path", &self.path)
+                            .set_source(err),
+                    );
+                }
+                Err(err)
+            }
         }
     }
 }
@@ -45,6 +45,11 @@ pub struct SwiftConfig {
     pub root: Option<String>,
     /// The token for Swift.
     pub token: Option<String>,
+    /// The threshold of object size to switch to Static Large Object (SLO).
+    ///
+    /// Objects larger than this size will be split into segments of this size
+    /// and uploaded as SLO. Default to 5 GiB, which is the max object size of Swift.
+    pub slo_threshold: Option<u64>,
 }
 
 impl Debug for SwiftConfig {
@@ -54,6 +59,7 @@ impl Debug for SwiftConfig {
         ds.field("root", &self.root);
         ds.field("endpoint", &self.endpoint);
         ds.field("container", &self.container);
+        ds.field("slo_threshold", &self.slo_threshold);
 
         if self.token.is_some() {
             ds.field("token", &"<redacted>");
@@ -138,6 +144,19 @@ impl SwiftBuilder {
         }
         self
     }
+
+    /// Set the threshold of object size to switch to Static Large Object (SLO).
+    ///
+    /// Objects larger than this size will be uploaded as SLO with segments
+    /// stored in the `<container>_segments` container.
+    ///
+    /// Default to 5 GiB.
+    pub fn slo_threshold(mut self, threshold: u64) -> Self {
+        if threshold > 0 {
+            self.config.slo_threshold = Some(threshold);
+        }
+        self
+    }
 }
 
 impl Builder for SwiftBuilder {
@@ -181,6 +200,9 @@ impl Builder for SwiftBuilder {
 
         let token = self.config.token.unwrap_or_default();
 
+        let slo_threshold = self.config.slo_threshold.unwrap_or(DEFAULT_SLO_THRESHOLD);
+        debug!("backend use slo threshold: {}", slo_threshold);
+
         let client = HttpClient::new()?;
 
         Ok(SwiftBackend {
@@ -189,6 +211,7 @@ impl Builder for SwiftBuilder {
                 endpoint,
                 container,
                 token,
+                slo_threshold,
                 client,
             }),
         })
@@ -203,7 +226,7 @@ pub struct SwiftBackend {
 
 impl Access for SwiftBackend {
     type Reader = HttpBody;
-    type Writer = oio::OneShotWriter<SwiftWriter>;
+    type Writer = oio::MultipartWriter<SwiftWriter>;
     type Lister = oio::PageLister<SwiftLister>;
     type BlockingReader = ();
     type BlockingWriter = ();
@@ -220,6 +243,17 @@ impl Access for SwiftBackend {
 
                 write: true,
                 write_can_empty: true,
+                write_can_multi: true,
+                // The min segment size of SLO is 1 MiB by default.
+                //
+                // ref: <https://docs.openstack.org/swift/latest/overview_large_objects.html>
+                write_multi_min_size: Some(1024 * 1024),
+                // The max segment size of SLO is the max object size: 5 GiB.
+                write_multi_max_size: if cfg!(target_pointer_width = "64") {
+                    Some(5 * 1024 * 1024 * 1024)
+                } else {
+                    Some(usize::MAX)
+                },
                 delete: true,
 
                 list: true,
@@ -262,13 +296,25 @@ impl Access for SwiftBackend {
     async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
         let writer = SwiftWriter::new(self.core.clone(), args.clone(), path.to_string());
 
-        let w = oio::OneShotWriter::new(writer);
+        let w = oio::MultipartWriter::new(writer, args.executor().cloned(), args.concurrent());
 
         Ok((RpWrite::default(), w))
     }
 
     async fn delete(&self, path: &str, _args: OpDelete) -> Result<RpDelete> {
-        let resp = self.core.swift_delete(path).await?;
+        // Only manifests of Static Large Objects can be deleted with
+        // `multipart-manifest=delete`, check it before deleting.
+        let resp = self.core.swift_get_metadata(path).await?;
+        let is_slo = match resp.status() {
+            StatusCode::OK => resp
+                .headers()
+                .get("X-Static-Large-Object")
+                .is_some_and(|v| v.as_bytes().eq_ignore_ascii_case(b"true")),
+            StatusCode::NOT_FOUND => return Ok(RpDelete::default()),
+            _ => return Err(parse_error(resp).await?),
+        };
+
+        let resp = self.core.swift_delete(path, is_slo).await?;
 
         let status = resp.status();
 
@@ -303,3 +349,49 @@ impl Access for SwiftBackend {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn new_backend(endpoint: &str) -> SwiftBackend {
+        SwiftBackend {
+            core: Arc::new(SwiftCore {
+                root: "/".to_string(),
+                endpoint: endpoint.to_string(),
+                container: "test".to_string(),
+                token: "token".to_string(),
+                slo_threshold: DEFAULT_SLO_THRESHOLD,
+                client: HttpClient::new().unwrap(),
+            }),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_delete_manifest_only_for_slo() {
+        let server = MockServer::start(|req| match (req.method.as_str(), req.uri.as_str()) {
+            ("HEAD", "/test/slo") => {
+                MockResponse::new(StatusCode::OK).with_header("X-Static-Large-Object", "True")
+            }
+            ("HEAD", "/test/missing") => MockResponse::new(StatusCode::NOT_FOUND),
+            ("HEAD", _) => MockResponse::new(StatusCode::OK),
+            _ => MockResponse::new(StatusCode::NO_CONTENT),
+        });
+        let backend = new_backend(server.endpoint());
+
+        for path in ["slo", "normal", "missing"] {
+            backend.delete(path, OpDelete::new()).await.unwrap();
+        }
+
+        let deletes: Vec<_> = server
+            .requests()
+            .into_iter()
+            .filter(|req| req.method == http::Method::DELETE)
+            .map(|req| req.uri)
+            .collect();
+        assert_eq!(
+            deletes,
+            vec!["/test/slo?multipart-manifest=delete", "/test/normal"]
+        );
+    }
+}
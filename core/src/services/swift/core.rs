@@ -21,15 +21,22 @@ use http::header;
 use http::Request;
 use http::Response;
 use serde::Deserialize;
+use serde::Serialize;
 
 use crate::raw::*;
 use crate::*;
 
+/// The default threshold to switch to Static Large Object (SLO).
+///
+/// Swift limits single object to 5 GiB by default.
+pub const DEFAULT_SLO_THRESHOLD: u64 = 5 * 1024 * 1024 * 1024;
+
 pub struct SwiftCore {
     pub root: String,
     pub endpoint: String,
     pub container: String,
     pub token: String,
+    pub slo_threshold: u64,
     pub client: HttpClient,
 }
 
@@ -39,20 +46,40 @@ impl Debug for SwiftCore {
             .field("root", &self.root)
             .field("endpoint", &self.endpoint)
             .field("container", &self.container)
+            .field("slo_threshold", &self.slo_threshold)
             .finish_non_exhaustive()
     }
 }
 
 impl SwiftCore {
-    pub async fn swift_delete(&self, path: &str) -> Result<Response<Buffer>> {
+    /// The container to store segments of Static Large Objects.
+    pub fn segments_container(&self) -> String {
+        format!("{}_segments", self.container)
+    }
+
+    /// Build the segment path of given path inside segments container.
+    pub fn segment_path(&self, path: &str, upload_id: &str, part_number: usize) -> String {
         let p = build_abs_path(&self.root, path);
 
-        let url = format!(
+        format!("{}/{upload_id}/{part_number:08}", p.trim_end_matches('/'))
+    }
+
+    /// Delete the object at given path.
+    ///
+    /// Pass `is_slo` for Static Large Objects, so that their segments will
+    /// be cleaned up too.
+    pub async fn swift_delete(&self, path: &str, is_slo: bool) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+
+        let mut url = format!(
             "{}/{}/{}",
             &self.endpoint,
             &self.container,
             percent_encode_path(&p)
         );
+        if is_slo {
+            url.push_str("?multipart-manifest=delete");
+        }
 
         let mut req = Request::delete(&url);
 
@@ -125,6 +152,94 @@ impl SwiftCore {
         self.client.send(req).await
     }
 
+    /// Create the segments container if not exists.
+    ///
+    /// Swift returns `201 Created` for new container and `202 Accepted`
+    /// for existing container.
+    pub async fn swift_create_segments_container(&self) -> Result<Response<Buffer>> {
+        let url = format!("{}/{}", self.endpoint, self.segments_container());
+
+        let mut req = Request::put(&url);
+
+        req = req.header("X-Auth-Token", &self.token);
+        req = req.header(header::CONTENT_LENGTH, 0);
+
+        let req = req.body(Buffer::new()).map_err(new_request_build_error)?;
+
+        self.client.send(req).await
+    }
+
+    pub async fn swift_put_segment(
+        &self,
+        segment: &str,
+        length: u64,
+        body: Buffer,
+    ) -> Result<Response<Buffer>> {
+        let url = format!(
+            "{}/{}/{}",
+            self.endpoint,
+            self.segments_container(),
+            percent_encode_path(segment)
+        );
+
+        let mut req = Request::put(&url);
+
+        req = req.header("X-Auth-Token", &self.token);
+        req = req.header(header::CONTENT_LENGTH, length);
+
+        let req = req.body(body).map_err(new_request_build_error)?;
+
+        self.client.send(req).await
+    }
+
+    pub async fn swift_delete_segment(&self, segment: &str) -> Result<Response<Buffer>> {
+        let url = format!(
+            "{}/{}/{}",
+            self.endpoint,
+            self.segments_container(),
+            percent_encode_path(segment)
+        );
+
+        let mut req = Request::delete(&url);
+
+        req = req.header("X-Auth-Token", &self.token);
+
+        let req = req.body(Buffer::new()).map_err(new_request_build_error)?;
+
+        self.client.send(req).await
+    }
+
+    /// Put the manifest of Static Large Object.
+    ///
+    /// Reference: <https://docs.openstack.org/swift/latest/overview_large_objects.html#static-large-objects>
+    pub async fn swift_put_manifest(
+        &self,
+        path: &str,
+        segments: &[SloSegment],
+    ) -> Result<Response<Buffer>> {
+        let p = build_abs_path(&self.root, path);
+
+        let url = format!(
+            "{}/{}/{}?multipart-manifest=put",
+            self.endpoint,
+            self.container,
+            percent_encode_path(&p)
+        );
+
+        let body = serde_json::to_vec(segments).map_err(new_json_serialize_error)?;
+
+        let mut req = Request::put(&url);
+
+        req = req.header("X-Auth-Token", &self.token);
+        req = req.header(header::CONTENT_LENGTH, body.len());
+
+        let req = req
+            .body(Buffer::from(body))
+            .map_err(new_request_build_error)?;
+
+        self.client.send(req).await
+    }
+
     pub async fn swift_read(
         &self,
         path: &str,
@@ -212,6 +327,15 @@ impl SwiftCore {
     }
 }
 
+/// The segment entry in the manifest of Static Large Object.
+#[derive(Debug, Eq, PartialEq, Serialize)]
+pub struct SloSegment {
+    /// The path of segment in the form of `/container/object`.
+    pub path: String,
+    pub etag: String,
+    pub size_bytes: u64,
+}
+
 #[derive(Debug, Eq, PartialEq, Deserialize)]
 #[serde(untagged)]
 pub enum ListOpResponse {
@@ -284,4 +408,29 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn serialize_slo_manifest_test() -> Result<()> {
+        let segments = vec![
+            SloSegment {
+                path: "/test_segments/a/b/upload/00000000".to_string(),
+                etag: "5e6b5b70b0426b1cc1968003e1afa5ad".to_string(),
+                size_bytes: 1048576,
+            },
+            SloSegment {
+                path: "/test_segments/a/b/upload/00000001".to_string(),
+                etag: "0cc175b9c0f1b6a831c399e269772661".to_string(),
+                size_bytes: 1,
+            },
+        ];
+
+        let bs = serde_json::to_string(&segments).map_err(new_json_serialize_error)?;
+
+        assert_eq!(
+            bs,
+            r#"[{"path":"/test_segments/a/b/upload/00000000","etag":"5e6b5b70b0426b1cc1968003e1afa5ad","size_bytes":1048576},{"path":"/test_segments/a/b/upload/00000001","etag":"0cc175b9c0f1b6a831c399e269772661","size_bytes":1}]"#
+        );
+
+        Ok(())
+    }
 }
@@ -15,38 +15,204 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::sync::Mutex;
 
+use bytes::Buf;
 use http::StatusCode;
+use uuid::Uuid;
 
+use super::core::SloSegment;
 use super::core::SwiftCore;
 use super::error::parse_error;
+use crate::raw::oio::MultipartPart;
 use crate::raw::*;
 use crate::*;
 
 pub struct SwiftWriter {
     core: Arc<SwiftCore>,
     path: String,
+
+    /// The size of uploaded segments, keyed by part number.
+    ///
+    /// SLO manifest requires the size of every segment, but `MultipartPart`
+    /// only carries etag, so we record them here.
+    sizes: Mutex<HashMap<usize, u64>>,
 }
 
 impl SwiftWriter {
     pub fn new(core: Arc<SwiftCore>, _op: OpWrite, path: String) -> Self {
-        SwiftWriter { core, path }
+        SwiftWriter {
+            core,
+            path,
+            sizes: Mutex::new(HashMap::new()),
+        }
+    }
+
+    async fn write_slo(&self, mut body: Buffer) -> Result<Metadata> {
+        let upload_id = oio::MultipartWrite::initiate_part(self).await?;
+
+        let segment_size = self.core.slo_threshold as usize;
+        let mut parts = Vec::new();
+        let mut part_number = 0;
+        while body.has_remaining() {
+            let size = body.remaining().min(segment_size);
+            let bs = body.slice(..size);
+            body.advance(size);
+
+            match oio::MultipartWrite::write_part(self, &upload_id, part_number, size as u64, bs)
+                .await
+            {
+                Ok(part) => parts.push(part),
+                Err(err) => {
+                    // Clean up uploaded segments, the error of abort is ignored
+                    // so that users can get the real cause.
+                    let _ = oio::MultipartWrite::abort_part(self, &upload_id).await;
+                    return Err(err);
+                }
+            }
+            part_number += 1;
+        }
+
+        oio::MultipartWrite::complete_part(self, &upload_id, &parts).await
     }
 }
 
-impl oio::OneShotWrite for SwiftWriter {
-    async fn write_once(&self, bs: Buffer) -> Result<()> {
+impl oio::MultipartWrite for SwiftWriter {
+    async fn write_once(&self, size: u64, body: Buffer) -> Result<Metadata> {
+        // Swift rejects objects larger than the max object size, we need to
+        // upload them as Static Large Object instead.
+        if size > self.core.slo_threshold {
+            return self.write_slo(body).await;
+        }
+
         let resp = self
             .core
-            .swift_create_object(&self.path, bs.len() as u64, bs)
+            .swift_create_object(&self.path, size, body)
             .await?;
 
         let status = resp.status();
 
         match status {
-            StatusCode::CREATED | StatusCode::OK => Ok(()),
+            StatusCode::CREATED | StatusCode::OK => Ok(Metadata::new(EntryMode::FILE)),
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn initiate_part(&self) -> Result<String> {
+        let resp = self.core.swift_create_segments_container().await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::CREATED | StatusCode::ACCEPTED | StatusCode::NO_CONTENT => {
+                // Swift doesn't have the concept of upload id, we generate one
+                // to make sure segments of different uploads won't conflict.
+                Ok(Uuid::new_v4().to_string())
+            }
             _ => Err(parse_error(resp).await?),
         }
     }
+
+    async fn write_part(
+        &self,
+        upload_id: &str,
+        part_number: usize,
+        size: u64,
+        body: Buffer,
+    ) -> Result<MultipartPart> {
+        let segment = self.core.segment_path(&self.path, upload_id, part_number);
+
+        let resp = self.core.swift_put_segment(&segment, size, body).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::CREATED | StatusCode::OK => {
+                let etag = parse_etag(resp.headers())?
+                    .ok_or_else(|| {
+                        Error::new(
+                            ErrorKind::Unexpected,
+                            "ETag not present in returning response",
+                        )
+                    })?
+                    .trim_matches('"')
+                    .to_string();
+
+                self.sizes
+                    .lock()
+                    .expect("lock must succeed")
+                    .insert(part_number, size);
+
+                Ok(MultipartPart {
+                    part_number,
+                    etag,
+                    checksum: None,
+                })
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn complete_part(&self, upload_id: &str, parts: &[MultipartPart]) -> Result<Metadata> {
+        let segments = {
+            let sizes = self.sizes.lock().expect("lock must succeed");
+
+            parts
+                .iter()
+                .map(|p| {
+                    let size_bytes = sizes.get(&p.part_number).copied().ok_or_else(|| {
+                        Error::new(ErrorKind::Unexpected, "size of segment is not recorded")
+                            .with_context("part_number", p.part_number.to_string())
+                    })?;
+
+                    Ok(SloSegment {
+                        path: format!(
+                            "/{}/{}",
+                            self.core.segments_container(),
+                            self.core.segment_path(&self.path, upload_id, p.part_number)
+                        ),
+                        etag: p.etag.clone(),
+                        size_bytes,
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?
+        };
+
+        let resp = self.core.swift_put_manifest(&self.path, &segments).await?;
+
+        let status = resp.status();
+
+        match status {
+            StatusCode::CREATED | StatusCode::OK => {
+                self.sizes.lock().expect("lock must succeed").clear();
+                Ok(Metadata::new(EntryMode::FILE))
+            }
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    async fn abort_part(&self, upload_id: &str) -> Result<()> {
+        let part_numbers: Vec<usize> = self
+            .sizes
+            .lock()
+            .expect("lock must succeed")
+            .drain()
+            .map(|(k, _)| k)
+            .collect();
+
+        for part_number in part_numbers {
+            let segment = self.core.segment_path(&self.path, upload_id, part_number);
+
+            let resp = self.core.swift_delete_segment(&segment).await?;
+
+            match resp.status() {
+                StatusCode::NO_CONTENT | StatusCode::OK | StatusCode::NOT_FOUND => {}
+                _ => return Err(parse_error(resp).await?),
+            }
+        }
+
+        Ok(())
+    }
 }
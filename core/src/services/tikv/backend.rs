@@ -222,6 +222,8 @@ impl kv::Adapter for Adapter {
             Capability {
                 read: true,
                 write: true,
+                batch: true,
+                batch_delete: true,
                 blocking: false,
                 ..Default::default()
             },
@@ -253,6 +255,14 @@ impl kv::Adapter for Adapter {
             .await
             .map_err(parse_tikv_error)
     }
+
+    async fn batch_delete(&self, paths: &[String]) -> Result<()> {
+        self.get_connection()
+            .await?
+            .batch_delete(paths.to_vec())
+            .await
+            .map_err(parse_tikv_error)
+    }
 }
 
 fn parse_tikv_error(e: tikv_client::Error) -> Error {
@@ -186,6 +186,8 @@ impl Access for VercelBlobBackend {
                 write_can_empty: true,
                 write_can_multi: true,
                 write_multi_min_size: Some(5 * 1024 * 1024),
+                write_with_content_type: true,
+                write_with_if_not_exists: true,
 
                 delete: true,
                 copy: true,
@@ -42,6 +42,9 @@ pub(super) mod constants {
     // Default value is 1, which means to add a random suffix.
     // Set it to 0 to disable the random suffix.
     pub const X_VERCEL_BLOB_ADD_RANDOM_SUFFIX: &str = "x-add-random-suffix";
+    // x-allow-overwrite specifies whether to overwrite the existing blob.
+    // Default value is 0, which means uploads to an existing pathname fail.
+    pub const X_VERCEL_BLOB_ALLOW_OVERWRITE: &str = "x-allow-overwrite";
     // https://github.com/vercel/storage/blob/main/packages/blob/src/put-multipart.ts#L84
     // x-mpu-action specifies the action to perform on the MPU.
     // Possible values are:
@@ -131,6 +134,7 @@ impl VercelBlobCore {
         let mut req = Request::put(&url);
 
         req = req.header(X_VERCEL_BLOB_ADD_RANDOM_SUFFIX, "0");
+        req = req.header(X_VERCEL_BLOB_ALLOW_OVERWRITE, allow_overwrite(args));
 
         if let Some(size) = size {
             req = req.header(header::CONTENT_LENGTH, size.to_string())
@@ -288,6 +292,7 @@ impl VercelBlobCore {
 
         req = req.header(X_VERCEL_BLOB_MPU_ACTION, "create");
         req = req.header(X_VERCEL_BLOB_ADD_RANDOM_SUFFIX, "0");
+        req = req.header(X_VERCEL_BLOB_ALLOW_OVERWRITE, allow_overwrite(args));
 
         if let Some(mime) = args.content_type() {
             req = req.header(X_VERCEL_BLOB_CONTENT_TYPE, mime);
@@ -335,6 +340,7 @@ impl VercelBlobCore {
         path: &str,
         upload_id: &str,
         parts: Vec<Part>,
+        args: &OpWrite,
     ) -> Result<Response<Buffer>> {
         let p = build_abs_path(&self.root, path);
 
@@ -348,6 +354,7 @@ impl VercelBlobCore {
         req = req.header(X_VERCEL_BLOB_MPU_ACTION, "complete");
         req = req.header(X_VERCEL_BLOB_MPU_KEY, p);
         req = req.header(X_VERCEL_BLOB_MPU_UPLOAD_ID, upload_id);
+        req = req.header(X_VERCEL_BLOB_ALLOW_OVERWRITE, allow_overwrite(args));
 
         let req = self.sign(req);
 
@@ -362,6 +369,15 @@ impl VercelBlobCore {
     }
 }
 
+/// Overwrite existing blobs unless the write is create-if-absent.
+fn allow_overwrite(args: &OpWrite) -> &'static str {
+    if args.if_not_exists() {
+        "0"
+    } else {
+        "1"
+    }
+}
+
 pub fn parse_blob(blob: &Blob) -> Result<Metadata> {
     let mode = if blob.pathname.ends_with('/') {
         EntryMode::DIR
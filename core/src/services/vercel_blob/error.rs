@@ -17,7 +17,6 @@
 
 use bytes::Buf;
 use http::Response;
-use quick_xml::de;
 use serde::Deserialize;
 
 use crate::raw::*;
@@ -45,13 +44,22 @@ pub async fn parse_error(resp: Response<Buffer>) -> Result<Error> {
     let (kind, retryable) = match parts.status.as_u16() {
         403 => (ErrorKind::PermissionDenied, false),
         404 => (ErrorKind::NotFound, false),
+        409 => (ErrorKind::AlreadyExists, false),
         500 | 502 | 503 | 504 => (ErrorKind::Unexpected, true),
         _ => (ErrorKind::Unexpected, false),
     };
 
-    let (message, _vercel_blob_err) = de::from_reader::<_, VercelBlobError>(bs.clone().reader())
-        .map(|vercel_blob_err| (format!("{vercel_blob_err:?}"), Some(vercel_blob_err)))
-        .unwrap_or_else(|_| (String::from_utf8_lossy(&bs).into_owned(), None));
+    let (message, vercel_blob_err) =
+        serde_json::from_reader::<_, VercelBlobError>(bs.clone().reader())
+            .map(|vercel_blob_err| (format!("{vercel_blob_err:?}"), Some(vercel_blob_err)))
+            .unwrap_or_else(|_| (String::from_utf8_lossy(&bs).into_owned(), None));
+
+    // Uploads to an existing pathname without `x-allow-overwrite` are
+    // rejected as bad requests.
+    let kind = match vercel_blob_err {
+        Some(e) if is_blob_already_exists(&e.error) => ErrorKind::AlreadyExists,
+        _ => kind,
+    };
 
     let mut err = Error::new(kind, message);
 
@@ -64,6 +72,14 @@ pub async fn parse_error(resp: Response<Buffer>) -> Result<Error> {
     Ok(err)
 }
 
+fn is_blob_already_exists(detail: &VercelBlobErrorDetail) -> bool {
+    detail.code == "blob_already_exists"
+        || detail
+            .message
+            .as_deref()
+            .is_some_and(|v| v.contains("already exists"))
+}
+
 #[cfg(test)]
 mod test {
     use http::StatusCode;
@@ -72,16 +88,28 @@ mod test {
 
     #[tokio::test]
     async fn test_parse_error() {
-        let err_res = vec![(
-            r#"{
+        let err_res = vec![
+            (
+                r#"{
                     "error": {
                         "code": "forbidden",
                         "message": "Invalid token"
                     }
                 }"#,
-            ErrorKind::PermissionDenied,
-            StatusCode::FORBIDDEN,
-        )];
+                ErrorKind::PermissionDenied,
+                StatusCode::FORBIDDEN,
+            ),
+            (
+                r#"{
+                    "error": {
+                        "code": "bad_request",
+                        "message": "This blob already exists, use `allowOverwrite: true` if you want to overwrite it."
+                    }
+                }"#,
+                ErrorKind::AlreadyExists,
+                StatusCode::BAD_REQUEST,
+            ),
+        ];
 
         for res in err_res {
             let body = Buffer::from(res.0.as_bytes().to_vec());
@@ -40,10 +40,23 @@ impl VercelBlobWriter {
     pub fn new(core: Arc<VercelBlobCore>, op: OpWrite, path: String) -> Self {
         VercelBlobWriter { core, op, path }
     }
+
+    /// Conflicts of create-if-absent writes are reported as condition not match.
+    async fn parse_write_error(&self, resp: http::Response<Buffer>) -> Result<Error> {
+        let err = parse_error(resp).await?;
+        if self.op.if_not_exists() && err.kind() == ErrorKind::AlreadyExists {
+            return Ok(
+                Error::new(ErrorKind::ConditionNotMatch, "blob already exists")
+                    .with_context("path", &self.path)
+                    .set_source(err),
+            );
+        }
+        Ok(err)
+    }
 }
 
 impl oio::MultipartWrite for VercelBlobWriter {
-    async fn write_once(&self, size: u64, body: Buffer) -> Result<()> {
+    async fn write_once(&self, size: u64, body: Buffer) -> Result<Metadata> {
         let req = self
             .core
             .get_put_request(&self.path, Some(size), &self.op, body)
@@ -54,8 +67,8 @@ impl oio::MultipartWrite for VercelBlobWriter {
         let status = resp.status();
 
         match status {
-            StatusCode::OK => Ok(()),
-            _ => Err(parse_error(resp).await?),
+            StatusCode::OK => Ok(Metadata::new(EntryMode::FILE)),
+            _ => Err(self.parse_write_error(resp).await?),
         }
     }
 
@@ -76,7 +89,7 @@ impl oio::MultipartWrite for VercelBlobWriter {
 
                 Ok(resp.upload_id)
             }
-            _ => Err(parse_error(resp).await?),
+            _ => Err(self.parse_write_error(resp).await?),
         }
     }
 
@@ -113,7 +126,11 @@ impl oio::MultipartWrite for VercelBlobWriter {
         }
     }
 
-    async fn complete_part(&self, upload_id: &str, parts: &[oio::MultipartPart]) -> Result<()> {
+    async fn complete_part(
+        &self,
+        upload_id: &str,
+        parts: &[oio::MultipartPart],
+    ) -> Result<Metadata> {
         let parts = parts
             .iter()
             .map(|p| Part {
@@ -124,14 +141,14 @@ impl oio::MultipartWrite for VercelBlobWriter {
 
         let resp = self
             .core
-            .complete_multipart_upload(&self.path, upload_id, parts)
+            .complete_multipart_upload(&self.path, upload_id, parts, &self.op)
             .await?;
 
         let status = resp.status();
 
         match status {
-            StatusCode::OK => Ok(()),
-            _ => Err(parse_error(resp).await?),
+            StatusCode::OK => Ok(Metadata::new(EntryMode::FILE)),
+            _ => Err(self.parse_write_error(resp).await?),
         }
     }
 
@@ -50,6 +50,10 @@ pub struct WebdavConfig {
     pub root: Option<String>,
     /// WebDAV Service doesn't support copy.
     pub disable_copy: bool,
+    /// Take an exclusive lock via `LOCK` before writing and release it after.
+    ///
+    /// Writes that carry a lock token provided by users will not take lock again.
+    pub enable_write_lock: bool,
 }
 
 impl Debug for WebdavConfig {
@@ -135,6 +139,15 @@ impl WebdavBuilder {
         self
     }
 
+    /// Enable taking an exclusive lock via `LOCK` before writing.
+    ///
+    /// The lock will be released via `UNLOCK` after write finished. Writes
+    /// that carry a lock token provided by users will use that token instead.
+    pub fn enable_write_lock(mut self) -> Self {
+        self.config.enable_write_lock = true;
+        self
+    }
+
     /// Set root path of http backend.
     pub fn root(mut self, root: &str) -> Self {
         self.config.root = if root.is_empty() {
@@ -211,6 +224,7 @@ impl Builder for WebdavBuilder {
             server_path,
             authorization,
             disable_copy: self.config.disable_copy,
+            enable_write_lock: self.config.enable_write_lock,
             root,
             client,
         });
@@ -246,11 +260,14 @@ impl Access for WebdavBackend {
             .set_root(&self.core.root)
             .set_native_capability(Capability {
                 stat: true,
+                stat_with_if_modified_since: true,
+                stat_with_if_unmodified_since: true,
 
                 read: true,
 
                 write: true,
                 write_can_empty: true,
+                write_with_lock_token: true,
 
                 create_dir: true,
                 delete: true,
@@ -260,8 +277,7 @@ impl Access for WebdavBackend {
                 rename: true,
 
                 list: true,
-                // We already support recursive list but some details still need to polish.
-                // list_with_recursive: true,
+                list_with_recursive: true,
                 ..Default::default()
             });
 
@@ -273,8 +289,11 @@ impl Access for WebdavBackend {
         Ok(RpCreateDir::default())
     }
 
-    async fn stat(&self, path: &str, _: OpStat) -> Result<RpStat> {
+    async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
         let metadata = self.core.webdav_stat(path).await?;
+        // Conditional headers are not widely supported for `PROPFIND`, check
+        // them against `getlastmodified` instead.
+        args.check_modified_since(metadata.last_modified())?;
         Ok(RpStat::new(metadata))
     }
 
@@ -333,8 +352,8 @@ impl Access for WebdavBackend {
         }
     }
 
-    async fn rename(&self, from: &str, to: &str, _args: OpRename) -> Result<RpRename> {
-        let resp = self.core.webdav_move(from, to).await?;
+    async fn rename(&self, from: &str, to: &str, args: OpRename) -> Result<RpRename> {
+        let resp = self.core.webdav_move(from, to, args.lock_token()).await?;
 
         let status = resp.status();
         match status {
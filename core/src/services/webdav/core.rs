@@ -19,6 +19,7 @@ use std::collections::VecDeque;
 use std::fmt;
 use std::fmt::Debug;
 use std::fmt::Formatter;
+use std::time::Duration;
 
 use bytes::Bytes;
 use http::header;
@@ -67,12 +68,33 @@ static HEADER_DESTINATION: &str = "Destination";
 ///
 /// reference: [RFC4918: 10.6.  Overwrite Header](https://datatracker.ietf.org/doc/html/rfc4918#section-10.6)
 static HEADER_OVERWRITE: &str = "Overwrite";
+/// The request to take an exclusive write lock.
+///
+/// reference: [RFC4918: 9.10. LOCK Method](https://datatracker.ietf.org/doc/html/rfc4918#section-9.10)
+static LOCK_REQUEST: &str = r#"<?xml version="1.0" encoding="utf-8" ?><D:lockinfo xmlns:D="DAV:"><D:lockscope><D:exclusive/></D:lockscope><D:locktype><D:write/></D:locktype></D:lockinfo>"#;
+/// The header to specify the lock token of the query.
+///
+/// The Lock-Token response header is returned by LOCK to carry the
+/// created lock token, and is required by UNLOCK to identify the lock
+/// to be removed.
+///
+/// reference: [RFC4918: 10.5. Lock-Token Header](https://datatracker.ietf.org/doc/html/rfc4918#section-10.5)
+static HEADER_LOCK_TOKEN: &str = "Lock-Token";
+/// The header to submit the lock token for the requests.
+///
+/// reference: [RFC4918: 10.4. If Header](https://datatracker.ietf.org/doc/html/rfc4918#section-10.4)
+static HEADER_IF: &str = "If";
+/// The header to specify the timeout of a lock.
+///
+/// reference: [RFC4918: 10.7. Timeout Request Header](https://datatracker.ietf.org/doc/html/rfc4918#section-10.7)
+static HEADER_TIMEOUT: &str = "Timeout";
 
 pub struct WebdavCore {
     pub endpoint: String,
     pub server_path: String,
     pub root: String,
     pub disable_copy: bool,
+    pub enable_write_lock: bool,
     pub authorization: Option<String>,
 
     pub client: HttpClient,
@@ -182,6 +204,10 @@ impl WebdavCore {
             req = req.header(header::CONTENT_DISPOSITION, v)
         }
 
+        if let Some(v) = args.lock_token() {
+            req = req.header(HEADER_IF, format_if_header(v))
+        }
+
         let req = req.body(body).map_err(new_request_build_error)?;
 
         self.client.send(req).await
@@ -228,7 +254,12 @@ impl WebdavCore {
         self.client.send(req).await
     }
 
-    pub async fn webdav_move(&self, from: &str, to: &str) -> Result<Response<Buffer>> {
+    pub async fn webdav_move(
+        &self,
+        from: &str,
+        to: &str,
+        lock_token: Option<&str>,
+    ) -> Result<Response<Buffer>> {
         // Check if source file exists.
         let _ = self.webdav_stat(from).await?;
         // Make sure target's dir is exist.
@@ -246,6 +277,10 @@ impl WebdavCore {
             req = req.header(header::AUTHORIZATION, auth);
         }
 
+        if let Some(v) = lock_token {
+            req = req.header(HEADER_IF, format_if_header(v));
+        }
+
         req = req.header(HEADER_DESTINATION, target_uri);
         req = req.header(HEADER_OVERWRITE, "T");
 
@@ -254,7 +289,88 @@ impl WebdavCore {
         self.client.send(req).await
     }
 
-    pub async fn webdav_list(&self, path: &str, args: &OpList) -> Result<Response<Buffer>> {
+    /// Take an exclusive write lock on the given path.
+    ///
+    /// Returns the lock token which should be passed to following requests
+    /// and [`WebdavCore::webdav_unlock`].
+    ///
+    /// Reference: [RFC4918: 9.10. LOCK Method](https://datatracker.ietf.org/doc/html/rfc4918#section-9.10)
+    pub async fn webdav_lock(&self, path: &str, timeout: Duration) -> Result<String> {
+        let path = build_rooted_abs_path(&self.root, path);
+        let url = format!("{}{}", self.endpoint, percent_encode_path(&path));
+
+        let mut req = Request::builder().method("LOCK").uri(&url);
+
+        req = req.header(header::CONTENT_TYPE, "application/xml");
+        req = req.header(header::CONTENT_LENGTH, LOCK_REQUEST.len());
+        if let Some(auth) = &self.authorization {
+            req = req.header(header::AUTHORIZATION, auth);
+        }
+
+        // Only lock the resource itself.
+        req = req.header(HEADER_DEPTH, "0");
+        req = req.header(HEADER_TIMEOUT, format!("Second-{}", timeout.as_secs()));
+
+        let req = req
+            .body(Buffer::from(Bytes::from(LOCK_REQUEST)))
+            .map_err(new_request_build_error)?;
+
+        let resp = self.client.send(req).await?;
+        match resp.status() {
+            StatusCode::OK | StatusCode::CREATED => {}
+            _ => return Err(parse_error(resp).await?),
+        }
+
+        let token = resp
+            .headers()
+            .get(HEADER_LOCK_TOKEN)
+            .ok_or_else(|| {
+                Error::new(
+                    ErrorKind::Unexpected,
+                    "webdav should return Lock-Token header, but it's missing",
+                )
+            })?
+            .to_str()
+            .map_err(|err| {
+                Error::new(ErrorKind::Unexpected, "header value is not valid string")
+                    .set_source(err)
+            })?;
+
+        Ok(token
+            .trim()
+            .trim_start_matches('<')
+            .trim_end_matches('>')
+            .to_string())
+    }
+
+    /// Release the lock on the given path.
+    ///
+    /// Reference: [RFC4918: 9.11. UNLOCK Method](https://datatracker.ietf.org/doc/html/rfc4918#section-9.11)
+    pub async fn webdav_unlock(&self, path: &str, lock_token: &str) -> Result<()> {
+        let path = build_rooted_abs_path(&self.root, path);
+        let url = format!("{}{}", self.endpoint, percent_encode_path(&path));
+
+        let mut req = Request::builder().method("UNLOCK").uri(&url);
+
+        if let Some(auth) = &self.authorization {
+            req = req.header(header::AUTHORIZATION, auth);
+        }
+
+        req = req.header(HEADER_LOCK_TOKEN, format!("<{lock_token}>"));
+
+        let req = req.body(Buffer::new()).map_err(new_request_build_error)?;
+
+        let resp = self.client.send(req).await?;
+        match resp.status() {
+            StatusCode::NO_CONTENT | StatusCode::OK => Ok(()),
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
+    /// List the given path with PROPFIND.
+    ///
+    /// `depth` should be `1` or `infinity`.
+    pub async fn webdav_list(&self, path: &str, depth: &str) -> Result<Response<Buffer>> {
         let path = build_rooted_abs_path(&self.root, path);
         let url = format!("{}{}", self.endpoint, percent_encode_path(&path));
 
@@ -266,11 +382,7 @@ impl WebdavCore {
             req = req.header(header::AUTHORIZATION, auth);
         }
 
-        if args.recursive() {
-            req = req.header(HEADER_DEPTH, "infinity");
-        } else {
-            req = req.header(HEADER_DEPTH, "1");
-        }
+        req = req.header(HEADER_DEPTH, depth);
 
         let req = req
             .body(Buffer::from(Bytes::from(PROPFIND_REQUEST)))
@@ -351,6 +463,11 @@ impl WebdavCore {
     }
 }
 
+/// Format the lock token into `If` header like `(<opaquelocktoken:xxx>)`.
+fn format_if_header(lock_token: &str) -> String {
+    format!("(<{lock_token}>)")
+}
+
 pub fn deserialize_multistatus(bs: &[u8]) -> Result<Multistatus> {
     let s = String::from_utf8_lossy(bs);
     // HACKS! HACKS! HACKS!
@@ -31,8 +31,8 @@ pub async fn parse_error(resp: Response<Buffer>) -> Result<Error> {
         StatusCode::NOT_FOUND => (ErrorKind::NotFound, false),
         // Some services (like owncloud) return 403 while file locked.
         StatusCode::FORBIDDEN => (ErrorKind::PermissionDenied, true),
-        // Allowing retry for resource locked.
-        StatusCode::LOCKED => (ErrorKind::Unexpected, true),
+        // Resource is locked by others, retry won't help before lock released.
+        StatusCode::LOCKED => (ErrorKind::Locked, false),
         StatusCode::INTERNAL_SERVER_ERROR
         | StatusCode::BAD_GATEWAY
         | StatusCode::SERVICE_UNAVAILABLE
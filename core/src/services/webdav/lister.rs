@@ -15,7 +15,9 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::VecDeque;
 use std::sync::Arc;
+use std::sync::Mutex;
 
 use http::StatusCode;
 
@@ -29,6 +31,12 @@ pub struct WebdavLister {
 
     path: String,
     args: OpList,
+
+    /// Dirs that pending to be listed while walking dir by dir.
+    ///
+    /// It's `None` until the server rejects `Depth: infinity`, and we
+    /// fall back to list every dir with `Depth: 1`.
+    walking: Mutex<Option<VecDeque<String>>>,
 }
 
 impl WebdavLister {
@@ -37,40 +45,28 @@ impl WebdavLister {
             core,
             path: path.to_string(),
             args,
+            walking: Mutex::new(None),
         }
     }
-}
-
-impl oio::PageList for WebdavLister {
-    async fn next_page(&self, ctx: &mut oio::PageContext) -> Result<()> {
-        let resp = self.core.webdav_list(&self.path, &self.args).await?;
-
-        // jfrog artifactory's webdav services have some strange behavior.
-        // We add this flag to check if the server is jfrog artifactory.
-        //
-        // Example: `"x-jfrog-version": "Artifactory/7.77.5 77705900"`
-        let is_jfrog_artifactory = if let Some(v) = resp.headers().get("x-jfrog-version") {
-            v.to_str().unwrap_or_default().starts_with("Artifactory")
-        } else {
-            false
-        };
-
-        let bs = if resp.status().is_success() {
-            resp.into_body()
-        } else if resp.status() == StatusCode::NOT_FOUND && self.path.ends_with('/') {
-            ctx.done = true;
-            return Ok(());
-        } else {
-            return Err(parse_error(resp).await?);
-        };
 
+    /// Parse the multistatus response of dir into entries.
+    ///
+    /// Returns the sub dirs found in the response.
+    fn parse_entries(
+        &self,
+        dir: &str,
+        bs: Buffer,
+        is_jfrog_artifactory: bool,
+        ctx: &mut oio::PageContext,
+    ) -> Result<Vec<String>> {
         let result: Multistatus = deserialize_multistatus(&bs.to_bytes())?;
 
+        let mut dirs = Vec::new();
         for res in result.response {
-            let mut path = res
-                .href
+            let href = resolve_href(&res.href);
+            let mut path = href
                 .strip_prefix(&self.core.server_path)
-                .unwrap_or(&res.href)
+                .unwrap_or(href)
                 .to_string();
 
             let meta = parse_propstat(&res.propstat)?;
@@ -88,7 +84,7 @@ impl oio::PageList for WebdavLister {
             let normalized_path = build_rel_path(&self.core.root, &path);
             let decoded_path = percent_decode_path(&normalized_path);
 
-            if normalized_path == self.path || decoded_path == self.path {
+            if normalized_path == dir || decoded_path == dir {
                 // WebDAV server may return the current path as an entry.
                 continue;
             }
@@ -102,10 +98,152 @@ impl oio::PageList for WebdavLister {
                 continue;
             }
 
+            if meta.is_dir() {
+                dirs.push(decoded_path.clone());
+            }
             ctx.entries.push_back(oio::Entry::new(&decoded_path, meta))
         }
+
+        Ok(dirs)
+    }
+
+    /// List the next pending dir with `Depth: 1`.
+    async fn next_walk_page(&self, ctx: &mut oio::PageContext) -> Result<()> {
+        let dir = self
+            .walking
+            .lock()
+            .expect("lock must succeed")
+            .as_mut()
+            .and_then(|dirs| dirs.pop_front());
+        let Some(dir) = dir else {
+            ctx.done = true;
+            return Ok(());
+        };
+
+        let resp = self.core.webdav_list(&dir, "1").await?;
+
+        let is_jfrog_artifactory = is_jfrog_artifactory(&resp);
+        let bs = if resp.status().is_success() {
+            resp.into_body()
+        } else if resp.status() == StatusCode::NOT_FOUND && dir.ends_with('/') {
+            return Ok(());
+        } else {
+            return Err(parse_error(resp).await?);
+        };
+
+        let dirs = self.parse_entries(&dir, bs, is_jfrog_artifactory, ctx)?;
+        if let Some(pending) = self.walking.lock().expect("lock must succeed").as_mut() {
+            pending.extend(dirs);
+        }
+
+        Ok(())
+    }
+}
+
+impl oio::PageList for WebdavLister {
+    async fn next_page(&self, ctx: &mut oio::PageContext) -> Result<()> {
+        if self.walking.lock().expect("lock must succeed").is_some() {
+            return self.next_walk_page(ctx).await;
+        }
+
+        let depth = if self.args.recursive() {
+            "infinity"
+        } else {
+            "1"
+        };
+        let resp = self.core.webdav_list(&self.path, depth).await?;
+
+        let is_jfrog_artifactory = is_jfrog_artifactory(&resp);
+
+        let bs = if resp.status().is_success() {
+            resp.into_body()
+        } else if resp.status() == StatusCode::NOT_FOUND && self.path.ends_with('/') {
+            ctx.done = true;
+            return Ok(());
+        } else if resp.status() == StatusCode::FORBIDDEN && self.args.recursive() {
+            let (parts, body) = resp.into_parts();
+            let bs = body.to_bytes();
+
+            // Server doesn't allow `Depth: infinity`, fall back to walk dir by dir.
+            //
+            // reference: [RFC4918: 9.1 PROPFIND Method](https://datatracker.ietf.org/doc/html/rfc4918#section-9.1)
+            if is_propfind_finite_depth(&bs) {
+                *self.walking.lock().expect("lock must succeed") =
+                    Some(VecDeque::from([self.path.clone()]));
+                return Ok(());
+            }
+
+            return Err(parse_error(http::Response::from_parts(parts, Buffer::from(bs))).await?);
+        } else {
+            return Err(parse_error(resp).await?);
+        };
+
+        self.parse_entries(&self.path, bs, is_jfrog_artifactory, ctx)?;
         ctx.done = true;
 
         Ok(())
     }
 }
+
+/// jfrog artifactory's webdav services have some strange behavior.
+/// We add this flag to check if the server is jfrog artifactory.
+///
+/// Example: `"x-jfrog-version": "Artifactory/7.77.5 77705900"`
+fn is_jfrog_artifactory<T>(resp: &http::Response<T>) -> bool {
+    if let Some(v) = resp.headers().get("x-jfrog-version") {
+        v.to_str().unwrap_or_default().starts_with("Artifactory")
+    } else {
+        false
+    }
+}
+
+/// Check if the error body contains the `propfind-finite-depth` precondition.
+fn is_propfind_finite_depth(bs: &[u8]) -> bool {
+    String::from_utf8_lossy(bs).contains("propfind-finite-depth")
+}
+
+/// Resolve the href returned by server into path.
+///
+/// The href could be either an absolute URL like `http://example.com/dav/a`
+/// or an absolute path like `/dav/a`.
+fn resolve_href(href: &str) -> &str {
+    let Some((_, rest)) = href.split_once("://") else {
+        return href;
+    };
+
+    match rest.find('/') {
+        Some(idx) => &rest[idx..],
+        None => "/",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_href() {
+        let cases = vec![
+            ("/dav/a/b", "/dav/a/b"),
+            ("/", "/"),
+            ("http://example.com/dav/a/b", "/dav/a/b"),
+            ("https://example.com:8080/dav/a/", "/dav/a/"),
+            ("https://example.com", "/"),
+        ];
+
+        for (href, expected) in cases {
+            assert_eq!(resolve_href(href), expected, "{href}");
+        }
+    }
+
+    #[test]
+    fn test_is_propfind_finite_depth() {
+        let bs = r#"<?xml version="1.0" encoding="utf-8"?>
+        <D:error xmlns:D="DAV:">
+          <D:propfind-finite-depth/>
+        </D:error>"#;
+
+        assert!(is_propfind_finite_depth(bs.as_bytes()));
+        assert!(!is_propfind_finite_depth(b"Forbidden"));
+    }
+}
@@ -16,6 +16,7 @@
 // under the License.
 
 use std::sync::Arc;
+use std::time::Duration;
 
 use http::StatusCode;
 
@@ -31,24 +32,51 @@ pub struct WebdavWriter {
     path: String,
 }
 
+/// The timeout of the lock taken by writer.
+///
+/// The lock will be released after write finished, the timeout only makes
+/// sure the lock won't be held forever if the process crashed.
+const WRITE_LOCK_TIMEOUT: Duration = Duration::from_secs(600);
+
 impl WebdavWriter {
     pub fn new(core: Arc<WebdavCore>, op: OpWrite, path: String) -> Self {
         WebdavWriter { core, op, path }
     }
-}
 
-impl oio::OneShotWrite for WebdavWriter {
-    async fn write_once(&self, bs: Buffer) -> Result<()> {
+    async fn put(&self, op: &OpWrite, bs: Buffer) -> Result<Metadata> {
         let resp = self
             .core
-            .webdav_put(&self.path, Some(bs.len() as u64), &self.op, bs)
+            .webdav_put(&self.path, Some(bs.len() as u64), op, bs)
             .await?;
 
         let status = resp.status();
 
         match status {
-            StatusCode::CREATED | StatusCode::OK | StatusCode::NO_CONTENT => Ok(()),
+            StatusCode::CREATED | StatusCode::OK | StatusCode::NO_CONTENT => {
+                parse_into_write_metadata(resp.headers())
+            }
             _ => Err(parse_error(resp).await?),
         }
     }
 }
+
+impl oio::OneShotWrite for WebdavWriter {
+    async fn write_once(&self, bs: Buffer) -> Result<Metadata> {
+        // Use the lock token provided by users directly.
+        if !self.core.enable_write_lock || self.op.lock_token().is_some() {
+            return self.put(&self.op, bs).await;
+        }
+
+        let token = self
+            .core
+            .webdav_lock(&self.path, WRITE_LOCK_TIMEOUT)
+            .await?;
+        let res = self.put(&self.op.clone().with_lock_token(&token), bs).await;
+        // Always release the lock even if the write failed.
+        let unlock = self.core.webdav_unlock(&self.path, &token).await;
+
+        let meta = res?;
+        unlock?;
+        Ok(meta)
+    }
+}
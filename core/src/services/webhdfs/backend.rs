@@ -57,6 +57,8 @@ pub struct WebhdfsConfig {
     pub disable_list_batch: bool,
     /// atomic_write_dir of this backend
     pub atomic_write_dir: Option<String>,
+    /// Override the host of the datanode location redirected by namenode.
+    pub redirect_host: Option<String>,
 }
 
 impl Debug for WebhdfsConfig {
@@ -65,6 +67,7 @@ impl Debug for WebhdfsConfig {
             .field("root", &self.root)
             .field("endpoint", &self.endpoint)
             .field("atomic_write_dir", &self.atomic_write_dir)
+            .field("redirect_host", &self.redirect_host)
             .finish_non_exhaustive()
     }
 }
@@ -161,6 +164,23 @@ impl WebhdfsBuilder {
         };
         self
     }
+
+    /// Set the host to override the datanode location redirected by namenode.
+    ///
+    /// # Notes
+    ///
+    /// Namenode returns the datanode's hostname for create and append, which
+    /// might be not resolvable from client (like HDFS running in containers).
+    /// The host can be `host` or `host:port`, the port of original location
+    /// will be kept if not specified.
+    pub fn redirect_host(mut self, host: &str) -> Self {
+        self.config.redirect_host = if host.is_empty() {
+            None
+        } else {
+            Some(host.to_string())
+        };
+        self
+    }
 }
 
 impl Builder for WebhdfsBuilder {
@@ -210,6 +230,7 @@ impl Builder for WebhdfsBuilder {
             root_checker: OnceCell::new(),
             atomic_write_dir,
             disable_list_batch: self.config.disable_list_batch,
+            redirect_host: self.config.redirect_host,
         };
 
         Ok(backend)
@@ -226,10 +247,19 @@ pub struct WebhdfsBackend {
 
     pub atomic_write_dir: Option<String>,
     pub disable_list_batch: bool,
+    pub redirect_host: Option<String>,
     pub client: HttpClient,
 }
 
 impl WebhdfsBackend {
+    /// Rewrite the host of location returned by namenode if `redirect_host` is set.
+    fn redirect_location(&self, location: String) -> Result<String> {
+        match &self.redirect_host {
+            Some(host) => rewrite_location_host(&location, host),
+            None => Ok(location),
+        }
+    }
+
     pub fn webhdfs_create_dir_request(&self, path: &str) -> Result<Request<Buffer>> {
         let p = build_abs_path(&self.root, path);
 
@@ -282,7 +312,9 @@ impl WebhdfsBackend {
         let resp: LocationResponse =
             serde_json::from_reader(bs.reader()).map_err(new_json_deserialize_error)?;
 
-        let mut req = Request::put(&resp.location);
+        let location = self.redirect_location(resp.location)?;
+
+        let mut req = Request::put(&location);
 
         if let Some(size) = size {
             req = req.header(CONTENT_LENGTH, size);
@@ -321,7 +353,7 @@ impl WebhdfsBackend {
                 let resp: LocationResponse =
                     serde_json::from_reader(bs.reader()).map_err(new_json_deserialize_error)?;
 
-                Ok(resp.location)
+                self.redirect_location(resp.location)
             }
             _ => Err(parse_error(resp).await?),
         }
@@ -398,6 +430,18 @@ impl WebhdfsBackend {
         req.body(Buffer::new()).map_err(new_request_build_error)
     }
 
+    /// Concat sources into the path, sources will be removed after concat.
+    pub async fn webhdfs_concat(&self, path: &str, sources: Vec<String>) -> Result<()> {
+        let req = self.webhdfs_concat_request(path, sources)?;
+
+        let resp = self.client.send(req).await?;
+
+        match resp.status() {
+            StatusCode::OK => Ok(()),
+            _ => Err(parse_error(resp).await?),
+        }
+    }
+
     async fn webhdfs_open_request(
         &self,
         path: &str,
@@ -560,6 +604,7 @@ impl Access for WebhdfsBackend {
 
                 write: true,
                 write_can_append: true,
+                write_returns_append_offset: true,
                 write_can_multi: self.atomic_write_dir.is_some(),
 
                 create_dir: true,
@@ -697,3 +742,61 @@ impl Access for WebhdfsBackend {
 pub(super) struct LocationResponse {
     pub location: String,
 }
+
+/// Replace the host (and port if given) of location with the given host.
+fn rewrite_location_host(location: &str, host: &str) -> Result<String> {
+    let uri: http::Uri = location.parse().map_err(|err| {
+        Error::new(ErrorKind::Unexpected, "webhdfs returns invalid location")
+            .with_context("location", location)
+            .set_source(err)
+    })?;
+
+    let authority = match uri.port_u16() {
+        Some(port) if !host.contains(':') => format!("{host}:{port}"),
+        _ => host.to_string(),
+    };
+
+    let mut parts = uri.into_parts();
+    parts.authority = Some(authority.parse().map_err(|err| {
+        Error::new(ErrorKind::ConfigInvalid, "redirect host is invalid")
+            .with_context("service", Scheme::Webhdfs)
+            .with_context("redirect_host", host)
+            .set_source(err)
+    })?);
+
+    let uri = http::Uri::from_parts(parts).map_err(|err| {
+        Error::new(ErrorKind::Unexpected, "failed to rewrite webhdfs location")
+            .with_context("location", location)
+            .set_source(err)
+    })?;
+
+    Ok(uri.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rewrite_location_host() {
+        let cases = vec![
+            (
+                "keep port",
+                "http://datanode-1.hdfs.svc:9864/webhdfs/v1/a?op=CREATE&namenoderpcaddress=nn:8020",
+                "localhost",
+                "http://localhost:9864/webhdfs/v1/a?op=CREATE&namenoderpcaddress=nn:8020",
+            ),
+            (
+                "override port",
+                "http://datanode-1:9864/webhdfs/v1/a?op=APPEND",
+                "127.0.0.1:19864",
+                "http://127.0.0.1:19864/webhdfs/v1/a?op=APPEND",
+            ),
+        ];
+
+        for (name, location, host, expected) in cases {
+            let actual = rewrite_location_host(location, host).expect("must succeed");
+            assert_eq!(actual, expected, "{name}");
+        }
+    }
+}
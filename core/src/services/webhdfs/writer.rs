@@ -15,11 +15,13 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use bytes::Buf;
 use http::StatusCode;
 use uuid::Uuid;
 
 use super::backend::WebhdfsBackend;
 use super::error::parse_error;
+use super::message::FileStatusWrapper;
 use crate::raw::*;
 use crate::*;
 
@@ -40,7 +42,7 @@ impl WebhdfsWriter {
 }
 
 impl oio::BlockWrite for WebhdfsWriter {
-    async fn write_once(&self, size: u64, body: Buffer) -> Result<()> {
+    async fn write_once(&self, size: u64, body: Buffer) -> Result<Metadata> {
         let req = self
             .backend
             .webhdfs_create_object_request(&self.path, Some(size), &self.op, body)
@@ -50,7 +52,7 @@ impl oio::BlockWrite for WebhdfsWriter {
 
         let status = resp.status();
         match status {
-            StatusCode::CREATED | StatusCode::OK => Ok(()),
+            StatusCode::CREATED | StatusCode::OK => Ok(Metadata::new(EntryMode::FILE)),
             _ => Err(parse_error(resp).await?),
         }
     }
@@ -81,7 +83,7 @@ impl oio::BlockWrite for WebhdfsWriter {
         }
     }
 
-    async fn complete_block(&self, block_ids: Vec<Uuid>) -> Result<()> {
+    async fn complete_block(&self, block_ids: Vec<Uuid>) -> Result<Metadata> {
         let Some(ref atomic_write_dir) = self.backend.atomic_write_dir else {
             return Err(Error::new(
                 ErrorKind::Unsupported,
@@ -95,17 +97,9 @@ impl oio::BlockWrite for WebhdfsWriter {
                 .map(|s| format!("{}{}", atomic_write_dir, s))
                 .collect();
             // concat blocks
-            let req = self
-                .backend
-                .webhdfs_concat_request(&first_block_id, sources)?;
-
-            let resp = self.backend.client.send(req).await?;
-
-            let status = resp.status();
-
-            if status != StatusCode::OK {
-                return Err(parse_error(resp).await?);
-            }
+            self.backend
+                .webhdfs_concat(&first_block_id, sources)
+                .await?;
         }
         // delete the path file
         let resp = self.backend.webhdfs_delete(&self.path).await?;
@@ -123,7 +117,7 @@ impl oio::BlockWrite for WebhdfsWriter {
         let status = resp.status();
 
         match status {
-            StatusCode::OK => Ok(()),
+            StatusCode::OK => Ok(Metadata::new(EntryMode::FILE)),
             _ => Err(parse_error(resp).await?),
         }
     }
@@ -142,10 +136,23 @@ impl oio::BlockWrite for WebhdfsWriter {
 
 impl oio::AppendWrite for WebhdfsWriter {
     async fn offset(&self) -> Result<u64> {
-        Ok(0)
+        let resp = self.backend.webhdfs_get_file_status(&self.path).await?;
+
+        let status = resp.status();
+        match status {
+            StatusCode::OK => {
+                let bs = resp.into_body();
+                let file_status = serde_json::from_reader::<_, FileStatusWrapper>(bs.reader())
+                    .map_err(new_json_deserialize_error)?
+                    .file_status;
+                Ok(file_status.length)
+            }
+            StatusCode::NOT_FOUND => Ok(0),
+            _ => Err(parse_error(resp).await?),
+        }
     }
 
-    async fn append(&self, _offset: u64, size: u64, body: Buffer) -> Result<()> {
+    async fn append(&self, _offset: u64, size: u64, body: Buffer) -> Result<Metadata> {
         let resp = self.backend.webhdfs_get_file_status(&self.path).await?;
 
         let status = resp.status();
@@ -185,7 +192,7 @@ impl oio::AppendWrite for WebhdfsWriter {
 
         let status = resp.status();
         match status {
-            StatusCode::OK => Ok(()),
+            StatusCode::OK => Ok(Metadata::new(EntryMode::FILE)),
             _ => Err(parse_error(resp).await?),
         }
     }
@@ -17,7 +17,6 @@
 
 use bytes::Buf;
 use http::Response;
-use quick_xml::de;
 use serde::Deserialize;
 
 use crate::raw::*;
@@ -47,10 +46,17 @@ pub async fn parse_error(resp: Response<Buffer>) -> Result<Error> {
         _ => (ErrorKind::Unexpected, false),
     };
 
-    let (message, _yandex_disk_err) = de::from_reader::<_, YandexDiskError>(bs.clone().reader())
+    let (message, yandex_disk_err) = serde_json::from_slice::<YandexDiskError>(&bs)
         .map(|yandex_disk_err| (format!("{yandex_disk_err:?}"), Some(yandex_disk_err)))
         .unwrap_or_else(|_| (String::from_utf8_lossy(&bs).into_owned(), None));
 
+    // YandexDisk returns 409 for both existing destination and missing
+    // parent, only the error code can tell.
+    let kind = match yandex_disk_err {
+        Some(e) if e.error == "DiskResourceAlreadyExistsError" => ErrorKind::AlreadyExists,
+        _ => kind,
+    };
+
     let mut err = Error::new(kind, message);
 
     err = with_error_response_context(err, parts);
@@ -89,6 +95,15 @@ mod test {
                 ErrorKind::PermissionDenied,
                 StatusCode::FORBIDDEN,
             ),
+            (
+                r#"{
+                    "message": "По указанному пути \"disk:/a\" уже существует папка с таким именем.",
+                    "description": "Specified path \"disk:/a\" points to existent directory.",
+                    "error": "DiskResourceAlreadyExistsError"
+                }"#,
+                ErrorKind::AlreadyExists,
+                StatusCode::CONFLICT,
+            ),
         ];
 
         for res in err_res {
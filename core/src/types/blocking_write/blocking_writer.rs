@@ -77,13 +77,28 @@ impl BlockingWriter {
         Ok(())
     }
 
+    /// Get the strategy that the writer uses to upload data, see
+    /// [`Writer::write_mode`] for details.
+    pub fn write_mode(&self) -> Option<WriteMode> {
+        self.inner.rp().mode()
+    }
+
+    /// Get the number of append requests issued by the writer so far, see
+    /// [`Writer::append_requests`] for details.
+    pub fn append_requests(&self) -> usize {
+        self.inner.rp().append_requests()
+    }
+
     /// Close the writer and make sure all data have been committed.
     ///
+    /// Returns the [`Metadata`] of the written file, see [`Writer::close`]
+    /// for details.
+    ///
     /// ## Notes
     ///
     /// Close should only be called when the writer is not closed or
     /// aborted, otherwise an unexpected error could be returned.
-    pub fn close(&mut self) -> Result<()> {
+    pub fn close(&mut self) -> Result<Metadata> {
         self.inner.close()
     }
 
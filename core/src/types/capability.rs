@@ -61,6 +61,10 @@ pub struct Capability {
     pub stat_with_if_match: bool,
     /// If operator supports stat with if none match.
     pub stat_with_if_none_match: bool,
+    /// If operator supports stat with if modified since.
+    pub stat_with_if_modified_since: bool,
+    /// If operator supports stat with if unmodified since.
+    pub stat_with_if_unmodified_since: bool,
     /// if operator supports read with override cache control.
     pub stat_with_override_cache_control: bool,
     /// if operator supports read with override content disposition.
@@ -97,6 +101,34 @@ pub struct Capability {
     pub write_with_cache_control: bool,
     /// If operator supports write with user defined metadata
     pub write_with_user_metadata: bool,
+    /// If operator supports write with tags.
+    pub write_with_tags: bool,
+    /// If operator supports write with lock token.
+    pub write_with_lock_token: bool,
+    /// If operator supports write with expiry.
+    pub write_with_expiry: bool,
+    /// If operator supports write with if not exists.
+    pub write_with_if_not_exists: bool,
+    /// If operator verifies the content md5 of write natively.
+    ///
+    /// Content md5 is always verified client-side before the write is
+    /// committed, services with this capability will send it to be verified
+    /// again on server side as well.
+    pub write_with_content_md5: bool,
+    /// If operator verifies the content sha256 of write natively.
+    ///
+    /// Content sha256 is always verified client-side before the write is
+    /// committed, services with this capability will send it to be verified
+    /// again on server side as well.
+    pub write_with_content_sha256: bool,
+    /// If operator returns the etag of the written file.
+    pub write_returns_etag: bool,
+    /// If operator returns the version of the written file.
+    pub write_returns_version: bool,
+    /// If operator returns the last modified time of the written file.
+    pub write_returns_last_modified: bool,
+    /// If operator returns the offset the data landed at for append writes.
+    pub write_returns_append_offset: bool,
     /// write_multi_max_size is the max size that services support in write_multi.
     ///
     /// For example, AWS S3 supports 5GiB as max in write_multi.
@@ -116,12 +148,27 @@ pub struct Capability {
 
     /// If operator supports create dir.
     pub create_dir: bool,
+    /// If create dir on an existing file fails with `NotADirectory`.
+    ///
+    /// Only services with hierarchical namespace can detect this, `path`
+    /// and `path/` are different keys for others.
+    pub create_dir_rejects_file: bool,
 
     /// If operator supports delete.
     pub delete: bool,
+    /// If operator supports delete with lock token.
+    pub delete_with_lock_token: bool,
 
     /// If operator supports copy.
     pub copy: bool,
+    /// If operator supports copy with replacing metadata.
+    pub copy_with_metadata_directive: bool,
+    /// If operator supports copy with content type.
+    pub copy_with_content_type: bool,
+    /// If operator supports copy with cache control.
+    pub copy_with_cache_control: bool,
+    /// If operator supports copy with if not exists.
+    pub copy_with_if_not_exists: bool,
 
     /// If operator supports rename.
     pub rename: bool,
@@ -134,6 +181,8 @@ pub struct Capability {
     pub list_with_start_after: bool,
     /// If backend supports list with recursive.
     pub list_with_recursive: bool,
+    /// If backend returns list entries in lexicographical order of their paths.
+    pub list_ordered: bool,
 
     /// If operator supports presign.
     pub presign: bool,
@@ -143,11 +192,30 @@ pub struct Capability {
     pub presign_stat: bool,
     /// If operator supports presign write.
     pub presign_write: bool,
+    /// If operator supports presign delete.
+    pub presign_delete: bool,
+    /// If operator supports presign upload part of a multipart upload.
+    pub presign_upload_part: bool,
+
+    /// If operator supports initiating, completing and aborting multipart uploads.
+    pub multipart: bool,
+
+    /// If operator supports updating content type, cache control and user
+    /// metadata of existing files in place without rewriting the content.
+    ///
+    /// This is provided by service extensions like `AzdlsPropertiesExt`
+    /// instead of `Operator`.
+    pub update_metadata: bool,
 
     /// If operator supports batch.
     pub batch: bool,
     /// If operator supports batch delete.
     pub batch_delete: bool,
+    /// If operator supports batch write, all writes in one batch will be
+    /// applied atomically.
+    pub batch_write: bool,
+    /// If operator supports batch stat.
+    pub batch_stat: bool,
     /// The max operations that operator supports in batch.
     pub batch_max_operations: Option<usize>,
 
@@ -72,10 +72,13 @@ impl WriteContext {
     /// Calculate the chunk size for this write process.
     ///
     /// Returns the chunk size and if the chunk size is exact.
+    ///
+    /// Services that require aligned chunks will always get exact chunks
+    /// rounded to the multiple of `write_multi_align_size`.
     fn calculate_chunk_size(&self) -> (Option<usize>, bool) {
         let cap = self.accessor().info().full_capability();
 
-        let exact = self.options().chunk().is_some();
+        let exact = self.options().chunk().is_some() || cap.write_multi_align_size.is_some();
         let chunk_size = self
             .options()
             .chunk()
@@ -87,6 +90,9 @@ impl WriteContext {
                 if let Some(v) = cap.write_multi_min_size {
                     size = size.max(v);
                 }
+                if let Some(v) = cap.write_multi_align_size {
+                    size = align_chunk_size(size, v, cap.write_multi_max_size);
+                }
 
                 size
             });
@@ -95,8 +101,23 @@ impl WriteContext {
     }
 }
 
+/// Round size up to the multiple of align, or down if it exceeds max.
+fn align_chunk_size(size: usize, align: usize, max: Option<usize>) -> usize {
+    if align == 0 {
+        return size;
+    }
+
+    let aligned = size.div_ceil(align) * align;
+    match max {
+        Some(max) if aligned > max => (max / align).max(1) * align,
+        _ => aligned,
+    }
+}
+
 pub struct WriteGenerator<W> {
     w: W,
+    /// The reply of `write`, counters in it are updated while writing.
+    rp: RpWrite,
 
     /// The size for buffer, we will flush the underlying storage at the size of this buffer.
     chunk_size: Option<usize>,
@@ -104,19 +125,42 @@ pub struct WriteGenerator<W> {
     /// exactly `chunk_size` bytes.
     exact: bool,
     buffer: oio::QueueBuf,
+
+    /// The size of data that has been accepted, used as the content length
+    /// of the written file if the service doesn't return it.
+    written: u64,
+    /// Appended file's size is unknown from the written data.
+    append: bool,
+}
+
+impl<W> WriteGenerator<W> {
+    /// Get the reply of `write`.
+    pub fn rp(&self) -> &RpWrite {
+        &self.rp
+    }
+
+    fn complete_metadata(&self, meta: Metadata) -> Metadata {
+        if self.append {
+            return meta;
+        }
+        oio::with_written_size(meta, self.written)
+    }
 }
 
 impl WriteGenerator<oio::Writer> {
     /// Create a new exact buf writer.
     pub async fn create(ctx: Arc<WriteContext>) -> Result<Self> {
         let (chunk_size, exact) = ctx.calculate_chunk_size();
-        let (_, w) = ctx.acc.write(ctx.path(), ctx.args().clone()).await?;
+        let (rp, w) = ctx.acc.write(ctx.path(), ctx.args().clone()).await?;
 
         Ok(Self {
             w,
+            rp,
             chunk_size,
             exact,
             buffer: oio::QueueBuf::new(),
+            written: 0,
+            append: ctx.args().append(),
         })
     }
 
@@ -125,16 +169,25 @@ impl WriteGenerator<oio::Writer> {
     fn new(w: oio::Writer, chunk_size: Option<usize>, exact: bool) -> Self {
         Self {
             w,
+            rp: RpWrite::default(),
             chunk_size,
             exact,
             buffer: oio::QueueBuf::new(),
+            written: 0,
+            append: false,
         }
     }
 }
 
 impl WriteGenerator<oio::Writer> {
     /// Write the entire buffer into writer.
-    pub async fn write(&mut self, mut bs: Buffer) -> Result<usize> {
+    pub async fn write(&mut self, bs: Buffer) -> Result<usize> {
+        let n = self.write_inner(bs).await?;
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    async fn write_inner(&mut self, mut bs: Buffer) -> Result<usize> {
         let Some(chunk_size) = self.chunk_size else {
             let size = bs.len();
             self.w.write_dyn(bs).await?;
@@ -184,7 +237,7 @@ impl WriteGenerator<oio::Writer> {
     }
 
     /// Finish the write process.
-    pub async fn close(&mut self) -> Result<()> {
+    pub async fn close(&mut self) -> Result<Metadata> {
         loop {
             if self.buffer.is_empty() {
                 break;
@@ -194,7 +247,8 @@ impl WriteGenerator<oio::Writer> {
             self.w.write_dyn(buf).await?;
         }
 
-        self.w.close().await
+        let meta = self.w.close().await?;
+        Ok(self.complete_metadata(meta))
     }
 
     /// Abort the write process.
@@ -208,20 +262,29 @@ impl WriteGenerator<oio::BlockingWriter> {
     /// Create a new exact buf writer.
     pub fn blocking_create(ctx: Arc<WriteContext>) -> Result<Self> {
         let (chunk_size, exact) = ctx.calculate_chunk_size();
-        let (_, w) = ctx.acc.blocking_write(ctx.path(), ctx.args().clone())?;
+        let (rp, w) = ctx.acc.blocking_write(ctx.path(), ctx.args().clone())?;
 
         Ok(Self {
             w,
+            rp,
             chunk_size,
             exact,
             buffer: oio::QueueBuf::new(),
+            written: 0,
+            append: ctx.args().append(),
         })
     }
 }
 
 impl WriteGenerator<oio::BlockingWriter> {
     /// Write the entire buffer into writer.
-    pub fn write(&mut self, mut bs: Buffer) -> Result<usize> {
+    pub fn write(&mut self, bs: Buffer) -> Result<usize> {
+        let n = self.write_inner(bs)?;
+        self.written += n as u64;
+        Ok(n)
+    }
+
+    fn write_inner(&mut self, mut bs: Buffer) -> Result<usize> {
         let Some(chunk_size) = self.chunk_size else {
             let size = bs.len();
             self.w.write(bs)?;
@@ -271,7 +334,7 @@ impl WriteGenerator<oio::BlockingWriter> {
     }
 
     /// Finish the write process.
-    pub fn close(&mut self) -> Result<()> {
+    pub fn close(&mut self) -> Result<Metadata> {
         loop {
             if self.buffer.is_empty() {
                 break;
@@ -281,7 +344,8 @@ impl WriteGenerator<oio::BlockingWriter> {
             self.w.write(buf)?;
         }
 
-        self.w.close()
+        let meta = self.w.close()?;
+        Ok(self.complete_metadata(meta))
     }
 }
 
@@ -302,6 +366,16 @@ mod tests {
     use super::*;
     use crate::raw::oio::Write;
 
+    #[test]
+    fn test_align_chunk_size() {
+        assert_eq!(align_chunk_size(1, 320, None), 320);
+        assert_eq!(align_chunk_size(320, 320, None), 320);
+        assert_eq!(align_chunk_size(321, 320, None), 640);
+        assert_eq!(align_chunk_size(1000, 320, Some(700)), 640);
+        assert_eq!(align_chunk_size(100, 320, Some(100)), 320);
+        assert_eq!(align_chunk_size(100, 0, None), 100);
+    }
+
     struct MockWriter {
         buf: Arc<Mutex<Vec<u8>>>,
     }
@@ -315,8 +389,8 @@ mod tests {
             Ok(())
         }
 
-        async fn close(&mut self) -> Result<()> {
-            Ok(())
+        async fn close(&mut self) -> Result<Metadata> {
+            Ok(Metadata::new(EntryMode::FILE))
         }
 
         async fn abort(&mut self) -> Result<()> {
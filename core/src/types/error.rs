@@ -41,12 +41,13 @@ use std::fmt::Debug;
 use std::fmt::Display;
 use std::fmt::Formatter;
 use std::io;
+use std::time::Duration;
 
 /// Result that is a wrapper of `Result<T, opendal::Error>`
 pub type Result<T, E = Error> = std::result::Result<T, E>;
 
 /// ErrorKind is all kinds of Error of opendal.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub enum ErrorKind {
     /// OpenDAL don't know what happened here, and no actions other than just
@@ -86,6 +87,34 @@ pub enum ErrorKind {
     ///
     /// OpenDAL returns this error to indicate that the range of the read request is not satisfied.
     RangeNotSatisfied,
+    /// The given path is locked by others.
+    ///
+    /// For example, WebDAV returns `423 Locked` if the resource is locked and the request
+    /// doesn't carry the lock token. Users should release the lock or provide the lock
+    /// token instead of retrying.
+    Locked,
+    /// The given path matches more than one entry.
+    ///
+    /// For example, Google Drive allows files with the same name in the same folder,
+    /// OpenDAL returns this error instead of picking one of them silently.
+    Ambiguous,
+    /// The given path is archived and can't be read before it's restored.
+    ///
+    /// For example, Azure Storage rejects reading blobs in `Archive` tier until
+    /// they are rehydrated to an online tier, which may take hours. Users should
+    /// trigger the restore instead of retrying.
+    Archived,
+    /// The content doesn't match its checksum.
+    ///
+    /// For example, the data written doesn't match the given `content_md5`,
+    /// or the data read doesn't match the checksum stored in metadata.
+    DataCorrupted,
+    /// The operation didn't finish in time.
+    ///
+    /// For example, `TimeoutLayer` returns this error if the operation or io
+    /// reaches its timeout. It's only temporary if the operation is safe to
+    /// retry.
+    Timeout,
 }
 
 impl ErrorKind {
@@ -116,6 +145,11 @@ impl From<ErrorKind> for &'static str {
             ErrorKind::IsSameFile => "IsSameFile",
             ErrorKind::ConditionNotMatch => "ConditionNotMatch",
             ErrorKind::RangeNotSatisfied => "RangeNotSatisfied",
+            ErrorKind::Locked => "Locked",
+            ErrorKind::Ambiguous => "Ambiguous",
+            ErrorKind::Archived => "Archived",
+            ErrorKind::DataCorrupted => "DataCorrupted",
+            ErrorKind::Timeout => "Timeout",
         }
     }
 }
@@ -220,6 +254,7 @@ pub struct Error {
     operation: &'static str,
     context: Vec<(&'static str, String)>,
     source: Option<anyhow::Error>,
+    retry_after: Option<Duration>,
     backtrace: Backtrace,
 }
 
@@ -264,6 +299,7 @@ impl Debug for Error {
             de.field("operation", &self.operation);
             de.field("context", &self.context);
             de.field("source", &self.source);
+            de.field("retry_after", &self.retry_after);
             return de.finish();
         }
 
@@ -312,6 +348,7 @@ impl Error {
             operation: "",
             context: Vec::default(),
             source: None,
+            retry_after: None,
             // `Backtrace::capture()` will check if backtrace has been enabled
             // internally. It's zero cost if backtrace is disabled.
             backtrace: Backtrace::capture(),
@@ -391,11 +428,25 @@ impl Error {
         self
     }
 
+    /// Set the delay suggested by services before retrying, like the
+    /// `Retry-After` header of `429 Too Many Requests` responses.
+    ///
+    /// `RetryLayer` will prefer it over its own backoff.
+    pub fn with_retry_after(mut self, retry_after: Duration) -> Self {
+        self.retry_after = Some(retry_after);
+        self
+    }
+
     /// Return error's kind.
     pub fn kind(&self) -> ErrorKind {
         self.kind
     }
 
+    /// Return the delay suggested by services before retrying.
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.retry_after
+    }
+
     /// Check if this error is temporary.
     pub fn is_temporary(&self) -> bool {
         self.status == ErrorStatus::Temporary
@@ -407,6 +458,7 @@ impl From<Error> for io::Error {
         let kind = match err.kind() {
             ErrorKind::NotFound => io::ErrorKind::NotFound,
             ErrorKind::PermissionDenied => io::ErrorKind::PermissionDenied,
+            ErrorKind::Timeout => io::ErrorKind::TimedOut,
             _ => io::ErrorKind::Other,
         };
 
@@ -432,6 +484,7 @@ mod tests {
             ("called", "send_async".to_string()),
         ],
         source: Some(anyhow!("networking error")),
+        retry_after: None,
         backtrace: Backtrace::disabled(),
     });
 
@@ -25,6 +25,8 @@ use std::task::Poll;
 use flagset::FlagSet;
 use futures::Stream;
 use futures::StreamExt;
+#[cfg(feature = "list-matching")]
+use regex::Regex;
 
 use crate::raw::*;
 use crate::*;
@@ -43,6 +45,8 @@ pub struct Lister {
     lister: Option<oio::Lister>,
     /// required_metakey is the metakey required by users.
     required_metakey: FlagSet<Metakey>,
+    /// matcher filters entries before their metadata is fetched.
+    matcher: Option<ListMatcher>,
 
     fut: Option<BoxedStaticFuture<(oio::Lister, Result<Option<oio::Entry>>)>>,
 
@@ -129,13 +133,16 @@ impl Lister {
     pub(crate) async fn create(acc: Accessor, path: &str, args: OpList) -> Result<Self> {
         let required_metakey = args.metakey();
         let concurrent = cmp::max(1, args.concurrent());
+        let matcher = ListMatcher::new(path, &args)?;
+        let path = list_path(path, &args);
 
-        let (_, lister) = acc.list(path, args).await?;
+        let (_, lister) = acc.list(&path, args).await?;
 
         Ok(Self {
             acc,
             lister: Some(lister),
             required_metakey,
+            matcher,
 
             fut: None,
             tasks: ConcurrentFutures::new(concurrent),
@@ -154,7 +161,10 @@ impl Stream for Lister {
         }
 
         // Trying to pull more tasks if there are more space.
-        if self.tasks.has_remaining() {
+        //
+        // Keep pulling while entries are filtered out by matcher, otherwise
+        // nothing will wake us up.
+        while self.tasks.has_remaining() {
             // Building future if we have a lister available.
             if let Some(mut lister) = self.lister.take() {
                 let fut = async move {
@@ -171,6 +181,12 @@ impl Stream for Lister {
 
                     match entry {
                         Ok(Some(oe)) => {
+                            if let Some(m) = &self.matcher {
+                                if !m.is_match(oe.path()) {
+                                    continue;
+                                }
+                            }
+
                             let (path, metadata) = oe.into_entry().into_parts();
                             if metadata.contains_metakey(self.required_metakey) {
                                 self.tasks
@@ -194,6 +210,7 @@ impl Stream for Lister {
                     }
                 }
             }
+            break;
         }
 
         // Try to poll tasks
@@ -221,6 +238,8 @@ pub struct BlockingLister {
     acc: Accessor,
     /// required_metakey is the metakey required by users.
     required_metakey: FlagSet<Metakey>,
+    /// matcher filters entries before their metadata is fetched.
+    matcher: Option<ListMatcher>,
 
     lister: oio::BlockingLister,
     errored: bool,
@@ -235,11 +254,15 @@ impl BlockingLister {
     /// Create a new lister.
     pub(crate) fn create(acc: Accessor, path: &str, args: OpList) -> Result<Self> {
         let required_metakey = args.metakey();
-        let (_, lister) = acc.blocking_list(path, args)?;
+        let matcher = ListMatcher::new(path, &args)?;
+        let path = list_path(path, &args);
+
+        let (_, lister) = acc.blocking_list(&path, args)?;
 
         Ok(Self {
             acc,
             required_metakey,
+            matcher,
 
             lister,
             errored: false,
@@ -257,12 +280,18 @@ impl Iterator for BlockingLister {
             return None;
         }
 
-        let entry = match self.lister.next() {
-            Ok(Some(entry)) => entry,
-            Ok(None) => return None,
-            Err(err) => {
-                self.errored = true;
-                return Some(Err(err));
+        let entry = loop {
+            let entry = match self.lister.next() {
+                Ok(Some(entry)) => entry,
+                Ok(None) => return None,
+                Err(err) => {
+                    self.errored = true;
+                    return Some(Err(err));
+                }
+            };
+            match &self.matcher {
+                Some(m) if !m.is_match(entry.path()) => continue,
+                _ => break entry,
             }
         };
 
@@ -282,6 +311,249 @@ impl Iterator for BlockingLister {
     }
 }
 
+/// ListMatcher filters entries by their path relative to the listed path.
+struct ListMatcher {
+    #[cfg(feature = "list-matching")]
+    base: String,
+    #[cfg(feature = "list-matching")]
+    glob: Option<Regex>,
+    #[cfg(feature = "list-matching")]
+    regex: Option<Regex>,
+}
+
+#[cfg(not(feature = "list-matching"))]
+impl ListMatcher {
+    /// Matching requires the `list-matching` feature, fail instead of
+    /// returning entries that are not filtered.
+    fn new(_: &str, args: &OpList) -> Result<Option<Self>> {
+        if args.matching_glob().is_none() && args.matching_regex().is_none() {
+            return Ok(None);
+        }
+
+        Err(Error::new(
+            ErrorKind::Unsupported,
+            "list with matching requires the list-matching feature",
+        )
+        .with_operation(Operation::List))
+    }
+
+    fn is_match(&self, _: &str) -> bool {
+        true
+    }
+}
+
+#[cfg(feature = "list-matching")]
+impl ListMatcher {
+    /// Build matcher from args, returns `None` if no filter is set.
+    fn new(path: &str, args: &OpList) -> Result<Option<Self>> {
+        if args.matching_glob().is_none() && args.matching_regex().is_none() {
+            return Ok(None);
+        }
+
+        let glob = args
+            .matching_glob()
+            .map(|v| {
+                Regex::new(&glob_to_regex(v)).map_err(|err| {
+                    Error::new(ErrorKind::ConfigInvalid, "glob pattern is invalid")
+                        .with_operation(Operation::List)
+                        .with_context("glob", v)
+                        .set_source(err)
+                })
+            })
+            .transpose()?;
+        let regex = args
+            .matching_regex()
+            .map(|v| {
+                Regex::new(v).map_err(|err| {
+                    Error::new(ErrorKind::ConfigInvalid, "regex is invalid")
+                        .with_operation(Operation::List)
+                        .with_context("regex", v)
+                        .set_source(err)
+                })
+            })
+            .transpose()?;
+
+        Ok(Some(Self {
+            base: list_base(path).to_string(),
+            glob,
+            regex,
+        }))
+    }
+
+    fn is_match(&self, path: &str) -> bool {
+        let rel = path.strip_prefix(&self.base).unwrap_or(path);
+        self.glob.as_ref().map_or(true, |v| v.is_match(rel))
+            && self.regex.as_ref().map_or(true, |v| v.is_match(rel))
+    }
+}
+
+/// The base that entry paths are relative to, root is `/` but entries
+/// under root don't start with it.
+#[cfg(feature = "list-matching")]
+fn list_base(path: &str) -> &str {
+    if path == "/" {
+        ""
+    } else {
+        path
+    }
+}
+
+/// Build the path to list, the dirs in the literal prefix of glob will be
+/// pushed down while listing a dir recursively.
+///
+/// Only whole dirs are pushed down, services like azdls can't list with a
+/// prefix that is not a dir.
+#[cfg(feature = "list-matching")]
+fn list_path(path: &str, args: &OpList) -> String {
+    let Some(glob) = args.matching_glob() else {
+        return path.to_string();
+    };
+    // Non-recursive list only returns entries in current dir, there is
+    // nothing to push down.
+    if !path.ends_with('/') || !args.recursive() {
+        return path.to_string();
+    }
+
+    let prefix = glob_literal_prefix(glob);
+    let prefix = prefix.rfind('/').map_or("", |idx| &prefix[..=idx]);
+    if prefix.is_empty() {
+        return path.to_string();
+    }
+
+    format!("{}{}", list_base(path), prefix)
+}
+
+#[cfg(not(feature = "list-matching"))]
+fn list_path(path: &str, _: &OpList) -> String {
+    path.to_string()
+}
+
+/// Returns the literal prefix of glob before the first special char.
+#[cfg(feature = "list-matching")]
+fn glob_literal_prefix(glob: &str) -> &str {
+    let idx = glob.find(['*', '?', '[', '{']).unwrap_or(glob.len());
+    &glob[..idx]
+}
+
+/// Translate glob into an anchored regex.
+#[cfg(feature = "list-matching")]
+fn glob_to_regex(glob: &str) -> String {
+    let mut re = String::from("^");
+    let mut in_group = false;
+    let mut chars = glob.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                // `**/` matches zero or more dirs.
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    re.push_str("(?:.*/)?");
+                } else {
+                    re.push_str(".*");
+                }
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            '[' => {
+                re.push('[');
+                if chars.peek() == Some(&'!') {
+                    chars.next();
+                    re.push('^');
+                }
+                for c in chars.by_ref() {
+                    if c == ']' {
+                        break;
+                    }
+                    if c == '\\' || c == '[' {
+                        re.push('\\');
+                    }
+                    re.push(c);
+                }
+                re.push(']');
+            }
+            '{' => {
+                in_group = true;
+                re.push_str("(?:");
+            }
+            ',' if in_group => re.push('|'),
+            '}' if in_group => {
+                in_group = false;
+                re.push(')');
+            }
+            c => re.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+
+    re.push('$');
+    re
+}
+
+#[cfg(test)]
+#[cfg(feature = "list-matching")]
+mod matcher_tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_to_regex() {
+        let cases = [
+            ("*.parquet", "a.parquet", true),
+            ("*.parquet", "a.csv", false),
+            ("*.parquet", "dir/a.parquet", false),
+            ("**/*.parquet", "a.parquet", true),
+            ("**/*.parquet", "dir/sub/a.parquet", true),
+            ("dir/**", "dir/sub/a", true),
+            ("file-?.txt", "file-1.txt", true),
+            ("file-?.txt", "file-10.txt", false),
+            ("file-[0-9].txt", "file-1.txt", true),
+            ("file-[!0-9].txt", "file-1.txt", false),
+            ("*.{csv,json}", "a.json", true),
+            ("*.{csv,json}", "a.parquet", false),
+            ("a+b(1).txt", "a+b(1).txt", true),
+        ];
+        for (glob, path, expected) in cases {
+            let re = Regex::new(&glob_to_regex(glob)).unwrap();
+            assert_eq!(re.is_match(path), expected, "glob {glob} on {path}");
+        }
+    }
+
+    #[test]
+    fn test_list_path() {
+        let args = OpList::new()
+            .with_matching_glob("2024-01/*.parquet")
+            .with_recursive(true);
+        assert_eq!(list_path("dir/", &args), "dir/2024-01/");
+        assert_eq!(list_path("/", &args), "2024-01/");
+        // Only whole dirs are pushed down.
+        let args = OpList::new()
+            .with_matching_glob("2024-01/data-*.parquet")
+            .with_recursive(true);
+        assert_eq!(list_path("dir/", &args), "dir/2024-01/");
+        let args = OpList::new()
+            .with_matching_glob("2024-*/*.parquet")
+            .with_recursive(true);
+        assert_eq!(list_path("dir/", &args), "dir/");
+        // Non-recursive and prefix list are kept as is.
+        let args = OpList::new().with_matching_glob("2024-01/*.parquet");
+        assert_eq!(list_path("dir/", &args), "dir/");
+        assert_eq!(list_path("dir/a", &args.with_recursive(true)), "dir/a");
+
+        assert_eq!(list_path("dir/", &OpList::new()), "dir/");
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        let args = OpList::new().with_matching_glob("[z-a]");
+        let err = ListMatcher::new("dir/", &args).err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+        let args = OpList::new().with_matching_regex("(");
+        let err = ListMatcher::new("dir/", &args).err().unwrap();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "services-azblob")]
 mod tests {
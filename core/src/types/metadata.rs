@@ -46,8 +46,17 @@ pub struct Metadata {
     content_type: Option<String>,
     etag: Option<String>,
     last_modified: Option<DateTime<Utc>>,
+    created: Option<DateTime<Utc>>,
+    permissions: Option<u32>,
+    uid: Option<u32>,
+    gid: Option<u32>,
     version: Option<String>,
     user_metadata: Option<HashMap<String, String>>,
+    /// Boxed since tags are rarely set, don't pay for them in every entry.
+    #[allow(clippy::box_collection)]
+    tags: Option<Box<HashMap<String, String>>>,
+    append_offset: Option<u64>,
+    storage_class: Option<String>,
 }
 
 impl Metadata {
@@ -71,10 +80,17 @@ impl Metadata {
             content_type: None,
             content_range: None,
             last_modified: None,
+            created: None,
+            permissions: None,
+            uid: None,
+            gid: None,
             etag: None,
             content_disposition: None,
             version: None,
             user_metadata: None,
+            tags: None,
+            append_offset: None,
+            storage_class: None,
         }
     }
 
@@ -214,6 +230,10 @@ impl Metadata {
     ///
     /// OpenDAL will try its best to set this value, but not guarantee this value is the md5 of content.
     ///
+    /// Unlike [`Metadata::etag`], which could change on metadata updates, this value only depends
+    /// on content. `None` means the hash is unavailable (for example, files uploaded by append or
+    /// multipart), please don't treat two entries without it as the same content.
+    ///
     /// This value is only available when calling on result of `stat` or `list` with
     /// [`Metakey::ContentMd5`], otherwise this method returns `None`.
     pub fn content_md5(&self) -> Option<&str> {
@@ -355,6 +375,111 @@ impl Metadata {
         self
     }
 
+    /// Creation time of this entry.
+    ///
+    /// Some services (like azdls) return the time when this entry was first
+    /// created besides the last modified time.
+    ///
+    /// OpenDAL parse the raw value into [`DateTime`] in UTC for convenient.
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::Created`], otherwise this method returns `None`.
+    pub fn created(&self) -> Option<DateTime<Utc>> {
+        #[cfg(feature = "tests")]
+        debug_assert!(
+            self.metakey.contains(Metakey::Created) || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: created, maybe a bug"
+        );
+
+        self.created
+    }
+
+    /// Set creation time of this entry.
+    pub fn set_created(&mut self, v: DateTime<Utc>) -> &mut Self {
+        self.created = Some(v);
+        self.metakey |= Metakey::Created;
+        self
+    }
+
+    /// Set creation time of this entry.
+    pub fn with_created(mut self, v: DateTime<Utc>) -> Self {
+        self.created = Some(v);
+        self.metakey |= Metakey::Created;
+        self
+    }
+
+    /// POSIX permission bits of this entry, like `0o644`.
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::Permissions`], otherwise this method returns `None`.
+    pub fn permissions(&self) -> Option<u32> {
+        #[cfg(feature = "tests")]
+        debug_assert!(
+            self.metakey.contains(Metakey::Permissions) || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: permissions, maybe a bug"
+        );
+
+        self.permissions
+    }
+
+    /// Set POSIX permission bits of this entry.
+    pub fn set_permissions(&mut self, v: u32) -> &mut Self {
+        self.permissions = Some(v);
+        self.metakey |= Metakey::Permissions;
+        self
+    }
+
+    /// Set POSIX permission bits of this entry.
+    pub fn with_permissions(mut self, v: u32) -> Self {
+        self.permissions = Some(v);
+        self.metakey |= Metakey::Permissions;
+        self
+    }
+
+    /// The user id of this entry's owner.
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::Owner`], otherwise this method returns `None`.
+    pub fn uid(&self) -> Option<u32> {
+        #[cfg(feature = "tests")]
+        debug_assert!(
+            self.metakey.contains(Metakey::Owner) || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: uid, maybe a bug"
+        );
+
+        self.uid
+    }
+
+    /// The group id of this entry's owner.
+    ///
+    /// This value is only available when calling on result of `stat` or `list` with
+    /// [`Metakey::Owner`], otherwise this method returns `None`.
+    pub fn gid(&self) -> Option<u32> {
+        #[cfg(feature = "tests")]
+        debug_assert!(
+            self.metakey.contains(Metakey::Owner) || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: gid, maybe a bug"
+        );
+
+        self.gid
+    }
+
+    /// Set the user id and group id of this entry's owner.
+    pub fn set_owner(&mut self, uid: u32, gid: u32) -> &mut Self {
+        self.uid = Some(uid);
+        self.gid = Some(gid);
+        self.metakey |= Metakey::Owner;
+        self
+    }
+
+    /// Set the user id and group id of this entry's owner.
+    pub fn with_owner(mut self, uid: u32, gid: u32) -> Self {
+        self.uid = Some(uid);
+        self.gid = Some(gid);
+        self.metakey |= Metakey::Owner;
+        self
+    }
+
     /// ETag of this entry.
     ///
     /// `ETag` is defined by [RFC 7232](https://httpwg.org/specs/rfc7232.html#header.etag)
@@ -526,6 +651,98 @@ impl Metadata {
         self.metakey |= Metakey::UserMetaData;
         self
     }
+
+    /// Tags of this entry, like blob index tags in Azure Storage.
+    ///
+    /// Tags are different from user defined metadata: they are stored
+    /// separately and need to be fetched by an extra request in most services.
+    ///
+    /// This value is only available when calling on result of `stat` with
+    /// [`Metakey::Tags`], otherwise this method returns `None`.
+    pub fn tags(&self) -> Option<&HashMap<String, String>> {
+        #[cfg(feature = "tests")]
+        debug_assert!(
+            self.metakey.contains(Metakey::Tags) || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: tags, maybe a bug"
+        );
+
+        self.tags.as_deref()
+    }
+
+    /// Set tags of this entry.
+    pub fn set_tags(&mut self, v: HashMap<String, String>) -> &mut Self {
+        self.tags = Some(Box::new(v));
+        self.metakey |= Metakey::Tags;
+        self
+    }
+
+    /// Set tags of this entry.
+    pub fn with_tags(mut self, v: HashMap<String, String>) -> Self {
+        self.tags = Some(Box::new(v));
+        self.metakey |= Metakey::Tags;
+        self
+    }
+
+    /// The offset at which the appended data starts.
+    ///
+    /// This value is only available on the metadata returned by an append
+    /// write on services that support
+    /// [`Capability::write_returns_append_offset`], and the `content_length`
+    /// will be the length of the object after the append.
+    pub fn append_offset(&self) -> Option<u64> {
+        #[cfg(feature = "tests")]
+        debug_assert!(
+            self.metakey.contains(Metakey::AppendOffset)
+                || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: append_offset, maybe a bug"
+        );
+
+        self.append_offset
+    }
+
+    /// Set the offset at which the appended data starts.
+    pub fn set_append_offset(&mut self, v: u64) -> &mut Self {
+        self.append_offset = Some(v);
+        self.metakey |= Metakey::AppendOffset;
+        self
+    }
+
+    /// Set the offset at which the appended data starts.
+    pub fn with_append_offset(mut self, v: u64) -> Self {
+        self.append_offset = Some(v);
+        self.metakey |= Metakey::AppendOffset;
+        self
+    }
+
+    /// Storage class of this entry, like `STANDARD` or `GLACIER` in S3 and
+    /// `Hot`, `Cool` or `Archive` access tier in Azure Storage.
+    ///
+    /// The value is returned as is by the service. `None` means the service
+    /// doesn't report it, callers should treat it as the default class.
+    pub fn storage_class(&self) -> Option<&str> {
+        #[cfg(feature = "tests")]
+        debug_assert!(
+            self.metakey.contains(Metakey::StorageClass)
+                || self.metakey.contains(Metakey::Complete),
+            "visiting not set metadata: storage_class, maybe a bug"
+        );
+
+        self.storage_class.as_deref()
+    }
+
+    /// Set storage class of this entry.
+    pub fn set_storage_class(&mut self, v: &str) -> &mut Self {
+        self.storage_class = Some(v.to_string());
+        self.metakey |= Metakey::StorageClass;
+        self
+    }
+
+    /// Set storage class of this entry.
+    pub fn with_storage_class(mut self, v: &str) -> Self {
+        self.storage_class = Some(v.to_string());
+        self.metakey |= Metakey::StorageClass;
+        self
+    }
 }
 
 flags! {
@@ -565,9 +782,31 @@ flags! {
         Etag,
         /// Key for last modified.
         LastModified,
+        /// Key for creation time.
+        Created,
+        /// Key for POSIX permission bits.
+        Permissions,
+        /// Key for owner's uid and gid.
+        Owner,
         /// Key for version.
         Version,
         /// Key for user metadata
         UserMetaData,
+        /// Key for tags.
+        Tags,
+        /// Key for append offset.
+        AppendOffset,
+        /// Key for storage class.
+        StorageClass,
     }
 }
+
+/// MetadataDirective decides which metadata the target of `copy` gets.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum MetadataDirective {
+    /// Copy metadata from the source.
+    #[default]
+    Copy,
+    /// Replace metadata with the ones given in the copy operation.
+    Replace,
+}
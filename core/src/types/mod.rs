@@ -26,6 +26,7 @@ pub use entry::Entry;
 
 mod metadata;
 pub use metadata::Metadata;
+pub use metadata::MetadataDirective;
 pub use metadata::Metakey;
 
 mod read;
@@ -40,6 +41,10 @@ pub use write::*;
 mod blocking_write;
 pub use blocking_write::*;
 
+mod multipart;
+pub use multipart::MultipartUpload;
+pub use multipart::UploadedPart;
+
 mod list;
 pub use list::BlockingLister;
 pub use list::Lister;
@@ -0,0 +1,79 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+/// MultipartUpload is an on-going multipart upload started by
+/// [`Operator::initiate_multipart`][crate::Operator::initiate_multipart].
+///
+/// Parts can be uploaded by anyone holding the URLs returned by
+/// [`Operator::presign_upload_part`][crate::Operator::presign_upload_part],
+/// the file will be visible after
+/// [`Operator::complete_multipart`][crate::Operator::complete_multipart].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MultipartUpload {
+    path: String,
+    upload_id: String,
+}
+
+impl MultipartUpload {
+    /// Create a new multipart upload.
+    pub fn new(path: &str, upload_id: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            upload_id: upload_id.to_string(),
+        }
+    }
+
+    /// Get the path of this upload.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// Get the id of this upload assigned by services.
+    pub fn upload_id(&self) -> &str {
+        &self.upload_id
+    }
+}
+
+/// UploadedPart is a part that has been uploaded to a [`MultipartUpload`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadedPart {
+    part_number: usize,
+    etag: String,
+}
+
+impl UploadedPart {
+    /// Create a new uploaded part.
+    ///
+    /// `part_number` starts from 1, `etag` is the `ETag` header returned by
+    /// the upload request.
+    pub fn new(part_number: usize, etag: &str) -> Self {
+        Self {
+            part_number,
+            etag: etag.to_string(),
+        }
+    }
+
+    /// Get the part number of this part.
+    pub fn part_number(&self) -> usize {
+        self.part_number
+    }
+
+    /// Get the etag of this part.
+    pub fn etag(&self) -> &str {
+        &self.etag
+    }
+}
@@ -15,6 +15,9 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::sync::Arc;
+
+use super::operator::OperatorDefaults;
 use super::operator_functions::*;
 use crate::raw::*;
 use crate::*;
@@ -76,6 +79,8 @@ pub struct BlockingOperator {
     accessor: Accessor,
 
     limit: usize,
+    /// The default options inherited from [`Operator`].
+    defaults: Arc<OperatorDefaults>,
 }
 
 impl BlockingOperator {
@@ -93,7 +98,17 @@ impl BlockingOperator {
             .full_capability()
             .batch_max_operations
             .unwrap_or(1000);
-        Self { accessor, limit }
+        Self {
+            accessor,
+            limit,
+            defaults: Arc::default(),
+        }
+    }
+
+    /// Specify the default options that explicit options are applied on top of.
+    pub(super) fn with_defaults(mut self, defaults: Arc<OperatorDefaults>) -> Self {
+        self.defaults = defaults;
+        self
     }
 
     /// Get current operator's limit
@@ -269,26 +284,36 @@ impl BlockingOperator {
 
     /// Check if this path exists or not.
     ///
+    /// # Notes
+    ///
+    /// Services may check the existence without fetching the metadata, it's
+    /// cheaper than [`BlockingOperator::stat`] on kv services.
+    ///
+    /// Only `NotFound` is treated as non-existence, other errors like
+    /// `PermissionDenied` will be returned as is.
+    ///
     /// # Example
     ///
     /// ```no_run
     /// use anyhow::Result;
     /// use opendal::BlockingOperator;
     /// fn test(op: BlockingOperator) -> Result<()> {
-    ///     let _ = op.is_exist("test")?;
+    ///     let _ = op.exists("test")?;
     ///
     ///     Ok(())
     /// }
     /// ```
+    pub fn exists(&self, path: &str) -> Result<bool> {
+        let path = normalize_path(path);
+
+        self.inner().blocking_exists(&path, OpStat::default())
+    }
+
+    /// Check if this path exists or not.
+    ///
+    /// This is an alias of [`BlockingOperator::exists`].
     pub fn is_exist(&self, path: &str) -> Result<bool> {
-        let r = self.stat(path);
-        match r {
-            Ok(_) => Ok(true),
-            Err(err) => match err.kind() {
-                ErrorKind::NotFound => Ok(false),
-                _ => Err(err),
-            },
-        }
+        self.exists(path)
     }
 
     /// Create a dir at given path.
@@ -377,7 +402,7 @@ impl BlockingOperator {
         FunctionRead(OperatorFunction::new(
             self.inner().clone(),
             path,
-            (OpRead::default(), BytesRange::default()),
+            (self.defaults.read.clone(), BytesRange::default()),
             |inner, path, (args, range)| {
                 if !validate_path(&path, EntryMode::FILE) {
                     return Err(
@@ -436,7 +461,7 @@ impl BlockingOperator {
         FunctionReader(OperatorFunction::new(
             self.inner().clone(),
             path,
-            OpRead::default(),
+            self.defaults.read.clone(),
             |inner, path, args| {
                 if !validate_path(&path, EntryMode::FILE) {
                     return Err(
@@ -473,7 +498,7 @@ impl BlockingOperator {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn write(&self, path: &str, bs: impl Into<Buffer>) -> Result<()> {
+    pub fn write(&self, path: &str, bs: impl Into<Buffer>) -> Result<Metadata> {
         self.write_with(path, bs).call()
     }
 
@@ -622,7 +647,7 @@ impl BlockingOperator {
         FunctionWrite(OperatorFunction::new(
             self.inner().clone(),
             path,
-            (OpWrite::default(), OpWriter::default(), bs),
+            (self.defaults.write.clone(), OpWriter::default(), bs),
             |inner, path, (args, options, bs)| {
                 if !validate_path(&path, EntryMode::FILE) {
                     return Err(
@@ -636,8 +661,7 @@ impl BlockingOperator {
                 let context = WriteContext::new(inner, path, args, options);
                 let mut w = BlockingWriter::new(context)?;
                 w.write(bs)?;
-                w.close()?;
-                Ok(())
+                w.close()
             },
         ))
     }
@@ -692,7 +716,7 @@ impl BlockingOperator {
         FunctionWriter(OperatorFunction::new(
             self.inner().clone(),
             path,
-            (OpWrite::default(), OpWriter::default()),
+            (self.defaults.write.clone(), OpWriter::default()),
             |inner, path, (args, options)| {
                 let path = normalize_path(&path);
 
@@ -1003,7 +1027,7 @@ impl BlockingOperator {
         FunctionList(OperatorFunction::new(
             self.inner().clone(),
             path,
-            OpList::default(),
+            self.defaults.list.clone(),
             |inner, path, args| {
                 let lister = BlockingLister::create(inner, &path, args)?;
 
@@ -1166,7 +1190,7 @@ impl BlockingOperator {
         FunctionLister(OperatorFunction::new(
             self.inner().clone(),
             path,
-            OpList::default(),
+            self.defaults.list.clone(),
             |inner, path, args| BlockingLister::create(inner, &path, args),
         ))
     }
@@ -1174,6 +1198,8 @@ impl BlockingOperator {
 
 impl From<BlockingOperator> for Operator {
     fn from(v: BlockingOperator) -> Self {
-        Operator::from_inner(v.accessor).with_limit(v.limit)
+        Operator::from_inner(v.accessor)
+            .with_limit(v.limit)
+            .with_defaults(v.defaults)
     }
 }
@@ -406,9 +406,11 @@ impl Operator {
     /// ```
     #[must_use]
     pub fn layer<L: Layer<Accessor>>(self, layer: L) -> Self {
+        let defaults = self.defaults();
         Self::from_inner(Arc::new(
             TypeEraseLayer.layer(layer.layer(self.into_inner())),
         ))
+        .with_defaults(defaults)
     }
 }
 
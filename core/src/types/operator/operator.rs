@@ -15,13 +15,19 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
 use std::future::Future;
+use std::sync::Arc;
 use std::time::Duration;
 
+use base64::engine::general_purpose;
+use base64::Engine;
 use futures::stream;
 use futures::Stream;
 use futures::StreamExt;
 use futures::TryStreamExt;
+use http::HeaderMap;
+use md5::Digest;
 
 use super::BlockingOperator;
 use crate::operator_futures::*;
@@ -65,6 +71,17 @@ pub struct Operator {
     limit: usize,
     /// The default executor that used to run futures in background.
     default_executor: Option<Executor>,
+    /// The default options that explicit options are applied on top of.
+    defaults: Arc<OperatorDefaults>,
+}
+
+/// The default options of operations, shared by [`Operator`] and
+/// [`BlockingOperator`].
+#[derive(Clone, Debug, Default)]
+pub(super) struct OperatorDefaults {
+    pub(super) read: OpRead,
+    pub(super) write: OpWrite,
+    pub(super) list: OpList,
 }
 
 /// # Operator basic API.
@@ -83,6 +100,7 @@ impl Operator {
             accessor,
             limit,
             default_executor: None,
+            defaults: Arc::default(),
         }
     }
 
@@ -117,6 +135,84 @@ impl Operator {
         op
     }
 
+    /// Specify the default options for `read`, `read_with`, `reader` and `reader_with`.
+    ///
+    /// Options set on each call take precedence over the defaults field by field,
+    /// so the defaults are only used for the fields that are not set explicitly.
+    /// Layers will see the merged options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use opendal::raw::OpRead;
+    /// use opendal::Operator;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let op = op.with_default_read_options(OpRead::new().with_override_cache_control("no-cache"));
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_default_read_options(&self, args: OpRead) -> Self {
+        let mut op = self.clone();
+        Arc::make_mut(&mut op.defaults).read = args;
+        op
+    }
+
+    /// Specify the default options for `write`, `write_with`, `writer`, `writer_with`
+    /// and `write_batch`.
+    ///
+    /// Options set on each call take precedence over the defaults field by field,
+    /// so the defaults are only used for the fields that are not set explicitly.
+    /// Layers will see the merged options.
+    ///
+    /// # Notes
+    ///
+    /// The defaults are not applied to presign operations, since the signed
+    /// headers must be sent by the client as is.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// use opendal::raw::OpWrite;
+    /// use opendal::Operator;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let op = op.with_default_write_options(OpWrite::new().with_cache_control("max-age=3600"));
+    /// // Written with `cache-control: max-age=3600`.
+    /// op.write("a.txt", "hello").await?;
+    /// // Explicit options beat the defaults.
+    /// op.write_with("b.txt", "hello").cache_control("no-cache").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_default_write_options(&self, args: OpWrite) -> Self {
+        let mut op = self.clone();
+        Arc::make_mut(&mut op.defaults).write = args;
+        op
+    }
+
+    /// Specify the default options for `list`, `list_with`, `lister` and `lister_with`.
+    ///
+    /// Options set on each call take precedence over the defaults field by field,
+    /// so the defaults are only used for the fields that are not set explicitly.
+    /// Layers will see the merged options.
+    pub fn with_default_list_options(&self, args: OpList) -> Self {
+        let mut op = self.clone();
+        Arc::make_mut(&mut op.defaults).list = args;
+        op
+    }
+
+    pub(super) fn defaults(&self) -> Arc<OperatorDefaults> {
+        self.defaults.clone()
+    }
+
+    pub(super) fn with_defaults(mut self, defaults: Arc<OperatorDefaults>) -> Self {
+        self.defaults = defaults;
+        self
+    }
+
     /// Get information of underlying accessor.
     ///
     /// # Examples
@@ -139,7 +235,9 @@ impl Operator {
     ///
     /// This operation is nearly no cost.
     pub fn blocking(&self) -> BlockingOperator {
-        BlockingOperator::from_inner(self.accessor.clone()).with_limit(self.limit)
+        BlockingOperator::from_inner(self.accessor.clone())
+            .with_limit(self.limit)
+            .with_defaults(self.defaults.clone())
     }
 }
 
@@ -329,6 +427,14 @@ impl Operator {
 
     /// Check if this path exists or not.
     ///
+    /// # Notes
+    ///
+    /// Services may check the existence without fetching the metadata, it's
+    /// cheaper than [`Operator::stat`] on kv services.
+    ///
+    /// Only `NotFound` is treated as non-existence, other errors like
+    /// `PermissionDenied` will be returned as is.
+    ///
     /// # Example
     ///
     /// ```
@@ -337,20 +443,103 @@ impl Operator {
     /// use opendal::Operator;
     ///
     /// async fn test(op: Operator) -> Result<()> {
-    ///     let _ = op.is_exist("test").await?;
+    ///     let _ = op.exists("test").await?;
     ///
     ///     Ok(())
     /// }
     /// ```
+    pub async fn exists(&self, path: &str) -> Result<bool> {
+        let path = normalize_path(path);
+
+        self.inner().exists(&path, OpStat::default()).await
+    }
+
+    /// Check if this path exists or not.
+    ///
+    /// This is an alias of [`Operator::exists`].
     pub async fn is_exist(&self, path: &str) -> Result<bool> {
-        let r = self.stat(path).await;
-        match r {
-            Ok(_) => Ok(true),
-            Err(err) => match err.kind() {
-                ErrorKind::NotFound => Ok(false),
-                _ => Err(err),
+        self.exists(path).await
+    }
+
+    /// Get metadata of multiple paths at once.
+    ///
+    /// Results are returned in the same order as the input paths, failures
+    /// like `NotFound` are returned in place instead of failing the whole
+    /// call.
+    ///
+    /// # Notes
+    ///
+    /// If underlying services support stat in batch, we will use batch stat
+    /// with chunks of [`Operator::limit`]. Otherwise, paths will be stated
+    /// concurrently with at most 16 requests in flight, or
+    /// [`Operator::limit`] if it's smaller. Use [`Operator::stat_many_with`]
+    /// to change it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// #
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let paths = vec!["abc".to_string(), "def".to_string()];
+    /// for (path, meta) in paths.iter().zip(op.stat_many(paths.clone()).await) {
+    ///     match meta {
+    ///         Ok(meta) => println!("{path}: {}", meta.content_length()),
+    ///         Err(err) => println!("{path}: {err}"),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn stat_many(&self, paths: Vec<String>) -> Vec<Result<Metadata>> {
+        let concurrent = self.limit().clamp(1, STAT_MANY_DEFAULT_CONCURRENT);
+        stat_many(self.inner().clone(), paths, concurrent, self.limit()).await
+    }
+
+    /// Get metadata of multiple paths at once with extra options.
+    ///
+    /// The outer result only fails if the options are invalid, failures of
+    /// every path are returned in place like [`Operator::stat_many`].
+    ///
+    /// # Options
+    ///
+    /// Visit [`FutureStatMany`] for all available options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// #
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let paths = vec!["abc".to_string(), "def".to_string()];
+    /// let metas = op.stat_many_with(paths).concurrent(64).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stat_many_with(
+        &self,
+        paths: Vec<String>,
+    ) -> FutureStatMany<impl Future<Output = Result<Vec<Result<Metadata>>>>> {
+        let concurrent = self.limit().clamp(1, STAT_MANY_DEFAULT_CONCURRENT);
+
+        OperatorFuture::new(
+            self.inner().clone(),
+            String::new(),
+            (paths, concurrent, self.limit()),
+            |inner, _, (paths, concurrent, limit)| async move {
+                if concurrent == 0 {
+                    return Err(Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "concurrent of stat_many must be larger than 0",
+                    )
+                    .with_operation("Operator::stat_many"));
+                }
+
+                Ok(stat_many(inner, paths, concurrent, limit).await)
             },
-        }
+        )
     }
 
     /// Create a dir at given path.
@@ -552,7 +741,10 @@ impl Operator {
             self.inner().clone(),
             path,
             (
-                OpRead::default().merge_executor(self.default_executor.clone()),
+                self.defaults
+                    .read
+                    .clone()
+                    .merge_executor(self.default_executor.clone()),
                 OpReader::default(),
             ),
             |inner, path, (args, options)| async move {
@@ -565,6 +757,10 @@ impl Operator {
                     );
                 }
 
+                if options.verify_checksum() {
+                    return read_with_checksum(inner, path, args, options).await;
+                }
+
                 let range = args.range();
                 let context = ReadContext::new(inner, path, args, options);
                 let r = Reader::new(context);
@@ -599,6 +795,34 @@ impl Operator {
         self.reader_with(path).await
     }
 
+    /// Read the whole file at given path into [`futures::AsyncWrite`], returns
+    /// the total bytes written.
+    ///
+    /// # Notes
+    ///
+    /// Data is streamed in chunks without buffering the whole file in memory.
+    /// To read with options like `chunk` and `concurrent`, or to track the
+    /// progress, please use [`Operator::reader_with`] and
+    /// [`Reader::read_to_writer_with_progress`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut w = futures::io::Cursor::new(Vec::new());
+    /// let n = op.read_to_writer("path/to/file", &mut w).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_to_writer<W>(&self, path: &str, writer: &mut W) -> Result<u64>
+    where
+        W: futures::AsyncWrite + Unpin + ?Sized,
+    {
+        self.reader(path).await?.read_to_writer(.., writer).await
+    }
+
     /// Create a new reader with extra options
     ///
     /// # Notes
@@ -668,7 +892,10 @@ impl Operator {
             self.inner().clone(),
             path,
             (
-                OpRead::default().merge_executor(self.default_executor.clone()),
+                self.defaults
+                    .read
+                    .clone()
+                    .merge_executor(self.default_executor.clone()),
                 OpReader::default(),
             ),
             |inner, path, (args, options)| async move {
@@ -708,6 +935,10 @@ impl Operator {
     /// handle the multipart uploads for you. You can control the behavior of multipart uploads
     /// by setting `chunk`, `concurrent` via [`Operator::writer_with`]
     ///
+    /// ## Returned Metadata
+    ///
+    /// The metadata of the written file will be returned, see [`Writer::close`] for details.
+    ///
     /// # Examples
     ///
     /// ```no_run
@@ -722,7 +953,7 @@ impl Operator {
     /// # Ok(())
     /// # }
     /// ```
-    pub async fn write(&self, path: &str, bs: impl Into<Buffer>) -> Result<()> {
+    pub async fn write(&self, path: &str, bs: impl Into<Buffer>) -> Result<Metadata> {
         let bs = bs.into();
         self.write_with(path, bs).await
     }
@@ -748,41 +979,135 @@ impl Operator {
     /// # }
     /// ```
     pub async fn copy(&self, from: &str, to: &str) -> Result<()> {
+        self.copy_with(from, to).await
+    }
+
+    /// Copy a file from `from` to `to` with extra options.
+    ///
+    /// Options not supported by the service will return
+    /// [`ErrorKind::Unsupported`] instead of being ignored, check
+    /// [`Capability`] before use.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// use opendal::MetadataDirective;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.copy_with("path/to/file", "path/to/file2")
+    ///     .metadata_directive(MetadataDirective::Replace)
+    ///     .content_type("application/json")
+    ///     .if_not_exists(true)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn copy_with(&self, from: &str, to: &str) -> FutureCopy<impl Future<Output = Result<()>>> {
         let from = normalize_path(from);
+        let to = normalize_path(to);
 
-        if !validate_path(&from, EntryMode::FILE) {
-            return Err(
-                Error::new(ErrorKind::IsADirectory, "from path is a directory")
-                    .with_operation("Operator::copy")
-                    .with_context("service", self.info().scheme())
-                    .with_context("from", from),
-            );
-        }
+        OperatorFuture::new(
+            self.inner().clone(),
+            from,
+            (OpCopy::default(), to),
+            |inner, from, (args, to)| async move {
+                if !validate_path(&from, EntryMode::FILE) {
+                    return Err(
+                        Error::new(ErrorKind::IsADirectory, "from path is a directory")
+                            .with_operation("Operator::copy")
+                            .with_context("service", inner.info().scheme())
+                            .with_context("from", from),
+                    );
+                }
 
-        let to = normalize_path(to);
+                if !validate_path(&to, EntryMode::FILE) {
+                    return Err(
+                        Error::new(ErrorKind::IsADirectory, "to path is a directory")
+                            .with_operation("Operator::copy")
+                            .with_context("service", inner.info().scheme())
+                            .with_context("to", to),
+                    );
+                }
 
-        if !validate_path(&to, EntryMode::FILE) {
-            return Err(
-                Error::new(ErrorKind::IsADirectory, "to path is a directory")
-                    .with_operation("Operator::copy")
-                    .with_context("service", self.info().scheme())
-                    .with_context("to", to),
-            );
-        }
+                if from == to {
+                    return Err(
+                        Error::new(ErrorKind::IsSameFile, "from and to paths are same")
+                            .with_operation("Operator::copy")
+                            .with_context("service", inner.info().scheme())
+                            .with_context("from", from)
+                            .with_context("to", to),
+                    );
+                }
 
-        if from == to {
-            return Err(
-                Error::new(ErrorKind::IsSameFile, "from and to paths are same")
+                if args.metadata_directive() == Some(MetadataDirective::Copy)
+                    && args.replace_metadata()
+                {
+                    return Err(Error::new(
+                        ErrorKind::ConfigInvalid,
+                        "content type and cache control can't be set while copying metadata",
+                    )
                     .with_operation("Operator::copy")
-                    .with_context("service", self.info().scheme())
-                    .with_context("from", from)
-                    .with_context("to", to),
-            );
-        }
+                    .with_context("service", inner.info().scheme()));
+                }
 
-        self.inner().copy(&from, &to, OpCopy::new()).await?;
+                inner.copy(&from, &to, args).await?;
+                Ok(())
+            },
+        )
+    }
 
-        Ok(())
+    /// Copy a file from `from` of current operator to `to` of another operator.
+    ///
+    /// # Notes
+    ///
+    /// - `from` and `to` must be a file.
+    /// - `to` will be overwritten if it exists.
+    /// - Content type and user metadata of `from` will be carried to `to`
+    ///   if the target supports them.
+    /// - If [`FutureCopyTo::native_copy`] is enabled and both operators have
+    ///   the same scheme, name and root, native [`Operator::copy`] will be
+    ///   used instead of streaming.
+    /// - Otherwise, data is streamed from source to target, the target will
+    ///   be written with multipart upload if supported, and will be aborted
+    ///   if the transfer failed.
+    ///
+    /// # Options
+    ///
+    /// Visit [`FutureCopyTo`] for all available options.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    ///
+    /// # async fn test(src: Operator, dst: Operator) -> Result<()> {
+    /// src.copy_to(&dst, "path/to/file", "path/to/file2")
+    ///     .chunk(8 * 1024 * 1024)
+    ///     .concurrent(4)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn copy_to(
+        &self,
+        other: &Operator,
+        from: &str,
+        to: &str,
+    ) -> FutureCopyTo<impl Future<Output = Result<()>>> {
+        let from = normalize_path(from);
+        let to = normalize_path(to);
+
+        OperatorFuture::new(
+            self.inner().clone(),
+            from,
+            (OpCopyTo::default(), self.clone(), other.clone(), to),
+            |_, from, (args, from_op, to_op, to)| async move {
+                copy_to(from_op, from, args, to_op, to).await
+            },
+        )
     }
 
     /// Rename a file from `from` to `to`.
@@ -913,6 +1238,32 @@ impl Operator {
     /// # }
     /// ```
     ///
+    /// ## `position`
+    ///
+    /// Set `position` for the append writer.
+    ///
+    /// Services with position based append (like `azdls`) will continue writing from the given
+    /// position directly instead of fetching the current offset. Errors returned by these writers
+    /// carry the last flushed position in context `flushed_position`, users can persist it and
+    /// resume an interrupted upload later.
+    ///
+    /// ```no_run
+    /// # use opendal::Result;
+    /// # use opendal::Operator;
+    /// use bytes::Bytes;
+    ///
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut w = op
+    ///     .writer_with("path/to/file")
+    ///     .append(true)
+    ///     .position(8192)
+    ///     .await?;
+    /// w.write(vec![2; 4096]).await?;
+    /// w.close().await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
     /// ## `chunk`
     ///
     /// Set `chunk` for the writer.
@@ -1080,7 +1431,10 @@ impl Operator {
             self.inner().clone(),
             path,
             (
-                OpWrite::default().merge_executor(self.default_executor.clone()),
+                self.defaults
+                    .write
+                    .clone()
+                    .merge_executor(self.default_executor.clone()),
                 OpWriter::default(),
             ),
             |inner, path, (args, options)| async move {
@@ -1221,7 +1575,7 @@ impl Operator {
         &self,
         path: &str,
         bs: impl Into<Buffer>,
-    ) -> FutureWrite<impl Future<Output = Result<()>>> {
+    ) -> FutureWrite<impl Future<Output = Result<Metadata>>> {
         let path = normalize_path(path);
         let bs = bs.into();
 
@@ -1229,7 +1583,10 @@ impl Operator {
             self.inner().clone(),
             path,
             (
-                OpWrite::default().merge_executor(self.default_executor.clone()),
+                self.defaults
+                    .write
+                    .clone()
+                    .merge_executor(self.default_executor.clone()),
                 OpWriter::default(),
                 bs,
             ),
@@ -1246,8 +1603,7 @@ impl Operator {
                 let context = WriteContext::new(inner, path, args, options);
                 let mut w = Writer::new(context).await?;
                 w.write(bs).await?;
-                w.close().await?;
-                Ok(())
+                w.close().await
             },
         )
     }
@@ -1386,6 +1742,70 @@ impl Operator {
         Ok(())
     }
 
+    /// Write multiple files in one batch.
+    ///
+    /// All writes will be applied atomically: either all of them succeed or
+    /// none of them is visible.
+    ///
+    /// # Notes
+    ///
+    /// Only services with `batch_write` capability (like `etcd`)
+    /// support this. Batches larger than `batch_max_operations` will be
+    /// rejected instead of being split, since splitting breaks atomicity.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.write_batch(vec![
+    ///     ("manifest".to_string(), "v2".into()),
+    ///     ("data/v2".to_string(), vec![0; 4096].into()),
+    /// ])
+    /// .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn write_batch(&self, entries: Vec<(String, Buffer)>) -> Result<()> {
+        let cap = self.info().full_capability();
+        if !cap.batch_write {
+            return Err(
+                Error::new(ErrorKind::Unsupported, "operation is not supported")
+                    .with_operation("Operator::write_batch")
+                    .with_context("service", self.info().scheme()),
+            );
+        }
+        if let Some(max) = cap.batch_max_operations {
+            if entries.len() > max {
+                return Err(Error::new(
+                    ErrorKind::Unsupported,
+                    "batch write exceeds the max operations of service",
+                )
+                .with_operation("Operator::write_batch")
+                .with_context("service", self.info().scheme())
+                .with_context("size", entries.len().to_string())
+                .with_context("batch_max_operations", max.to_string()));
+            }
+        }
+
+        let ops = entries
+            .into_iter()
+            .map(|(path, bs)| {
+                (
+                    normalize_path(&path),
+                    (self.defaults.write.clone(), bs).into(),
+                )
+            })
+            .collect();
+        let results = self.inner().batch(OpBatch::new(ops)).await?.into_results();
+        for (_, result) in results {
+            let _ = result?;
+        }
+
+        Ok(())
+    }
+
     /// Remove the path and all nested dirs and files recursively.
     ///
     /// # Notes
@@ -1406,56 +1826,45 @@ impl Operator {
     /// # }
     /// ```
     pub async fn remove_all(&self, path: &str) -> Result<()> {
-        match self.stat(path).await {
-            // If object exists.
-            Ok(metadata) => {
-                // If the object is a file, we can delete it.
-                if metadata.mode() != EntryMode::DIR {
-                    self.delete(path).await?;
-                    // There may still be objects prefixed with the path in some backend, so we can't return here.
-                }
-            }
-
-            // If dir not found, it may be a prefix in object store like S3,
-            // and we still need to delete objects under the prefix.
-            Err(e) if e.kind() == ErrorKind::NotFound => {}
+        self.remove_all_with(path).await
+    }
 
-            // Pass on any other error.
-            Err(e) => return Err(e),
-        };
-
-        let obs = self.lister_with(path).recursive(true).await?;
-
-        if self.info().full_capability().batch {
-            let mut obs = obs.try_chunks(self.limit());
-
-            while let Some(batches) = obs.next().await {
-                let batches = batches
-                    .map_err(|err| err.1)?
-                    .into_iter()
-                    .map(|v| (v.path().to_string(), OpDelete::default().into()))
-                    .collect();
-
-                let results = self
-                    .inner()
-                    .batch(OpBatch::new(batches))
-                    .await?
-                    .into_results();
-
-                // TODO: return error here directly seems not a good idea?
-                for (_, result) in results {
-                    let _ = result?;
-                }
-            }
-        } else {
-            obs.try_for_each(|v| async move { self.delete(v.path()).await })
-                .await?;
-        }
-
-        // Remove the directory itself.
-        self.delete(path).await?;
+    /// Remove the path and all nested dirs and files recursively with
+    /// extra options.
+    ///
+    /// Files are removed while listing, at most `concurrent` batches of them
+    /// are kept in memory instead of the whole listing. Dirs can only be
+    /// removed after their nested entries, so the paths of all dirs are
+    /// kept in memory until the listing is done and removed at last.
+    ///
+    /// # Options
+    ///
+    /// Visit [`FutureRemoveAll`] for all available options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use anyhow::Result;
+    /// # use opendal::Operator;
+    /// #
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// op.remove_all_with("path/to/dir")
+    ///     .concurrent(8)
+    ///     .continue_on_error(true)
+    ///     .progress(|entries, bytes| println!("removed {entries} entries, {bytes} bytes"))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn remove_all_with(&self, path: &str) -> FutureRemoveAll<impl Future<Output = Result<()>>> {
+        let path = normalize_path(path);
 
-        Ok(())
+        OperatorFuture::new(
+            self.inner().clone(),
+            path,
+            (OpRemoveAll::default(), self.limit()),
+            |inner, path, (args, limit)| async move { remove_all(inner, path, args, limit).await },
+        )
     }
 
     /// List entries that starts with given `path` in parent dir.
@@ -1698,7 +2107,7 @@ impl Operator {
         OperatorFuture::new(
             self.inner().clone(),
             path,
-            OpList::default(),
+            self.defaults.list.clone(),
             |inner, path, args| async move {
                 let lister = Lister::create(inner, &path, args).await?;
 
@@ -1909,7 +2318,7 @@ impl Operator {
         OperatorFuture::new(
             self.inner().clone(),
             path,
-            OpList::default(),
+            self.defaults.list.clone(),
             |inner, path, args| async move { Lister::create(inner, &path, args).await },
         )
     }
@@ -2215,6 +2624,36 @@ impl Operator {
     ///     Ok(())
     /// }
     /// ```
+    ///
+    /// ## `signed_header`
+    ///
+    /// Include the header in the signature so that the uploader can't change it. All
+    /// headers returned by [`PresignedRequest::header`] must be sent verbatim.
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use anyhow::Result;
+    /// use http::header::CONTENT_TYPE;
+    /// use http::HeaderValue;
+    /// use opendal::Operator;
+    ///
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     let signed_req = op
+    ///         .presign_write_with("test", Duration::from_secs(3600))
+    ///         .signed_header(CONTENT_TYPE, HeaderValue::from_static("image/png"))
+    ///         .await?;
+    ///     let mut req = http::Request::builder()
+    ///         .method(signed_req.method())
+    ///         .uri(signed_req.uri());
+    ///     for (k, v) in signed_req.header() {
+    ///         req = req.header(k, v);
+    ///     }
+    ///     let req = req.body(())?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
     pub fn presign_write_with(
         &self,
         path: &str,
@@ -2225,12 +2664,759 @@ impl Operator {
         OperatorFuture::new(
             self.inner().clone(),
             path,
-            (OpWrite::default(), expire),
-            |inner, path, (args, dur)| async move {
-                let op = OpPresign::new(args, dur);
+            (OpWrite::default(), expire, HeaderMap::new()),
+            |inner, path, (args, dur, headers)| async move {
+                let op = OpPresign::new(args, dur).with_signed_headers(headers);
                 let rp = inner.presign(&path, op).await?;
                 Ok(rp.into_presigned_request())
             },
         )
     }
+
+    /// Presign an operation for delete.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use anyhow::Result;
+    /// use opendal::Operator;
+    ///
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     let signed_req = op
+    ///         .presign_delete("test.txt", Duration::from_secs(3600))
+    ///         .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    ///
+    /// - `signed_req.method()`: `DELETE`
+    /// - `signed_req.uri()`: `https://s3.amazonaws.com/examplebucket/test.txt?X-Amz-Algorithm=AWS4-HMAC-SHA256&X-Amz-Credential=access_key_id/20130721/us-east-1/s3/aws4_request&X-Amz-Date=20130721T201207Z&X-Amz-Expires=86400&X-Amz-SignedHeaders=host&X-Amz-Signature=<signature-value>`
+    /// - `signed_req.headers()`: `{ "host": "s3.amazonaws.com" }`
+    pub async fn presign_delete(&self, path: &str, expire: Duration) -> Result<PresignedRequest> {
+        let path = normalize_path(path);
+
+        let op = OpPresign::new(OpDelete::new(), expire);
+        let rp = self.inner().presign(&path, op).await?;
+        Ok(rp.into_presigned_request())
+    }
+
+    /// Initiate a multipart upload so that its parts can be uploaded via
+    /// presigned requests.
+    ///
+    /// Require [`Capability::multipart`] and [`Capability::presign_upload_part`]
+    /// to upload parts without credentials.
+    ///
+    /// # Notes
+    ///
+    /// - The file is invisible until [`Self::complete_multipart`] is called.
+    /// - Services have their own limits on parts, for example, every part of
+    ///   s3 except the last one must be at least 5MiB.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use std::time::Duration;
+    ///
+    /// use anyhow::Result;
+    /// use opendal::Operator;
+    /// use opendal::UploadedPart;
+    ///
+    /// async fn test(op: Operator) -> Result<()> {
+    ///     let upload = op.initiate_multipart("test.txt").await?;
+    ///
+    ///     let signed_req = op
+    ///         .presign_upload_part(
+    ///             upload.path(),
+    ///             upload.upload_id(),
+    ///             1,
+    ///             Duration::from_secs(3600),
+    ///         )
+    ///         .await?;
+    ///     // Send the part to `signed_req.uri()` and keep the returned etag.
+    ///     let etag = "\"etag-of-part-1\"";
+    ///
+    ///     op.complete_multipart(
+    ///         upload.path(),
+    ///         upload.upload_id(),
+    ///         vec![UploadedPart::new(1, etag)],
+    ///     )
+    ///     .await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn initiate_multipart(&self, path: &str) -> Result<MultipartUpload> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "upload path is a directory")
+                    .with_operation("Operator::initiate_multipart")
+                    .with_context("service", self.inner().info().scheme())
+                    .with_context("path", &path),
+            );
+        }
+
+        let rp = self
+            .inner()
+            .initiate_multipart(&path, OpWrite::default())
+            .await?;
+        Ok(MultipartUpload::new(&path, rp.upload_id()))
+    }
+
+    /// Presign an operation for uploading a part of a multipart upload
+    /// started by [`Self::initiate_multipart`].
+    ///
+    /// `part_number` starts from 1, the `ETag` header returned by the upload
+    /// request must be kept to complete the upload.
+    ///
+    /// - `signed_req.method()`: `PUT`
+    /// - `signed_req.uri()`: `https://s3.amazonaws.com/examplebucket/test.txt?partNumber=1&uploadId=<upload-id>&X-Amz-Algorithm=AWS4-HMAC-SHA256&...&X-Amz-Signature=<signature-value>`
+    pub async fn presign_upload_part(
+        &self,
+        path: &str,
+        upload_id: &str,
+        part_number: usize,
+        expire: Duration,
+    ) -> Result<PresignedRequest> {
+        let path = normalize_path(path);
+
+        if part_number == 0 {
+            return Err(
+                Error::new(ErrorKind::ConfigInvalid, "part number starts from 1")
+                    .with_operation("Operator::presign_upload_part")
+                    .with_context("service", self.inner().info().scheme())
+                    .with_context("path", &path),
+            );
+        }
+
+        let op = OpPresign::new(OpUploadPart::new(upload_id, part_number), expire);
+        let rp = self.inner().presign(&path, op).await?;
+        Ok(rp.into_presigned_request())
+    }
+
+    /// Complete a multipart upload started by [`Self::initiate_multipart`],
+    /// the given parts will be concatenated in order.
+    ///
+    /// Returns the metadata of the completed file.
+    pub async fn complete_multipart(
+        &self,
+        path: &str,
+        upload_id: &str,
+        parts: Vec<UploadedPart>,
+    ) -> Result<Metadata> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "upload path is a directory")
+                    .with_operation("Operator::complete_multipart")
+                    .with_context("service", self.inner().info().scheme())
+                    .with_context("path", &path),
+            );
+        }
+
+        let rp = self
+            .inner()
+            .complete_multipart(&path, OpCompleteMultipart::new(upload_id, parts))
+            .await?;
+        Ok(rp.into_metadata())
+    }
+
+    /// Abort a multipart upload started by [`Self::initiate_multipart`],
+    /// parts uploaded so far will be discarded.
+    ///
+    /// # Notes
+    ///
+    /// Services without upload sessions like `azblob` can't delete uploaded
+    /// parts explicitly, they will be cleaned up by the service later.
+    pub async fn abort_multipart(&self, path: &str, upload_id: &str) -> Result<()> {
+        let path = normalize_path(path);
+
+        if !validate_path(&path, EntryMode::FILE) {
+            return Err(
+                Error::new(ErrorKind::IsADirectory, "upload path is a directory")
+                    .with_operation("Operator::abort_multipart")
+                    .with_context("service", self.inner().info().scheme())
+                    .with_context("path", &path),
+            );
+        }
+
+        self.inner()
+            .abort_multipart(&path, OpAbortMultipart::new(upload_id))
+            .await?;
+        Ok(())
+    }
+}
+
+/// Read the whole file and verify it against the MD5 stored in metadata.
+async fn read_with_checksum(
+    acc: Accessor,
+    path: String,
+    mut args: OpRead,
+    options: OpReader,
+) -> Result<Buffer> {
+    if !args.range().is_full() {
+        return Err(Error::new(
+            ErrorKind::ConfigInvalid,
+            "verify_checksum can't be used with range read",
+        )
+        .with_operation("read")
+        .with_context("service", acc.info().scheme())
+        .with_context("path", &path));
+    }
+
+    let mut op_stat = OpStat::new();
+    if let Some(v) = args.version() {
+        op_stat = op_stat.with_version(v);
+    }
+    if let Some(v) = args.snapshot() {
+        op_stat = op_stat.with_snapshot(v);
+    }
+    if let Some(v) = args.if_match() {
+        op_stat = op_stat.with_if_match(v);
+    }
+    let meta = acc.stat(&path, op_stat).await?.into_metadata();
+    let Some(expected) = meta.content_md5() else {
+        return Err(
+            Error::new(ErrorKind::Unsupported, "stored checksum is not available")
+                .with_operation("read")
+                .with_context("service", acc.info().scheme())
+                .with_context("path", &path),
+        );
+    };
+
+    // Make sure the content read is the same version as stated.
+    if args.if_match().is_none() && args.version().is_none() {
+        if let Some(etag) = meta
+            .etag()
+            .filter(|_| acc.info().full_capability().read_with_if_match)
+        {
+            args = args.with_if_match(etag);
+        }
+    }
+
+    let context = ReadContext::new(acc.clone(), path.clone(), args, options);
+    let buf = Reader::new(context).read(..).await?;
+
+    let mut hasher = md5::Md5::new();
+    for bs in buf.clone() {
+        hasher.update(&bs);
+    }
+    let digest = hasher.finalize();
+    // Services store content md5 in either base64 or hex.
+    let actual = general_purpose::STANDARD.encode(digest);
+    let actual_hex = format!("{digest:x}");
+    if expected != actual && !expected.eq_ignore_ascii_case(&actual_hex) {
+        return Err(
+            Error::new(ErrorKind::DataCorrupted, "content doesn't match stored md5")
+                .with_operation("read")
+                .with_context("service", acc.info().scheme())
+                .with_context("path", &path)
+                .with_context("expected", expected)
+                .with_context("actual", actual),
+        );
+    }
+    Ok(buf)
+}
+
+/// Copy `from` of `from_op` to `to` of `to_op`.
+///
+/// Use native copy if users opted in and both operators look like the same
+/// storage, otherwise stream the content from source to target.
+async fn copy_to(
+    from_op: Operator,
+    from: String,
+    args: OpCopyTo,
+    to_op: Operator,
+    to: String,
+) -> Result<()> {
+    for (path, op, name) in [(&from, &from_op, "from"), (&to, &to_op, "to")] {
+        if !validate_path(path, EntryMode::FILE) {
+            return Err(Error::new(
+                ErrorKind::IsADirectory,
+                format!("{name} path is a directory"),
+            )
+            .with_operation("Operator::copy_to")
+            .with_context("service", op.info().scheme())
+            .with_context(name, path));
+        }
+    }
+
+    let (from_info, to_info) = (from_op.info(), to_op.info());
+    // Users have to opt-in native copy, since endpoint and account are not
+    // visible here, this is only a sanity check.
+    let same_storage = from_info.scheme() == to_info.scheme()
+        && from_info.name() == to_info.name()
+        && from_info.root() == to_info.root();
+    if args.native_copy() && same_storage && from_info.full_capability().copy {
+        return from_op.copy(&from, &to).await;
+    }
+
+    let meta = from_op.stat(&from).await?;
+    let read_cap = from_info.full_capability();
+    let write_cap = to_info.full_capability();
+
+    let mut reader = from_op.reader_with(&from).concurrent(args.concurrent());
+    if let Some(chunk) = args.chunk() {
+        reader = reader.chunk(chunk);
+    }
+    // Make sure all chunks are read from the same version of source.
+    if let Some(etag) = meta.etag().filter(|_| read_cap.read_with_if_match) {
+        reader = reader.if_match(etag);
+    }
+    let reader = reader.await?;
+
+    let mut writer = to_op.writer_with(&to).concurrent(args.concurrent());
+    if let Some(chunk) = args.chunk() {
+        writer = writer.chunk(chunk);
+    }
+    if let Some(content_type) = meta
+        .content_type()
+        .filter(|_| write_cap.write_with_content_type)
+    {
+        writer = writer.content_type(content_type);
+    }
+    if let Some(user_metadata) = meta
+        .user_metadata()
+        .filter(|_| write_cap.write_with_user_metadata)
+    {
+        writer = writer.user_metadata(user_metadata.clone());
+    }
+    let writer = writer.await?;
+
+    // The writer will be aborted by `pipe_to` if the transfer failed.
+    reader.pipe_to(0..meta.content_length(), writer).await?;
+    Ok(())
+}
+
+/// Max failed paths to carry in the error of `remove_all`.
+const REMOVE_ALL_MAX_FAILED_PATHS: usize = 10;
+
+/// The default max stat requests in flight of [`Operator::stat_many`].
+const STAT_MANY_DEFAULT_CONCURRENT: usize = 16;
+
+async fn stat_many(
+    acc: Accessor,
+    paths: Vec<String>,
+    concurrent: usize,
+    limit: usize,
+) -> Vec<Result<Metadata>> {
+    let paths: Vec<_> = paths.iter().map(|v| normalize_path(v)).collect();
+    let limit = limit.max(1);
+
+    if !acc.info().full_capability().batch_stat {
+        return stream::iter(paths)
+            .map(|path| {
+                let acc = acc.clone();
+                async move {
+                    acc.stat(&path, OpStat::default())
+                        .await
+                        .map(|rp| rp.into_metadata())
+                }
+            })
+            .buffered(concurrent)
+            .collect()
+            .await;
+    }
+
+    let mut results = Vec::with_capacity(paths.len());
+    for chunk in paths.chunks(limit) {
+        let ops = chunk
+            .iter()
+            .map(|v| (v.clone(), OpStat::default().into()))
+            .collect();
+        let replies = match acc.batch(OpBatch::new(ops)).await {
+            Ok(rp) => rp.into_results(),
+            Err(err) => {
+                // Error is not cloneable, every path in this chunk gets
+                // an error with the same kind and message.
+                let message = err.to_string();
+                results.extend(chunk.iter().map(|_| {
+                    let e = Error::new(err.kind(), &message);
+                    Err(if err.is_temporary() {
+                        e.set_temporary()
+                    } else {
+                        e
+                    })
+                }));
+                continue;
+            }
+        };
+
+        // Services must reply in the same order of the batch, fail the
+        // whole chunk if they didn't.
+        if replies.len() != chunk.len() || replies.iter().zip(chunk).any(|((path, _), v)| path != v)
+        {
+            results.extend(chunk.iter().map(|_| {
+                Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "batch stat replies don't match the requested paths",
+                )
+                .with_operation("Operator::stat_many")
+                .with_context("service", acc.info().scheme()))
+            }));
+            continue;
+        }
+
+        results.extend(replies.into_iter().map(|(_, reply)| {
+            match reply? {
+                BatchedReply::Stat(rp) => Ok(rp.into_metadata()),
+                _ => Err(Error::new(
+                    ErrorKind::Unexpected,
+                    "batch stat got reply of other operation",
+                )
+                .with_operation("Operator::stat_many")),
+            }
+        }));
+    }
+
+    results
+}
+
+async fn remove_all(acc: Accessor, path: String, args: OpRemoveAll, limit: usize) -> Result<()> {
+    match acc.stat(&path, OpStat::new()).await {
+        // If the object is a file, we can delete it.
+        Ok(rp) => {
+            if rp.into_metadata().mode() != EntryMode::DIR {
+                acc.delete(&path, OpDelete::new()).await?;
+                // There may still be objects prefixed with the path in some backend, so we can't return here.
+            }
+        }
+        // If dir not found, it may be a prefix in object store like S3,
+        // and we still need to delete objects under the prefix.
+        Err(e) if e.kind() == ErrorKind::NotFound => {}
+        Err(e) => return Err(e),
+    }
+
+    let cap = acc.info().full_capability();
+    let batch_size = if cap.batch {
+        cap.batch_max_operations
+            .map_or(limit, |v| v.min(limit))
+            .max(1)
+    } else {
+        1
+    };
+
+    let mut tasks = Lister::create(acc.clone(), &path, OpList::new().with_recursive(true))
+        .await?
+        .try_chunks(batch_size)
+        .map(|entries| {
+            let acc = acc.clone();
+            async move {
+                let entries = entries.map_err(|err| err.1)?;
+                // Dirs can't be removed before their nested entries on services
+                // like fs, remove them after all files have been removed.
+                let (dirs, files): (Vec<_>, Vec<_>) =
+                    entries.into_iter().partition(|v| v.metadata().is_dir());
+                let results = remove_entries(&acc, files, cap.batch).await;
+                Ok::<_, Error>((results, dirs))
+            }
+        })
+        .buffer_unordered(args.concurrent().max(1));
+
+    let mut removed = 0;
+    let mut freed = 0;
+    let mut failures = Vec::new();
+    let mut record = |results: Vec<(String, u64, Result<()>)>| -> Result<()> {
+        for (path, size, result) in results {
+            match result {
+                Ok(()) => {
+                    removed += 1;
+                    freed += size;
+                }
+                Err(err) if args.continue_on_error() => failures.push((path, err)),
+                Err(err) => return Err(err),
+            }
+        }
+        if let Some(f) = args.progress() {
+            f(removed, freed);
+        }
+        Ok(())
+    };
+
+    // Listings are not ordered and batches finish out of order, so a dir
+    // can't be known as empty until all files have been removed.
+    let mut dirs = Vec::new();
+    while let Some(res) = tasks.next().await {
+        let (results, ds) = res?;
+        record(results)?;
+        dirs.extend(ds.into_iter().map(|v| v.path().to_string()));
+    }
+
+    // Remove nested dirs first and the path itself at last.
+    dirs.sort_by_key(|v| std::cmp::Reverse(v.matches('/').count()));
+    dirs.push(path.clone());
+    for dir in dirs {
+        let result = acc.delete(&dir, OpDelete::new()).await.map(|_| ());
+        record(vec![(dir, 0, result)])?;
+    }
+
+    if failures.is_empty() {
+        return Ok(());
+    }
+
+    let failed = failures.len();
+    let kind = failures[0].1.kind();
+    let kind = if failures.iter().all(|(_, err)| err.kind() == kind) {
+        kind
+    } else {
+        ErrorKind::Unexpected
+    };
+    let mut failures = failures.into_iter();
+    let (first_path, first_err) = failures.next().expect("failures must not be empty");
+    let mut err = Error::new(kind, format!("failed to remove {failed} entries"))
+        .with_operation("Operator::remove_all")
+        .with_context("path", &path)
+        .with_context("failed_path", first_path);
+    for (path, _) in failures.take(REMOVE_ALL_MAX_FAILED_PATHS - 1) {
+        err = err.with_context("failed_path", path);
+    }
+    Err(err.set_source(first_err))
+}
+
+/// Remove the given entries, returns the path, size and result for each of them.
+async fn remove_entries(
+    acc: &Accessor,
+    entries: Vec<Entry>,
+    batch: bool,
+) -> Vec<(String, u64, Result<()>)> {
+    let entries: Vec<_> = entries
+        .into_iter()
+        .map(|v| {
+            // Only count the size returned by list, fetching it is not worth.
+            let size = if v.metadata().metakey().contains(Metakey::ContentLength) {
+                v.metadata().content_length()
+            } else {
+                0
+            };
+            (v.path().to_string(), size)
+        })
+        .collect();
+
+    if !batch || entries.len() <= 1 {
+        let mut results = Vec::with_capacity(entries.len());
+        for (path, size) in entries {
+            let result = acc.delete(&path, OpDelete::new()).await.map(|_| ());
+            results.push((path, size, result));
+        }
+        return results;
+    }
+
+    let ops = entries
+        .iter()
+        .map(|(path, _)| (path.clone(), OpDelete::new().into()))
+        .collect();
+    match acc.batch(OpBatch::new(ops)).await {
+        Ok(rp) => {
+            let sizes: HashMap<_, _> = entries.into_iter().collect();
+            rp.into_results()
+                .into_iter()
+                .map(|(path, result)| {
+                    let size = sizes.get(&path).copied().unwrap_or_default();
+                    (path, size, result.map(|_| ()))
+                })
+                .collect()
+        }
+        // The whole batch failed, report it on the first entry and mark
+        // others as failed as well.
+        Err(err) => {
+            let mut entries = entries.into_iter();
+            let mut results = Vec::new();
+            if let Some((path, size)) = entries.next() {
+                results.push((path, size, Err(err)));
+            }
+            for (path, size) in entries {
+                let err = Error::new(ErrorKind::Unexpected, "batch delete failed")
+                    .with_operation("Operator::remove_all");
+                results.push((path, size, Err(err)));
+            }
+            results
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fmt::Debug;
+    use std::sync::atomic::AtomicUsize;
+    use std::sync::atomic::Ordering;
+    use std::sync::Mutex;
+    use std::time::Duration;
+
+    use super::*;
+    use crate::services::Memory;
+
+    /// Record the args that layers see.
+    #[derive(Clone, Default)]
+    struct RecordLayer {
+        writes: Arc<Mutex<Vec<OpWrite>>>,
+        reads: Arc<Mutex<Vec<OpRead>>>,
+        lists: Arc<Mutex<Vec<OpList>>>,
+        stats_in_flight: Arc<AtomicUsize>,
+        max_stats_in_flight: Arc<AtomicUsize>,
+    }
+
+    impl<A: Access> Layer<A> for RecordLayer {
+        type LayeredAccess = RecordAccessor<A>;
+
+        fn layer(&self, inner: A) -> Self::LayeredAccess {
+            RecordAccessor {
+                inner,
+                layer: self.clone(),
+            }
+        }
+    }
+
+    struct RecordAccessor<A> {
+        inner: A,
+        layer: RecordLayer,
+    }
+
+    impl<A: Access> Debug for RecordAccessor<A> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.debug_struct("RecordAccessor").finish_non_exhaustive()
+        }
+    }
+
+    impl<A: Access> LayeredAccess for RecordAccessor<A> {
+        type Inner = A;
+        type Reader = A::Reader;
+        type BlockingReader = A::BlockingReader;
+        type Writer = A::Writer;
+        type BlockingWriter = A::BlockingWriter;
+        type Lister = A::Lister;
+        type BlockingLister = A::BlockingLister;
+
+        fn inner(&self) -> &Self::Inner {
+            &self.inner
+        }
+
+        async fn stat(&self, path: &str, args: OpStat) -> Result<RpStat> {
+            let n = self.layer.stats_in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.layer
+                .max_stats_in_flight
+                .fetch_max(n, Ordering::SeqCst);
+            // Keep the stat in flight long enough to overlap with others.
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            let res = self.inner.stat(path, args).await;
+            self.layer.stats_in_flight.fetch_sub(1, Ordering::SeqCst);
+            res
+        }
+
+        async fn read(&self, path: &str, args: OpRead) -> Result<(RpRead, Self::Reader)> {
+            self.layer.reads.lock().unwrap().push(args.clone());
+            self.inner.read(path, args).await
+        }
+
+        async fn write(&self, path: &str, args: OpWrite) -> Result<(RpWrite, Self::Writer)> {
+            self.layer.writes.lock().unwrap().push(args.clone());
+            self.inner.write(path, args).await
+        }
+
+        async fn list(&self, path: &str, args: OpList) -> Result<(RpList, Self::Lister)> {
+            self.layer.lists.lock().unwrap().push(args.clone());
+            self.inner.list(path, args).await
+        }
+
+        fn blocking_read(
+            &self,
+            path: &str,
+            args: OpRead,
+        ) -> Result<(RpRead, Self::BlockingReader)> {
+            self.layer.reads.lock().unwrap().push(args.clone());
+            self.inner.blocking_read(path, args)
+        }
+
+        fn blocking_write(
+            &self,
+            path: &str,
+            args: OpWrite,
+        ) -> Result<(RpWrite, Self::BlockingWriter)> {
+            self.layer.writes.lock().unwrap().push(args.clone());
+            self.inner.blocking_write(path, args)
+        }
+
+        fn blocking_list(
+            &self,
+            path: &str,
+            args: OpList,
+        ) -> Result<(RpList, Self::BlockingLister)> {
+            self.layer.lists.lock().unwrap().push(args.clone());
+            self.inner.blocking_list(path, args)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_default_options() -> Result<()> {
+        let layer = RecordLayer::default();
+        let op = Operator::new(Memory::default())?
+            .finish()
+            .layer(layer.clone())
+            .with_default_read_options(OpRead::new().with_override_content_type("text/plain"))
+            .with_default_write_options(
+                OpWrite::new()
+                    .with_cache_control("max-age=3600")
+                    .with_content_type("text/plain"),
+            )
+            .with_default_list_options(OpList::new().with_recursive(true));
+
+        op.write("a.txt", "hello").await?;
+        op.write_with("b.txt", "hello")
+            .cache_control("no-cache")
+            .await?;
+        {
+            let writes = layer.writes.lock().unwrap();
+            assert_eq!(writes[0].cache_control(), Some("max-age=3600"));
+            assert_eq!(writes[0].content_type(), Some("text/plain"));
+            // Explicit option wins, other fields still come from defaults.
+            assert_eq!(writes[1].cache_control(), Some("no-cache"));
+            assert_eq!(writes[1].content_type(), Some("text/plain"));
+        }
+
+        op.read("a.txt").await?;
+        op.read_with("a.txt").if_none_match("etag").await?;
+        {
+            let reads = layer.reads.lock().unwrap();
+            assert_eq!(reads[0].override_content_type(), Some("text/plain"));
+            assert_eq!(reads[1].override_content_type(), Some("text/plain"));
+            assert_eq!(reads[1].if_none_match(), Some("etag"));
+        }
+
+        op.list("/").await?;
+        op.list_with("/").recursive(false).await?;
+        {
+            let lists = layer.lists.lock().unwrap();
+            assert!(lists[0].recursive());
+            assert!(!lists[1].recursive());
+        }
+
+        // Defaults are kept for blocking operator and new layers.
+        let op = op.layer(RecordLayer::default());
+        op.blocking().write("c.txt", "hello")?;
+        let writes = layer.writes.lock().unwrap();
+        assert_eq!(writes[2].cache_control(), Some("max-age=3600"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_stat_many_with_concurrent() -> Result<()> {
+        let layer = RecordLayer::default();
+        let op = Operator::new(Memory::default())?
+            .finish()
+            .layer(layer.clone());
+        op.write("3", "hello").await?;
+
+        let paths: Vec<_> = (0..16).map(|i| i.to_string()).collect();
+        let results = op.stat_many_with(paths.clone()).concurrent(4).await?;
+        assert_eq!(results.len(), 16);
+        assert_eq!(results[3].as_ref().unwrap().content_length(), 5);
+        assert_eq!(results[0].as_ref().unwrap_err().kind(), ErrorKind::NotFound);
+        assert_eq!(layer.max_stats_in_flight.load(Ordering::SeqCst), 4);
+
+        let err = op.stat_many_with(paths).concurrent(0).await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+        Ok(())
+    }
 }
@@ -21,6 +21,8 @@
 
 use std::ops::RangeBounds;
 
+use chrono::DateTime;
+use chrono::Utc;
 use flagset::FlagSet;
 
 use crate::raw::*;
@@ -71,7 +73,7 @@ impl<T, R> OperatorFunction<T, R> {
 pub struct FunctionWrite(
     /// The args for FunctionWrite is a bit special because we also
     /// need to move the bytes input this function.
-    pub(crate) OperatorFunction<(OpWrite, OpWriter, Buffer), ()>,
+    pub(crate) OperatorFunction<(OpWrite, OpWriter, Buffer), Metadata>,
 );
 
 impl FunctionWrite {
@@ -137,7 +139,7 @@ impl FunctionWrite {
 
     /// Call the function to consume all the input and generate a
     /// result.
-    pub fn call(self) -> Result<()> {
+    pub fn call(self) -> Result<Metadata> {
         self.0.call()
     }
 }
@@ -290,6 +292,59 @@ impl FunctionList {
         self
     }
 
+    /// Only return entries whose path relative to the listed path matches
+    /// the glob pattern, like `*.parquet` or `**/2024-*/*.csv`.
+    ///
+    /// The filter runs before fetching the metadata, so excluded entries never
+    /// trigger extra `stat` calls. While listing recursively, dirs in the
+    /// literal prefix of the glob are pushed down to the list path.
+    ///
+    /// Requires the `list-matching` feature.
+    #[cfg(feature = "list-matching")]
+    pub fn matching_glob(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_matching_glob(v));
+        self
+    }
+
+    /// Only return entries whose path relative to the listed path matches
+    /// the regex.
+    ///
+    /// The regex is not anchored, use `^` and `$` to match the whole path.
+    ///
+    /// Requires the `list-matching` feature.
+    #[cfg(feature = "list-matching")]
+    pub fn matching_regex(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_matching_regex(v));
+        self
+    }
+
+    /// Return entries in lexicographical order of their paths.
+    ///
+    /// Services with [`Capability::list_ordered`] return entries in order
+    /// natively. For other services, `Unsupported` error will be returned
+    /// unless [`Self::sort_unordered`] is enabled.
+    ///
+    /// Default to `false`.
+    pub fn ordered(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_ordered(v));
+        self
+    }
+
+    /// Sort entries in memory if [`Self::ordered`] is requested but the
+    /// service can't list in order.
+    ///
+    /// A dir is listed completely and sorted before its first entry is
+    /// returned, so memory usage grows with the number of entries in the
+    /// dir. Recursive list walks dirs one by one instead of using native
+    /// recursive list, and only keeps the remaining entries of dirs on the
+    /// current path, not the whole listing.
+    ///
+    /// Default to `false`.
+    pub fn sort_unordered(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_sort_unordered(v));
+        self
+    }
+
     /// Call the function to consume all the input and generate a
     /// result.
     pub fn call(self) -> Result<Vec<Entry>> {
@@ -343,6 +398,59 @@ impl FunctionLister {
         self
     }
 
+    /// Only return entries whose path relative to the listed path matches
+    /// the glob pattern, like `*.parquet` or `**/2024-*/*.csv`.
+    ///
+    /// The filter runs before fetching the metadata, so excluded entries never
+    /// trigger extra `stat` calls. While listing recursively, dirs in the
+    /// literal prefix of the glob are pushed down to the list path.
+    ///
+    /// Requires the `list-matching` feature.
+    #[cfg(feature = "list-matching")]
+    pub fn matching_glob(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_matching_glob(v));
+        self
+    }
+
+    /// Only return entries whose path relative to the listed path matches
+    /// the regex.
+    ///
+    /// The regex is not anchored, use `^` and `$` to match the whole path.
+    ///
+    /// Requires the `list-matching` feature.
+    #[cfg(feature = "list-matching")]
+    pub fn matching_regex(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_matching_regex(v));
+        self
+    }
+
+    /// Return entries in lexicographical order of their paths.
+    ///
+    /// Services with [`Capability::list_ordered`] return entries in order
+    /// natively. For other services, `Unsupported` error will be returned
+    /// unless [`Self::sort_unordered`] is enabled.
+    ///
+    /// Default to `false`.
+    pub fn ordered(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_ordered(v));
+        self
+    }
+
+    /// Sort entries in memory if [`Self::ordered`] is requested but the
+    /// service can't list in order.
+    ///
+    /// A dir is listed completely and sorted before its first entry is
+    /// returned, so memory usage grows with the number of entries in the
+    /// dir. Recursive list walks dirs one by one instead of using native
+    /// recursive list, and only keeps the remaining entries of dirs on the
+    /// current path, not the whole listing.
+    ///
+    /// Default to `false`.
+    pub fn sort_unordered(mut self, v: bool) -> Self {
+        self.0 = self.0.map_args(|args| args.with_sort_unordered(v));
+        self
+    }
+
     /// Call the function to consume all the input and generate a
     /// result.
     pub fn call(self) -> Result<BlockingLister> {
@@ -417,6 +525,12 @@ impl FunctionReader {
         self
     }
 
+    /// Set the snapshot for this operation.
+    pub fn snapshot(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_snapshot(v));
+        self
+    }
+
     /// Call the function to consume all the input and generate a
     /// result.
     pub fn call(self) -> Result<BlockingReader> {
@@ -442,12 +556,30 @@ impl FunctionStat {
         self
     }
 
+    /// Set the If-Modified-Since for this operation.
+    pub fn if_modified_since(mut self, v: DateTime<Utc>) -> Self {
+        self.0 = self.0.map_args(|args| args.with_if_modified_since(v));
+        self
+    }
+
+    /// Set the If-Unmodified-Since for this operation.
+    pub fn if_unmodified_since(mut self, v: DateTime<Utc>) -> Self {
+        self.0 = self.0.map_args(|args| args.with_if_unmodified_since(v));
+        self
+    }
+
     /// Set the version for this operation.
     pub fn version(mut self, v: &str) -> Self {
         self.0 = self.0.map_args(|args| args.with_version(v));
         self
     }
 
+    /// Set the snapshot for this operation.
+    pub fn snapshot(mut self, v: &str) -> Self {
+        self.0 = self.0.map_args(|args| args.with_snapshot(v));
+        self
+    }
+
     /// Call the function to consume all the input and generate a
     /// result.
     pub fn call(self) -> Result<Metadata> {
@@ -24,8 +24,13 @@ use std::future::IntoFuture;
 use std::ops::RangeBounds;
 use std::time::Duration;
 
+use chrono::DateTime;
+use chrono::Utc;
 use flagset::FlagSet;
 use futures::Future;
+use http::HeaderMap;
+use http::HeaderName;
+use http::HeaderValue;
 
 use crate::raw::*;
 use crate::*;
@@ -105,10 +110,31 @@ impl<F: Future<Output = Result<Metadata>>> FutureStat<F> {
         self.map(|args| args.with_if_none_match(v))
     }
 
+    /// Set the If-Modified-Since for this operation.
+    ///
+    /// Stat will return [`ErrorKind::ConditionNotMatch`] if the object is
+    /// not modified since the given time.
+    pub fn if_modified_since(self, v: DateTime<Utc>) -> Self {
+        self.map(|args| args.with_if_modified_since(v))
+    }
+
+    /// Set the If-Unmodified-Since for this operation.
+    ///
+    /// Stat will return [`ErrorKind::ConditionNotMatch`] if the object is
+    /// modified since the given time.
+    pub fn if_unmodified_since(self, v: DateTime<Utc>) -> Self {
+        self.map(|args| args.with_if_unmodified_since(v))
+    }
+
     /// Set the version for this operation.
     pub fn version(self, v: &str) -> Self {
         self.map(|args| args.with_version(v))
     }
+
+    /// Set the snapshot for this operation.
+    pub fn snapshot(self, v: &str) -> Self {
+        self.map(|args| args.with_snapshot(v))
+    }
 }
 
 /// Future that generated by [`Operator::presign_stat_with`].
@@ -178,22 +204,39 @@ impl<F: Future<Output = Result<PresignedRequest>>> FuturePresignRead<F> {
 /// Future that generated by [`Operator::presign_write_with`].
 ///
 /// Users can add more options by public functions provided by this struct.
-pub type FuturePresignWrite<F> = OperatorFuture<(OpWrite, Duration), PresignedRequest, F>;
+pub type FuturePresignWrite<F> =
+    OperatorFuture<(OpWrite, Duration, HeaderMap), PresignedRequest, F>;
 
 impl<F: Future<Output = Result<PresignedRequest>>> FuturePresignWrite<F> {
     /// Set the content type of option
     pub fn content_type(self, v: &str) -> Self {
-        self.map(|(args, dur)| (args.with_content_type(v), dur))
+        self.map(|(args, dur, headers)| (args.with_content_type(v), dur, headers))
     }
 
     /// Set the content disposition of option
     pub fn content_disposition(self, v: &str) -> Self {
-        self.map(|(args, dur)| (args.with_content_disposition(v), dur))
+        self.map(|(args, dur, headers)| (args.with_content_disposition(v), dur, headers))
     }
 
     /// Set the content type of option
     pub fn cache_control(self, v: &str) -> Self {
-        self.map(|(args, dur)| (args.with_cache_control(v), dur))
+        self.map(|(args, dur, headers)| (args.with_cache_control(v), dur, headers))
+    }
+
+    /// Include the header in the signature, so that the uploader must send
+    /// it verbatim and can't change it.
+    ///
+    /// All required headers are returned by [`PresignedRequest::header`].
+    ///
+    /// # Notes
+    ///
+    /// Azblob SAS tokens can't cover headers, the header is returned for
+    /// clients to send but not enforced by the service.
+    pub fn signed_header(self, name: HeaderName, value: HeaderValue) -> Self {
+        self.map(|(args, dur, mut headers)| {
+            headers.insert(name, value);
+            (args, dur, headers)
+        })
     }
 }
 
@@ -218,6 +261,11 @@ impl<F: Future<Output = Result<Buffer>>> FutureRead<F> {
         self.map(|(args, op_reader)| (args.with_version(v), op_reader))
     }
 
+    /// Set the snapshot for this operation.
+    pub fn snapshot(self, v: &str) -> Self {
+        self.map(|(args, op_reader)| (args.with_snapshot(v), op_reader))
+    }
+
     /// Set the executor for this operation.
     pub fn executor(self, executor: Executor) -> Self {
         self.map(|(args, op_reader)| (args.with_executor(executor), op_reader))
@@ -237,6 +285,15 @@ impl<F: Future<Output = Result<Buffer>>> FutureRead<F> {
     pub fn chunk(self, chunk_size: usize) -> Self {
         self.map(|(args, op_reader)| (args, op_reader.with_chunk(chunk_size)))
     }
+
+    /// Verify the content read against the MD5 stored in metadata.
+    ///
+    /// Only whole file reads can be verified. [`ErrorKind::DataCorrupted`]
+    /// will be returned if not matched, and [`ErrorKind::Unsupported`] if
+    /// the file doesn't have a stored MD5.
+    pub fn verify_checksum(self, v: bool) -> Self {
+        self.map(|(args, op_reader)| (args, op_reader.with_verify_checksum(v)))
+    }
 }
 
 /// Future that generated by [`Operator::read_with`] or [`Operator::reader_with`].
@@ -249,11 +306,29 @@ impl<F: Future<Output = Result<Buffer>>> FutureRead<F> {
 pub type FutureReader<F> = OperatorFuture<(OpRead, OpReader), Reader, F>;
 
 impl<F: Future<Output = Result<Reader>>> FutureReader<F> {
+    /// Set the If-Match for this operation.
+    ///
+    /// All ranges read by this reader will carry the same If-Match, so that
+    /// they are guaranteed to come from the same version of the file.
+    pub fn if_match(self, v: &str) -> Self {
+        self.map(|(op_read, op_reader)| (op_read.with_if_match(v), op_reader))
+    }
+
+    /// Set the If-None-Match for this operation.
+    pub fn if_none_match(self, v: &str) -> Self {
+        self.map(|(op_read, op_reader)| (op_read.with_if_none_match(v), op_reader))
+    }
+
     /// Set the version for this operation.
     pub fn version(self, v: &str) -> Self {
         self.map(|(op_read, op_reader)| (op_read.with_version(v), op_reader))
     }
 
+    /// Set the snapshot for this operation.
+    pub fn snapshot(self, v: &str) -> Self {
+        self.map(|(op_read, op_reader)| (op_read.with_snapshot(v), op_reader))
+    }
+
     /// Set the concurrent read task amount.
     pub fn concurrent(self, concurrent: usize) -> Self {
         self.map(|(op_read, op_reader)| (op_read, op_reader.with_concurrent(concurrent)))
@@ -273,9 +348,9 @@ impl<F: Future<Output = Result<Reader>>> FutureReader<F> {
 /// Future that generated by [`Operator::write_with`].
 ///
 /// Users can add more options by public functions provided by this struct.
-pub type FutureWrite<F> = OperatorFuture<(OpWrite, OpWriter, Buffer), (), F>;
+pub type FutureWrite<F> = OperatorFuture<(OpWrite, OpWriter, Buffer), Metadata, F>;
 
-impl<F: Future<Output = Result<()>>> FutureWrite<F> {
+impl<F: Future<Output = Result<Metadata>>> FutureWrite<F> {
     /// Set the append mode of op.
     ///
     /// If the append mode is set, the data will be appended to the end of the file.
@@ -339,6 +414,70 @@ impl<F: Future<Output = Result<()>>> FutureWrite<F> {
             )
         })
     }
+
+    /// Set the tags of the op
+    ///
+    /// Tags are stored separately from user defined metadata. Check
+    /// [`Capability::write_with_tags`] before use, services without tags
+    /// support will ignore them.
+    pub fn tags(self, tags: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.map(|(args, options, bs)| (args.with_tags(HashMap::from_iter(tags)), options, bs))
+    }
+    /// Set the lock token for this operation.
+    ///
+    /// Services like WebDAV will carry the lock token so that the write
+    /// can be performed on a resource locked by the caller. Azdls will carry
+    /// it as the lease id of a path leased by the caller.
+    pub fn lock_token(self, v: &str) -> Self {
+        self.map(|(args, options, bs)| (args.with_lock_token(v), options, bs))
+    }
+
+    /// Set the file to expire after the given duration since now.
+    ///
+    /// Check [`Capability::write_with_expiry`] before use, services without
+    /// expiry support will ignore it.
+    pub fn expires_in(self, v: Duration) -> Self {
+        self.map(|(args, options, bs)| (args.with_expires_in(v), options, bs))
+    }
+
+    /// Set the file to expire at the given time.
+    ///
+    /// Check [`Capability::write_with_expiry`] before use, services without
+    /// expiry support will ignore it.
+    pub fn expires_at(self, v: DateTime<Utc>) -> Self {
+        self.map(|(args, options, bs)| (args.with_expires_at(v), options, bs))
+    }
+
+    /// Only write the file if it doesn't exist.
+    ///
+    /// [`ErrorKind::ConditionNotMatch`] will be returned if the file already
+    /// exists, and [`ErrorKind::Unsupported`] if the service doesn't support
+    /// [`Capability::write_with_if_not_exists`].
+    pub fn if_not_exists(self, v: bool) -> Self {
+        self.map(|(args, options, bs)| (args.with_if_not_exists(v), options, bs))
+    }
+
+    /// Set the base64 encoded MD5 of the whole content.
+    ///
+    /// The content will be verified before the write is committed,
+    /// [`ErrorKind::DataCorrupted`] will be returned if not matched. Services
+    /// with [`Capability::write_with_content_md5`] will verify it natively
+    /// as well.
+    pub fn content_md5(self, v: &str) -> Self {
+        self.map(|(args, options, bs)| (args.with_content_md5(v), options, bs))
+    }
+
+    /// Set the base64 encoded SHA256 of the whole content.
+    ///
+    /// The content will be verified before the write is committed,
+    /// [`ErrorKind::DataCorrupted`] will be returned if not matched. Services
+    /// with [`Capability::write_with_content_sha256`] will verify it natively
+    /// as well.
+    ///
+    /// Requires the `content-sha256` feature.
+    pub fn content_sha256(self, v: &str) -> Self {
+        self.map(|(args, options, bs)| (args.with_content_sha256(v), options, bs))
+    }
 }
 
 /// Future that generated by [`Operator::writer_with`].
@@ -411,6 +550,152 @@ impl<F: Future<Output = Result<Writer>>> FutureWriter<F> {
     pub fn user_metadata(self, data: impl IntoIterator<Item = (String, String)>) -> Self {
         self.map(|(args, options)| (args.with_user_metadata(HashMap::from_iter(data)), options))
     }
+
+    /// Set the tags of the op
+    ///
+    /// Tags are stored separately from user defined metadata. Check
+    /// [`Capability::write_with_tags`] before use, services without tags
+    /// support will ignore them.
+    pub fn tags(self, tags: impl IntoIterator<Item = (String, String)>) -> Self {
+        self.map(|(args, options)| (args.with_tags(HashMap::from_iter(tags)), options))
+    }
+    /// Set the lock token for this operation.
+    ///
+    /// Services like WebDAV will carry the lock token so that the write
+    /// can be performed on a resource locked by the caller. Azdls will carry
+    /// it as the lease id of a path leased by the caller.
+    pub fn lock_token(self, v: &str) -> Self {
+        self.map(|(args, options)| (args.with_lock_token(v), options))
+    }
+
+    /// Set the file to expire after the given duration since now.
+    ///
+    /// Check [`Capability::write_with_expiry`] before use, services without
+    /// expiry support will ignore it.
+    pub fn expires_in(self, v: Duration) -> Self {
+        self.map(|(args, options)| (args.with_expires_in(v), options))
+    }
+
+    /// Set the file to expire at the given time.
+    ///
+    /// Check [`Capability::write_with_expiry`] before use, services without
+    /// expiry support will ignore it.
+    pub fn expires_at(self, v: DateTime<Utc>) -> Self {
+        self.map(|(args, options)| (args.with_expires_at(v), options))
+    }
+
+    /// Only write the file if it doesn't exist.
+    ///
+    /// [`ErrorKind::ConditionNotMatch`] will be returned if the file already
+    /// exists, and [`ErrorKind::Unsupported`] if the service doesn't support
+    /// [`Capability::write_with_if_not_exists`].
+    pub fn if_not_exists(self, v: bool) -> Self {
+        self.map(|(args, options)| (args.with_if_not_exists(v), options))
+    }
+
+    /// Set the base64 encoded MD5 of the whole content.
+    ///
+    /// The content will be verified before the write is committed,
+    /// [`ErrorKind::DataCorrupted`] will be returned if not matched. Services
+    /// with [`Capability::write_with_content_md5`] will verify it natively
+    /// as well.
+    pub fn content_md5(self, v: &str) -> Self {
+        self.map(|(args, options)| (args.with_content_md5(v), options))
+    }
+
+    /// Set the base64 encoded SHA256 of the whole content.
+    ///
+    /// The content will be verified before the write is committed,
+    /// [`ErrorKind::DataCorrupted`] will be returned if not matched. Services
+    /// with [`Capability::write_with_content_sha256`] will verify it natively
+    /// as well.
+    ///
+    /// Requires the `content-sha256` feature.
+    pub fn content_sha256(self, v: &str) -> Self {
+        self.map(|(args, options)| (args.with_content_sha256(v), options))
+    }
+
+    /// Set the position to continue the append write from.
+    ///
+    /// This is only used while `append` is enabled. Services that support position
+    /// based append will start appending from the given position directly instead of
+    /// fetching current offset, which allows resuming an interrupted upload.
+    pub fn position(self, v: u64) -> Self {
+        self.map(|(args, options)| (args.with_position(v), options))
+    }
+    /// Flush appended data while retaining uncommitted data.
+    ///
+    /// This is only used while `append` is enabled. Services like azdls will
+    /// flush every appended chunk as a durable prefix without finalizing the
+    /// file, which enables write-ahead-log style usage.
+    pub fn retain_uncommitted_data(self, v: bool) -> Self {
+        self.map(|(args, options)| (args.with_retain_uncommitted_data(v), options))
+    }
+}
+
+/// Future that generated by [`Operator::copy_with`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub type FutureCopy<F> = OperatorFuture<(OpCopy, String), (), F>;
+
+impl<F: Future<Output = Result<()>>> FutureCopy<F> {
+    /// Set whether the target copies or replaces the metadata of source.
+    ///
+    /// Default to copy, overriding content type or cache control implies
+    /// [`MetadataDirective::Replace`].
+    pub fn metadata_directive(self, v: MetadataDirective) -> Self {
+        self.map(|(args, to)| (args.with_metadata_directive(v), to))
+    }
+
+    /// Set the content type of the target.
+    pub fn content_type(self, v: &str) -> Self {
+        self.map(|(args, to)| (args.with_content_type(v), to))
+    }
+
+    /// Set the cache control of the target.
+    pub fn cache_control(self, v: &str) -> Self {
+        self.map(|(args, to)| (args.with_cache_control(v), to))
+    }
+
+    /// Only copy if the target doesn't exist.
+    ///
+    /// [`ErrorKind::ConditionNotMatch`] will be returned if the target
+    /// already exists.
+    pub fn if_not_exists(self, v: bool) -> Self {
+        self.map(|(args, to)| (args.with_if_not_exists(v), to))
+    }
+}
+
+/// Future that generated by [`Operator::copy_to`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub type FutureCopyTo<F> = OperatorFuture<(OpCopyTo, Operator, Operator, String), (), F>;
+
+impl<F: Future<Output = Result<()>>> FutureCopyTo<F> {
+    /// Set the chunk size used to read from source and write to target.
+    ///
+    /// Target will be written with multipart upload in `chunk` sized parts
+    /// if it supports.
+    pub fn chunk(self, v: usize) -> Self {
+        self.map(|(args, from_op, to_op, to)| (args.with_chunk(v), from_op, to_op, to))
+    }
+
+    /// Set the concurrent of reading from source and writing to target.
+    pub fn concurrent(self, v: usize) -> Self {
+        self.map(|(args, from_op, to_op, to)| (args.with_concurrent(v), from_op, to_op, to))
+    }
+
+    /// Use native copy if source and target are the same storage, default
+    /// to false.
+    ///
+    /// OpenDAL can't tell whether two operators point to the same endpoint
+    /// and account, two S3 compatible services could share the same bucket
+    /// name for example. Only enable this if both operators are known to be
+    /// backed by the same storage. Native copy will still be skipped if
+    /// their scheme, name (bucket, container, ...) or root differ.
+    pub fn native_copy(self, v: bool) -> Self {
+        self.map(|(args, from_op, to_op, to)| (args.with_native_copy(v), from_op, to_op, to))
+    }
 }
 
 /// Future that generated by [`Operator::delete_with`].
@@ -423,6 +708,69 @@ impl<F: Future<Output = Result<()>>> FutureDelete<F> {
     pub fn version(self, v: &str) -> Self {
         self.map(|args| args.with_version(v))
     }
+
+    /// Set the lock token for this operation.
+    ///
+    /// Services like azdls will carry the lease id as lock token so that
+    /// the delete can be performed on a path leased by the caller.
+    pub fn lock_token(self, v: &str) -> Self {
+        self.map(|args| args.with_lock_token(v))
+    }
+}
+
+/// Future that generated by [`Operator::remove_all_with`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub type FutureRemoveAll<F> = OperatorFuture<(OpRemoveAll, usize), (), F>;
+
+impl<F: Future<Output = Result<()>>> FutureRemoveAll<F> {
+    /// Change the concurrent of this remove_all operation.
+    ///
+    /// Entries will be removed in batches of the service's
+    /// `batch_max_operations` if it supports batch delete, and at most
+    /// `concurrent` batches (or single deletes) will be running at the same
+    /// time.
+    ///
+    /// The default concurrent is 1.
+    pub fn concurrent(self, v: usize) -> Self {
+        self.map(|(args, limit)| (args.with_concurrent(v), limit))
+    }
+
+    /// Keep removing other entries if some of them failed.
+    ///
+    /// All failures will be collected and returned as one error after the
+    /// listing has been exhausted. Failures of listing itself still stop the
+    /// operation immediately.
+    ///
+    /// Default to `false`, which returns the first error.
+    pub fn continue_on_error(self, v: bool) -> Self {
+        self.map(|(args, limit)| (args.with_continue_on_error(v), limit))
+    }
+
+    /// Set the progress callback, which will be called with the total
+    /// entries removed and the total bytes freed so far.
+    ///
+    /// Bytes are only counted for entries whose size is returned by list.
+    pub fn progress(self, f: impl Fn(u64, u64) + Send + Sync + 'static) -> Self {
+        self.map(|(args, limit)| (args.with_progress(f), limit))
+    }
+}
+
+/// Future that generated by [`Operator::stat_many_with`].
+///
+/// Users can add more options by public functions provided by this struct.
+pub type FutureStatMany<F> = OperatorFuture<(Vec<String>, usize, usize), Vec<Result<Metadata>>, F>;
+
+impl<F: Future<Output = Result<Vec<Result<Metadata>>>>> FutureStatMany<F> {
+    /// Change the concurrent of this stat_many operation.
+    ///
+    /// At most `concurrent` stat requests will be running at the same time,
+    /// services that support batch stat are not affected.
+    ///
+    /// The default concurrent is 16, or [`Operator::limit`] if it's smaller.
+    pub fn concurrent(self, v: usize) -> Self {
+        self.map(|(paths, _, limit)| (paths, v, limit))
+    }
 }
 
 /// Future that generated by [`Operator::list_with`] or [`Operator::lister_with`].
@@ -475,6 +823,55 @@ impl<F: Future<Output = Result<Vec<Entry>>>> FutureList<F> {
     pub fn concurrent(self, v: usize) -> Self {
         self.map(|args| args.with_concurrent(v))
     }
+
+    /// Only return entries whose path relative to the listed path matches
+    /// the glob pattern, like `*.parquet` or `**/2024-*/*.csv`.
+    ///
+    /// The filter runs before fetching the metadata, so excluded entries never
+    /// trigger extra `stat` calls. While listing recursively, dirs in the
+    /// literal prefix of the glob are pushed down to the list path.
+    ///
+    /// Requires the `list-matching` feature.
+    #[cfg(feature = "list-matching")]
+    pub fn matching_glob(self, v: &str) -> Self {
+        self.map(|args| args.with_matching_glob(v))
+    }
+
+    /// Only return entries whose path relative to the listed path matches
+    /// the regex.
+    ///
+    /// The regex is not anchored, use `^` and `$` to match the whole path.
+    ///
+    /// Requires the `list-matching` feature.
+    #[cfg(feature = "list-matching")]
+    pub fn matching_regex(self, v: &str) -> Self {
+        self.map(|args| args.with_matching_regex(v))
+    }
+
+    /// Return entries in lexicographical order of their paths.
+    ///
+    /// Services with [`Capability::list_ordered`] return entries in order
+    /// natively. For other services, `Unsupported` error will be returned
+    /// unless [`Self::sort_unordered`] is enabled.
+    ///
+    /// Default to `false`.
+    pub fn ordered(self, v: bool) -> Self {
+        self.map(|args| args.with_ordered(v))
+    }
+
+    /// Sort entries in memory if [`Self::ordered`] is requested but the
+    /// service can't list in order.
+    ///
+    /// A dir is listed completely and sorted before its first entry is
+    /// returned, so memory usage grows with the number of entries in the
+    /// dir. Recursive list walks dirs one by one instead of using native
+    /// recursive list, and only keeps the remaining entries of dirs on the
+    /// current path, not the whole listing.
+    ///
+    /// Default to `false`.
+    pub fn sort_unordered(self, v: bool) -> Self {
+        self.map(|args| args.with_sort_unordered(v))
+    }
 }
 
 /// Future that generated by [`Operator::list_with`] or [`Operator::lister_with`].
@@ -527,4 +924,53 @@ impl<F: Future<Output = Result<Lister>>> FutureLister<F> {
     pub fn concurrent(self, v: usize) -> Self {
         self.map(|args| args.with_concurrent(v))
     }
+
+    /// Only return entries whose path relative to the listed path matches
+    /// the glob pattern, like `*.parquet` or `**/2024-*/*.csv`.
+    ///
+    /// The filter runs before fetching the metadata, so excluded entries never
+    /// trigger extra `stat` calls. While listing recursively, dirs in the
+    /// literal prefix of the glob are pushed down to the list path.
+    ///
+    /// Requires the `list-matching` feature.
+    #[cfg(feature = "list-matching")]
+    pub fn matching_glob(self, v: &str) -> Self {
+        self.map(|args| args.with_matching_glob(v))
+    }
+
+    /// Only return entries whose path relative to the listed path matches
+    /// the regex.
+    ///
+    /// The regex is not anchored, use `^` and `$` to match the whole path.
+    ///
+    /// Requires the `list-matching` feature.
+    #[cfg(feature = "list-matching")]
+    pub fn matching_regex(self, v: &str) -> Self {
+        self.map(|args| args.with_matching_regex(v))
+    }
+
+    /// Return entries in lexicographical order of their paths.
+    ///
+    /// Services with [`Capability::list_ordered`] return entries in order
+    /// natively. For other services, `Unsupported` error will be returned
+    /// unless [`Self::sort_unordered`] is enabled.
+    ///
+    /// Default to `false`.
+    pub fn ordered(self, v: bool) -> Self {
+        self.map(|args| args.with_ordered(v))
+    }
+
+    /// Sort entries in memory if [`Self::ordered`] is requested but the
+    /// service can't list in order.
+    ///
+    /// A dir is listed completely and sorted before its first entry is
+    /// returned, so memory usage grows with the number of entries in the
+    /// dir. Recursive list walks dirs one by one instead of using native
+    /// recursive list, and only keeps the remaining entries of dirs on the
+    /// current path, not the whole listing.
+    ///
+    /// Default to `false`.
+    pub fn sort_unordered(self, v: bool) -> Self {
+        self.map(|args| args.with_sort_unordered(v))
+    }
 }
@@ -22,6 +22,7 @@ use std::sync::Arc;
 
 use bytes::BufMut;
 use futures::stream;
+use futures::AsyncWriteExt;
 use futures::StreamExt;
 use futures::TryStreamExt;
 
@@ -182,15 +183,157 @@ impl Reader {
         }
     }
 
+    /// Copy given range from reader into [`futures::AsyncWrite`], returns the
+    /// total bytes written.
+    ///
+    /// Data is streamed in chunks without buffering the whole range in memory.
+    /// Use [`Operator::reader_with`] with `chunk` and `concurrent` to control
+    /// the chunk size and how many ranged reads are prefetched in flight.
+    ///
+    /// The writer will be flushed after all data is written. Dropping the
+    /// returned future cancels all in-flight reads, data that has already
+    /// been written to the writer is kept as is.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::Operator;
+    /// # use opendal::Result;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let mut w = futures::io::Cursor::new(Vec::new());
+    /// let n = op
+    ///     .reader_with("path/to/file")
+    ///     .chunk(4 * 1024 * 1024)
+    ///     .concurrent(8)
+    ///     .await?
+    ///     .read_to_writer(.., &mut w)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_to_writer<W>(
+        &self,
+        range: impl RangeBounds<u64>,
+        writer: &mut W,
+    ) -> Result<u64>
+    where
+        W: futures::AsyncWrite + Unpin + ?Sized,
+    {
+        self.read_to_writer_with_progress(range, writer, |_| {})
+            .await
+    }
+
+    /// Copy given range from reader into [`futures::AsyncWrite`] like
+    /// [`Reader::read_to_writer`], calling `progress` with the total bytes
+    /// written so far after every chunk.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::Operator;
+    /// # use opendal::Result;
+    /// # async fn test(op: Operator) -> Result<()> {
+    /// let r = op.reader_with("path/to/file").concurrent(4).await?;
+    /// let mut w = futures::io::sink();
+    /// r.read_to_writer_with_progress(.., &mut w, |n| println!("downloaded {n} bytes"))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn read_to_writer_with_progress<W>(
+        &self,
+        range: impl RangeBounds<u64>,
+        writer: &mut W,
+        mut progress: impl FnMut(u64),
+    ) -> Result<u64>
+    where
+        W: futures::AsyncWrite + Unpin + ?Sized,
+    {
+        let mut stream = self.clone().into_stream(range).await?;
+
+        let mut written = 0;
+        while let Some(buf) = stream.try_next().await? {
+            for bs in buf {
+                writer.write_all(&bs).await.map_err(new_std_io_error)?;
+                written += bs.len() as u64;
+            }
+            progress(written);
+        }
+        writer.flush().await.map_err(new_std_io_error)?;
+
+        Ok(written)
+    }
+
+    /// Pipe given range from reader into [`Writer`] and close it, returns the
+    /// [`Metadata`] of the written file.
+    ///
+    /// Buffers returned by the reader are passed to the writer as is without
+    /// extra copy. The next chunk is only read after the writer accepted the
+    /// previous one, so at most `concurrent * chunk` bytes of the reader plus
+    /// the writer's own buffer are held in memory no matter how large the file
+    /// is. This makes it suitable for transferring data between two operators,
+    /// for example two storage accounts.
+    ///
+    /// The writer will be aborted if either reading or writing fails.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use opendal::Operator;
+    /// # use opendal::Result;
+    /// # async fn test(src: Operator, dst: Operator) -> Result<()> {
+    /// let r = src
+    ///     .reader_with("path/to/file")
+    ///     .chunk(8 * 1024 * 1024)
+    ///     .concurrent(4)
+    ///     .await?;
+    /// let w = dst
+    ///     .writer_with("path/to/file")
+    ///     .chunk(8 * 1024 * 1024)
+    ///     .concurrent(4)
+    ///     .await?;
+    /// let meta = r.pipe_to(.., w).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn pipe_to(
+        &self,
+        range: impl RangeBounds<u64>,
+        mut writer: Writer,
+    ) -> Result<Metadata> {
+        let res = async {
+            let mut stream = self.clone().into_stream(range).await?;
+            while let Some(buf) = stream.try_next().await? {
+                writer.write(buf).await?;
+            }
+            writer.close().await
+        }
+        .await;
+
+        if res.is_err() {
+            // Keep the original error, the abort error is not actionable.
+            let _ = writer.abort().await;
+        }
+        res
+    }
+
     /// Fetch specific ranges from reader.
     ///
     /// This operation try to merge given ranges into a list of
     /// non-overlapping ranges. Users may also specify a `gap` to merge
-    /// close ranges.
+    /// close ranges, default to 1MiB. Merged ranges will be read with
+    /// `concurrent` requests in flight.
+    ///
+    /// Buffers are returned in the same order as given ranges. Like `read`,
+    /// ranges that exceed the end of file will be truncated.
     ///
     /// The returning `Buffer` may share the same underlying memory without
     /// any extra copy.
     pub async fn fetch(&self, ranges: Vec<Range<u64>>) -> Result<Vec<Buffer>> {
+        if ranges.is_empty() {
+            return Ok(Vec::new());
+        }
+
         let merged_ranges = self.merge_ranges(ranges.clone());
 
         let merged_bufs: Vec<_> =
@@ -202,9 +345,12 @@ impl Reader {
         let mut bufs = Vec::with_capacity(ranges.len());
         for range in ranges {
             let idx = merged_ranges.partition_point(|v| v.start <= range.start) - 1;
-            let start = range.start - merged_ranges[idx].start;
-            let end = range.end - merged_ranges[idx].start;
-            bufs.push(merged_bufs[idx].slice(start as usize..end as usize));
+            let buf = &merged_bufs[idx];
+            // The merged buffer could be shorter than expected if it reaches
+            // the end of file.
+            let start = ((range.start - merged_ranges[idx].start) as usize).min(buf.len());
+            let end = ((range.end - merged_ranges[idx].start) as usize).clamp(start, buf.len());
+            bufs.push(buf.slice(start..end));
         }
 
         Ok(bufs)
@@ -553,4 +699,97 @@ mod tests {
         }
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_fetch_edge_cases() -> Result<()> {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+
+        let content = gen_fixed_bytes(1024);
+        op.write(path, content.clone())
+            .await
+            .expect("write must succeed");
+
+        let reader = op.reader_with(path).gap(1).await.unwrap();
+
+        let bufs = reader.fetch(vec![]).await.expect("fetch must succeed");
+        assert!(bufs.is_empty());
+
+        let bufs = reader
+            .fetch(vec![1020..1024, 5..5, 1000..1024, 0..1])
+            .await
+            .expect("fetch must succeed");
+        assert_eq!(bufs[0].to_bytes(), content[1020..]);
+        assert!(bufs[1].is_empty());
+        assert_eq!(bufs[2].to_bytes(), content[1000..]);
+        assert_eq!(bufs[3].to_bytes(), content[..1]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_read_to_writer() -> Result<()> {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+
+        let content = gen_fixed_bytes(4096);
+        op.write(path, content.clone())
+            .await
+            .expect("write must succeed");
+
+        let reader = op
+            .reader_with(path)
+            .chunk(1000)
+            .concurrent(3)
+            .await
+            .unwrap();
+
+        let mut progress = vec![];
+        let mut w = futures::io::Cursor::new(Vec::new());
+        let n = reader
+            .read_to_writer_with_progress(.., &mut w, |n| progress.push(n))
+            .await
+            .expect("read must succeed");
+        assert_eq!(n, 4096);
+        assert_eq!(w.into_inner(), content);
+        assert_eq!(progress, vec![1000, 2000, 3000, 4000, 4096]);
+
+        let mut w = futures::io::Cursor::new(Vec::new());
+        let n = reader
+            .read_to_writer(100..200, &mut w)
+            .await
+            .expect("read must succeed");
+        assert_eq!(n, 100);
+        assert_eq!(w.into_inner(), content[100..200]);
+
+        let mut w = futures::io::Cursor::new(Vec::new());
+        let n = op
+            .read_to_writer(path, &mut w)
+            .await
+            .expect("read must succeed");
+        assert_eq!(n, 4096);
+        assert_eq!(w.into_inner(), content);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pipe_to() -> Result<()> {
+        let src = Operator::new(services::Memory::default()).unwrap().finish();
+        let dst = Operator::new(services::Memory::default()).unwrap().finish();
+        let path = "test_file";
+
+        let content = gen_fixed_bytes(4096);
+        src.write(path, content.clone())
+            .await
+            .expect("write must succeed");
+
+        let reader = src.reader_with(path).chunk(1000).concurrent(2).await?;
+        let meta = reader.pipe_to(.., dst.writer(path).await?).await?;
+        assert_eq!(meta.content_length(), 4096);
+        assert_eq!(dst.read(path).await?.to_vec(), content);
+
+        let meta = reader.pipe_to(1000..1500, dst.writer(path).await?).await?;
+        assert_eq!(meta.content_length(), 500);
+        assert_eq!(dst.read(path).await?.to_vec(), content[1000..1500]);
+        Ok(())
+    }
 }
@@ -169,13 +169,33 @@ impl Writer {
         self.inner.abort().await
     }
 
+    /// Get the strategy that the writer uses to upload data.
+    ///
+    /// Returns `None` if the service doesn't report it.
+    pub fn write_mode(&self) -> Option<WriteMode> {
+        self.inner.rp().mode()
+    }
+
+    /// Get the number of append requests issued by the writer so far.
+    ///
+    /// Check it after the writer is closed to get the final value.
+    pub fn append_requests(&self) -> usize {
+        self.inner.rp().append_requests()
+    }
+
     /// Close the writer and make sure all data have been committed.
     ///
+    /// Returns the [`Metadata`] of the written file. Fields like `etag` and
+    /// `version` are only set if the service returns them, check
+    /// `write_returns_*` of [`Capability`] to know if they are authoritative.
+    /// `content_length` is filled with the size that has been written if the
+    /// service doesn't return it.
+    ///
     /// ## Notes
     ///
     /// Close should only be called when the writer is not closed or
     /// aborted, otherwise an unexpected error could be returned.
-    pub async fn close(&mut self) -> Result<()> {
+    pub async fn close(&mut self) -> Result<Metadata> {
         self.inner.close().await
     }
 
@@ -302,6 +322,7 @@ mod tests {
     use rand::Rng;
     use rand::RngCore;
 
+    use crate::raw::WriteMode;
     use crate::services;
     use crate::Operator;
 
@@ -332,6 +353,19 @@ mod tests {
         assert_eq!(buf.to_bytes(), content);
     }
 
+    #[tokio::test]
+    async fn test_writer_write_mode() {
+        let op = Operator::new(services::Memory::default()).unwrap().finish();
+
+        let mut writer = op.writer("test_file").await.unwrap();
+        writer.write("hello").await.unwrap();
+        writer.write("world").await.unwrap();
+        writer.close().await.unwrap();
+
+        assert_eq!(writer.write_mode(), Some(WriteMode::OneShot));
+        assert_eq!(writer.append_requests(), 0);
+    }
+
     #[tokio::test]
     async fn test_writer_write_from() {
         let op = Operator::new(services::Memory::default()).unwrap().finish();
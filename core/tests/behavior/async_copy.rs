@@ -34,9 +34,16 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_copy_target_dir,
             test_copy_self,
             test_copy_nested,
-            test_copy_overwrite
+            test_copy_overwrite,
+            test_copy_with_content_type,
+            test_copy_with_if_not_exists,
+            test_copy_to_native
         ))
     }
+
+    if cap.stat && cap.read && cap.write {
+        tests.extend(async_trials!(op, test_copy_to_streaming))
+    }
 }
 
 /// Copy a file with ascii name and test contents.
@@ -229,3 +236,124 @@ pub async fn test_copy_overwrite(op: Operator) -> Result<()> {
     op.delete(&target_path).await.expect("delete must succeed");
     Ok(())
 }
+
+/// Copy with content type should replace the content type of target.
+pub async fn test_copy_with_content_type(op: Operator) -> Result<()> {
+    if !op.info().full_capability().copy_with_content_type {
+        return Ok(());
+    }
+
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let (source_content, _) = gen_bytes(op.info().full_capability());
+
+    op.write(&source_path, source_content).await?;
+
+    let target_path = uuid::Uuid::new_v4().to_string();
+    op.copy_with(&source_path, &target_path)
+        .content_type("application/json")
+        .await?;
+
+    let meta = op.stat(&target_path).await.expect("stat must succeed");
+    assert_eq!(meta.content_type(), Some("application/json"));
+
+    op.delete(&source_path).await.expect("delete must succeed");
+    op.delete(&target_path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Copy with if_not_exists should fail if the target exists.
+pub async fn test_copy_with_if_not_exists(op: Operator) -> Result<()> {
+    if !op.info().full_capability().copy_with_if_not_exists {
+        return Ok(());
+    }
+
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let (source_content, _) = gen_bytes(op.info().full_capability());
+
+    op.write(&source_path, source_content).await?;
+
+    let target_path = uuid::Uuid::new_v4().to_string();
+    op.copy_with(&source_path, &target_path)
+        .if_not_exists(true)
+        .await?;
+
+    let err = op
+        .copy_with(&source_path, &target_path)
+        .if_not_exists(true)
+        .await
+        .expect_err("copy must fail");
+    assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+
+    op.delete(&source_path).await.expect("delete must succeed");
+    op.delete(&target_path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Copy to the same operator should use native copy.
+pub async fn test_copy_to_native(op: Operator) -> Result<()> {
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let (source_content, _) = gen_bytes(op.info().full_capability());
+
+    op.write(&source_path, source_content.clone()).await?;
+
+    let target_path = uuid::Uuid::new_v4().to_string();
+
+    op.copy_to(&op, &source_path, &target_path)
+        .native_copy(true)
+        .await?;
+
+    let target_content = op
+        .read(&target_path)
+        .await
+        .expect("read must succeed")
+        .to_bytes();
+    assert_eq!(
+        format!("{:x}", Sha256::digest(target_content)),
+        format!("{:x}", Sha256::digest(&source_content)),
+    );
+
+    op.delete(&source_path).await.expect("delete must succeed");
+    op.delete(&target_path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Copy to should stream the content by default and carry the content type.
+pub async fn test_copy_to_streaming(op: Operator) -> Result<()> {
+    let cap = op.info().full_capability();
+    let source_path = uuid::Uuid::new_v4().to_string();
+    let (source_content, _) = gen_bytes(cap);
+    let content_type = "application/opendal";
+
+    if cap.write_with_content_type {
+        op.write_with(&source_path, source_content.clone())
+            .content_type(content_type)
+            .await?;
+    } else {
+        op.write(&source_path, source_content.clone()).await?;
+    }
+
+    let target_path = uuid::Uuid::new_v4().to_string();
+
+    op.copy_to(&op, &source_path, &target_path)
+        .concurrent(2)
+        .await?;
+
+    let target_content = op
+        .read(&target_path)
+        .await
+        .expect("read must succeed")
+        .to_bytes();
+    assert_eq!(
+        format!("{:x}", Sha256::digest(target_content)),
+        format!("{:x}", Sha256::digest(&source_content)),
+    );
+
+    if cap.write_with_content_type {
+        let meta = op.stat(&target_path).await?;
+        assert_eq!(meta.content_type(), Some(content_type));
+    }
+
+    op.delete(&source_path).await.expect("delete must succeed");
+    op.delete(&target_path).await.expect("delete must succeed");
+    Ok(())
+}
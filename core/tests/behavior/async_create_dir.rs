@@ -23,7 +23,12 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
     let cap = op.info().full_capability();
 
     if cap.create_dir && cap.stat {
-        tests.extend(async_trials!(op, test_create_dir, test_create_dir_existing))
+        tests.extend(async_trials!(
+            op,
+            test_create_dir,
+            test_create_dir_existing,
+            test_create_dir_on_existing_file
+        ))
     }
 }
 
@@ -51,3 +56,22 @@ pub async fn test_create_dir_existing(op: Operator) -> Result<()> {
 
     Ok(())
 }
+
+/// Create dir on existing file should fail with NotADirectory.
+pub async fn test_create_dir_on_existing_file(op: Operator) -> Result<()> {
+    let cap = op.info().full_capability();
+    if !cap.write || !cap.create_dir_rejects_file {
+        return Ok(());
+    }
+
+    let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+    op.write(&path, content).await?;
+
+    let err = op
+        .create_dir(&format!("{path}/"))
+        .await
+        .expect_err("create dir on existing file must fail");
+    assert_eq!(err.kind(), ErrorKind::NotADirectory);
+
+    Ok(())
+}
@@ -15,6 +15,9 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::sync::Arc;
+use std::sync::Mutex;
+
 use anyhow::Result;
 use futures::StreamExt;
 use futures::TryStreamExt;
@@ -35,6 +38,13 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_delete_stream,
             test_remove_one_file
         ));
+        if cap.list {
+            tests.extend(async_trials!(
+                op,
+                test_remove_all_with_concurrent,
+                test_remove_all_nested_dirs
+            ));
+        }
         if cap.list_with_recursive {
             tests.extend(async_trials!(op, test_remove_all_basic));
             if !cap.create_dir {
@@ -212,3 +222,54 @@ pub async fn test_remove_all_with_prefix_exists(op: Operator) -> Result<()> {
         .expect("write must succeed");
     test_blocking_remove_all_with_objects(op, parent, ["a", "a/b", "a/c", "a/b/e"]).await
 }
+
+/// Remove all with concurrent should remove nested dirs and report progress.
+pub async fn test_remove_all_with_concurrent(op: Operator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+    let paths = ["a/b", "a/c", "a/d/e", "a/d/f/g", "h"];
+    for path in paths {
+        op.write(&format!("{parent}/{path}"), "test_remove_all")
+            .await
+            .expect("write must succeed");
+    }
+
+    let progress = Arc::new(Mutex::new((0, 0)));
+    let recorder = progress.clone();
+    op.remove_all_with(&format!("{parent}/"))
+        .concurrent(4)
+        .progress(move |entries, bytes| *recorder.lock().unwrap() = (entries, bytes))
+        .await?;
+
+    for path in paths {
+        assert!(
+            !op.exists(&format!("{parent}/{path}")).await?,
+            "{path} should be removed"
+        );
+    }
+    let (entries, _) = *progress.lock().unwrap();
+    assert!(
+        entries >= paths.len() as u64,
+        "progress must count all removed entries"
+    );
+
+    Ok(())
+}
+
+/// Remove all should not leave empty nested dirs behind.
+pub async fn test_remove_all_nested_dirs(op: Operator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+    if op.info().full_capability().create_dir {
+        op.create_dir(&format!("{parent}/a/empty/")).await?;
+    }
+    op.write(&format!("{parent}/a/b/c"), "test_remove_all")
+        .await
+        .expect("write must succeed");
+
+    op.remove_all(&format!("{parent}/")).await?;
+
+    let entries = op.list_with(&format!("{parent}/")).recursive(true).await?;
+    assert!(entries.is_empty(), "left entries: {entries:?}");
+    assert!(!op.exists(&format!("{parent}/a/")).await?);
+
+    Ok(())
+}
@@ -44,10 +44,15 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_list_nested_dir,
             test_list_dir_with_file_path,
             test_list_with_start_after,
+            test_list_with_start_after_resume,
             test_list_dir_with_recursive,
             test_list_dir_with_recursive_no_trailing_slash,
             test_list_file_with_recursive,
+            test_list_prefix_with_recursive,
             test_list_root_with_recursive,
+            test_list_with_matching_glob,
+            test_list_with_matching_regex,
+            test_list_with_ordered,
             test_remove_all
         ))
     }
@@ -469,6 +474,56 @@ pub async fn test_list_with_start_after(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// List with start after should return entries in order and could be
+/// used to resume a broken listing from the last returned key.
+pub async fn test_list_with_start_after_resume(op: Operator) -> Result<()> {
+    if !op.info().full_capability().list_with_start_after {
+        return Ok(());
+    }
+
+    let dir = &format!("{}/", uuid::Uuid::new_v4());
+    op.create_dir(dir).await?;
+
+    let mut given: Vec<String> = (0..10000).map(|i| format!("{dir}file-{i:05}")).collect();
+    futures::stream::iter(given.iter())
+        .for_each_concurrent(64, |name| async {
+            op.write(name, "content")
+                .await
+                .expect("create must succeed");
+        })
+        .await;
+    given.sort();
+
+    // Stop listing after 2500 entries to simulate a crashed scan.
+    let mut actual = vec![];
+    let mut lister = op.lister_with(dir).start_after(dir).await?;
+    while let Some(o) = lister.try_next().await? {
+        if o.path() == dir {
+            continue;
+        }
+        actual.push(o.path().to_string());
+        if actual.len() == 2500 {
+            break;
+        }
+    }
+    drop(lister);
+
+    // Resume from the last processed key.
+    let last = actual.last().expect("entries must be returned").clone();
+    let mut lister = op.lister_with(dir).start_after(&last).await?;
+    while let Some(o) = lister.try_next().await? {
+        if o.path() == dir {
+            continue;
+        }
+        actual.push(o.path().to_string());
+    }
+
+    assert_eq!(given, actual, "entries must be in order without duplicates");
+
+    op.remove_all(dir).await?;
+    Ok(())
+}
+
 pub async fn test_list_root_with_recursive(op: Operator) -> Result<()> {
     let w = op.lister_with("").recursive(true).await?;
     let actual = w
@@ -590,7 +645,178 @@ pub async fn test_list_file_with_recursive(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// List with recursive on a prefix that is not a dir should return all
+/// files starting with it.
+pub async fn test_list_prefix_with_recursive(op: Operator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+
+    let paths = ["ab/c", "ab/d/e", "abc", "b"];
+    for path in paths.iter() {
+        op.write(&format!("{parent}/{path}"), "test_scan").await?;
+    }
+    let w = op
+        .lister_with(&format!("{parent}/a"))
+        .recursive(true)
+        .await?;
+    let mut actual = w
+        .try_collect::<Vec<_>>()
+        .await?
+        .into_iter()
+        .filter(|v| v.metadata().mode() == EntryMode::FILE)
+        .map(|v| {
+            v.path()
+                .strip_prefix(&format!("{parent}/"))
+                .unwrap()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    actual.sort();
+
+    let expected = vec!["ab/c", "ab/d/e", "abc"];
+    assert_eq!(actual, expected);
+    Ok(())
+}
+
 // Remove all should remove all in this path.
+/// List with glob should only return matched entries.
+pub async fn test_list_with_matching_glob(op: Operator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+
+    let paths = [
+        "a.parquet",
+        "b.csv",
+        "2024-01/c.parquet",
+        "2024-01/d.csv",
+        "2024-02/e.parquet",
+    ];
+    for path in paths.iter() {
+        op.write(&format!("{parent}/{path}"), "test_glob").await?;
+    }
+
+    let collect = |entries: Vec<Entry>| {
+        let mut actual = entries
+            .into_iter()
+            .map(|v| {
+                v.path()
+                    .strip_prefix(&format!("{parent}/"))
+                    .unwrap()
+                    .to_string()
+            })
+            .collect::<Vec<_>>();
+        actual.sort();
+        actual
+    };
+
+    let entries = op
+        .list_with(&format!("{parent}/"))
+        .matching_glob("*.parquet")
+        .await?;
+    assert_eq!(collect(entries), vec!["a.parquet"]);
+
+    let entries = op
+        .list_with(&format!("{parent}/"))
+        .matching_glob("2024-*/")
+        .await?;
+    assert_eq!(collect(entries), vec!["2024-01/", "2024-02/"]);
+
+    let entries = op
+        .list_with(&format!("{parent}/"))
+        .recursive(true)
+        .matching_glob("**/*.parquet")
+        .await?;
+    assert_eq!(
+        collect(entries),
+        vec!["2024-01/c.parquet", "2024-02/e.parquet", "a.parquet"]
+    );
+
+    // The literal prefix `2024-01/` will be pushed down.
+    let entries = op
+        .list_with(&format!("{parent}/"))
+        .recursive(true)
+        .matching_glob("2024-01/*.{csv,parquet}")
+        .await?;
+    assert_eq!(collect(entries), vec!["2024-01/c.parquet", "2024-01/d.csv"]);
+
+    Ok(())
+}
+
+/// List with regex should only return matched entries.
+pub async fn test_list_with_matching_regex(op: Operator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+
+    for path in ["a-1.txt", "a-2.txt", "a-x.txt", "b-1.txt"] {
+        op.write(&format!("{parent}/{path}"), "test_regex").await?;
+    }
+
+    let mut actual = op
+        .lister_with(&format!("{parent}/"))
+        .matching_regex(r"^a-\d\.txt$")
+        .await?
+        .try_collect::<Vec<_>>()
+        .await?
+        .into_iter()
+        .map(|v| v.name().to_string())
+        .collect::<Vec<_>>();
+    actual.sort();
+    assert_eq!(actual, vec!["a-1.txt", "a-2.txt"]);
+
+    let res = op
+        .list_with(&format!("{parent}/"))
+        .matching_regex("(")
+        .await;
+    assert_eq!(
+        res.expect_err("invalid regex must fail").kind(),
+        ErrorKind::ConfigInvalid
+    );
+
+    Ok(())
+}
+
+/// List with ordered should return entries in lexicographical order.
+pub async fn test_list_with_ordered(op: Operator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+
+    let paths = ["z", "a-b", "a/y", "a/b/c", "b/x", "a.txt"];
+    for path in paths.iter() {
+        op.write(&format!("{parent}/{path}"), "test_ordered")
+            .await?;
+    }
+
+    let sort_unordered = !op.info().full_capability().list_ordered;
+    if sort_unordered {
+        let res = op.list_with(&format!("{parent}/")).ordered(true).await;
+        assert_eq!(
+            res.expect_err("ordered list must fail without sort_unordered")
+                .kind(),
+            ErrorKind::Unsupported
+        );
+    }
+
+    for recursive in [false, true] {
+        let actual = op
+            .lister_with(&format!("{parent}/"))
+            .recursive(recursive)
+            .ordered(true)
+            .sort_unordered(sort_unordered)
+            .await?
+            .try_collect::<Vec<_>>()
+            .await?
+            .into_iter()
+            .map(|v| v.path().to_string())
+            .collect::<Vec<_>>();
+
+        let mut expected = actual.clone();
+        expected.sort();
+        assert_eq!(actual, expected, "entries must be in order");
+        assert!(actual.contains(&format!("{parent}/a-b")));
+        if recursive {
+            assert!(actual.contains(&format!("{parent}/a/b/c")));
+        }
+    }
+
+    Ok(())
+}
+
 pub async fn test_remove_all(op: Operator) -> Result<()> {
     let parent = uuid::Uuid::new_v4().to_string();
 
@@ -39,6 +39,22 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_presign_stat
         ))
     }
+
+    if cap.read && cap.write && cap.presign_write {
+        tests.extend(async_trials!(op, test_presign_write_with_signed_header))
+    }
+
+    if cap.stat && cap.write && cap.presign_delete {
+        tests.extend(async_trials!(op, test_presign_delete))
+    }
+
+    if cap.read && cap.write && cap.multipart && cap.presign_upload_part {
+        tests.extend(async_trials!(
+            op,
+            test_presign_upload_part,
+            test_abort_multipart
+        ))
+    }
 }
 
 /// Presign write should succeed.
@@ -138,3 +154,193 @@ pub async fn test_presign_read(op: Operator) -> Result<()> {
     op.delete(&path).await.expect("delete must succeed");
     Ok(())
 }
+
+/// Headers included in the signature should be applied to the written file.
+pub async fn test_presign_write_with_signed_header(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, size) = gen_bytes(op.info().full_capability());
+
+    let signed_req = op
+        .presign_write_with(&path, Duration::from_secs(3600))
+        .signed_header(
+            header::CONTENT_TYPE,
+            header::HeaderValue::from_static("image/png"),
+        )
+        .await?;
+    debug!("Generated request: {signed_req:?}");
+    assert_eq!(
+        signed_req.header().get(header::CONTENT_TYPE),
+        Some(&header::HeaderValue::from_static("image/png")),
+        "signed header must be returned"
+    );
+
+    let client = reqwest::Client::new();
+    let mut req = client.request(
+        signed_req.method().clone(),
+        Url::from_str(&signed_req.uri().to_string()).expect("must be valid url"),
+    );
+    for (k, v) in signed_req.header() {
+        req = req.header(k, v);
+    }
+    req = req.header(header::CONTENT_LENGTH, content.len());
+    req = req.body(reqwest::Body::from(content));
+
+    let resp = req.send().await.expect("send request must succeed");
+    assert!(resp.status().is_success(), "write must succeed");
+
+    let meta = op.stat(&path).await.expect("stat must succeed");
+    assert_eq!(meta.content_length(), size as u64);
+    assert_eq!(meta.content_type(), Some("image/png"));
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Presign delete should remove the file.
+pub async fn test_presign_delete(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, _) = gen_bytes(op.info().full_capability());
+    op.write(&path, content).await.expect("write must succeed");
+
+    let signed_req = op.presign_delete(&path, Duration::from_secs(3600)).await?;
+    debug!("Generated request: {signed_req:?}");
+    assert_eq!(signed_req.method(), http::Method::DELETE);
+
+    let client = reqwest::Client::new();
+    let mut req = client.request(
+        signed_req.method().clone(),
+        Url::from_str(&signed_req.uri().to_string()).expect("must be valid url"),
+    );
+    for (k, v) in signed_req.header() {
+        req = req.header(k, v);
+    }
+
+    let resp = req.send().await.expect("send request must succeed");
+    assert!(resp.status().is_success(), "delete must succeed");
+
+    assert!(!op.exists(&path).await?, "file must be deleted");
+    Ok(())
+}
+
+/// Parts uploaded via presigned requests should be completed into one file.
+pub async fn test_presign_upload_part(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let cap = op.info().full_capability();
+    // All parts except the last one must be at least `write_multi_min_size`.
+    let first = gen_fixed_bytes(cap.write_multi_min_size.unwrap_or(5 * 1024 * 1024));
+    let (second, _) = gen_bytes(cap);
+
+    let upload = op.initiate_multipart(&path).await?;
+    assert_eq!(upload.path(), path);
+
+    let client = reqwest::Client::new();
+    let mut parts = vec![];
+    for (idx, content) in [first.clone(), second.clone()].into_iter().enumerate() {
+        let part_number = idx + 1;
+        let signed_req = op
+            .presign_upload_part(
+                upload.path(),
+                upload.upload_id(),
+                part_number,
+                Duration::from_secs(3600),
+            )
+            .await?;
+        debug!("Generated request: {signed_req:?}");
+
+        let mut req = client.request(
+            signed_req.method().clone(),
+            Url::from_str(&signed_req.uri().to_string()).expect("must be valid url"),
+        );
+        for (k, v) in signed_req.header() {
+            req = req.header(k, v);
+        }
+        req = req.header(header::CONTENT_LENGTH, content.len());
+        req = req.body(reqwest::Body::from(content));
+
+        let resp = req.send().await.expect("send request must succeed");
+        assert!(
+            resp.status().is_success(),
+            "upload part must succeed: {:?}",
+            resp.text().await
+        );
+        let etag = resp
+            .headers()
+            .get(header::ETAG)
+            .map(|v| v.to_str().expect("etag must be valid").to_string())
+            .unwrap_or_default();
+        parts.push(UploadedPart::new(part_number, &etag));
+    }
+
+    op.complete_multipart(upload.path(), upload.upload_id(), parts)
+        .await
+        .expect("complete multipart must succeed");
+
+    let bs = op.read(&path).await.expect("read must succeed");
+    assert_eq!(bs.len(), first.len() + second.len());
+    assert_eq!(
+        format!("{:x}", Sha256::digest(bs.to_vec())),
+        format!("{:x}", Sha256::digest([first, second].concat())),
+        "read content"
+    );
+
+    op.delete(&path).await.expect("delete must succeed");
+    Ok(())
+}
+
+/// Aborted multipart upload should not create the file.
+pub async fn test_abort_multipart(op: Operator) -> Result<()> {
+    let path = uuid::Uuid::new_v4().to_string();
+    debug!("Generate a random file: {}", &path);
+    let (content, _) = gen_bytes(op.info().full_capability());
+
+    let upload = op.initiate_multipart(&path).await?;
+
+    let signed_req = op
+        .presign_upload_part(
+            upload.path(),
+            upload.upload_id(),
+            1,
+            Duration::from_secs(3600),
+        )
+        .await?;
+    let mut req = reqwest::Client::new().request(
+        signed_req.method().clone(),
+        Url::from_str(&signed_req.uri().to_string()).expect("must be valid url"),
+    );
+    for (k, v) in signed_req.header() {
+        req = req.header(k, v);
+    }
+    req = req.header(header::CONTENT_LENGTH, content.len());
+    req = req.body(reqwest::Body::from(content));
+    let resp = req.send().await.expect("send request must succeed");
+    assert!(resp.status().is_success(), "upload part must succeed");
+
+    // Directory paths must be rejected before reaching services.
+    let err = op
+        .abort_multipart(&format!("{path}/"), upload.upload_id())
+        .await
+        .expect_err("abort dir path must fail");
+    assert_eq!(err.kind(), ErrorKind::IsADirectory);
+    let err = op
+        .complete_multipart(
+            &format!("{path}/"),
+            upload.upload_id(),
+            vec![UploadedPart::new(1, "etag")],
+        )
+        .await
+        .expect_err("complete dir path must fail");
+    assert_eq!(err.kind(), ErrorKind::IsADirectory);
+
+    op.abort_multipart(upload.path(), upload.upload_id())
+        .await
+        .expect("abort multipart must succeed");
+
+    assert!(
+        !op.exists(&path).await?,
+        "aborted upload must not be visible"
+    );
+    Ok(())
+}
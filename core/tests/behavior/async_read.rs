@@ -18,6 +18,8 @@
 use std::str::FromStr;
 use std::time::Duration;
 
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
 use futures::AsyncReadExt;
 use futures::TryStreamExt;
 use http::StatusCode;
@@ -37,6 +39,7 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_read_full,
             test_read_range,
             test_reader,
+            test_reader_fetch,
             test_read_not_exist,
             test_read_with_if_match,
             test_read_with_if_none_match,
@@ -44,7 +47,8 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_read_with_special_chars,
             test_read_with_override_cache_control,
             test_read_with_override_content_disposition,
-            test_read_with_override_content_type
+            test_read_with_override_content_type,
+            test_read_with_verify_checksum
         ))
     }
 
@@ -81,6 +85,41 @@ pub async fn test_read_full(op: Operator) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Read with verify checksum should match the stored md5.
+pub async fn test_read_with_verify_checksum(op: Operator) -> anyhow::Result<()> {
+    if !op.info().full_capability().stat {
+        return Ok(());
+    }
+
+    let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+    let content_md5 = BASE64_STANDARD.encode(md5::Md5::digest(&content));
+
+    op.write_with(&path, content.clone())
+        .content_md5(&content_md5)
+        .await
+        .expect("write must succeed");
+
+    let res = op.read_with(&path).verify_checksum(true).await;
+    if op.stat(&path).await?.content_md5().is_none() {
+        assert_eq!(
+            res.expect_err("read must fail").kind(),
+            ErrorKind::Unsupported
+        );
+        return Ok(());
+    }
+    assert_eq!(res?.to_bytes(), content);
+
+    let err = op
+        .read_with(&path)
+        .range(1..)
+        .verify_checksum(true)
+        .await
+        .expect_err("range read must fail");
+    assert_eq!(err.kind(), ErrorKind::ConfigInvalid);
+
+    Ok(())
+}
+
 /// Read range content should match.
 pub async fn test_read_range(op: Operator) -> anyhow::Result<()> {
     let (path, content, size) = TEST_FIXTURE.new_file(op.clone());
@@ -108,6 +147,41 @@ pub async fn test_read_range(op: Operator) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Fetch ranges via reader should match the content.
+pub async fn test_reader_fetch(op: Operator) -> anyhow::Result<()> {
+    let (path, content, size) = TEST_FIXTURE.new_file(op.clone());
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    let size = size as u64;
+    let mut ranges = Vec::new();
+    for _ in 0..8 {
+        let (offset, length) = gen_offset_length(size as usize);
+        ranges.push(offset..offset + length);
+    }
+    // Overlapping, adjacent and far away ranges.
+    ranges.push(0..size / 2);
+    ranges.push(size / 4..size / 2 + 1);
+    ranges.push(size / 2 + 1..size);
+    ranges.push(size - 1..size);
+
+    let reader = op.reader_with(&path).gap(64).concurrent(2).await?;
+    let bufs = reader.fetch(ranges.clone()).await?;
+    assert_eq!(bufs.len(), ranges.len());
+    for (range, buf) in ranges.into_iter().zip(bufs) {
+        let expected = &content[range.start as usize..range.end as usize];
+        assert_eq!(
+            format!("{:x}", Sha256::digest(buf.to_bytes())),
+            format!("{:x}", Sha256::digest(expected)),
+            "fetch content of range {range:?}"
+        );
+    }
+
+    Ok(())
+}
+
 /// Read full content should match.
 pub async fn test_reader(op: Operator) -> anyhow::Result<()> {
     let (path, content, size) = TEST_FIXTURE.new_file(op.clone());
@@ -39,10 +39,14 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_stat_not_exist,
             test_stat_with_if_match,
             test_stat_with_if_none_match,
+            test_stat_with_if_modified_since,
+            test_stat_with_if_unmodified_since,
             test_stat_with_override_cache_control,
             test_stat_with_override_content_disposition,
             test_stat_with_override_content_type,
-            test_stat_root
+            test_stat_root,
+            test_exists,
+            test_stat_many
         ))
     }
 
@@ -181,6 +185,58 @@ pub async fn test_stat_not_exist(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Exists should return true for existing file and dir, false otherwise.
+pub async fn test_exists(op: Operator) -> Result<()> {
+    let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+
+    assert!(!op.exists(&path).await?);
+
+    op.write(&path, content).await.expect("write must succeed");
+
+    assert!(op.exists(&path).await?);
+    assert!(op.exists("/").await?);
+    assert!(!op.exists(&uuid::Uuid::new_v4().to_string()).await?);
+
+    if op.info().full_capability().create_dir {
+        let dir = format!("{}/", uuid::Uuid::new_v4());
+        assert!(!op.exists(&dir).await?);
+
+        op.create_dir(&dir).await?;
+        assert!(op.exists(&dir).await?);
+
+        op.delete(&dir).await?;
+    }
+
+    Ok(())
+}
+
+/// Stat many should return results in order with failures in place.
+pub async fn test_stat_many(op: Operator) -> Result<()> {
+    let mut paths = Vec::new();
+    let mut sizes = Vec::new();
+    for _ in 0..3 {
+        let (path, content, size) = TEST_FIXTURE.new_file(op.clone());
+        op.write(&path, content).await.expect("write must succeed");
+        paths.push(path);
+        sizes.push(size);
+    }
+    paths.insert(1, uuid::Uuid::new_v4().to_string());
+
+    let results = op.with_limit(2).stat_many(paths).await;
+    assert_eq!(results.len(), 4);
+    assert_eq!(results[1].as_ref().unwrap_err().kind(), ErrorKind::NotFound);
+    for (result, size) in [&results[0], &results[2], &results[3]]
+        .into_iter()
+        .zip(sizes)
+    {
+        let meta = result.as_ref().expect("stat must succeed");
+        assert_eq!(meta.mode(), EntryMode::FILE);
+        assert_eq!(meta.content_length(), size as u64);
+    }
+
+    Ok(())
+}
+
 /// Stat with if_match should succeed, else get a ConditionNotMatch error.
 pub async fn test_stat_with_if_match(op: Operator) -> Result<()> {
     if !op.info().full_capability().stat_with_if_match {
@@ -243,6 +299,71 @@ pub async fn test_stat_with_if_none_match(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Stat with if_modified_since should succeed if the file is modified after
+/// the given time, else get a ConditionNotMatch.
+pub async fn test_stat_with_if_modified_since(op: Operator) -> Result<()> {
+    if !op.info().full_capability().stat_with_if_modified_since {
+        return Ok(());
+    }
+
+    let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    let meta = op.stat(&path).await?;
+    let Some(last_modified) = meta.last_modified() else {
+        return Ok(());
+    };
+
+    let res = op
+        .stat_with(&path)
+        .if_modified_since(last_modified - chrono::Duration::seconds(1))
+        .await?;
+    assert_eq!(res.content_length(), meta.content_length());
+
+    let res = op.stat_with(&path).if_modified_since(last_modified).await;
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::ConditionNotMatch);
+
+    Ok(())
+}
+
+/// Stat with if_unmodified_since should succeed if the file is not modified
+/// after the given time, else get a ConditionNotMatch.
+pub async fn test_stat_with_if_unmodified_since(op: Operator) -> Result<()> {
+    if !op.info().full_capability().stat_with_if_unmodified_since {
+        return Ok(());
+    }
+
+    let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+
+    op.write(&path, content.clone())
+        .await
+        .expect("write must succeed");
+
+    let meta = op.stat(&path).await?;
+    let Some(last_modified) = meta.last_modified() else {
+        return Ok(());
+    };
+
+    let res = op
+        .stat_with(&path)
+        .if_unmodified_since(last_modified)
+        .await?;
+    assert_eq!(res.content_length(), meta.content_length());
+
+    let res = op
+        .stat_with(&path)
+        .if_unmodified_since(last_modified - chrono::Duration::seconds(1))
+        .await;
+    assert!(res.is_err());
+    assert_eq!(res.unwrap_err().kind(), ErrorKind::ConditionNotMatch);
+
+    Ok(())
+}
+
 /// Stat file with override-cache-control should succeed.
 pub async fn test_stat_with_override_cache_control(op: Operator) -> Result<()> {
     if !(op.info().full_capability().stat_with_override_cache_control
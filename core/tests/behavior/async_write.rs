@@ -18,6 +18,8 @@
 use std::collections::HashMap;
 
 use anyhow::Result;
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
 use bytes::Bytes;
 use futures::io::BufReader;
 use futures::io::Cursor;
@@ -38,6 +40,8 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
         tests.extend(async_trials!(
             op,
             test_write_only,
+            test_write_returns_metadata,
+            test_write_returns_committed_etag,
             test_write_with_empty_content,
             test_write_with_dir_path,
             test_write_with_special_chars,
@@ -45,6 +49,8 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_write_with_content_type,
             test_write_with_content_disposition,
             test_write_with_user_metadata,
+            test_write_with_if_not_exists,
+            test_write_with_content_checksum,
             test_writer_write,
             test_writer_write_with_overwrite,
             test_writer_write_with_concurrent,
@@ -64,6 +70,14 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_writer_with_append
         ))
     }
+
+    if cap.write && cap.write_can_append && cap.write_returns_append_offset {
+        tests.extend(async_trials!(op, test_writer_with_append_offset))
+    }
+
+    if cap.read && cap.write && cap.batch_write {
+        tests.extend(async_trials!(op, test_write_batch))
+    }
 }
 
 /// Write a single file and test with stat.
@@ -78,6 +92,75 @@ pub async fn test_write_only(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Write a file and check the returned metadata.
+pub async fn test_write_returns_metadata(op: Operator) -> Result<()> {
+    let cap = op.info().full_capability();
+    let (path, content, size) = TEST_FIXTURE.new_file(op.clone());
+
+    let meta = op.write(&path, content).await?;
+    assert!(meta.is_file());
+    assert_eq!(meta.content_length(), size as u64);
+    if cap.write_returns_etag {
+        assert!(meta.etag().is_some(), "etag must be returned");
+    }
+    if cap.write_returns_last_modified {
+        assert!(
+            meta.last_modified().is_some(),
+            "last modified must be returned"
+        );
+    }
+
+    Ok(())
+}
+
+/// The etag returned by write must be the committed one, so that it can be
+/// used in conditional operations without an extra stat.
+pub async fn test_write_returns_committed_etag(op: Operator) -> Result<()> {
+    let cap = op.info().full_capability();
+    if !(cap.write_returns_etag && cap.stat_with_if_match) {
+        return Ok(());
+    }
+
+    let (path, content, _) = TEST_FIXTURE.new_file(op.clone());
+
+    let meta = op.write(&path, content).await?;
+    let etag = meta.etag().expect("etag must be returned").to_string();
+
+    op.stat_with(&path)
+        .if_match(&etag)
+        .await
+        .expect("stat with returned etag must succeed");
+
+    let (content, _) = gen_bytes(cap);
+    let meta = op.write(&path, content).await?;
+    assert_ne!(meta.etag(), Some(etag.as_str()), "etag must be changed");
+
+    let err = op
+        .stat_with(&path)
+        .if_match(&etag)
+        .await
+        .expect_err("stat with outdated etag must fail");
+    assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+
+    Ok(())
+}
+
+/// Write multiple files in one batch.
+pub async fn test_write_batch(op: Operator) -> Result<()> {
+    let (path_a, content_a, _) = TEST_FIXTURE.new_file(op.clone());
+    let (path_b, content_b, _) = TEST_FIXTURE.new_file(op.clone());
+
+    op.write_batch(vec![
+        (path_a.clone(), content_a.clone().into()),
+        (path_b.clone(), content_b.clone().into()),
+    ])
+    .await?;
+
+    assert_eq!(op.read(&path_a).await?.to_vec(), content_a);
+    assert_eq!(op.read(&path_b).await?.to_vec(), content_b);
+    Ok(())
+}
+
 /// Write a file with empty content.
 pub async fn test_write_with_empty_content(op: Operator) -> Result<()> {
     if !op.info().full_capability().write_can_empty {
@@ -184,6 +267,60 @@ pub async fn test_write_with_content_type(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Write a single file with if_not_exists should fail if the file exists.
+pub async fn test_write_with_if_not_exists(op: Operator) -> Result<()> {
+    if !op.info().full_capability().write_with_if_not_exists {
+        return Ok(());
+    }
+
+    let (path, content, size) = TEST_FIXTURE.new_file(op.clone());
+
+    op.write_with(&path, content.clone())
+        .if_not_exists(true)
+        .await?;
+
+    let meta = op.stat(&path).await.expect("stat must succeed");
+    assert_eq!(meta.content_length(), size as u64);
+
+    let err = op
+        .write_with(&path, content)
+        .if_not_exists(true)
+        .await
+        .expect_err("write must fail");
+    assert_eq!(err.kind(), ErrorKind::ConditionNotMatch);
+
+    Ok(())
+}
+
+/// Write with content checksum should be verified.
+pub async fn test_write_with_content_checksum(op: Operator) -> Result<()> {
+    let (path, content, size) = TEST_FIXTURE.new_file(op.clone());
+    let content_md5 = BASE64_STANDARD.encode(md5::Md5::digest(&content));
+    let content_sha256 = BASE64_STANDARD.encode(Sha256::digest(&content));
+
+    op.write_with(&path, content.clone())
+        .content_md5(&content_md5)
+        .content_sha256(&content_sha256)
+        .await?;
+
+    let meta = op.stat(&path).await.expect("stat must succeed");
+    assert_eq!(meta.content_length(), size as u64);
+
+    // Rejected writes must leave the previous content intact.
+    let (_, other, _) = TEST_FIXTURE.new_file(op.clone());
+    let err = op
+        .write_with(&path, other)
+        .content_sha256(&content_sha256)
+        .await
+        .expect_err("write must fail");
+    assert_eq!(err.kind(), ErrorKind::DataCorrupted);
+
+    let bs = op.read(&path).await?.to_bytes();
+    assert_eq!(bs, content, "previous content must be kept");
+
+    Ok(())
+}
+
 /// Write a single file with content disposition should succeed.
 pub async fn test_write_with_content_disposition(op: Operator) -> Result<()> {
     if !op.info().full_capability().write_with_content_disposition {
@@ -589,6 +726,29 @@ pub async fn test_writer_with_append(op: Operator) -> Result<()> {
     Ok(())
 }
 
+/// Append writes must report the offset the data landed at.
+pub async fn test_writer_with_append_offset(op: Operator) -> Result<()> {
+    let path = TEST_FIXTURE.new_file_path();
+    let (content_one, size_one) = gen_bytes(op.info().full_capability());
+    let (content_two, size_two) = gen_bytes(op.info().full_capability());
+
+    let meta = op
+        .write_with(&path, content_one)
+        .append(true)
+        .await
+        .expect("append file first time must success");
+    assert_eq!(meta.append_offset(), Some(0));
+    assert_eq!(meta.content_length(), size_one as u64);
+
+    let mut w = op.writer_with(&path).append(true).await?;
+    w.write(content_two).await?;
+    let meta = w.close().await?;
+    assert_eq!(meta.append_offset(), Some(size_one as u64));
+    assert_eq!(meta.content_length(), (size_one + size_two) as u64);
+
+    Ok(())
+}
+
 pub async fn test_writer_write_with_overwrite(op: Operator) -> Result<()> {
     // ghac does not support overwrite
     if op.info().scheme() == Scheme::Ghac {
@@ -35,6 +35,8 @@ pub fn tests(op: &Operator, tests: &mut Vec<Trial>) {
             test_blocking_list_dir_with_recursive,
             test_blocking_list_dir_with_recursive_no_trailing_slash,
             test_blocking_list_file_with_recursive,
+            test_blocking_list_with_matching_glob,
+            test_blocking_list_with_ordered,
             test_blocking_remove_all
         ))
     }
@@ -310,3 +312,64 @@ pub fn test_blocking_list_file_with_recursive(op: BlockingOperator) -> Result<()
     assert_eq!(actual, expected);
     Ok(())
 }
+
+/// List with glob should only return matched entries.
+pub fn test_blocking_list_with_matching_glob(op: BlockingOperator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+
+    for path in ["a.parquet", "b.csv", "x/c.parquet"] {
+        op.write(&format!("{parent}/{path}"), "test_glob")?;
+    }
+
+    let mut actual = op
+        .lister_with(&format!("{parent}/"))
+        .recursive(true)
+        .matching_glob("**/*.parquet")
+        .call()?
+        .map(|v| {
+            v.unwrap()
+                .path()
+                .strip_prefix(&format!("{parent}/"))
+                .unwrap()
+                .to_string()
+        })
+        .collect::<Vec<_>>();
+    actual.sort();
+    assert_eq!(actual, vec!["a.parquet", "x/c.parquet"]);
+
+    let actual = op
+        .list_with(&format!("{parent}/"))
+        .matching_regex(r"\.csv$")
+        .call()?
+        .into_iter()
+        .map(|v| v.name().to_string())
+        .collect::<Vec<_>>();
+    assert_eq!(actual, vec!["b.csv"]);
+    Ok(())
+}
+
+/// List with ordered should return entries in lexicographical order.
+pub fn test_blocking_list_with_ordered(op: BlockingOperator) -> Result<()> {
+    let parent = uuid::Uuid::new_v4().to_string();
+
+    for path in ["z", "a-b", "a/y", "a/b/c", "a.txt"] {
+        op.write(&format!("{parent}/{path}"), "test_ordered")?;
+    }
+
+    let sort_unordered = !op.info().full_capability().list_ordered;
+    let actual = op
+        .list_with(&format!("{parent}/"))
+        .recursive(true)
+        .ordered(true)
+        .sort_unordered(sort_unordered)
+        .call()?
+        .into_iter()
+        .map(|v| v.path().to_string())
+        .collect::<Vec<_>>();
+
+    let mut expected = actual.clone();
+    expected.sort();
+    assert_eq!(actual, expected, "entries must be in order");
+    assert!(actual.contains(&format!("{parent}/a/b/c")));
+    Ok(())
+}